@@ -0,0 +1,129 @@
+//! Compares the traversal of `LinkedList` and `SkipList` across the widths of their offset handles.
+//!
+//! The narrower the handle, the smaller the nodes, at the cost of a smaller addressable block of memory. Each bench
+//! reports the bytes allocated for its nodes as throughput, so that the size of the nodes can be read alongside the
+//! traversal speed.
+//!
+//! On 64-bits targets, the nodes measure:
+//!
+//! | Handle  | `LinkedList<u32>` | `SkipList<u32, u32>`, per link |
+//! |---------|-------------------|--------------------------------|
+//! | `u8`    | 8 bytes           | 9 bytes                        |
+//! | `u16`   | 8 bytes           | 10 bytes                       |
+//! | `u32`   | 12 bytes          | 12 bytes                       |
+//! | `usize` | 24 bytes          | 16 bytes                       |
+//!
+//! The links of `SkipList` pair each handle with a `usize` span, which dwarfs the handle, hence `SkipList` benefits
+//! less than `LinkedList` from narrower handles. A `u8` handle cannot address the nodes of a `SkipList`
+//! reliably, and is therefore only benchmarked with a short `LinkedList`.
+//!
+//! Resolving an offset handle costs an addition, whatever its width, hence as long as the nodes fit in the caches the
+//! traversal speed does not depend on the handle width: the benefit of narrower handles is their footprint, which
+//! allows fitting more nodes in a block, a cache line, or a cache.
+//!
+//! Measured on x86_64, Intel Xeon, with nightly 2026-05-19, as the median of 3 runs:
+//!
+//! | Bench                    | `u8`     | `u16`     | `u32`     | `usize`   |
+//! |--------------------------|----------|-----------|-----------|-----------|
+//! | `linked_list_short_*`    | 19 ns    | 16 ns     | 20 ns     | 17 ns     |
+//! | `linked_list_long_*`     |          | 3,804 ns  | 3,722 ns  | 3,958 ns  |
+//! | `skip_list_iter_*`       |          | 561 ns    | 550 ns    | 578 ns    |
+//! | `skip_list_get_*`        |          | 25,006 ns | 25,537 ns | 20,731 ns |
+//!
+//! The differences across widths are within the noise of the machine, which reaches 40% for `skip_list_get_*`.
+//!
+//! Run with `cargo bench --bench handle_width`.
+
+#![feature(test)]
+
+extern crate test;
+
+use storage::{
+    collection::{LinkedList, SkipList},
+    interface::{Store, StoreStable},
+    store::InlineBumpStore,
+};
+use test::{black_box, Bencher};
+
+//  As many nodes as a `u8` handle may address, with a `u32` element.
+const SHORT_LEN: u32 = 24;
+
+//  As many nodes as a `u16` handle may address, with a `u32` element, rounded down to a power of 2.
+const LONG_LEN: u32 = 2048;
+
+//  Nodes of a skip list have 2 links on average, on top of their key and value.
+const SKIP_LEN: u32 = 256;
+
+type ShortBlock = [u64; 31];
+
+type LongBlock = [u64; 4096];
+
+fn skip_list<S>(store: S) -> SkipList<u32, u32, S>
+where
+    S: Store + StoreStable,
+{
+    let mut list = SkipList::with_store(store);
+
+    //  Insert in a scrambled order, as 37 and `SKIP_LEN`, a power of 2, are coprime.
+    for i in 0..SKIP_LEN {
+        let key = (i * 37) % SKIP_LEN;
+
+        list.insert(key, key);
+    }
+
+    list
+}
+
+macro_rules! linked_list_bench {
+    ($name:ident, $handle:ty, $block:ty, $len:expr) => {
+        #[bench]
+        fn $name(bencher: &mut Bencher) {
+            let mut list = LinkedList::new_in(InlineBumpStore::<$handle, $block>::new());
+
+            list.extend(0..$len);
+
+            bencher.bytes = list.memory_usage().bytes_allocated as u64;
+
+            bencher.iter(|| black_box(&list).iter().sum::<u32>());
+        }
+    };
+}
+
+macro_rules! skip_list_bench {
+    ($iter:ident, $get:ident, $handle:ty) => {
+        #[bench]
+        fn $iter(bencher: &mut Bencher) {
+            let list = skip_list(InlineBumpStore::<$handle, LongBlock>::new());
+
+            bencher.bytes = list.memory_usage().bytes_allocated as u64;
+
+            bencher.iter(|| black_box(&list).iter().map(|(_, value)| *value).sum::<u32>());
+        }
+
+        #[bench]
+        fn $get(bencher: &mut Bencher) {
+            let list = skip_list(InlineBumpStore::<$handle, LongBlock>::new());
+
+            bencher.bytes = list.memory_usage().bytes_allocated as u64;
+
+            bencher.iter(|| {
+                let list = black_box(&list);
+
+                (0..SKIP_LEN).filter_map(|key| list.get(&key)).sum::<u32>()
+            });
+        }
+    };
+}
+
+linked_list_bench!(linked_list_short_u8, u8, ShortBlock, SHORT_LEN);
+linked_list_bench!(linked_list_short_u16, u16, ShortBlock, SHORT_LEN);
+linked_list_bench!(linked_list_short_u32, u32, [u64; 48], SHORT_LEN);
+linked_list_bench!(linked_list_short_usize, usize, [u64; 96], SHORT_LEN);
+
+linked_list_bench!(linked_list_long_u16, u16, LongBlock, LONG_LEN);
+linked_list_bench!(linked_list_long_u32, u32, LongBlock, LONG_LEN);
+linked_list_bench!(linked_list_long_usize, usize, [u64; 8192], LONG_LEN);
+
+skip_list_bench!(skip_list_iter_u16, skip_list_get_u16, u16);
+skip_list_bench!(skip_list_iter_u32, skip_list_get_u32, u32);
+skip_list_bench!(skip_list_iter_usize, skip_list_get_usize, usize);
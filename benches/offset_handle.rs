@@ -0,0 +1,89 @@
+//! Compares the ways of accessing the elements of a slice allocated in an offset-based store.
+//!
+//! -   Resolving the slice handle for each element.
+//! -   Offsetting the slice handle for each element, via `StoreOffsetHandle`, then resolving the element handle.
+//! -   Resolving the slice handle once, then iterating over the slice.
+//!
+//! Run with `cargo bench --bench offset_handle`.
+
+#![feature(test)]
+
+extern crate test;
+
+use storage::{extension::typed::TypedHandle, store::InlineBumpStore};
+use test::{black_box, Bencher};
+
+const LEN: usize = 256;
+
+type BenchStore = InlineBumpStore<u32, [u32; LEN]>;
+
+fn setup() -> (BenchStore, TypedHandle<[u32], u32>) {
+    let store = BenchStore::default();
+    let slice = TypedHandle::allocate_slice(LEN, &store);
+
+    //  Safety:
+    //  -   `slice` was allocated by `store`, and is still valid.
+    let elements = unsafe { slice.resolve_raw(&store).as_mut() };
+
+    for (index, element) in elements.iter_mut().enumerate() {
+        *element = index as u32;
+    }
+
+    (store, slice)
+}
+
+#[bench]
+fn resolve_per_element(bencher: &mut Bencher) {
+    let (store, slice) = setup();
+
+    bencher.iter(|| {
+        let store = black_box(&store);
+
+        (0..LEN)
+            .map(|index| {
+                //  Safety:
+                //  -   `slice` was allocated by `store`, and is still valid.
+                //  -   `index` is within bounds.
+                unsafe { slice.resolve(store)[index] }
+            })
+            .sum::<u32>()
+    });
+}
+
+#[bench]
+fn offset_per_element(bencher: &mut Bencher) {
+    let (store, slice) = setup();
+
+    bencher.iter(|| {
+        let store = black_box(&store);
+
+        (0..LEN)
+            .map(|index| {
+                //  Safety:
+                //  -   `slice` was allocated by `store`, and is still valid.
+                //  -   `index` is within bounds.
+                let element = unsafe { slice.element(index, store) };
+
+                //  Safety:
+                //  -   `element` is valid, as `slice` is.
+                //  -   `element` is associated to an initialized `u32`.
+                unsafe { *element.resolve(store) }
+            })
+            .sum::<u32>()
+    });
+}
+
+#[bench]
+fn resolve_once(bencher: &mut Bencher) {
+    let (store, slice) = setup();
+
+    bencher.iter(|| {
+        let store = black_box(&store);
+
+        //  Safety:
+        //  -   `slice` was allocated by `store`, and is still valid.
+        let elements = unsafe { slice.resolve(store) };
+
+        elements.iter().sum::<u32>()
+    });
+}
@@ -0,0 +1,64 @@
+//! Compares a `StoreVec` with a small-buffer optimization, via `SmallStore`, to a `StoreVec` always allocating.
+//!
+//! Measured on x86_64, Intel Xeon, with the system allocator:
+//!
+//! | Bench                   | Time           |
+//! |-------------------------|----------------|
+//! | `heap_push_8`           | 71 ns/iter     |
+//! | `small_push_8_inline`   | 17 ns/iter     |
+//! | `heap_push_64`          | 268 ns/iter    |
+//! | `small_push_64_spilled` | 235 ns/iter    |
+//!
+//! As long as the elements fit inline, the small-buffer optimization avoids the allocator altogether; once spilled,
+//! the vector performs on par with a vector always allocating, the differences being within the noise, of about 20%.
+//!
+//! Run with `cargo bench --bench small_store`.
+
+#![feature(test)]
+
+extern crate test;
+
+use std::alloc::System;
+
+use storage::{collection::StoreVec, interface::StoreSingle, store::SmallStore};
+use test::{black_box, Bencher};
+
+type SmallVec<T, const N: usize> = StoreVec<T, SmallStore<[T; N], System>>;
+
+type HeapVec<T> = StoreVec<T, System>;
+
+//  Pushes `0..count` onto `vec`, then sums its elements.
+fn push_sum<S: StoreSingle>(mut vec: StoreVec<u32, S>, count: u32) -> u32 {
+    for i in 0..count {
+        vec.push(black_box(i));
+    }
+
+    vec.iter().sum()
+}
+
+//  Checks the result of `push_sum`, then benchmarks it.
+fn bench_push_sum<S: StoreSingle>(bencher: &mut Bencher, new: impl Fn() -> StoreVec<u32, S>, count: u32) {
+    assert_eq!(count * (count - 1) / 2, push_sum(new(), count));
+
+    bencher.iter(|| push_sum(new(), count));
+}
+
+#[bench]
+fn heap_push_8(bencher: &mut Bencher) {
+    bench_push_sum(bencher, HeapVec::<u32>::new, 8);
+}
+
+#[bench]
+fn small_push_8_inline(bencher: &mut Bencher) {
+    bench_push_sum(bencher, SmallVec::<u32, 8>::new, 8);
+}
+
+#[bench]
+fn heap_push_64(bencher: &mut Bencher) {
+    bench_push_sum(bencher, HeapVec::<u32>::new, 64);
+}
+
+#[bench]
+fn small_push_64_spilled(bencher: &mut Bencher) {
+    bench_push_sum(bencher, SmallVec::<u32, 8>::new, 64);
+}
@@ -0,0 +1,197 @@
+//! Derive macros for the `storage` crate.
+//!
+//! Enabled by the `derive` feature of `storage`, and re-exported alongside the traits they implement; the generated
+//! code refers to the crate as `::storage`.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, GenericParam, Lifetime};
+
+/// Implements `storage::collection::SoA` for a struct with named fields.
+///
+/// For a struct `Foo`, also generates the `FooRef`, `FooMut`, `FooSlices`, and `FooSlicesMut` structs, with the same
+/// visibility as `Foo`, and respectively one shared reference, mutable reference, shared slice, and mutable slice per
+/// field of `Foo`, with the same name and visibility.
+#[proc_macro_derive(SoA)]
+pub fn derive_soa(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    soa(input).unwrap_or_else(Error::into_compile_error).into()
+}
+
+fn soa(input: DeriveInput) -> Result<TokenStream2, Error> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(&input.ident, "SoA can only be derived for structs"));
+    };
+
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "SoA can only be derived for structs with named fields",
+        ));
+    };
+
+    let name = &input.ident;
+    let vis = &input.vis;
+
+    let names: Vec<_> = fields
+        .named
+        .iter()
+        .map(|field| field.ident.as_ref().expect("Named"))
+        .collect();
+    let types: Vec<_> = fields.named.iter().map(|field| &field.ty).collect();
+    let visibilities: Vec<_> = fields.named.iter().map(|field| &field.vis).collect();
+    let indexes: Vec<_> = (0..names.len()).collect();
+
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    //  The generated structs borrow from the arrays for `'__soa`, which all generic parameters must outlive.
+    let lifetime = Lifetime::new("'__soa", Span::call_site());
+
+    let mut borrowed = input.generics.clone();
+
+    let outlives: Vec<_> = borrowed
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            GenericParam::Type(param) => {
+                let ident = &param.ident;
+                Some(quote! { #ident: #lifetime })
+            }
+            GenericParam::Lifetime(param) => {
+                let other = &param.lifetime;
+                Some(quote! { #other: #lifetime })
+            }
+            GenericParam::Const(_) => None,
+        })
+        .collect();
+
+    borrowed.params.insert(0, syn::parse_quote! { #lifetime });
+
+    {
+        let where_clause = borrowed.make_where_clause();
+
+        for predicate in &outlives {
+            where_clause.predicates.push(syn::parse_quote! { #predicate });
+        }
+    }
+
+    let (_, borrowed_type_generics, borrowed_where_clause) = borrowed.split_for_impl();
+
+    let generated = |suffix: &str, doc: &str, field_type: &dyn Fn(&syn::Type) -> TokenStream2| {
+        let generated = format_ident!("{}{}", name, suffix);
+        let doc = format!("The {doc} of a `{name}`, as stored in a `StoreSoA`.");
+        let field_types = types.iter().map(|ty| field_type(ty));
+
+        quote! {
+            #[doc = #doc]
+            #[allow(dead_code, missing_docs)]
+            #vis struct #generated #borrowed #borrowed_where_clause {
+                #( #visibilities #names: #field_types, )*
+            }
+        }
+    };
+
+    let reference = generated(
+        "Ref",
+        "shared references to the fields",
+        &|ty| quote! { &#lifetime #ty },
+    );
+    let mutable = generated(
+        "Mut",
+        "mutable references to the fields",
+        &|ty| quote! { &#lifetime mut #ty },
+    );
+    let slices = generated(
+        "Slices",
+        "shared slices of the fields",
+        &|ty| quote! { &#lifetime [#ty] },
+    );
+    let slices_mut = generated(
+        "SlicesMut",
+        "mutable slices of the fields",
+        &|ty| quote! { &#lifetime mut [#ty] },
+    );
+
+    let ref_name = format_ident!("{}Ref", name);
+    let mut_name = format_ident!("{}Mut", name);
+    let slices_name = format_ident!("{}Slices", name);
+    let slices_mut_name = format_ident!("{}SlicesMut", name);
+
+    Ok(quote! {
+        #reference
+        #mutable
+        #slices
+        #slices_mut
+
+        #[automatically_derived]
+        unsafe impl #impl_generics ::storage::collection::SoA for #name #type_generics #where_clause {
+            type Ref<#lifetime> = #ref_name #borrowed_type_generics where Self: #lifetime;
+            type Mut<#lifetime> = #mut_name #borrowed_type_generics where Self: #lifetime;
+            type Slices<#lifetime> = #slices_name #borrowed_type_generics where Self: #lifetime;
+            type SlicesMut<#lifetime> = #slices_mut_name #borrowed_type_generics where Self: #lifetime;
+
+            const LAYOUTS: &'static [::core::alloc::Layout] = &[ #( ::core::alloc::Layout::new::<#types>(), )* ];
+
+            unsafe fn write<F>(self, mut field: F, index: usize)
+            where
+                F: FnMut(usize) -> ::core::ptr::NonNull<u8>,
+            {
+                //  Moves the fields out one at a time, even if `Self` implements `Drop`.
+                let this = ::core::mem::ManuallyDrop::new(self);
+
+                #( unsafe { field(#indexes).cast::<#types>().add(index).write(::core::ptr::read(&this.#names)) }; )*
+            }
+
+            unsafe fn read<F>(mut field: F, index: usize) -> Self
+            where
+                F: FnMut(usize) -> ::core::ptr::NonNull<u8>,
+            {
+                Self {
+                    #( #names: unsafe { field(#indexes).cast::<#types>().add(index).read() }, )*
+                }
+            }
+
+            unsafe fn get<#lifetime, F>(mut field: F, index: usize) -> Self::Ref<#lifetime>
+            where
+                F: FnMut(usize) -> ::core::ptr::NonNull<u8>,
+            {
+                #ref_name {
+                    #( #names: unsafe { field(#indexes).cast::<#types>().add(index).as_ref() }, )*
+                }
+            }
+
+            unsafe fn get_mut<#lifetime, F>(mut field: F, index: usize) -> Self::Mut<#lifetime>
+            where
+                F: FnMut(usize) -> ::core::ptr::NonNull<u8>,
+            {
+                #mut_name {
+                    #( #names: unsafe { field(#indexes).cast::<#types>().add(index).as_mut() }, )*
+                }
+            }
+
+            unsafe fn slices<#lifetime, F>(mut field: F, length: usize) -> Self::Slices<#lifetime>
+            where
+                F: FnMut(usize) -> ::core::ptr::NonNull<u8>,
+            {
+                #slices_name {
+                    #( #names: unsafe {
+                        ::core::slice::from_raw_parts(field(#indexes).cast::<#types>().as_ptr(), length)
+                    }, )*
+                }
+            }
+
+            unsafe fn slices_mut<#lifetime, F>(mut field: F, length: usize) -> Self::SlicesMut<#lifetime>
+            where
+                F: FnMut(usize) -> ::core::ptr::NonNull<u8>,
+            {
+                #slices_mut_name {
+                    #( #names: unsafe {
+                        ::core::slice::from_raw_parts_mut(field(#indexes).cast::<#types>().as_ptr(), length)
+                    }, )*
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,81 @@
+//! Walks an arena, and dumps the content of each live allocation.
+//!
+//! A `TrackingStore` records the handle and layout of each live allocation of its underlying store, and enumerates them
+//! via `StoreEnumerate`. Together with `Store::resolve`, this is sufficient to inspect the arena, without any
+//! cooperation from the collections allocating from it.
+
+use core::{alloc::Layout, slice};
+
+use storage::{
+    collection::{StoreBox, StoreVec},
+    interface::{Store, StoreEnumerate},
+    store::{InlineBumpStore, RefStore, TrackingStore},
+};
+
+type Arena = TrackingStore<InlineBumpStore<u16, [u64; 64]>, 16>;
+
+fn main() {
+    let arena = Arena::new(InlineBumpStore::default());
+
+    let boxed = StoreBox::new_in_ref(0xDEAD_BEEF_u32, &arena);
+
+    let mut vec = StoreVec::new_in(RefStore::new(&arena));
+    vec.push(1u16);
+    vec.push(2);
+    vec.push(3);
+
+    let greeting = StoreBox::new_in_ref(*b"hello", &arena);
+
+    //  The arena grows exactly, hence no element of `vec` is left uninitialized, see `hex`.
+    assert_eq!(vec.len(), vec.capacity());
+
+    println!("Before dropping the box:");
+    dump(&arena);
+
+    drop(boxed);
+
+    println!("After dropping the box:");
+    dump(&arena);
+
+    drop((vec, greeting));
+
+    println!("After dropping everything:");
+    dump(&arena);
+}
+
+fn dump(arena: &Arena) {
+    let mut allocations = Vec::new();
+
+    arena.for_each_handle(|handle, layout| allocations.push((handle, layout)));
+
+    allocations.sort_by_key(|(handle, _)| *handle);
+
+    if allocations.is_empty() {
+        println!("    <empty>");
+    }
+
+    for (handle, layout) in allocations {
+        println!(
+            "    @{handle:>4}: {:>3} bytes | {}",
+            layout.size(),
+            hex(arena, handle, layout)
+        );
+    }
+}
+
+fn hex(arena: &Arena, handle: u16, layout: Layout) -> String {
+    //  Safety:
+    //  -   `handle` was enumerated by `arena`, hence is valid.
+    let pointer = unsafe { arena.resolve(handle) };
+
+    //  Safety:
+    //  -   `pointer` points to a block of memory of at least `layout.size()` bytes, as `layout` fits it.
+    //  -   The bytes of the block are initialized, as all the blocks of `arena` are filled with padding-free values.
+    let bytes = unsafe { slice::from_raw_parts(pointer.as_ptr(), layout.size()) };
+
+    bytes
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,94 @@
+//! Builds a DMA descriptor ring in a pinned store.
+//!
+//! DMA engines are handed the addresses of the descriptors and buffers ahead of time, hence those must never move. With
+//! a store implementing `StoreRegion`, the addresses are fixed as follows:
+//!
+//! -   `allocate`, `resolve`, and `deallocate` never move any existing block of memory.
+//! -   `grow` and `shrink` may relocate the block of memory, and must not be used on blocks handed to the hardware.
+//! -   Moving, or sharing, the store never moves any block of memory, as per `StorePinning`.
+
+use core::{array, ops::Range};
+
+use storage::{
+    extension::typed::TypedHandle,
+    interface::StoreRegion,
+    store::{StackBumpBlock, StackBumpStore},
+};
+
+const RING_SIZE: usize = 4;
+const BUFFER_SIZE: usize = 64;
+
+type Handle<T> = TypedHandle<T, u16>;
+
+/// A descriptor, as read by a (fictional) DMA engine.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+struct Descriptor {
+    address: usize,
+    length: u32,
+    flags: u32,
+}
+
+fn main() {
+    //  Room for the ring of descriptors, and one buffer per descriptor.
+    let block = StackBumpBlock::<[u64; 64]>::new();
+
+    let store: StackBumpStore<'_, u16> = block.create_store();
+
+    let region = store.region();
+    let region = {
+        let start = region.cast::<u8>().as_ptr() as usize;
+
+        start..(start + region.len())
+    };
+
+    let buffers: [Handle<[u8; BUFFER_SIZE]>; RING_SIZE] = array::from_fn(|_| Handle::new([0; BUFFER_SIZE], &store));
+
+    let descriptors = buffers.map(|buffer| {
+        //  Safety:
+        //  -   `buffer` was allocated by `store`, and is still valid.
+        let address = unsafe { buffer.resolve_raw(&store) }.as_ptr() as usize;
+
+        let length = BUFFER_SIZE as u32;
+        let flags = 0;
+
+        Descriptor { address, length, flags }
+    });
+
+    let ring: Handle<[Descriptor; RING_SIZE]> = Handle::new(descriptors, &store);
+
+    //  Safety:
+    //  -   `ring` was allocated by `store`, and is still valid.
+    let ring_address = unsafe { ring.resolve_raw(&store) }.as_ptr() as usize;
+
+    println!("Region: {region:#x?}");
+    println!("Ring: {ring_address:#x}");
+
+    check_within(&region, ring_address);
+
+    for descriptor in &descriptors {
+        println!("    {descriptor:x?}");
+
+        check_within(&region, descriptor.address);
+    }
+
+    //  Moving the store does not move the blocks of memory.
+    let moved = store;
+
+    //  Safety:
+    //  -   `ring` was allocated by `moved`, and is still valid.
+    assert_eq!(ring_address, unsafe { ring.resolve_raw(&moved) }.as_ptr() as usize);
+
+    //  Safety:
+    //  -   `ring` and `buffers` were allocated by `moved`, and are still valid.
+    //  -   `ring` and `buffers` are not used afterwards.
+    unsafe {
+        ring.deallocate(&moved);
+
+        buffers.iter().for_each(|buffer| buffer.deallocate(&moved));
+    }
+}
+
+fn check_within(region: &Range<usize>, address: usize) {
+    assert!(region.contains(&address), "{address:#x} not within {region:#x?}");
+}
@@ -0,0 +1,116 @@
+//! Reads the header of a store-backed `StoreVec<u8, _>` from C.
+//!
+//! The vector is `#[repr(C)]`, its handle is the offset of the elements within the region of a `StackBumpStore`, and
+//! a `StoreRegion` exposes the base address of this region. The C side is thus handed a pointer to the vector, and the
+//! base address of the region, and declared as follows:
+//!
+//! ```c
+//! struct VecHeader {
+//!     size_t length;
+//!     uint16_t handle;
+//!     size_t capacity;
+//! };
+//!
+//! size_t checksum(struct VecHeader const* header, uint8_t const* region);
+//! ```
+//!
+//! For the sake of keeping the example self-contained, it calls `mirror_checksum`, a Rust mirror of `checksum`. The C
+//! implementation lives in `tests/ffi/checksum.c`, and `tests/ffi_vec.rs` compiles it, then hands it a vector.
+//!
+//! With the `coercible-metadata` feature, the layout of the handle is unspecified, see `TypedMetadata`, hence the
+//! example does nothing.
+
+use core::mem;
+
+use storage::{
+    collection::StoreVec,
+    extension::{layout::assert_same_layout, unique_single::UniqueSingleHandle},
+    interface::StoreRegion,
+    store::{StackBumpBlock, StackBumpStore},
+};
+
+type Store<'a> = StackBumpStore<'a, u16>;
+
+/// The mirror of `struct VecHeader`.
+#[repr(C)]
+struct VecHeader {
+    length: usize,
+    handle: u16,
+    capacity: usize,
+}
+
+/// The mirror of the handle and capacity of `struct VecHeader`.
+#[repr(C)]
+struct ArrayHandle {
+    handle: u16,
+    capacity: usize,
+}
+
+//  The handle of the vector, followed by its capacity.
+const _: () = if !cfg!(feature = "coercible-metadata") {
+    assert_same_layout::<UniqueSingleHandle<[u8], u16>, ArrayHandle>()
+};
+
+//  The header is a prefix of the vector; the store follows it, aligned as per its own alignment.
+const _: () = assert!(mem::size_of::<VecHeader>() <= mem::size_of::<StoreVec<u8, Store<'static>>>());
+
+fn main() {
+    if cfg!(feature = "coercible-metadata") {
+        println!("The layout of the handle is unspecified with `coercible-metadata`, skipping.");
+        return;
+    }
+
+    let block = StackBumpBlock::<[u64; 8]>::new();
+
+    let store: Store<'_> = block.create_store();
+    let region = store.region();
+
+    let mut vec = StoreVec::with_capacity_in(16, store);
+
+    for byte in b"Hello, C!" {
+        vec.push(*byte);
+    }
+
+    let header = &vec as *const StoreVec<u8, Store<'_>> as *const VecHeader;
+
+    //  Safety:
+    //  -   `header` points to a `StoreVec`, whose prefix is laid out as a `VecHeader`.
+    //  -   `region` is the region of the store of the vector, in which its elements are allocated.
+    let sum = unsafe { mirror_checksum(header, region.cast::<u8>().as_ptr()) };
+
+    let expected: usize = vec.iter().map(|byte| *byte as usize).sum();
+
+    let text = String::from_utf8_lossy(&vec);
+
+    println!("{text:?}: checksum {sum} (expected {expected})");
+
+    assert_eq!(expected, sum);
+}
+
+/// Sums the elements of the vector, as `checksum` does.
+///
+/// #   Safety
+///
+/// -   `header` must point to a live `StoreVec<u8, StackBumpStore<'_, u16>>`.
+/// -   `region` must point to the start of the region of the store of this vector.
+unsafe extern "C" fn mirror_checksum(header: *const VecHeader, region: *const u8) -> usize {
+    //  Safety:
+    //  -   `header` points to a live `VecHeader`, as per pre-conditions.
+    let header = unsafe { &*header };
+
+    assert!(header.length <= header.capacity);
+
+    //  Safety:
+    //  -   `header.handle` is the offset of the elements within the region, as per pre-conditions.
+    let elements = unsafe { region.add(header.handle as usize) };
+
+    let mut sum = 0;
+
+    for index in 0..header.length {
+        //  Safety:
+        //  -   `index` is within the initialized elements, as `index < header.length`.
+        sum += unsafe { *elements.add(index) } as usize;
+    }
+
+    sum
+}
@@ -0,0 +1,63 @@
+//! Nests collections -- a `StoreVec` of `LinkedList` -- all allocating from a single sharing set.
+//!
+//! The outer vector and each inner list own one instance of the same sharing set, all carved out of a single block
+//! of memory. The store is wrapped in a `SharingStore`, so that the only way to create an instance is to share an
+//! existing one: `LinkedList::new`, which would create a store from scratch, is not available.
+//!
+//! As all the lists share the same set, nodes can be moved from one list to another without reallocation.
+
+use storage::{
+    collection::{LinkedList, StoreVec},
+    store::{SharingStore, StackBumpBlock, StackBumpStore},
+};
+
+type Shared<'a> = SharingStore<StackBumpStore<'a, u16>>;
+
+type Buckets<'a> = StoreVec<LinkedList<&'static str, Shared<'a>>, Shared<'a>>;
+
+const WORDS: [&str; 8] = ["a", "by", "the", "of", "to", "and", "in", "for"];
+
+fn main() {
+    let block = StackBumpBlock::<[u64; 64]>::new();
+
+    let mut buckets = Buckets::new_in(SharingStore::new(block.create_store()));
+
+    //  Growing a vector within a bump store leaves the old block of memory behind, hence reserve up-front.
+    buckets.reserve(3);
+
+    //  Each bucket is created from a share of the vector's store, rather than from a new store.
+    for _ in 0..3 {
+        buckets.push_shared(LinkedList::new_in);
+    }
+
+    for word in WORDS {
+        buckets[word.len() - 1].push_back(word);
+    }
+
+    for (index, bucket) in buckets.iter().enumerate() {
+        println!("Words of {} letter(s): {:?}", index + 1, bucket);
+    }
+
+    //  Nodes may be moved across buckets, as all buckets share the same set.
+    let (short, long) = buckets.split_at_mut(1);
+
+    short[0]
+        .try_append(&mut long[1])
+        .unwrap_or_else(|_| panic!("Buckets to share the same set"));
+
+    println!("Merged 1- and 3-letter words: {:?}", buckets[0]);
+
+    assert_eq!(4, buckets[0].len());
+    assert!(buckets[2].is_empty());
+
+    //  Further lists are created by sharing with an existing one.
+    let mut stragglers = buckets[2].new_sharing();
+
+    stragglers.push_back("but");
+
+    buckets[2]
+        .try_append(&mut stragglers)
+        .unwrap_or_else(|_| panic!("Lists to share the same set"));
+
+    println!("Late 3-letter words: {:?}", buckets[2]);
+}
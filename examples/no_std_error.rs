@@ -0,0 +1,83 @@
+//! Propagates errors of different types as a single `dyn Error`, without `alloc`.
+//!
+//! `StoreError<S>` is the store-generic counterpart of `Box<dyn Error + Send + Sync>`. With `InlineSingleStore`, the
+//! error is boxed inline, and so is the message of ad-hoc errors, hence the whole chain works in `no_std` environments;
+//! the example only uses `std` to print.
+//!
+//! The stores bound the size of the errors: an error type, or a message, which does not fit is an allocation failure.
+
+use core::error::Error;
+
+use storage::{
+    collection::{ErrorMessage, StoreError},
+    store::InlineSingleStore,
+};
+
+//  Room for a message of up to 48 bytes.
+type MessageStore = InlineSingleStore<[u8; 48]>;
+
+//  Room for any error of up to 64 bytes, including an `ErrorMessage<MessageStore>`.
+type ErrorStore = InlineSingleStore<[u64; 8]>;
+
+type ConfigError = StoreError<ErrorStore>;
+
+#[derive(Debug)]
+struct Config {
+    width: u16,
+    height: u16,
+}
+
+fn main() {
+    for input in [
+        "width=640 height=480",
+        "width=640 height=4k",
+        "width=640",
+        "width:640",
+        "depth=8",
+    ] {
+        match parse_config(input) {
+            Ok(config) => println!("{input:>20}: {config:?}"),
+            Err(error) => println!("{input:>20}: error: {error} ({})", kind(&*error)),
+        }
+    }
+}
+
+fn parse_config(input: &str) -> Result<Config, ConfigError> {
+    let (mut width, mut height) = (None, None);
+
+    for pair in input.split_whitespace() {
+        let (key, value) = pair
+            .split_once('=')
+            .ok_or_else(|| message(format_args!("missing '=' in {pair:?}")))?;
+
+        let slot = match key {
+            "width" => &mut width,
+            "height" => &mut height,
+            _ => return Err(message(format_args!("unknown key {key:?}"))),
+        };
+
+        //  A `ParseIntError`, boxed as is.
+        let value = value
+            .parse()
+            .map_err(|error| ConfigError::from_error(error, ErrorStore::default()))?;
+
+        *slot = Some(value);
+    }
+
+    let width = width.ok_or_else(|| message(format_args!("missing width")))?;
+    let height = height.ok_or_else(|| message(format_args!("missing height")))?;
+
+    Ok(Config { width, height })
+}
+
+fn message(args: core::fmt::Arguments<'_>) -> ConfigError {
+    ConfigError::from_fmt(args, MessageStore::default(), ErrorStore::default())
+}
+
+fn kind(error: &(dyn Error + 'static)) -> &'static str {
+    if error.is::<ErrorMessage<MessageStore>>() {
+        "message"
+    } else {
+        "parse"
+    }
+}
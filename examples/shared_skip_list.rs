@@ -0,0 +1,225 @@
+//! Shares a `SkipList` between two processes, via a block of shared memory.
+//!
+//! The parent process creates a shared memory region, builds a `SkipList` with offset handles into it, then spawns a
+//! child process. The child process maps the same region -- most likely at a different address -- creates a fresh store
+//! over it, and reads the list.
+//!
+//! This works because the handles of a `StackBumpStore` are offsets from the start of its block of memory, rather than
+//! addresses, and thus remain meaningful regardless of where the block of memory is mapped.
+//!
+//! Only the handles are relocatable: the keys and values must not contain any pointer either, hence plain integers.
+
+#[cfg(unix)]
+fn main() {
+    let mut arguments = std::env::args().skip(1);
+
+    match (arguments.next().as_deref(), arguments.next()) {
+        (Some("child"), Some(name)) => shm::child(&name),
+        _ => shm::parent(),
+    }
+}
+
+#[cfg(not(unix))]
+fn main() {
+    println!("Shared memory is only demonstrated on unix.");
+}
+
+#[cfg(unix)]
+mod shm {
+    use std::{
+        env,
+        ffi::CString,
+        io, mem,
+        process::{self, Command},
+        ptr::{self, NonNull},
+    };
+
+    use storage::{
+        collection::SkipList,
+        store::{StackBumpBlock, StackBumpStore},
+    };
+
+    type Store<'a> = StackBumpStore<'a, u16>;
+
+    type List<'a> = SkipList<u32, u64, Store<'a>>;
+
+    /// The layout of the shared memory region.
+    ///
+    /// Both processes run the same executable, hence agree on the layout of `StackBumpBlock`.
+    struct Shared {
+        length: usize,
+        head: u16,
+        block: StackBumpBlock<[u64; 512]>,
+    }
+
+    pub(super) fn parent() {
+        let name = format!("/storage-shared-skip-list-{}", process::id());
+
+        let region = Region::create(&name).expect("Shared memory region to be created");
+
+        //  Safety:
+        //  -   `region` is valid for writes of `Shared`, and suitably aligned, as it is page-aligned.
+        //  -   `region` is not yet shared with any other process.
+        unsafe {
+            ptr::write(
+                region.shared().as_ptr(),
+                Shared {
+                    length: 0,
+                    head: 0,
+                    block: StackBumpBlock::new(),
+                },
+            )
+        };
+
+        //  Safety:
+        //  -   `region` was initialized just above.
+        //  -   The child process only accesses `region` once spawned, and the parent waits for it.
+        let shared = unsafe { region.shared().as_mut() };
+
+        let mut list = List::with_store(shared.block.create_store());
+
+        for key in [5, 3, 8, 1, 9, 2, 7, 4, 6] {
+            list.insert(key, u64::from(key) * 100);
+        }
+
+        let (length, head, _) = list.into_raw_parts();
+
+        shared.length = length;
+        shared.head = head;
+
+        println!("parent: built a list of {length} elements at {:p}", region.shared());
+
+        let status = Command::new(env::current_exe().expect("Current executable"))
+            .arg("child")
+            .arg(&name)
+            .status()
+            .expect("Child process to run");
+
+        assert!(status.success(), "child process failed: {status}");
+
+        //  Safety:
+        //  -   `length` and `head` were obtained from `into_raw_parts`, and the child did not modify the list.
+        let list = unsafe { List::from_raw_parts(shared.length, shared.head, shared.block.create_store()) };
+
+        assert_eq!(9, list.len());
+    }
+
+    pub(super) fn child(name: &str) {
+        let region = Region::open(name).expect("Shared memory region to be opened");
+
+        //  Safety:
+        //  -   `region` was initialized by the parent.
+        //  -   The parent does not access `region` until the child exits.
+        let shared = unsafe { region.shared().as_ref() };
+
+        //  A fresh store, over the very same block of memory, mapped at a different address.
+        //
+        //  Safety:
+        //  -   `length` and `head` were obtained from `into_raw_parts`.
+        //  -   The store resolves the handles to the same nodes, as its block of memory is the same.
+        let list = unsafe { List::from_raw_parts(shared.length, shared.head, shared.block.create_store()) };
+
+        println!(
+            "child: mapped a list of {} elements at {:p}",
+            list.len(),
+            region.shared()
+        );
+
+        for (key, value) in &list {
+            println!("child: {key} => {value}");
+
+            assert_eq!(u64::from(*key) * 100, *value);
+        }
+
+        assert_eq!(Some(&700), list.get(&7));
+        assert_eq!(None, list.get(&10));
+
+        //  The list belongs to the parent: no destruction, no deallocation.
+        mem::forget(list);
+    }
+
+    /// A shared memory region, unlinked on drop by its creator, and unmapped on drop.
+    struct Region {
+        name: CString,
+        owner: bool,
+        address: NonNull<Shared>,
+    }
+
+    impl Region {
+        fn create(name: &str) -> io::Result<Self> {
+            Self::map(name, libc::O_CREAT | libc::O_EXCL | libc::O_RDWR, true)
+        }
+
+        fn open(name: &str) -> io::Result<Self> {
+            Self::map(name, libc::O_RDWR, false)
+        }
+
+        fn shared(&self) -> NonNull<Shared> {
+            self.address
+        }
+
+        fn map(name: &str, flags: libc::c_int, owner: bool) -> io::Result<Self> {
+            let name = CString::new(name).expect("No NUL in name");
+            let size = mem::size_of::<Shared>();
+
+            //  Safety:
+            //  -   `name` is a valid C string.
+            let fd = unsafe { libc::shm_open(name.as_ptr(), flags, 0o600) };
+
+            if fd < 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            //  Safety:
+            //  -   `fd` is a valid file descriptor.
+            if owner && unsafe { libc::ftruncate(fd, size as libc::off_t) } != 0 {
+                let error = io::Error::last_os_error();
+
+                //  Safety:
+                //  -   `fd` is a valid file descriptor.
+                unsafe { libc::close(fd) };
+
+                return Err(error);
+            }
+
+            //  Safety:
+            //  -   `fd` is a valid file descriptor, to an object of at least `size` bytes.
+            let address = unsafe {
+                libc::mmap(
+                    ptr::null_mut(),
+                    size,
+                    libc::PROT_READ | libc::PROT_WRITE,
+                    libc::MAP_SHARED,
+                    fd,
+                    0,
+                )
+            };
+
+            //  Safety:
+            //  -   `fd` is a valid file descriptor, no longer needed once mapped.
+            unsafe { libc::close(fd) };
+
+            if address == libc::MAP_FAILED {
+                return Err(io::Error::last_os_error());
+            }
+
+            let address = NonNull::new(address.cast()).expect("Non-null mapping");
+
+            Ok(Self { name, owner, address })
+        }
+    }
+
+    impl Drop for Region {
+        fn drop(&mut self) {
+            //  Safety:
+            //  -   `self.address` was mapped with this size, and is no longer referenced.
+            unsafe { libc::munmap(self.address.as_ptr().cast(), mem::size_of::<Shared>()) };
+
+            if self.owner {
+                //  Safety:
+                //  -   `self.name` is a valid C string.
+                unsafe { libc::shm_unlink(self.name.as_ptr()) };
+            }
+        }
+    }
+}
@@ -0,0 +1,250 @@
+//! Builds, and traverses, a directed graph whose node and edge arenas share a single store.
+//!
+//! The nodes and the edges live in two distinct `StoreVec`, each owning one instance of the same sharing set, so that
+//! both arenas are carved out of a single block of memory. Any `StoreSharing` store would do; a `StackBumpStore` with
+//! `u16` handles keeps the vectors themselves small.
+//!
+//! Nodes and edges refer to one another by small typed indices, rather than pointers: an edge carrying a `u32` weight
+//! takes 8 bytes, where a pointer-based design takes 24 bytes on 64-bits targets.
+
+use std::{collections::VecDeque, mem, num::NonZeroU16, ptr::NonNull};
+
+use storage::{
+    collection::StoreVec,
+    interface::{StoreSharing, StoreSingle},
+    store::{StackBumpBlock, StackBumpStore},
+};
+
+type Graph<'a> = StoreGraph<&'static str, u32, StackBumpStore<'a, u16>>;
+
+fn main() {
+    let block = StackBumpBlock::<[u64; 32]>::new();
+
+    let mut graph = Graph::new(block.create_store()).unwrap_or_else(|_| panic!("Bump stores to always share"));
+
+    //  Growing a vector within a bump store leaves the old block of memory behind, hence reserve up-front.
+    graph.reserve(5, 6);
+
+    let paris = graph.add_node("Paris");
+    let lyon = graph.add_node("Lyon");
+    let marseille = graph.add_node("Marseille");
+    let nice = graph.add_node("Nice");
+    let lille = graph.add_node("Lille");
+
+    graph.add_edge(paris, lyon, 465);
+    graph.add_edge(paris, lille, 225);
+    graph.add_edge(lyon, marseille, 315);
+    graph.add_edge(lyon, nice, 470);
+    graph.add_edge(marseille, nice, 200);
+    graph.add_edge(nice, marseille, 200);
+
+    for (_, target, distance) in graph.edges(lyon) {
+        println!("Lyon -> {}: {distance} km", graph.node(target));
+    }
+
+    let reachable: Vec<_> = graph.breadth_first(lyon).map(|node| *graph.node(node)).collect();
+
+    println!("Reachable from Lyon: {reachable:?}");
+
+    assert_eq!(["Lyon", "Nice", "Marseille"], &reachable[..]);
+
+    println!(
+        "Size of an edge: {} bytes, versus {} bytes for a pointer-based edge.",
+        mem::size_of::<Edge<u32>>(),
+        mem::size_of::<PointerEdge<u32>>(),
+    );
+}
+
+/// The identifier of a node of a `StoreGraph`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+struct NodeId(NonZeroU16);
+
+/// The identifier of an edge of a `StoreGraph`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+struct EdgeId(NonZeroU16);
+
+/// A directed graph, whose node and edge arenas share a single store.
+///
+/// The edges leaving a node form a singly-linked list, threaded through the edge arena.
+struct StoreGraph<N, E, S: StoreSingle> {
+    nodes: StoreVec<Node<N>, S>,
+    edges: StoreVec<Edge<E>, S>,
+}
+
+impl<N, E, S> StoreGraph<N, E, S>
+where
+    S: StoreSingle + StoreSharing,
+{
+    /// Creates an empty graph, whose nodes and edges are allocated in `store`, or in stores sharing with it.
+    fn new(store: S) -> Result<Self, S::SharingError> {
+        let edges = StoreVec::new_in(store.share()?);
+        let nodes = StoreVec::new_in(store);
+
+        Ok(Self { nodes, edges })
+    }
+
+    /// Reserves capacity for at least `nodes` more nodes, and `edges` more edges.
+    fn reserve(&mut self, nodes: usize, edges: usize) {
+        self.nodes.reserve(nodes);
+        self.edges.reserve(edges);
+    }
+
+    /// Adds a node, returning its identifier.
+    ///
+    /// #   Panics
+    ///
+    /// If the graph already contains `u16::MAX - 1` nodes.
+    fn add_node(&mut self, value: N) -> NodeId {
+        let id = NodeId(Self::id(self.nodes.len()));
+
+        self.nodes.push(Node { value, first: None });
+
+        id
+    }
+
+    /// Adds an edge from `source` to `target`, returning its identifier.
+    ///
+    /// #   Panics
+    ///
+    /// If either `source` or `target` is not a node of this graph, or if the graph already contains `u16::MAX - 1`
+    /// edges.
+    fn add_edge(&mut self, source: NodeId, target: NodeId, value: E) -> EdgeId {
+        assert!(
+            Self::index(target.0) < self.nodes.len(),
+            "{target:?} should be a node of the graph"
+        );
+
+        let id = EdgeId(Self::id(self.edges.len()));
+
+        let node = &mut self.nodes[Self::index(source.0)];
+        let next = node.first.replace(id);
+
+        self.edges.push(Edge { value, target, next });
+
+        id
+    }
+
+    /// Returns the value of the node `id`.
+    ///
+    /// #   Panics
+    ///
+    /// If `id` is not a node of this graph.
+    fn node(&self, id: NodeId) -> &N {
+        &self.nodes[Self::index(id.0)].value
+    }
+
+    /// Returns the edges leaving `source`, most recently added first, as `(edge, target, value)`.
+    ///
+    /// #   Panics
+    ///
+    /// If `source` is not a node of this graph.
+    fn edges(&self, source: NodeId) -> Edges<'_, N, E, S> {
+        let next = self.nodes[Self::index(source.0)].first;
+
+        Edges { graph: self, next }
+    }
+
+    /// Returns the nodes reachable from `start`, including `start` itself, in breadth-first order.
+    ///
+    /// #   Panics
+    ///
+    /// If `start` is not a node of this graph.
+    fn breadth_first(&self, start: NodeId) -> BreadthFirst<'_, N, E, S> {
+        let mut visited = vec![false; self.nodes.len()];
+        visited[Self::index(start.0)] = true;
+
+        let queue = VecDeque::from([start]);
+
+        BreadthFirst {
+            graph: self,
+            visited,
+            queue,
+        }
+    }
+
+    //  Returns the identifier of the element at `index`.
+    fn id(index: usize) -> NonZeroU16 {
+        u16::try_from(index + 1)
+            .ok()
+            .and_then(NonZeroU16::new)
+            .unwrap_or_else(|| panic!("{index} elements should be representable"))
+    }
+
+    //  Returns the index of the element identified by `id`.
+    fn index(id: NonZeroU16) -> usize {
+        usize::from(id.get()) - 1
+    }
+}
+
+struct Node<N> {
+    value: N,
+    //  The most recently added edge leaving this node, if any.
+    first: Option<EdgeId>,
+}
+
+struct Edge<E> {
+    value: E,
+    target: NodeId,
+    //  The next edge leaving the same node, if any.
+    next: Option<EdgeId>,
+}
+
+//  The pointer-based equivalent of `Edge`, for comparison.
+#[allow(dead_code)]
+struct PointerEdge<E> {
+    value: E,
+    target: NonNull<()>,
+    next: Option<NonNull<PointerEdge<E>>>,
+}
+
+/// Iterator over the edges leaving a node.
+struct Edges<'a, N, E, S: StoreSingle> {
+    graph: &'a StoreGraph<N, E, S>,
+    next: Option<EdgeId>,
+}
+
+impl<'a, N, E, S> Iterator for Edges<'a, N, E, S>
+where
+    S: StoreSingle + StoreSharing,
+{
+    type Item = (EdgeId, NodeId, &'a E);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let id = self.next?;
+
+        let edge = &self.graph.edges[StoreGraph::<N, E, S>::index(id.0)];
+
+        self.next = edge.next;
+
+        Some((id, edge.target, &edge.value))
+    }
+}
+
+/// Iterator over the nodes reachable from a node, in breadth-first order.
+struct BreadthFirst<'a, N, E, S: StoreSingle> {
+    graph: &'a StoreGraph<N, E, S>,
+    visited: Vec<bool>,
+    queue: VecDeque<NodeId>,
+}
+
+impl<N, E, S> Iterator for BreadthFirst<'_, N, E, S>
+where
+    S: StoreSingle + StoreSharing,
+{
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.queue.pop_front()?;
+
+        for (_, target, _) in self.graph.edges(node) {
+            let visited = &mut self.visited[StoreGraph::<N, E, S>::index(target.0)];
+
+            if !*visited {
+                *visited = true;
+                self.queue.push_back(target);
+            }
+        }
+
+        Some(node)
+    }
+}
@@ -1,7 +1,7 @@
 //! A polyfill over some alloc crate pieces of functionality.
 
 #[cfg(feature = "alloc")]
-pub use alloc::alloc::handle_alloc_error;
+pub use std_alloc::alloc::handle_alloc_error;
 
 #[cfg(not(feature = "alloc"))]
 pub use polyfill::handle_alloc_error;
@@ -10,6 +10,8 @@ pub use polyfill::handle_alloc_error;
 mod polyfill {
     use core::alloc::Layout;
 
+    #[cold]
+    #[inline(never)]
     pub const fn handle_alloc_error(_layout: Layout) -> ! {
         panic!("allocation failed")
     }
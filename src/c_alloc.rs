@@ -0,0 +1,451 @@
+//! C `malloc`-style allocation, backed by a store.
+//!
+//! Embedded projects sometimes vendor C libraries expecting `malloc`, `calloc`, `realloc`, and `free`. This module
+//! allows backing those by a store -- for example, an `InlineBumpStore` living in the same fixed block of memory as the
+//! rest of the application.
+//!
+//! `CAllocator` wraps the store, and a fixed-capacity registry mapping the raw pointers handed out to C back to the
+//! handles and layouts of their blocks of memory, as C passes neither the size nor the alignment on `free`, and the
+//! handles of most stores are not pointers.
+//!
+//! The `c_allocator_shims!` macro then generates the `extern "C"` functions, bound to a `static` instance:
+//!
+//! ```
+//! #![feature(allocator_api)]
+//!
+//! use storage::{
+//!     c_alloc::CAllocator,
+//!     c_allocator_shims,
+//!     store::{AlignedBlock, InlineBumpStore},
+//! };
+//!
+//! static ALLOCATOR: CAllocator<InlineBumpStore<u16, AlignedBlock<4096, 16>>, 32> =
+//!     CAllocator::new(InlineBumpStore::new());
+//!
+//! c_allocator_shims! {
+//!     ALLOCATOR => malloc: vendor_malloc, calloc: vendor_calloc, realloc: vendor_realloc, free: vendor_free
+//! }
+//!
+//! //  Safety:
+//! //  -   `pointer` is only used while allocated, then freed once.
+//! unsafe {
+//!     let pointer = vendor_malloc(8).cast::<u64>();
+//!
+//!     assert!(!pointer.is_null());
+//!
+//!     pointer.write(42);
+//!
+//!     vendor_free(pointer.cast());
+//! }
+//! ```
+//!
+//! Access to the store is serialized by a spin lock, as C may call from any thread.
+
+use core::{
+    alloc::Layout,
+    cell::UnsafeCell,
+    fmt, hint, mem,
+    ptr::{self, NonNull},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use crate::interface::{Store, StoreStable};
+
+/// The alignment of the blocks of memory returned by `malloc`, `calloc`, and `realloc`.
+///
+/// Matches the guarantee of mainstream C libraries: twice the size of a pointer.
+pub const MALLOC_ALIGNMENT: usize = 2 * mem::size_of::<usize>();
+
+/// A C-compatible allocator, backed by a store, tracking up to `N` live blocks of memory.
+///
+/// Allocation fails, returning a null pointer, if the store fails, or if `N` blocks of memory are already live.
+///
+/// The pointers handed out remain valid for as long as the allocator is neither moved nor dropped, as per
+/// `StoreStable`, which a `static` guarantees.
+pub struct CAllocator<S: Store, const N: usize> {
+    locked: AtomicBool,
+    //  The store is only ever accessed through shared references, as its memory may be part of `self`, hence the
+    //  registry is kept apart, so as to never create a mutable reference overlapping the blocks of memory handed out.
+    store: S,
+    registry: UnsafeCell<[Option<Entry<S::Handle>>; N]>,
+}
+
+impl<S: Store, const N: usize> CAllocator<S, N> {
+    const VACANT: Option<Entry<S::Handle>> = None;
+
+    /// Creates an allocator, backed by `store`.
+    pub const fn new(store: S) -> Self {
+        let locked = AtomicBool::new(false);
+        let registry = UnsafeCell::new([Self::VACANT; N]);
+
+        Self {
+            locked,
+            store,
+            registry,
+        }
+    }
+
+    /// Returns the number of live blocks of memory.
+    pub fn live(&self) -> usize {
+        self.lock().registry().iter().filter(|entry| entry.is_some()).count()
+    }
+}
+
+impl<S: Store + StoreStable, const N: usize> CAllocator<S, N> {
+    /// Allocates `size` bytes, aligned as per `MALLOC_ALIGNMENT`, as per C `malloc`.
+    ///
+    /// Returns a null pointer on failure.
+    pub fn malloc(&self, size: usize) -> *mut u8 {
+        self.allocate(size, MALLOC_ALIGNMENT, false)
+    }
+
+    /// Allocates `count * size` zeroed bytes, aligned as per `MALLOC_ALIGNMENT`, as per C `calloc`.
+    ///
+    /// Returns a null pointer on failure, including if `count * size` overflows.
+    pub fn calloc(&self, count: usize, size: usize) -> *mut u8 {
+        let Some(size) = count.checked_mul(size) else {
+            return ptr::null_mut();
+        };
+
+        self.allocate(size, MALLOC_ALIGNMENT, true)
+    }
+
+    /// Allocates `size` bytes, aligned as per `alignment`, as per C `aligned_alloc`.
+    ///
+    /// Returns a null pointer on failure, including if `alignment` is not a power of 2.
+    pub fn aligned_alloc(&self, alignment: usize, size: usize) -> *mut u8 {
+        if !alignment.is_power_of_two() {
+            return ptr::null_mut();
+        }
+
+        self.allocate(size, alignment, false)
+    }
+
+    /// Resizes the block of memory pointed to by `pointer` to `size` bytes, as per C `realloc`.
+    ///
+    /// If `pointer` is null, allocates as per `malloc`. If `size` is 0, frees `pointer` and returns a null pointer.
+    ///
+    /// Returns a null pointer on failure, in which case the block of memory pointed to by `pointer` is left untouched.
+    ///
+    /// #   Safety
+    ///
+    /// -   `pointer` must be null, or have been returned by this allocator and not freed since.
+    pub unsafe fn realloc(&self, pointer: *mut u8, size: usize) -> *mut u8 {
+        let Some(pointer) = NonNull::new(pointer) else {
+            return self.malloc(size);
+        };
+
+        if size == 0 {
+            //  Safety:
+            //  -   As per pre-conditions.
+            unsafe { self.free(pointer.as_ptr()) };
+
+            return ptr::null_mut();
+        }
+
+        let mut guard = self.lock();
+
+        let Some(index) = guard.find(pointer) else {
+            debug_assert!(false, "{pointer:?} not allocated by this allocator");
+            return ptr::null_mut();
+        };
+
+        let Some(entry) = guard.registry()[index] else {
+            unreachable!("`find` to return an occupied entry");
+        };
+
+        let Ok(new_layout) = Layout::from_size_align(size, entry.layout.align()) else {
+            return ptr::null_mut();
+        };
+
+        let result = if new_layout.size() >= entry.layout.size() {
+            //  Safety:
+            //  -   `entry.handle` was allocated by `self.store`, and is still valid, as it is registered.
+            //  -   `entry.layout` fits the block of memory, as it was used to allocate it.
+            //  -   `new_layout.size()` is greater than or equal to `entry.layout.size()`.
+            unsafe { self.store.grow(entry.handle, entry.layout, new_layout) }
+        } else {
+            //  Safety:
+            //  -   `entry.handle` was allocated by `self.store`, and is still valid, as it is registered.
+            //  -   `entry.layout` fits the block of memory, as it was used to allocate it.
+            //  -   `new_layout.size()` is less than `entry.layout.size()`.
+            unsafe { self.store.shrink(entry.handle, entry.layout, new_layout) }
+        };
+
+        let Ok((handle, _)) = result else {
+            return ptr::null_mut();
+        };
+
+        //  Safety:
+        //  -   `handle` was allocated by `self.store`, and is still valid.
+        let pointer = unsafe { self.store.resolve(handle) };
+
+        guard.registry()[index] = Some(Entry {
+            pointer,
+            handle,
+            layout: new_layout,
+        });
+
+        pointer.as_ptr()
+    }
+
+    /// Frees the block of memory pointed to by `pointer`, as per C `free`.
+    ///
+    /// Does nothing if `pointer` is null.
+    ///
+    /// #   Safety
+    ///
+    /// -   `pointer` must be null, or have been returned by this allocator and not freed since.
+    pub unsafe fn free(&self, pointer: *mut u8) {
+        let Some(pointer) = NonNull::new(pointer) else {
+            return;
+        };
+
+        let mut guard = self.lock();
+
+        let Some(index) = guard.find(pointer) else {
+            debug_assert!(false, "{pointer:?} not allocated by this allocator");
+            return;
+        };
+
+        let Some(entry) = guard.registry()[index].take() else {
+            unreachable!("`find` to return an occupied entry");
+        };
+
+        //  Safety:
+        //  -   `entry.handle` was allocated by `self.store`, and is still valid, as it was registered.
+        //  -   `entry.layout` fits the block of memory, as it was used to allocate it.
+        unsafe { self.store.deallocate(entry.handle, entry.layout) };
+    }
+}
+
+//  Safety:
+//  -   All accesses to the store, and the registry, are serialized by `self.locked`, and the store is used from any
+//      thread, hence it must be `Send`.
+unsafe impl<S: Store + Send, const N: usize> Sync for CAllocator<S, N> {}
+
+impl<S: Store, const N: usize> fmt::Debug for CAllocator<S, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_struct("CAllocator")
+            .field("live", &self.live())
+            .field("capacity", &N)
+            .finish()
+    }
+}
+
+/// Generates C `malloc`, `calloc`, `realloc`, and `free` functions, bound to a `static` `CAllocator`.
+///
+/// The functions are `#[no_mangle]` and `extern "C"`, under the names given. Optionally, `aligned_alloc` is generated
+/// too. See the `c_alloc` module for an example.
+#[macro_export]
+macro_rules! c_allocator_shims {
+    (
+        $allocator:path => malloc: $malloc:ident, calloc: $calloc:ident, realloc: $realloc:ident, free: $free:ident
+        $(, aligned_alloc: $aligned_alloc:ident)? $(,)?
+    ) => {
+        /// Allocates, as per C `malloc`.
+        #[no_mangle]
+        pub extern "C" fn $malloc(size: usize) -> *mut ::core::ffi::c_void {
+            $allocator.malloc(size).cast()
+        }
+
+        /// Allocates zeroed memory, as per C `calloc`.
+        #[no_mangle]
+        pub extern "C" fn $calloc(count: usize, size: usize) -> *mut ::core::ffi::c_void {
+            $allocator.calloc(count, size).cast()
+        }
+
+        /// Resizes, as per C `realloc`.
+        ///
+        /// #   Safety
+        ///
+        /// -   `pointer` must be null, or have been returned by this allocator and not freed since.
+        #[no_mangle]
+        pub unsafe extern "C" fn $realloc(pointer: *mut ::core::ffi::c_void, size: usize) -> *mut ::core::ffi::c_void {
+            //  Safety:
+            //  -   As per pre-conditions.
+            unsafe { $allocator.realloc(pointer.cast(), size).cast() }
+        }
+
+        /// Frees, as per C `free`.
+        ///
+        /// #   Safety
+        ///
+        /// -   `pointer` must be null, or have been returned by this allocator and not freed since.
+        #[no_mangle]
+        pub unsafe extern "C" fn $free(pointer: *mut ::core::ffi::c_void) {
+            //  Safety:
+            //  -   As per pre-conditions.
+            unsafe { $allocator.free(pointer.cast()) }
+        }
+
+        $(
+            /// Allocates aligned memory, as per C `aligned_alloc`.
+            #[no_mangle]
+            pub extern "C" fn $aligned_alloc(alignment: usize, size: usize) -> *mut ::core::ffi::c_void {
+                $allocator.aligned_alloc(alignment, size).cast()
+            }
+        )?
+    };
+}
+
+//
+//  Implementation
+//
+
+//  A live block of memory, as handed out to C.
+#[derive(Clone, Copy)]
+struct Entry<H> {
+    pointer: NonNull<u8>,
+    handle: H,
+    layout: Layout,
+}
+
+impl<S: Store + StoreStable, const N: usize> CAllocator<S, N> {
+    //  Allocates a block of memory of `size` bytes, aligned on `alignment`, zeroed if requested.
+    //
+    //  Zero-sized requests allocate a single byte, so that each live pointer is unique.
+    fn allocate(&self, size: usize, alignment: usize, zeroed: bool) -> *mut u8 {
+        let Ok(layout) = Layout::from_size_align(size.max(1), alignment) else {
+            return ptr::null_mut();
+        };
+
+        let mut guard = self.lock();
+
+        let Some(index) = guard.registry().iter().position(Option::is_none) else {
+            return ptr::null_mut();
+        };
+
+        let result = if zeroed {
+            self.store.allocate_zeroed(layout)
+        } else {
+            self.store.allocate(layout)
+        };
+
+        let Ok((handle, _)) = result else {
+            return ptr::null_mut();
+        };
+
+        //  Safety:
+        //  -   `handle` was allocated by `self.store`, and is still valid.
+        let pointer = unsafe { self.store.resolve(handle) };
+
+        guard.registry()[index] = Some(Entry {
+            pointer,
+            handle,
+            layout,
+        });
+
+        pointer.as_ptr()
+    }
+}
+
+impl<S: Store, const N: usize> CAllocator<S, N> {
+    fn lock(&self) -> Guard<'_, S, N> {
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            hint::spin_loop();
+        }
+
+        Guard { allocator: self }
+    }
+}
+
+//  Exclusive access to the store and registry, for as long as it lives.
+//
+//  Only the registry is handed out mutably, the store being used through `&S` as any store.
+struct Guard<'a, S: Store, const N: usize> {
+    allocator: &'a CAllocator<S, N>,
+}
+
+impl<S: Store, const N: usize> Guard<'_, S, N> {
+    //  Returns the registry.
+    fn registry(&mut self) -> &mut [Option<Entry<S::Handle>>; N] {
+        //  Safety:
+        //  -   Access is exclusive, as the lock is held, and `self` is borrowed mutably.
+        unsafe { &mut *self.allocator.registry.get() }
+    }
+
+    //  Returns the index of the entry of `pointer`, if any.
+    fn find(&mut self, pointer: NonNull<u8>) -> Option<usize> {
+        self.registry()
+            .iter()
+            .position(|entry| entry.is_some_and(|entry| entry.pointer == pointer))
+    }
+}
+
+impl<S: Store, const N: usize> Drop for Guard<'_, S, N> {
+    fn drop(&mut self) {
+        self.allocator.locked.store(false, Ordering::Release);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::store::{AlignedBlock, InlineBumpStore};
+
+    use super::*;
+
+    type TestAllocator = CAllocator<InlineBumpStore<u16, AlignedBlock<256, 64>>, 4>;
+
+    static ALLOCATOR: TestAllocator = CAllocator::new(InlineBumpStore::new());
+
+    crate::c_allocator_shims! {
+        ALLOCATOR => malloc: test_malloc, calloc: test_calloc, realloc: test_realloc, free: test_free,
+        aligned_alloc: test_aligned_alloc
+    }
+
+    #[test]
+    fn shims() {
+        let pointer = test_calloc(4, 4).cast::<u32>();
+
+        assert!(!pointer.is_null());
+        assert_eq!(0, pointer.addr() % MALLOC_ALIGNMENT);
+
+        //  Safety:
+        //  -   `pointer` points to 4 zeroed `u32`, which are then written, and read back.
+        //  -   `pointer` is freed once, through the same allocator.
+        unsafe {
+            assert_eq!([0; 4], pointer.cast::<[u32; 4]>().read());
+
+            pointer.cast::<[u32; 4]>().write([1, 2, 3, 4]);
+
+            let pointer = test_realloc(pointer.cast(), 32).cast::<u32>();
+
+            assert!(!pointer.is_null());
+            assert_eq!([1, 2, 3, 4], pointer.cast::<[u32; 4]>().read());
+
+            let aligned = test_aligned_alloc(64, 1);
+
+            assert_eq!(0, aligned.addr() % 64);
+            assert_eq!(2, ALLOCATOR.live());
+
+            test_free(aligned);
+            test_free(pointer.cast());
+            test_free(ptr::null_mut());
+        }
+
+        assert_eq!(0, ALLOCATOR.live());
+        assert!(test_aligned_alloc(3, 1).is_null());
+    }
+
+    #[test]
+    fn registry_exhausted() {
+        let allocator = CAllocator::<InlineBumpStore<u16, AlignedBlock<256, 16>>, 2>::new(InlineBumpStore::new());
+
+        let (first, second) = (allocator.malloc(0), allocator.malloc(0));
+
+        assert!(!first.is_null() && !second.is_null());
+        assert_ne!(first, second);
+        assert!(allocator.malloc(1).is_null());
+
+        //  Safety:
+        //  -   `first` was returned by `allocator`, and is freed once.
+        unsafe { allocator.free(first) };
+
+        assert!(!allocator.malloc(1).is_null());
+    }
+} // mod tests
@@ -2,18 +2,69 @@
 //!
 //! The collections may have a rather minimal interface, as the emphasis is put on demonstrating the flexibility of the
 //! `Store` trait, rather than providing fully implemented collections -- for now.
+//!
+//! #   Allocation failures
+//!
+//! The collections follow a single policy with regard to allocation failures:
+//!
+//! -   Methods prefixed with `try_` return an `AllocError` if the store fails to provide the memory, leaving the choice
+//!     of how to handle the failure to the caller. `StoreVec::try_push` returns the element instead, so that it is not
+//!     lost, as does `ConcurrentVec::push`, while `LinkedList::try_clone` and `SkipList::try_clone` return a
+//!     `CloneError`, which also covers the failure to share the store.
+//! -   Other methods which may allocate, including trait implementations such as `Clone` or `Extend`, call
+//!     `handle_alloc_error` if the store fails to provide the memory.
+//!
+//! With the `alloc` feature, `handle_alloc_error` aborts by default, and may be customized as per the `alloc` crate.
+//! Without it, `handle_alloc_error` panics.
 
 mod concurrent_vec;
+mod gap_buffer;
+mod hash_table;
+mod intrusive_list;
 mod linked_list;
+mod memory_usage;
+mod pinned;
+mod segmented_vec;
 mod skip_list;
+mod spsc_queue;
 mod store_box;
+mod store_cell;
+mod store_cow_slice;
+mod store_error;
+mod store_hash_set;
+mod store_pool;
+mod store_rc;
+mod store_soa;
+mod store_stable_vec;
+mod store_string;
 mod store_vec;
+mod zero_copy;
 
 #[cfg(test)]
 mod utils;
 
-pub use concurrent_vec::ConcurrentVec;
-pub use linked_list::LinkedList;
+pub use concurrent_vec::{ConcurrentVec, ConcurrentVecRef};
+pub use gap_buffer::GapBuffer;
+pub use intrusive_list::{Adapter, IntrusiveList, Link};
+pub use linked_list::{CloneError, InlineLinkedList, LinkedList, LinkedListBlock};
+pub use memory_usage::MemoryUsage;
+pub use pinned::Pinned;
+pub use segmented_vec::{SegmentedVec, Segments, SegmentsMut};
 pub use skip_list::SkipList;
-pub use store_box::StoreBox;
+pub use spsc_queue::{Consumer, Producer, SpscQueue};
+pub use store_box::{EmptyStoreBox, RefStoreBox, StoreBox};
+pub use store_cell::{CellRef, CellRefMut, StoreCell};
+pub use store_cow_slice::StoreCowSlice;
+pub use store_error::{ErrorMessage, StoreError};
+pub use store_hash_set::{HashSetBucket, StoreHashSet};
+pub use store_pool::{PoolGuard, StorePool};
+pub use store_rc::{SharedCapable, StoreRc};
+pub use store_soa::{SoA, StoreSoA};
+pub use store_stable_vec::{StableVecIter, StoreStableVec};
+pub use store_string::{InlineString, StoreString};
 pub use store_vec::StoreVec;
+pub use zero_copy::{PersistedSlice, Pod, ViewError};
+
+#[cfg(feature = "derive")]
+#[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
+pub use storage_derive::SoA;
@@ -1,17 +1,44 @@
 //! Proof of concept concurrent access vector.
 //!
 //! For simplification, the capacity is fixed at creation, and elements cannot be removed.
+//!
+//! A `ConcurrentVec` is typically shared across threads by wrapping it in an `Arc`. When the store implements
+//! `StoreSharing`, a `ConcurrentVecRef` may be used instead: each instance owns its own part of the sharing set, and
+//! the length and reference count live in the block of memory alongside the elements, so that no `Arc` is needed,
+//! even in `no_std` environments.
+//!
+//! The length doubles as a lock, taken by negating it while appending an element. With the `epoch-check` feature, a
+//! separate epoch counter checks this locking protocol, panicking on any modification of the length while locked, or
+//! any acquisition of the lock in between that would otherwise go unnoticed, as a safety net when evolving it.
 
 use core::{
-    alloc::Layout,
-    fmt, hint,
+    alloc::{AllocError, Layout},
+    cmp, fmt, hash,
     mem::{self, ManuallyDrop, MaybeUninit},
     ops,
     ptr::{self, NonNull},
-    sync::atomic::{AtomicIsize, Ordering},
+    slice,
+};
+
+#[cfg(not(all(test, loom)))]
+use core::{
+    hint,
+    sync::atomic::{fence, AtomicIsize, AtomicUsize, Ordering},
+};
+
+//  Under loom, atomics are modelled, and spinning yields to the model scheduler.
+#[cfg(all(test, loom))]
+use loom::{
+    hint,
+    sync::atomic::{fence, AtomicIsize, AtomicUsize, Ordering},
 };
 
-use crate::{extension::unique::UniqueHandle, interface::Store};
+use crate::{
+    alloc,
+    collection::MemoryUsage,
+    extension::{header_slice::HeaderSliceHandle, unique::UniqueHandle},
+    interface::{Store, StoreSharing},
+};
 
 /// A fixed-capacity vector which can be modified concurrently.
 pub struct ConcurrentVec<T, S: Store> {
@@ -19,7 +46,7 @@ pub struct ConcurrentVec<T, S: Store> {
     //  -   `length` is negative if a thread is appending a new element.
     //  -   `length.abs() - 1 <= self.store.capacity`.
     //  -   Elements in 0..(length.abs() - 1) are initialized.
-    length: AtomicIsize,
+    length: Tracker,
     store: Inner<T, S>,
 }
 
@@ -37,11 +64,32 @@ impl<T, S: Store> ConcurrentVec<T, S> {
     /// Creates a vector with a given capacity and store.
     ///
     /// Since the vector cannot be resized later, pick well!
+    ///
+    /// #   Panics
+    ///
+    /// If the capacity exceeds `isize::MAX` bytes.
+    ///
+    /// Calls `handle_alloc_error` on allocation failure.
     pub fn with_store(capacity: usize, store: S) -> Self {
-        let length = AtomicIsize::new(1);
-        let store = Inner::with_store(capacity, store);
+        let Ok(layout) = Layout::array::<T>(capacity) else {
+            panic!("Capacity exceeds isize::MAX bytes")
+        };
+
+        let Ok(this) = Self::try_with_store(capacity, store) else {
+            alloc::handle_alloc_error(layout)
+        };
+
+        this
+    }
 
-        Self { length, store }
+    /// Attempts to create a vector with a given capacity and store.
+    ///
+    /// Returns an error if the capacity exceeds `isize::MAX` bytes, or on allocation failure.
+    pub fn try_with_store(capacity: usize, store: S) -> Result<Self, AllocError> {
+        let length = Tracker::new(0);
+        let store = Inner::try_with_store(capacity, store)?;
+
+        Ok(Self { length, store })
     }
 
     /// Returns whether the vector is empty.
@@ -51,7 +99,7 @@ impl<T, S: Store> ConcurrentVec<T, S> {
 
     /// Returns the length of the vector.
     pub fn len(&self) -> usize {
-        (self.length.load(Ordering::Acquire).abs() - 1) as usize
+        self.length.len()
     }
 
     /// Returns the capacity of the vector.
@@ -59,6 +107,21 @@ impl<T, S: Store> ConcurrentVec<T, S> {
         self.store.capacity()
     }
 
+    /// Returns the memory footprint of the vector, as per the layout of its buffer.
+    ///
+    /// Elements may be pushed concurrently, hence the number of bytes used is a lower bound.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        let bytes_allocated = self.capacity() * mem::size_of::<T>();
+        let bytes_used = self.len() * mem::size_of::<T>();
+        let allocations = if bytes_allocated > 0 { 1 } else { 0 };
+
+        MemoryUsage {
+            bytes_allocated,
+            bytes_used,
+            allocations,
+        }
+    }
+
     /// Returns a reference to the slice of initialized elements.
     pub fn as_slice(&self) -> &[T] {
         let initialized = self.initialized();
@@ -73,6 +136,8 @@ impl<T, S: Store> ConcurrentVec<T, S> {
 
     /// Returns a mutable reference to the slice of initialized elements.
     pub fn as_slice_mut(&mut self) -> &mut [T] {
+        self.length.check_exclusive();
+
         let mut initialized = self.initialized();
 
         //  Safety:
@@ -133,59 +198,10 @@ impl<T, S: Store> ConcurrentVec<T, S> {
     ///
     /// Returns an error if the vector is full, that is, if `self.len() == self.capacity()`.
     pub fn push(&self, element: T) -> Result<(), T> {
-        let mut length = self.length.load(Ordering::Acquire);
-
-        loop {
-            if length.unsigned_abs() > self.store.capacity() {
-                return Err(element);
-            }
-
-            if length < 0 {
-                hint::spin_loop();
-
-                length = self.length.load(Ordering::Acquire);
-                continue;
-            }
-
-            debug_assert!(length > 0);
-
-            let result = self
-                .length
-                .compare_exchange_weak(length, -length, Ordering::Acquire, Ordering::Relaxed);
-
-            if let Err(prev) = result {
-                hint::spin_loop();
-
-                length = prev;
-                continue;
-            }
-
-            break;
-        }
-
-        //  The slot at `length - 1` is ours!
-        debug_assert!(length > 0, "{length}");
-        debug_assert!(
-            length.unsigned_abs() <= self.store.capacity(),
-            "{length} > {}",
-            self.store.capacity()
-        );
-
-        let slots = self.store.slots();
-
         //  Safety:
-        //  -   `length - 1 < self.store.capacity()`, since `length > 0` and `length <= self.store.capacity()`.
-        let slot = unsafe { slots.get_unchecked_mut(length as usize - 1) };
-
-        //  Safety:
-        //  -   `slot` points to a valid area of memory.
-        //  -   `slot` points to a writeable area of memory.
-        //  -   `slot` is accessible in exclusive mode, as per the lock on `self.length`.
-        unsafe { ptr::write(slot.as_ptr(), element) };
-
-        self.length.store(length + 1, Ordering::Release);
-
-        Ok(())
+        //  -   `self.store.slots()` is valid for writes, and remains valid for the duration of the call.
+        //  -   `self.length` tracks the initialized elements of `self.store.slots()`.
+        unsafe { push_into(&self.length, self.store.slots(), element) }
     }
 }
 
@@ -195,7 +211,7 @@ where
     S: Store + Clone,
 {
     fn clone(&self) -> Self {
-        let clone = Self::with_store(self.store.capacity(), self.store.store.clone());
+        let mut clone = Self::with_store(self.store.capacity(), self.store.store.clone());
 
         let elements = self.as_slice();
         let slots = clone.store.slots();
@@ -215,31 +231,30 @@ where
 
         MaybeUninit::write_slice_cloned(slots, elements);
 
-        clone.length.store(elements.len() as isize + 1, Ordering::Release);
+        clone.length = Tracker::new(elements.len());
 
         clone
     }
 }
 
+//  The capacity is 0, so as not to allocate; this is mostly useful for `mem::take`.
+impl<T, S: Store + Default> Default for ConcurrentVec<T, S> {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
 impl<T, S: Store> Drop for ConcurrentVec<T, S> {
     fn drop(&mut self) {
-        if !mem::needs_drop::<T>() {
-            return;
-        }
-
-        let initialized = self.initialized();
+        self.length.check_exclusive();
 
-        for index in 0..initialized.len() {
-            //  Safety:
-            //  -   `index <= initialized.len()`.
-            let element = unsafe { initialized.get_unchecked_mut(index) };
+        let length = self.len();
 
-            //  Safety:
-            //  -   `element` is valid for both reads and writes.
-            //  -   `element` is properly aligned.
-            //  -   There are no specific invariant to uphold for `element`.
-            unsafe { ptr::drop_in_place(element.as_ptr()) };
-        }
+        //  Safety:
+        //  -   `self.store.handle` was allocated by `self.store.store`, and is still valid.
+        //  -   `length <= self.capacity()`, and the first `length` elements are initialized, as per invariant.
+        //  -   No reference to the elements is active, as `self` is borrowed mutably, and they are not used afterwards.
+        unsafe { self.store.handle.drop_initialized_prefix(length, &self.store.store) };
     }
 }
 
@@ -266,6 +281,24 @@ impl<T, S: Store> ops::DerefMut for ConcurrentVec<T, S> {
     }
 }
 
+impl<'a, T, S: Store> IntoIterator for &'a ConcurrentVec<T, S> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
+impl<'a, T, S: Store> IntoIterator for &'a mut ConcurrentVec<T, S> {
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice_mut().iter_mut()
+    }
+}
+
 //  Safety:
 //  -   Same as `Vec<T>`.
 unsafe impl<T, S> Send for ConcurrentVec<T, S>
@@ -284,6 +317,310 @@ where
 {
 }
 
+//
+//  Comparison
+//
+
+impl<T, S, OS> cmp::PartialEq<ConcurrentVec<T, OS>> for ConcurrentVec<T, S>
+where
+    T: cmp::PartialEq,
+    S: Store,
+    OS: Store,
+{
+    fn eq(&self, other: &ConcurrentVec<T, OS>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: cmp::PartialEq, S: Store> cmp::PartialEq<[T]> for ConcurrentVec<T, S> {
+    fn eq(&self, other: &[T]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl<T: cmp::PartialEq, S: Store> cmp::PartialEq<&[T]> for ConcurrentVec<T, S> {
+    fn eq(&self, other: &&[T]) -> bool {
+        self.as_slice() == *other
+    }
+}
+
+impl<T: cmp::PartialEq, S: Store, const N: usize> cmp::PartialEq<[T; N]> for ConcurrentVec<T, S> {
+    fn eq(&self, other: &[T; N]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl<T: cmp::PartialEq, S: Store> cmp::PartialEq<ConcurrentVec<T, S>> for [T] {
+    fn eq(&self, other: &ConcurrentVec<T, S>) -> bool {
+        self == other.as_slice()
+    }
+}
+
+impl<T: cmp::PartialEq, S: Store> cmp::PartialEq<ConcurrentVec<T, S>> for &[T] {
+    fn eq(&self, other: &ConcurrentVec<T, S>) -> bool {
+        *self == other.as_slice()
+    }
+}
+
+impl<T: cmp::PartialEq, S: Store, const N: usize> cmp::PartialEq<ConcurrentVec<T, S>> for [T; N] {
+    fn eq(&self, other: &ConcurrentVec<T, S>) -> bool {
+        self == other.as_slice()
+    }
+}
+
+impl<T: cmp::Eq, S: Store> cmp::Eq for ConcurrentVec<T, S> {}
+
+/// Hashes as a slice, hence as a `Vec`, with the same elements.
+impl<T: hash::Hash, S: Store> hash::Hash for ConcurrentVec<T, S> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state);
+    }
+}
+
+impl<T, S, OS> cmp::PartialOrd<ConcurrentVec<T, OS>> for ConcurrentVec<T, S>
+where
+    T: cmp::PartialOrd,
+    S: Store,
+    OS: Store,
+{
+    fn partial_cmp(&self, other: &ConcurrentVec<T, OS>) -> Option<cmp::Ordering> {
+        self.as_slice().partial_cmp(other.as_slice())
+    }
+}
+
+impl<T: cmp::Ord, S: Store> cmp::Ord for ConcurrentVec<T, S> {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.as_slice().cmp(other.as_slice())
+    }
+}
+
+/// A fixed-capacity vector which can be modified concurrently, and shared without `Arc`.
+///
+/// Each instance owns one part of a set of sharing stores, and a copy of the handle to the vector. The length and the
+/// reference count live in the block of memory, ahead of the elements, so that all instances observe the same vector.
+///
+/// Cloning an instance shares the store, and increments the reference count. The block of memory is deallocated, and
+/// its elements dropped, by whichever instance drops the last reference, using its own part of the sharing set.
+pub struct ConcurrentVecRef<T, S: Store> {
+    store: ManuallyDrop<S>,
+    handle: SharedHandle<T, S::Handle>,
+}
+
+impl<T, S: Store> ConcurrentVecRef<T, S> {
+    /// Creates a vector with a given capacity and a default store.
+    ///
+    /// Since the vector cannot be resized later, pick well!
+    pub fn new(capacity: usize) -> Self
+    where
+        S: Default,
+    {
+        Self::with_store(capacity, S::default())
+    }
+
+    /// Creates a vector with a given capacity and store.
+    ///
+    /// Since the vector cannot be resized later, pick well!
+    pub fn with_store(capacity: usize, store: S) -> Self {
+        let shared = Shared {
+            length: Tracker::new(0),
+            references: AtomicUsize::new(1),
+            capacity,
+        };
+
+        let handle = SharedHandle::new(shared, capacity, |_| MaybeUninit::uninit(), &store);
+        let store = ManuallyDrop::new(store);
+
+        Self { store, handle }
+    }
+
+    /// Attempts to create a vector with a given capacity and store.
+    ///
+    /// Returns an error if the capacity exceeds `isize::MAX` bytes, or on allocation failure.
+    pub fn try_with_store(capacity: usize, store: S) -> Result<Self, AllocError> {
+        let shared = Shared {
+            length: Tracker::new(0),
+            references: AtomicUsize::new(1),
+            capacity,
+        };
+
+        let handle = SharedHandle::try_new(shared, capacity, |_| MaybeUninit::uninit(), &store)?;
+        let store = ManuallyDrop::new(store);
+
+        Ok(Self { store, handle })
+    }
+
+    /// Returns whether the vector is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the length of the vector.
+    pub fn len(&self) -> usize {
+        self.shared().length.len()
+    }
+
+    /// Returns the capacity of the vector.
+    pub fn capacity(&self) -> usize {
+        self.shared().capacity
+    }
+
+    /// Returns the number of instances referring to the same vector.
+    pub fn reference_count(&self) -> usize {
+        self.shared().references.load(Ordering::Acquire)
+    }
+
+    /// Returns a reference to the slice of initialized elements.
+    pub fn as_slice(&self) -> &[T] {
+        let slots = self.slots();
+
+        //  Safety:
+        //  -   `self.len() <= slots.len()`, as per invariant.
+        let initialized = unsafe { slots.get_unchecked_mut(..self.len()) };
+
+        //  Safety:
+        //  -   `initialized` covers a valid and readable area of memory, of initialized elements.
+        //  -   `initialized` is accessible in shared mode, as initialized elements are never modified.
+        //  -   The lifetime of the resulting slice will not exceed that of `self`, which keeps the vector alive.
+        unsafe { initialized.as_ref() }
+    }
+
+    /// Attempts to push a new element into the vector.
+    ///
+    /// The vector is locked for writes for the duration of the operation, across all instances referring to it.
+    ///
+    /// Returns an error if the vector is full, that is, if `self.len() == self.capacity()`.
+    pub fn push(&self, element: T) -> Result<(), T> {
+        //  Safety:
+        //  -   `self.slots()` is valid for writes, and remains valid for the duration of the call.
+        //  -   `self.shared().length` tracks the initialized elements of `self.slots()`.
+        unsafe { push_into(&self.shared().length, self.slots(), element) }
+    }
+}
+
+impl<T, S: Store + StoreSharing> ConcurrentVecRef<T, S> {
+    /// Attempts to create a new instance referring to the same vector, sharing the store of `self`.
+    ///
+    /// Returns an error if sharing is not currently possible.
+    pub fn try_clone(&self) -> Result<Self, S::SharingError> {
+        let store = ManuallyDrop::new(self.store.share()?);
+
+        //  Relaxed is sufficient, as for `Arc`: `self` is a live reference, hence the vector cannot be deallocated
+        //  concurrently, and the new reference is only made available to another thread through synchronization.
+        self.shared().references.fetch_add(1, Ordering::Relaxed);
+
+        Ok(Self {
+            store,
+            handle: self.handle,
+        })
+    }
+}
+
+impl<T, S> Clone for ConcurrentVecRef<T, S>
+where
+    S: Store + StoreSharing<SharingError = !>,
+{
+    fn clone(&self) -> Self {
+        self.try_clone().into_ok()
+    }
+}
+
+//  The capacity is 0, so as not to allocate; this is mostly useful for `mem::take`.
+impl<T, S: Store + Default> Default for ConcurrentVecRef<T, S> {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl<T, S: Store> Drop for ConcurrentVecRef<T, S> {
+    fn drop(&mut self) {
+        let previous = self.shared().references.fetch_sub(1, Ordering::Release);
+
+        if previous > 1 {
+            //  Safety:
+            //  -   `self.store` will never be used ever again.
+            unsafe { ManuallyDrop::drop(&mut self.store) };
+
+            return;
+        }
+
+        //  Synchronizes with the `Release` decrements of all other instances, so that all their pushes are visible.
+        fence(Ordering::Acquire);
+
+        self.shared().length.check_exclusive();
+
+        let length = self.len();
+        let capacity = self.capacity();
+
+        if mem::needs_drop::<T>() {
+            let slots = self.slots();
+
+            //  Safety:
+            //  -   `length <= slots.len()`, as per invariant.
+            let initialized = unsafe { slots.get_unchecked_mut(..length) };
+
+            //  Safety:
+            //  -   `initialized` is valid for both reads and writes, and properly aligned.
+            //  -   `initialized` is accessible in exclusive mode, as `self` is the last instance.
+            //  -   The elements will not be used after this point.
+            unsafe { ptr::drop_in_place(initialized.as_ptr()) };
+        }
+
+        //  Safety:
+        //  -   `self.store` will never be used ever again.
+        let store = unsafe { ManuallyDrop::take(&mut self.store) };
+
+        //  Safety:
+        //  -   `self.handle` was allocated by a store sharing with `store`, and is still valid.
+        //  -   `capacity` is the length of its slice, as recorded at allocation.
+        //  -   `self.handle` is invalidated, and no other instance refers to it.
+        let _ = unsafe { self.handle.deallocate(capacity, &store) };
+    }
+}
+
+impl<T, S: Store> fmt::Debug for ConcurrentVecRef<T, S>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{:?}", self.as_slice())
+    }
+}
+
+impl<T, S: Store> ops::Deref for ConcurrentVecRef<T, S> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<'a, T, S: Store> IntoIterator for &'a ConcurrentVecRef<T, S> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
+//  Safety:
+//  -   Same as `Arc<ConcurrentVec<T>>`, with each instance owning its own part of the sharing set.
+unsafe impl<T, S> Send for ConcurrentVecRef<T, S>
+where
+    T: Send + Sync,
+    S: Store + Send,
+{
+}
+
+//  Safety:
+//  -   Same as `Arc<ConcurrentVec<T>>`: any instance may push, or drop the last elements.
+unsafe impl<T, S> Sync for ConcurrentVecRef<T, S>
+where
+    T: Send + Sync,
+    S: Store + Sync,
+{
+}
+
 //
 //  Implementation
 //
@@ -314,17 +651,209 @@ impl<T, S: Store> ConcurrentVec<T, S> {
     }
 }
 
+impl<T, S: Store> ConcurrentVecRef<T, S> {
+    //  Returns the header of the vector.
+    fn shared(&self) -> &Shared {
+        //  Safety:
+        //  -   `self.handle` was allocated by `self.store`, or a store sharing with it, and is still valid.
+        //  -   No mutable reference to the header is ever created.
+        //  -   The reference is bound to `self`, which keeps the vector alive.
+        unsafe { self.handle.resolve(&*self.store) }
+    }
+
+    //  Returns a pointer to the slots of the vector.
+    fn slots(&self) -> NonNull<[T]> {
+        let capacity = self.capacity();
+
+        let layout = SharedHandle::<T, S::Handle>::layout(capacity);
+
+        debug_assert!(layout.is_ok());
+
+        //  Safety:
+        //  -   The layout was computed successfully at allocation, with the same capacity.
+        let (_, offset) = unsafe { layout.unwrap_unchecked() };
+
+        //  Safety:
+        //  -   `self.handle` was allocated by `self.store`, or a store sharing with it, and is still valid.
+        let head = unsafe { self.handle.resolve_raw(&*self.store) };
+
+        //  Safety:
+        //  -   `offset` is within the block of memory, as per the layout it was allocated with.
+        let first = unsafe { head.cast::<u8>().add(offset) };
+
+        NonNull::slice_from_raw_parts(first.cast(), capacity)
+    }
+}
+
+//  The header of the block of memory of a `ConcurrentVecRef`.
+struct Shared {
+    //  Same invariants as `ConcurrentVec::length`.
+    length: Tracker,
+    //  Number of instances referring to the vector.
+    references: AtomicUsize,
+    //  Number of slots following the header.
+    capacity: usize,
+}
+
+type SharedHandle<T, H> = HeaderSliceHandle<Shared, MaybeUninit<T>, H>;
+
+//  The length of a concurrent vector, doubling as a lock.
+//
+//  The length is stored biased by 1, so that its negation is always negative: a negative length indicates that a
+//  thread is appending a new element, and holds the lock.
+//
+//  With the `epoch-check` feature, a separate epoch counter is incremented on each acquisition and release of the lock,
+//  and is therefore odd if and only if the lock is held. Comparing the epoch recorded at acquisition with the epoch
+//  at release detects any other acquisition in between, which the length alone cannot distinguish from no acquisition
+//  at all (ABA), and checking the length at release detects any modification of the length while locked.
+struct Tracker {
+    length: AtomicIsize,
+    #[cfg(feature = "epoch-check")]
+    epoch: AtomicUsize,
+}
+
+impl Tracker {
+    //  Creates an unlocked tracker, with the given length.
+    fn new(length: usize) -> Self {
+        let length = AtomicIsize::new(length as isize + 1);
+
+        #[cfg(feature = "epoch-check")]
+        let epoch = AtomicUsize::new(0);
+
+        Self {
+            length,
+            #[cfg(feature = "epoch-check")]
+            epoch,
+        }
+    }
+
+    //  Returns the length tracked.
+    fn len(&self) -> usize {
+        (self.length.load(Ordering::Acquire).abs() - 1) as usize
+    }
+
+    //  Records the acquisition of the lock, at (biased) `length`, returning the epoch of the acquisition.
+    #[cfg(feature = "epoch-check")]
+    fn on_lock(&self, length: isize) -> usize {
+        let previous = self.epoch.fetch_add(1, Ordering::AcqRel);
+
+        assert!(
+            previous & 1 == 0,
+            "Lock acquired at length {length} in epoch {previous}, while already locked"
+        );
+
+        previous + 1
+    }
+
+    #[cfg(not(feature = "epoch-check"))]
+    fn on_lock(&self, _length: isize) -> usize {
+        0
+    }
+
+    //  Records the release of the lock, acquired at (biased) `length` in `epoch`.
+    #[cfg(feature = "epoch-check")]
+    fn on_unlock(&self, length: isize, epoch: usize) {
+        let current = self.length.load(Ordering::Relaxed);
+
+        assert_eq!(-length, current, "Length modified while locked in epoch {epoch}");
+
+        let previous = self.epoch.fetch_add(1, Ordering::AcqRel);
+
+        assert_eq!(
+            epoch, previous,
+            "Lock acquired at length {length} in epoch {epoch}, re-acquired in between"
+        );
+    }
+
+    #[cfg(not(feature = "epoch-check"))]
+    fn on_unlock(&self, _length: isize, _epoch: usize) {}
+
+    //  Checks that the lock is not held, when accessed exclusively.
+    #[cfg(feature = "epoch-check")]
+    fn check_exclusive(&self) {
+        let length = self.length.load(Ordering::Acquire);
+        let epoch = self.epoch.load(Ordering::Acquire);
+
+        assert!(length > 0, "Length {length} locked, while accessed exclusively");
+        assert!(epoch & 1 == 0, "Epoch {epoch} locked, while accessed exclusively");
+    }
+
+    #[cfg(not(feature = "epoch-check"))]
+    fn check_exclusive(&self) {}
+}
+
+//  Attempts to push `element` into the first vacant slot of `slots`, as tracked by `tracker`.
+//
+//  #   Safety
+//
+//  -   `slots` must be valid for writes, for the duration of the call.
+//  -   `tracker` must track the initialized elements of `slots`, as per the invariants of `ConcurrentVec::length`.
+unsafe fn push_into<T>(tracker: &Tracker, slots: NonNull<[T]>, element: T) -> Result<(), T> {
+    let capacity = slots.len();
+    let mut length = tracker.length.load(Ordering::Acquire);
+
+    loop {
+        if length.unsigned_abs() > capacity {
+            return Err(element);
+        }
+
+        if length < 0 {
+            hint::spin_loop();
+
+            length = tracker.length.load(Ordering::Acquire);
+            continue;
+        }
+
+        debug_assert!(length > 0);
+
+        let result = tracker
+            .length
+            .compare_exchange_weak(length, -length, Ordering::Acquire, Ordering::Relaxed);
+
+        if let Err(prev) = result {
+            hint::spin_loop();
+
+            length = prev;
+            continue;
+        }
+
+        break;
+    }
+
+    let epoch = tracker.on_lock(length);
+
+    //  The slot at `length - 1` is ours!
+    debug_assert!(length > 0, "{length}");
+    debug_assert!(length.unsigned_abs() <= capacity, "{length} > {capacity}");
+
+    //  Safety:
+    //  -   `length - 1 < capacity`, since `length > 0` and `length <= capacity`.
+    let slot = unsafe { slots.get_unchecked_mut(length as usize - 1) };
+
+    //  Safety:
+    //  -   `slot` points to a valid area of memory.
+    //  -   `slot` points to a writeable area of memory, as per pre-conditions.
+    //  -   `slot` is accessible in exclusive mode, as per the lock on `tracker`.
+    unsafe { ptr::write(slot.as_ptr(), element) };
+
+    tracker.on_unlock(length, epoch);
+
+    tracker.length.store(length + 1, Ordering::Release);
+
+    Ok(())
+}
+
 struct Inner<T, S: Store> {
     store: S,
     handle: ManuallyDrop<UniqueHandle<[T], S::Handle>>,
 }
 
 impl<T, S: Store> Inner<T, S> {
-    //  Creates a store with a given capacity and store.
-    fn with_store(capacity: usize, store: S) -> Self {
-        let layout = Layout::array::<T>(capacity).expect("Small enough capacity");
+    //  Attempts to create a store with a given capacity and store.
+    fn try_with_store(capacity: usize, store: S) -> Result<Self, AllocError> {
+        let layout = Layout::array::<T>(capacity).map_err(|_| AllocError)?;
 
-        let (handle, _) = store.allocate(layout).expect("Successful allocation");
+        let (handle, _) = store.allocate(layout)?;
 
         //  Safety:
         //  -   `handle` is associated to a block of memory which fits `[T; capacity]`.
@@ -334,7 +863,7 @@ impl<T, S: Store> Inner<T, S> {
 
         let handle = ManuallyDrop::new(handle);
 
-        Self { store, handle }
+        Ok(Self { store, handle })
     }
 
     //  Returns the capacity of the store, in number of elements.
@@ -367,11 +896,15 @@ impl<T, S: Store> Drop for Inner<T, S> {
     }
 }
 
-#[cfg(test)]
+//  Loom atomics cannot be used outside of a loom model.
+#[cfg(all(test, not(loom)))]
 mod tests {
-    use std::{sync::Arc, thread};
+    use std::{rc::Rc, sync::Arc, thread};
 
-    use crate::collection::utils::Global;
+    use crate::{
+        collection::utils::Global,
+        store::{InlineBumpStore, RefStore, TrackingStore},
+    };
 
     use super::*;
 
@@ -386,6 +919,53 @@ mod tests {
         assert_eq!(42, empty.capacity());
     }
 
+    #[test]
+    fn try_with_store() {
+        type Store = InlineBumpStore<u16, [u64; 4]>;
+
+        let vec = ConcurrentVec::<u64, Store>::try_with_store(4, Store::default()).unwrap();
+
+        assert_eq!(4, vec.capacity());
+
+        assert!(ConcurrentVec::<u64, Store>::try_with_store(5, Store::default()).is_err());
+        assert!(ConcurrentVec::<u64, Store>::try_with_store(usize::MAX, Store::default()).is_err());
+        assert!(
+            ConcurrentVecRef::<u64, RefStore<Store>>::try_with_store(usize::MAX, RefStore::new(&Store::default()))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn comparison() {
+        let (one, mut two) = (GlobalVec::new(4), GlobalVec::new(2));
+
+        one.push(String::from("0")).unwrap();
+        two.push(String::from("1")).unwrap();
+
+        assert_eq!(r#"["0"]"#, format!("{one:?}"));
+        assert_ne!(one, two);
+        assert!(one < two);
+        assert_eq!(one, [String::from("0")]);
+
+        two.as_slice_mut()[0] = String::from("0");
+
+        assert_eq!(one, two);
+        assert_eq!(one.cmp(&two), cmp::Ordering::Equal);
+    }
+
+    #[test]
+    fn memory_usage() {
+        let vec = GlobalVec::new(4);
+
+        vec.push(String::from("0")).unwrap();
+
+        let usage = vec.memory_usage();
+
+        assert_eq!(4 * mem::size_of::<String>(), usage.bytes_allocated);
+        assert_eq!(mem::size_of::<String>(), usage.bytes_used);
+        assert_eq!(1, usage.allocations);
+    }
+
     #[test]
     fn brush() {
         let vec = GlobalVec::new(42);
@@ -411,6 +991,20 @@ mod tests {
         assert_eq!(Err(CAP.to_string()), result);
     }
 
+    #[test]
+    fn take() {
+        let mut vec = GlobalVec::new(2);
+
+        vec.push(String::from("0")).unwrap();
+
+        let taken = mem::take(&mut vec);
+
+        assert_eq!(&["0"][..], taken.as_slice());
+        assert!(vec.is_empty());
+        assert_eq!(0, vec.capacity());
+        assert_eq!(Err(String::from("1")), vec.push(String::from("1")));
+    }
+
     #[test]
     fn multithreaded() {
         const THREADS: usize = 4;
@@ -441,4 +1035,322 @@ mod tests {
 
         assert_eq!(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15][..], &elements);
     }
+
+    #[test]
+    fn shared_last_deallocates() {
+        let store = TrackingStore::<InlineBumpStore<u16, [u64; 32]>, 2>::new(InlineBumpStore::default());
+
+        let first = ConcurrentVecRef::with_store(4, RefStore::new(&store));
+        let second = first.clone();
+
+        first.push(String::from("0")).unwrap();
+        second.push(String::from("1")).unwrap();
+
+        assert_eq!(2, first.reference_count());
+        assert_eq!(&["0", "1"][..], second.as_slice());
+        assert_eq!(4, second.capacity());
+
+        drop(first);
+
+        assert_eq!(1, second.reference_count());
+        assert_eq!(1, store.len());
+
+        drop(second);
+
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn shared_last_drops_elements() {
+        let element = Rc::new(());
+        let store = TrackingStore::<InlineBumpStore<u16, [u64; 32]>, 2>::new(InlineBumpStore::default());
+
+        let first = ConcurrentVecRef::with_store(4, RefStore::new(&store));
+        let second = first.clone();
+
+        first.push(element.clone()).unwrap();
+        second.push(element.clone()).unwrap();
+
+        drop(first);
+
+        assert_eq!(3, Rc::strong_count(&element));
+
+        drop(second);
+
+        assert_eq!(1, Rc::strong_count(&element));
+        assert!(store.is_empty());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn shared_multithreaded() {
+        const THREADS: usize = 4;
+        const ELEMENTS: usize = 4;
+
+        let vec = ConcurrentVecRef::<String, Global>::new(THREADS * ELEMENTS);
+
+        let handles: Vec<_> = (0..THREADS)
+            .map(|i| {
+                let vec = vec.clone();
+
+                thread::spawn(move || {
+                    for k in 0..ELEMENTS {
+                        vec.push((i * ELEMENTS + k).to_string()).unwrap();
+                    }
+                })
+            })
+            .collect();
+
+        for h in handles {
+            h.join().unwrap();
+        }
+
+        assert_eq!(1, vec.reference_count());
+        assert_eq!(THREADS * ELEMENTS, vec.len());
+
+        let mut elements: Vec<usize> = vec.iter().map(|n| n.parse().unwrap()).collect();
+        elements.sort();
+
+        assert_eq!((0..THREADS * ELEMENTS).collect::<Vec<_>>(), elements);
+    }
 } // mod tests
+
+//  Checks that the epoch counter detects violations of the locking protocol.
+#[cfg(all(test, not(loom), feature = "epoch-check"))]
+mod tests_epoch {
+    use std::{
+        panic::{self, AssertUnwindSafe},
+        sync::Arc,
+        thread,
+    };
+
+    use crate::collection::utils::Global;
+
+    use super::*;
+
+    type GlobalVec = ConcurrentVec<usize, Global>;
+
+    //  Acquires the lock of `tracker`, as `push_into` would, returning the (biased) length and epoch.
+    fn lock(tracker: &Tracker) -> (isize, usize) {
+        let length = tracker.length.load(Ordering::Acquire);
+
+        tracker
+            .length
+            .compare_exchange(length, -length, Ordering::Acquire, Ordering::Relaxed)
+            .unwrap();
+
+        (length, tracker.on_lock(length))
+    }
+
+    fn panics<F: FnOnce()>(fun: F) -> bool {
+        panic::catch_unwind(AssertUnwindSafe(fun)).is_err()
+    }
+
+    #[test]
+    fn epoch_pushes() {
+        let mut vec = GlobalVec::new(4);
+
+        for i in 0..3 {
+            vec.push(i).unwrap();
+        }
+
+        assert_eq!(Err(4), vec.push(3).and_then(|_| vec.push(4)));
+        assert_eq!(8, vec.length.epoch.load(Ordering::Relaxed));
+
+        let clone = vec.clone();
+
+        assert_eq!(0, clone.length.epoch.load(Ordering::Relaxed));
+        assert_eq!([0, 1, 2, 3], vec.as_slice_mut());
+    }
+
+    #[test]
+    fn epoch_concurrent_pushes() {
+        const THREADS: usize = 4;
+        const ELEMENTS: usize = 64;
+
+        let vec = Arc::new(GlobalVec::new(THREADS * ELEMENTS));
+
+        let handles: Vec<_> = (0..THREADS)
+            .map(|_| {
+                let vec = vec.clone();
+
+                thread::spawn(move || (0..ELEMENTS).for_each(|i| vec.push(i).unwrap()))
+            })
+            .collect();
+
+        handles.into_iter().for_each(|handle| handle.join().unwrap());
+
+        assert_eq!(THREADS * ELEMENTS, vec.len());
+        assert_eq!(2 * THREADS * ELEMENTS, vec.length.epoch.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn epoch_double_lock() {
+        let tracker = Tracker::new(2);
+
+        let (length, _) = lock(&tracker);
+
+        assert!(panics(|| {
+            tracker.on_lock(length);
+        }));
+    }
+
+    #[test]
+    fn epoch_length_corrupted() {
+        let tracker = Tracker::new(2);
+
+        let (length, epoch) = lock(&tracker);
+
+        tracker.length.store(-length - 1, Ordering::Release);
+
+        assert!(panics(|| tracker.on_unlock(length, epoch)));
+    }
+
+    #[test]
+    fn epoch_aba() {
+        let tracker = Tracker::new(2);
+
+        let (length, epoch) = lock(&tracker);
+
+        //  Another release and acquisition, restoring the very same length.
+        tracker.epoch.fetch_add(2, Ordering::AcqRel);
+
+        assert!(panics(|| tracker.on_unlock(length, epoch)));
+    }
+
+    #[test]
+    fn epoch_exclusive_locked() {
+        let tracker = Tracker::new(2);
+
+        tracker.check_exclusive();
+
+        lock(&tracker);
+
+        assert!(panics(|| tracker.check_exclusive()));
+    }
+} // mod tests_epoch
+
+//  Run with `RUSTFLAGS="--cfg loom" cargo test --release tests_loom`.
+#[cfg(all(test, loom))]
+mod tests_loom {
+    use loom::{sync::Arc, thread};
+
+    use crate::collection::utils::Global;
+
+    use super::*;
+
+    type GlobalVec = ConcurrentVec<usize, Global>;
+
+    #[test]
+    fn concurrent_push() {
+        loom::model(|| {
+            let vec = Arc::new(GlobalVec::new(2));
+
+            let handle = {
+                let vec = vec.clone();
+
+                thread::spawn(move || vec.push(1).unwrap())
+            };
+
+            vec.push(0).unwrap();
+
+            handle.join().unwrap();
+
+            let mut elements = vec.as_slice().to_vec();
+            elements.sort();
+
+            assert_eq!(&[0, 1][..], &elements);
+        });
+    }
+
+    #[test]
+    fn concurrent_push_len() {
+        loom::model(|| {
+            let vec = Arc::new(GlobalVec::new(2));
+
+            let handle = {
+                let vec = vec.clone();
+
+                thread::spawn(move || vec.push(1).unwrap())
+            };
+
+            //  Whether or not the push is observed, only initialized elements may be observed.
+            let len = vec.len();
+            let elements = vec.as_slice();
+
+            assert!(len <= 1);
+            assert!(elements.iter().all(|e| *e == 1));
+
+            handle.join().unwrap();
+
+            assert_eq!(1, vec.len());
+        });
+    }
+
+    #[test]
+    fn concurrent_push_overflow() {
+        loom::model(|| {
+            let vec = Arc::new(GlobalVec::new(1));
+
+            let handle = {
+                let vec = vec.clone();
+
+                thread::spawn(move || vec.push(1).is_ok())
+            };
+
+            let pushed = vec.push(0).is_ok();
+            let other_pushed = handle.join().unwrap();
+
+            assert!(pushed != other_pushed);
+            assert_eq!(1, vec.len());
+        });
+    }
+} // mod tests_loom
+
+//  Exercises the raw access paths of the vector: references to the elements are kept alive whilst further elements are
+//  pushed, through the same instance and through a clone sharing the block of memory.
+//
+//  Run with `RUSTFLAGS="--cfg storage_miri_strict" MIRIFLAGS="-Zmiri-tree-borrows" cargo miri test tests_miri`, and
+//  without `-Zmiri-tree-borrows` for Stacked Borrows.
+#[cfg(all(test, not(loom), storage_miri_strict))]
+mod tests_miri {
+    use crate::{
+        collection::utils::Global,
+        store::{InlineBumpStore, RefStore},
+    };
+
+    use super::*;
+
+    #[test]
+    fn push_with_live_slice() {
+        let vec = ConcurrentVec::<String, Global>::new(4);
+
+        vec.push(String::from("0")).unwrap();
+
+        let (slice, first) = (vec.as_slice(), &vec.as_slice()[0]);
+
+        vec.push(String::from("1")).unwrap();
+        vec.push(String::from("2")).unwrap();
+
+        assert_eq!(["0"], slice);
+        assert_eq!("0", first);
+        assert_eq!(["0", "1", "2"], vec.as_slice());
+    }
+
+    #[test]
+    fn push_through_clone() {
+        let store = InlineBumpStore::<u16, [u64; 32]>::default();
+
+        let vec = ConcurrentVecRef::<String, RefStore<'_, _>>::with_store(4, RefStore::new(&store));
+        let clone = vec.try_clone().unwrap();
+
+        vec.push(String::from("0")).unwrap();
+
+        let first = &vec.as_slice()[0];
+
+        clone.push(String::from("1")).unwrap();
+
+        assert_eq!("0", first);
+        assert_eq!(["0", "1"], vec.as_slice());
+    }
+} // mod tests_miri
@@ -0,0 +1,716 @@
+//! Proof of concept gap buffer.
+//!
+//! A `GapBuffer<T, S>` stores its elements in a single array, split in two by a gap of uninitialized slots located at
+//! the cursor. Inserting or removing elements at the cursor only moves the boundaries of the gap, whilst moving the
+//! cursor moves the elements between its old and new positions across the gap. This is the classic representation of
+//! the text of an editor, in which edits are clustered around the cursor.
+//!
+//! When the gap is exhausted, the array is grown in place, if the store supports it, and the elements after the gap
+//! are moved to the end of the grown array, so that the new slots all end up in the gap.
+
+use core::{
+    alloc::{AllocError, Layout},
+    fmt, iter,
+    ptr::{self, NonNull},
+    slice,
+};
+
+use crate::{
+    alloc,
+    extension::unique::UniqueHandle,
+    interface::{Growth, Store},
+};
+
+/// A gap buffer, for insertions and removals at a cursor.
+pub struct GapBuffer<T, S: Store> {
+    //  Type invariants:
+    //  -   `self.gap_start <= self.gap_end <= self.handle.len()`.
+    //  -   Slots in `0..self.gap_start` and `self.gap_end..self.handle.len()` are initialized, all others are not.
+    //  -   `self.handle` is dangling if its length is 0, and is otherwise associated to a block of `self.store`.
+    gap_start: usize,
+    gap_end: usize,
+    handle: UniqueHandle<[T], S::Handle>,
+    store: S,
+}
+
+impl<T, S: Store + Default> GapBuffer<T, S> {
+    /// Creates a new, empty, instance.
+    pub fn new() -> Self {
+        Self::new_in(S::default())
+    }
+
+    /// Creates a new, empty, instance with at least the specified capacity.
+    ///
+    /// Calls `handle_alloc_error` if the store cannot accommodate `capacity` elements.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_in(capacity, S::default())
+    }
+}
+
+impl<T, S: Store> GapBuffer<T, S> {
+    /// Creates a new, empty, instance with the specified `store`.
+    pub fn new_in(store: S) -> Self {
+        let handle = UniqueHandle::dangling_slice(&store);
+
+        Self {
+            gap_start: 0,
+            gap_end: 0,
+            handle,
+            store,
+        }
+    }
+
+    /// Creates a new, empty, instance with at least the specified `capacity`, and the specified `store`.
+    ///
+    /// Calls `handle_alloc_error` if the store cannot accommodate `capacity` elements.
+    pub fn with_capacity_in(capacity: usize, store: S) -> Self {
+        let mut this = Self::new_in(store);
+
+        this.reserve(capacity);
+
+        this
+    }
+
+    /// Returns whether the buffer is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of elements, on both sides of the gap.
+    pub const fn len(&self) -> usize {
+        self.handle.len() - self.gap_len()
+    }
+
+    /// Returns the capacity, in number of elements.
+    pub const fn capacity(&self) -> usize {
+        self.handle.len()
+    }
+
+    /// Returns the position of the cursor, that is the number of elements before the gap.
+    pub const fn cursor(&self) -> usize {
+        self.gap_start
+    }
+
+    /// Moves the cursor to `position`, moving the elements in between across the gap.
+    ///
+    /// This operation is O(n) in the distance between the current and new positions of the cursor.
+    ///
+    /// #   Panics
+    ///
+    /// Panics if `position > self.len()`.
+    pub fn set_cursor(&mut self, position: usize) {
+        assert!(
+            position <= self.len(),
+            "cursor position {position} should be <= len {}",
+            self.len()
+        );
+
+        let gap = self.gap_len();
+
+        if position == self.gap_start || gap == 0 {
+            self.gap_start = position;
+            self.gap_end = position + gap;
+            return;
+        }
+
+        let base = self.slots().as_mut_ptr();
+
+        //  Safety:
+        //  -   `base` is valid for reads and writes of `self.capacity()` elements.
+        //  -   All offsets are within `0..=self.capacity()`, as `position <= self.len()`.
+        //  -   The moved elements are initialized, and land in slots of the gap, or of the elements previously moved.
+        unsafe {
+            if position < self.gap_start {
+                let count = self.gap_start - position;
+
+                ptr::copy(base.add(position), base.add(position + gap), count);
+            } else {
+                let count = position - self.gap_start;
+
+                ptr::copy(base.add(self.gap_end), base.add(self.gap_start), count);
+            }
+        }
+
+        self.gap_start = position;
+        self.gap_end = position + gap;
+    }
+
+    /// Returns the element at `index`, if any, skipping over the gap.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        let (before, after) = self.as_slices();
+
+        match index.checked_sub(before.len()) {
+            None => before.get(index),
+            Some(index) => after.get(index),
+        }
+    }
+
+    /// Returns the element at `index`, if any, skipping over the gap.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        let (before, after) = self.as_mut_slices();
+
+        match index.checked_sub(before.len()) {
+            None => before.get_mut(index),
+            Some(index) => after.get_mut(index),
+        }
+    }
+
+    /// Returns the elements before the cursor.
+    pub fn before(&self) -> &[T] {
+        self.as_slices().0
+    }
+
+    /// Returns the elements after the cursor.
+    pub fn after(&self) -> &[T] {
+        self.as_slices().1
+    }
+
+    /// Returns the elements before, and after, the cursor.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let base = self.slots().as_mut_ptr();
+
+        //  Safety:
+        //  -   `base` is valid for reads of `self.capacity()` elements, for as long as `self` is borrowed.
+        //  -   The elements in `0..self.gap_start` and `self.gap_end..self.capacity()` are initialized.
+        unsafe {
+            (
+                slice::from_raw_parts(base, self.gap_start),
+                slice::from_raw_parts(base.add(self.gap_end), self.capacity() - self.gap_end),
+            )
+        }
+    }
+
+    /// Returns the elements before, and after, the cursor.
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let base = self.slots().as_mut_ptr();
+
+        //  Safety:
+        //  -   `base` is valid for reads and writes of `self.capacity()` elements, for as long as `self` is borrowed.
+        //  -   The elements in `0..self.gap_start` and `self.gap_end..self.capacity()` are initialized.
+        //  -   The two slices are disjoint, as `self.gap_start <= self.gap_end`.
+        unsafe {
+            (
+                slice::from_raw_parts_mut(base, self.gap_start),
+                slice::from_raw_parts_mut(base.add(self.gap_end), self.capacity() - self.gap_end),
+            )
+        }
+    }
+
+    /// Moves the cursor to the end, and returns all the elements, now contiguous.
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        self.set_cursor(self.len());
+
+        self.as_mut_slices().0
+    }
+
+    /// Returns an iterator over the elements, skipping over the gap.
+    pub fn iter(&self) -> iter::Chain<slice::Iter<'_, T>, slice::Iter<'_, T>> {
+        let (before, after) = self.as_slices();
+
+        before.iter().chain(after)
+    }
+
+    /// Reserves capacity for at least `additional` more elements.
+    ///
+    /// #   Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    ///
+    /// Calls `handle_alloc_error` if the store fails to provide the new capacity.
+    pub fn reserve(&mut self, additional: usize) {
+        let Some(target_capacity) = self.len().checked_add(additional) else {
+            Self::capacity_exceeded()
+        };
+
+        if target_capacity <= self.capacity() {
+            return;
+        }
+
+        let Some(grown_capacity) = S::GROWTH.capacity(self.capacity(), target_capacity) else {
+            Self::capacity_exceeded()
+        };
+
+        let Ok(layout) = Layout::array::<T>(grown_capacity) else {
+            Self::capacity_exceeded()
+        };
+
+        //  Safety:
+        //  -   `grown_capacity` is greater than `self.capacity()`.
+        if unsafe { self.grow_to(grown_capacity) }.is_err() {
+            alloc::handle_alloc_error(layout)
+        }
+    }
+
+    /// Attempts to reserve capacity for at least `additional` more elements.
+    ///
+    /// Returns an error if the new capacity exceeds `isize::MAX` bytes, or if the store fails to provide it.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), AllocError> {
+        let target_capacity = self.len().checked_add(additional).ok_or(AllocError)?;
+
+        if target_capacity <= self.capacity() {
+            return Ok(());
+        }
+
+        if let Some(grown_capacity) = S::GROWTH.capacity(self.capacity(), target_capacity) {
+            //  Safety:
+            //  -   `grown_capacity` is greater than or equal to `target_capacity`, itself greater than
+            //      `self.capacity()`.
+            if unsafe { self.grow_to(grown_capacity) }.is_ok() {
+                return Ok(());
+            }
+        }
+
+        //  The store may still be able to accommodate the exact capacity, notably if of fixed capacity.
+
+        //  Safety:
+        //  -   `target_capacity` is greater than `self.capacity()`.
+        unsafe { self.grow_to(target_capacity) }
+    }
+
+    /// Inserts an element at the cursor, and moves the cursor after it.
+    ///
+    /// #   Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    ///
+    /// Calls `handle_alloc_error` if the store fails to provide the new capacity.
+    pub fn insert(&mut self, value: T) {
+        if self.gap_len() == 0 && self.try_grow_for_insert().is_err() {
+            let Ok(layout) = Layout::array::<T>(self.len().saturating_add(1)) else {
+                Self::capacity_exceeded()
+            };
+
+            alloc::handle_alloc_error(layout)
+        }
+
+        //  Safety:
+        //  -   The gap is not empty, after reserving.
+        unsafe { self.insert_unchecked(value) };
+    }
+
+    /// Attempts to insert an element at the cursor, and move the cursor after it.
+    ///
+    /// Returns the element if the store fails to provide the new capacity.
+    pub fn try_insert(&mut self, value: T) -> Result<(), T> {
+        if self.gap_len() == 0 && self.try_grow_for_insert().is_err() {
+            return Err(value);
+        }
+
+        //  Safety:
+        //  -   The gap is not empty, after reserving.
+        unsafe { self.insert_unchecked(value) };
+
+        Ok(())
+    }
+
+    /// Removes the element before the cursor, if any, moving the cursor back.
+    pub fn remove_before(&mut self) -> Option<T> {
+        if self.gap_start == 0 {
+            return None;
+        }
+
+        self.gap_start -= 1;
+
+        //  Safety:
+        //  -   `self.gap_start < self.capacity()`.
+        //  -   The element at `self.gap_start` was initialized, and is now part of the gap.
+        Some(unsafe { ptr::read(self.slots().as_mut_ptr().add(self.gap_start)) })
+    }
+
+    /// Removes the element after the cursor, if any.
+    pub fn remove_after(&mut self) -> Option<T> {
+        if self.gap_end == self.capacity() {
+            return None;
+        }
+
+        self.gap_end += 1;
+
+        //  Safety:
+        //  -   `self.gap_end - 1 < self.capacity()`.
+        //  -   The element at `self.gap_end - 1` was initialized, and is now part of the gap.
+        Some(unsafe { ptr::read(self.slots().as_mut_ptr().add(self.gap_end - 1)) })
+    }
+
+    /// Clears the buffer, dropping all its elements, and moving the cursor to the start.
+    pub fn clear(&mut self) {
+        let (before, after) = self.as_mut_slices();
+
+        let (before, after): (*mut [T], *mut [T]) = (before, after);
+
+        //  The elements are considered dropped even if dropping one of them panics, leaking the others.
+        self.gap_start = 0;
+        self.gap_end = self.capacity();
+
+        //  Safety:
+        //  -   `before` and `after` are valid for reads and writes, and their elements are initialized.
+        //  -   The elements are no longer considered initialized, and will not be accessed again.
+        unsafe {
+            ptr::drop_in_place(before);
+            ptr::drop_in_place(after);
+        }
+    }
+}
+
+impl<T, S: Store + Default> Default for GapBuffer<T, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, S: Store> Drop for GapBuffer<T, S> {
+    fn drop(&mut self) {
+        self.clear();
+
+        if self.capacity() == 0 {
+            return;
+        }
+
+        //  Safety:
+        //  -   `self.handle` will not be used after this point.
+        let handle = unsafe { ptr::read(&self.handle) };
+
+        //  Safety:
+        //  -   `handle` was allocated by `self.store`, and is still valid, as its length is non-zero.
+        unsafe { handle.deallocate(&self.store) };
+    }
+}
+
+impl<T: fmt::Debug, S: Store> fmt::Debug for GapBuffer<T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Inserts the elements at the cursor, in order, reserving capacity for the lower bound of the size hint upfront.
+impl<T, S: Store> Extend<T> for GapBuffer<T, S> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let iter = iter.into_iter();
+
+        let (lower, _) = iter.size_hint();
+
+        self.reserve(lower);
+
+        for element in iter {
+            self.insert(element);
+        }
+    }
+}
+
+impl<T, S: Store + Default> FromIterator<T> for GapBuffer<T, S> {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut this = Self::new();
+
+        this.extend(iter);
+
+        this
+    }
+}
+
+impl<'a, T, S: Store> IntoIterator for &'a GapBuffer<T, S> {
+    type Item = &'a T;
+    type IntoIter = iter::Chain<slice::Iter<'a, T>, slice::Iter<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+//
+//  Implementation
+//
+
+impl<T, S: Store> GapBuffer<T, S> {
+    const fn gap_len(&self) -> usize {
+        self.gap_end - self.gap_start
+    }
+
+    //  Resolves the array, gap included.
+    //
+    //  The pointer is only valid until the next call to the store.
+    fn slots(&self) -> NonNull<[T]> {
+        //  Safety:
+        //  -   `self.handle` is dangling, or was allocated by `self.store` and is still valid.
+        unsafe { self.handle.resolve_raw(&self.store) }
+    }
+
+    //  Inserts an element at the cursor.
+    //
+    //  #   Safety
+    //
+    //  -   The gap must not be empty.
+    unsafe fn insert_unchecked(&mut self, value: T) {
+        debug_assert!(self.gap_len() > 0);
+
+        //  Safety:
+        //  -   `self.gap_start < self.gap_end <= self.capacity()`, as the gap is not empty.
+        //  -   The slot at `self.gap_start` is part of the gap, hence not initialized.
+        unsafe { ptr::write(self.slots().as_mut_ptr().add(self.gap_start), value) };
+
+        self.gap_start += 1;
+    }
+
+    //  Grows the array for the insertion of a single element, the gap being empty.
+    //
+    //  Each growth moves the elements after the gap, hence growing by a single element at a time, as `Growth::Exact`
+    //  would, makes a sequence of insertions quadratic. The growth is thus geometric, whatever the hint of the store,
+    //  falling back to a single element only if the store cannot accommodate more, notably if of fixed capacity.
+    fn try_grow_for_insert(&mut self) -> Result<(), AllocError> {
+        debug_assert_eq!(0, self.gap_len());
+
+        let required = self.len().checked_add(1).ok_or(AllocError)?;
+
+        if let Some(grown_capacity) = Growth::Amortized.capacity(self.capacity(), required) {
+            //  Safety:
+            //  -   `grown_capacity` is greater than or equal to `required`, itself greater than `self.capacity()` as
+            //      the gap is empty.
+            if unsafe { self.grow_to(grown_capacity) }.is_ok() {
+                return Ok(());
+            }
+        }
+
+        //  Safety:
+        //  -   `required` is greater than `self.capacity()`, as the gap is empty.
+        unsafe { self.grow_to(required) }
+    }
+
+    //  Grows the array to `capacity`, then moves the elements after the gap to the end of the array.
+    //
+    //  On failure, the buffer is left untouched.
+    //
+    //  #   Safety
+    //
+    //  -   `capacity` must be greater than `self.capacity()`.
+    unsafe fn grow_to(&mut self, capacity: usize) -> Result<(), AllocError> {
+        debug_assert!(capacity > self.capacity());
+
+        if Layout::array::<T>(capacity).is_err() {
+            return Err(AllocError);
+        }
+
+        let old_capacity = self.capacity();
+
+        if old_capacity == 0 {
+            self.handle = UniqueHandle::try_allocate_slice(capacity, &self.store)?;
+        } else {
+            //  Safety:
+            //  -   `self.handle` was allocated by `self.store`, and is still valid, as its length is non-zero.
+            //  -   `capacity` is greater than `self.handle.len()`, as per pre-conditions.
+            unsafe { self.handle.try_grow(capacity, &self.store)? };
+        }
+
+        let after = old_capacity - self.gap_end;
+        let base = self.slots().as_mut_ptr();
+
+        //  Safety:
+        //  -   `base` is valid for reads and writes of `capacity` elements.
+        //  -   The `after` elements from `self.gap_end` are initialized, and moved to the last `after` slots.
+        unsafe { ptr::copy(base.add(self.gap_end), base.add(capacity - after), after) };
+
+        self.gap_end = capacity - after;
+
+        Ok(())
+    }
+
+    #[inline(never)]
+    fn capacity_exceeded() -> ! {
+        panic!("New capacity exceeds isize::MAX bytes")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{
+        cell::Cell,
+        ptr::{Alignment, NonNull},
+    };
+
+    use crate::{
+        collection::utils::Global,
+        interface::StoreDangling,
+        store::{InlineBumpStore, RefStore, TrackingStore},
+    };
+
+    use super::*;
+
+    fn text<S: Store>(buffer: &GapBuffer<u8, S>) -> String {
+        String::from_utf8(buffer.iter().copied().collect()).unwrap()
+    }
+
+    #[test]
+    fn edit() {
+        let mut buffer = GapBuffer::<u8, Global>::new();
+
+        assert!(buffer.is_empty());
+        assert_eq!(None, buffer.remove_before());
+        assert_eq!(None, buffer.remove_after());
+
+        buffer.extend(*b"Hello world");
+
+        assert_eq!(11, buffer.cursor());
+
+        buffer.set_cursor(5);
+        buffer.insert(b',');
+
+        assert_eq!(b"Hello,", buffer.before());
+        assert_eq!(b" world", buffer.after());
+
+        buffer.set_cursor(buffer.len());
+        buffer.insert(b'!');
+
+        assert_eq!("Hello, world!", text(&buffer));
+
+        buffer.set_cursor(7);
+
+        assert_eq!(Some(b'w'), buffer.remove_after());
+        assert_eq!(Some(b' '), buffer.remove_before());
+
+        buffer.insert(b'W');
+
+        assert_eq!("Hello,World!", text(&buffer));
+        assert_eq!(Some(&b'W'), buffer.get(6));
+        assert_eq!(Some(&b'o'), buffer.get(7));
+        assert_eq!(None, buffer.get(12));
+
+        *buffer.get_mut(11).unwrap() = b'?';
+
+        assert_eq!(b"Hello,World?", buffer.make_contiguous());
+        assert_eq!(12, buffer.cursor());
+    }
+
+    #[test]
+    fn grow_around_gap() {
+        let mut buffer: GapBuffer<String, Global> = (0..4).map(|n| n.to_string()).collect();
+
+        buffer.set_cursor(1);
+
+        //  Grows several times, with elements on both sides of the gap.
+        (4..20).for_each(|n| buffer.insert(n.to_string()));
+
+        assert_eq!(20, buffer.len());
+        assert_eq!(17, buffer.cursor());
+        assert_eq!(["1", "2", "3"], buffer.after());
+        assert!(buffer.iter().take(2).map(String::as_str).eq(["0", "4"]));
+
+        buffer.clear();
+
+        assert!(buffer.is_empty());
+        assert_eq!(0, buffer.cursor());
+        assert_eq!("[]", format!("{buffer:?}"));
+    }
+
+    #[test]
+    fn fixed_capacity() {
+        let mut buffer = GapBuffer::<u32, InlineBumpStore<u16, [u32; 8]>>::new();
+
+        let mut inserted = 0;
+
+        while buffer.try_insert(inserted).is_ok() {
+            buffer.set_cursor(0);
+            inserted += 1;
+        }
+
+        assert!(inserted > 0);
+        assert_eq!(inserted as usize, buffer.len());
+        assert!(buffer.iter().copied().eq((0..inserted).rev()));
+    }
+
+    #[test]
+    fn geometric_growth() {
+        //  A store hinting at exact growth, counting its allocations.
+        #[derive(Default)]
+        struct ExactStore(Cell<usize>);
+
+        unsafe impl StoreDangling for ExactStore {
+            type Handle = <Global as StoreDangling>::Handle;
+
+            fn dangling(&self, alignment: Alignment) -> Result<Self::Handle, AllocError> {
+                Global.dangling(alignment)
+            }
+        }
+
+        unsafe impl Store for ExactStore {
+            const GROWTH: Growth = Growth::Exact;
+
+            unsafe fn resolve(&self, handle: Self::Handle) -> NonNull<u8> {
+                //  Safety:
+                //  -   As per pre-conditions.
+                unsafe { Global.resolve(handle) }
+            }
+
+            fn allocate(&self, layout: Layout) -> Result<(Self::Handle, usize), AllocError> {
+                self.0.set(self.0.get() + 1);
+
+                Global.allocate(layout)
+            }
+
+            unsafe fn deallocate(&self, handle: Self::Handle, layout: Layout) {
+                //  Safety:
+                //  -   As per pre-conditions.
+                unsafe { Global.deallocate(handle, layout) }
+            }
+        }
+
+        let mut buffer = GapBuffer::<u32, ExactStore>::new();
+
+        for n in 0..64 {
+            buffer.set_cursor(0);
+            buffer.insert(n);
+        }
+
+        assert!(buffer.iter().copied().eq((0..64).rev()));
+
+        //  Growing one element at a time would have allocated 64 times.
+        assert!(buffer.store.0.get() <= 12, "{} allocations", buffer.store.0.get());
+    }
+
+    #[test]
+    fn single_block() {
+        let store = TrackingStore::<Global, 8>::new(Global);
+
+        {
+            let mut buffer = GapBuffer::<u8, _>::with_capacity_in(4, RefStore::new(&store));
+
+            buffer.extend(*b"abc");
+            buffer.set_cursor(1);
+            buffer.extend(*b"0123456789");
+
+            assert_eq!(1, store.len());
+            assert_eq!("a0123456789bc", text(&buffer));
+        }
+
+        assert_eq!(0, store.len());
+    }
+
+    #[test]
+    fn drops() {
+        struct Noisy<'a>(&'a Cell<u32>);
+
+        impl Drop for Noisy<'_> {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        let drops = Cell::new(0);
+
+        {
+            let mut buffer = GapBuffer::<Noisy<'_>, Global>::new();
+
+            (0..6).for_each(|_| buffer.insert(Noisy(&drops)));
+
+            buffer.set_cursor(3);
+
+            drop(buffer.remove_after());
+
+            assert_eq!(1, drops.get());
+        }
+
+        assert_eq!(6, drops.get());
+    }
+} // mod tests
@@ -0,0 +1,400 @@
+//! An open-addressing hash table, the building block of the hashed collections.
+//!
+//! The table uses linear probing, and marks removed elements with tombstones so as not to break probe sequences.
+//!
+//! Tombstones are purged by rehashing the table in place, which never requires any additional memory. This matters for
+//! stores whose `deallocate` is a no-op, such as the bump stores: purging by reallocating the table would leak the
+//! previous block of memory every time.
+
+use core::{
+    alloc::{AllocError, Layout},
+    mem, ptr, slice,
+};
+
+use crate::{alloc, extension::unique_single::UniqueSingleHandle, interface::StoreSingle};
+
+//  A bucket of the table.
+pub(crate) enum Bucket<T> {
+    //  The bucket has not held any element since the last rehash.
+    Empty,
+    //  The bucket held an element, which was since removed.
+    Deleted,
+    //  The bucket holds an element.
+    Full(T),
+    //  The bucket holds an element which is not yet at its place, only used during in-place rehashing.
+    Pending(T),
+}
+
+//  The raw hash table, the hashing and comparison of elements are left to the caller.
+pub(crate) struct RawTable<T, S: StoreSingle> {
+    //  Type invariant:
+    //  -   `self.buckets.len()` is either 0, in which case `self.buckets` is dangling, or at least `MINIMUM_BUCKETS`.
+    //  -   All buckets are initialized, and none is `Pending`.
+    //  -   `self.length` is the number of `Full` buckets, and `self.deleted` the number of `Deleted` buckets.
+    //  -   `self.length + self.deleted` is at most `maximum_load(self.buckets.len())`.
+    length: usize,
+    deleted: usize,
+    buckets: UniqueSingleHandle<[Bucket<T>], S::Handle>,
+    store: S,
+}
+
+impl<T, S: StoreSingle> RawTable<T, S> {
+    //  Creates a new, empty, table.
+    pub(crate) fn new_in(store: S) -> Self {
+        let buckets = UniqueSingleHandle::dangling_slice(&store);
+
+        Self {
+            length: 0,
+            deleted: 0,
+            buckets,
+            store,
+        }
+    }
+
+    //  Returns the number of elements in the table.
+    pub(crate) const fn len(&self) -> usize {
+        self.length
+    }
+
+    //  Returns the number of elements the table can hold without reallocating.
+    pub(crate) const fn capacity(&self) -> usize {
+        maximum_load(self.buckets.len())
+    }
+
+    //  Returns an iterator over the elements of the table.
+    pub(crate) fn iter(&self) -> RawIter<'_, T> {
+        RawIter {
+            buckets: self.buckets().iter(),
+            remaining: self.length,
+        }
+    }
+
+    //  Returns the index of the bucket containing the element with `hash`, for which `eq` returns true, if any.
+    pub(crate) fn find<F>(&self, hash: u64, mut eq: F) -> Option<usize>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let buckets = self.buckets();
+
+        for index in Self::probe(hash, buckets.len()) {
+            match &buckets[index] {
+                Bucket::Empty => return None,
+                Bucket::Full(element) if eq(element) => return Some(index),
+                _ => continue,
+            }
+        }
+
+        None
+    }
+
+    //  Returns a reference to the element at `index`.
+    //
+    //  #   Panics
+    //
+    //  If the bucket at `index` is not full.
+    pub(crate) fn get(&self, index: usize) -> &T {
+        let Bucket::Full(element) = &self.buckets()[index] else {
+            panic!("Bucket {index} should be full")
+        };
+
+        element
+    }
+
+    //  Inserts `element` with `hash` in the table, `hasher` computing the hash of the elements already present.
+    //
+    //  The caller is responsible for ensuring that no element comparing equal to `element` is already present.
+    pub(crate) fn try_insert<H>(&mut self, hash: u64, element: T, hasher: H) -> Result<usize, AllocError>
+    where
+        H: Fn(&T) -> u64,
+    {
+        self.try_reserve(1, hasher)?;
+
+        let buckets = self.buckets_mut();
+
+        let Some(index) = Self::probe(hash, buckets.len()).find(|index| !matches!(buckets[*index], Bucket::Full(_)))
+        else {
+            unreachable!("There should be a non-full bucket after reserving");
+        };
+
+        if let Bucket::Deleted = mem::replace(&mut buckets[index], Bucket::Full(element)) {
+            self.deleted -= 1;
+        }
+
+        self.length += 1;
+
+        Ok(index)
+    }
+
+    //  Removes the element at `index` from the table.
+    //
+    //  #   Panics
+    //
+    //  If the bucket at `index` is not full.
+    pub(crate) fn remove(&mut self, index: usize) -> T {
+        let buckets = self.buckets_mut();
+
+        //  If the next bucket is empty, no probe sequence goes through this bucket, and thus no tombstone is necessary.
+        let next = (index + 1) % buckets.len();
+
+        let tombstone = if let Bucket::Empty = buckets[next] {
+            Bucket::Empty
+        } else {
+            Bucket::Deleted
+        };
+
+        let is_deleted = matches!(tombstone, Bucket::Deleted);
+
+        let Bucket::Full(element) = mem::replace(&mut buckets[index], tombstone) else {
+            panic!("Bucket {index} should be full")
+        };
+
+        self.length -= 1;
+        self.deleted += usize::from(is_deleted);
+
+        element
+    }
+
+    //  Removes all the elements of the table, keeping the allocated memory.
+    pub(crate) fn clear(&mut self) {
+        self.buckets_mut().fill_with(|| Bucket::Empty);
+
+        self.length = 0;
+        self.deleted = 0;
+    }
+
+    //  Reserves room for at least `additional` more elements, `hasher` is used to compute the hash of the elements.
+    pub(crate) fn reserve<H>(&mut self, additional: usize, hasher: H)
+    where
+        H: Fn(&T) -> u64,
+    {
+        if self.try_reserve(additional, hasher).is_err() {
+            alloc::handle_alloc_error(Layout::new::<Bucket<T>>())
+        }
+    }
+
+    //  Attempts to reserve room for at least `additional` more elements, `hasher` is used to compute the hash of the
+    //  elements.
+    pub(crate) fn try_reserve<H>(&mut self, additional: usize, hasher: H) -> Result<(), AllocError>
+    where
+        H: Fn(&T) -> u64,
+    {
+        let required = self.length.checked_add(additional).ok_or(AllocError)?;
+
+        if required <= self.capacity() {
+            //  Purge the tombstones if they would prevent the insertion.
+            if required + self.deleted > self.capacity() {
+                self.rehash_in_place(hasher);
+            }
+
+            return Ok(());
+        }
+
+        let mut number_buckets = self.buckets.len().max(MINIMUM_BUCKETS);
+
+        while maximum_load(number_buckets) < required {
+            number_buckets = number_buckets.checked_mul(2).ok_or(AllocError)?;
+        }
+
+        self.grow_to(number_buckets, hasher)
+    }
+}
+
+impl<T, S: StoreSingle> Drop for RawTable<T, S> {
+    fn drop(&mut self) {
+        if self.buckets.is_empty() {
+            return;
+        }
+
+        //  Safety:
+        //  -   `self.buckets` is valid, and all buckets are initialized, as per type invariant.
+        //  -   The buckets will not be used after this point.
+        unsafe { ptr::drop_in_place(self.buckets_mut()) };
+
+        //  Safety:
+        //  -   `self.buckets` is valid.
+        //  -   `self.buckets` will not be used after this point.
+        let buckets = unsafe { ptr::read(&self.buckets) };
+
+        //  Safety:
+        //  -   `buckets` is still valid, notably it is not dangling since its length is non-zero.
+        //  -   `buckets` was allocated by `self.store`.
+        unsafe { buckets.deallocate(&mut self.store) };
+    }
+}
+
+//  An iterator over the elements of a table.
+pub(crate) struct RawIter<'a, T> {
+    buckets: slice::Iter<'a, Bucket<T>>,
+    remaining: usize,
+}
+
+impl<'a, T> Iterator for RawIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let element = self.buckets.find_map(|bucket| match bucket {
+            Bucket::Full(element) => Some(element),
+            _ => None,
+        })?;
+
+        self.remaining -= 1;
+
+        Some(element)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T> Clone for RawIter<'a, T> {
+    fn clone(&self) -> Self {
+        Self {
+            buckets: self.buckets.clone(),
+            remaining: self.remaining,
+        }
+    }
+}
+
+//
+//  Implementation
+//
+
+const MINIMUM_BUCKETS: usize = 8;
+
+//  Returns the maximum number of elements and tombstones, for a given number of buckets.
+//
+//  Since there are at least `MINIMUM_BUCKETS` buckets once allocated, at least one bucket is always left empty, which
+//  guarantees that probing terminates.
+const fn maximum_load(number_buckets: usize) -> usize {
+    number_buckets - number_buckets / 8
+}
+
+//  Returns the number of buckets allocated by a table reserving `capacity` elements from scratch.
+//
+//  Panics if the number of buckets overflows.
+pub(crate) const fn buckets_for(capacity: usize) -> usize {
+    let mut number_buckets = MINIMUM_BUCKETS;
+
+    while maximum_load(number_buckets) < capacity {
+        number_buckets = match number_buckets.checked_mul(2) {
+            Some(number_buckets) => number_buckets,
+            None => panic!("Capacity overflow"),
+        };
+    }
+
+    number_buckets
+}
+
+impl<T, S: StoreSingle> RawTable<T, S> {
+    //  Returns the sequence of indexes to probe, for a given hash.
+    fn probe(hash: u64, number_buckets: usize) -> impl Iterator<Item = usize> {
+        //  Truncation is fine, the hash is reduced modulo the number of buckets anyway.
+        let start = hash as usize;
+
+        (0..number_buckets).map(move |offset| (start % number_buckets + offset) % number_buckets)
+    }
+
+    fn buckets(&self) -> &[Bucket<T>] {
+        //  Safety:
+        //  -   `self.buckets` was allocated by `self.store`, or is dangling.
+        //  -   `self.buckets` is still valid.
+        //  -   All buckets are initialized, as per type invariant.
+        unsafe { self.buckets.resolve(&self.store) }
+    }
+
+    fn buckets_mut(&mut self) -> &mut [Bucket<T>] {
+        //  Safety:
+        //  -   `self.buckets` was allocated by `self.store`, or is dangling.
+        //  -   `self.buckets` is still valid.
+        //  -   All buckets are initialized, as per type invariant.
+        unsafe { self.buckets.resolve_mut(&mut self.store) }
+    }
+
+    //  Grows the table to at least `number_buckets`, then rehashes it.
+    //
+    //  `number_buckets` must be greater than the current number of buckets.
+    fn grow_to<H>(&mut self, number_buckets: usize, hasher: H) -> Result<(), AllocError>
+    where
+        H: Fn(&T) -> u64,
+    {
+        debug_assert!(number_buckets > self.buckets.len());
+
+        let old_number_buckets = self.buckets.len();
+
+        if old_number_buckets == 0 {
+            self.buckets = UniqueSingleHandle::try_allocate_slice(number_buckets, &mut self.store)?;
+        } else {
+            //  Safety:
+            //  -   `self.buckets` was allocated by `self.store`, and is still valid.
+            //  -   `number_buckets` is greater than `self.buckets.len()`.
+            unsafe { self.buckets.try_grow(number_buckets, &mut self.store)? };
+        }
+
+        //  Safety:
+        //  -   `self.buckets` was allocated by `self.store`, and is still valid.
+        let buckets = unsafe { self.buckets.resolve_raw_mut(&mut self.store) };
+
+        //  The store may have provided more buckets than requested.
+        for index in old_number_buckets..buckets.len() {
+            //  Safety:
+            //  -   `index` is within bounds.
+            //  -   The pointer is valid for writes, and properly aligned.
+            unsafe { buckets.as_mut_ptr().add(index).write(Bucket::Empty) };
+        }
+
+        if old_number_buckets != 0 {
+            self.rehash_in_place(hasher);
+        }
+
+        Ok(())
+    }
+
+    //  Rehashes all the elements in place, purging all tombstones.
+    fn rehash_in_place<H>(&mut self, hasher: H)
+    where
+        H: Fn(&T) -> u64,
+    {
+        let buckets = self.buckets_mut();
+
+        for bucket in buckets.iter_mut() {
+            *bucket = match mem::replace(bucket, Bucket::Empty) {
+                Bucket::Full(element) => Bucket::Pending(element),
+                _ => Bucket::Empty,
+            };
+        }
+
+        for index in 0..buckets.len() {
+            if !matches!(buckets[index], Bucket::Pending(_)) {
+                continue;
+            }
+
+            let Bucket::Pending(mut element) = mem::replace(&mut buckets[index], Bucket::Empty) else {
+                unreachable!("Bucket {index} should be pending")
+            };
+
+            //  Place `element`, displacing the pending element already at its place, if any, until no element is
+            //  displaced.
+            loop {
+                let hash = hasher(&element);
+
+                let Some(target) = Self::probe(hash, buckets.len())
+                    .find(|target| matches!(buckets[*target], Bucket::Empty | Bucket::Pending(_)))
+                else {
+                    unreachable!("There should be a non-full bucket while rehashing");
+                };
+
+                match mem::replace(&mut buckets[target], Bucket::Full(element)) {
+                    Bucket::Pending(displaced) => element = displaced,
+                    _ => break,
+                }
+            }
+        }
+
+        self.deleted = 0;
+    }
+}
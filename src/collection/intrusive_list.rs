@@ -0,0 +1,588 @@
+//! An intrusive doubly-linked list.
+//!
+//! The elements embed their own `Link`, hence the list itself never allocates: the elements are allocated by the user,
+//! typically via `UniqueHandle`, then handed over to the list. Since the links are handles, rather than pointers, the
+//! elements may live in any store, including inline or shared memory stores.
+
+use core::{fmt, iter::FusedIterator, marker::PhantomData, ptr};
+
+use crate::{
+    extension::{typed::TypedHandle, typed_metadata::TypedMetadata, unique::UniqueHandle},
+    interface::{Store, StoreStable},
+};
+
+/// The link to embed in the elements of an `IntrusiveList`.
+pub struct Link<H> {
+    prev: Option<H>,
+    next: Option<H>,
+}
+
+impl<H> Link<H> {
+    /// Creates a new, unlinked, link.
+    pub const fn new() -> Self {
+        Self { prev: None, next: None }
+    }
+}
+
+impl<H> Default for Link<H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H> fmt::Debug for Link<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_struct("Link").finish_non_exhaustive()
+    }
+}
+
+/// Selects the `Link` of an element threaded through an `IntrusiveList`.
+///
+/// Distinct adapters may select distinct links of a same type of element, such as `prev`, `next`, or `ready`, `wait`.
+///
+/// #   Safety
+///
+/// -   `link` and `link_mut` must return the same `Link` for a given element, at any time.
+/// -   The `Link` must not be modified, other than by the list.
+pub unsafe trait Adapter<T, H> {
+    /// Returns a reference to the link of `element`.
+    fn link(element: &T) -> &Link<H>;
+
+    /// Returns a mutable reference to the link of `element`.
+    fn link_mut(element: &mut T) -> &mut Link<H>;
+}
+
+/// An intrusive doubly-linked list.
+///
+/// The list owns its elements, which are allocated by the user in the store of the list, and which the list threads
+/// through the `Link` selected by `A`. Pushing an element returns a copy of its handle, with which the element can
+/// later be removed in O(1), wherever it is in the list.
+///
+/// ```
+/// use storage::{
+///     collection::{Adapter, IntrusiveList, Link},
+///     extension::unique::UniqueHandle,
+///     store::StackBumpBlock,
+/// };
+///
+/// struct Task {
+///     id: u32,
+///     link: Link<u16>,
+/// }
+///
+/// struct TaskLink;
+///
+/// unsafe impl Adapter<Task, u16> for TaskLink {
+///     fn link(task: &Task) -> &Link<u16> {
+///         &task.link
+///     }
+///
+///     fn link_mut(task: &mut Task) -> &mut Link<u16> {
+///         &mut task.link
+///     }
+/// }
+///
+/// let block = StackBumpBlock::<[u64; 16]>::new();
+/// let mut list = IntrusiveList::<Task, _, TaskLink>::new_in(block.create_store::<u16>());
+///
+/// let tasks: Vec<_> = (1..4)
+///     .map(|id| {
+///         let task = UniqueHandle::new(Task { id, link: Link::new() }, list.store());
+///
+///         //  Safety:
+///         //  -   `task` was allocated by the store of `list`, and contains a live `Task`.
+///         unsafe { list.push_back(task) }
+///     })
+///     .collect();
+///
+/// //  Safety:
+/// //  -   `tasks[1]` is an element of `list`.
+/// let task = unsafe { list.remove(tasks[1]) };
+///
+/// //  Safety:
+/// //  -   `task` was allocated by the store of `list`, and is still valid.
+/// unsafe { task.deallocate(list.store()) };
+///
+/// assert_eq!(vec![1, 3], list.iter().map(|task| task.id).collect::<Vec<_>>());
+/// ```
+pub struct IntrusiveList<T, S: Store, A: Adapter<T, S::Handle>> {
+    //  Type invariant:
+    //  -   `self.length` is the number of elements threaded from `self.head` to `self.tail`.
+    //  -   `self.head` and `self.tail` are `None` if, and only if, `self.length` is 0.
+    //  -   All elements have been allocated by `self.store`, and are owned by `self`.
+    length: usize,
+    head: Option<S::Handle>,
+    tail: Option<S::Handle>,
+    store: S,
+    _marker: PhantomData<(T, fn() -> A)>,
+}
+
+impl<T, S: Store, A: Adapter<T, S::Handle>> IntrusiveList<T, S, A> {
+    /// Creates a new, empty, list.
+    pub fn new() -> Self
+    where
+        S: Default,
+    {
+        Self::new_in(S::default())
+    }
+
+    /// Creates a new, empty, list with the specified `store`.
+    pub const fn new_in(store: S) -> Self {
+        Self {
+            length: 0,
+            head: None,
+            tail: None,
+            store,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns whether the list is empty, or not.
+    pub const fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Returns the number of elements in the list.
+    pub const fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Returns a reference to the store, in which the elements are to be allocated.
+    pub const fn store(&self) -> &S {
+        &self.store
+    }
+}
+
+impl<T, S: Store, A: Adapter<T, S::Handle>> IntrusiveList<T, S, A> {
+    /// Returns a reference to the front element, if any.
+    pub fn front(&self) -> Option<&T> {
+        //  Safety:
+        //  -   `head` is an element of `self`, as per type invariant.
+        self.head.map(|head| unsafe { Self::typed(head).resolve(&self.store) })
+    }
+
+    /// Returns a reference to the back element, if any.
+    pub fn back(&self) -> Option<&T> {
+        //  Safety:
+        //  -   `tail` is an element of `self`, as per type invariant.
+        self.tail.map(|tail| unsafe { Self::typed(tail).resolve(&self.store) })
+    }
+
+    /// Links `element` at the front of the list, returning a copy of its handle.
+    ///
+    /// #   Safety
+    ///
+    /// -   `element` must have been allocated by the store of the list, as returned by `store`.
+    /// -   `element` must be associated to a block of memory containing a live instance of `T`.
+    pub unsafe fn push_front(&mut self, element: UniqueHandle<T, S::Handle>) -> TypedHandle<T, S::Handle> {
+        let (handle, metadata) = element.to_raw_parts();
+
+        let head = self.head;
+
+        {
+            //  Safety:
+            //  -   `handle` was allocated by `self.store`, and contains a live `T`, as per pre-conditions.
+            let link = unsafe { self.link_mut(handle) };
+
+            link.prev = None;
+            link.next = head;
+        }
+
+        match head {
+            //  Safety:
+            //  -   `head` is an element of `self`, as per type invariant.
+            Some(head) => unsafe { self.link_mut(head) }.prev = Some(handle),
+            None => self.tail = Some(handle),
+        }
+
+        self.head = Some(handle);
+        self.length += 1;
+
+        TypedHandle::from_raw_parts(handle, metadata)
+    }
+
+    /// Links `element` at the back of the list, returning a copy of its handle.
+    ///
+    /// #   Safety
+    ///
+    /// -   `element` must have been allocated by the store of the list, as returned by `store`.
+    /// -   `element` must be associated to a block of memory containing a live instance of `T`.
+    pub unsafe fn push_back(&mut self, element: UniqueHandle<T, S::Handle>) -> TypedHandle<T, S::Handle> {
+        let (handle, metadata) = element.to_raw_parts();
+
+        let tail = self.tail;
+
+        {
+            //  Safety:
+            //  -   `handle` was allocated by `self.store`, and contains a live `T`, as per pre-conditions.
+            let link = unsafe { self.link_mut(handle) };
+
+            link.prev = tail;
+            link.next = None;
+        }
+
+        match tail {
+            //  Safety:
+            //  -   `tail` is an element of `self`, as per type invariant.
+            Some(tail) => unsafe { self.link_mut(tail) }.next = Some(handle),
+            None => self.head = Some(handle),
+        }
+
+        self.tail = Some(handle);
+        self.length += 1;
+
+        TypedHandle::from_raw_parts(handle, metadata)
+    }
+
+    /// Unlinks the front element, if any, and returns it.
+    pub fn pop_front(&mut self) -> Option<UniqueHandle<T, S::Handle>> {
+        let head = self.head?;
+
+        //  Safety:
+        //  -   `head` is an element of `self`, as per type invariant.
+        Some(unsafe { self.unlink(head) })
+    }
+
+    /// Unlinks the back element, if any, and returns it.
+    pub fn pop_back(&mut self) -> Option<UniqueHandle<T, S::Handle>> {
+        let tail = self.tail?;
+
+        //  Safety:
+        //  -   `tail` is an element of `self`, as per type invariant.
+        Some(unsafe { self.unlink(tail) })
+    }
+
+    /// Unlinks `element`, wherever it is in the list, and returns it.
+    ///
+    /// #   Safety
+    ///
+    /// -   `element` must be an element of this list, as returned by `push_front` or `push_back`, and not since
+    ///     removed.
+    pub unsafe fn remove(&mut self, element: TypedHandle<T, S::Handle>) -> UniqueHandle<T, S::Handle> {
+        //  Safety:
+        //  -   `element` is an element of `self`, as per pre-conditions.
+        unsafe { self.unlink(element.to_raw_parts().0) }
+    }
+
+    /// Clears the list, dropping and deallocating all its elements.
+    pub fn clear(&mut self) {
+        while let Some(element) = self.pop_front() {
+            //  Safety:
+            //  -   `element` was allocated by `self.store`, and is still valid, as per type invariant.
+            let pointer = unsafe { element.resolve_raw(&self.store) };
+
+            //  Safety:
+            //  -   `pointer` points to a live instance of `T`, which will not be used afterwards.
+            unsafe { ptr::drop_in_place(pointer.as_ptr()) };
+
+            //  Safety:
+            //  -   `element` was allocated by `self.store`, and is still valid, as per type invariant.
+            unsafe { element.deallocate(&self.store) };
+        }
+    }
+}
+
+impl<T, S: Store + StoreStable, A: Adapter<T, S::Handle>> IntrusiveList<T, S, A> {
+    /// Returns an iterator over the elements of the list, from front to back.
+    pub fn iter(&self) -> Iter<'_, T, S, A> {
+        Iter {
+            remaining: self.length,
+            next: self.head,
+            list: self,
+        }
+    }
+}
+
+impl<T, S: Store, A: Adapter<T, S::Handle>> Drop for IntrusiveList<T, S, A> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T, S: Store + Default, A: Adapter<T, S::Handle>> Default for IntrusiveList<T, S, A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, S, A> fmt::Debug for IntrusiveList<T, S, A>
+where
+    T: fmt::Debug,
+    S: Store + StoreStable,
+    A: Adapter<T, S::Handle>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+unsafe impl<T, S, A> Send for IntrusiveList<T, S, A>
+where
+    T: Send,
+    S: Store + Send,
+    A: Adapter<T, S::Handle>,
+{
+}
+
+unsafe impl<T, S, A> Sync for IntrusiveList<T, S, A>
+where
+    T: Sync,
+    S: Store + Sync,
+    A: Adapter<T, S::Handle>,
+{
+}
+
+impl<'a, T, S: Store + StoreStable, A: Adapter<T, S::Handle>> IntoIterator for &'a IntrusiveList<T, S, A> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T, S, A>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the elements of an `IntrusiveList`.
+pub struct Iter<'a, T, S: Store, A: Adapter<T, S::Handle>> {
+    remaining: usize,
+    next: Option<S::Handle>,
+    list: &'a IntrusiveList<T, S, A>,
+}
+
+impl<'a, T: 'a, S: Store + StoreStable, A: Adapter<T, S::Handle>> Iterator for Iter<'a, T, S, A> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let handle = self.next?;
+
+        //  Safety:
+        //  -   `handle` is an element of the list, which is borrowed immutably for `'a`.
+        //  -   The reference remains valid across further resolutions, as the store is `StoreStable`.
+        let element = unsafe { IntrusiveList::<T, S, A>::typed(handle).resolve(&self.list.store) };
+
+        self.remaining -= 1;
+        self.next = A::link(element).next;
+
+        Some(element)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T: 'a, S: Store + StoreStable, A: Adapter<T, S::Handle>> ExactSizeIterator for Iter<'a, T, S, A> {}
+
+impl<'a, T: 'a, S: Store + StoreStable, A: Adapter<T, S::Handle>> FusedIterator for Iter<'a, T, S, A> {}
+
+//
+//  Implementation
+//
+
+impl<T, S: Store, A: Adapter<T, S::Handle>> IntrusiveList<T, S, A> {
+    fn typed(handle: S::Handle) -> TypedHandle<T, S::Handle> {
+        TypedHandle::from_raw_parts(handle, TypedMetadata::default())
+    }
+}
+
+impl<T, S: Store, A: Adapter<T, S::Handle>> IntrusiveList<T, S, A> {
+    //  Returns a mutable reference to the link of `handle`.
+    //
+    //  #   Safety
+    //
+    //  -   `handle` must have been allocated by `self.store`, and still be valid.
+    //  -   `handle` must be associated to a block of memory containing a live instance of `T`.
+    unsafe fn link_mut(&mut self, handle: S::Handle) -> &mut Link<S::Handle> {
+        let mut element = Self::typed(handle);
+
+        //  Safety:
+        //  -   As per pre-conditions.
+        //  -   Access is exclusive, as `self` is borrowed mutably.
+        A::link_mut(unsafe { element.resolve_mut(&self.store) })
+    }
+
+    //  Unlinks `handle` from the list, and returns it.
+    //
+    //  #   Safety
+    //
+    //  -   `handle` must be an element of the list.
+    unsafe fn unlink(&mut self, handle: S::Handle) -> UniqueHandle<T, S::Handle> {
+        let (prev, next) = {
+            //  Safety:
+            //  -   `handle` is an element of `self`, as per pre-conditions.
+            let link = unsafe { self.link_mut(handle) };
+
+            (link.prev.take(), link.next.take())
+        };
+
+        match prev {
+            //  Safety:
+            //  -   `prev` is an element of `self`, as it is linked to `handle`.
+            Some(prev) => unsafe { self.link_mut(prev) }.next = next,
+            None => self.head = next,
+        }
+
+        match next {
+            //  Safety:
+            //  -   `next` is an element of `self`, as it is linked to `handle`.
+            Some(next) => unsafe { self.link_mut(next) }.prev = prev,
+            None => self.tail = prev,
+        }
+
+        self.length -= 1;
+
+        //  Safety:
+        //  -   `handle` is no longer linked, hence no copy of it is used by `self` henceforth.
+        unsafe { UniqueHandle::from_raw_parts(handle, TypedMetadata::default()) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::string::{String, ToString};
+
+    use crate::store::{StackBumpBlock, StackBumpStore};
+
+    use super::*;
+
+    struct Node {
+        name: String,
+        link: Link<u16>,
+    }
+
+    struct NodeLink;
+
+    unsafe impl Adapter<Node, u16> for NodeLink {
+        fn link(node: &Node) -> &Link<u16> {
+            &node.link
+        }
+
+        fn link_mut(node: &mut Node) -> &mut Link<u16> {
+            &mut node.link
+        }
+    }
+
+    type List<'a> = IntrusiveList<Node, StackBumpStore<'a, u16>, NodeLink>;
+
+    fn push_back(list: &mut List<'_>, name: &str) -> TypedHandle<Node, u16> {
+        let node = UniqueHandle::new(
+            Node {
+                name: name.to_string(),
+                link: Link::new(),
+            },
+            list.store(),
+        );
+
+        //  Safety:
+        //  -   `node` was allocated by the store of `list`, and contains a live `Node`.
+        unsafe { list.push_back(node) }
+    }
+
+    fn names<'a>(list: &'a List<'_>) -> Vec<&'a str> {
+        list.iter().map(|node| node.name.as_str()).collect()
+    }
+
+    fn release(list: &List<'_>, node: UniqueHandle<Node, u16>) -> String {
+        //  Safety:
+        //  -   `node` was allocated by the store of `list`, and is still valid.
+        //  -   `node` contains a live `Node`, which is not used afterwards.
+        let node = unsafe { ptr::read(node.resolve_raw(list.store()).as_ptr()) };
+
+        node.name
+    }
+
+    #[test]
+    fn push_pop() {
+        let block = StackBumpBlock::<[u64; 64]>::new();
+        let mut list = List::new_in(block.create_store());
+
+        assert!(list.is_empty());
+        assert!(list.front().is_none());
+
+        push_back(&mut list, "1");
+        push_back(&mut list, "2");
+
+        let node = UniqueHandle::new(
+            Node {
+                name: "0".to_string(),
+                link: Link::new(),
+            },
+            list.store(),
+        );
+
+        //  Safety:
+        //  -   `node` was allocated by the store of `list`, and contains a live `Node`.
+        unsafe { list.push_front(node) };
+
+        assert_eq!(3, list.len());
+        assert_eq!(["0", "1", "2"], &names(&list)[..]);
+        assert_eq!("0", list.front().unwrap().name);
+        assert_eq!("2", list.back().unwrap().name);
+
+        let back = list.pop_back().unwrap();
+
+        assert_eq!("2", release(&list, back));
+
+        let front = list.pop_front().unwrap();
+
+        assert_eq!("0", release(&list, front));
+
+        assert_eq!(["1"], &names(&list)[..]);
+    }
+
+    #[test]
+    fn remove() {
+        let block = StackBumpBlock::<[u64; 64]>::new();
+        let mut list = List::new_in(block.create_store());
+
+        let handles: Vec<_> = ["0", "1", "2", "3"]
+            .iter()
+            .map(|name| push_back(&mut list, name))
+            .collect();
+
+        //  Safety:
+        //  -   All `handles` are elements of `list`, and each is removed once.
+        let (middle, front, back) = unsafe {
+            (
+                list.remove(handles[2]),
+                list.remove(handles[0]),
+                list.remove(handles[3]),
+            )
+        };
+
+        assert_eq!("2", release(&list, middle));
+        assert_eq!("0", release(&list, front));
+        assert_eq!("3", release(&list, back));
+
+        assert_eq!(["1"], &names(&list)[..]);
+
+        //  Re-linking after removal.
+        push_back(&mut list, "4");
+
+        assert_eq!(["1", "4"], &names(&list)[..]);
+
+        //  Safety:
+        //  -   `handles[1]` is an element of `list`.
+        let only = unsafe { list.remove(handles[1]) };
+
+        assert_eq!("1", release(&list, only));
+        assert_eq!(["4"], &names(&list)[..]);
+    }
+
+    #[test]
+    fn clear() {
+        let block = StackBumpBlock::<[u64; 64]>::new();
+        let mut list = List::new_in(block.create_store());
+
+        push_back(&mut list, "0");
+        push_back(&mut list, "1");
+
+        list.clear();
+
+        assert!(list.is_empty());
+        assert!(list.iter().next().is_none());
+
+        push_back(&mut list, "2");
+
+        assert_eq!(["2"], &names(&list)[..]);
+    }
+} // mod tests
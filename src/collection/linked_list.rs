@@ -2,13 +2,42 @@
 //!
 //! This implementation is solely meant to demonstrate the use of `StoreSharing`, it is incomplete, and may be buggy.
 
-use core::{alloc::AllocError, cmp, fmt, hash, mem, ptr};
+use core::{
+    alloc::{AllocError, Layout},
+    cmp, fmt, hash,
+    iter::FusedIterator,
+    mem, ptr,
+};
 
 use crate::{
+    alloc,
+    collection::MemoryUsage,
     extension::typed::TypedHandle,
-    interface::{Store, StoreDangling, StoreSharing, StoreStable},
+    interface::{Store, StoreDangling, StoreSharing, StoreStable, StoreValidate},
+    store::InlineBumpStore,
 };
 
+/// A linked list stored inline, with room for `N` elements, and using `H` as handles.
+///
+/// ```
+/// use storage::collection::InlineLinkedList;
+///
+/// let mut list = InlineLinkedList::<u32, u8, 4>::new();
+///
+/// (0..4).for_each(|n| list.try_push_back(n).unwrap());
+///
+/// assert!(list.try_push_back(4).is_err());
+/// ```
+pub type InlineLinkedList<T, H, const N: usize> = LinkedList<T, InlineBumpStore<H, LinkedListBlock<T, H, N>>>;
+
+/// A block of memory sized and aligned for `N` nodes of a `LinkedList` of `T`, using `H` as handles.
+///
+/// This type is solely meant to size the block of memory of inline stores, such as `InlineBumpStore`, and is never
+/// instantiated.
+pub struct LinkedListBlock<T, H, const N: usize> {
+    _nodes: [Node<T, H>; N],
+}
+
 /// A singly-linked list.
 pub struct LinkedList<T, S: Store> {
     //  Invariant: number of nodes in the list. A length of 0 means that the `head` and `tail` handles are dangling.
@@ -54,6 +83,26 @@ impl<T, S: Store> LinkedList<T, S> {
         self.length
     }
 
+    /// Returns the layout of the node of each element, as allocated in the store.
+    ///
+    /// Useful to size a store for a given number of elements; see `LinkedListBlock` for inline stores.
+    pub const fn node_layout() -> Layout {
+        Layout::new::<Node<T, S::Handle>>()
+    }
+
+    /// Returns the memory footprint of the list, as per the layouts of its nodes.
+    pub const fn memory_usage(&self) -> MemoryUsage {
+        let bytes_allocated = self.length * Self::node_layout().size();
+        let bytes_used = self.length * mem::size_of::<T>();
+        let allocations = if Self::node_layout().size() > 0 { self.length } else { 0 };
+
+        MemoryUsage {
+            bytes_allocated,
+            bytes_used,
+            allocations,
+        }
+    }
+
     /// Returns whether the list contains `element`, or not.
     pub fn contains(&self, element: &T) -> bool
     where
@@ -222,9 +271,120 @@ impl<T, S: Store> LinkedList<T, S> {
 
         Some(element)
     }
+
+    /// Returns a mutable reference to the element at `index`, if any.
+    ///
+    /// This operation is O(n), as it walks the list from the front.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len() {
+            return None;
+        }
+
+        let mut handle = self.nth(index);
+
+        //  Safety:
+        //  -   `handle` has been allocated by `self.store`.
+        //  -   `handle` is valid, since `index < length`.
+        //  -   `handle` is associated with a memory block containing a valid instance of `Node`.
+        //  -   Access to the resulting `node` is exclusive, as guaranteed by `self` being borrowed mutably.
+        let node = unsafe { handle.resolve_mut(&self.store) };
+
+        //  It is safe to return the reference, as it extends the borrow of `self`, guaranteeing that no operation on
+        //  `self.store` will occur which could potentially invalidate either handle or pointer.
+        Some(&mut node.element)
+    }
+
+    /// Removes the element at `index`, if any.
+    ///
+    /// This operation is O(n), as it walks the list from the front.
+    pub fn remove_at(&mut self, index: usize) -> Option<T> {
+        if index >= self.len() {
+            return None;
+        }
+
+        if index == 0 {
+            return self.pop_front();
+        }
+
+        if index + 1 == self.len() {
+            return self.pop_back();
+        }
+
+        let handle = self.nth(index);
+
+        //  Safety:
+        //  -   `handle` has been allocated by `self.store`.
+        //  -   `handle` is valid, since `index < length`.
+        let node = unsafe { handle.resolve_raw(&self.store) }.as_ptr();
+
+        //  Safety:
+        //  -   `node` points to a valid instance of `Node`, whose `element` is a live instance of `T`.
+        //  -   `node.element` will not be used again.
+        //  -   Access is exclusive, as guaranteed by `self` being borrowed mutably.
+        let (element, prev, next) = unsafe {
+            (
+                ptr::read(ptr::addr_of!((*node).element)),
+                ptr::read(ptr::addr_of!((*node).prev)),
+                ptr::read(ptr::addr_of!((*node).next)),
+            )
+        };
+
+        //  Safety:
+        //  -   `handle` has been allocated by `self.store`.
+        //  -   `handle` is valid, since `index < length`.
+        //  -   Access to the resulting `node` is exclusive, as guaranteed by `self` being borrowed mutably.
+        unsafe { handle.deallocate(&self.store) };
+
+        //  Safety:
+        //  -   `prev` and `next` have been allocated by `self.store`.
+        //  -   `prev` and `next` are valid, since `0 < index < length - 1`.
+        //  -   `prev` and `next` are associated with memory blocks containing valid instances of `Node`.
+        //  -   Access to the nodes is exclusive, as guaranteed by `self` being borrowed mutably.
+        unsafe {
+            Self::set_next(prev, next, &self.store);
+            Self::set_prev(next, prev, &self.store);
+        }
+
+        self.length -= 1;
+
+        Some(element)
+    }
 }
 
 impl<T, S: Store> LinkedList<T, S> {
+    /// Pushes an element to the front of the list.
+    ///
+    /// Calls `handle_alloc_error` if memory allocation fails.
+    pub fn push_front(&mut self, element: T) {
+        if self.try_push_front(element).is_err() {
+            Self::handle_alloc_error()
+        }
+    }
+
+    /// Pushes an element to the back of the list.
+    ///
+    /// Calls `handle_alloc_error` if memory allocation fails.
+    pub fn push_back(&mut self, element: T) {
+        if self.try_push_back(element).is_err() {
+            Self::handle_alloc_error()
+        }
+    }
+
+    /// Appends all the elements of `iter` to the back of the list, unless memory allocation fails.
+    ///
+    /// On failure, the elements appended prior to the failure remain in the list, and the remaining elements of `iter`
+    /// are dropped.
+    pub fn try_extend<I>(&mut self, iter: I) -> Result<(), AllocError>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for element in iter {
+            self.try_push_back(element)?;
+        }
+
+        Ok(())
+    }
+
     /// Pushes an element to the front of the list, unless memory allocation fails.
     pub fn try_push_front(&mut self, element: T) -> Result<(), AllocError> {
         let node = Node {
@@ -234,12 +394,19 @@ impl<T, S: Store> LinkedList<T, S> {
         };
         let handle = TypedHandle::try_new(node, &self.store)?;
 
-        self.head = handle;
-
-        if self.is_empty() {
+        if !self.is_empty() {
+            //  Safety:
+            //  -   `self.head` has been allocated by `self.store`.
+            //  -   `self.head` is valid, since `length` is not 0.
+            //  -   `self.head` is associated with a memory block containing a valid instance of `Node`.
+            //  -   Access to `head` is exclusive, as guaranteed by `self` being borrowed mutably.
+            unsafe { Self::set_prev(self.head, handle, &self.store) };
+        } else {
             self.tail = handle;
         }
 
+        self.head = handle;
+
         self.length += 1;
 
         Ok(())
@@ -259,10 +426,8 @@ impl<T, S: Store> LinkedList<T, S> {
             //  -   `self.tail` has been allocated by `self.store`.
             //  -   `self.tail` is valid, since `length` is not 0.
             //  -   `self.tail` is associated with a memory block containing a valid instance of `Node`.
-            //  -   Access to the resulting `tail` is exclusive, as guaranteed by `self` being borrowed mutably.
-            let tail = unsafe { self.tail.resolve_mut(&self.store) };
-
-            tail.next = handle;
+            //  -   Access to `tail` is exclusive, as guaranteed by `self` being borrowed mutably.
+            unsafe { Self::set_next(self.tail, handle, &self.store) };
         } else {
             self.head = handle;
         }
@@ -272,6 +437,67 @@ impl<T, S: Store> LinkedList<T, S> {
 
         Ok(())
     }
+
+    /// Inserts an element at `index`, shifting all elements after it towards the back.
+    ///
+    /// This operation is O(n), as it walks the list from the front.
+    ///
+    /// Calls `handle_alloc_error` if memory allocation fails.
+    ///
+    /// #   Panics
+    ///
+    /// Panics if `index > self.len()`.
+    pub fn insert_at(&mut self, index: usize, element: T) {
+        if self.try_insert_at(index, element).is_err() {
+            Self::handle_alloc_error()
+        }
+    }
+
+    /// Inserts an element at `index`, shifting all elements after it towards the back, unless memory allocation
+    /// fails.
+    ///
+    /// This operation is O(n), as it walks the list from the front.
+    ///
+    /// #   Panics
+    ///
+    /// Panics if `index > self.len()`.
+    pub fn try_insert_at(&mut self, index: usize, element: T) -> Result<(), AllocError> {
+        assert!(index <= self.len(), "{index} > {}", self.len());
+
+        if index == 0 {
+            return self.try_push_front(element);
+        }
+
+        if index == self.len() {
+            return self.try_push_back(element);
+        }
+
+        let next = self.nth(index);
+
+        //  Safety:
+        //  -   `next` has been allocated by `self.store`.
+        //  -   `next` is valid, since `index < length`.
+        //  -   `next` is associated with a memory block containing a valid instance of `Node`.
+        //  -   Access to the resulting `node` is shared, as guaranteed by `self` being borrowed mutably.
+        let prev = unsafe { next.resolve(&self.store).prev };
+
+        let node = Node { element, next, prev };
+        let handle = TypedHandle::try_new(node, &self.store)?;
+
+        //  Safety:
+        //  -   `prev` and `next` have been allocated by `self.store`.
+        //  -   `prev` and `next` are valid, since `0 < index < length`.
+        //  -   `prev` and `next` are associated with memory blocks containing valid instances of `Node`.
+        //  -   Access to the nodes is exclusive, as guaranteed by `self` being borrowed mutably.
+        unsafe {
+            Self::set_next(prev, handle, &self.store);
+            Self::set_prev(next, handle, &self.store);
+        }
+
+        self.length += 1;
+
+        Ok(())
+    }
 }
 
 impl<T, S: Store + StoreStable> LinkedList<T, S> {
@@ -338,17 +564,64 @@ impl<T, S: Store + StoreStable> LinkedList<T, S> {
         //  either handle or pointer.
         Some(&node.element)
     }
+
+    /// Returns a reference to the element at `index`, if any.
+    ///
+    /// This operation is O(n), as it walks the list from the front.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len() {
+            return None;
+        }
+
+        let handle = self.nth(index);
+
+        //  Safety:
+        //  -   `handle` has been allocated by `self.store`.
+        //  -   `handle` is valid, since `index < length`.
+        //  -   `handle` is associated with a memory block containing a valid instance of `Node`.
+        //  -   Access to the resulting `node` is shared, as guaranteed by `self` being borrowed immutably.
+        let node = unsafe { handle.resolve(&self.store) };
+
+        //  It is safe to return the reference, as it extends the borrow of `self`, guaranteeing that `self.store` will
+        //  not be moved, in addition to `StoreStable` guaranteeing that no operation on `self.store` will invalidate
+        //  either handle or pointer.
+        Some(&node.element)
+    }
 }
 
 impl<T, S: Store + StoreSharing> LinkedList<T, S> {
+    /// Creates a new, empty, list whose store is sharing with the store of `self`.
+    ///
+    /// Nested collections created this way all allocate from the same sharing set.
+    pub fn new_sharing(&self) -> Self
+    where
+        S: StoreSharing<SharingError = !>,
+    {
+        self.try_new_sharing().into_ok()
+    }
+
+    /// Attempts to create a new, empty, list whose store is sharing with the store of `self`.
+    ///
+    /// Returns an error if the store cannot be shared.
+    pub fn try_new_sharing(&self) -> Result<Self, S::SharingError> {
+        let store = self.store.share()?;
+
+        Ok(Self::new_in(store))
+    }
+
     /// Tries to append the nodes from `other` to `self`.
     ///
     /// On success, the nodes are transferred and `other` is left empty. On failure, `self` and `other` are unmodified.
     ///
-    /// Fails if the store of `other` is not sharing with the store of `self`.
+    /// Fails if the store of `other` is not known to be sharing with the store of `self`, as per either their sharing
+    /// tokens or `is_sharing_with`.
     #[allow(clippy::result_unit_err)]
     pub fn try_append(&mut self, other: &mut Self) -> Result<(), ()> {
-        if !self.store.is_sharing_with(&other.store) {
+        //  Equal tokens prove sharing, whereas `is_sharing_with` may recover sharing stores whose tokens differ.
+        let sharing =
+            self.store.sharing_token() == other.store.sharing_token() || self.store.is_sharing_with(&other.store);
+
+        if !sharing {
             return Err(());
         }
 
@@ -381,20 +654,17 @@ impl<T, S: Store + StoreSharing> LinkedList<T, S> {
         //  -   `self.tail` has been allocated by `self.store`.
         //  -   `self.tail` is valid, since `length` is not 0.
         //  -   `self.tail` is associated with a memory block containing a valid instance of `Node`.
-        //  -   Access to the resulting `tail` is exclusive, as guaranteed by `self` being borrowed mutably.
-        let tail = unsafe { self.tail.resolve_mut(&self.store) };
-
-        tail.next = other.head;
+        //  -   Access to `tail` is exclusive, as guaranteed by `self` being borrowed mutably.
+        unsafe { Self::set_next(self.tail, other.head, &self.store) };
 
         //  Safety:
         //  -   `other.head` has been allocated by `other.store`.
         //  -   `other.head` is valid, since `length` is not 0.
         //  -   `other.head` is associated with a memory block containing a valid instance of `Node`.
-        //  -   Access to the resulting `head` is exclusive, as guaranteed by `other` being borrowed mutably.
-        let head = unsafe { other.head.resolve_mut(&other.store) };
-
-        head.prev = self.tail;
+        //  -   Access to `head` is exclusive, as guaranteed by `other` being borrowed mutably.
+        unsafe { Self::set_prev(other.head, self.tail, &other.store) };
 
+        self.tail = other.tail;
         self.length += other.length;
         other.length = 0;
     }
@@ -452,18 +722,109 @@ impl<T, S: Store + StoreSharing> LinkedList<T, S> {
     }
 }
 
-impl<T: Clone, S: Store + StoreStable + Default> Clone for LinkedList<T, S> {
-    fn clone(&self) -> Self {
-        let mut result = Self::default();
+impl<T, S: Store + StoreValidate> LinkedList<T, S> {
+    /// Validates the handles of the list, as far as the store can tell.
+    ///
+    /// Returns `Some(false)` if any handle is known to be invalid, `None` if the validity of any handle is unknown, and
+    /// `Some(true)` otherwise.
+    ///
+    /// Walking the list requires resolving its handles, hence this method is only meaningful on lists whose invariants
+    /// have not been violated yet; it is meant as a debugging aid to catch issues as early as possible.
+    pub fn debug_validate(&self) -> Option<bool> {
+        let mut result = Some(true);
+        let mut handle = self.head;
 
-        for item in self {
-            result.try_push_back(item.clone()).expect("Sufficient space in store");
+        for _ in 0..self.length {
+            let (raw, _) = handle.to_raw_parts();
+
+            match self.store.is_valid(raw) {
+                Some(true) => (),
+                Some(false) => return Some(false),
+                None => result = None,
+            }
+
+            //  Safety:
+            //  -   `handle` has been allocated by `self.store`.
+            //  -   `handle` is valid, since there are `length` valid handles.
+            //  -   `handle` is associated with a memory block containing a valid instance of `Node`.
+            //  -   Access to the resulting `node` is shared, as guaranteed by `self` being borrowed immutably.
+            let node = unsafe { handle.resolve(&self.store) };
+
+            handle = node.next;
         }
 
         result
     }
 }
 
+impl<T: Clone, S: Store + StoreStable> LinkedList<T, S> {
+    /// Clones the list, using `store` for the clone.
+    ///
+    /// Calls `handle_alloc_error` if `store` cannot accommodate all the elements of the list.
+    pub fn clone_in(&self, store: S) -> Self {
+        let Ok(result) = self.try_clone_in(store) else {
+            Self::handle_alloc_error()
+        };
+
+        result
+    }
+
+    /// Attempts to clone the list, using `store` for the clone.
+    ///
+    /// Returns an error if `store` cannot accommodate all the elements of the list.
+    pub fn try_clone_in(&self, store: S) -> Result<Self, AllocError> {
+        let mut result = Self::new_in(store);
+
+        for item in self {
+            result.try_push_back(item.clone())?;
+        }
+
+        Ok(result)
+    }
+}
+
+impl<T: Clone, S: Store + StoreSharing> LinkedList<T, S> {
+    /// Attempts to clone the list, sharing the store of `self` for the clone.
+    ///
+    /// Returns an error if sharing is not currently possible, or if the store cannot accommodate all the elements of
+    /// the list.
+    pub fn try_clone(&self) -> Result<Self, CloneError<S::SharingError>> {
+        let store = self.store.share().map_err(CloneError::Sharing)?;
+
+        Ok(self.try_clone_in(store)?)
+    }
+}
+
+/// Error returned when cloning a collection into a part of the sharing set of its store.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CloneError<E> {
+    /// Sharing the store is not currently possible.
+    Sharing(E),
+    /// The store cannot accommodate all the elements of the collection.
+    Alloc(AllocError),
+}
+
+impl<E> From<AllocError> for CloneError<E> {
+    fn from(error: AllocError) -> Self {
+        Self::Alloc(error)
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for CloneError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            Self::Sharing(error) => write!(f, "cannot share the store: {error}"),
+            Self::Alloc(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+impl<T: Clone, S: Store + StoreStable + Default> Clone for LinkedList<T, S> {
+    fn clone(&self) -> Self {
+        self.clone_in(S::default())
+    }
+}
+
 impl<T: fmt::Debug, S: Store + StoreStable> fmt::Debug for LinkedList<T, S> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
         f.debug_list().entries(self).finish()
@@ -520,8 +881,12 @@ where
 
 impl<T: hash::Hash, S: Store + StoreStable> hash::Hash for LinkedList<T, S> {
     fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        #[cfg(feature = "prefix-free-hash")]
         state.write_length_prefix(self.len());
 
+        #[cfg(not(feature = "prefix-free-hash"))]
+        state.write_usize(self.len());
+
         for element in self {
             element.hash(state);
         }
@@ -586,7 +951,7 @@ impl<T, S: Store> Extend<T> for LinkedList<T, S> {
         I: IntoIterator<Item = T>,
     {
         for element in iter {
-            self.try_push_back(element).expect("Sufficient space in store");
+            self.push_back(element);
         }
     }
 }
@@ -599,7 +964,7 @@ impl<T, S: Store + Default> FromIterator<T> for LinkedList<T, S> {
         let mut result = LinkedList::new();
 
         for element in iter {
-            result.try_push_back(element).expect("Sufficient space in store");
+            result.push_back(element);
         }
 
         result
@@ -642,6 +1007,10 @@ impl<T, S: Store + StoreStable> Iterator for IntoIter<T, S> {
     fn next(&mut self) -> Option<Self::Item> {
         self.0.pop_front()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len(), Some(self.0.len()))
+    }
 }
 
 impl<T, S: Store + StoreStable> DoubleEndedIterator for IntoIter<T, S> {
@@ -650,6 +1019,10 @@ impl<T, S: Store + StoreStable> DoubleEndedIterator for IntoIter<T, S> {
     }
 }
 
+impl<T, S: Store + StoreStable> ExactSizeIterator for IntoIter<T, S> {}
+
+impl<T, S: Store + StoreStable> FusedIterator for IntoIter<T, S> {}
+
 /// Iterator over a reference to a linked list.
 pub struct Iter<'a, T, S: Store> {
     //  Only `length` iterators are valid.
@@ -681,6 +1054,10 @@ impl<'a, T: 'a, S: Store + StoreStable> Iterator for Iter<'a, T, S> {
 
         Some(element)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.length, Some(self.length))
+    }
 }
 
 impl<'a, T: 'a, S: Store + StoreStable> DoubleEndedIterator for Iter<'a, T, S> {
@@ -705,6 +1082,10 @@ impl<'a, T: 'a, S: Store + StoreStable> DoubleEndedIterator for Iter<'a, T, S> {
     }
 }
 
+impl<'a, T: 'a, S: Store + StoreStable> ExactSizeIterator for Iter<'a, T, S> {}
+
+impl<'a, T: 'a, S: Store + StoreStable> FusedIterator for Iter<'a, T, S> {}
+
 /// Iterator over a mutable reference to a linked list.
 pub struct IterMut<'a, T, S: Store> {
     //  Only `length` iterators are valid.
@@ -725,17 +1106,26 @@ impl<'a, T: 'a, S: Store + StoreStable> Iterator for IterMut<'a, T, S> {
         //  Safety:
         //  -   `self.head` has been allocated by `self.store`.
         //  -   `self.head` is valid, since `length` is not 0.
-        //  -   `self.head` is associated with a memory block containing a valid instance of `Node`.
-        //  -   Access to the resulting `head` is exclusive, as guaranteed by the list being borrowed mutably.
-        let head = unsafe { self.head.resolve_mut(self.store) };
+        let head = unsafe { self.head.resolve_raw(self.store) }.as_ptr();
 
-        let element = &mut head.element;
+        //  Safety:
+        //  -   `head` points to a valid instance of `Node`.
+        //  -   Access to `head.element` is exclusive, as guaranteed by the list being borrowed mutably, and each
+        //      element being yielded at most once.
+        //  -   Only the element is borrowed, the links are copied, hence the node is never borrowed as a whole.
+        let element = unsafe { &mut *ptr::addr_of_mut!((*head).element) };
 
-        self.head = head.next;
+        //  Safety:
+        //  -   `head` points to a valid instance of `Node`.
+        self.head = unsafe { ptr::read(ptr::addr_of!((*head).next)) };
         self.length -= 1;
 
         Some(element)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.length, Some(self.length))
+    }
 }
 
 impl<'a, T: 'a, S: Store + StoreStable> DoubleEndedIterator for IterMut<'a, T, S> {
@@ -747,19 +1137,28 @@ impl<'a, T: 'a, S: Store + StoreStable> DoubleEndedIterator for IterMut<'a, T, S
         //  Safety:
         //  -   `self.tail` has been allocated by `self.store`.
         //  -   `self.tail` is valid, since `length` is not 0.
-        //  -   `self.tail` is associated with a memory block containing a valid instance of `Node`.
-        //  -   Access to the resulting `tail` is exclusive, as guaranteed by the list being borrowed mutably.
-        let tail = unsafe { self.tail.resolve_mut(self.store) };
+        let tail = unsafe { self.tail.resolve_raw(self.store) }.as_ptr();
 
-        let element = &mut tail.element;
+        //  Safety:
+        //  -   `tail` points to a valid instance of `Node`.
+        //  -   Access to `tail.element` is exclusive, as guaranteed by the list being borrowed mutably, and each
+        //      element being yielded at most once.
+        //  -   Only the element is borrowed, the links are copied, hence the node is never borrowed as a whole.
+        let element = unsafe { &mut *ptr::addr_of_mut!((*tail).element) };
 
-        self.tail = tail.prev;
+        //  Safety:
+        //  -   `tail` points to a valid instance of `Node`.
+        self.tail = unsafe { ptr::read(ptr::addr_of!((*tail).prev)) };
         self.length -= 1;
 
         Some(element)
     }
 }
 
+impl<'a, T: 'a, S: Store + StoreStable> ExactSizeIterator for IterMut<'a, T, S> {}
+
+impl<'a, T: 'a, S: Store + StoreStable> FusedIterator for IterMut<'a, T, S> {}
+
 //
 //  Implementation
 //
@@ -798,6 +1197,48 @@ impl<T, S: Store> LinkedList<T, S> {
 
         handle
     }
+
+    //  Sets the `next` link of the node associated to `handle`, without creating a reference to the node.
+    //
+    //  #   Safety
+    //
+    //  -   `handle` must have been allocated by `store`, and still be valid.
+    //  -   `handle` must be associated with a memory block containing a valid instance of `Node`.
+    //  -   No reference to the `next` link of the node must be active.
+    unsafe fn set_next(handle: NodeHandle<T, S::Handle>, next: NodeHandle<T, S::Handle>, store: &S) {
+        //  Safety:
+        //  -   As per pre-conditions.
+        let node = unsafe { handle.resolve_raw(store) }.as_ptr();
+
+        //  Safety:
+        //  -   `node` points to a valid instance of `Node`, as per pre-conditions.
+        //  -   No reference to `node.next` is active, as per pre-conditions.
+        unsafe { ptr::write(ptr::addr_of_mut!((*node).next), next) };
+    }
+
+    //  Sets the `prev` link of the node associated to `handle`, without creating a reference to the node.
+    //
+    //  #   Safety
+    //
+    //  -   `handle` must have been allocated by `store`, and still be valid.
+    //  -   `handle` must be associated with a memory block containing a valid instance of `Node`.
+    //  -   No reference to the `prev` link of the node must be active.
+    unsafe fn set_prev(handle: NodeHandle<T, S::Handle>, prev: NodeHandle<T, S::Handle>, store: &S) {
+        //  Safety:
+        //  -   As per pre-conditions.
+        let node = unsafe { handle.resolve_raw(store) }.as_ptr();
+
+        //  Safety:
+        //  -   `node` points to a valid instance of `Node`, as per pre-conditions.
+        //  -   No reference to `node.prev` is active, as per pre-conditions.
+        unsafe { ptr::write(ptr::addr_of_mut!((*node).prev), prev) };
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn handle_alloc_error() -> ! {
+        alloc::handle_alloc_error(Layout::new::<Node<T, S::Handle>>())
+    }
 }
 
 #[cfg(test)]
@@ -910,6 +1351,49 @@ mod allocator_tests {
         assert_eq!(0, list.len());
     }
 
+    #[test]
+    fn list_front_then_back() {
+        let mut list = TestList::new();
+
+        list.push_front(String::from("1"));
+        list.push_front(String::from("0"));
+
+        assert_eq!(Some("1"), list.pop_back().as_deref());
+        assert_eq!(Some("0"), list.back().map(|s| s.as_str()));
+        assert_eq!(Some("0"), list.pop_back().as_deref());
+
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn list_positional() {
+        let mut list: TestList = [0, 2, 4].iter().map(|i| i.to_string()).collect();
+
+        list.insert_at(1, String::from("1"));
+        list.insert_at(3, String::from("3"));
+        list.insert_at(0, String::from("-1"));
+        list.insert_at(6, String::from("5"));
+
+        assert_eq!(r#"["-1", "0", "1", "2", "3", "4", "5"]"#, format!("{list:?}"));
+
+        assert_eq!(Some("3"), list.get(4).map(|s| s.as_str()));
+        assert_eq!(None, list.get(7));
+
+        list.get_mut(4).unwrap().push('a');
+
+        assert_eq!(None, list.get_mut(7));
+
+        assert_eq!(Some("3a"), list.remove_at(4).as_deref());
+        assert_eq!(Some("-1"), list.remove_at(0).as_deref());
+        assert_eq!(Some("5"), list.remove_at(4).as_deref());
+        assert_eq!(None, list.remove_at(4));
+
+        assert_eq!(r#"["0", "1", "2", "4"]"#, format!("{list:?}"));
+        assert_eq!(Some("4"), list.pop_back().as_deref());
+        assert_eq!(Some("2"), list.pop_back().as_deref());
+        assert_eq!(Some("1"), list.back().map(|s| s.as_str()));
+    }
+
     #[test]
     fn list_clone() {
         let mut list = TestList::new();
@@ -957,6 +1441,7 @@ mod allocator_tests {
 
         assert_eq!(r#"["0", "1", "2", "3", "4", "5"]"#, format!("{list:?}"));
         assert_eq!(r#"[]"#, format!("{other:?}"));
+        assert_eq!(Some("5"), list.back().map(|s| s.as_str()));
     }
 
     #[cfg(feature = "alloc")]
@@ -1084,234 +1569,511 @@ mod allocator_tests {
 
         assert_eq!(r#"["0a", "1a", "2a"]"#, format!("{list:?}"));
     }
+
+    #[test]
+    fn list_memory_usage() {
+        let list: TestList = [0, 1, 2].iter().map(|i| i.to_string()).collect();
+
+        let usage = list.memory_usage();
+        let node = TestList::node_layout().size();
+
+        assert_eq!(3 * node, usage.bytes_allocated);
+        assert_eq!(3 * mem::size_of::<String>(), usage.bytes_used);
+        assert_eq!(3, usage.allocations);
+    }
 } // mod allocator_tests
 
+//  The suite is instantiated for each width of handle: the narrower the handle, the smaller the nodes, and the fewer
+//  bytes the block of memory may span.
 #[cfg(test)]
-mod inline_bump_tests {
-    use crate::store::InlineBumpStore;
+macro_rules! inline_bump_tests {
+    ($name:ident, $handle:ty) => {
+        mod $name {
+            use core::alloc::Layout;
 
-    use super::*;
+            use crate::store::InlineBumpStore;
 
-    type InlineLinkedList<T, H, const N: usize> = LinkedList<T, InlineBumpStore<H, [Node<T, H>; N]>>;
+            use super::*;
 
-    type TestList = InlineLinkedList<String, u8, 6>;
+            type TestHandle = $handle;
 
-    #[test]
-    fn list_empty() {
-        let list = TestList::new();
+            type TestList = InlineLinkedList<String, TestHandle, 6>;
 
-        assert!(list.is_empty());
-        assert_eq!(0, list.len());
-    }
+            #[test]
+            fn list_node_layout() {
+                let layout = TestList::node_layout();
 
-    #[test]
-    fn list_front() {
-        let mut list = TestList::new();
+                assert_eq!(Layout::new::<Node<String, TestHandle>>(), layout);
+                assert_eq!(
+                    6 * layout.size(),
+                    mem::size_of::<LinkedListBlock<String, TestHandle, 6>>()
+                );
+                assert_eq!(
+                    layout.align(),
+                    mem::align_of::<LinkedListBlock<String, TestHandle, 6>>()
+                );
+            }
 
-        list.try_push_front(String::from("0")).unwrap();
+            #[test]
+            fn list_empty() {
+                let list = TestList::new();
 
-        assert!(!list.is_empty());
-        assert_eq!(1, list.len());
-        assert_eq!(Some("0"), list.front().map(|s| s.as_str()));
+                assert!(list.is_empty());
+                assert_eq!(0, list.len());
+            }
 
-        if let Some(e) = list.front_mut() {
-            e.push('1');
-        }
+            #[test]
+            fn list_front() {
+                let mut list = TestList::new();
 
-        assert_eq!(Some("01"), list.pop_front().as_deref());
+                list.try_push_front(String::from("0")).unwrap();
 
-        assert!(list.is_empty());
-        assert_eq!(0, list.len());
-    }
+                assert!(!list.is_empty());
+                assert_eq!(1, list.len());
+                assert_eq!(Some("0"), list.front().map(|s| s.as_str()));
 
-    #[test]
-    fn list_front_multiple() {
-        let mut list = TestList::new();
+                if let Some(e) = list.front_mut() {
+                    e.push('1');
+                }
 
-        list.try_push_front(String::from("2")).unwrap();
-        list.try_push_front(String::from("1")).unwrap();
-        list.try_push_front(String::from("0")).unwrap();
+                assert_eq!(Some("01"), list.pop_front().as_deref());
 
-        assert!(!list.is_empty());
-        assert_eq!(3, list.len());
-        assert_eq!(Some("0"), list.front().map(|s| s.as_str()));
-        assert_eq!(Some("0"), list.pop_front().as_deref());
+                assert!(list.is_empty());
+                assert_eq!(0, list.len());
+            }
 
-        assert!(!list.is_empty());
-        assert_eq!(2, list.len());
-        assert_eq!(Some("1"), list.front().map(|s| s.as_str()));
-        assert_eq!(Some("1"), list.pop_front().as_deref());
+            #[test]
+            fn list_front_multiple() {
+                let mut list = TestList::new();
 
-        assert!(!list.is_empty());
-        assert_eq!(1, list.len());
-        assert_eq!(Some("2"), list.front().map(|s| s.as_str()));
-        assert_eq!(Some("2"), list.pop_front().as_deref());
+                list.try_push_front(String::from("2")).unwrap();
+                list.try_push_front(String::from("1")).unwrap();
+                list.try_push_front(String::from("0")).unwrap();
 
-        assert!(list.is_empty());
-        assert_eq!(0, list.len());
-    }
+                assert!(!list.is_empty());
+                assert_eq!(3, list.len());
+                assert_eq!(Some("0"), list.front().map(|s| s.as_str()));
+                assert_eq!(Some("0"), list.pop_front().as_deref());
 
-    #[test]
-    fn list_back() {
-        let mut list = TestList::new();
+                assert!(!list.is_empty());
+                assert_eq!(2, list.len());
+                assert_eq!(Some("1"), list.front().map(|s| s.as_str()));
+                assert_eq!(Some("1"), list.pop_front().as_deref());
 
-        list.try_push_back(String::from("0")).unwrap();
+                assert!(!list.is_empty());
+                assert_eq!(1, list.len());
+                assert_eq!(Some("2"), list.front().map(|s| s.as_str()));
+                assert_eq!(Some("2"), list.pop_front().as_deref());
 
-        assert!(!list.is_empty());
-        assert_eq!(1, list.len());
-        assert_eq!(Some("0"), list.back().map(|s| s.as_str()));
+                assert!(list.is_empty());
+                assert_eq!(0, list.len());
+            }
 
-        if let Some(e) = list.back_mut() {
-            e.push('1');
-        }
+            #[test]
+            fn list_back() {
+                let mut list = TestList::new();
 
-        assert_eq!(Some("01"), list.pop_back().as_deref());
+                list.try_push_back(String::from("0")).unwrap();
 
-        assert!(list.is_empty());
-        assert_eq!(0, list.len());
-    }
+                assert!(!list.is_empty());
+                assert_eq!(1, list.len());
+                assert_eq!(Some("0"), list.back().map(|s| s.as_str()));
 
-    #[test]
-    fn list_back_multiple() {
-        let mut list = TestList::new();
+                if let Some(e) = list.back_mut() {
+                    e.push('1');
+                }
 
-        list.try_push_back(String::from("2")).unwrap();
-        list.try_push_back(String::from("1")).unwrap();
-        list.try_push_back(String::from("0")).unwrap();
+                assert_eq!(Some("01"), list.pop_back().as_deref());
 
-        assert!(!list.is_empty());
-        assert_eq!(3, list.len());
-        assert_eq!(Some("0"), list.back().map(|s| s.as_str()));
-        assert_eq!(Some("0"), list.pop_back().as_deref());
+                assert!(list.is_empty());
+                assert_eq!(0, list.len());
+            }
 
-        assert!(!list.is_empty());
-        assert_eq!(2, list.len());
-        assert_eq!(Some("1"), list.back().map(|s| s.as_str()));
-        assert_eq!(Some("1"), list.pop_back().as_deref());
+            #[test]
+            fn list_back_multiple() {
+                let mut list = TestList::new();
 
-        assert!(!list.is_empty());
-        assert_eq!(1, list.len());
-        assert_eq!(Some("2"), list.back().map(|s| s.as_str()));
-        assert_eq!(Some("2"), list.pop_back().as_deref());
+                list.try_push_back(String::from("2")).unwrap();
+                list.try_push_back(String::from("1")).unwrap();
+                list.try_push_back(String::from("0")).unwrap();
 
-        assert!(list.is_empty());
-        assert_eq!(0, list.len());
-    }
+                assert!(!list.is_empty());
+                assert_eq!(3, list.len());
+                assert_eq!(Some("0"), list.back().map(|s| s.as_str()));
+                assert_eq!(Some("0"), list.pop_back().as_deref());
 
-    #[test]
-    fn list_clone() {
-        let mut list = TestList::new();
+                assert!(!list.is_empty());
+                assert_eq!(2, list.len());
+                assert_eq!(Some("1"), list.back().map(|s| s.as_str()));
+                assert_eq!(Some("1"), list.pop_back().as_deref());
 
-        list.try_push_front(String::from("2")).unwrap();
-        list.try_push_front(String::from("1")).unwrap();
-        list.try_push_front(String::from("0")).unwrap();
+                assert!(!list.is_empty());
+                assert_eq!(1, list.len());
+                assert_eq!(Some("2"), list.back().map(|s| s.as_str()));
+                assert_eq!(Some("2"), list.pop_back().as_deref());
 
-        let mut clone = list.clone();
+                assert!(list.is_empty());
+                assert_eq!(0, list.len());
+            }
 
-        assert_eq!(Some("0"), clone.pop_front().as_deref());
-        assert_eq!(Some("1"), clone.pop_front().as_deref());
-        assert_eq!(Some("2"), clone.pop_front().as_deref());
-        assert_eq!(None, clone.pop_front().as_deref());
+            #[test]
+            fn list_clone() {
+                let mut list = TestList::new();
 
-        assert_eq!(Some("0"), list.pop_front().as_deref());
-        assert_eq!(Some("1"), list.pop_front().as_deref());
-        assert_eq!(Some("2"), list.pop_front().as_deref());
-        assert_eq!(None, list.pop_front().as_deref());
-    }
+                list.try_push_front(String::from("2")).unwrap();
+                list.try_push_front(String::from("1")).unwrap();
+                list.try_push_front(String::from("0")).unwrap();
 
-    #[test]
-    fn list_from_array() {
-        let list = TestList::try_from([String::from("0"), String::from("1"), String::from("2")]).unwrap();
+                let mut clone = list.clone();
 
-        assert_eq!(r#"["0", "1", "2"]"#, format!("{list:?}"));
-    }
+                assert_eq!(Some("0"), clone.pop_front().as_deref());
+                assert_eq!(Some("1"), clone.pop_front().as_deref());
+                assert_eq!(Some("2"), clone.pop_front().as_deref());
+                assert_eq!(None, clone.pop_front().as_deref());
 
-    #[test]
-    fn list_partial_comparison() {
-        let one: InlineLinkedList<f32, u8, 3> = [0.1, 0.2, 0.3].try_into().unwrap();
-        let two: InlineLinkedList<f32, u8, 3> = [0.1, 0.2, f32::NAN].try_into().unwrap();
+                assert_eq!(Some("0"), list.pop_front().as_deref());
+                assert_eq!(Some("1"), list.pop_front().as_deref());
+                assert_eq!(Some("2"), list.pop_front().as_deref());
+                assert_eq!(None, list.pop_front().as_deref());
+            }
 
-        assert_eq!(one, one);
-        assert_ne!(one, two);
-        assert_ne!(two, two);
+            #[test]
+            fn list_try_clone_in() {
+                let list = TestList::try_from([String::from("0"), String::from("1"), String::from("2")]).unwrap();
 
-        assert_eq!(Some(cmp::Ordering::Equal), one.partial_cmp(&one));
-        assert_eq!(None, one.partial_cmp(&two));
-        assert_eq!(None, two.partial_cmp(&two));
-    }
+                let clone = list.try_clone_in(InlineBumpStore::default()).unwrap();
 
-    #[test]
-    fn list_comparison() {
-        let one: TestList = [String::from("0"), String::from("1"), String::from("2")]
-            .try_into()
-            .unwrap();
-        let two: TestList = [String::from("0"), String::from("1"), String::from("3")]
-            .try_into()
-            .unwrap();
+                assert_eq!(r#"["0", "1", "2"]"#, format!("{clone:?}"));
 
-        assert_eq!(one, one);
-        assert_ne!(one, two);
-        assert_eq!(two, two);
+                //  Leave room for only 2 nodes in the store.
+                let store = InlineBumpStore::default();
+                Store::allocate(&store, Layout::new::<[Node<String, TestHandle>; 4]>()).unwrap();
 
-        assert_eq!(cmp::Ordering::Equal, one.cmp(&one));
-        assert_eq!(cmp::Ordering::Less, one.cmp(&two));
-        assert_eq!(cmp::Ordering::Equal, two.cmp(&two));
-        assert_eq!(cmp::Ordering::Greater, two.cmp(&one));
-    }
+                assert!(list.try_clone_in(store).is_err());
+            }
+
+            #[test]
+            fn list_from_array() {
+                let list = TestList::try_from([String::from("0"), String::from("1"), String::from("2")]).unwrap();
+
+                assert_eq!(r#"["0", "1", "2"]"#, format!("{list:?}"));
+            }
+
+            #[test]
+            fn list_try_extend() {
+                let mut list = TestList::new();
+
+                list.push_back(String::from("0"));
+                list.push_front(String::from("1"));
+
+                list.try_extend((2..6).map(|i| i.to_string())).unwrap();
+
+                assert_eq!(r#"["1", "0", "2", "3", "4", "5"]"#, format!("{list:?}"));
+
+                //  The store is full.
+                assert!(list.try_extend([String::from("6")]).is_err());
+
+                assert_eq!(6, list.len());
+            }
+
+            #[test]
+            fn list_try_insert_at() {
+                let mut list = TestList::new();
+
+                list.try_extend((0..5).map(|i| i.to_string())).unwrap();
+                list.try_insert_at(2, String::from("a")).unwrap();
+
+                assert_eq!(r#"["0", "1", "a", "2", "3", "4"]"#, format!("{list:?}"));
+
+                //  The store is full.
+                assert!(list.try_insert_at(2, String::from("b")).is_err());
+
+                assert_eq!(Some("a"), list.remove_at(2).as_deref());
+                assert_eq!(r#"["0", "1", "2", "3", "4"]"#, format!("{list:?}"));
+            }
+
+            #[test]
+            fn list_partial_comparison() {
+                let one: InlineLinkedList<f32, TestHandle, 3> = [0.1, 0.2, 0.3].try_into().unwrap();
+                let two: InlineLinkedList<f32, TestHandle, 3> = [0.1, 0.2, f32::NAN].try_into().unwrap();
+
+                assert_eq!(one, one);
+                assert_ne!(one, two);
+                assert_ne!(two, two);
+
+                assert_eq!(Some(cmp::Ordering::Equal), one.partial_cmp(&one));
+                assert_eq!(None, one.partial_cmp(&two));
+                assert_eq!(None, two.partial_cmp(&two));
+            }
+
+            #[test]
+            fn list_comparison() {
+                let one: TestList = [String::from("0"), String::from("1"), String::from("2")]
+                    .try_into()
+                    .unwrap();
+                let two: TestList = [String::from("0"), String::from("1"), String::from("3")]
+                    .try_into()
+                    .unwrap();
+
+                assert_eq!(one, one);
+                assert_ne!(one, two);
+                assert_eq!(two, two);
+
+                assert_eq!(cmp::Ordering::Equal, one.cmp(&one));
+                assert_eq!(cmp::Ordering::Less, one.cmp(&two));
+                assert_eq!(cmp::Ordering::Equal, two.cmp(&two));
+                assert_eq!(cmp::Ordering::Greater, two.cmp(&one));
+            }
+
+            #[test]
+            fn list_extend_clone() {
+                let mut list = TestList::try_from([String::from("0"), String::from("1"), String::from("2")]).unwrap();
+
+                list.extend(&[String::from("3"), String::from("4"), String::from("5")]);
+
+                assert_eq!(r#"["0", "1", "2", "3", "4", "5"]"#, format!("{list:?}"));
+            }
+
+            #[test]
+            fn list_extend() {
+                let mut list = TestList::try_from([String::from("0"), String::from("1"), String::from("2")]).unwrap();
+
+                list.extend([String::from("3"), String::from("4"), String::from("5")]);
+
+                assert_eq!(r#"["0", "1", "2", "3", "4", "5"]"#, format!("{list:?}"));
+            }
+
+            #[test]
+            fn list_from_iterator() {
+                let list: TestList = [0, 1, 2].iter().map(|i| i.to_string()).collect();
+
+                assert_eq!(r#"["0", "1", "2"]"#, format!("{list:?}"));
+            }
+
+            #[test]
+            fn list_into_iter() {
+                let list: TestList = [0, 1, 2].iter().map(|i| i.to_string()).collect();
+
+                let v: Vec<_> = list.into_iter().collect();
+
+                assert_eq!(r#"["0", "1", "2"]"#, format!("{v:?}"));
+            }
+
+            #[test]
+            fn list_iter() {
+                let list: TestList = [0, 1, 2].iter().map(|i| i.to_string()).collect();
+
+                let v: Vec<_> = list.iter().collect();
+
+                assert_eq!(r#"["0", "1", "2"]"#, format!("{v:?}"));
+            }
+
+            #[test]
+            fn list_iter_len() {
+                let list: TestList = [0, 1, 2].iter().map(|i| i.to_string()).collect();
+
+                let mut iter = list.iter();
+
+                assert_eq!(3, iter.len());
+
+                iter.next();
+                iter.next_back();
+
+                assert_eq!(1, iter.len());
+
+                iter.next();
+
+                assert_eq!((0, Some(0)), iter.size_hint());
+                assert_eq!(None, iter.next());
+            }
+
+            #[test]
+            fn list_iter_mut() {
+                let mut list: TestList = [0, 1, 2].iter().map(|i| i.to_string()).collect();
+
+                let mut v: Vec<_> = list.iter_mut().collect();
+
+                for e in &mut v {
+                    e.push('a');
+                }
+
+                assert_eq!(r#"["0a", "1a", "2a"]"#, format!("{list:?}"));
+            }
+
+            #[test]
+            fn list_debug_validate() {
+                let mut list = TestList::new();
+
+                assert_eq!(Some(true), list.debug_validate());
+
+                list.try_push_back(String::from("0")).unwrap();
+                list.try_push_back(String::from("1")).unwrap();
+
+                //  The store cannot distinguish valid handles from dangling ones below its watermark.
+                assert_eq!(None, list.debug_validate());
+            }
+        } // mod $name
+    };
+}
+
+//  The handles of `coercible-metadata` embed a pointer, and 6 such nodes exceed the offsets representable by `u8`.
+#[cfg(all(test, not(feature = "coercible-metadata")))]
+inline_bump_tests!(inline_bump_tests_u8, u8);
+
+#[cfg(test)]
+inline_bump_tests!(inline_bump_tests_u16, u16);
+
+#[cfg(test)]
+inline_bump_tests!(inline_bump_tests_u32, u32);
+
+#[cfg(test)]
+inline_bump_tests!(inline_bump_tests_usize, usize);
+
+#[cfg(test)]
+mod stack_chain_tests {
+    use crate::store::{StackBumpBlock, StackChainStore};
+
+    use super::*;
+
+    type ChainLinkedList<'a, T> = LinkedList<T, StackChainStore<'a, [Node<T, usize>; 2], usize>>;
 
     #[test]
-    fn list_extend_clone() {
-        let mut list = TestList::try_from([String::from("0"), String::from("1"), String::from("2")]).unwrap();
+    fn list_spillover() {
+        let (first, second) = (StackBumpBlock::new(), StackBumpBlock::new());
+        let blocks = [&first, &second];
 
-        list.extend(&[String::from("3"), String::from("4"), String::from("5")]);
+        let mut list = ChainLinkedList::<'_, String>::new_in(StackBumpBlock::chain(&blocks));
 
-        assert_eq!(r#"["0", "1", "2", "3", "4", "5"]"#, format!("{list:?}"));
+        for i in 0..4 {
+            list.try_push_back(i.to_string()).unwrap();
+        }
+
+        assert!(list.try_push_back(String::from("4")).is_err());
+
+        assert_eq!(r#"["0", "1", "2", "3"]"#, format!("{list:?}"));
     }
 
     #[test]
-    fn list_extend() {
-        let mut list = TestList::try_from([String::from("0"), String::from("1"), String::from("2")]).unwrap();
+    fn list_try_clone() {
+        let (first, second) = (StackBumpBlock::new(), StackBumpBlock::new());
+        let blocks = [&first, &second];
 
-        list.extend([String::from("3"), String::from("4"), String::from("5")]);
+        let mut list = ChainLinkedList::<'_, String>::new_in(StackBumpBlock::chain(&blocks));
 
-        assert_eq!(r#"["0", "1", "2", "3", "4", "5"]"#, format!("{list:?}"));
+        list.try_push_back(String::from("0")).unwrap();
+        list.try_push_back(String::from("1")).unwrap();
+
+        let clone = list.try_clone().unwrap();
+
+        assert_eq!(r#"["0", "1"]"#, format!("{clone:?}"));
+
+        //  The clone shares the blocks, which are now full.
+        assert!(list.try_push_back(String::from("2")).is_err());
+        assert_eq!(Err(CloneError::Alloc(AllocError)), list.try_clone().map(|_| ()));
     }
 
     #[test]
-    fn list_from_iterator() {
-        let list: TestList = [0, 1, 2].iter().map(|i| i.to_string()).collect();
+    fn list_try_append() {
+        let (first, second, third) = (StackBumpBlock::new(), StackBumpBlock::new(), StackBumpBlock::new());
+        let (blocks, others) = ([&first, &second], [&third]);
 
-        assert_eq!(r#"["0", "1", "2"]"#, format!("{list:?}"));
+        let mut list = ChainLinkedList::<'_, String>::new_in(StackBumpBlock::chain(&blocks));
+        let mut foreign = ChainLinkedList::<'_, String>::new_in(StackBumpBlock::chain(&others));
+
+        list.try_push_back(String::from("0")).unwrap();
+        foreign.try_push_back(String::from("x")).unwrap();
+
+        assert_eq!(Err(()), list.try_append(&mut foreign));
+
+        //  A store created anew over the same blocks is not a share, yet has the same sharing token.
+        let mut other = ChainLinkedList::<'_, String>::new_in(StackBumpBlock::chain(&blocks));
+
+        other.try_push_back(String::from("1")).unwrap();
+
+        list.try_append(&mut other).unwrap();
+
+        assert_eq!(r#"["0", "1"]"#, format!("{list:?}"));
+        assert_eq!(r#"["x"]"#, format!("{foreign:?}"));
+        assert!(other.is_empty());
     }
+} // mod stack_chain_tests
+
+//  Exercises the raw access paths of the list: references to elements are kept alive across further iterations,
+//  lookups and clones, and mutable references obtained from both ends are used out of order.
+//
+//  Run with `RUSTFLAGS="--cfg storage_miri_strict" MIRIFLAGS="-Zmiri-tree-borrows" cargo miri test tests_miri`, and
+//  without `-Zmiri-tree-borrows` for Stacked Borrows.
+#[cfg(all(test, storage_miri_strict))]
+mod tests_miri {
+    use crate::store::{InlineBumpStore, RefStore};
+
+    use super::*;
+
+    type TestStore = InlineBumpStore<u16, LinkedListBlock<String, u16, 8>>;
+    type TestList<'a> = LinkedList<String, RefStore<'a, TestStore>>;
 
     #[test]
-    fn list_into_iter() {
-        let list: TestList = [0, 1, 2].iter().map(|i| i.to_string()).collect();
+    fn iter_mut_both_ends() {
+        let store = TestStore::default();
+        let mut list: TestList<'_> = LinkedList::new_in(RefStore::new(&store));
 
-        let v: Vec<_> = list.into_iter().collect();
+        list.extend((0..4).map(|i| i.to_string()));
 
-        assert_eq!(r#"["0", "1", "2"]"#, format!("{v:?}"));
+        let mut iter = list.iter_mut();
+
+        let (first, last) = (iter.next().unwrap(), iter.next_back().unwrap());
+        let (second, third) = (iter.next().unwrap(), iter.next_back().unwrap());
+
+        assert!(iter.next().is_none());
+
+        //  All references are live at once, and used in an order differing from their creation.
+        for element in [third, first, last, second] {
+            element.push('a');
+        }
+
+        assert_eq!(r#"["0a", "1a", "2a", "3a"]"#, format!("{list:?}"));
     }
 
     #[test]
-    fn list_iter() {
-        let list: TestList = [0, 1, 2].iter().map(|i| i.to_string()).collect();
+    fn shared_across_reads() {
+        let store = TestStore::default();
+        let mut list: TestList<'_> = LinkedList::new_in(RefStore::new(&store));
 
-        let v: Vec<_> = list.iter().collect();
+        list.extend((0..4).map(|i| i.to_string()));
 
-        assert_eq!(r#"["0", "1", "2"]"#, format!("{v:?}"));
+        let (front, back) = (list.get(0).unwrap(), list.get(3).unwrap());
+        let reversed: Vec<&String> = list.iter().rev().collect();
+
+        //  The following walk the links of the nodes, whilst references to the elements are live.
+        let clone = list.clone_in(RefStore::new(&store));
+
+        assert!(list.contains(&String::from("2")));
+        assert_eq!(Some("2"), list.get(2).map(String::as_str));
+
+        assert_eq!(("0", "3"), (front.as_str(), back.as_str()));
+        assert!(reversed.into_iter().rev().eq(clone.iter()));
     }
 
     #[test]
-    fn list_iter_mut() {
-        let mut list: TestList = [0, 1, 2].iter().map(|i| i.to_string()).collect();
+    fn relink() {
+        let store = TestStore::default();
+        let mut list: TestList<'_> = LinkedList::new_in(RefStore::new(&store));
+        let mut other: TestList<'_> = LinkedList::new_in(RefStore::new(&store));
 
-        let mut v: Vec<_> = list.iter_mut().collect();
+        list.push_back(String::from("1"));
+        list.push_front(String::from("0"));
+        list.insert_at(1, String::from("a"));
 
-        for e in &mut v {
-            e.push('a');
-        }
+        other.push_back(String::from("2"));
+        other.push_back(String::from("3"));
 
-        assert_eq!(r#"["0a", "1a", "2a"]"#, format!("{list:?}"));
+        list.try_append(&mut other).unwrap();
+
+        assert_eq!(Some("a"), list.remove_at(1).as_deref());
+        assert_eq!(Some("2"), list.remove_at(2).as_deref());
+
+        assert_eq!(r#"["0", "1", "3"]"#, format!("{list:?}"));
+        assert_eq!(Some("3"), list.pop_back().as_deref());
+        assert_eq!(Some("0"), list.pop_front().as_deref());
     }
-} // mod inline_bump_tests
+} // mod tests_miri
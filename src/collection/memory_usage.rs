@@ -0,0 +1,43 @@
+//! The memory footprint of a collection.
+//!
+//! Collections report their footprint from the layouts they requested, without any support from the store, so that
+//! applications may budget memory per data-structure even atop the simplest stores. The footprint thus excludes any
+//! rounding, or bookkeeping, performed by the store, as well as the collection itself, which may live anywhere.
+
+use core::ops;
+
+/// The memory footprint of a collection, as per the layouts it requested from its store.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct MemoryUsage {
+    /// The number of bytes of the blocks of memory allocated, as per the layouts requested.
+    pub bytes_allocated: usize,
+    /// The number of bytes occupied by the elements, a subset of `bytes_allocated`.
+    pub bytes_used: usize,
+    /// The number of blocks of memory allocated, excluding zero-sized ones.
+    pub allocations: usize,
+}
+
+impl MemoryUsage {
+    /// Returns the number of bytes allocated, yet not occupied by elements: spare capacity, links, headers, etc...
+    pub const fn overhead(&self) -> usize {
+        self.bytes_allocated - self.bytes_used
+    }
+}
+
+impl ops::Add for MemoryUsage {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            bytes_allocated: self.bytes_allocated + other.bytes_allocated,
+            bytes_used: self.bytes_used + other.bytes_used,
+            allocations: self.allocations + other.allocations,
+        }
+    }
+}
+
+impl ops::AddAssign for MemoryUsage {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
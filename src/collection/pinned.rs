@@ -0,0 +1,332 @@
+//! Stable-address wrapper for collections, projecting pins onto their elements.
+//!
+//! A `Pin<&mut StoreVec<T, S>>` is not sufficient to pin the elements of the vector: `StoreVec` is `Unpin`, hence the
+//! pinned reference may be turned back into a mutable reference, and the vector grown, moving its elements. Instead,
+//! `Pinned<C>` wraps a collection, and only exposes the operations which never move the elements of the collection, in
+//! exchange for pinned references to those elements.
+//!
+//! The elements of a collection have stable addresses when:
+//!
+//! -   The store implements `StorePinning`, so that moving the collection, and its store, does not move the blocks of
+//!     memory. This excludes inline stores, for example.
+//! -   The store is `'static`, so that forgetting the collection leaks the blocks of memory, rather than letting them
+//!     be repurposed once the lifetime of a borrowed store expires.
+//! -   The collection does not move the elements itself. `StoreVec` moves its elements when growing, hence
+//!     `Pinned<StoreVec<T, S>>` only appends elements within the current capacity, whereas `LinkedList` allocates each
+//!     element in its own node, hence `Pinned<LinkedList<T, S>>` may append elements freely.
+//!
+//! In either case, the elements are dropped in place, when cleared, or when the collection is dropped.
+//!
+//! The boxed value of a `StoreBox` is pinned by `StoreBox::into_pin`, under the same conditions on the store.
+
+use core::pin::Pin;
+
+use crate::{
+    collection::{LinkedList, StoreVec},
+    interface::{Store, StorePinning, StoreSingle},
+};
+
+/// A collection whose elements are pinned in memory.
+///
+/// Created by `StoreVec::into_pinned` or `LinkedList::into_pinned`; see the module documentation for the conditions on
+/// their stores.
+pub struct Pinned<C>(C);
+
+impl<C> Pinned<C> {
+    /// Returns a reference to the collection.
+    pub const fn get_ref(&self) -> &C {
+        &self.0
+    }
+}
+
+impl<T, S> StoreVec<T, S>
+where
+    S: StoreSingle + StorePinning + 'static,
+{
+    /// Pins the elements of the vector.
+    pub fn into_pinned(self) -> Pinned<Self> {
+        Pinned(self)
+    }
+}
+
+impl<T, S> Pinned<StoreVec<T, S>>
+where
+    S: StoreSingle + StorePinning + 'static,
+{
+    /// Returns the vector, unpinning its elements.
+    pub fn into_inner(self) -> StoreVec<T, S>
+    where
+        T: Unpin,
+    {
+        self.0
+    }
+
+    /// Returns the number of elements of the vector.
+    pub const fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether the vector is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the capacity of the vector, which is never exceeded once pinned.
+    pub const fn capacity(&self) -> usize {
+        self.0.capacity()
+    }
+
+    /// Returns a slice of the elements of the vector.
+    pub fn as_slice(&self) -> &[T] {
+        self.0.as_slice()
+    }
+
+    /// Returns a pinned mutable slice of the elements of the vector.
+    pub fn as_pinned_slice(&mut self) -> Pin<&mut [T]> {
+        let slice = self.0.as_mut_slice();
+
+        //  Safety:
+        //  -   The elements are never moved: the vector never grows, and the store is pinning.
+        //  -   The elements are dropped in place, by `clear` or by `StoreVec::drop`, before their memory is reused.
+        unsafe { Pin::new_unchecked(slice) }
+    }
+
+    /// Returns a pinned mutable reference to the element at `index`, if any.
+    pub fn get_pinned(&mut self, index: usize) -> Option<Pin<&mut T>> {
+        let element = self.0.get_mut(index)?;
+
+        //  Safety:
+        //  -   Same as `as_pinned_slice`.
+        Some(unsafe { Pin::new_unchecked(element) })
+    }
+
+    /// Appends an element at the back of the vector, if within its capacity.
+    ///
+    /// Returns the element if the vector is full, as growing the vector would move its elements.
+    pub fn push_within_capacity(&mut self, value: T) -> Result<(), T> {
+        if self.0.len() == self.0.capacity() {
+            return Err(value);
+        }
+
+        //  Safety:
+        //  -   `self.0.len() < self.0.capacity()`, as checked above.
+        unsafe { self.0.push_unchecked(value) };
+
+        Ok(())
+    }
+
+    /// Clears the vector, dropping its elements in place.
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+impl<T, S> From<StoreVec<T, S>> for Pinned<StoreVec<T, S>>
+where
+    S: StoreSingle + StorePinning + 'static,
+{
+    fn from(value: StoreVec<T, S>) -> Self {
+        value.into_pinned()
+    }
+}
+
+impl<T, S> LinkedList<T, S>
+where
+    S: Store + StorePinning + 'static,
+{
+    /// Pins the elements of the list.
+    pub fn into_pinned(self) -> Pinned<Self> {
+        Pinned(self)
+    }
+}
+
+impl<T, S> Pinned<LinkedList<T, S>>
+where
+    S: Store + StorePinning + 'static,
+{
+    /// Returns the list, unpinning its elements.
+    pub fn into_inner(self) -> LinkedList<T, S>
+    where
+        T: Unpin,
+    {
+        self.0
+    }
+
+    /// Returns the number of elements of the list.
+    pub const fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether the list is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns a pinned mutable reference to the front element, if any.
+    pub fn front_pinned(&mut self) -> Option<Pin<&mut T>> {
+        let element = self.0.front_mut()?;
+
+        //  Safety:
+        //  -   The elements are never moved: each lives in its own node, and the store is pinning.
+        //  -   The elements are dropped in place, by `clear` or by `LinkedList::drop`, before their memory is reused.
+        Some(unsafe { Pin::new_unchecked(element) })
+    }
+
+    /// Returns a pinned mutable reference to the back element, if any.
+    pub fn back_pinned(&mut self) -> Option<Pin<&mut T>> {
+        let element = self.0.back_mut()?;
+
+        //  Safety:
+        //  -   Same as `front_pinned`.
+        Some(unsafe { Pin::new_unchecked(element) })
+    }
+
+    /// Returns a pinned mutable reference to the element at `index`, if any.
+    ///
+    /// This operation is O(n), as it walks the list from the front.
+    pub fn get_pinned(&mut self, index: usize) -> Option<Pin<&mut T>> {
+        let element = self.0.get_mut(index)?;
+
+        //  Safety:
+        //  -   Same as `front_pinned`.
+        Some(unsafe { Pin::new_unchecked(element) })
+    }
+
+    /// Pushes an element to the front of the list.
+    ///
+    /// Calls `handle_alloc_error` if memory allocation fails.
+    pub fn push_front(&mut self, element: T) {
+        self.0.push_front(element);
+    }
+
+    /// Pushes an element to the back of the list.
+    ///
+    /// Calls `handle_alloc_error` if memory allocation fails.
+    pub fn push_back(&mut self, element: T) {
+        self.0.push_back(element);
+    }
+
+    /// Clears the list, dropping its elements in place.
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+impl<T, S> From<LinkedList<T, S>> for Pinned<LinkedList<T, S>>
+where
+    S: Store + StorePinning + 'static,
+{
+    fn from(value: LinkedList<T, S>) -> Self {
+        value.into_pinned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::marker::PhantomPinned;
+
+    use crate::collection::{utils::Global, StoreBox};
+
+    use super::*;
+
+    //  A value recording its own address when first checked, and comparing it on every subsequent check.
+    #[derive(Default)]
+    struct SelfAware {
+        address: Option<usize>,
+        _pinned: PhantomPinned,
+    }
+
+    impl SelfAware {
+        fn check(self: Pin<&mut Self>) -> bool {
+            let address = &*self as *const Self as usize;
+
+            //  Safety:
+            //  -   `this` is not moved, only `this.address`, which is not structurally pinned, is modified.
+            let this = unsafe { self.get_unchecked_mut() };
+
+            *this.address.get_or_insert(address) == address
+        }
+    }
+
+    #[test]
+    fn vec_within_capacity() {
+        let vec = StoreVec::<SelfAware, Global>::with_capacity(2);
+        let mut pinned = vec.into_pinned();
+
+        assert!(pinned.push_within_capacity(SelfAware::default()).is_ok());
+        assert!(pinned.get_pinned(0).unwrap().check());
+
+        assert!(pinned.push_within_capacity(SelfAware::default()).is_ok());
+        assert!(pinned.push_within_capacity(SelfAware::default()).is_err());
+        assert_eq!(2, pinned.capacity());
+
+        //  Moving the wrapper does not move the elements.
+        let mut moved = pinned;
+
+        assert_eq!(2, moved.as_pinned_slice().len());
+        assert!((0..2).all(|i| moved.get_pinned(i).unwrap().check()));
+        assert!(moved.get_pinned(2).is_none());
+
+        moved.clear();
+
+        assert!(moved.is_empty());
+    }
+
+    #[test]
+    fn list_push() {
+        let mut pinned = LinkedList::<SelfAware, Global>::new().into_pinned();
+
+        pinned.push_back(SelfAware::default());
+
+        assert!(pinned.front_pinned().unwrap().check());
+
+        //  Pushing does not move the existing elements.
+        for _ in 0..8 {
+            pinned.push_front(SelfAware::default());
+            pinned.push_back(SelfAware::default());
+        }
+
+        assert_eq!(17, pinned.len());
+        assert!(pinned.get_pinned(8).unwrap().check());
+        assert!(pinned.back_pinned().unwrap().check());
+
+        let mut moved = pinned;
+
+        assert!(moved.get_pinned(8).unwrap().check());
+
+        moved.clear();
+
+        assert!(moved.front_pinned().is_none());
+    }
+
+    #[test]
+    fn store_box() {
+        let mut boxed = StoreBox::<_, Global>::pin_in(SelfAware::default(), Global);
+
+        assert!(boxed.as_mut().check());
+
+        let mut moved = boxed;
+
+        assert!(moved.as_mut().check());
+
+        let slice: StoreBox<[u32], Global> = StoreBox::new_in([1, 2], Global).coerce();
+        let mut boxed = StoreBox::into_pin(slice);
+
+        boxed[1] = 3;
+
+        assert_eq!([1, 3], *boxed);
+    }
+
+    #[test]
+    fn into_inner() {
+        let pinned = StoreVec::<u32, Global>::from_iter([1, 2]).into_pinned();
+
+        assert_eq!([1, 2], pinned.as_slice());
+        assert_eq!([1, 2], &pinned.into_inner()[..]);
+
+        let pinned = Pinned::from(LinkedList::<u32, Global>::from_iter([1, 2]));
+
+        assert_eq!(2, pinned.get_ref().len());
+        assert_eq!(2, pinned.into_inner().len());
+    }
+} // mod tests
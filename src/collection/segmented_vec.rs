@@ -0,0 +1,789 @@
+//! Proof of concept segmented vector.
+//!
+//! A `SegmentedVec<T, S>` stores its elements in a chain of segments, each allocated separately from the store, rather
+//! than in a single array. When full, a new segment is chained after the existing ones, and no element is ever moved.
+//!
+//! This trades contiguity for growth without copying and without relocation. In stores where growing a block requires
+//! relocating it, such as bump stores when the block is not the last one allocated, each relocation of a `StoreVec`
+//! leaves the old block behind as dead memory; a `SegmentedVec` leaves none, which makes it particularly suitable for
+//! append-heavy logs in fixed regions of memory.
+//!
+//! The segments double in capacity, so that an index is mapped to its segment in O(1), and the number of segments is
+//! logarithmic in the number of elements. As a consequence, the growth policy of the store is not consulted.
+
+use core::{
+    alloc::{AllocError, Layout},
+    fmt, iter,
+    marker::PhantomData,
+    mem::{self, MaybeUninit},
+    ops,
+    ptr::{self, Alignment, NonNull},
+};
+
+use crate::{
+    alloc,
+    collection::MemoryUsage,
+    interface::{Store, StoreStable},
+};
+
+//  The capacity of segment `k` is `FIRST << k` elements, hence `usize::BITS - 1` segments exceed any address space.
+const MAX_SEGMENTS: usize = usize::BITS as usize - 1;
+
+/// A vector which chains new segments, rather than relocating its elements, when growing.
+pub struct SegmentedVec<T, S: Store> {
+    //  Type invariants:
+    //  -   `self.length <= self.capacity()`.
+    //  -   `self.handles[..self.allocated]` are initialized, each associated to a block of `self.store` fitting
+    //      `Self::segment_layout(k)`, or dangling if `T` is zero-sized.
+    //  -   The first `self.length` elements, spread across the segments in order, are initialized.
+    length: usize,
+    allocated: usize,
+    handles: [MaybeUninit<S::Handle>; MAX_SEGMENTS],
+    store: S,
+    _marker: PhantomData<T>,
+}
+
+impl<T, S: Store + Default> SegmentedVec<T, S> {
+    /// Creates a new, empty, instance.
+    pub fn new() -> Self {
+        Self::new_in(S::default())
+    }
+
+    /// Creates a new, empty, instance with at least the specified capacity.
+    ///
+    /// Calls `handle_alloc_error` if the store cannot accommodate `capacity` elements.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_in(capacity, S::default())
+    }
+}
+
+impl<T, S: Store> SegmentedVec<T, S> {
+    /// Creates a new, empty, instance with the specified `store`.
+    pub const fn new_in(store: S) -> Self {
+        Self {
+            length: 0,
+            allocated: 0,
+            handles: [MaybeUninit::uninit(); MAX_SEGMENTS],
+            store,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Creates a new, empty, instance with at least the specified `capacity`, and the specified `store`.
+    ///
+    /// Calls `handle_alloc_error` if the store cannot accommodate `capacity` elements.
+    pub fn with_capacity_in(capacity: usize, store: S) -> Self {
+        let mut this = Self::new_in(store);
+
+        this.reserve(capacity);
+
+        this
+    }
+
+    /// Returns whether the vector is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Returns the number of elements in the vector.
+    pub const fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Returns the capacity of the vector, across all its segments.
+    pub const fn capacity(&self) -> usize {
+        Self::segment_start(self.allocated)
+    }
+
+    /// Returns the number of segments allocated.
+    pub const fn segment_count(&self) -> usize {
+        self.allocated
+    }
+
+    /// Returns the memory footprint of the vector, as per the layouts of its segments.
+    pub const fn memory_usage(&self) -> MemoryUsage {
+        let bytes_allocated = self.capacity() * mem::size_of::<T>();
+        let bytes_used = self.length * mem::size_of::<T>();
+        let allocations = if mem::size_of::<T>() > 0 { self.allocated } else { 0 };
+
+        MemoryUsage {
+            bytes_allocated,
+            bytes_used,
+            allocations,
+        }
+    }
+
+    /// Reserves capacity for at least `additional` more elements, chaining as many segments as necessary.
+    ///
+    /// #   Panics
+    ///
+    /// Panics if a new segment would exceed `isize::MAX` bytes.
+    ///
+    /// Calls `handle_alloc_error` if the store fails to provide a new segment.
+    pub fn reserve(&mut self, additional: usize) {
+        let Some(target_capacity) = self.length.checked_add(additional) else {
+            Self::capacity_exceeded()
+        };
+
+        while self.capacity() < target_capacity {
+            let Some(layout) = Self::segment_layout(self.allocated) else {
+                Self::capacity_exceeded()
+            };
+
+            if self.try_chain_segment().is_err() {
+                alloc::handle_alloc_error(layout)
+            }
+        }
+    }
+
+    /// Attempts to reserve capacity for at least `additional` more elements, chaining as many segments as necessary.
+    ///
+    /// Returns an error if a new segment would exceed `isize::MAX` bytes, or if the store fails to provide it. The
+    /// segments chained before the failure, if any, are kept.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), AllocError> {
+        let target_capacity = self.length.checked_add(additional).ok_or(AllocError)?;
+
+        while self.capacity() < target_capacity {
+            self.try_chain_segment()?;
+        }
+
+        Ok(())
+    }
+
+    /// Appends an element at the back of the vector.
+    ///
+    /// No element is moved, even if a new segment is chained.
+    ///
+    /// #   Panics
+    ///
+    /// Panics if a new segment would exceed `isize::MAX` bytes.
+    ///
+    /// Calls `handle_alloc_error` if the store fails to provide a new segment.
+    pub fn push(&mut self, value: T) {
+        if self.length == self.capacity() {
+            self.reserve(1);
+        }
+
+        //  Safety:
+        //  -   `self.length < self.capacity()`, after reserving.
+        unsafe { self.push_unchecked(value) };
+    }
+
+    /// Attempts to append an element at the back of the vector.
+    ///
+    /// Returns the element if the store fails to provide a new segment.
+    pub fn try_push(&mut self, value: T) -> Result<(), T> {
+        if self.length == self.capacity() && self.try_chain_segment().is_err() {
+            return Err(value);
+        }
+
+        //  Safety:
+        //  -   `self.length < self.capacity()`, after chaining a new segment.
+        unsafe { self.push_unchecked(value) };
+
+        Ok(())
+    }
+
+    /// Removes the last element of the vector, if any.
+    ///
+    /// The segments are kept, see `shrink_to_fit` to release them.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.length == 0 {
+            return None;
+        }
+
+        self.length -= 1;
+
+        //  Safety:
+        //  -   `self.length < self.capacity()`.
+        let slot = unsafe { self.slot(self.length) };
+
+        //  Safety:
+        //  -   The element at `self.length` was initialized, and is no longer considered so.
+        Some(unsafe { ptr::read(slot.as_ptr()) })
+    }
+
+    /// Shortens the vector to `len` elements, dropping the others, last first.
+    ///
+    /// Does nothing if `len` is greater than or equal to `self.len()`. The segments are kept, see `shrink_to_fit` to
+    /// release them.
+    pub fn truncate(&mut self, len: usize) {
+        while self.length > len {
+            drop(self.pop());
+        }
+    }
+
+    /// Clears the vector, dropping all its elements, segment by segment.
+    ///
+    /// The segments are kept, see `shrink_to_fit` to release them.
+    pub fn clear(&mut self) {
+        let length = mem::replace(&mut self.length, 0);
+
+        //  The elements are considered dropped even if dropping one of them panics, leaking the others.
+        for segment in 0..self.allocated {
+            let start = Self::segment_start(segment);
+
+            if start >= length {
+                break;
+            }
+
+            let count = (length - start).min(Self::segment_capacity(segment));
+
+            //  Safety:
+            //  -   `segment < self.allocated`.
+            let elements = unsafe { self.segment(segment, count) };
+
+            //  Safety:
+            //  -   The `count` first elements of `segment` are initialized, and will not be accessed again.
+            unsafe { ptr::drop_in_place(elements.as_ptr()) };
+        }
+    }
+
+    /// Releases the trailing segments which hold no element, last first.
+    ///
+    /// Releasing the segments in the reverse order of their allocation allows bump stores to reclaim their memory, as
+    /// long as no other block was allocated since.
+    pub fn shrink_to_fit(&mut self) {
+        while self.allocated > 0 && Self::segment_start(self.allocated - 1) >= self.length {
+            self.allocated -= 1;
+
+            //  Safety:
+            //  -   `self.allocated` was less than the former `self.allocated`, hence the segment is allocated, and
+            //      holds no element.
+            unsafe { self.release_segment(self.allocated) };
+        }
+    }
+}
+
+impl<T, S: Store + StoreStable> SegmentedVec<T, S> {
+    /// Returns a reference to the element at `index`, if any.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.length {
+            return None;
+        }
+
+        //  Safety:
+        //  -   `index < self.length <= self.capacity()`.
+        let slot = unsafe { self.slot(index) };
+
+        //  Safety:
+        //  -   The element at `index` is initialized, as `index < self.length`.
+        //  -   The reference extends the borrow of `self`, guaranteeing that `self.store` will not be moved, whilst
+        //      `StoreStable` guarantees that resolving other segments will not invalidate it.
+        Some(unsafe { slot.as_ref() })
+    }
+
+    /// Returns a mutable reference to the element at `index`, if any.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.length {
+            return None;
+        }
+
+        //  Safety:
+        //  -   `index < self.length <= self.capacity()`.
+        let mut slot = unsafe { self.slot(index) };
+
+        //  Safety:
+        //  -   Same as `get`, with exclusive access guaranteed by `self` being borrowed mutably.
+        Some(unsafe { slot.as_mut() })
+    }
+
+    /// Returns an iterator over the segments, as slices of their elements.
+    pub fn segments(&self) -> Segments<'_, T, S> {
+        Segments { vec: self, segment: 0 }
+    }
+
+    /// Returns an iterator over the segments, as mutable slices of their elements.
+    pub fn segments_mut(&mut self) -> SegmentsMut<'_, T, S> {
+        SegmentsMut {
+            vec: self,
+            segment: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns an iterator of references to the elements.
+    pub fn iter(&self) -> iter::Flatten<Segments<'_, T, S>> {
+        self.segments().flatten()
+    }
+
+    /// Returns an iterator of mutable references to the elements.
+    pub fn iter_mut(&mut self) -> iter::Flatten<SegmentsMut<'_, T, S>> {
+        self.segments_mut().flatten()
+    }
+}
+
+impl<T, S: Store + Default> Default for SegmentedVec<T, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, S: Store> Drop for SegmentedVec<T, S> {
+    fn drop(&mut self) {
+        self.clear();
+
+        while self.allocated > 0 {
+            self.allocated -= 1;
+
+            //  Safety:
+            //  -   `self.allocated` was less than the former `self.allocated`, and the vector is empty.
+            unsafe { self.release_segment(self.allocated) };
+        }
+    }
+}
+
+impl<T: fmt::Debug, S: Store + StoreStable> fmt::Debug for SegmentedVec<T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T, S: Store + StoreStable> ops::Index<usize> for SegmentedVec<T, S> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        let length = self.length;
+
+        self.get(index)
+            .unwrap_or_else(|| panic!("index {index} should be < len {length}"))
+    }
+}
+
+impl<T, S: Store + StoreStable> ops::IndexMut<usize> for SegmentedVec<T, S> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        let length = self.length;
+
+        self.get_mut(index)
+            .unwrap_or_else(|| panic!("index {index} should be < len {length}"))
+    }
+}
+
+/// Appends the elements, in order, reserving capacity for the lower bound of the size hint upfront.
+impl<T, S: Store> Extend<T> for SegmentedVec<T, S> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let iter = iter.into_iter();
+
+        let (lower, _) = iter.size_hint();
+
+        self.reserve(lower);
+
+        for element in iter {
+            self.push(element);
+        }
+    }
+}
+
+impl<T, S: Store + Default> FromIterator<T> for SegmentedVec<T, S> {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut this = Self::new();
+
+        this.extend(iter);
+
+        this
+    }
+}
+
+impl<'a, T, S: Store + StoreStable> IntoIterator for &'a SegmentedVec<T, S> {
+    type Item = &'a T;
+    type IntoIter = iter::Flatten<Segments<'a, T, S>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, S: Store + StoreStable> IntoIterator for &'a mut SegmentedVec<T, S> {
+    type Item = &'a mut T;
+    type IntoIter = iter::Flatten<SegmentsMut<'a, T, S>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// An iterator over the segments of a `SegmentedVec`, as slices of their elements.
+pub struct Segments<'a, T, S: Store> {
+    vec: &'a SegmentedVec<T, S>,
+    segment: usize,
+}
+
+impl<'a, T, S: Store + StoreStable> Iterator for Segments<'a, T, S> {
+    type Item = &'a [T];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let elements = self.vec.next_segment(&mut self.segment)?;
+
+        //  Safety:
+        //  -   The elements are initialized, and the slice extends the borrow of `self.vec`, see `get`.
+        Some(unsafe { elements.as_ref() })
+    }
+}
+
+/// An iterator over the segments of a `SegmentedVec`, as mutable slices of their elements.
+pub struct SegmentsMut<'a, T, S: Store> {
+    //  Borrowed mutably for `'a`, the mutable slices are handed out from the shared re-borrow.
+    vec: &'a SegmentedVec<T, S>,
+    segment: usize,
+    _marker: PhantomData<&'a mut T>,
+}
+
+impl<'a, T, S: Store + StoreStable> Iterator for SegmentsMut<'a, T, S> {
+    type Item = &'a mut [T];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut elements = self.vec.next_segment(&mut self.segment)?;
+
+        //  Safety:
+        //  -   The elements are initialized, and the slice extends the mutable borrow of the vector, see `get_mut`.
+        //  -   Each segment is handed out at most once, hence the slices are disjoint.
+        Some(unsafe { elements.as_mut() })
+    }
+}
+
+//
+//  Implementation
+//
+
+impl<T, S: Store> SegmentedVec<T, S> {
+    //  The capacity of the first segment, in number of elements, as per `RawVec`, or unbounded for zero-sized `T`.
+    const FIRST: usize = match mem::size_of::<T>() {
+        0 => usize::MAX,
+        1 => 8,
+        n if n <= 1024 => 4,
+        _ => 1,
+    };
+
+    //  Returns the index of the first element of `segment`, which is also the capacity of the segments before it.
+    const fn segment_start(segment: usize) -> usize {
+        Self::FIRST * ((1 << segment) - 1)
+    }
+
+    const fn segment_capacity(segment: usize) -> usize {
+        Self::FIRST << segment
+    }
+
+    fn segment_layout(segment: usize) -> Option<Layout> {
+        if segment >= MAX_SEGMENTS {
+            return None;
+        }
+
+        let capacity = Self::FIRST.checked_mul(1 << segment)?;
+
+        Layout::array::<T>(capacity).ok()
+    }
+
+    //  Returns the segment, and the offset within it, of the element at `index`.
+    const fn locate(index: usize) -> (usize, usize) {
+        let segment = (index / Self::FIRST + 1).ilog2() as usize;
+
+        (segment, index - Self::segment_start(segment))
+    }
+
+    //  Resolves the first `count` slots of `segment`.
+    //
+    //  #   Safety
+    //
+    //  -   `segment` must be less than `self.allocated`.
+    //  -   `count` must be less than or equal to `Self::segment_capacity(segment)`.
+    unsafe fn segment(&self, segment: usize, count: usize) -> NonNull<[T]> {
+        debug_assert!(segment < self.allocated);
+        debug_assert!(count <= Self::segment_capacity(segment));
+
+        //  Safety:
+        //  -   `self.handles[segment]` is initialized, as `segment < self.allocated`.
+        let handle = unsafe { self.handles[segment].assume_init() };
+
+        //  Safety:
+        //  -   `handle` was allocated by `self.store`, or is dangling, and is still valid.
+        let pointer = unsafe { self.store.resolve(handle) };
+
+        NonNull::slice_from_raw_parts(pointer.cast(), count)
+    }
+
+    //  Resolves the slot at `index`.
+    //
+    //  #   Safety
+    //
+    //  -   `index` must be less than `self.capacity()`.
+    unsafe fn slot(&self, index: usize) -> NonNull<T> {
+        debug_assert!(index < self.capacity());
+
+        let (segment, offset) = Self::locate(index);
+
+        //  Safety:
+        //  -   `segment < self.allocated`, as `index < self.capacity()`.
+        //  -   `offset < Self::segment_capacity(segment)`, by construction.
+        let elements = unsafe { self.segment(segment, offset + 1) };
+
+        //  Safety:
+        //  -   `offset` is within `elements`.
+        unsafe { elements.as_non_null_ptr().add(offset) }
+    }
+
+    //  Returns the initialized elements of `*segment`, if any, and moves on to the next segment.
+    fn next_segment(&self, segment: &mut usize) -> Option<NonNull<[T]>> {
+        let start = Self::segment_start(*segment);
+
+        if *segment >= self.allocated || start >= self.length {
+            return None;
+        }
+
+        let count = (self.length - start).min(Self::segment_capacity(*segment));
+
+        //  Safety:
+        //  -   `*segment < self.allocated`.
+        //  -   `count <= Self::segment_capacity(*segment)`.
+        let elements = unsafe { self.segment(*segment, count) };
+
+        *segment += 1;
+
+        Some(elements)
+    }
+
+    //  Appends an element.
+    //
+    //  #   Safety
+    //
+    //  -   `self.length` must be less than `self.capacity()`.
+    unsafe fn push_unchecked(&mut self, value: T) {
+        //  Safety:
+        //  -   `self.length < self.capacity()`, as per pre-conditions.
+        let slot = unsafe { self.slot(self.length) };
+
+        //  Safety:
+        //  -   The slot at `self.length` is not initialized.
+        unsafe { ptr::write(slot.as_ptr(), value) };
+
+        self.length += 1;
+    }
+
+    //  Chains a new segment, twice the capacity of the last one.
+    //
+    //  On failure, the vector is left untouched.
+    fn try_chain_segment(&mut self) -> Result<(), AllocError> {
+        let layout = Self::segment_layout(self.allocated).ok_or(AllocError)?;
+
+        let handle = if layout.size() == 0 {
+            self.store.dangling(Alignment::of::<T>())?
+        } else {
+            self.store.allocate(layout)?.0
+        };
+
+        self.handles[self.allocated].write(handle);
+        self.allocated += 1;
+
+        Ok(())
+    }
+
+    //  #   Safety
+    //
+    //  -   `segment` must have been allocated, and hold no element.
+    //  -   `segment` must be considered released: it must be at or past `self.allocated`.
+    unsafe fn release_segment(&mut self, segment: usize) {
+        debug_assert!(segment >= self.allocated);
+
+        let layout = Self::segment_layout(segment);
+
+        debug_assert!(layout.is_some());
+
+        //  Safety:
+        //  -   The layout was computed successfully when `segment` was allocated, as per pre-conditions.
+        let layout = unsafe { layout.unwrap_unchecked() };
+
+        if layout.size() == 0 {
+            return;
+        }
+
+        //  Safety:
+        //  -   `self.handles[segment]` is initialized, as the segment was allocated.
+        let handle = unsafe { self.handles[segment].assume_init() };
+
+        //  Safety:
+        //  -   `handle` was allocated by `self.store`, with `layout`, and is still valid.
+        unsafe { self.store.deallocate(handle, layout) };
+    }
+
+    #[inline(never)]
+    fn capacity_exceeded() -> ! {
+        panic!("New segment exceeds isize::MAX bytes")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::Cell;
+
+    use crate::{
+        collection::{utils::Global, StoreVec},
+        store::{InlineBumpStore, RefStore, TrackingStore},
+    };
+
+    use super::*;
+
+    type Bump = InlineBumpStore<u16, [u32; 256]>;
+
+    #[test]
+    fn push_pop() {
+        let mut vec = SegmentedVec::<u32, Global>::new();
+
+        assert!(vec.is_empty());
+        assert_eq!(None, vec.pop());
+
+        vec.extend(0..100);
+
+        assert_eq!(100, vec.len());
+        assert_eq!(124, vec.capacity());
+        assert_eq!(5, vec.segment_count());
+        let lengths: Vec<_> = vec.segments().map(<[u32]>::len).collect();
+
+        assert_eq!([4, 8, 16, 32, 40], *lengths);
+
+        assert!(vec.iter().copied().eq(0..100));
+        assert_eq!(Some(&57), vec.get(57));
+        assert_eq!(None, vec.get(100));
+
+        vec.iter_mut().for_each(|n| *n *= 2);
+        vec[3] = 7;
+
+        assert_eq!(7, vec[3]);
+        assert_eq!(198, vec[99]);
+        assert_eq!(Some(198), vec.pop());
+        assert_eq!(99, vec.len());
+    }
+
+    #[test]
+    fn no_relocation() {
+        let mut vec = SegmentedVec::<u32, Bump>::new();
+
+        vec.push(42);
+
+        let first: *const u32 = &vec[0];
+
+        vec.extend(0..200);
+
+        assert_eq!(first, &vec[0] as *const u32);
+        assert_eq!(42, vec[0]);
+    }
+
+    #[test]
+    fn interleaved() {
+        //  Two vectors growing in turn within a fixed region: each growth of a `StoreVec` relocates its elements, and
+        //  leaves its previous block behind, whereas a `SegmentedVec` leaves nothing behind.
+        fn fill<V>(mut left: V, mut right: V, push: fn(&mut V, u32) -> Result<(), u32>) -> u32 {
+            let mut count = 0;
+
+            while push(&mut left, count).is_ok() && push(&mut right, count).is_ok() {
+                count += 1;
+            }
+
+            count
+        }
+
+        let bump = Bump::default();
+
+        let segmented = fill(
+            SegmentedVec::new_in(RefStore::new(&bump)),
+            SegmentedVec::new_in(RefStore::new(&bump)),
+            SegmentedVec::try_push,
+        );
+
+        let bump = Bump::default();
+
+        let contiguous = fill(
+            StoreVec::new_in(RefStore::new(&bump)),
+            StoreVec::new_in(RefStore::new(&bump)),
+            StoreVec::try_push,
+        );
+
+        assert_eq!(124, segmented);
+        assert!(contiguous < segmented / 2, "{contiguous}");
+    }
+
+    #[test]
+    fn shrink_to_fit() {
+        let tracking = TrackingStore::<Bump, 8>::new(Bump::default());
+
+        let mut vec = SegmentedVec::<u32, _>::new_in(RefStore::new(&tracking));
+
+        vec.extend(0..20);
+
+        assert_eq!(3, tracking.len());
+        assert_eq!(3, vec.memory_usage().allocations);
+        assert_eq!(80, vec.memory_usage().bytes_used);
+        assert_eq!(112, vec.memory_usage().bytes_allocated);
+
+        vec.truncate(4);
+        vec.shrink_to_fit();
+
+        assert_eq!(1, tracking.len());
+        assert_eq!(4, vec.capacity());
+
+        drop(vec);
+
+        assert_eq!(0, tracking.len());
+    }
+
+    #[test]
+    fn try_reserve() {
+        let mut vec = SegmentedVec::<u32, Bump>::new();
+
+        assert!(vec.try_reserve(300).is_err());
+        assert_eq!(252, vec.capacity());
+
+        vec.extend(0..252);
+
+        assert_eq!(Err(252), vec.try_push(252));
+        assert_eq!(252, vec.len());
+    }
+
+    #[test]
+    fn zero_sized() {
+        let mut vec = SegmentedVec::<(), Bump>::new();
+
+        vec.extend(iter::repeat(()).take(1000));
+
+        assert_eq!(1000, vec.len());
+        assert_eq!(usize::MAX, vec.capacity());
+        assert_eq!(0, vec.memory_usage().allocations);
+        assert_eq!(1000, vec.iter().count());
+        assert_eq!(Some(()), vec.pop());
+    }
+
+    #[test]
+    fn drops() {
+        struct Counted<'a>(&'a Cell<usize>);
+
+        impl Drop for Counted<'_> {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        let dropped = Cell::new(0);
+
+        let mut vec = SegmentedVec::<_, Global>::new();
+
+        vec.extend((0..30).map(|_| Counted(&dropped)));
+
+        drop(vec.pop());
+
+        assert_eq!(1, dropped.get());
+
+        vec.clear();
+
+        assert_eq!(30, dropped.get());
+
+        vec.extend((0..10).map(|_| Counted(&dropped)));
+
+        drop(vec);
+
+        assert_eq!(40, dropped.get());
+    }
+} // mod tests
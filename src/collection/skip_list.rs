@@ -3,10 +3,11 @@
 //! The implementation is incomplete, only intended to demonstrate why thin pointers matter.
 
 use core::{
-    alloc::Layout,
+    alloc::{AllocError, Layout},
     cmp,
+    iter::FusedIterator,
     marker::PhantomData,
-    mem,
+    mem::{self, MaybeUninit},
     ptr::{self, NonNull},
     slice,
 };
@@ -14,11 +15,16 @@ use core::{
 use oorandom::Rand32;
 
 use crate::{
-    extension::{typed::TypedHandle, typed_metadata::TypedMetadata},
-    interface::{Store, StoreStable},
+    alloc,
+    collection::{CloneError, MemoryUsage},
+    extension::header_slice::HeaderSliceHandle,
+    interface::{Store, StoreSharing, StoreStable},
 };
 
 /// A Skip List, with minimal memory usage.
+///
+/// Each link is paired with its span, the number of elements it skips over, which allows positional access, via `nth`
+/// and `rank`, in a logarithmic number of steps on average.
 pub struct SkipList<K, V, S: Store> {
     //  Invariant: `length == 0` => `head` is a dangling handle.
     length: usize,
@@ -62,6 +68,89 @@ impl<K, V, S: Store> SkipList<K, V, S> {
         self.length
     }
 
+    /// Returns the memory footprint of the list, as per the layouts of its nodes.
+    ///
+    /// The nodes have a varying number of links, hence all the nodes are visited.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        let mut bytes_allocated = 0;
+        let mut handle = self.head;
+
+        for _ in 0..self.length {
+            //  Safety:
+            //  -   `handle` has been allocated by `self.store`.
+            //  -   `handle` is valid, since `length` nodes exist.
+            //  -   Access to the node is shared, as `self` is borrowed immutably.
+            let node = unsafe { handle.resolve(&self.store) };
+
+            let layout = NodeHandle::<K, V, S::Handle>::layout(node.number_links.into());
+
+            debug_assert!(layout.is_ok());
+
+            //  Safety:
+            //  -   The layout was computed successfully when the node was allocated.
+            let (layout, _) = unsafe { layout.unwrap_unchecked() };
+
+            bytes_allocated += layout.size();
+
+            //  All nodes have at least one link; that of the last node is dangling, and unused.
+            handle = node.links()[0].next;
+        }
+
+        let bytes_used = self.length * (mem::size_of::<K>() + mem::size_of::<V>());
+
+        MemoryUsage {
+            bytes_allocated,
+            bytes_used,
+            allocations: self.length,
+        }
+    }
+
+    /// Decomposes the list into its length, the handle of its first node, and its store.
+    ///
+    /// The nodes are neither dropped nor deallocated, the list can be recomposed with `from_raw_parts`.
+    pub fn into_raw_parts(self) -> (usize, S::Handle, S) {
+        let this = mem::ManuallyDrop::new(self);
+
+        //  Safety:
+        //  -   `this.store` is valid for reads.
+        //  -   `this` is never used, nor dropped, afterwards.
+        let store = unsafe { ptr::read(&this.store) };
+
+        (this.length, this.head.to_raw_parts(), store)
+    }
+
+    /// Recomposes a list from its length, the handle of its first node, and its store.
+    ///
+    /// Since handles, unlike pointers, need not be addresses, the list may be recomposed in another process, as long as
+    /// `store` resolves the handles to the same nodes, for example by providing the same block of shared memory.
+    ///
+    /// #   Safety
+    ///
+    /// -   `length` and `head` must have been obtained from `into_raw_parts`.
+    /// -   `store` must resolve all handles of the list, and allocate and deallocate them, as the original store did.
+    /// -   The list must not have been recomposed since, unless that list was decomposed again.
+    pub unsafe fn from_raw_parts(length: usize, head: S::Handle, store: S) -> Self {
+        let head = NodeHandle::from_raw_parts(head);
+
+        let seed = if length == 0 {
+            0
+        } else {
+            //  Safety:
+            //  -   `head` was allocated by `store`, as per pre-conditions.
+            //  -   `head` is still valid, as per pre-conditions, since the list is not empty.
+            unsafe { head.resolve_raw(&store) }.as_ptr() as usize as u64
+        };
+
+        let prng = Rand32::new(seed);
+
+        Self {
+            length,
+            head,
+            store,
+            prng,
+        }
+    }
+
     /// Clears the list, destroying any node.
     ///
     /// Afterwards, the list is empty.
@@ -89,7 +178,7 @@ impl<K, V, S: Store> SkipList<K, V, S> {
 
                 //  Safety:
                 //  -   All nodes have at least one link.
-                unsafe { *links.get_unchecked(0) }
+                unsafe { links.get_unchecked(0).next }
             };
 
             //  Safety:
@@ -135,12 +224,85 @@ where
         })
     }
 
+    /// Returns the number of keys in the list which compare less than `key`.
+    ///
+    /// If `key` is in the list, this is its position, as accepted by `nth`.
+    pub fn rank(&self, key: &K) -> usize {
+        if self.length == 0 {
+            return 0;
+        }
+
+        //  Safety:
+        //  -   `self.head` was allocated by `self.store.`
+        //  -   `self.head` is still valid, notably it is not dangling per invariant, since `self.length > 0`.
+        //  -   `self.head` is associated to block of memory containing a live instance of `NodeHeader`.
+        let head = unsafe { self.head.resolve(&self.store) };
+
+        if *key <= head.key {
+            return 0;
+        }
+
+        let mut handle = self.head;
+        let mut rank = 0;
+
+        for level in (0..head.number_links as usize).rev() {
+            //  Advance as far as possible in this level.
+            loop {
+                //  Safety:
+                //  -   `handle` was allocated by `self.store`.
+                //  -   `handle` is still valid, as it is either `self.head` or was reached from it.
+                //  -   Access to the resulting `node` is shared, as `self` is borrowed immutably.
+                let node = unsafe { handle.resolve(&self.store) };
+
+                let Link { next, span } = node.links()[level];
+
+                if span == 0 {
+                    break;
+                }
+
+                //  Safety:
+                //  -   `next` was allocated by `self.store`.
+                //  -   `next` is still valid, as its span is not 0.
+                //  -   Access to the resulting `next_node` is shared, as `self` is borrowed immutably.
+                let next_node = unsafe { next.resolve(&self.store) };
+
+                if next_node.key >= *key {
+                    break;
+                }
+
+                handle = next;
+                rank += span;
+            }
+        }
+
+        //  `handle` is the last node whose key compares less than `key`, at position `rank`.
+        rank + 1
+    }
+
     /// Inserts a new key and value in the list.
     ///
     /// If a `key` comparing equal is already in the list, it is returned alongside the value it's in with.
+    ///
+    /// Calls `handle_alloc_error` if the store cannot accommodate the new node.
     pub fn insert(&mut self, key: K, value: V) -> Option<(K, V)> {
+        let Ok(result) = self.try_insert(key, value) else {
+            Self::handle_alloc_error()
+        };
+
+        result
+    }
+
+    /// Attempts to insert a new key and value in the list.
+    ///
+    /// If a `key` comparing equal is already in the list, it is returned alongside the value it's in with.
+    ///
+    /// Returns an error if the store cannot accommodate the new node, in which case `key` and `value` are dropped, and
+    /// the elements of the list are left untouched.
+    pub fn try_insert(&mut self, key: K, value: V) -> Result<Option<(K, V)>, AllocError> {
+        let target_links = self.determine_number_links();
+
         if self.length == 0 {
-            self.head = NodeHeader::new(key, value, 0, &self.store).0;
+            self.head = NodeHeader::try_new(key, value, target_links, &self.store)?;
             self.length = 1;
 
             //  Safety:
@@ -152,79 +314,105 @@ where
 
             self.prng = Rand32::new(seed);
 
-            return None;
+            return Ok(None);
         }
 
-        let target_links = self.determine_number_links();
-
         //  There are already elements, so we need to figure out:
         //  -   Whether a node with an equal key exist, and replace it key and value.
-        //  -   Otherwise find the pair of nodes between which to allocate this particular node, and link it in.
+        //  -   Otherwise find the predecessors of this particular node at each level, and link it in, adjusting the
+        //      spans of the predecessors.
         //  -   And as a further complication, if the PRNG decides to use more links for this node than the head node
-        //      currently has, we need to reallocate the first node with more handles.
+        //      currently has, we need to reallocate the first node with more links.
 
         //  Safety:
         //  -   `self.head` was allocated by `self.store.`
         //  -   `self.head` is still valid, notably it is not dangling per invariant, since `self.length > 0`.
         //  -   No other reference to the block of memory exist, since `self` is borrowed mutably.
-        let mut node = unsafe { self.head.resolve_mut(&self.store) };
-        let head_links = node.number_links as usize;
+        let head = unsafe { self.head.resolve_mut(&self.store) };
+        let head_links = head.number_links as usize;
 
         //  Well, that'll avoid having to reallocate `head`!
-        if key < node.key {
-            let target_links = cmp::max(target_links, head_links);
+        if key < head.key {
+            let number_links = cmp::max(target_links, head_links);
 
-            let (node, links) = NodeHeader::new(key, value, target_links, &self.store);
+            let mut handle = NodeHeader::try_new(key, value, number_links, &self.store)?;
 
-            links.iter_mut().for_each(|link| *link = self.head);
+            //  Safety:
+            //  -   `handle` was allocated by `self.store`.
+            //  -   `handle` is still valid.
+            //  -   No other reference to the block of memory exist, since `self` is borrowed mutably.
+            let node = unsafe { handle.resolve_mut(&self.store) };
 
-            self.head = node;
+            //  The former head follows at each of its levels, any level beyond is left unlinked.
+            node.links_mut()[..head_links].fill(Link {
+                next: self.head,
+                span: 1,
+            });
+
+            self.head = handle;
             self.length += 1;
 
-            return None;
+            return Ok(None);
         }
 
         //  And what if the right node is just in front of our eyes?
-        if key == node.key {
-            let key = mem::replace(&mut node.key, key);
-            let value = mem::replace(&mut node.value, value);
+        if key == head.key {
+            let key = mem::replace(&mut head.key, key);
+            let value = mem::replace(&mut head.value, value);
 
-            return Some((key, value));
+            return Ok(Some((key, value)));
         }
 
-        debug_assert!(key > node.key);
+        debug_assert!(key > head.key);
 
-        //  Buffer of handles:
-        //  -   For each level in `0..head_links`, a pointer to the handle in the node preceeding the new node, and
-        //      pointing to the node following the new node (or dangling).
-        //  -   This handle will need to be replaced _if_ the new node is tall enough.
-        //
-        //  IMPORTANT: if the last node should preceed the new node, they are swapped instead.
-        #[allow(clippy::type_complexity)]
-        let mut handles: [Option<NonNull<NodeHandle<K, V, S::Handle>>>; MAX_NUMBER_LINKS] = [None; MAX_NUMBER_LINKS];
+        //  Reallocate head, if necessary, so that it precedes the new node at every level.
+        if target_links > head_links {
+            //  Safety:
+            //  -   `self.head` was allocated by `self.store`.
+            //  -   `self.head` is still valid.
+            //  -   No other reference to the block of memory associated with `self.head` is active, since `self` is
+            //      borrowed mutably.
+            //  -   `head_links` is the number of links of `self.head`.
+            //  -   `target_links > head_links`.
+            self.head = unsafe { NodeHeader::<K, V, _>::try_grow(self.head, head_links, target_links, &self.store)? };
+        }
 
-        let mut last = (head_links == 0).then_some(self.head);
+        let number_levels = cmp::max(target_links, head_links);
 
-        for level in (0..head_links).rev() {
+        //  For each level, the node preceeding the new node, and its position.
+        let mut predecessors = [self.head; MAX_NUMBER_LINKS];
+        let mut ranks = [0; MAX_NUMBER_LINKS];
+
+        let mut handle = self.head;
+        let mut rank = 0;
+
+        for level in (0..number_levels).rev() {
             //  Advance as far as possible in this level.
             loop {
-                let Some(next) = node.links_mut().get_mut(level) else {
-                    break;
+                let Link { mut next, span } = {
+                    //  Safety:
+                    //  -   `handle` was allocated by `self.store`.
+                    //  -   `handle` is still valid, as it is either `self.head` or was reached from it.
+                    //  -   No mutable reference to the block of memory is active.
+                    let node = unsafe { handle.resolve(&self.store) };
+
+                    node.links()[level]
                 };
 
+                if span == 0 {
+                    break;
+                }
+
                 //  Safety:
                 //  -   `next` was allocated by `self.store.`
-                //  -   `next` is still valid, since apart from `self.head`, only valid handles are kept.
+                //  -   `next` is still valid, as its span is not 0.
                 //  -   No other reference to the block of memory exist, since `self` is borrowed mutably.
                 let next_node = unsafe { next.resolve_mut(&self.store) };
 
                 if key > next_node.key {
-                    if next_node.number_links == 0 {
-                        last = Some(*next);
-                        break;
-                    }
+                    handle = next;
+                    rank += span;
 
-                    node = next_node;
                     continue;
                 }
 
@@ -232,7 +420,7 @@ where
                     let key = mem::replace(&mut next_node.key, key);
                     let value = mem::replace(&mut next_node.value, value);
 
-                    return Some((key, value));
+                    return Ok(Some((key, value)));
                 }
 
                 debug_assert!(key < next_node.key);
@@ -240,74 +428,176 @@ where
                 break;
             }
 
-            debug_assert!(key > node.key);
-
-            handles[level] = Some(NonNull::from(&mut node.links_mut()[level]));
+            predecessors[level] = handle;
+            ranks[level] = rank;
         }
 
-        //  `handles` is now filled, and a new node need be introduced.
-        let (mut handle, links) = NodeHeader::new(key, value, target_links, &self.store);
-
-        //  Splice in the new node, at each level it participates in.
-        for (prev_handle, dangling_handle) in handles.iter_mut().take(head_links).zip(links.iter_mut()) {
-            let Some(prev_handle) = prev_handle else { continue };
+        //  `predecessors` is now filled, and a new node need be introduced.
+        let mut handle = NodeHeader::try_new(key, value, target_links, &self.store)?;
+        let new_rank = ranks[0] + 1;
 
+        //  Splice in the new node at each level it participates in, and account for it in the spans of the levels it
+        //  does not participate in.
+        for (level, (predecessor, rank)) in predecessors.iter_mut().zip(ranks).take(number_levels).enumerate() {
             //  Safety:
-            //  -   `prev_handle` points to a readable and writeable block of memory.
-            //  -   `prev_handle` points to an initialized handle.
-            //  -   No other reference to `prev_handle` is active, since `self` is borrow mutably.
-            let prev_handle = unsafe { prev_handle.as_mut() };
+            //  -   `predecessor` was allocated by `self.store`.
+            //  -   `predecessor` is still valid.
+            //  -   No other reference to the block of memory exist, since `self` is borrowed mutably.
+            let predecessor = unsafe { predecessor.resolve_mut(&self.store) };
 
-            let prev_handle = mem::replace(prev_handle, handle);
-            *dangling_handle = prev_handle;
-        }
+            let span = predecessor.links()[level].span;
 
-        //  Exchange with last, if it goes beyond last.
-        if let Some(mut last) = last {
-            //  Safety:
-            //  -   `next` was allocated by `self.store.`
-            //  -   `next` is still valid, since apart from `self.head`, only valid handles are kept.
-            //  -   No other reference to the block of memory exist, since `self` is borrowed mutably.
-            let last_node = unsafe { last.resolve_mut(&self.store) };
+            if level >= target_links {
+                if span != 0 {
+                    predecessor.links_mut()[level].span += 1;
+                }
+
+                continue;
+            }
+
+            let distance = new_rank - rank;
+
+            let next = mem::replace(
+                &mut predecessor.links_mut()[level],
+                Link {
+                    next: handle,
+                    span: distance,
+                },
+            )
+            .next;
 
             //  Safety:
             //  -   `handle` was allocated by `self.store`.
             //  -   `handle` is still valid.
-            //  -   No other active reference to the block of memory pointed to by `handle` exists.
-            let new_node = unsafe { handle.resolve_mut(&self.store) };
-
-            mem::swap(&mut last_node.key, &mut new_node.key);
-            mem::swap(&mut last_node.value, &mut new_node.value);
+            //  -   No other reference to the block of memory exist, since `self` is borrowed mutably.
+            let node = unsafe { handle.resolve_mut(&self.store) };
 
-            links.iter_mut().for_each(|link| *link = last);
+            node.links_mut()[level] = Link {
+                next,
+                span: if span == 0 { 0 } else { span + 1 - distance },
+            };
         }
 
-        //  Last is head.
-        if head_links == 0 {
-            debug_assert!(last.is_some());
+        self.length += 1;
 
-            self.head = handle;
-            self.length += 1;
+        Ok(None)
+    }
+}
 
+impl<K, V, S: Store + StoreStable> SkipList<K, V, S> {
+    /// Returns the key and value at position `n`, by order of increasing key, if any.
+    ///
+    /// Unlike `self.iter().nth(n)`, this only takes a logarithmic number of steps, on average.
+    pub fn nth(&self, n: usize) -> Option<(&K, &V)> {
+        if n >= self.length {
             return None;
         }
 
-        //  Reallocate head, if necessary.
-        if target_links > head_links {
-            //  Safety:
-            //  -   `self.head` was allocated by `self.store`.
-            //  -   `self.head` is still valid.
-            //  -   No other reference to the block of memory associated with `self.head` is active, since `self` is
-            //      borrowed mutably.
-            //  -   `head_links` is the number of links of `self.head`.
-            //  -   `target_links > head_links`.
-            self.head =
-                unsafe { NodeHeader::<K, V, _>::grow(self.head, handle, head_links, target_links, &self.store) };
+        //  Safety:
+        //  -   `self.head` was allocated by `self.store.`
+        //  -   `self.head` is still valid, notably it is not dangling per invariant, since `self.length > 0`.
+        //  -   `self.head` is associated to block of memory containing a live instance of `NodeHeader`.
+        let head = unsafe { self.head.resolve(&self.store) };
+
+        let mut handle = self.head;
+        let mut rank = 0;
+
+        for level in (0..head.number_links as usize).rev() {
+            //  Advance as far as possible in this level, without overshooting.
+            loop {
+                //  Safety:
+                //  -   `handle` was allocated by `self.store`.
+                //  -   `handle` is still valid, as it is either `self.head` or was reached from it.
+                //  -   Access to the resulting `node` is shared, as `self` is borrowed immutably.
+                let node = unsafe { handle.resolve(&self.store) };
+
+                let Link { next, span } = node.links()[level];
+
+                if span == 0 || rank + span > n {
+                    break;
+                }
+
+                handle = next;
+                rank += span;
+            }
         }
 
-        self.length += 1;
+        debug_assert_eq!(n, rank);
 
-        None
+        //  Safety:
+        //  -   `handle` was allocated by `self.store`.
+        //  -   `handle` is still valid, as it was reached from `self.head`.
+        //  -   Access to the resulting `node` is shared, as `self` is borrowed immutably.
+        let node = unsafe { handle.resolve(&self.store) };
+
+        //  Safety:
+        //  -   `handle` was allocated by `self.store`.
+        //  -   `handle` is still valid.
+        let value = unsafe { Self::resolve_value(handle, &self.store) };
+
+        //  Safety:
+        //  -   `value` points to a valid instance of `V`.
+        //  -   Access to the resulting `value` is shared, as `self` is borrowed immutably.
+        let value = unsafe { value.as_ref() };
+
+        Some((&node.key, value))
+    }
+}
+
+impl<K, V, S: Store + StoreStable> SkipList<K, V, S>
+where
+    K: Clone + Ord,
+    V: Clone,
+{
+    /// Clones the list, using `store` for the clone.
+    ///
+    /// Calls `handle_alloc_error` if `store` cannot accommodate all the nodes of the list.
+    pub fn clone_in(&self, store: S) -> Self {
+        let Ok(result) = self.try_clone_in(store) else {
+            Self::handle_alloc_error()
+        };
+
+        result
+    }
+
+    /// Attempts to clone the list, using `store` for the clone.
+    ///
+    /// Returns an error if `store` cannot accommodate all the nodes of the list.
+    pub fn try_clone_in(&self, store: S) -> Result<Self, AllocError> {
+        let mut result = Self::with_store(store);
+
+        for (key, value) in self {
+            result.try_insert(key.clone(), value.clone())?;
+        }
+
+        Ok(result)
+    }
+}
+
+impl<K, V, S: Store + StoreSharing> SkipList<K, V, S>
+where
+    K: Clone + Ord,
+    V: Clone,
+{
+    /// Attempts to clone the list, sharing the store of `self` for the clone.
+    ///
+    /// Returns an error if sharing is not currently possible, or if the store cannot accommodate all the nodes of the
+    /// list.
+    pub fn try_clone(&self) -> Result<Self, CloneError<S::SharingError>> {
+        let store = self.store.share().map_err(CloneError::Sharing)?;
+
+        Ok(self.try_clone_in(store)?)
+    }
+}
+
+impl<K, V, S> Clone for SkipList<K, V, S>
+where
+    K: Clone + Ord,
+    V: Clone,
+    S: Store + StoreStable + Default,
+{
+    fn clone(&self) -> Self {
+        self.clone_in(S::default())
     }
 }
 
@@ -326,18 +616,117 @@ where
     }
 }
 
+unsafe impl<K, V, S> Send for SkipList<K, V, S>
+where
+    K: Send,
+    V: Send,
+    S: Store + Send,
+{
+}
+
+unsafe impl<K, V, S> Sync for SkipList<K, V, S>
+where
+    K: Sync,
+    V: Sync,
+    S: Store + Sync,
+{
+}
+
+//
+//  Iteration
+//
+
+impl<K, V, S: Store + StoreStable> SkipList<K, V, S> {
+    /// Returns an iterator over the keys and values of the list, by order of increasing key.
+    pub fn iter(&self) -> Iter<'_, K, V, S> {
+        Iter {
+            length: self.length,
+            handle: self.head,
+            store: &self.store,
+        }
+    }
+}
+
+impl<'a, K, V, S: Store + StoreStable> IntoIterator for &'a SkipList<K, V, S> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V, S>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over a reference to a skip list.
+pub struct Iter<'a, K, V, S: Store> {
+    //  Only `length` nodes remain, starting from `handle`.
+    length: usize,
+    handle: NodeHandle<K, V, S::Handle>,
+    store: &'a S,
+}
+
+impl<'a, K: 'a, V: 'a, S: Store + StoreStable> Iterator for Iter<'a, K, V, S> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.length == 0 {
+            return None;
+        }
+
+        //  Safety:
+        //  -   `self.handle` was allocated by `self.store`.
+        //  -   `self.handle` is still valid, since `length` is not 0.
+        //  -   `self.handle` is associated to block of memory containing a live instance of `NodeHeader`.
+        //  -   Access to the resulting `node` is shared, as guaranteed by the list being borrowed immutably.
+        let node = unsafe { self.handle.resolve(self.store) };
+
+        //  Safety:
+        //  -   `self.handle` was allocated by `self.store`.
+        //  -   `self.handle` is still valid.
+        let value = unsafe { SkipList::<K, V, S>::resolve_value(self.handle, self.store) };
+
+        //  Safety:
+        //  -   `value` points to a valid instance of `V`.
+        //  -   Access to the resulting `value` is shared, as guaranteed by the list being borrowed immutably.
+        let value = unsafe { value.as_ref() };
+
+        self.length -= 1;
+
+        //  The link of the last node is dangling, but is never resolved as `length` drops to 0.
+        self.handle = node.links()[0].next;
+
+        Some((&node.key, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.length, Some(self.length))
+    }
+}
+
+impl<'a, K: 'a, V: 'a, S: Store + StoreStable> ExactSizeIterator for Iter<'a, K, V, S> {}
+
+impl<'a, K: 'a, V: 'a, S: Store + StoreStable> FusedIterator for Iter<'a, K, V, S> {}
+
 //
 //  Implementation
 //
 
 const MAX_NUMBER_LINKS: usize = 32;
 
+//  The number of links of a node is stored as a `u8`.
+const _: () = assert!(MAX_NUMBER_LINKS <= u8::MAX as usize);
+
 impl<K, V, S: Store> SkipList<K, V, S> {
     //  Returns the number of links a (new) node should have.
     fn determine_number_links(&mut self) -> usize {
         (self.prng.rand_u32() | 1).trailing_ones() as usize
     }
 
+    #[cold]
+    #[inline(never)]
+    fn handle_alloc_error() -> ! {
+        alloc::handle_alloc_error(Layout::new::<NodeHeader<K, V, S::Handle>>())
+    }
+
     //  #   Safety
     //
     //  -   `handle` must have been allocated by `store`.
@@ -353,7 +742,7 @@ impl<K, V, S: Store> SkipList<K, V, S> {
         //  Safety:
         //  -   `pointer` points to a valid `NodeHeader`.
         //  -   `offset` is an offset within the allocation of `NodeHeader`.
-        let pointer = unsafe { pointer.as_ptr().add(offset) };
+        let pointer = unsafe { pointer.as_ptr().cast::<u8>().add(offset) };
 
         //  Safety:
         //  -   `pointer` is not null.
@@ -390,27 +779,36 @@ where
             return Some(value);
         }
 
+        let mut pointers = [MaybeUninit::uninit(); MAX_NUMBER_LINKS];
+
+        //  Safety:
+        //  -   The links of `node` were allocated by `store`, and are still valid.
+        let mut next = unsafe { Self::resolve_links(node, store, &mut pointers) };
+
         for level in (0..number_links).rev() {
             //  Advance as far as possible in this level.
-            loop {
-                let Some(next) = node.links().get(level) else { break };
-
+            while let Some(pointer) = next.get(level) {
                 //  Safety:
-                //  -   `next` was allocated by `store.`
-                //  -   `next` is still valid, since apart from `head`, only valid handles are kept.
-                //  -   `next` is associated to block of memory containing a live instance of `NodeHeader`.
-                let next_node = unsafe { next.resolve(store) };
+                //  -   `pointer` points to a live instance of `NodeHeader`, as it was resolved from a valid link.
+                //  -   `pointer` is still valid, as `store` is `StoreStable`.
+                //  -   Access to the resulting `next_node` is shared.
+                let next_node = unsafe { pointer.cast::<NodeHeader<K, V, S::Handle>>().as_ref() };
 
                 if *key > next_node.key {
                     node = next_node;
+
+                    //  Safety:
+                    //  -   The links of `node` were allocated by `store`, and are still valid.
+                    next = unsafe { Self::resolve_links(node, store, &mut pointers) };
+
                     continue;
                 }
 
                 if *key == next_node.key {
                     //  Safety:
-                    //  -   `next` was allocated by `store`.
-                    //  -   `next` is still valid.
-                    let value = unsafe { Self::resolve_value(*next, store) };
+                    //  -   `node.links()[level].next` was allocated by `store`.
+                    //  -   `node.links()[level].next` is still valid.
+                    let value = unsafe { Self::resolve_value(node.links()[level].next, store) };
 
                     return Some(value);
                 }
@@ -423,88 +821,98 @@ where
 
         None
     }
+
+    //  Resolves all the non-dangling links of `node` at once, returning the pointers to the next nodes.
+    //
+    //  #   Safety
+    //
+    //  -   The links of `node` must have been allocated by `store`.
+    //  -   The links of `node` must still be valid.
+    unsafe fn resolve_links<'a>(
+        node: &NodeHeader<K, V, S::Handle>,
+        store: &S,
+        pointers: &'a mut [MaybeUninit<NonNull<u8>>; MAX_NUMBER_LINKS],
+    ) -> &'a [NonNull<u8>] {
+        let links = &node.links()[..node.number_next()];
+
+        debug_assert!(links.len() <= MAX_NUMBER_LINKS);
+
+        let mut handles = [MaybeUninit::uninit(); MAX_NUMBER_LINKS];
+
+        for (handle, link) in handles.iter_mut().zip(links) {
+            handle.write(link.next.to_raw_parts());
+        }
+
+        //  Safety:
+        //  -   The first `links.len()` handles were just initialized.
+        let handles = unsafe { slice::from_raw_parts(handles.as_ptr() as *const S::Handle, links.len()) };
+
+        //  Safety:
+        //  -   `handles` were allocated by `store`, and are still valid, as per pre-conditions.
+        unsafe { store.resolve_many(handles, &mut pointers[..links.len()]) };
+
+        //  Safety:
+        //  -   The first `links.len()` pointers were just initialized.
+        unsafe { slice::from_raw_parts(pointers.as_ptr() as *const NonNull<u8>, links.len()) }
+    }
 }
 
-type NodeHandle<K, V, H> = TypedHandle<NodeHeader<K, V, H>, H>;
+type NodeHandle<K, V, H> = HeaderSliceHandle<NodeHeader<K, V, H>, Link<K, V, H>, H>;
 
+//  A node is laid out as its header, followed by an array of `number_links` links.
 struct NodeHeader<K, V, H> {
     key: K,
     value: V,
-    //  A node always has at least 1 link, with the exception of the last node, which always has 0 links.
+    //  A node always has at least 1 link.
     number_links: u8,
     _marker: PhantomData<H>,
 }
 
+//  A link to the next node at a given level, and its span.
+//
+//  The span of a link is the number of elements it skips over, that is the difference between the rank of the node it
+//  points to and the rank of the node it belongs to. A span of 0 indicates that there is no next node at this level, in
+//  which case the link itself is dangling.
+//
+//  The link is packed, lest a handle narrower than `usize` be padded to the alignment of the span: the links are only
+//  ever copied in and out, and never borrowed field by field.
+#[repr(C, packed)]
+struct Link<K, V, H> {
+    next: NodeHandle<K, V, H>,
+    span: usize,
+}
+
 impl<K, V, H> NodeHeader<K, V, H>
 where
     H: Copy,
 {
-    //  Returns the layout of a node with the given number of links, and the offset of the array of links.
-    fn layout(number_links: usize) -> (Layout, usize) {
-        let layout = Layout::new::<Self>();
-        let links = Layout::array::<H>(number_links).expect("Sufficiently small number of links");
-
-        layout.extend(links).expect("Sufficiently small number of links")
-    }
-
-    //  Creates a node with `number_links` links, returning a handle to the node and an array of dangling links.
-    #[allow(clippy::new_ret_no_self, clippy::type_complexity)]
-    fn new<S>(key: K, value: V, number_links: usize, store: &S) -> (NodeHandle<K, V, H>, &mut [NodeHandle<K, V, H>])
+    //  Creates a node with `number_links` dangling links, all with a span of 0.
+    //
+    //  On failure, `key` and `value` are dropped.
+    fn try_new<S>(key: K, value: V, number_links: usize, store: &S) -> Result<NodeHandle<K, V, H>, AllocError>
     where
         S: Store<Handle = H>,
     {
-        let (layout, offset) = Self::layout(number_links);
-
-        let (handle, _) = store.allocate(layout).expect("Allocation to succeed.");
-
-        //  Safety:
-        //  -   `handle` was allocated by `store`, and is still valid.
-        let pointer = unsafe { store.resolve(handle) };
-
-        {
-            let number_links: u8 = number_links.try_into().expect("number_links to be sufficiently small");
-            let _marker = PhantomData;
-
-            let header = Self {
-                key,
-                value,
-                number_links,
-                _marker,
-            };
-
-            //  Safety:
-            //  -   `pointer` is valid for writes.
-            //  -   `pointer` is properly aligned.
-            unsafe { ptr::write(pointer.as_ptr() as *mut _, header) };
-        }
-
-        //  Safety:
-        //  -   `offset + index * size` is within bounds, since the calculation of the layout succeeded.
-        let pointer = unsafe { pointer.as_ptr().add(offset) as *mut NodeHandle<K, V, H> };
-
-        for index in 0..number_links {
-            //  Safety:
-            //  -   `offset + index * size` is within bounds, since the calculation of the layout succeeded.
-            let link = unsafe { pointer.add(index) };
-
-            let dangling = NodeHandle::dangling(store);
-
-            //  Safety:
-            //  -   `link` is valid for writes.
-            //  -   `link` is properly aligned.
-            unsafe { ptr::write(link, dangling) };
-        }
-
-        //  Safety:
-        //  -   `pointer` is valid for both reads and writes for `number_links` elements.
-        //  -   Access to `links` is exclusive, as the memory is freshly allocated.
-        let links = unsafe { slice::from_raw_parts_mut(pointer, number_links) };
+        debug_assert!(number_links > 0);
+        debug_assert!(number_links <= MAX_NUMBER_LINKS);
+
+        //  `MAX_NUMBER_LINKS` fits in `u8`, hence the conversion is lossless.
+        let header = Self {
+            key,
+            value,
+            number_links: number_links as u8,
+            _marker: PhantomData,
+        };
 
-        let handle = NodeHandle::from_raw_parts(handle, TypedMetadata::default());
+        let dangling = Link::try_dangling(store)?;
 
-        (handle, links)
+        NodeHandle::try_new(header, number_links, |_| dangling, store)
     }
 
+    //  Grows the node to `new_number_links`, the additional links are dangling, with a span of 0.
+    //
+    //  On failure, `handle` is left untouched.
+    //
     //  #   Safety
     //
     //  -   `handle` must have been allocated by `store`.
@@ -512,63 +920,37 @@ where
     //  -   No other reference to its block of memory is active.
     //  -   `old_number_links` must match the previous number of links.
     //  -   `new_number_links` must be strictly greater than `old_number_links`.
-    unsafe fn grow<S>(
-        handle: NodeHandle<K, V, H>,
-        with: NodeHandle<K, V, H>,
+    unsafe fn try_grow<S>(
+        mut handle: NodeHandle<K, V, H>,
         old_number_links: usize,
         new_number_links: usize,
         store: &S,
-    ) -> NodeHandle<K, V, H>
+    ) -> Result<NodeHandle<K, V, H>, AllocError>
     where
         S: Store<Handle = H>,
     {
-        let (old_layout, offset) = Self::layout(old_number_links);
-        let (new_layout, _) = Self::layout(new_number_links);
+        debug_assert!(new_number_links > old_number_links);
 
-        //  Safety:
-        //  -   `handle` has been allocated by `store`.
-        //  -   `handle` is still valid.
-        //  -   No other reference to its block of memory is active.
-        //  -   `old_layout` fits the block of memory associated with `handle`.
-        //  -   `new_layout` is greater than `old_layout`.
-        let (handle, _) = unsafe {
-            store
-                .grow(handle.to_raw_parts().0, old_layout, new_layout)
-                .expect("Allocation to succeed")
-        };
+        let dangling = Link::try_dangling(store)?;
 
         //  Safety:
-        //  -   `handle` was allocated by `store`, and is still valid.
-        let pointer = unsafe { store.resolve(handle) };
-
-        {
-            //  Safety:
-            //  -   `pointer` points to a readable and writeable area of memory.
-            //  -   `pointer` points to an initialized area of memory of `Self` type.
-            //  -   No other reference to this area of memory is active.
-            let this: &mut Self = unsafe { pointer.cast().as_mut() };
-
-            this.number_links = new_number_links
-                .try_into()
-                .expect("new_number_links to be sufficiently small");
-        }
+        //  -   `handle` has been allocated by `store`, and is still valid, as per pre-conditions.
+        //  -   `old_number_links` is the current number of links, as per pre-conditions.
+        //  -   `new_number_links` is greater than `old_number_links`, as per pre-conditions.
+        //  -   No other reference to its block of memory is active, as per pre-conditions.
+        unsafe { handle.try_grow(old_number_links, new_number_links, |_| dangling, store)? };
 
         //  Safety:
-        //  -   `offset + index * size` is within bounds, since the calculation of the layout succeeded.
-        let pointer = unsafe { pointer.as_ptr().add(offset) as *mut NodeHandle<K, V, H> };
+        //  -   `handle` was just grown by `store`, and is still valid.
+        //  -   No other reference to its block of memory is active.
+        let this = unsafe { handle.resolve_mut(store) };
 
-        for index in old_number_links..new_number_links {
-            //  Safety:
-            //  -   `offset + index * size` is within bounds, since the calculation of the layout succeeded.
-            let link = unsafe { pointer.add(index) };
+        debug_assert!(new_number_links <= MAX_NUMBER_LINKS);
 
-            //  Safety:
-            //  -   `link` is valid for writes.
-            //  -   `link` is properly aligned.
-            unsafe { ptr::write(link, with) };
-        }
+        //  `MAX_NUMBER_LINKS` fits in `u8`, hence the conversion is lossless.
+        this.number_links = new_number_links as u8;
 
-        NodeHandle::from_raw_parts(handle, TypedMetadata::default())
+        Ok(handle)
     }
 
     //  #   Safety
@@ -577,7 +959,7 @@ where
     //  -   `handle` must still be valid.
     //  -   `handle` must be associated to a block of memory containing a live instance of `NodeHeader`.
     //  -   No other reference to its block of memory is active.
-    unsafe fn deallocate<S>(mut handle: NodeHandle<K, V, H>, store: &S) -> (K, V)
+    unsafe fn deallocate<S>(handle: NodeHandle<K, V, H>, store: &S) -> (K, V)
     where
         S: Store<Handle = H>,
     {
@@ -585,76 +967,72 @@ where
         //  -   `handle` was allocated by `store`, and is still valid, as per pre-conditions.
         //  -   `handle` is associated to a block of memory containing a live instance of `NodeHeader`, as per
         //      pre-conditions.
-        //  -   No other reference to its block of memory is active, as per pre-conditions.
-        let this = unsafe { handle.resolve_mut(store) };
-
-        //  Safety:
-        //  -   `this.key` and `this.value` are valid for reads.
-        //  -   `this.key` and `this.value` are properly aligned.
-        //  -   The values are initialized, and will no longer be used.
-        let key = unsafe { ptr::read(&this.key) };
-        let value = unsafe { ptr::read(&this.value) };
-        let number_links: usize = this.number_links.into();
-
-        let (layout, _) = Self::layout(number_links);
+        let number_links: usize = unsafe { handle.resolve(store) }.number_links.into();
 
         //  Safety:
-        //  -   `handle` was allocated by `store`.
-        //  -   `handle` is still valid.
-        //  -   `layout` fits the block of memory.
-        unsafe { store.deallocate(handle.to_raw_parts().0, layout) };
+        //  -   `handle` was allocated by `store`, and is still valid, as per pre-conditions.
+        //  -   `number_links` is the current number of links.
+        //  -   No other reference to its block of memory is active, as per pre-conditions.
+        let this = unsafe { handle.deallocate(number_links, store) };
 
-        (key, value)
+        (this.key, this.value)
     }
 
-    fn links(&self) -> &[NodeHandle<K, V, H>] {
-        let number_links: usize = self.number_links.into();
-
-        if number_links == 0 {
-            return &[];
-        }
-
-        let (_, offset) = Self::layout(number_links);
-
-        //  Safety:
-        //  -   `offset` is within bounds, since the node was allocated.
-        let first = unsafe { (self as *const Self as *const u8).add(offset) };
+    //  Returns the number of links which point to a next node, that is, whose span is not 0.
+    //
+    //  Those form a prefix of the links, as a node following at a given level also follows at all lower levels.
+    fn number_next(&self) -> usize {
+        self.links().iter().take_while(|link| link.span != 0).count()
+    }
 
+    fn links(&self) -> &[Link<K, V, H>] {
         //  Safety:
-        //  -   The pointer is properly aligned.
-        //  -   The pointer is dereferenceable.
-        //  -   The pointer points to an initialized instance of `[NodeHandle<K, V, H>]`.
-        //  -   The slice is accessible in shared mode, since `self` is, and its lifetime is bound to `self`.
-        unsafe { slice::from_raw_parts(first as *const NodeHandle<K, V, H>, number_links) }
+        //  -   `self` is the header of a node, as nodes are only ever accessed through their handle.
+        //  -   `self.number_links` is the number of links of the node.
+        unsafe { NodeHandle::tail(self, self.number_links.into()) }
     }
 
-    fn links_mut(&mut self) -> &mut [NodeHandle<K, V, H>] {
-        let number_links: usize = self.number_links.into();
+    fn links_mut(&mut self) -> &mut [Link<K, V, H>] {
+        let number_links = self.number_links.into();
 
-        if number_links == 0 {
-            return &mut [];
-        }
+        //  Safety:
+        //  -   `self` is the header of a node, as nodes are only ever accessed through their handle.
+        //  -   `number_links` is the number of links of the node.
+        unsafe { NodeHandle::tail_mut(self, number_links) }
+    }
+}
 
-        let (_, offset) = Self::layout(number_links);
+impl<K, V, H> Link<K, V, H>
+where
+    H: Copy,
+{
+    //  Attempts to create a dangling link, with a span of 0.
+    fn try_dangling<S>(store: &S) -> Result<Self, AllocError>
+    where
+        S: Store<Handle = H>,
+    {
+        let next = NodeHandle::try_dangling(store)?;
 
-        //  Safety:
-        //  -   `offset` is within bounds, since the node was allocated.
-        let first = unsafe { (self as *mut Self as *mut u8).add(offset) };
+        Ok(Self { next, span: 0 })
+    }
+}
 
-        //  Safety:
-        //  -   The pointer is properly aligned.
-        //  -   The pointer is dereferenceable.
-        //  -   The pointer points to an initialized instance of `[NodeHandle<K, V, H>]`.
-        //  -   The slice is accessible in exclusive mode, since `self` is, and its lifetime is bound to `self`.
-        unsafe { slice::from_raw_parts_mut(first as *mut NodeHandle<K, V, H>, number_links) }
+impl<K, V, H> Clone for Link<K, V, H>
+where
+    H: Copy,
+{
+    fn clone(&self) -> Self {
+        *self
     }
 }
 
+impl<K, V, H> Copy for Link<K, V, H> where H: Copy {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use crate::collection::utils::Global;
+    use crate::{collection::utils::Global, store::StackBumpBlock};
 
     type GlobalList = SkipList<i32, String, Global>;
 
@@ -667,6 +1045,32 @@ mod tests {
         assert_eq!(None, list.get(&0));
     }
 
+    #[test]
+    fn memory_usage() {
+        let mut list = GlobalList::default();
+
+        assert_eq!(MemoryUsage::default(), list.memory_usage());
+
+        for i in 0..8 {
+            list.insert(i, i.to_string());
+        }
+
+        let usage = list.memory_usage();
+
+        assert_eq!(8, usage.allocations);
+        assert_eq!(8 * (mem::size_of::<i32>() + mem::size_of::<String>()), usage.bytes_used);
+
+        //  Each node holds at least one link, on top of its key and value.
+        assert!(usage.overhead() >= 8 * mem::size_of::<Link<i32, String, NonNull<u8>>>());
+    }
+
+    #[test]
+    fn link_unpadded() {
+        assert_eq!(mem::size_of::<usize>() + 1, mem::size_of::<Link<i32, i32, u8>>());
+        assert_eq!(mem::size_of::<usize>() + 2, mem::size_of::<Link<i32, i32, u16>>());
+        assert_eq!(mem::size_of::<usize>() + 4, mem::size_of::<Link<i32, i32, u32>>());
+    }
+
     #[test]
     fn insert_single() {
         let mut list = GlobalList::default();
@@ -691,41 +1095,278 @@ mod tests {
     //  header not encompassing the tail.
     #[cfg_attr(miri, ignore)]
     #[test]
-    fn insert_front() {
+    fn insert_front_back() {
+        //  Inserting 0 then 1 inserts at the back, and 1 then 0 at the front.
+        for keys in [[0, 1], [1, 0]] {
+            let mut list = GlobalList::default();
+
+            list.insert(keys[0], keys[0].to_string());
+
+            assert_eq!(1, list.len());
+
+            list.insert(keys[1], keys[1].to_string());
+
+            assert_eq!(2, list.len());
+
+            assert_eq!(None, list.get(&-1));
+            assert_eq!(Some(&String::from("0")), list.get(&0));
+            assert_eq!(Some(&String::from("1")), list.get(&1));
+            assert_eq!(None, list.get(&2));
+        }
+    }
+
+    //  MIRI does not like the idea of borrowing the "tail" links from the header, due to the original borrow of the
+    //  header not encompassing the tail.
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn insert_shuffled() {
         let mut list = GlobalList::default();
 
-        list.insert(1, String::from("1"));
+        //  Enough keys that some nodes are all but guaranteed to be taller than the head, at the front, in the middle,
+        //  and at the back.
+        for i in 0..256 {
+            let key = (i * 37) % 256;
 
-        assert_eq!(1, list.len());
+            list.insert(key, key.to_string());
+        }
 
-        list.insert(0, String::from("0"));
+        assert_eq!(256, list.len());
 
-        assert_eq!(2, list.len());
+        for i in 0..256 {
+            assert_eq!(Some(&i.to_string()), list.get(&i));
+        }
 
-        assert_eq!(None, list.get(&-1));
-        assert_eq!(Some(&String::from("0")), list.get(&0));
-        assert_eq!(Some(&String::from("1")), list.get(&1));
-        assert_eq!(None, list.get(&2));
+        assert_eq!(None, list.get(&256));
     }
 
     //  MIRI does not like the idea of borrowing the "tail" links from the header, due to the original borrow of the
     //  header not encompassing the tail.
     #[cfg_attr(miri, ignore)]
     #[test]
-    fn insert_back() {
+    fn clone() {
         let mut list = GlobalList::default();
 
-        list.insert(0, String::from("0"));
+        for i in [3, 1, 4, 0, 2] {
+            list.insert(i, i.to_string());
+        }
 
-        assert_eq!(1, list.len());
+        let clone = list.clone();
+
+        assert_eq!(5, clone.len());
+
+        for i in 0..5 {
+            assert_eq!(Some(&i.to_string()), clone.get(&i));
+        }
+    }
+
+    //  MIRI does not like the idea of borrowing the "tail" links from the header, due to the original borrow of the
+    //  header not encompassing the tail.
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn try_clone_exhausted() {
+        let block = StackBumpBlock::<[u64; 32]>::new();
+        let (small, large) = (StackBumpBlock::<[u64; 4]>::new(), StackBumpBlock::<[u64; 64]>::new());
+
+        let mut list = SkipList::with_store(block.create_store::<u16>());
+
+        //  Fill the block, the list being left untouched by the failed insertion.
+        let mut length = 0;
+
+        while list.try_insert(length, length * 10).is_ok() {
+            length += 1;
+        }
+
+        assert_eq!(length as usize, list.len());
+        assert_eq!(Err(CloneError::Alloc(AllocError)), list.try_clone().map(|_| ()));
+
+        assert_eq!(Err(AllocError), list.try_clone_in(small.create_store()).map(|_| ()));
+
+        let clone = list.try_clone_in(large.create_store()).unwrap();
+
+        assert_eq!(length as usize, clone.len());
+        assert_eq!(Some(&10), clone.get(&1));
+    }
+
+    //  MIRI does not like the idea of borrowing the "tail" links from the header, due to the original borrow of the
+    //  header not encompassing the tail.
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn iter() {
+        let mut list = GlobalList::default();
 
         list.insert(1, String::from("1"));
+        list.insert(0, String::from("0"));
 
-        assert_eq!(2, list.len());
+        let iter = list.iter();
 
-        assert_eq!(None, list.get(&-1));
-        assert_eq!(Some(&String::from("0")), list.get(&0));
-        assert_eq!(Some(&String::from("1")), list.get(&1));
-        assert_eq!(None, list.get(&2));
+        assert_eq!(2, iter.len());
+
+        let v: Vec<_> = iter.collect();
+
+        assert_eq!(r#"[(0, "0"), (1, "1")]"#, format!("{v:?}"));
+    }
+
+    //  MIRI does not like the idea of borrowing the "tail" links from the header, due to the original borrow of the
+    //  header not encompassing the tail.
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn raw_parts() {
+        let block = StackBumpBlock::<[u64; 128]>::new();
+
+        let mut list = SkipList::with_store(block.create_store::<u16>());
+
+        for i in 0..8 {
+            list.insert(i, i * 10);
+        }
+
+        let (length, head, _) = list.into_raw_parts();
+
+        //  Safety:
+        //  -   `length` and `head` were obtained from `into_raw_parts`.
+        //  -   All stores created from `block` are fungible.
+        let list = unsafe { SkipList::<i32, i32, _>::from_raw_parts(length, head, block.create_store::<u16>()) };
+
+        assert_eq!(8, list.len());
+        assert_eq!(Some(&30), list.get(&3));
+        assert_eq!(None, list.get(&8));
+    }
+
+    //  MIRI does not like the idea of borrowing the "tail" links from the header, due to the original borrow of the
+    //  header not encompassing the tail.
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn positional() {
+        const N: i32 = 101;
+
+        let mut list = SkipList::<i32, i32, Global>::default();
+
+        //  Insert the even keys in a scrambled order, as 37 and N are coprime.
+        for i in 0..N {
+            let key = (i * 37) % N * 2;
+
+            assert_eq!(None, list.insert(key, key * 10));
+        }
+
+        assert_eq!(Some((20, 200)), list.insert(20, 21));
+        assert_eq!(N as usize, list.len());
+
+        let keys: Vec<_> = list.iter().map(|(key, _)| *key).collect();
+
+        assert_eq!((0..N).map(|i| i * 2).collect::<Vec<_>>(), keys);
+
+        for i in 0..N {
+            let key = i * 2;
+            let value = if key == 20 { 21 } else { key * 10 };
+
+            assert_eq!(Some(&value), list.get(&key));
+            assert_eq!(None, list.get(&(key + 1)));
+
+            assert_eq!(Some((&key, &value)), list.nth(i as usize));
+
+            assert_eq!(i as usize, list.rank(&key));
+            assert_eq!(i as usize + 1, list.rank(&(key + 1)));
+        }
+
+        assert_eq!(None, list.nth(N as usize));
+        assert_eq!(0, list.rank(&-1));
+    }
+
+    #[test]
+    fn send_sync() {
+        fn require_send<T: Send>() {}
+        fn require_sync<T: Sync>() {}
+
+        require_send::<GlobalList>();
+        require_sync::<GlobalList>();
     }
 } // mod tests
+
+//  The suite is instantiated for each width of handle, but `u8`: a node may require up to 32 links, each pairing a
+//  handle with a `usize` span, hence a `u8` handle cannot reliably address the nodes of even a handful of elements.
+#[cfg(test)]
+macro_rules! inline_bump_tests {
+    ($name:ident, $handle:ty) => {
+        mod $name {
+            use crate::store::InlineBumpStore;
+
+            use super::*;
+
+            type TestList = SkipList<i32, String, InlineBumpStore<$handle, [u64; 1024]>>;
+
+            #[test]
+            fn insert_get() {
+                let mut list = TestList::default();
+
+                for i in 0..8 {
+                    //  Scrambled, as 3 and 8 are coprime.
+                    let key = (i * 3) % 8;
+
+                    assert_eq!(None, list.insert(key, key.to_string()));
+                }
+
+                assert_eq!(Some((4, String::from("4"))), list.insert(4, String::from("four")));
+
+                assert_eq!(8, list.len());
+                assert_eq!(Some(&String::from("four")), list.get(&4));
+                assert_eq!(None, list.get(&8));
+
+                assert!((0..8).eq(list.iter().map(|(key, _)| *key)));
+            }
+
+            #[test]
+            fn positional() {
+                let mut list = TestList::default();
+
+                for i in (0..8).rev() {
+                    list.insert(i, i.to_string());
+                }
+
+                for i in 0..8 {
+                    assert_eq!(Some(&i), list.nth(i as usize).map(|(key, _)| key));
+                    assert_eq!(i as usize, list.rank(&i));
+                }
+            }
+        } // mod $name
+    };
+}
+
+#[cfg(test)]
+inline_bump_tests!(inline_bump_tests_u16, u16);
+
+#[cfg(test)]
+inline_bump_tests!(inline_bump_tests_u32, u32);
+
+#[cfg(test)]
+inline_bump_tests!(inline_bump_tests_usize, usize);
+
+//  Exercises the raw access paths of the list: references to keys and values are kept alive whilst the towers of
+//  links are walked by lookups, ranking, and cloning.
+//
+//  Run with `RUSTFLAGS="--cfg storage_miri_strict" MIRIFLAGS="-Zmiri-tree-borrows" cargo miri test tests_miri`, and
+//  without `-Zmiri-tree-borrows` for Stacked Borrows.
+#[cfg(all(test, storage_miri_strict))]
+mod tests_miri {
+    use crate::collection::utils::Global;
+
+    use super::*;
+
+    #[test]
+    fn shared_across_walks() {
+        let mut list = SkipList::<i32, String, Global>::default();
+
+        for i in (0..16).rev() {
+            list.insert(i, i.to_string());
+        }
+
+        let (first, last) = (list.get(&0).unwrap(), list.get(&15).unwrap());
+        let (key, value) = list.nth(7).unwrap();
+
+        let clone = list.clone_in(Global);
+
+        assert_eq!(8, list.rank(&8));
+        assert!(list.iter().eq(clone.iter()));
+
+        assert_eq!(("0", "15"), (first.as_str(), last.as_str()));
+        assert_eq!((7, "7"), (*key, value.as_str()));
+    }
+} // mod tests_miri
@@ -0,0 +1,520 @@
+//! Proof of concept single-producer single-consumer queue.
+//!
+//! The queue is a lock-free ring of `N` elements, allocated once, at creation. It is split into a `Producer` and a
+//! `Consumer`, each of which may be sent to a different thread -- or be used from an interrupt handler -- and which
+//! synchronize solely via two atomic indices.
+//!
+//! The store must implement `StorePinning`: the slots are resolved once, at creation, and the halves only ever access
+//! them through this pointer, never through the store. Hence the store is never accessed concurrently, and needs be
+//! neither `Send` nor `Sync` for the halves to be sent to other threads.
+
+use core::{
+    fmt,
+    mem::MaybeUninit,
+    ptr::{self, NonNull},
+};
+
+#[cfg(not(all(test, loom)))]
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+#[cfg(all(test, loom))]
+use loom::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::{
+    extension::typed::TypedHandle,
+    interface::{Store, StorePinning},
+};
+
+/// A fixed-capacity single-producer single-consumer queue, of capacity `N`.
+pub struct SpscQueue<T, S: Store, const N: usize> {
+    ring: Ring<T, N>,
+    handle: TypedHandle<[MaybeUninit<T>; N], S::Handle>,
+    store: S,
+}
+
+/// The producing half of a `SpscQueue`.
+pub struct Producer<'a, T, const N: usize> {
+    ring: &'a Ring<T, N>,
+}
+
+/// The consuming half of a `SpscQueue`.
+pub struct Consumer<'a, T, const N: usize> {
+    ring: &'a Ring<T, N>,
+}
+
+impl<T, S: Store + StorePinning, const N: usize> SpscQueue<T, S, N> {
+    /// Creates a queue with a default store.
+    ///
+    /// #   Panics
+    ///
+    /// If `N` is 0, or greater than `usize::MAX / 2`.
+    pub fn new() -> Self
+    where
+        S: Default,
+    {
+        Self::with_store(S::default())
+    }
+
+    /// Creates a queue with the given store.
+    ///
+    /// Calls `handle_alloc_error` if the store cannot accommodate `N` elements.
+    ///
+    /// #   Panics
+    ///
+    /// If `N` is 0, or greater than `usize::MAX / 2`.
+    pub fn with_store(store: S) -> Self {
+        assert!(
+            0 < N && N <= usize::MAX / 2,
+            "Capacity must be within 1..=usize::MAX / 2"
+        );
+
+        let handle = TypedHandle::allocate(&store);
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        //  -   The pointer remains valid until `handle` is deallocated, as `store` is pinning: it is neither
+        //      invalidated by further calls to `store`, which only occur in `drop`, nor by moving `store`.
+        let slots = unsafe { handle.resolve_raw(&store) };
+
+        let ring = Ring::new(slots.cast());
+
+        Self { ring, handle, store }
+    }
+}
+
+impl<T, S: Store, const N: usize> SpscQueue<T, S, N> {
+    /// Returns whether the queue is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns whether the queue is full.
+    pub fn is_full(&self) -> bool {
+        self.len() == N
+    }
+
+    /// Returns the number of elements in the queue.
+    pub fn len(&self) -> usize {
+        self.ring.len()
+    }
+
+    /// Returns the capacity of the queue.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Splits the queue into its producing and consuming halves.
+    ///
+    /// Elements left in the queue when the halves are dropped remain available for later splits.
+    pub fn split(&mut self) -> (Producer<'_, T, N>, Consumer<'_, T, N>) {
+        let ring = &self.ring;
+
+        (Producer { ring }, Consumer { ring })
+    }
+}
+
+impl<T, S: Store + StorePinning + Default, const N: usize> Default for SpscQueue<T, S, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, S: Store, const N: usize> Drop for SpscQueue<T, S, N> {
+    fn drop(&mut self) {
+        let mut consumer = Consumer { ring: &self.ring };
+
+        while consumer.pop().is_some() {}
+
+        //  Safety:
+        //  -   `self.handle` was allocated by `self.store`, and is still valid.
+        //  -   `self.handle` will not be used after this point.
+        unsafe { self.handle.deallocate(&self.store) };
+    }
+}
+
+impl<T, S: Store, const N: usize> fmt::Debug for SpscQueue<T, S, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_struct("SpscQueue")
+            .field("len", &self.len())
+            .field("capacity", &N)
+            .finish()
+    }
+}
+
+//  Safety:
+//  -   Same as `Vec<T>`.
+unsafe impl<T, S, const N: usize> Send for SpscQueue<T, S, N>
+where
+    T: Send,
+    S: Store + Send,
+{
+}
+
+//  Safety:
+//  -   No element is accessible from a shared reference.
+unsafe impl<T, S, const N: usize> Sync for SpscQueue<T, S, N> where S: Store + Sync {}
+
+impl<T, const N: usize> Producer<'_, T, N> {
+    /// Returns whether the queue is empty.
+    ///
+    /// The consumer may pop elements concurrently, hence the queue may have become empty by the time this returns.
+    pub fn is_empty(&self) -> bool {
+        self.ring.len() == 0
+    }
+
+    /// Returns whether the queue is full.
+    ///
+    /// The consumer may pop elements concurrently, hence the queue may no longer be full by the time this returns.
+    pub fn is_full(&self) -> bool {
+        self.ring.len() == N
+    }
+
+    /// Returns the number of elements in the queue.
+    ///
+    /// The consumer may pop elements concurrently, hence the result is an upper bound.
+    pub fn len(&self) -> usize {
+        self.ring.len()
+    }
+
+    /// Appends an element at the back of the queue.
+    ///
+    /// Returns the element back if the queue is full.
+    pub fn push(&mut self, element: T) -> Result<(), T> {
+        //  Safety:
+        //  -   `self` is the only producer, as it exclusively borrows the queue alongside the only consumer.
+        unsafe { self.ring.push(element) }
+    }
+}
+
+impl<T, const N: usize> Consumer<'_, T, N> {
+    /// Returns whether the queue is empty.
+    ///
+    /// The producer may push elements concurrently, hence the queue may no longer be empty by the time this returns.
+    pub fn is_empty(&self) -> bool {
+        self.ring.len() == 0
+    }
+
+    /// Returns whether the queue is full.
+    ///
+    /// The producer may push elements concurrently, hence the queue may have become full by the time this returns.
+    pub fn is_full(&self) -> bool {
+        self.ring.len() == N
+    }
+
+    /// Returns the number of elements in the queue.
+    ///
+    /// The producer may push elements concurrently, hence the result is a lower bound.
+    pub fn len(&self) -> usize {
+        self.ring.len()
+    }
+
+    /// Removes the element at the front of the queue, if any.
+    pub fn pop(&mut self) -> Option<T> {
+        //  Safety:
+        //  -   `self` is the only consumer, as it exclusively borrows the queue alongside the only producer.
+        unsafe { self.ring.pop() }
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for Producer<'_, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_struct("Producer").field("len", &self.len()).finish()
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for Consumer<'_, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_struct("Consumer").field("len", &self.len()).finish()
+    }
+}
+
+//  Safety:
+//  -   Elements are sent to the consumer, hence `T: Send`.
+//  -   The store is never accessed through the producer, see module documentation.
+unsafe impl<T: Send, const N: usize> Send for Producer<'_, T, N> {}
+
+//  Safety:
+//  -   Elements are received from the producer, hence `T: Send`.
+//  -   The store is never accessed through the consumer, see module documentation.
+unsafe impl<T: Send, const N: usize> Send for Consumer<'_, T, N> {}
+
+//
+//  Implementation
+//
+
+//  The ring of slots, and the indices delimiting its elements.
+struct Ring<T, const N: usize> {
+    //  Invariants:
+    //  -   `head` and `tail` are within `0..2 * N`, and designate the slot at their value modulo `N`.
+    //  -   The distance from `head` to `tail`, modulo `2 * N`, is the number of elements, at most `N`.
+    //  -   The slots from `head` (included) to `tail` (excluded) are initialized, all others may not be.
+    //  -   `head` is only written to by the consumer, and `tail` only by the producer.
+    head: AtomicUsize,
+    tail: AtomicUsize,
+    slots: NonNull<MaybeUninit<T>>,
+}
+
+impl<T, const N: usize> Ring<T, N> {
+    //  Creates an empty ring, atop `slots`.
+    //
+    //  `slots` must point to `N` slots, valid for as long as the ring is.
+    fn new(slots: NonNull<MaybeUninit<T>>) -> Self {
+        let (head, tail) = (AtomicUsize::new(0), AtomicUsize::new(0));
+
+        Self { head, tail, slots }
+    }
+
+    fn len(&self) -> usize {
+        let head = self.head.load(Ordering::Acquire);
+        let tail = self.tail.load(Ordering::Acquire);
+
+        Self::distance(head, tail)
+    }
+
+    //  #   Safety
+    //
+    //  -   Only a single thread may push at any given time.
+    unsafe fn push(&self, element: T) -> Result<(), T> {
+        //  Only ever written to by this thread, as per pre-conditions.
+        let tail = self.tail.load(Ordering::Relaxed);
+
+        //  Acquire, to ensure the consumer is done reading the slot, if it was just popped.
+        let head = self.head.load(Ordering::Acquire);
+
+        if Self::distance(head, tail) == N {
+            return Err(element);
+        }
+
+        //  Safety:
+        //  -   `tail` is within `0..2 * N`, as per invariant.
+        let slot = unsafe { self.slot(tail) };
+
+        //  Safety:
+        //  -   `slot` is valid for writes, as it is within the ring.
+        //  -   `slot` is accessible in exclusive mode, as it is not between `head` and `tail`, and only this thread
+        //      may move `tail` over it.
+        unsafe { ptr::write(slot.as_ptr(), MaybeUninit::new(element)) };
+
+        //  Release, to publish the element to the consumer.
+        self.tail.store(Self::next(tail), Ordering::Release);
+
+        Ok(())
+    }
+
+    //  #   Safety
+    //
+    //  -   Only a single thread may pop at any given time.
+    unsafe fn pop(&self) -> Option<T> {
+        //  Only ever written to by this thread, as per pre-conditions.
+        let head = self.head.load(Ordering::Relaxed);
+
+        //  Acquire, to ensure the element written by the producer is visible.
+        let tail = self.tail.load(Ordering::Acquire);
+
+        if head == tail {
+            return None;
+        }
+
+        //  Safety:
+        //  -   `head` is within `0..2 * N`, as per invariant.
+        let slot = unsafe { self.slot(head) };
+
+        //  Safety:
+        //  -   `slot` is valid for reads, as it is within the ring.
+        //  -   `slot` is initialized, as it is between `head` and `tail`.
+        //  -   `slot` is accessible in exclusive mode, as only this thread may move `head` over it.
+        let element = unsafe { ptr::read(slot.as_ptr()).assume_init() };
+
+        //  Release, to hand the slot back to the producer.
+        self.head.store(Self::next(head), Ordering::Release);
+
+        Some(element)
+    }
+
+    //  Returns the number of elements from `head` to `tail`.
+    fn distance(head: usize, tail: usize) -> usize {
+        if head <= tail {
+            tail - head
+        } else {
+            tail + (2 * N - head)
+        }
+    }
+
+    //  Returns the index following `index`.
+    fn next(index: usize) -> usize {
+        if index + 1 == 2 * N {
+            0
+        } else {
+            index + 1
+        }
+    }
+
+    //  #   Safety
+    //
+    //  -   `index` must be within `0..2 * N`.
+    unsafe fn slot(&self, index: usize) -> NonNull<MaybeUninit<T>> {
+        //  Safety:
+        //  -   `index % N` is within `0..N`, hence within the ring.
+        unsafe { self.slots.add(index % N) }
+    }
+}
+
+//  Loom atomics cannot be used outside of a loom model.
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use std::{rc::Rc, thread};
+
+    use crate::store::{InlineBumpStore, RefStore, StackBumpBlock, StackBumpStore, TrackingStore};
+
+    use super::*;
+
+    #[test]
+    fn brush() {
+        let block = StackBumpBlock::<[u32; 4]>::new();
+
+        let mut queue = SpscQueue::<u32, StackBumpStore<'_, u8>, 3>::with_store(block.create_store());
+
+        assert!(queue.is_empty());
+        assert_eq!(3, queue.capacity());
+
+        let (mut producer, mut consumer) = queue.split();
+
+        //  Several laps, to wrap around.
+        for lap in 0..3 {
+            for i in 0..3 {
+                producer.push(lap * 3 + i).unwrap();
+            }
+
+            assert!(producer.is_full());
+            assert_eq!(Err(9), producer.push(9));
+
+            for i in 0..3 {
+                assert_eq!(Some(lap * 3 + i), consumer.pop());
+            }
+
+            assert!(consumer.is_empty());
+            assert_eq!(None, consumer.pop());
+        }
+
+        producer.push(42).unwrap();
+
+        assert_eq!(1, queue.len());
+    }
+
+    #[test]
+    fn drop_remaining() {
+        let store = TrackingStore::<InlineBumpStore<u16, [u64; 8]>, 1>::new(InlineBumpStore::default());
+        let counter = Rc::new(());
+
+        let mut queue = SpscQueue::<Rc<()>, _, 4>::with_store(RefStore::new(&store));
+
+        {
+            let (mut producer, mut consumer) = queue.split();
+
+            for _ in 0..3 {
+                producer.push(counter.clone()).unwrap();
+            }
+
+            consumer.pop().unwrap();
+        }
+
+        assert_eq!(3, Rc::strong_count(&counter));
+        assert_eq!(1, store.len());
+
+        drop(queue);
+
+        assert_eq!(1, Rc::strong_count(&counter));
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn multithreaded() {
+        const ELEMENTS: usize = 1000;
+
+        //  `StackBumpStore` is neither `Send` nor `Sync`, yet the halves are, as they never access the store.
+        let block = StackBumpBlock::<[usize; 4]>::new();
+
+        let mut queue = SpscQueue::<usize, StackBumpStore<'_, u8>, 4>::with_store(block.create_store());
+
+        let (mut producer, mut consumer) = queue.split();
+
+        thread::scope(|scope| {
+            scope.spawn(move || {
+                for i in 0..ELEMENTS {
+                    let mut element = i;
+
+                    while let Err(e) = producer.push(element) {
+                        element = e;
+                        thread::yield_now();
+                    }
+                }
+            });
+
+            scope.spawn(move || {
+                for i in 0..ELEMENTS {
+                    loop {
+                        if let Some(element) = consumer.pop() {
+                            assert_eq!(i, element);
+                            break;
+                        }
+
+                        thread::yield_now();
+                    }
+                }
+            });
+        });
+
+        assert!(queue.is_empty());
+    }
+} // mod tests
+
+//  Run with `RUSTFLAGS="--cfg loom" cargo test --release tests_loom`.
+#[cfg(all(test, loom))]
+mod tests_loom {
+    use loom::{hint, sync::Arc, thread};
+
+    use crate::collection::utils::Global;
+
+    use super::*;
+
+    type GlobalQueue = SpscQueue<usize, Global, 1>;
+
+    #[test]
+    fn push_pop() {
+        loom::model(|| {
+            let queue = Arc::new(GlobalQueue::new());
+
+            //  The halves borrow the queue, hence cannot be moved into a loom thread; the ring is used directly.
+            let producer = {
+                let queue = queue.clone();
+
+                thread::spawn(move || {
+                    for mut element in 0..2 {
+                        //  Safety:
+                        //  -   Only this thread pushes.
+                        while let Err(e) = unsafe { queue.ring.push(element) } {
+                            element = e;
+                            hint::spin_loop();
+                        }
+                    }
+                })
+            };
+
+            for i in 0..2 {
+                //  Safety:
+                //  -   Only this thread pops.
+                let element = loop {
+                    if let Some(element) = unsafe { queue.ring.pop() } {
+                        break element;
+                    }
+
+                    hint::spin_loop();
+                };
+
+                assert_eq!(i, element);
+            }
+
+            producer.join().unwrap();
+        });
+    }
+} // mod tests_loom
@@ -1,17 +1,41 @@
 //! Proof-of-Concept implementation of a `Box` atop a `StoreSingle`.
+//!
+//! Each `StoreBox` owns its store. When many boxes should share a single store, they may instead borrow it, via
+//! `StoreBox::new_in_ref`, resulting in a `RefStoreBox` which only deallocates its block of memory on drop, leaving
+//! the borrowed store untouched.
+//!
+//! A `StoreBox<SharedCapable<T>, S>`, created by `StoreBox::new_shared_capable`, may be promoted into a `StoreRc`
+//! without copying the value, via `StoreBox::into_shared`.
+//!
+//! A `StoreBox<str, S>` may be created by copying a `&str`, via `StoreBox::try_from_str`, or by formatting, via
+//! `StoreBox::try_format`, without requiring `alloc`.
 
 use core::{
-    alloc::AllocError,
+    alloc::{AllocError, Layout},
     fmt,
     marker::Unsize,
-    mem::{self, ManuallyDrop},
-    ops, ptr,
+    mem::{self, ManuallyDrop, MaybeUninit},
+    ops,
+    pin::Pin,
+    ptr,
 };
 
 #[cfg(feature = "coercible-metadata")]
 use core::ops::CoerceUnsized;
 
-use crate::{extension::unique_single::UniqueSingleHandle, interface::StoreSingle};
+#[cfg(feature = "alloc")]
+use core::{alloc::Allocator, ptr::NonNull};
+
+#[cfg(feature = "alloc")]
+use std_alloc::boxed::Box;
+
+use crate::{
+    alloc,
+    collection::{SharedCapable, StoreRc},
+    extension::{typed_metadata::TypedMetadata, typed_single::TypedSingleHandle, unique_single::UniqueSingleHandle},
+    interface::{Store, StorePinning, StoreSingle},
+    store::RefStore,
+};
 
 /// A `Box` atop a `StoreSingle`.
 pub struct StoreBox<T: ?Sized, S: StoreSingle> {
@@ -19,6 +43,15 @@ pub struct StoreBox<T: ?Sized, S: StoreSingle> {
     handle: UniqueSingleHandle<T, S::Handle>,
 }
 
+/// A `Box` borrowing its `Store`.
+pub type RefStoreBox<'a, T, S> = StoreBox<T, RefStore<'a, S>>;
+
+/// A `Box` whose value was moved out, keeping its block of memory for reuse.
+///
+/// Created by `StoreBox::take_value`, and refilled by `StoreBox::write`, without reallocating. Dropping it deallocates
+/// the block of memory.
+pub type EmptyStoreBox<T, S> = StoreBox<MaybeUninit<T>, S>;
+
 impl<T, S: StoreSingle + Default> StoreBox<T, S> {
     /// Creates a new instance.
     pub fn new(value: T) -> Self {
@@ -36,11 +69,280 @@ impl<T, S: StoreSingle> StoreBox<T, S> {
     }
 
     /// Attempts to create a new instance.
-    pub fn try_new_in(value: T, mut store: S) -> Result<Self, AllocError> {
-        let handle = UniqueSingleHandle::try_new(value, &mut store)?;
+    pub fn try_new_in(value: T, store: S) -> Result<Self, AllocError> {
+        Ok(StoreBox::write(Self::try_new_uninit_in(store)?, value))
+    }
+
+    /// Creates a new instance, with uninitialized contents.
+    pub fn new_uninit_in(mut store: S) -> StoreBox<MaybeUninit<T>, S> {
+        let handle = UniqueSingleHandle::allocate_uninit(&mut store);
+        let store = ManuallyDrop::new(store);
+
+        StoreBox { store, handle }
+    }
+
+    /// Attempts to create a new instance, with uninitialized contents.
+    pub fn try_new_uninit_in(mut store: S) -> Result<StoreBox<MaybeUninit<T>, S>, AllocError> {
+        let handle = UniqueSingleHandle::try_allocate_uninit(&mut store)?;
         let store = ManuallyDrop::new(store);
 
-        Ok(Self { store, handle })
+        Ok(StoreBox { store, handle })
+    }
+}
+
+impl<T, S> StoreBox<T, S>
+where
+    S: StoreSingle + StorePinning + 'static,
+{
+    /// Creates a new instance, pinning its value.
+    pub fn pin_in(value: T, store: S) -> Pin<Self> {
+        Self::into_pin(Self::new_in(value, store))
+    }
+}
+
+impl<T: ?Sized, S> StoreBox<T, S>
+where
+    S: StoreSingle + StorePinning + 'static,
+{
+    /// Converts the box into a pinned box, pinning its value.
+    ///
+    /// The store must be pinning, so that moving the box does not move the value, and `'static`, so that forgetting
+    /// the box leaks the value rather than letting its memory be repurposed.
+    pub fn into_pin(boxed: Self) -> Pin<Self> {
+        //  Safety:
+        //  -   The value is never moved: `StoreBox` never moves it out through `Deref` or `DerefMut`, and the store
+        //      is pinning.
+        //  -   The value is dropped in place, by `StoreBox::drop`, before its memory is reused.
+        unsafe { Pin::new_unchecked(boxed) }
+    }
+}
+
+impl<T, S: StoreSingle> StoreBox<T, S> {
+    /// Replaces the value in the box by `value`, returning the former value, without reallocating.
+    ///
+    /// This is an associated function, rather than a method, so as not to shadow the methods of `T`.
+    pub fn replace(boxed: &mut Self, value: T) -> T {
+        mem::replace(&mut **boxed, value)
+    }
+
+    /// Takes the value out of the box, leaving `T::default()` in its place, without reallocating.
+    ///
+    /// `StoreBox` cannot implement `Default` without allocating, hence `mem::take` cannot be used on the box itself;
+    /// this function is the next best thing, for example leaving `None` behind in a `StoreBox<Option<T>, S>`.
+    ///
+    /// This is an associated function, rather than a method, so as not to shadow the methods of `T`.
+    pub fn take(boxed: &mut Self) -> T
+    where
+        T: Default,
+    {
+        mem::take(&mut **boxed)
+    }
+
+    /// Moves the value out of the box, keeping the block of memory in an `EmptyStoreBox`.
+    ///
+    /// The empty box may be refilled by `StoreBox::write`, without reallocating, which is notably useful to replace a
+    /// boxed value when the new value is only available once the old one has been consumed.
+    ///
+    /// This is an associated function, rather than a method, so as not to shadow the methods of `T`.
+    pub fn take_value(boxed: Self) -> (T, EmptyStoreBox<T, S>) {
+        let (handle, mut store) = boxed.into_raw_parts();
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        let pointer = unsafe { handle.resolve_raw_mut(&mut store) };
+
+        //  Safety:
+        //  -   `pointer` points to a live instance of `T`, which is never used again, as the block of memory is
+        //      henceforth only accessed as `MaybeUninit<T>`.
+        let value = unsafe { pointer.read() };
+
+        let (handle, _) = handle.to_raw_parts();
+
+        //  Safety:
+        //  -   `handle` is associated to a block of memory fitting `T`, and thus `MaybeUninit<T>`.
+        //  -   No copy of `handle` will be used henceforth.
+        let handle = unsafe { UniqueSingleHandle::from_raw_parts(handle, TypedMetadata::new()) };
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        //  -   `handle` is associated to a block of memory containing a `MaybeUninit<T>`, which is always live.
+        let empty = unsafe { StoreBox::from_raw_parts(handle, store) };
+
+        (value, empty)
+    }
+}
+
+impl<T, S: StoreSingle> StoreBox<MaybeUninit<T>, S> {
+    /// Initializes the contents with `value`, and converts to `StoreBox<T, S>`.
+    ///
+    /// Like `Box::write`, this is an associated function, so as not to shadow `MaybeUninit::write`.
+    pub fn write(boxed: Self, value: T) -> StoreBox<T, S> {
+        let (handle, mut store) = boxed.into_raw_parts();
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        let handle = unsafe { handle.write(value, &mut store) };
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        //  -   `handle` is associated to a block of memory containing a live instance of `T`, as it was just written.
+        unsafe { StoreBox::from_raw_parts(handle, store) }
+    }
+
+    /// Converts to `StoreBox<T, S>`.
+    ///
+    /// #   Safety
+    ///
+    /// -   The contents must have been initialized.
+    pub unsafe fn assume_init(self) -> StoreBox<T, S> {
+        let (handle, store) = self.into_raw_parts();
+
+        //  Safety:
+        //  -   The contents were initialized, as per pre-conditions.
+        let handle = unsafe { handle.assume_init() };
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        //  -   `handle` is associated to a block of memory containing a live instance of `T`, as per pre-conditions.
+        unsafe { StoreBox::from_raw_parts(handle, store) }
+    }
+}
+
+impl<'a, T, S: Store> StoreBox<T, RefStore<'a, S>> {
+    /// Creates a new instance, borrowing `store`.
+    pub fn new_in_ref(value: T, store: &'a S) -> Self {
+        Self::new_in(value, RefStore::new(store))
+    }
+
+    /// Attempts to create a new instance, borrowing `store`.
+    pub fn try_new_in_ref(value: T, store: &'a S) -> Result<Self, AllocError> {
+        Self::try_new_in(value, RefStore::new(store))
+    }
+}
+
+impl<T, S: StoreSingle> StoreBox<SharedCapable<T>, S> {
+    /// Creates a new instance, with headroom for the reference count of a `StoreRc`.
+    ///
+    /// The instance may later be promoted into a `StoreRc`, without copying the value, via `into_shared`.
+    pub fn new_shared_capable(value: T, store: S) -> Self {
+        Self::new_in(SharedCapable::new(value), store)
+    }
+
+    /// Attempts to create a new instance, with headroom for the reference count of a `StoreRc`.
+    ///
+    /// The instance may later be promoted into a `StoreRc`, without copying the value, via `into_shared`.
+    pub fn try_new_shared_capable(value: T, store: S) -> Result<Self, AllocError> {
+        Self::try_new_in(SharedCapable::new(value), store)
+    }
+
+    /// Promotes the instance into a `StoreRc`, without copying the value.
+    pub fn into_shared(self) -> StoreRc<T, S> {
+        let (handle, store) = self.into_raw_parts();
+        let (handle, metadata) = handle.to_raw_parts();
+
+        let handle = TypedSingleHandle::from_raw_parts(handle, metadata);
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        //  -   `handle` is associated to a live instance of `SharedCapable<T>`, whose count is 1, as it cannot be
+        //      modified while boxed.
+        unsafe { StoreRc::from_parts(handle, store) }
+    }
+}
+
+impl<S: StoreSingle> StoreBox<str, S> {
+    /// Creates a new instance, copying `value`.
+    ///
+    /// Calls `handle_alloc_error` if `store` cannot accommodate `value`.
+    pub fn from_str_in(value: &str, store: S) -> Self {
+        let Ok(this) = Self::try_from_str(value, store) else {
+            alloc::handle_alloc_error(Layout::for_value(value))
+        };
+
+        this
+    }
+
+    /// Attempts to create a new instance, copying `value`.
+    pub fn try_from_str(value: &str, mut store: S) -> Result<Self, AllocError> {
+        let bytes = value.as_bytes();
+
+        let handle = UniqueSingleHandle::<[u8], _>::try_allocate_slice(bytes.len(), &mut store)?;
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        let pointer = unsafe { handle.resolve_raw_mut(&mut store) };
+
+        //  Safety:
+        //  -   `bytes` is valid for reads of `bytes.len()` bytes.
+        //  -   `pointer` is valid for writes of `bytes.len()` bytes, as it was allocated with at least this length.
+        //  -   `bytes` and `pointer` do not overlap, as `pointer` was freshly allocated.
+        unsafe { ptr::copy_nonoverlapping(bytes.as_ptr(), pointer.as_mut_ptr(), bytes.len()) };
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, for a slice of `bytes.len()` bytes, and is still valid.
+        //  -   `handle` is associated to a block of memory starting with a copy of `value`, hence valid UTF-8.
+        Ok(unsafe { Self::from_utf8_unchecked(handle, bytes.len(), store) })
+    }
+
+    /// Creates a new instance, formatting `args`.
+    ///
+    /// Calls `handle_alloc_error` if `store` cannot accommodate the formatted string.
+    ///
+    /// #   Panics
+    ///
+    /// If a formatting trait implementation returns an error, or formats differently when called twice.
+    pub fn format(args: fmt::Arguments<'_>, store: S) -> Self {
+        let Ok(this) = Self::try_format(args, store) else {
+            let length = Length::of(args);
+
+            alloc::handle_alloc_error(Layout::array::<u8>(length).expect("Formatted string to fit in memory"))
+        };
+
+        this
+    }
+
+    /// Attempts to create a new instance, formatting `args`.
+    ///
+    /// The formatted string is measured first, then written into a block of memory of exactly that size.
+    ///
+    /// #   Panics
+    ///
+    /// If a formatting trait implementation returns an error, or formats differently when called twice.
+    pub fn try_format(args: fmt::Arguments<'_>, mut store: S) -> Result<Self, AllocError> {
+        if let Some(value) = args.as_str() {
+            return Self::try_from_str(value, store);
+        }
+
+        let length = Length::of(args);
+
+        let handle = UniqueSingleHandle::<[u8], _>::try_allocate_slice(length, &mut store)?;
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        let pointer = unsafe { handle.resolve_raw_mut(&mut store) };
+
+        //  Safety:
+        //  -   `pointer` is valid for reads and writes of its length, at least `length` bytes, as it was allocated.
+        //  -   No other reference to this block of memory exists, as it was freshly allocated.
+        let buffer = unsafe { pointer.as_uninit_slice_mut() };
+
+        let mut writer = Fill { buffer, written: 0 };
+
+        let result = fmt::write(&mut writer, args);
+
+        if result.is_err() || writer.written != length {
+            //  Safety:
+            //  -   `handle` was allocated by `store`, and is still valid.
+            unsafe { handle.deallocate(&mut store) };
+
+            panic!("a formatting trait implementation returned an error, or formatted differently when called twice");
+        }
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, for a slice of `length` bytes, and is still valid.
+        //  -   `handle` is associated to a block of memory whose first `length` bytes were written by `fmt::write`,
+        //      hence valid UTF-8.
+        Ok(unsafe { Self::from_utf8_unchecked(handle, length, store) })
     }
 }
 
@@ -84,6 +386,37 @@ impl<T: ?Sized, S: StoreSingle> Drop for StoreBox<T, S> {
 }
 
 impl<T: ?Sized, S: StoreSingle> StoreBox<T, S> {
+    /// Creates an instance from its raw parts.
+    ///
+    /// #   Safety
+    ///
+    /// -   `handle` must have been allocated by `store`.
+    /// -   `handle` must still be valid.
+    /// -   `handle` must be associated to a block of memory containing a live instance of `T`.
+    pub unsafe fn from_raw_parts(handle: UniqueSingleHandle<T, S::Handle>, store: S) -> Self {
+        let store = ManuallyDrop::new(store);
+
+        Self { store, handle }
+    }
+
+    /// Decomposes the instance into its raw parts.
+    ///
+    /// The caller becomes responsible for dropping the instance of `T`, and deallocating the handle.
+    pub fn into_raw_parts(mut self) -> (UniqueSingleHandle<T, S::Handle>, S) {
+        //  Safety:
+        //  -   `self.handle` is valid.
+        //  -   `self.handle` will not be used after this point.
+        let handle = unsafe { ptr::read(&self.handle) };
+
+        //  Safety:
+        //  -   `self.store` will never be used ever again.
+        let store = unsafe { ManuallyDrop::take(&mut self.store) };
+
+        mem::forget(self);
+
+        (handle, store)
+    }
+
     /// Coerces to another `StoreBox`.
     ///
     /// A poor's man `CoerceUnsized`, since that trait cannot unfortunately be implemented.
@@ -143,8 +476,174 @@ where
     }
 }
 
+impl<T: ?Sized, S: StoreSingle> fmt::Display for StoreBox<T, S>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        let value: &T = self;
+
+        fmt::Display::fmt(value, f)
+    }
+}
+
 #[cfg(feature = "coercible-metadata")]
-impl<T, U: ?Sized, S: StoreSingle> CoerceUnsized<StoreBox<U, S>> for StoreBox<T, S> where T: Unsize<U> {}
+#[cfg_attr(docsrs, doc(cfg(feature = "coercible-metadata")))]
+impl<T: ?Sized, U: ?Sized, S: StoreSingle> CoerceUnsized<StoreBox<U, S>> for StoreBox<T, S> where T: Unsize<U> {}
+
+//
+//  Conversion
+//
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<T: ?Sized, A: Allocator> StoreBox<T, A> {
+    /// Converts into a standard `Box`, retaining the allocator.
+    ///
+    /// _Note: this cannot be expressed as `From` or `TryFrom`, as `Box` is `#[fundamental]`._
+    pub fn into_box(self) -> Box<T, A> {
+        let (handle, mut store) = self.into_raw_parts();
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`.
+        //  -   `handle` is still valid.
+        let pointer = unsafe { handle.resolve_raw_mut(&mut store) };
+
+        //  Safety:
+        //  -   `pointer` points to a live instance of `T`.
+        let layout = unsafe { Layout::for_value_raw(pointer.as_ptr()) };
+
+        let pointer = if layout.size() == 0 {
+            //  `Box` never deallocates zero-sized values, hence the block of memory is returned to the store at once.
+
+            //  Safety:
+            //  -   `handle` was allocated by `store`.
+            //  -   `handle` is still valid.
+            unsafe { handle.deallocate(&mut store) };
+
+            let dangling: *mut () = ptr::invalid_mut(layout.align());
+            let metadata = ptr::metadata(pointer.as_ptr());
+
+            ptr::from_raw_parts_mut(dangling, metadata)
+        } else {
+            pointer.as_ptr()
+        };
+
+        //  Safety:
+        //  -   `pointer` was allocated by `store` with `layout`, or is suitably dangling if `layout` is zero-sized.
+        //  -   `pointer` points to a live instance of `T`.
+        unsafe { Box::from_raw_in(pointer, store) }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<T: ?Sized, A: Allocator> From<Box<T, A>> for StoreBox<T, A> {
+    fn from(value: Box<T, A>) -> Self {
+        let layout = Layout::for_value::<T>(&value);
+
+        let (pointer, mut store) = Box::into_raw_with_allocator(value);
+
+        let metadata = TypedMetadata::from_metadata(ptr::metadata(pointer));
+
+        let handle = if layout.size() == 0 {
+            //  `Box` never allocates zero-sized values, whereas `StoreBox` always does.
+            let Ok((handle, _)) = StoreSingle::allocate(&mut store, layout) else {
+                alloc::handle_alloc_error(layout)
+            };
+
+            handle
+        } else {
+            //  Safety:
+            //  -   `pointer` is non-null, as it comes from a `Box`.
+            let pointer = unsafe { NonNull::new_unchecked(pointer as *mut u8) };
+
+            pointer.into()
+        };
+
+        //  Safety:
+        //  -   `handle` is associated to a block of memory allocated by `store`, which fits an instance of `T`.
+        //  -   No copy of `handle` exists.
+        let handle = unsafe { UniqueSingleHandle::from_raw_parts(handle, metadata) };
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        //  -   `handle` is associated to a block of memory containing a live instance of `T`.
+        unsafe { Self::from_raw_parts(handle, store) }
+    }
+}
+
+//
+//  Implementation
+//
+
+impl<S: StoreSingle> StoreBox<str, S> {
+    //  #   Safety
+    //
+    //  -   `handle` must have been allocated by `store`, for a slice of `length` bytes.
+    //  -   `handle` must still be valid.
+    //  -   `handle` must be associated to a block of memory whose first `length` bytes are valid UTF-8.
+    unsafe fn from_utf8_unchecked(handle: UniqueSingleHandle<[u8], S::Handle>, length: usize, store: S) -> Self {
+        //  The length of `handle` is the capacity of its block of memory, which may exceed `length`.
+        let (handle, _) = handle.to_raw_parts();
+
+        let metadata = TypedMetadata::from_metadata(length);
+
+        //  Safety:
+        //  -   `handle` is associated to a block of memory fitting a `str` of `length` bytes, as it was requested.
+        //  -   No copy of `handle` exists, as it was unique.
+        let handle = unsafe { UniqueSingleHandle::from_raw_parts(handle, metadata) };
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid, as per pre-conditions.
+        //  -   `handle` is associated to a block of memory containing a valid `str`, as per pre-conditions.
+        unsafe { Self::from_raw_parts(handle, store) }
+    }
+}
+
+//  Measures the length of a formatted string.
+struct Length(usize);
+
+impl Length {
+    fn of(args: fmt::Arguments<'_>) -> usize {
+        let mut length = Self(0);
+
+        fmt::write(&mut length, args).expect("a formatting trait implementation returned an error");
+
+        length.0
+    }
+}
+
+impl fmt::Write for Length {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 = self.0.checked_add(s.len()).ok_or(fmt::Error)?;
+
+        Ok(())
+    }
+}
+
+//  Fills a buffer with a formatted string, failing if it does not fit.
+struct Fill<'a> {
+    buffer: &'a mut [MaybeUninit<u8>],
+    written: usize,
+}
+
+impl fmt::Write for Fill<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let destination = self.buffer.get_mut(self.written..).ok_or(fmt::Error)?;
+        let destination = destination.get_mut(..s.len()).ok_or(fmt::Error)?;
+
+        //  Safety:
+        //  -   `s` is valid for reads of `s.len()` bytes.
+        //  -   `destination` is valid for writes of `s.len()` bytes, as it was sliced to this length.
+        //  -   `s` and `destination` do not overlap, as `destination` is exclusively borrowed.
+        unsafe { ptr::copy_nonoverlapping(s.as_ptr(), destination.as_mut_ptr() as *mut u8, s.len()) };
+
+        self.written += s.len();
+
+        Ok(())
+    }
+}
 
 #[cfg(test)]
 mod test_inline {
@@ -171,6 +670,77 @@ mod test_inline {
         assert_eq!(3u8, *clone);
     }
 
+    #[test]
+    fn take_value() {
+        let boxed = StoreBox::new_in(String::from("first"), InlineSingleStore::<String>::default());
+
+        let (value, empty) = StoreBox::take_value(boxed);
+
+        assert_eq!("first", value);
+
+        let boxed = StoreBox::write(empty, value + ", second");
+
+        assert_eq!("first, second", *boxed);
+
+        let (_, empty) = StoreBox::take_value(boxed);
+
+        drop(empty);
+    }
+
+    #[test]
+    fn uninit_store() {
+        let boxed = StoreBox::<u32, _>::new_uninit_in(InlineSingleStore::<u32>::default());
+        let boxed = StoreBox::write(boxed, 7);
+
+        assert_eq!(7, *boxed);
+
+        let mut boxed = StoreBox::<u32, _>::try_new_uninit_in(InlineSingleStore::<u32>::default()).unwrap();
+        boxed.write(8);
+
+        //  Safety:
+        //  -   The contents were just initialized.
+        let boxed = unsafe { boxed.assume_init() };
+
+        assert_eq!(8, *boxed);
+    }
+
+    #[test]
+    fn take_replace() {
+        let mut boxed = StoreBox::new_in(Some(1u32), InlineSingleStore::<Option<u32>>::default());
+
+        assert_eq!(Some(1), StoreBox::replace(&mut boxed, Some(2)));
+        assert_eq!(Some(2), StoreBox::take(&mut boxed));
+        assert_eq!(None, *boxed);
+    }
+
+    #[test]
+    fn uninit_slice_store() {
+        let mut store = InlineSingleStore::<[u8; 4]>::default();
+
+        let handle = UniqueSingleHandle::<[u8], _>::allocate_uninit_slice(4, &mut store);
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        let mut pointer = unsafe { handle.resolve_raw_mut(&mut store) };
+
+        //  Safety:
+        //  -   `pointer` is valid for writes of `handle.len()` elements, and no other reference to them is active.
+        for (index, element) in unsafe { pointer.as_mut() }.iter_mut().enumerate() {
+            element.write(index as u8);
+        }
+
+        //  Safety:
+        //  -   All the elements were just initialized.
+        let handle = unsafe { handle.assume_init() };
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        //  -   `handle` is associated to a block of memory containing live instances of `u8`.
+        let boxed = unsafe { StoreBox::from_raw_parts(handle, store) };
+
+        assert_eq!([0, 1, 2, 3], &*boxed);
+    }
+
     #[test]
     fn slice_store() {
         let store = InlineSingleStore::<[u8; 4]>::default();
@@ -216,8 +786,122 @@ mod test_inline {
 
         assert_eq!("StoreBox([1, 2, 3])", format!("{:?}", boxed));
     }
+
+    #[test]
+    fn str_store() {
+        let store = InlineSingleStore::<[u8; 8]>::default();
+        let boxed = StoreBox::try_from_str("Hello", store).unwrap();
+
+        assert_eq!("Hello", &*boxed);
+        assert_eq!("Hello", boxed.to_string());
+
+        let store = InlineSingleStore::<[u8; 4]>::default();
+
+        assert_eq!(Err(AllocError), StoreBox::try_from_str("Hello", store).map(|_| ()));
+    }
+
+    #[test]
+    fn format_store() {
+        let store = InlineSingleStore::<[u8; 16]>::default();
+        let boxed = StoreBox::format(format_args!("{}-{:?}", 42, "x"), store);
+
+        assert_eq!(r#"42-"x""#, &*boxed);
+
+        let store = InlineSingleStore::<[u8; 16]>::default();
+        let boxed = StoreBox::format(format_args!("constant"), store);
+
+        assert_eq!("constant", &*boxed);
+
+        let store = InlineSingleStore::<[u8; 4]>::default();
+        let result = StoreBox::try_format(format_args!("{}", 123_456), store);
+
+        assert_eq!(Err(AllocError), result.map(|_| ()));
+    }
 } // mod test_inline
 
+#[cfg(test)]
+mod test_stack {
+    use crate::store::{StackBumpBlock, StackBumpStore};
+
+    use super::*;
+
+    #[test]
+    fn sized_store() {
+        let block = StackBumpBlock::<[u32; 4]>::new();
+
+        let mut boxed = StoreBox::new_in(1u32, block.create_store::<u8>());
+
+        assert_eq!(1u32, *boxed);
+
+        *boxed = 2;
+
+        assert_eq!(2u32, *boxed);
+    }
+
+    #[test]
+    fn take_value_reuse() {
+        //  Room for a single `u32`: each replacement must reuse the block of memory.
+        let block = StackBumpBlock::<[u32; 1]>::new();
+
+        let mut boxed = StoreBox::new_in(1u32, block.create_store::<u8>());
+
+        for _ in 0..4 {
+            let (value, empty) = StoreBox::take_value(boxed);
+
+            boxed = StoreBox::write(empty, value * 2);
+        }
+
+        assert_eq!(16, *boxed);
+    }
+
+    #[test]
+    fn shared_block() {
+        let block = StackBumpBlock::<[u32; 4]>::new();
+
+        let first: StoreBox<[u32], StackBumpStore<'_, u8>> =
+            StoreBox::coerce(StoreBox::new_in([1u32, 2], block.create_store()));
+        let second: StoreBox<[u32], StackBumpStore<'_, u8>> =
+            StoreBox::coerce(StoreBox::new_in([3u32, 4], block.create_store()));
+
+        //  Allocating the second box must not invalidate the first, as both stores reference the same block.
+        assert_eq!([1u32, 2], &*first);
+        assert_eq!([3u32, 4], &*second);
+    }
+} // mod test_stack
+
+#[cfg(test)]
+mod test_ref {
+    use crate::store::InlineBumpStore;
+
+    use super::*;
+
+    #[test]
+    fn shared_store() {
+        let store = InlineBumpStore::<u8, [u32; 8]>::default();
+
+        let boxes: [RefStoreBox<'_, u32, _>; 4] = core::array::from_fn(|i| StoreBox::new_in_ref(i as u32, &store));
+
+        assert_eq!([0, 1, 2, 3], boxes.each_ref().map(|boxed| **boxed));
+        assert_eq!(16, store.used());
+
+        drop(boxes);
+
+        //  The store is only borrowed, hence still usable after the boxes are dropped.
+        let boxed = StoreBox::try_new_in_ref(4u32, &store).unwrap();
+
+        assert_eq!(4, *boxed);
+    }
+
+    #[test]
+    fn shared_store_exhausted() {
+        let store = InlineBumpStore::<u8, [u32; 1]>::default();
+
+        let _first = StoreBox::new_in_ref(1u32, &store);
+
+        assert!(StoreBox::try_new_in_ref(2u32, &store).is_err());
+    }
+} // mod test_ref
+
 #[cfg(test)]
 mod test_allocator {
     use std::alloc::System;
@@ -292,6 +976,33 @@ mod test_allocator {
         assert_eq!("StoreBox([1, 2, 3])", format!("{:?}", boxed));
     }
 
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn from_box() {
+        let boxed: Box<[u8], System> = Box::new_in([1u8, 2, 3], System);
+        let mut boxed: StoreBox<[u8], _> = boxed.into();
+
+        assert_eq!([1u8, 2, 3], &*boxed);
+
+        boxed[2] = 4;
+
+        let boxed = boxed.into_box();
+
+        assert_eq!([1u8, 2, 4], &*boxed);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn from_box_zero_sized() {
+        let boxed: StoreBox<(), _> = Box::new_in((), System).into();
+
+        assert_eq!((), *boxed);
+
+        let boxed = boxed.into_box();
+
+        assert_eq!((), *boxed);
+    }
+
     #[cfg(feature = "coercible-metadata")]
     #[test]
     fn trait_coercion() {
@@ -0,0 +1,447 @@
+//! Proof-of-Concept implementation of a `Box<RefCell<T>>` atop a `StoreSingle`.
+//!
+//! The value lives in the store, rather than inline, while the borrow flag lives alongside the store, hence a large
+//! value may be mutated through a shared reference whilst living in fixed memory.
+//!
+//! The handle is resolved anew on each borrow, hence the cell may be moved freely while not borrowed, even when its
+//! store is inline.
+
+use core::{
+    alloc::AllocError,
+    cell::{Cell, UnsafeCell},
+    fmt,
+    marker::PhantomData,
+    mem::{self, ManuallyDrop},
+    ops::{Deref, DerefMut},
+    ptr::{self, NonNull},
+};
+
+use crate::{extension::unique_single::UniqueSingleHandle, interface::StoreSingle};
+
+/// A `RefCell` atop a `StoreSingle`.
+pub struct StoreCell<T, S: StoreSingle> {
+    //  0 if not borrowed, the number of shared borrows if positive, -1 if mutably borrowed.
+    borrow: Cell<isize>,
+    //  Only ever accessed mutably while mutably borrowed, or through `&mut self`.
+    store: UnsafeCell<ManuallyDrop<S>>,
+    handle: UniqueSingleHandle<T, S::Handle>,
+}
+
+/// A shared borrow of the value of a `StoreCell`.
+pub struct CellRef<'a, T> {
+    value: NonNull<T>,
+    borrow: &'a Cell<isize>,
+}
+
+/// A mutable borrow of the value of a `StoreCell`.
+pub struct CellRefMut<'a, T> {
+    value: NonNull<T>,
+    borrow: &'a Cell<isize>,
+    _marker: PhantomData<&'a mut T>,
+}
+
+impl<T, S: StoreSingle + Default> StoreCell<T, S> {
+    /// Creates a new instance.
+    ///
+    /// Calls `handle_alloc_error` if the store fails to provide the memory.
+    pub fn new(value: T) -> Self {
+        Self::new_in(value, S::default())
+    }
+}
+
+impl<T, S: StoreSingle> StoreCell<T, S> {
+    /// Creates a new instance.
+    ///
+    /// Calls `handle_alloc_error` if the store fails to provide the memory.
+    pub fn new_in(value: T, mut store: S) -> Self {
+        let handle = UniqueSingleHandle::new(value, &mut store);
+
+        Self::from_parts(handle, store)
+    }
+
+    /// Attempts to create a new instance.
+    pub fn try_new_in(value: T, mut store: S) -> Result<Self, AllocError> {
+        let handle = UniqueSingleHandle::try_new(value, &mut store)?;
+
+        Ok(Self::from_parts(handle, store))
+    }
+
+    /// Consumes the cell, returning its value.
+    pub fn into_inner(self) -> T {
+        let mut this = ManuallyDrop::new(self);
+
+        //  Safety:
+        //  -   `this.handle` is valid, and will not be used after this point, as `this` is never dropped.
+        let handle = unsafe { ptr::read(&this.handle) };
+
+        //  Safety:
+        //  -   `this.store` will not be used after this point, as `this` is never dropped.
+        let mut store = unsafe { ManuallyDrop::take(this.store.get_mut()) };
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        //  -   `handle` is associated to a block of memory containing a live instance of `T`.
+        let value = unsafe { ptr::read(handle.resolve_raw(&store).as_ptr()) };
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        //  -   The value was moved out, hence the block of memory is no longer used.
+        unsafe { handle.deallocate(&mut store) };
+
+        value
+    }
+
+    /// Returns whether the value is currently borrowed, mutably or not.
+    pub fn is_borrowed(&self) -> bool {
+        self.borrow.get() != 0
+    }
+
+    /// Borrows the value.
+    ///
+    /// #   Panics
+    ///
+    /// If the value is currently mutably borrowed.
+    #[track_caller]
+    pub fn borrow(&self) -> CellRef<'_, T> {
+        let Some(borrowed) = self.try_borrow() else {
+            panic!("StoreCell already mutably borrowed")
+        };
+
+        borrowed
+    }
+
+    /// Attempts to borrow the value.
+    ///
+    /// Returns `None` if the value is currently mutably borrowed.
+    pub fn try_borrow(&self) -> Option<CellRef<'_, T>> {
+        let borrow = self.borrow.get();
+
+        if borrow < 0 || borrow == isize::MAX {
+            return None;
+        }
+
+        self.borrow.set(borrow + 1);
+
+        //  Safety:
+        //  -   The store is not mutably borrowed, as the value is not mutably borrowed, and `self` is not borrowed
+        //      mutably either.
+        let store = unsafe { &*self.store.get() };
+
+        //  Safety:
+        //  -   `self.handle` was allocated by `store`, and is still valid.
+        let value = unsafe { self.handle.resolve_raw(&**store) };
+
+        Some(CellRef {
+            value,
+            borrow: &self.borrow,
+        })
+    }
+
+    /// Borrows the value, mutably.
+    ///
+    /// #   Panics
+    ///
+    /// If the value is currently borrowed, mutably or not.
+    #[track_caller]
+    pub fn borrow_mut(&self) -> CellRefMut<'_, T> {
+        let Some(borrowed) = self.try_borrow_mut() else {
+            panic!("StoreCell already borrowed")
+        };
+
+        borrowed
+    }
+
+    /// Attempts to borrow the value, mutably.
+    ///
+    /// Returns `None` if the value is currently borrowed, mutably or not.
+    pub fn try_borrow_mut(&self) -> Option<CellRefMut<'_, T>> {
+        if self.borrow.get() != 0 {
+            return None;
+        }
+
+        self.borrow.set(-1);
+
+        //  Safety:
+        //  -   The store is not otherwise borrowed, as the value was not borrowed, and `self` is not borrowed mutably
+        //      either.
+        //  -   The store will not be borrowed for as long as the value is mutably borrowed.
+        let store = unsafe { &mut *self.store.get() };
+
+        //  Safety:
+        //  -   `self.handle` was allocated by `store`, and is still valid.
+        let value = unsafe { self.handle.resolve_raw_mut(&mut **store) };
+
+        Some(CellRefMut {
+            value,
+            borrow: &self.borrow,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Returns a mutable reference to the value.
+    ///
+    /// No runtime check is necessary, as `self` is borrowed mutably.
+    pub fn get_mut(&mut self) -> &mut T {
+        //  Safety:
+        //  -   `self.handle` was allocated by `self.store`, and is still valid.
+        //  -   `self.handle` is associated to a block of memory containing a live instance of `T`.
+        //  -   The block of memory is not otherwise borrowed, as `self` is borrowed mutably.
+        unsafe { self.handle.resolve_mut(&mut **self.store.get_mut()) }
+    }
+
+    /// Replaces the value, returning the former one.
+    ///
+    /// #   Panics
+    ///
+    /// If the value is currently borrowed, mutably or not.
+    #[track_caller]
+    pub fn replace(&self, value: T) -> T {
+        mem::replace(&mut *self.borrow_mut(), value)
+    }
+
+    /// Replaces the value with its default, returning the former one.
+    ///
+    /// #   Panics
+    ///
+    /// If the value is currently borrowed, mutably or not.
+    #[track_caller]
+    pub fn take(&self) -> T
+    where
+        T: Default,
+    {
+        self.replace(T::default())
+    }
+}
+
+impl<T, S: StoreSingle> Drop for StoreCell<T, S> {
+    fn drop(&mut self) {
+        let value: *mut T = self.get_mut();
+
+        //  Safety:
+        //  -   The instance is live.
+        unsafe { ptr::drop_in_place(value) };
+
+        //  Safety:
+        //  -   `self.handle` is valid.
+        //  -   `self.handle` will not be used after this point.
+        let handle = unsafe { ptr::read(&self.handle) };
+
+        //  Safety:
+        //  -   `self.store` will never be used ever again.
+        let mut store = unsafe { ManuallyDrop::take(self.store.get_mut()) };
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`.
+        //  -   `handle` is still valid.
+        unsafe { handle.deallocate(&mut store) };
+    }
+}
+
+impl<T: Default, S: StoreSingle + Default> Default for StoreCell<T, S> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: Clone, S: StoreSingle + Default> Clone for StoreCell<T, S> {
+    #[track_caller]
+    fn clone(&self) -> Self {
+        Self::new(self.borrow().clone())
+    }
+}
+
+impl<T: fmt::Debug, S: StoreSingle> fmt::Debug for StoreCell<T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        let mut builder = f.debug_struct("StoreCell");
+
+        match self.try_borrow() {
+            Some(value) => builder.field("value", &*value),
+            None => builder.field("value", &format_args!("<borrowed>")),
+        };
+
+        builder.finish()
+    }
+}
+
+//  Safety:
+//  -   The cell owns its value and its store, hence may be sent to another thread if both can.
+//  -   The cell is not borrowed while being sent, as sending requires moving it.
+unsafe impl<T, S> Send for StoreCell<T, S>
+where
+    T: Send,
+    S: StoreSingle + Send,
+{
+}
+
+//
+//  Borrows
+//
+
+impl<T> Deref for CellRef<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        //  Safety:
+        //  -   `self.value` is valid, and not mutably borrowed, as long as `self` lives.
+        unsafe { self.value.as_ref() }
+    }
+}
+
+impl<T> Drop for CellRef<'_, T> {
+    fn drop(&mut self) {
+        debug_assert!(self.borrow.get() > 0);
+
+        self.borrow.set(self.borrow.get() - 1);
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for CellRef<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_tuple("CellRef").field(&**self).finish()
+    }
+}
+
+impl<T> Deref for CellRefMut<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        //  Safety:
+        //  -   `self.value` is valid, and exclusively borrowed by `self`, as long as `self` lives.
+        unsafe { self.value.as_ref() }
+    }
+}
+
+impl<T> DerefMut for CellRefMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        //  Safety:
+        //  -   `self.value` is valid, and exclusively borrowed by `self`, as long as `self` lives.
+        unsafe { self.value.as_mut() }
+    }
+}
+
+impl<T> Drop for CellRefMut<'_, T> {
+    fn drop(&mut self) {
+        debug_assert_eq!(-1, self.borrow.get());
+
+        self.borrow.set(0);
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for CellRefMut<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_tuple("CellRefMut").field(&**self).finish()
+    }
+}
+
+//
+//  Implementation
+//
+
+impl<T, S: StoreSingle> StoreCell<T, S> {
+    fn from_parts(handle: UniqueSingleHandle<T, S::Handle>, store: S) -> Self {
+        let borrow = Cell::new(0);
+        let store = UnsafeCell::new(ManuallyDrop::new(store));
+
+        Self { borrow, store, handle }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::store::{InlineSingleStore, StackBumpBlock};
+
+    use super::*;
+
+    type InlineCell<T> = StoreCell<T, InlineSingleStore<T>>;
+
+    #[test]
+    fn borrow() {
+        let cell = InlineCell::new(String::from("0"));
+
+        let first = cell.borrow();
+        let second = cell.borrow();
+
+        assert!(cell.is_borrowed());
+        assert_eq!("0", &*first);
+        assert_eq!("0", &*second);
+        assert!(cell.try_borrow_mut().is_none());
+
+        drop((first, second));
+
+        assert!(!cell.is_borrowed());
+    }
+
+    #[test]
+    fn borrow_mut() {
+        let cell = InlineCell::new(String::from("0"));
+
+        {
+            let mut value = cell.borrow_mut();
+
+            value.push('1');
+
+            assert!(cell.try_borrow().is_none());
+            assert!(cell.try_borrow_mut().is_none());
+        }
+
+        assert_eq!("01", &*cell.borrow());
+    }
+
+    #[test]
+    fn moved() {
+        let cell = InlineCell::new([7u64; 16]);
+
+        cell.borrow_mut()[3] = 3;
+
+        //  The store, and thus the value, moves along with the cell.
+        let cell = [cell];
+
+        assert_eq!(3, cell[0].borrow()[3]);
+        assert_eq!(7, cell[0].borrow()[4]);
+    }
+
+    #[test]
+    fn replace_take() {
+        let mut cell = InlineCell::new(String::from("0"));
+
+        assert_eq!("0", cell.replace(String::from("1")));
+        assert_eq!("1", cell.take());
+        assert_eq!("", &*cell.borrow());
+
+        cell.get_mut().push('2');
+
+        assert_eq!("2", cell.into_inner());
+    }
+
+    #[test]
+    fn debug() {
+        let cell = InlineCell::new(1u32);
+
+        assert_eq!("StoreCell { value: 1 }", format!("{cell:?}"));
+
+        let value = cell.borrow_mut();
+
+        assert_eq!("StoreCell { value: <borrowed> }", format!("{cell:?}"));
+        assert_eq!("CellRefMut(1)", format!("{value:?}"));
+    }
+
+    #[test]
+    fn stack_store() {
+        let block = StackBumpBlock::<[u64; 4]>::new();
+
+        let cell = StoreCell::new_in(String::from("0"), block.create_store::<u8>());
+
+        cell.borrow_mut().push('1');
+
+        assert_eq!("01", cell.into_inner());
+    }
+
+    #[test]
+    fn exhausted() {
+        let block = StackBumpBlock::<[u8; 4]>::new();
+
+        let result = StoreCell::try_new_in(0u64, block.create_store::<u8>());
+
+        assert_eq!(Some(AllocError), result.err());
+    }
+} // mod tests
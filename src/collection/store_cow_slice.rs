@@ -0,0 +1,183 @@
+//! Proof of concept clone-on-write slice.
+//!
+//! The slice either borrows its elements, or owns them in a block of memory provided by its store, and only copies
+//! the borrowed elements into the store when mutable access is first requested. This suits parsers, for example,
+//! which mostly hand out slices of their input, yet occasionally need to modify them, in fixed memory.
+//!
+//! Unlike `std::borrow::Cow`, the slice holds onto its store even while borrowing, so that it can promote its
+//! elements at any time.
+
+use core::{alloc::AllocError, fmt, ops::Deref};
+
+use crate::{
+    collection::{StoreBox, StoreVec},
+    interface::{StoreDangling, StoreSingle},
+};
+
+/// A slice, either borrowing its elements, or owning them in its store.
+pub struct StoreCowSlice<'a, T, S: StoreSingle> {
+    //  Type invariant:
+    //  -   If `borrowed` is `Some`, the elements of `owned` are meaningless: they may be left over from a promotion
+    //      interrupted by a panicking `clone`.
+    borrowed: Option<&'a [T]>,
+    owned: StoreVec<T, S>,
+}
+
+impl<'a, T, S: StoreSingle> StoreCowSlice<'a, T, S> {
+    /// Creates a slice borrowing `slice`, which will promote its elements into `store` when mutated.
+    pub const fn borrowed_in(slice: &'a [T], store: S) -> Self
+    where
+        S: ~const StoreDangling,
+    {
+        let owned = StoreVec::new_in(store);
+
+        Self {
+            borrowed: Some(slice),
+            owned,
+        }
+    }
+
+    /// Creates a slice owning the elements of `boxed`.
+    pub fn owned(boxed: StoreBox<[T], S>) -> Self {
+        let owned = StoreVec::from_boxed_slice(boxed);
+
+        Self { borrowed: None, owned }
+    }
+
+    /// Returns whether the elements are borrowed.
+    pub const fn is_borrowed(&self) -> bool {
+        self.borrowed.is_some()
+    }
+
+    /// Returns whether the elements are owned.
+    pub const fn is_owned(&self) -> bool {
+        self.borrowed.is_none()
+    }
+
+    /// Returns the elements, whether borrowed or owned.
+    pub fn as_slice(&self) -> &[T] {
+        match self.borrowed {
+            Some(slice) => slice,
+            None => self.owned.as_slice(),
+        }
+    }
+}
+
+impl<'a, T: Clone, S: StoreSingle> StoreCowSlice<'a, T, S> {
+    /// Returns the elements mutably, cloning them into the store first if borrowed.
+    ///
+    /// Calls `handle_alloc_error` if the store cannot accommodate the elements.
+    pub fn to_mut(&mut self) -> &mut [T] {
+        if let Some(slice) = self.borrowed {
+            self.owned.clear();
+            self.owned.extend(slice.iter().cloned());
+            self.borrowed = None;
+        }
+
+        self.owned.as_mut_slice()
+    }
+
+    /// Attempts to return the elements mutably, cloning them into the store first if borrowed.
+    ///
+    /// Returns an error, and keeps borrowing the elements, if the store cannot accommodate them.
+    pub fn try_to_mut(&mut self) -> Result<&mut [T], AllocError> {
+        if let Some(slice) = self.borrowed {
+            self.owned.clear();
+            self.owned.try_reserve(slice.len())?;
+
+            //  No reallocation occurs, as sufficient capacity was reserved.
+            self.owned.extend(slice.iter().cloned());
+            self.borrowed = None;
+        }
+
+        Ok(self.owned.as_mut_slice())
+    }
+
+    /// Converts the slice into a `StoreBox<[T], S>`, cloning the elements into the store first if borrowed.
+    ///
+    /// Calls `handle_alloc_error` if the store cannot accommodate the elements.
+    pub fn into_owned(mut self) -> StoreBox<[T], S> {
+        self.to_mut();
+
+        self.owned.into_boxed_slice()
+    }
+}
+
+impl<'a, T, S: StoreSingle> Deref for StoreCowSlice<'a, T, S> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<'a, T, S: StoreSingle> AsRef<[T]> for StoreCowSlice<'a, T, S> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<'a, T, S: StoreSingle> From<StoreBox<[T], S>> for StoreCowSlice<'a, T, S> {
+    fn from(value: StoreBox<[T], S>) -> Self {
+        Self::owned(value)
+    }
+}
+
+impl<'a, T: fmt::Debug, S: StoreSingle> fmt::Debug for StoreCowSlice<'a, T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        self.as_slice().fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::store::InlineSingleStore;
+
+    use super::*;
+
+    type TestStore = InlineSingleStore<[String; 4]>;
+
+    #[test]
+    fn borrow_then_promote() {
+        let input = [String::from("0"), String::from("1")];
+
+        let mut cow = StoreCowSlice::borrowed_in(&input[..], TestStore::default());
+
+        assert!(cow.is_borrowed());
+        assert_eq!(input.as_ptr(), cow.as_ptr());
+
+        cow.to_mut()[1].push('1');
+
+        assert!(cow.is_owned());
+        assert_eq!(["0", "11"], &cow[..]);
+        assert_eq!(["0", "1"], input);
+
+        let boxed = cow.into_owned();
+
+        assert_eq!(["0", "11"], &boxed[..]);
+    }
+
+    #[test]
+    fn try_to_mut_exhausted() {
+        let input: [String; 5] = core::array::from_fn(|i| i.to_string());
+
+        let mut cow = StoreCowSlice::borrowed_in(&input[..], TestStore::default());
+
+        assert_eq!(Err(AllocError), cow.try_to_mut().map(|_| ()));
+        assert!(cow.is_borrowed());
+        assert_eq!(input, &cow[..]);
+    }
+
+    #[test]
+    fn owned() {
+        let boxed = StoreVec::from_iter((0..3).map(|i| i.to_string())).into_boxed_slice();
+
+        let mut cow: StoreCowSlice<'_, _, TestStore> = StoreCowSlice::owned(boxed);
+
+        assert!(cow.is_owned());
+
+        cow.to_mut()[0].push('0');
+
+        assert_eq!(r#"["00", "1", "2"]"#, format!("{cow:?}"));
+    }
+} // mod tests
@@ -0,0 +1,181 @@
+//! A `Box<dyn Error + Send + Sync>` atop a `StoreSingle`, usable without `alloc`.
+//!
+//! A `StoreError` boxes any error in its store, via `StoreError::from_error`, so that errors of different types may be
+//! propagated as one, as with `Box<dyn Error + Send + Sync>`. Ad-hoc errors may be created from a message, via
+//! `StoreError::from_fmt` or `StoreError::from_display`, which format the message into a `StoreBox<str, M>` of its own
+//! store.
+//!
+//! Using `InlineSingleStore` for both stores, the error, and its message, are entirely inline, at the cost of a fixed
+//! maximum size for each: see the `no_std_error` example.
+//!
+//! _Note: `StoreError` cannot implement `From<E>` for any error `E`, as `?` would require, since it would conflict with
+//! the conversion from `Box` with the `alloc` feature._
+
+use core::{alloc::AllocError, error::Error, fmt};
+
+use crate::{collection::StoreBox, interface::StoreSingle};
+
+/// A `Box<dyn Error + Send + Sync>` atop a `StoreSingle`.
+pub type StoreError<S> = StoreBox<dyn Error + Send + Sync, S>;
+
+/// An error consisting solely of a message, itself boxed in a store of type `M`.
+pub struct ErrorMessage<M: StoreSingle>(StoreBox<str, M>);
+
+impl<M: StoreSingle> ErrorMessage<M> {
+    /// Creates a new instance, formatting `args` in `store`.
+    ///
+    /// Calls `handle_alloc_error` if `store` cannot accommodate the formatted string.
+    pub fn new(args: fmt::Arguments<'_>, store: M) -> Self {
+        Self(StoreBox::format(args, store))
+    }
+
+    /// Attempts to create a new instance, formatting `args` in `store`.
+    pub fn try_new(args: fmt::Arguments<'_>, store: M) -> Result<Self, AllocError> {
+        StoreBox::try_format(args, store).map(Self)
+    }
+
+    /// Returns the message.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<M: StoreSingle> fmt::Debug for ErrorMessage<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<M: StoreSingle> fmt::Display for ErrorMessage<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<M: StoreSingle> Error for ErrorMessage<M> {}
+
+//  Safety:
+//  -   `str` is `Send`.
+//  -   The handle is uniquely owned, and only ever resolved by the store it is sent alongside of, which is `Send`.
+unsafe impl<M: StoreSingle + Send> Send for ErrorMessage<M> {}
+
+//  Safety:
+//  -   `str` is `Sync`.
+//  -   The handle is only ever resolved by shared reference to the store, which is `Sync`.
+unsafe impl<M: StoreSingle + Sync> Sync for ErrorMessage<M> {}
+
+impl<S: StoreSingle> StoreBox<dyn Error + Send + Sync, S> {
+    /// Creates a new instance, boxing `error` in `store`.
+    ///
+    /// Calls `handle_alloc_error` if `store` cannot accommodate `error`.
+    pub fn from_error<E>(error: E, store: S) -> Self
+    where
+        E: Error + Send + Sync + 'static,
+    {
+        let boxed: StoreBox<E, S> = StoreBox::new_in(error, store);
+
+        StoreBox::coerce(boxed)
+    }
+
+    /// Attempts to create a new instance, boxing `error` in `store`.
+    pub fn try_from_error<E>(error: E, store: S) -> Result<Self, AllocError>
+    where
+        E: Error + Send + Sync + 'static,
+    {
+        let boxed: StoreBox<E, S> = StoreBox::try_new_in(error, store)?;
+
+        Ok(StoreBox::coerce(boxed))
+    }
+
+    /// Creates a new instance, from a message formatted in `message_store`, and boxed in `store`.
+    ///
+    /// Calls `handle_alloc_error` if either store cannot accommodate its part.
+    pub fn from_fmt<M>(args: fmt::Arguments<'_>, message_store: M, store: S) -> Self
+    where
+        M: StoreSingle + Send + Sync + 'static,
+    {
+        Self::from_error(ErrorMessage::new(args, message_store), store)
+    }
+
+    /// Attempts to create a new instance, from a message formatted in `message_store`, and boxed in `store`.
+    pub fn try_from_fmt<M>(args: fmt::Arguments<'_>, message_store: M, store: S) -> Result<Self, AllocError>
+    where
+        M: StoreSingle + Send + Sync + 'static,
+    {
+        Self::try_from_error(ErrorMessage::try_new(args, message_store)?, store)
+    }
+
+    /// Creates a new instance, from `value` displayed in `message_store`, and boxed in `store`.
+    ///
+    /// Calls `handle_alloc_error` if either store cannot accommodate its part.
+    pub fn from_display<M>(value: impl fmt::Display, message_store: M, store: S) -> Self
+    where
+        M: StoreSingle + Send + Sync + 'static,
+    {
+        Self::from_fmt(format_args!("{value}"), message_store, store)
+    }
+
+    /// Attempts to create a new instance, from `value` displayed in `message_store`, and boxed in `store`.
+    pub fn try_from_display<M>(value: impl fmt::Display, message_store: M, store: S) -> Result<Self, AllocError>
+    where
+        M: StoreSingle + Send + Sync + 'static,
+    {
+        Self::try_from_fmt(format_args!("{value}"), message_store, store)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::num::ParseIntError;
+
+    use crate::store::InlineSingleStore;
+
+    use super::*;
+
+    type MessageStore = InlineSingleStore<[u8; 32]>;
+    type TestStore = InlineSingleStore<[usize; 8]>;
+
+    type TestError = StoreError<TestStore>;
+
+    fn parse(value: &str) -> Result<u8, TestError> {
+        let number: u32 = value
+            .parse()
+            .map_err(|error| TestError::from_error(error, TestStore::default()))?;
+
+        u8::try_from(number).map_err(|_| {
+            let (message_store, store) = (MessageStore::default(), TestStore::default());
+
+            TestError::from_fmt(format_args!("{number} is too large"), message_store, store)
+        })
+    }
+
+    #[test]
+    fn propagate() {
+        assert_eq!(42, parse("42").unwrap());
+
+        let error = parse("x").unwrap_err();
+
+        assert!(error.is::<ParseIntError>());
+        assert_eq!("invalid digit found in string", error.to_string());
+
+        let error = parse("256").unwrap_err();
+
+        assert!(error.is::<ErrorMessage<MessageStore>>());
+        assert_eq!("256 is too large", error.to_string());
+    }
+
+    #[test]
+    fn from_display() {
+        let error = TestError::from_display(42, MessageStore::default(), TestStore::default());
+
+        assert_eq!("42", error.to_string());
+        assert_eq!("StoreBox(\"42\")", format!("{error:?}"));
+    }
+
+    #[test]
+    fn message_too_long() {
+        let result = TestError::try_from_display("x".repeat(33), MessageStore::default(), TestStore::default());
+
+        assert_eq!(Some(AllocError), result.err());
+    }
+} // mod tests
@@ -0,0 +1,564 @@
+//! A Hash Set.
+//!
+//! This implementation is solely meant to demonstrate the use of `StoreSingle` with an open-addressing hash table, and
+//! notably the handling of tombstones over stores with a no-op `deallocate`. It is incomplete, and may be buggy.
+
+use core::{
+    alloc::AllocError,
+    borrow::Borrow,
+    fmt,
+    hash::{BuildHasher, Hash},
+    iter::{Chain, FusedIterator},
+};
+
+use crate::{
+    collection::hash_table::{self, Bucket, RawIter, RawTable},
+    interface::StoreSingle,
+};
+
+/// A hash set, using open-addressing.
+pub struct StoreHashSet<T, B, S: StoreSingle> {
+    table: RawTable<T, S>,
+    hasher: B,
+}
+
+/// A bucket of the table of a hash set.
+///
+/// Its sole purpose is to size inline stores: a set reserving `capacity` elements requires a store able to hold
+/// `[HashSetBucket<T>; N]`, with `N` being `HashSetBucket::<T>::buckets_for(capacity)`.
+#[repr(transparent)]
+pub struct HashSetBucket<T>(Bucket<T>);
+
+impl<T> HashSetBucket<T> {
+    /// Returns the number of buckets of the table of a set reserving `capacity` elements from scratch.
+    ///
+    /// #   Panics
+    ///
+    /// If the number of buckets overflows `usize`.
+    pub const fn buckets_for(capacity: usize) -> usize {
+        hash_table::buckets_for(capacity)
+    }
+}
+
+impl<T, B: Default, S: StoreSingle + Default> StoreHashSet<T, B, S> {
+    /// Creates a new, empty, set.
+    pub fn new() -> Self {
+        Self::new_in(S::default())
+    }
+}
+
+impl<T, B: Default, S: StoreSingle> StoreHashSet<T, B, S> {
+    /// Creates a new, empty, set with the specified `store`.
+    pub fn new_in(store: S) -> Self {
+        Self::with_hasher_in(B::default(), store)
+    }
+}
+
+impl<T, B, S: StoreSingle> StoreHashSet<T, B, S> {
+    /// Creates a new, empty, set with the specified `hasher` and `store`.
+    pub fn with_hasher_in(hasher: B, store: S) -> Self {
+        let table = RawTable::new_in(store);
+
+        Self { table, hasher }
+    }
+
+    /// Returns whether the set is empty, or not.
+    pub const fn is_empty(&self) -> bool {
+        self.table.len() == 0
+    }
+
+    /// Returns the number of elements in the set.
+    pub const fn len(&self) -> usize {
+        self.table.len()
+    }
+
+    /// Returns the number of elements the set can hold without reallocating.
+    pub const fn capacity(&self) -> usize {
+        self.table.capacity()
+    }
+
+    /// Returns a reference to the hasher of the set.
+    pub const fn hasher(&self) -> &B {
+        &self.hasher
+    }
+
+    /// Returns an iterator over the elements of the set, in arbitrary order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter(self.table.iter())
+    }
+
+    /// Removes all the elements of the set, keeping the allocated memory.
+    pub fn clear(&mut self) {
+        self.table.clear();
+    }
+}
+
+impl<T: Hash + Eq, B: BuildHasher, S: StoreSingle> StoreHashSet<T, B, S> {
+    /// Returns whether the set contains `value`, or not.
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.find(value).is_some()
+    }
+
+    /// Returns a reference to the element comparing equal to `value`, if any.
+    pub fn get<Q>(&self, value: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.find(value).map(|index| self.table.get(index))
+    }
+
+    /// Inserts `value` in the set, returning whether it was newly inserted.
+    ///
+    /// If an element comparing equal to `value` is already in the set, the set is left unchanged.
+    ///
+    /// Calls `handle_alloc_error` if the store fails to provide the memory.
+    pub fn insert(&mut self, value: T) -> bool {
+        if self.contains(&value) {
+            return false;
+        }
+
+        self.reserve(1);
+
+        self.try_insert(value).is_ok()
+    }
+
+    /// Attempts to insert `value` in the set, returning whether it was newly inserted.
+    ///
+    /// If an element comparing equal to `value` is already in the set, the set is left unchanged.
+    ///
+    /// Returns an error if the store fails to provide the memory.
+    pub fn try_insert(&mut self, value: T) -> Result<bool, AllocError> {
+        if self.contains(&value) {
+            return Ok(false);
+        }
+
+        let hash = Self::hash(&self.hasher, &value);
+        let hasher = &self.hasher;
+
+        self.table
+            .try_insert(hash, value, |element| Self::hash(hasher, element))?;
+
+        Ok(true)
+    }
+
+    /// Removes the element comparing equal to `value`, returning whether there was one.
+    pub fn remove<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.take(value).is_some()
+    }
+
+    /// Removes and returns the element comparing equal to `value`, if any.
+    pub fn take<Q>(&mut self, value: &Q) -> Option<T>
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.find(value).map(|index| self.table.remove(index))
+    }
+
+    /// Reserves room for at least `additional` more elements.
+    ///
+    /// Calls `handle_alloc_error` if the store fails to provide the memory.
+    pub fn reserve(&mut self, additional: usize) {
+        let hasher = &self.hasher;
+
+        self.table.reserve(additional, |element| Self::hash(hasher, element));
+    }
+
+    /// Attempts to reserve room for at least `additional` more elements.
+    ///
+    /// Returns an error if the store fails to provide the memory.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), AllocError> {
+        let hasher = &self.hasher;
+
+        self.table
+            .try_reserve(additional, |element| Self::hash(hasher, element))
+    }
+
+    /// Inserts all the elements of `iter` in the set, unless memory allocation fails.
+    ///
+    /// On failure, the elements inserted prior to the failure remain in the set, and the remaining elements of `iter`
+    /// are dropped.
+    pub fn try_extend<I>(&mut self, iter: I) -> Result<(), AllocError>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for element in iter {
+            self.try_insert(element)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns an iterator over the elements of `self` which are not in `other`.
+    pub fn difference<'a, OB, OS>(&'a self, other: &'a StoreHashSet<T, OB, OS>) -> Difference<'a, T, OB, OS>
+    where
+        OB: BuildHasher,
+        OS: StoreSingle,
+    {
+        Difference {
+            iter: self.iter(),
+            other,
+        }
+    }
+
+    /// Returns an iterator over the elements both in `self` and `other`.
+    pub fn intersection<'a, OB, OS>(&'a self, other: &'a StoreHashSet<T, OB, OS>) -> Intersection<'a, T, OB, OS>
+    where
+        OB: BuildHasher,
+        OS: StoreSingle,
+    {
+        Intersection {
+            iter: self.iter(),
+            other,
+        }
+    }
+
+    /// Returns an iterator over the elements either in `self` or `other`, without duplicates.
+    pub fn union<'a, OB, OS>(&'a self, other: &'a StoreHashSet<T, OB, OS>) -> Union<'a, T, B, S>
+    where
+        OB: BuildHasher,
+        OS: StoreSingle,
+    {
+        Union(self.iter().chain(other.difference(self)))
+    }
+}
+
+impl<T: Clone + Hash + Eq, B: BuildHasher + Clone, S: StoreSingle + Default> Clone for StoreHashSet<T, B, S> {
+    fn clone(&self) -> Self {
+        let mut result = Self::with_hasher_in(self.hasher.clone(), S::default());
+
+        result.extend(self.iter().cloned());
+
+        result
+    }
+}
+
+impl<T: fmt::Debug, B, S: StoreSingle> fmt::Debug for StoreHashSet<T, B, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<T, B: Default, S: StoreSingle + Default> Default for StoreHashSet<T, B, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, B, S, OB, OS> PartialEq<StoreHashSet<T, OB, OS>> for StoreHashSet<T, B, S>
+where
+    T: Hash + Eq,
+    B: BuildHasher,
+    S: StoreSingle,
+    OB: BuildHasher,
+    OS: StoreSingle,
+{
+    fn eq(&self, other: &StoreHashSet<T, OB, OS>) -> bool {
+        self.len() == other.len() && self.iter().all(|element| other.contains(element))
+    }
+}
+
+impl<T: Hash + Eq, B: BuildHasher, S: StoreSingle> Eq for StoreHashSet<T, B, S> {}
+
+//
+//  Iteration
+//
+
+impl<T: Hash + Eq, B: BuildHasher, S: StoreSingle> Extend<T> for StoreHashSet<T, B, S> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let iter = iter.into_iter();
+
+        //  Duplicates may lead to over-reserving, hence only the lower bound is used.
+        self.reserve(iter.size_hint().0);
+
+        for element in iter {
+            self.insert(element);
+        }
+    }
+}
+
+impl<'a, T: 'a + Copy + Hash + Eq, B: BuildHasher, S: StoreSingle> Extend<&'a T> for StoreHashSet<T, B, S> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a T>,
+    {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<T: Hash + Eq, B: BuildHasher + Default, S: StoreSingle + Default> FromIterator<T> for StoreHashSet<T, B, S> {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut result = Self::new();
+
+        result.extend(iter);
+
+        result
+    }
+}
+
+impl<'a, T, B, S: StoreSingle> IntoIterator for &'a StoreHashSet<T, B, S> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the elements of a hash set.
+pub struct Iter<'a, T>(RawIter<'a, T>);
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+impl<'a, T> FusedIterator for Iter<'a, T> {}
+
+impl<'a, T> Clone for Iter<'a, T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+/// Iterator over the elements of a hash set which are not in another.
+pub struct Difference<'a, T, B, S: StoreSingle> {
+    iter: Iter<'a, T>,
+    other: &'a StoreHashSet<T, B, S>,
+}
+
+impl<'a, T: Hash + Eq, B: BuildHasher, S: StoreSingle> Iterator for Difference<'a, T, B, S> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let other = self.other;
+
+        self.iter.find(|element| !other.contains(*element))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<'a, T: Hash + Eq, B: BuildHasher, S: StoreSingle> FusedIterator for Difference<'a, T, B, S> {}
+
+/// Iterator over the elements of a hash set which are also in another.
+pub struct Intersection<'a, T, B, S: StoreSingle> {
+    iter: Iter<'a, T>,
+    other: &'a StoreHashSet<T, B, S>,
+}
+
+impl<'a, T: Hash + Eq, B: BuildHasher, S: StoreSingle> Iterator for Intersection<'a, T, B, S> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let other = self.other;
+
+        self.iter.find(|element| other.contains(*element))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<'a, T: Hash + Eq, B: BuildHasher, S: StoreSingle> FusedIterator for Intersection<'a, T, B, S> {}
+
+/// Iterator over the elements of either of two hash sets, without duplicates.
+pub struct Union<'a, T, B, S: StoreSingle>(Chain<Iter<'a, T>, Difference<'a, T, B, S>>);
+
+impl<'a, T: Hash + Eq, B: BuildHasher, S: StoreSingle> Iterator for Union<'a, T, B, S> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a, T: Hash + Eq, B: BuildHasher, S: StoreSingle> FusedIterator for Union<'a, T, B, S> {}
+
+//
+//  Implementation
+//
+
+impl<T: Hash + Eq, B: BuildHasher, S: StoreSingle> StoreHashSet<T, B, S> {
+    fn hash<Q>(hasher: &B, value: &Q) -> u64
+    where
+        Q: ?Sized + Hash,
+    {
+        hasher.hash_one(value)
+    }
+
+    fn find<Q>(&self, value: &Q) -> Option<usize>
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        let hash = Self::hash(&self.hasher, value);
+
+        self.table.find(hash, |element| element.borrow() == value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::hash_map::DefaultHasher, hash::BuildHasherDefault};
+
+    use crate::store::{InlineSingleStore, StackBumpBlock, StackBumpStore};
+
+    use super::*;
+
+    type TestBuildHasher = BuildHasherDefault<DefaultHasher>;
+
+    type InlineHashSet<T, const N: usize> = StoreHashSet<T, TestBuildHasher, InlineSingleStore<[HashSetBucket<T>; N]>>;
+
+    #[test]
+    fn insert_remove() {
+        let mut set = InlineHashSet::<u32, 16>::new();
+
+        assert!(set.is_empty());
+
+        assert!(set.insert(1));
+        assert!(set.insert(2));
+        assert!(!set.insert(1));
+
+        assert_eq!(2, set.len());
+        assert!(set.contains(&1));
+        assert!(!set.contains(&3));
+        assert_eq!(Some(&2), set.get(&2));
+
+        assert!(set.remove(&1));
+        assert!(!set.remove(&1));
+        assert_eq!(Some(2), set.take(&2));
+
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn sized_from_buckets_for() {
+        const BUCKETS: usize = HashSetBucket::<u64>::buckets_for(20);
+
+        let mut set = InlineHashSet::<u64, BUCKETS>::new();
+
+        set.try_reserve(20).unwrap();
+        set.try_extend(0..20).unwrap();
+
+        assert_eq!(20, set.len());
+        assert!(set.capacity() >= 20);
+    }
+
+    #[test]
+    fn full() {
+        let mut set = InlineHashSet::<u32, 16>::new();
+
+        set.try_extend(0..14).unwrap();
+
+        assert_eq!(14, set.len());
+        assert_eq!(Err(AllocError), set.try_insert(14));
+
+        //  Inserting an element already present requires no memory.
+        assert_eq!(Ok(false), set.try_insert(13));
+    }
+
+    #[test]
+    fn tombstones() {
+        let mut set = InlineHashSet::<u32, 16>::new();
+
+        set.extend(0..13);
+
+        //  Each removal may leave a tombstone behind, which must be purged in place as the store cannot grow.
+        for i in 13..1_000 {
+            assert_eq!(Ok(true), set.try_insert(i));
+            assert!(set.remove(&(i - 13)));
+        }
+
+        assert_eq!(13, set.len());
+        assert_eq!(14, set.capacity());
+        assert!((987..1_000).all(|i| set.contains(&i)));
+    }
+
+    #[test]
+    fn tombstones_no_op_deallocate() {
+        let block = StackBumpBlock::<[u64; 64]>::new();
+        let store: StackBumpStore<'_, usize> = block.create_store();
+
+        let mut set = StoreHashSet::<u64, TestBuildHasher, _>::new_in(store);
+
+        set.extend(0..7);
+
+        //  With a no-op `deallocate`, reallocating to purge tombstones would exhaust the block.
+        for i in 7..1_000 {
+            set.insert(i);
+            set.remove(&(i - 7));
+        }
+
+        assert_eq!(7, set.len());
+        assert!((993..1_000).all(|i| set.contains(&i)));
+    }
+
+    #[test]
+    fn set_operations() {
+        let one: InlineHashSet<u32, 16> = [1, 2, 3, 4].into_iter().collect();
+        let two: InlineHashSet<u32, 16> = [3, 4, 5].into_iter().collect();
+
+        let sorted = |iter: &mut dyn Iterator<Item = &u32>| {
+            let mut result: Vec<_> = iter.copied().collect();
+            result.sort();
+            result
+        };
+
+        assert_eq!(vec![1, 2, 3, 4, 5], sorted(&mut one.union(&two)));
+        assert_eq!(vec![3, 4], sorted(&mut one.intersection(&two)));
+        assert_eq!(vec![1, 2], sorted(&mut one.difference(&two)));
+        assert_eq!(vec![5], sorted(&mut two.difference(&one)));
+    }
+
+    #[test]
+    fn equality() {
+        let one: InlineHashSet<u32, 16> = [1, 2, 3].into_iter().collect();
+        let mut two = InlineHashSet::<u32, 16>::new();
+
+        two.extend(&[3, 2, 1]);
+
+        assert_eq!(one, two);
+        assert_eq!(one, one.clone());
+
+        two.remove(&2);
+
+        assert_ne!(one, two);
+
+        two.clear();
+        two.insert(7);
+
+        assert_eq!("{7}", format!("{two:?}"));
+    }
+} // mod tests
@@ -0,0 +1,456 @@
+//! Proof of concept object pool.
+//!
+//! The pool allocates its slots once, at creation, then lends objects out as `PoolGuard`s, which return them to the
+//! pool when dropped. The store is never asked to deallocate, nor to allocate again, until the pool itself is dropped,
+//! hence the pool works equally well atop stores which never reclaim memory, such as `InlineBumpStore`.
+//!
+//! Objects are constructed lazily, on their first checkout, and are then kept alive across checkouts, so that their
+//! resources -- a buffer, for example -- may be reused. `StorePool::fill_with` constructs them all upfront instead.
+
+use core::{
+    alloc::AllocError,
+    cell::Cell,
+    fmt,
+    mem::MaybeUninit,
+    ops::{Deref, DerefMut},
+    ptr::{self, NonNull},
+};
+
+use crate::{extension::typed::TypedHandle, interface::Store};
+
+/// A fixed-capacity pool of objects, lent out as `PoolGuard`s.
+pub struct StorePool<T, S: Store> {
+    free: FreeList,
+    slots: TypedHandle<[Slot<T>], S::Handle>,
+    store: S,
+}
+
+/// An object checked out of a `StorePool`, returned to the pool when dropped.
+pub struct PoolGuard<'a, T> {
+    slot: NonNull<Slot<T>>,
+    index: usize,
+    free: &'a FreeList,
+}
+
+impl<T, S: Store> StorePool<T, S> {
+    /// Creates a pool of `capacity` slots, with a default store.
+    ///
+    /// Calls `handle_alloc_error` if the store cannot accommodate `capacity` slots.
+    pub fn with_capacity(capacity: usize) -> Self
+    where
+        S: Default,
+    {
+        Self::with_capacity_in(capacity, S::default())
+    }
+
+    /// Creates a pool of `capacity` slots, with the given store.
+    ///
+    /// Calls `handle_alloc_error` if the store cannot accommodate `capacity` slots.
+    pub fn with_capacity_in(capacity: usize, store: S) -> Self {
+        let slots = TypedHandle::allocate_slice(capacity, &store);
+
+        Self::from_slots(slots, store)
+    }
+
+    /// Attempts to create a pool of `capacity` slots, with the given store.
+    pub fn try_with_capacity_in(capacity: usize, store: S) -> Result<Self, AllocError> {
+        let slots = TypedHandle::try_allocate_slice(capacity, &store)?;
+
+        Ok(Self::from_slots(slots, store))
+    }
+}
+
+impl<T, S: Store> StorePool<T, S> {
+    /// Returns the number of slots of the pool.
+    pub const fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns the number of slots available for checkout.
+    pub fn available(&self) -> usize {
+        self.free.available.get()
+    }
+
+    /// Checks out an object, constructing it with `T::default()` if its slot was never used.
+    ///
+    /// Returns `None` if all objects are checked out.
+    pub fn checkout(&self) -> Option<PoolGuard<'_, T>>
+    where
+        T: Default,
+    {
+        self.checkout_with(T::default)
+    }
+
+    /// Checks out an object, constructing it with `f` if its slot was never used.
+    ///
+    /// The most recently returned object is checked out first, and objects already constructed are preferred.
+    ///
+    /// Returns `None` if all objects are checked out.
+    pub fn checkout_with<F>(&self, f: F) -> Option<PoolGuard<'_, T>>
+    where
+        F: FnOnce() -> T,
+    {
+        let index = self.free.pop(|index| {
+            //  Safety:
+            //  -   `index` is within bounds, as only indices of slots are ever pushed.
+            let slot = unsafe { self.slot(index) };
+
+            //  Safety:
+            //  -   `slot` is valid, and free, hence not referenced by any guard.
+            unsafe { (*slot.as_ptr()).next }
+        })?;
+
+        //  Safety:
+        //  -   `index` is within bounds, as only indices of slots are ever pushed.
+        let slot = unsafe { self.slot(index) };
+
+        //  Safety:
+        //  -   `slot` is valid, and now exclusively owned by the future guard.
+        let slot_ref = unsafe { &mut *slot.as_ptr() };
+
+        if !slot_ref.initialized {
+            slot_ref.value.write(f());
+            slot_ref.initialized = true;
+        }
+
+        let free = &self.free;
+
+        Some(PoolGuard { slot, index, free })
+    }
+
+    /// Constructs, with `f`, the objects of all the slots never used so far.
+    pub fn fill_with<F>(&mut self, mut f: F)
+    where
+        F: FnMut() -> T,
+    {
+        for index in 0..self.capacity() {
+            //  Safety:
+            //  -   `index` is within bounds.
+            let slot = unsafe { self.slot(index) };
+
+            //  Safety:
+            //  -   `slot` is valid, and no guard exists, as `self` is borrowed mutably.
+            let slot = unsafe { &mut *slot.as_ptr() };
+
+            if !slot.initialized {
+                slot.value.write(f());
+                slot.initialized = true;
+            }
+        }
+    }
+}
+
+impl<T, S: Store> Drop for StorePool<T, S> {
+    fn drop(&mut self) {
+        for index in 0..self.capacity() {
+            //  Safety:
+            //  -   `index` is within bounds.
+            let slot = unsafe { self.slot(index) };
+
+            //  Safety:
+            //  -   `slot` is valid, and no guard exists, as `self` is borrowed mutably.
+            let slot = unsafe { &mut *slot.as_ptr() };
+
+            if slot.initialized {
+                //  Safety:
+                //  -   `slot.value` is initialized, as per `slot.initialized`.
+                unsafe { slot.value.assume_init_drop() };
+            }
+        }
+
+        //  Safety:
+        //  -   `self.slots` was allocated by `self.store`, and is still valid.
+        //  -   `self.slots` will not be used after this point.
+        unsafe { self.slots.deallocate(&self.store) };
+    }
+}
+
+impl<T, S: Store> fmt::Debug for StorePool<T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_struct("StorePool")
+            .field("capacity", &self.capacity())
+            .field("available", &self.available())
+            .finish()
+    }
+}
+
+//  Safety:
+//  -   Same as `Vec<T>`: the objects are owned, and only lent out to guards borrowing the pool.
+unsafe impl<T, S> Send for StorePool<T, S>
+where
+    T: Send,
+    S: Store + Send,
+{
+}
+
+impl<T> PoolGuard<'_, T> {
+    /// Takes the object out of the pool, leaving its slot to be constructed anew on its next checkout.
+    pub fn take(this: Self) -> T {
+        //  Safety:
+        //  -   `this.slot` is valid, and exclusively owned by `this`.
+        let slot = unsafe { &mut *this.slot.as_ptr() };
+
+        slot.initialized = false;
+
+        //  Safety:
+        //  -   `slot.value` was initialized, and is now considered uninitialized.
+        unsafe { slot.value.assume_init_read() }
+    }
+}
+
+impl<T> Deref for PoolGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        //  Safety:
+        //  -   `self.slot` is valid, and exclusively owned by `self`.
+        //  -   `value` is initialized, as long as `self` lives.
+        unsafe { (*self.slot.as_ptr()).value.assume_init_ref() }
+    }
+}
+
+impl<T> DerefMut for PoolGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        //  Safety:
+        //  -   `self.slot` is valid, and exclusively owned by `self`.
+        //  -   `value` is initialized, as long as `self` lives.
+        unsafe { (*self.slot.as_ptr()).value.assume_init_mut() }
+    }
+}
+
+impl<T> Drop for PoolGuard<'_, T> {
+    fn drop(&mut self) {
+        let slot = self.slot.as_ptr();
+
+        self.free.push(self.index, |head| {
+            //  Safety:
+            //  -   `slot` is valid, and exclusively owned by `self`, until pushed.
+            unsafe { ptr::addr_of_mut!((*slot).next).write(head) };
+        });
+    }
+}
+
+impl<T> fmt::Debug for PoolGuard<'_, T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_tuple("PoolGuard").field(&**self).finish()
+    }
+}
+
+//
+//  Implementation
+//
+
+//  A free list of slots, threaded through the slots themselves.
+struct FreeList {
+    head: Cell<usize>,
+    available: Cell<usize>,
+}
+
+struct Slot<T> {
+    //  The index of the next free slot, if the slot is free.
+    next: usize,
+    initialized: bool,
+    value: MaybeUninit<T>,
+}
+
+impl FreeList {
+    //  Sentinel index, terminating the free list.
+    const NONE: usize = usize::MAX;
+
+    //  Pops the index of a free slot, if any, using `next` to read the index of the slot following it.
+    fn pop<F>(&self, next: F) -> Option<usize>
+    where
+        F: FnOnce(usize) -> usize,
+    {
+        let head = self.head.get();
+
+        if head == Self::NONE {
+            return None;
+        }
+
+        self.head.set(next(head));
+        self.available.set(self.available.get() - 1);
+
+        Some(head)
+    }
+
+    //  Pushes the index of a free slot, using `link` to write the index of the slot following it.
+    fn push<F>(&self, index: usize, link: F)
+    where
+        F: FnOnce(usize),
+    {
+        link(self.head.get());
+
+        self.head.set(index);
+        self.available.set(self.available.get() + 1);
+    }
+}
+
+impl<T, S: Store> StorePool<T, S> {
+    fn from_slots(slots: TypedHandle<[Slot<T>], S::Handle>, store: S) -> Self {
+        let capacity = slots.len();
+
+        //  Safety:
+        //  -   `slots` was allocated by `store`, and is still valid.
+        let pointer = unsafe { slots.resolve_raw(&store) };
+
+        //  The slots are linked in order, so that the first checkouts go to the first slots.
+        for index in 0..capacity {
+            let next = if index + 1 < capacity {
+                index + 1
+            } else {
+                FreeList::NONE
+            };
+
+            //  Safety:
+            //  -   `index` is within the bounds of the allocation.
+            let slot = unsafe { pointer.cast::<Slot<T>>().add(index) };
+
+            //  Safety:
+            //  -   `slot` is valid for writes, and suitably aligned.
+            unsafe {
+                slot.write(Slot {
+                    next,
+                    initialized: false,
+                    value: MaybeUninit::uninit(),
+                })
+            };
+        }
+
+        let head = Cell::new(if capacity > 0 { 0 } else { FreeList::NONE });
+        let available = Cell::new(capacity);
+
+        let free = FreeList { head, available };
+
+        Self { free, slots, store }
+    }
+
+    //  Returns a pointer to the slot at `index`.
+    //
+    //  The pointer remains valid as long as `self` does not move: only `self.slots` is ever resolved, and `self.store`
+    //  is not otherwise called until `self` is dropped.
+    //
+    //  #   Safety
+    //
+    //  -   `index` must be within bounds.
+    unsafe fn slot(&self, index: usize) -> NonNull<Slot<T>> {
+        debug_assert!(index < self.capacity());
+
+        //  Safety:
+        //  -   `self.slots` was allocated by `self.store`, and is still valid.
+        let pointer = unsafe { self.slots.resolve_raw(&self.store) };
+
+        //  Safety:
+        //  -   `index` is within bounds, as per pre-conditions.
+        unsafe { pointer.cast::<Slot<T>>().add(index) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::Cell;
+
+    use crate::store::InlineBumpStore;
+
+    use super::*;
+
+    type TestStore = InlineBumpStore<u8, [usize; 16]>;
+
+    #[test]
+    fn checkout_checkin() {
+        let pool = StorePool::<u32, TestStore>::with_capacity(2);
+
+        assert_eq!(2, pool.capacity());
+
+        let mut first = pool.checkout().unwrap();
+        let mut second = pool.checkout().unwrap();
+
+        assert_eq!(0, pool.available());
+        assert!(pool.checkout().is_none());
+
+        *first = 1;
+        *second = 2;
+
+        drop(first);
+
+        assert_eq!(1, pool.available());
+
+        //  The object is reused, as is.
+        let first = pool.checkout_with(|| 42).unwrap();
+
+        assert_eq!((1, 2), (*first, *second));
+    }
+
+    #[test]
+    fn lazy_construction() {
+        let constructed = Cell::new(0);
+
+        let construct = || {
+            constructed.set(constructed.get() + 1);
+
+            constructed.get()
+        };
+
+        let mut pool = StorePool::<usize, TestStore>::with_capacity(3);
+
+        let guard = pool.checkout_with(construct).unwrap();
+
+        assert_eq!(1, constructed.get());
+
+        drop(guard);
+
+        let guard = pool.checkout_with(construct).unwrap();
+
+        assert_eq!(1, *guard);
+        assert_eq!(1, constructed.get());
+
+        //  Taking the object leaves its slot to be constructed anew.
+        assert_eq!(1, PoolGuard::take(guard));
+
+        let guard = pool.checkout_with(construct).unwrap();
+
+        assert_eq!(2, *guard);
+
+        drop(guard);
+
+        pool.fill_with(construct);
+
+        assert_eq!(4, constructed.get());
+    }
+
+    #[test]
+    fn drop_constructed() {
+        let dropped = Cell::new(0);
+
+        struct Counted<'a>(&'a Cell<usize>);
+
+        impl Drop for Counted<'_> {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        {
+            let pool = StorePool::<Counted<'_>, TestStore>::with_capacity(3);
+
+            let first = pool.checkout_with(|| Counted(&dropped)).unwrap();
+            let _second = pool.checkout_with(|| Counted(&dropped)).unwrap();
+
+            drop(first);
+
+            assert_eq!(0, dropped.get());
+        }
+
+        assert_eq!(2, dropped.get());
+    }
+
+    #[test]
+    fn exhausted() {
+        let result = StorePool::<[u8; 64], TestStore>::try_with_capacity_in(4, TestStore::default());
+
+        assert_eq!(Some(AllocError), result.err());
+    }
+} // mod tests
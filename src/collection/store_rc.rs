@@ -0,0 +1,378 @@
+//! Proof-of-Concept implementation of a reference-counted pointer atop a sharing `StoreSingle`.
+//!
+//! Each `StoreRc` owns one part of a set of sharing stores, and the reference count is stored in the block of memory,
+//! ahead of the value. Cloning a `StoreRc` thus shares the store, and the block of memory is deallocated by whichever
+//! part drops the last reference.
+//!
+//! A `StoreBox` created by `StoreBox::new_shared_capable` has headroom for the reference count, and may be promoted
+//! into a `StoreRc` without copying, via `StoreBox::into_shared`. This enables building a value in place, then
+//! publishing it, for example in shared memory.
+
+use core::{alloc::AllocError, cell::Cell, fmt, marker::PhantomData, mem::ManuallyDrop, ops, ptr};
+
+use crate::{
+    extension::typed_single::TypedSingleHandle,
+    interface::{StoreSharing, StoreSingle},
+};
+
+/// A value, with headroom for the reference count of a `StoreRc`.
+///
+/// Dereferences to the value.
+pub struct SharedCapable<T> {
+    count: Cell<usize>,
+    value: T,
+}
+
+impl<T> SharedCapable<T> {
+    /// Creates a new instance.
+    pub const fn new(value: T) -> Self {
+        let count = Cell::new(1);
+
+        Self { count, value }
+    }
+
+    /// Returns the value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> ops::Deref for SharedCapable<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> ops::DerefMut for SharedCapable<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<T> fmt::Debug for SharedCapable<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        self.value.fmt(f)
+    }
+}
+
+/// A `Rc` atop a sharing `StoreSingle`.
+pub struct StoreRc<T, S: StoreSingle> {
+    store: ManuallyDrop<S>,
+    handle: TypedSingleHandle<SharedCapable<T>, S::Handle>,
+    //  Like `Rc`, neither `Send` nor `Sync`, as the reference count is not atomic.
+    _marker: PhantomData<*const SharedCapable<T>>,
+}
+
+impl<T, S: StoreSingle + Default> StoreRc<T, S> {
+    /// Creates a new instance.
+    pub fn new(value: T) -> Self {
+        Self::new_in(value, S::default())
+    }
+}
+
+impl<T, S: StoreSingle> StoreRc<T, S> {
+    /// Creates a new instance.
+    pub fn new_in(value: T, mut store: S) -> Self {
+        let handle = TypedSingleHandle::new(SharedCapable::new(value), &mut store);
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is associated to a live instance, with a count of 1.
+        unsafe { Self::from_parts(handle, store) }
+    }
+
+    /// Attempts to create a new instance.
+    pub fn try_new_in(value: T, mut store: S) -> Result<Self, AllocError> {
+        let handle = TypedSingleHandle::try_new(SharedCapable::new(value), &mut store)?;
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is associated to a live instance, with a count of 1.
+        Ok(unsafe { Self::from_parts(handle, store) })
+    }
+
+    /// Returns the number of instances referring to the same value.
+    pub fn strong_count(this: &Self) -> usize {
+        this.inner().count.get()
+    }
+
+    /// Returns a mutable reference to the value, if no other instance refers to it.
+    pub fn get_mut(this: &mut Self) -> Option<&mut T> {
+        if Self::strong_count(this) != 1 {
+            return None;
+        }
+
+        //  Safety:
+        //  -   `this.handle` was allocated by `this.store`, and is still valid.
+        //  -   `this.handle` is associated to a live instance.
+        //  -   No other reference to the instance is active, as `this` is the only instance, and is borrowed mutably.
+        let inner = unsafe { this.handle.resolve_mut(&mut *this.store) };
+
+        Some(&mut inner.value)
+    }
+
+    /// Returns whether both instances refer to the same value.
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        ptr::eq(this.inner(), other.inner())
+    }
+}
+
+impl<T, S: StoreSingle + StoreSharing> StoreRc<T, S> {
+    /// Attempts to create a new instance referring to the same value, sharing the store of `self`.
+    ///
+    /// Returns an error if sharing is not currently possible.
+    pub fn try_clone(&self) -> Result<Self, S::SharingError> {
+        let store = self.store.share()?;
+
+        self.inner().increment();
+
+        //  Safety:
+        //  -   `self.handle` was allocated by a store sharing with `store`, and is still valid.
+        //  -   Its count was just incremented to account for the new instance.
+        Ok(unsafe { Self::from_parts(self.handle, store) })
+    }
+}
+
+impl<T, S> Clone for StoreRc<T, S>
+where
+    S: StoreSingle + StoreSharing<SharingError = !>,
+{
+    fn clone(&self) -> Self {
+        self.try_clone().into_ok()
+    }
+}
+
+impl<T, S: StoreSingle> Drop for StoreRc<T, S> {
+    fn drop(&mut self) {
+        let inner = self.inner();
+        let count = inner.count.get() - 1;
+
+        inner.count.set(count);
+
+        //  Safety:
+        //  -   `self.store` will never be used ever again.
+        let mut store = unsafe { ManuallyDrop::take(&mut self.store) };
+
+        if count > 0 {
+            return;
+        }
+
+        //  Safety:
+        //  -   `self.handle` was allocated by a store sharing with `store`, and is still valid.
+        let inner = unsafe { self.handle.resolve_raw_mut(&mut store) };
+
+        //  Safety:
+        //  -   `inner` points to a live instance, and no other instance refers to it.
+        //  -   The instance will not be used after this point.
+        unsafe { ptr::drop_in_place(inner.as_ptr()) };
+
+        //  Safety:
+        //  -   `self.handle` was allocated by a store sharing with `store`, and is still valid.
+        //  -   `self.handle` will not be used after this point.
+        unsafe { self.handle.deallocate(&mut store) };
+    }
+}
+
+impl<T, S: StoreSingle> ops::Deref for StoreRc<T, S> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.inner().value
+    }
+}
+
+impl<T, S: StoreSingle> fmt::Debug for StoreRc<T, S>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        let value: &T = self;
+
+        value.fmt(f)
+    }
+}
+
+impl<T, S: StoreSingle> fmt::Display for StoreRc<T, S>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        let value: &T = self;
+
+        value.fmt(f)
+    }
+}
+
+//
+//  Implementation
+//
+
+impl<T> SharedCapable<T> {
+    //  Increments the count, aborting on overflow, like `Rc`.
+    //
+    //  The count may only overflow if instances are leaked, for example via `mem::forget`, and a panic could be caught,
+    //  and the leaking carried on until the count wraps around, leading to a use-after-free.
+    fn increment(&self) {
+        match self.count.get().checked_add(1) {
+            Some(count) => self.count.set(count),
+            None => abort(),
+        }
+    }
+}
+
+//  Aborts the process, even without `std`: a panic whilst panicking aborts.
+#[cold]
+#[inline(never)]
+fn abort() -> ! {
+    struct Abort;
+
+    impl Drop for Abort {
+        fn drop(&mut self) {
+            panic!("Reference count overflow");
+        }
+    }
+
+    let _abort = Abort;
+
+    panic!("Reference count overflow");
+}
+
+impl<T, S: StoreSingle> StoreRc<T, S> {
+    //  Creates an instance from its parts.
+    //
+    //  #   Safety
+    //
+    //  -   `handle` must have been allocated by `store`, or a store sharing with it.
+    //  -   `handle` must still be valid.
+    //  -   `handle` must be associated to a block of memory containing a live instance of `SharedCapable<T>`, whose
+    //      count accounts for the new instance.
+    pub(crate) unsafe fn from_parts(handle: TypedSingleHandle<SharedCapable<T>, S::Handle>, store: S) -> Self {
+        let store = ManuallyDrop::new(store);
+        let _marker = PhantomData;
+
+        Self { store, handle, _marker }
+    }
+
+    fn inner(&self) -> &SharedCapable<T> {
+        //  Safety:
+        //  -   `self.handle` was allocated by `self.store`, or a store sharing with it, and is still valid.
+        //  -   `self.handle` is associated to a live instance.
+        //  -   No mutable reference to the instance is active, as those require `self` to be borrowed mutably.
+        unsafe { self.handle.resolve(&*self.store) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        collection::StoreBox,
+        store::{InlineBumpStore, RefStore, TrackingStore},
+    };
+
+    use super::*;
+
+    type TestStore = TrackingStore<InlineBumpStore<u8, [u64; 16]>, 4>;
+
+    #[test]
+    fn clone_drop() {
+        let store = TestStore::new(InlineBumpStore::default());
+
+        let first = StoreRc::new_in(String::from("Hello"), RefStore::new(&store));
+        let second = first.clone();
+
+        assert_eq!(2, StoreRc::strong_count(&first));
+        assert!(StoreRc::ptr_eq(&first, &second));
+        assert_eq!("Hello", &*second);
+
+        drop(first);
+
+        assert_eq!(1, StoreRc::strong_count(&second));
+        assert_eq!(1, store.len());
+
+        drop(second);
+
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn get_mut() {
+        let store = TestStore::new(InlineBumpStore::default());
+
+        let mut first = StoreRc::new_in(1, RefStore::new(&store));
+
+        *StoreRc::get_mut(&mut first).unwrap() += 1;
+
+        let second = first.clone();
+
+        assert_eq!(None, StoreRc::get_mut(&mut first));
+
+        drop(second);
+
+        assert_eq!(Some(&mut 2), StoreRc::get_mut(&mut first));
+    }
+
+    #[test]
+    fn publish_after_build() {
+        let store = TestStore::new(InlineBumpStore::default());
+
+        let mut boxed = StoreBox::new_shared_capable([0u32; 4], RefStore::new(&store));
+
+        for (index, element) in boxed.iter_mut().enumerate() {
+            *element = index as u32;
+        }
+
+        let address = &**boxed as *const [u32; 4];
+
+        let shared = StoreBox::into_shared(boxed);
+        let other = shared.clone();
+
+        //  No copy: the value is where it was built.
+        assert_eq!(address, &*shared as *const [u32; 4]);
+        assert_eq!([0, 1, 2, 3], *other);
+        assert_eq!(2, StoreRc::strong_count(&shared));
+
+        drop((shared, other));
+
+        assert!(store.is_empty());
+    }
+} // mod tests
+
+//  Exercises the raw access paths of the reference-counted block: a reference to the value is kept alive whilst its
+//  siblings are cloned and dropped, up to the drop of the value itself.
+//
+//  Run with `RUSTFLAGS="--cfg storage_miri_strict" MIRIFLAGS="-Zmiri-tree-borrows" cargo miri test tests_miri`, and
+//  without `-Zmiri-tree-borrows` for Stacked Borrows.
+#[cfg(all(test, storage_miri_strict))]
+mod tests_miri {
+    use crate::store::{InlineBumpStore, RefStore};
+
+    use super::*;
+
+    #[test]
+    fn drop_last_with_live_siblings() {
+        let store = InlineBumpStore::<u8, [u64; 16]>::default();
+
+        let first = StoreRc::new_in(String::from("Hello"), RefStore::new(&store));
+        let second = first.clone();
+
+        let value: &str = &second;
+
+        //  The counts are updated, and a sibling dropped, whilst `value` is live.
+        let third = second.clone();
+
+        drop(first);
+
+        assert_eq!("Hello", value);
+        assert_eq!(2, StoreRc::strong_count(&third));
+
+        drop(third);
+
+        assert_eq!("Hello", value);
+        assert_eq!(1, StoreRc::strong_count(&second));
+
+        drop(second);
+    }
+} // mod tests_miri
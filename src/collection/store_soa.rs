@@ -0,0 +1,944 @@
+//! Proof of concept structure of arrays.
+//!
+//! A `StoreSoA<T, S>` stores each field of `T` in its own array, each allocated separately within the same store, so
+//! that iterating over a single field only touches the memory of that field. This is the layout favoured by ECS-style
+//! code, in fixed memory if the store is.
+//!
+//! The fields of `T` are described by implementing the `SoA` trait, which is best done with `#[derive(SoA)]`, with the
+//! `derive` feature:
+//!
+#![cfg_attr(feature = "derive", doc = "```")]
+#![cfg_attr(not(feature = "derive"), doc = "```ignore")]
+//! use storage::{
+//!     collection::{SoA, StoreSoA},
+//!     store::InlineBumpStore,
+//! };
+//!
+//! #[derive(SoA)]
+//! pub struct Particle {
+//!     pub position: [f32; 2],
+//!     pub mass: f32,
+//! }
+//!
+//! let mut particles = StoreSoA::<Particle, InlineBumpStore<u16, [u64; 8]>>::new();
+//!
+//! particles.push(Particle { position: [0.0, 1.0], mass: 2.0 });
+//!
+//! let slices: ParticleSlices<'_> = particles.as_slices();
+//!
+//! assert_eq!([2.0], slices.mass);
+//! ```
+//!
+//! The derive generates, alongside the implementation, the `ParticleRef`, `ParticleMut`, `ParticleSlices`, and
+//! `ParticleSlicesMut` structs, with one reference, respectively slice, per field.
+
+use core::{
+    alloc::{AllocError, Layout},
+    iter::FusedIterator,
+    marker::PhantomData,
+    ptr::{self, NonNull},
+};
+
+use crate::{
+    alloc,
+    extension::typed::TypedHandle,
+    interface::{Store, StoreStable},
+};
+
+/// A type which may be stored as a structure of arrays, with one array per field.
+///
+/// The methods access the array of the field at index `i` through the pointer returned by `field(i)`.
+///
+/// #   Safety
+///
+/// Implementers must guarantee that:
+///
+/// -   `LAYOUTS` contains the layout of each field, in order.
+/// -   The methods only ever access the field at index `i` through the pointer returned by `field(i)`, as an array of
+///     instances of the type of this field.
+/// -   `write` and `read` only ever call `field(i)` once per field, and do not use the resulting pointer after calling
+///     `field` again.
+pub unsafe trait SoA: Sized {
+    /// The shared references to the fields of an element.
+    type Ref<'a>
+    where
+        Self: 'a;
+
+    /// The mutable references to the fields of an element.
+    type Mut<'a>
+    where
+        Self: 'a;
+
+    /// The shared slices of each field, across elements.
+    type Slices<'a>
+    where
+        Self: 'a;
+
+    /// The mutable slices of each field, across elements.
+    type SlicesMut<'a>
+    where
+        Self: 'a;
+
+    /// The layouts of the fields, in order.
+    const LAYOUTS: &'static [Layout];
+
+    /// Moves the fields of `self` into their arrays, at `index`.
+    ///
+    /// #   Safety
+    ///
+    /// -   `field(i)` must return a pointer to an array of at least `index + 1` elements of the field at index `i`,
+    ///     valid for writes.
+    /// -   The elements at `index` are overwritten, without being dropped.
+    unsafe fn write<F>(self, field: F, index: usize)
+    where
+        F: FnMut(usize) -> NonNull<u8>;
+
+    /// Moves the fields out of their arrays, at `index`.
+    ///
+    /// #   Safety
+    ///
+    /// -   `field(i)` must return a pointer to an array of at least `index + 1` elements of the field at index `i`,
+    ///     valid for reads.
+    /// -   The elements at `index` must be initialized, and are logically moved out.
+    unsafe fn read<F>(field: F, index: usize) -> Self
+    where
+        F: FnMut(usize) -> NonNull<u8>;
+
+    /// Returns references to the fields at `index` of their arrays.
+    ///
+    /// #   Safety
+    ///
+    /// -   `field(i)` must return a pointer to an array of at least `index + 1` elements of the field at index `i`,
+    ///     valid for reads for `'a`, even after further calls to `field`.
+    /// -   The elements at `index` must be initialized.
+    /// -   No mutable reference to the elements at `index` must exist for `'a`.
+    unsafe fn get<'a, F>(field: F, index: usize) -> Self::Ref<'a>
+    where
+        F: FnMut(usize) -> NonNull<u8>;
+
+    /// Returns mutable references to the fields at `index` of their arrays.
+    ///
+    /// #   Safety
+    ///
+    /// -   `field(i)` must return a pointer to an array of at least `index + 1` elements of the field at index `i`,
+    ///     valid for reads and writes for `'a`, even after further calls to `field`.
+    /// -   The elements at `index` must be initialized.
+    /// -   No other reference to the elements at `index` must exist for `'a`.
+    unsafe fn get_mut<'a, F>(field: F, index: usize) -> Self::Mut<'a>
+    where
+        F: FnMut(usize) -> NonNull<u8>;
+
+    /// Returns the slices of the first `length` elements of each array.
+    ///
+    /// #   Safety
+    ///
+    /// -   `field(i)` must return a pointer to an array of at least `length` elements of the field at index `i`, valid
+    ///     for reads for `'a`, even after further calls to `field`.
+    /// -   The first `length` elements must be initialized.
+    /// -   No mutable reference to these elements must exist for `'a`.
+    unsafe fn slices<'a, F>(field: F, length: usize) -> Self::Slices<'a>
+    where
+        F: FnMut(usize) -> NonNull<u8>;
+
+    /// Returns the mutable slices of the first `length` elements of each array.
+    ///
+    /// #   Safety
+    ///
+    /// -   `field(i)` must return a pointer to an array of at least `length` elements of the field at index `i`, valid
+    ///     for reads and writes for `'a`, even after further calls to `field`.
+    /// -   The first `length` elements must be initialized.
+    /// -   No other reference to these elements must exist for `'a`.
+    unsafe fn slices_mut<'a, F>(field: F, length: usize) -> Self::SlicesMut<'a>
+    where
+        F: FnMut(usize) -> NonNull<u8>;
+}
+
+/// A dynamic array of `T`, stored as one array per field of `T`.
+pub struct StoreSoA<T: SoA, S: Store> {
+    //  Type invariants:
+    //  -   If `T` has no field, or `self.capacity` is 0, `self.fields` is dangling.
+    //  -   Otherwise, `self.fields` is associated to a block of at least `T::LAYOUTS.len()` handles, one per field.
+    //  -   The handle of each field is dangling if the field is zero-sized, and otherwise is associated to a block of
+    //      memory fitting `self.capacity` elements of the field.
+    //  -   Elements in `0..self.length` are initialized.
+    length: usize,
+    capacity: usize,
+    fields: TypedHandle<[S::Handle], S::Handle>,
+    store: S,
+    _marker: PhantomData<T>,
+}
+
+impl<T: SoA, S: Store> StoreSoA<T, S> {
+    /// Creates a new, empty, instance.
+    pub fn new() -> Self
+    where
+        S: Default,
+    {
+        Self::new_in(S::default())
+    }
+
+    /// Creates a new, empty, instance with the specified `store`.
+    pub fn new_in(store: S) -> Self {
+        let fields = TypedHandle::dangling_slice(&store);
+
+        //  Without any field, no memory is ever necessary.
+        let capacity = if T::LAYOUTS.is_empty() { usize::MAX } else { 0 };
+
+        Self {
+            length: 0,
+            capacity,
+            fields,
+            store,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns whether the collection is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Returns the number of elements.
+    pub const fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Returns the capacity, in number of elements.
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Removes the last element, and returns it, if any.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.length == 0 {
+            return None;
+        }
+
+        self.length -= 1;
+
+        //  Safety:
+        //  -   Each field is resolved to an array of `self.capacity` elements, and `self.length < self.capacity`.
+        //  -   The elements at `self.length` were initialized, and are no longer considered so.
+        Some(unsafe { T::read(|i| self.resolve_field(i), self.length) })
+    }
+
+    /// Clears the collection, dropping all its elements.
+    pub fn clear(&mut self) {
+        while let Some(element) = self.pop() {
+            drop(element);
+        }
+    }
+}
+
+impl<T: SoA, S: Store + StoreStable> StoreSoA<T, S> {
+    /// Creates a new, empty, instance with at least the specified `capacity`, and the specified `store`.
+    ///
+    /// Calls `handle_alloc_error` if the store cannot accommodate `capacity` elements.
+    pub fn with_capacity_in(capacity: usize, store: S) -> Self {
+        let mut this = Self::new_in(store);
+
+        this.reserve(capacity);
+
+        this
+    }
+
+    /// Reserves capacity for at least `additional` more elements.
+    ///
+    /// #   Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes for any field.
+    ///
+    /// Calls `handle_alloc_error` if the store fails to provide the new capacity.
+    pub fn reserve(&mut self, additional: usize) {
+        let Some(target_capacity) = self.length.checked_add(additional) else {
+            Self::capacity_exceeded()
+        };
+
+        if target_capacity <= self.capacity {
+            return;
+        }
+
+        let Some(grown_capacity) = S::GROWTH.capacity(self.capacity, target_capacity) else {
+            Self::capacity_exceeded()
+        };
+
+        if Self::layouts(grown_capacity).is_none() {
+            Self::capacity_exceeded()
+        }
+
+        if self.grow_to(grown_capacity).is_err() {
+            alloc::handle_alloc_error(T::LAYOUTS.first().copied().unwrap_or(Layout::new::<()>()))
+        }
+    }
+
+    /// Attempts to reserve capacity for at least `additional` more elements.
+    ///
+    /// Returns an error if the new capacity exceeds `isize::MAX` bytes for any field, or if the store fails to
+    /// provide it.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), AllocError> {
+        let target_capacity = self.length.checked_add(additional).ok_or(AllocError)?;
+
+        if target_capacity <= self.capacity {
+            return Ok(());
+        }
+
+        if let Some(grown_capacity) = S::GROWTH.capacity(self.capacity, target_capacity) {
+            if self.grow_to(grown_capacity).is_ok() {
+                return Ok(());
+            }
+        }
+
+        //  The store may still be able to accommodate the exact capacity, notably if of fixed capacity.
+        self.grow_to(target_capacity)
+    }
+
+    /// Appends an element at the back.
+    ///
+    /// #   Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes for any field.
+    ///
+    /// Calls `handle_alloc_error` if the store fails to provide the new capacity.
+    pub fn push(&mut self, value: T) {
+        self.reserve(1);
+
+        //  Safety:
+        //  -   `self.length < self.capacity`, after reserving.
+        unsafe { self.push_unchecked(value) };
+    }
+
+    /// Attempts to append an element at the back.
+    ///
+    /// Returns the element if the store fails to provide the new capacity.
+    pub fn try_push(&mut self, value: T) -> Result<(), T> {
+        if self.try_reserve(1).is_err() {
+            return Err(value);
+        }
+
+        //  Safety:
+        //  -   `self.length < self.capacity`, after reserving.
+        unsafe { self.push_unchecked(value) };
+
+        Ok(())
+    }
+
+    /// Returns references to the fields of the element at `index`, if any.
+    pub fn get(&self, index: usize) -> Option<T::Ref<'_>> {
+        if index >= self.length {
+            return None;
+        }
+
+        //  Safety:
+        //  -   Each field is resolved to an array of `self.capacity` elements, and `index < self.length`.
+        //  -   The resolved pointers remain valid for as long as `self` is borrowed, as `S` is `StoreStable`.
+        //  -   The elements at `index` are initialized, as per type invariant.
+        //  -   `self` is borrowed immutably for the lifetime of the result.
+        Some(unsafe { T::get(|i| self.resolve_field(i), index) })
+    }
+
+    /// Returns mutable references to the fields of the element at `index`, if any.
+    pub fn get_mut(&mut self, index: usize) -> Option<T::Mut<'_>> {
+        if index >= self.length {
+            return None;
+        }
+
+        //  Safety:
+        //  -   Each field is resolved to an array of `self.capacity` elements, and `index < self.length`.
+        //  -   The resolved pointers remain valid for as long as `self` is borrowed, as `S` is `StoreStable`.
+        //  -   The elements at `index` are initialized, as per type invariant.
+        //  -   `self` is borrowed mutably for the lifetime of the result.
+        Some(unsafe { T::get_mut(|i| self.resolve_field(i), index) })
+    }
+
+    /// Returns the slices of each field, across all elements.
+    pub fn as_slices(&self) -> T::Slices<'_> {
+        //  Safety:
+        //  -   Each field is resolved to an array of `self.capacity` elements, and `self.length <= self.capacity`.
+        //  -   The resolved pointers remain valid for as long as `self` is borrowed, as `S` is `StoreStable`.
+        //  -   The elements in `0..self.length` are initialized, as per type invariant.
+        //  -   `self` is borrowed immutably for the lifetime of the result.
+        unsafe { T::slices(|i| self.resolve_field(i), self.length) }
+    }
+
+    /// Returns the mutable slices of each field, across all elements.
+    pub fn as_mut_slices(&mut self) -> T::SlicesMut<'_> {
+        //  Safety:
+        //  -   Each field is resolved to an array of `self.capacity` elements, and `self.length <= self.capacity`.
+        //  -   The resolved pointers remain valid for as long as `self` is borrowed, as `S` is `StoreStable`.
+        //  -   The elements in `0..self.length` are initialized, as per type invariant.
+        //  -   `self` is borrowed mutably for the lifetime of the result.
+        unsafe { T::slices_mut(|i| self.resolve_field(i), self.length) }
+    }
+
+    /// Returns an iterator over the references to the fields of each element.
+    pub fn iter(&self) -> Iter<'_, T, S> {
+        Iter {
+            front: 0,
+            back: self.length,
+            soa: self,
+        }
+    }
+}
+
+impl<T: SoA, S: Store + Default> Default for StoreSoA<T, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: SoA, S: Store> Drop for StoreSoA<T, S> {
+    fn drop(&mut self) {
+        self.clear();
+
+        if T::LAYOUTS.is_empty() || self.capacity == 0 {
+            return;
+        }
+
+        //  Safety:
+        //  -   `self.fields` is associated to a block of handles, one per field, as per type invariant.
+        //  -   `self.capacity` is the capacity of each field, as per type invariant.
+        unsafe { Self::release(self.fields, self.capacity, &self.store) };
+    }
+}
+
+impl<T: SoA, S: Store + StoreStable> Extend<T> for StoreSoA<T, S> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let iter = iter.into_iter();
+
+        self.reserve(iter.size_hint().0);
+
+        iter.for_each(|element| self.push(element));
+    }
+}
+
+impl<T: SoA, S: Store + StoreStable + Default> FromIterator<T> for StoreSoA<T, S> {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut this = Self::new();
+        this.extend(iter);
+        this
+    }
+}
+
+impl<'a, T: SoA, S: Store + StoreStable> IntoIterator for &'a StoreSoA<T, S> {
+    type Item = T::Ref<'a>;
+    type IntoIter = Iter<'a, T, S>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the references to the fields of each element of a `StoreSoA`.
+pub struct Iter<'a, T: SoA, S: Store> {
+    //  Invariant: `front <= back <= soa.len()`.
+    front: usize,
+    back: usize,
+    soa: &'a StoreSoA<T, S>,
+}
+
+impl<'a, T: SoA, S: Store + StoreStable> Iterator for Iter<'a, T, S> {
+    type Item = T::Ref<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+
+        let element = self.soa.get(self.front);
+
+        self.front += 1;
+
+        element
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let length = self.back - self.front;
+
+        (length, Some(length))
+    }
+}
+
+impl<'a, T: SoA, S: Store + StoreStable> DoubleEndedIterator for Iter<'a, T, S> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.back -= 1;
+
+        self.soa.get(self.back)
+    }
+}
+
+impl<'a, T: SoA, S: Store + StoreStable> ExactSizeIterator for Iter<'a, T, S> {}
+
+impl<'a, T: SoA, S: Store + StoreStable> FusedIterator for Iter<'a, T, S> {}
+
+//
+//  Implementation
+//
+
+impl<T: SoA, S: Store> StoreSoA<T, S> {
+    //  Resolves the array of the field at `index`.
+    //
+    //  #   Safety
+    //
+    //  -   `index` must be less than `T::LAYOUTS.len()`.
+    //  -   `self.capacity` must be greater than 0.
+    unsafe fn resolve_field(&self, index: usize) -> NonNull<u8> {
+        debug_assert!(index < T::LAYOUTS.len());
+        debug_assert!(self.capacity > 0);
+
+        //  Safety:
+        //  -   `self.fields` is associated to a block of handles, one per field, as `self.capacity > 0`.
+        let handles = unsafe { self.fields.resolve(&self.store) };
+
+        //  Safety:
+        //  -   The handle of the field was allocated by `self.store`, and is still valid, or is dangling.
+        unsafe { self.store.resolve(handles[index]) }
+    }
+
+    //  Appends an element at the back.
+    //
+    //  #   Safety
+    //
+    //  -   `self.length` must be less than `self.capacity`.
+    unsafe fn push_unchecked(&mut self, value: T) {
+        debug_assert!(self.length < self.capacity);
+
+        //  Safety:
+        //  -   Each field is resolved to an array of `self.capacity` elements, and `self.length < self.capacity`.
+        unsafe { T::write(value, |i| self.resolve_field(i), self.length) };
+
+        self.length += 1;
+    }
+
+    //  Deallocates the blocks of each field, and then the block of their handles.
+    //
+    //  #   Safety
+    //
+    //  -   `fields` must be associated to a block of handles, one per field.
+    //  -   `capacity` must be the capacity of each field.
+    unsafe fn release(fields: TypedHandle<[S::Handle], S::Handle>, capacity: usize, store: &S) {
+        //  Safety:
+        //  -   `fields` is associated to a block of handles, as per pre-conditions.
+        let handles = unsafe { fields.resolve(store) };
+
+        for (handle, layout) in handles.iter().zip(T::LAYOUTS) {
+            if layout.size() == 0 {
+                continue;
+            }
+
+            //  Safety:
+            //  -   The arrays of `capacity` elements of each field were allocated, as per pre-conditions.
+            let layout = unsafe { Self::allocated_layout(*layout, capacity) };
+
+            //  Safety:
+            //  -   `handle` was allocated by `store`, and is still valid, as the field is not zero-sized.
+            //  -   `layout` fits the block, as it was computed identically at allocation.
+            unsafe { store.deallocate(*handle, layout) };
+        }
+
+        //  Safety:
+        //  -   `fields` was allocated by `store`, and is still valid.
+        unsafe { fields.deallocate(store) };
+    }
+
+    //  Returns the layout of an array of `capacity` elements of `layout`, if representable.
+    fn layout(layout: Layout, capacity: usize) -> Option<Layout> {
+        let size = layout.size().checked_mul(capacity)?;
+
+        Layout::from_size_align(size, layout.align()).ok()
+    }
+
+    //  Returns the layout of an array of `capacity` elements of `layout`.
+    //
+    //  #   Safety
+    //
+    //  -   The layout must be representable, for example because it was computed successfully at allocation.
+    #[inline(always)]
+    unsafe fn allocated_layout(layout: Layout, capacity: usize) -> Layout {
+        let layout = Self::layout(layout, capacity);
+
+        debug_assert!(layout.is_some());
+
+        //  Safety:
+        //  -   The layout is representable, as per pre-conditions.
+        unsafe { layout.unwrap_unchecked() }
+    }
+
+    //  Returns whether the arrays of `capacity` elements of each field are representable.
+    fn layouts(capacity: usize) -> Option<()> {
+        T::LAYOUTS
+            .iter()
+            .try_for_each(|layout| Self::layout(*layout, capacity).map(|_| ()))
+    }
+
+    #[inline(never)]
+    fn capacity_exceeded() -> ! {
+        panic!("New capacity exceeds isize::MAX bytes")
+    }
+}
+
+impl<T: SoA, S: Store + StoreStable> StoreSoA<T, S> {
+    //  Grows the arrays of each field to `capacity`, moving the elements.
+    //
+    //  On failure, the collection is left untouched.
+    #[inline(never)]
+    fn grow_to(&mut self, capacity: usize) -> Result<(), AllocError> {
+        debug_assert!(capacity >= self.capacity);
+
+        Self::layouts(capacity).ok_or(AllocError)?;
+
+        let number_fields = T::LAYOUTS.len();
+
+        //  Without any field, the capacity is already maximal.
+        debug_assert!(number_fields > 0);
+
+        let fields = TypedHandle::<[S::Handle], _>::try_allocate_slice(number_fields, &self.store)?;
+
+        //  Safety:
+        //  -   `fields` was allocated by `self.store`, and is still valid.
+        let handles: NonNull<S::Handle> = unsafe { fields.resolve_raw(&self.store) }.cast();
+
+        for (index, layout) in T::LAYOUTS.iter().enumerate() {
+            let handle = if layout.size() == 0 {
+                let alignment = layout.alignment();
+
+                self.store.dangling(alignment)
+            } else {
+                //  Safety:
+                //  -   The arrays of `capacity` elements of each field are representable, as checked above.
+                let layout = unsafe { Self::allocated_layout(*layout, capacity) };
+
+                self.store.allocate(layout).map(|(handle, _)| handle)
+            };
+
+            let Ok(handle) = handle else {
+                //  Safety:
+                //  -   The handles in `0..index` were allocated, or are dangling if zero-sized.
+                unsafe { Self::abandon(fields, index, capacity, &self.store) };
+
+                return Err(AllocError);
+            };
+
+            //  Safety:
+            //  -   `index < number_fields`, and `fields` fits at least `number_fields` handles.
+            //  -   `handles` remains valid, as `S` is `StoreStable`.
+            unsafe { ptr::write(handles.as_ptr().add(index), handle) };
+        }
+
+        if self.capacity > 0 {
+            for (index, layout) in T::LAYOUTS.iter().enumerate() {
+                //  Safety:
+                //  -   `index < number_fields`, and `self.capacity > 0`.
+                let old = unsafe { self.resolve_field(index) };
+
+                //  Safety:
+                //  -   `index < number_fields`, and all handles in `fields` were initialized above.
+                let new = unsafe { self.store.resolve(*handles.as_ptr().add(index)) };
+
+                //  Safety:
+                //  -   `old` is valid for reads of the `self.length` initialized elements of the field.
+                //  -   `new` is valid for writes of `capacity >= self.length` elements of the field.
+                //  -   `old` and `new` are distinct blocks of memory, or both dangling for zero-sized fields.
+                unsafe { ptr::copy_nonoverlapping(old.as_ptr(), new.as_ptr(), layout.size() * self.length) };
+            }
+
+            //  Safety:
+            //  -   `self.fields` is associated to a block of handles, one per field, as `self.capacity > 0`.
+            //  -   `self.capacity` is the capacity of each field.
+            unsafe { Self::release(self.fields, self.capacity, &self.store) };
+        }
+
+        self.fields = fields;
+        self.capacity = capacity;
+
+        Ok(())
+    }
+
+    //  Deallocates the blocks of the `allocated` first fields, and then the block of their handles.
+    //
+    //  #   Safety
+    //
+    //  -   `fields` must be associated to a block of handles, whose `allocated` first are initialized.
+    //  -   `capacity` must be the capacity of each of the `allocated` first fields.
+    unsafe fn abandon(fields: TypedHandle<[S::Handle], S::Handle>, allocated: usize, capacity: usize, store: &S) {
+        //  Safety:
+        //  -   `fields` is associated to a block of handles, as per pre-conditions.
+        let handles: NonNull<S::Handle> = unsafe { fields.resolve_raw(store) }.cast();
+
+        for (index, layout) in T::LAYOUTS.iter().take(allocated).enumerate() {
+            if layout.size() == 0 {
+                continue;
+            }
+
+            //  Safety:
+            //  -   The arrays of `capacity` elements of each field were allocated, as per pre-conditions.
+            let layout = unsafe { Self::allocated_layout(*layout, capacity) };
+
+            //  Safety:
+            //  -   `index < allocated`, hence the handle is initialized, as per pre-conditions.
+            let handle = unsafe { *handles.as_ptr().add(index) };
+
+            //  Safety:
+            //  -   `handle` was allocated by `store`, and is still valid, as the field is not zero-sized.
+            //  -   `layout` fits the block, as it was computed identically at allocation.
+            unsafe { store.deallocate(handle, layout) };
+        }
+
+        //  Safety:
+        //  -   `fields` was allocated by `store`, and is still valid.
+        unsafe { fields.deallocate(store) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::slice;
+
+    use crate::{
+        collection::utils::Global,
+        store::{InlineBumpStore, RefStore, TrackingStore},
+    };
+
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Particle {
+        position: [f32; 2],
+        mass: u8,
+        name: String,
+    }
+
+    type Refs<'a> = (&'a [f32; 2], &'a u8, &'a String);
+    type Muts<'a> = (&'a mut [f32; 2], &'a mut u8, &'a mut String);
+
+    //  Safety:
+    //  -   `LAYOUTS` lists the fields in order.
+    //  -   Each field is accessed as an array of its type, through `field(i)` once per method.
+    unsafe impl SoA for Particle {
+        type Ref<'a> = Refs<'a>;
+        type Mut<'a> = Muts<'a>;
+        type Slices<'a> = (&'a [[f32; 2]], &'a [u8], &'a [String]);
+        type SlicesMut<'a> = (&'a mut [[f32; 2]], &'a mut [u8], &'a mut [String]);
+
+        const LAYOUTS: &'static [Layout] = &[Layout::new::<[f32; 2]>(), Layout::new::<u8>(), Layout::new::<String>()];
+
+        unsafe fn write<F>(self, mut field: F, index: usize)
+        where
+            F: FnMut(usize) -> NonNull<u8>,
+        {
+            unsafe {
+                field(0).cast::<[f32; 2]>().add(index).write(self.position);
+                field(1).cast::<u8>().add(index).write(self.mass);
+                field(2).cast::<String>().add(index).write(self.name);
+            }
+        }
+
+        unsafe fn read<F>(mut field: F, index: usize) -> Self
+        where
+            F: FnMut(usize) -> NonNull<u8>,
+        {
+            unsafe {
+                Self {
+                    position: field(0).cast::<[f32; 2]>().add(index).read(),
+                    mass: field(1).cast::<u8>().add(index).read(),
+                    name: field(2).cast::<String>().add(index).read(),
+                }
+            }
+        }
+
+        unsafe fn get<'a, F>(mut field: F, index: usize) -> Self::Ref<'a>
+        where
+            F: FnMut(usize) -> NonNull<u8>,
+        {
+            unsafe {
+                (
+                    field(0).cast::<[f32; 2]>().add(index).as_ref(),
+                    field(1).cast::<u8>().add(index).as_ref(),
+                    field(2).cast::<String>().add(index).as_ref(),
+                )
+            }
+        }
+
+        unsafe fn get_mut<'a, F>(mut field: F, index: usize) -> Self::Mut<'a>
+        where
+            F: FnMut(usize) -> NonNull<u8>,
+        {
+            unsafe {
+                (
+                    field(0).cast::<[f32; 2]>().add(index).as_mut(),
+                    field(1).cast::<u8>().add(index).as_mut(),
+                    field(2).cast::<String>().add(index).as_mut(),
+                )
+            }
+        }
+
+        unsafe fn slices<'a, F>(mut field: F, length: usize) -> Self::Slices<'a>
+        where
+            F: FnMut(usize) -> NonNull<u8>,
+        {
+            unsafe {
+                (
+                    slice::from_raw_parts(field(0).cast().as_ptr(), length),
+                    slice::from_raw_parts(field(1).cast().as_ptr(), length),
+                    slice::from_raw_parts(field(2).cast().as_ptr(), length),
+                )
+            }
+        }
+
+        unsafe fn slices_mut<'a, F>(mut field: F, length: usize) -> Self::SlicesMut<'a>
+        where
+            F: FnMut(usize) -> NonNull<u8>,
+        {
+            unsafe {
+                (
+                    slice::from_raw_parts_mut(field(0).cast().as_ptr(), length),
+                    slice::from_raw_parts_mut(field(1).cast().as_ptr(), length),
+                    slice::from_raw_parts_mut(field(2).cast().as_ptr(), length),
+                )
+            }
+        }
+    }
+
+    fn particle(n: u8) -> Particle {
+        Particle {
+            position: [n as f32, -(n as f32)],
+            mass: n,
+            name: n.to_string(),
+        }
+    }
+
+    #[test]
+    fn push_get_pop() {
+        let mut soa = StoreSoA::<Particle, Global>::new();
+
+        assert!(soa.is_empty());
+        assert!(soa.get(0).is_none());
+
+        (0..10).for_each(|n| soa.push(particle(n)));
+
+        assert_eq!(10, soa.len());
+        assert!(soa.capacity() >= 10);
+
+        let (position, mass, name) = soa.get(3).unwrap();
+
+        assert_eq!(([3.0, -3.0], 3, "3"), (*position, *mass, &name[..]));
+
+        *soa.get_mut(3).unwrap().2 = String::from("three");
+
+        assert_eq!(Some(particle(9)), soa.pop());
+        assert_eq!("three", soa.get(3).unwrap().2);
+        assert_eq!(9, soa.len());
+    }
+
+    #[test]
+    fn slices() {
+        let mut soa: StoreSoA<Particle, Global> = (0..4).map(particle).collect();
+
+        let (_, masses, names) = soa.as_slices();
+
+        assert_eq!([0, 1, 2, 3], masses);
+        assert_eq!(["0", "1", "2", "3"], names);
+
+        soa.as_mut_slices().1.iter_mut().for_each(|mass| *mass *= 2);
+
+        let masses: Vec<_> = soa.iter().map(|(_, mass, _)| *mass).collect();
+
+        assert_eq!(vec![0, 2, 4, 6], masses);
+
+        let names: Vec<_> = soa.iter().rev().map(|(_, _, name)| name.clone()).collect();
+
+        assert_eq!(vec!["3", "2", "1", "0"], names);
+    }
+
+    #[test]
+    fn separate_blocks() {
+        let store = TrackingStore::<Global, 8>::new(Global);
+
+        {
+            let mut soa = StoreSoA::<Particle, _>::with_capacity_in(4, RefStore::new(&store));
+
+            soa.push(particle(1));
+
+            //  One block of handles, and one block per field.
+            assert_eq!(4, store.len());
+
+            soa.extend((2..10).map(particle));
+
+            assert_eq!(4, store.len());
+        }
+
+        assert_eq!(0, store.len());
+    }
+
+    #[test]
+    fn fixed_capacity() {
+        let mut soa = StoreSoA::<Particle, InlineBumpStore<u16, [u64; 24]>>::new();
+
+        let mut pushed = 0;
+
+        while soa.try_push(particle(pushed)).is_ok() {
+            pushed += 1;
+        }
+
+        assert!(pushed > 0);
+        assert_eq!(pushed as usize, soa.len());
+        assert!(soa.iter().map(|(_, mass, _)| *mass).eq(0..pushed));
+
+        soa.clear();
+
+        assert!(soa.is_empty());
+    }
+
+    #[test]
+    fn no_fields() {
+        #[derive(Debug, PartialEq)]
+        struct Unit;
+
+        //  Safety:
+        //  -   There are no fields.
+        unsafe impl SoA for Unit {
+            type Ref<'a> = ();
+            type Mut<'a> = ();
+            type Slices<'a> = ();
+            type SlicesMut<'a> = ();
+
+            const LAYOUTS: &'static [Layout] = &[];
+
+            unsafe fn write<F: FnMut(usize) -> NonNull<u8>>(self, _field: F, _index: usize) {}
+
+            unsafe fn read<F: FnMut(usize) -> NonNull<u8>>(_field: F, _index: usize) -> Self {
+                Unit
+            }
+
+            unsafe fn get<'a, F: FnMut(usize) -> NonNull<u8>>(_field: F, _index: usize) -> Self::Ref<'a> {}
+
+            unsafe fn get_mut<'a, F: FnMut(usize) -> NonNull<u8>>(_field: F, _index: usize) -> Self::Mut<'a> {}
+
+            unsafe fn slices<'a, F: FnMut(usize) -> NonNull<u8>>(_field: F, _length: usize) -> Self::Slices<'a> {}
+
+            unsafe fn slices_mut<'a, F>(_field: F, _length: usize) -> Self::SlicesMut<'a>
+            where
+                F: FnMut(usize) -> NonNull<u8>,
+            {
+            }
+        }
+
+        let mut soa = StoreSoA::<Unit, InlineBumpStore<u8, [u8; 0]>>::new();
+
+        soa.push(Unit);
+        soa.push(Unit);
+
+        assert_eq!(2, soa.iter().count());
+        assert_eq!(Some(Unit), soa.pop());
+        assert_eq!(1, soa.len());
+    }
+} // mod tests
@@ -0,0 +1,641 @@
+//! Proof of concept append-only vector, whose elements never move.
+//!
+//! A `StoreStableVec<T, S, N>` stores its elements in fixed-size chunks of `N` elements, each allocated separately from
+//! the store, and records the handles of the chunks in a directory, itself allocated from the store. Only the directory
+//! is ever reallocated when growing, hence an element never moves once pushed.
+//!
+//! As a result, elements may be pushed through a shared reference, and the reference to the element returned by
+//! `push` remains valid for as long as the vector is borrowed, even as further elements are pushed. This is the
+//! stability offered by arenas such as `typed-arena` or `elsa`, and relies on `StoreStable` guaranteeing that resolving
+//! a handle never invalidates the pointers to other blocks: it allows building self-referential graphs incrementally.
+
+use core::{
+    alloc::{AllocError, Layout},
+    cell::Cell,
+    fmt,
+    marker::PhantomData,
+    mem, ops,
+    ptr::{self, Alignment, NonNull},
+};
+
+use crate::{
+    alloc,
+    collection::MemoryUsage,
+    interface::{Store, StoreMultiple, StoreStable},
+};
+
+/// An append-only vector, whose elements never move once pushed.
+///
+/// The elements are stored in chunks of `N` elements.
+pub struct StoreStableVec<T, S: StoreMultiple, const N: usize = 16> {
+    //  Type invariants:
+    //  -   `self.length <= self.chunks * N`.
+    //  -   `self.directory` is `None` if `self.directory_capacity` is 0, and is otherwise associated to a block of
+    //      `self.store` fitting an array of `self.directory_capacity` handles.
+    //  -   The first `self.chunks` handles of the directory are initialized, each associated to a block of `self.store`
+    //      fitting `Self::chunk_layout()`, or dangling if `T` is zero-sized.
+    //  -   The first `self.length` elements, spread across the chunks in order, are initialized.
+    length: Cell<usize>,
+    chunks: Cell<usize>,
+    directory: Cell<Option<S::Handle>>,
+    directory_capacity: Cell<usize>,
+    store: S,
+    _marker: PhantomData<T>,
+}
+
+impl<T, S: StoreMultiple + Default, const N: usize> StoreStableVec<T, S, N> {
+    /// Creates a new, empty, instance.
+    ///
+    /// #   Panics
+    ///
+    /// Panics if `N` is 0.
+    pub fn new() -> Self {
+        Self::new_in(S::default())
+    }
+}
+
+impl<T, S: StoreMultiple, const N: usize> StoreStableVec<T, S, N> {
+    /// Creates a new, empty, instance with the specified `store`.
+    ///
+    /// #   Panics
+    ///
+    /// Panics if `N` is 0.
+    pub const fn new_in(store: S) -> Self {
+        assert!(N > 0, "chunks should hold at least 1 element");
+
+        Self {
+            length: Cell::new(0),
+            chunks: Cell::new(0),
+            directory: Cell::new(None),
+            directory_capacity: Cell::new(0),
+            store,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns whether the vector is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of elements in the vector.
+    pub fn len(&self) -> usize {
+        self.length.get()
+    }
+
+    /// Returns the capacity of the vector, across all its chunks.
+    pub fn capacity(&self) -> usize {
+        self.chunks.get() * N
+    }
+
+    /// Returns the memory footprint of the vector, as per the layouts of its chunks and directory.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        let directory = self.directory_capacity.get() * mem::size_of::<S::Handle>();
+
+        let bytes_allocated = self.capacity() * mem::size_of::<T>() + directory;
+        let bytes_used = self.len() * mem::size_of::<T>();
+        let allocations = if mem::size_of::<T>() > 0 { self.chunks.get() } else { 0 } + usize::from(directory > 0);
+
+        MemoryUsage {
+            bytes_allocated,
+            bytes_used,
+            allocations,
+        }
+    }
+
+    /// Clears the vector, dropping all its elements, chunk by chunk.
+    ///
+    /// The chunks are kept, and reused by further pushes.
+    pub fn clear(&mut self) {
+        let length = self.length.replace(0);
+
+        //  The elements are considered dropped even if dropping one of them panics, leaking the others.
+        for chunk in 0..length.div_ceil(N) {
+            let count = (length - chunk * N).min(N);
+
+            //  Safety:
+            //  -   `chunk < self.chunks`, as `length <= self.chunks * N`.
+            let elements = unsafe { self.chunk(chunk) };
+
+            let elements = ptr::slice_from_raw_parts_mut(elements.as_ptr(), count);
+
+            //  Safety:
+            //  -   The `count` first elements of `chunk` are initialized, and will not be accessed again.
+            unsafe { ptr::drop_in_place(elements) };
+        }
+    }
+}
+
+impl<T, S: StoreMultiple + StoreStable, const N: usize> StoreStableVec<T, S, N> {
+    /// Appends an element at the back of the vector, and returns a reference to it.
+    ///
+    /// No element is moved, hence references to previously pushed elements remain valid.
+    ///
+    /// #   Panics
+    ///
+    /// Panics if a chunk, or the directory, would exceed `isize::MAX` bytes.
+    ///
+    /// Calls `handle_alloc_error` if the store fails to provide a new chunk, or to grow the directory.
+    pub fn push(&self, value: T) -> &T {
+        if self.len() == self.capacity() {
+            let Some(layout) = Self::chunk_layout() else {
+                Self::capacity_exceeded()
+            };
+
+            if self.try_chain_chunk().is_err() {
+                alloc::handle_alloc_error(layout)
+            }
+        }
+
+        //  Safety:
+        //  -   `self.len() < self.capacity()`, after chaining a new chunk.
+        unsafe { self.push_unchecked(value) }
+    }
+
+    /// Attempts to append an element at the back of the vector, and returns a reference to it.
+    ///
+    /// Returns the element if the store fails to provide a new chunk, or to grow the directory.
+    pub fn try_push(&self, value: T) -> Result<&T, T> {
+        if self.len() == self.capacity() && self.try_chain_chunk().is_err() {
+            return Err(value);
+        }
+
+        //  Safety:
+        //  -   `self.len() < self.capacity()`, after chaining a new chunk.
+        Ok(unsafe { self.push_unchecked(value) })
+    }
+
+    /// Returns a reference to the element at `index`, if any.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len() {
+            return None;
+        }
+
+        //  Safety:
+        //  -   `index < self.len() <= self.capacity()`.
+        let slot = unsafe { self.slot(index) };
+
+        //  Safety:
+        //  -   The element at `index` is initialized, as `index < self.len()`, and never moved nor dropped until the
+        //      vector is borrowed mutably.
+        //  -   The reference extends the borrow of `self`, guaranteeing that `self.store` will not be moved, whilst
+        //      `StoreStable` guarantees that resolving, or allocating, other blocks will not invalidate it.
+        Some(unsafe { slot.as_ref() })
+    }
+
+    /// Returns a mutable reference to the element at `index`, if any.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len() {
+            return None;
+        }
+
+        //  Safety:
+        //  -   `index < self.len() <= self.capacity()`.
+        let mut slot = unsafe { self.slot(index) };
+
+        //  Safety:
+        //  -   Same as `get`, with exclusive access guaranteed by `self` being borrowed mutably.
+        Some(unsafe { slot.as_mut() })
+    }
+
+    /// Returns an iterator of references to the elements.
+    ///
+    /// The iterator observes the elements pushed whilst iterating.
+    pub fn iter(&self) -> StableVecIter<'_, T, S, N> {
+        StableVecIter { vec: self, index: 0 }
+    }
+}
+
+impl<T, S: StoreMultiple + Default, const N: usize> Default for StoreStableVec<T, S, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+//  Safety:
+//  -   The elements are only dropped, and never otherwise accessed, hence they may dangle.
+unsafe impl<#[may_dangle] T, S: StoreMultiple, const N: usize> Drop for StoreStableVec<T, S, N> {
+    fn drop(&mut self) {
+        self.clear();
+
+        let Some(directory) = self.directory.get() else {
+            return;
+        };
+
+        if let Some(layout) = Self::chunk_layout().filter(|layout| layout.size() > 0) {
+            for chunk in 0..self.chunks.get() {
+                //  Safety:
+                //  -   `chunk < self.chunks`, hence its handle is initialized.
+                let handle = unsafe { self.directory().add(chunk).read() };
+
+                //  Safety:
+                //  -   `handle` was allocated by `self.store`, with `layout`, and is still valid.
+                unsafe { self.store.deallocate(handle, layout) };
+            }
+        }
+
+        let layout = self.allocated_directory_layout();
+
+        //  Safety:
+        //  -   `directory` was allocated by `self.store`, with `layout`, and is still valid.
+        unsafe { self.store.deallocate(directory, layout) };
+    }
+}
+
+impl<T: fmt::Debug, S: StoreMultiple + StoreStable, const N: usize> fmt::Debug for StoreStableVec<T, S, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T, S: StoreMultiple + StoreStable, const N: usize> ops::Index<usize> for StoreStableVec<T, S, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        self.get(index)
+            .unwrap_or_else(|| panic!("index {index} should be < len {}", self.len()))
+    }
+}
+
+impl<T, S: StoreMultiple + StoreStable, const N: usize> ops::IndexMut<usize> for StoreStableVec<T, S, N> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        let length = self.len();
+
+        self.get_mut(index)
+            .unwrap_or_else(|| panic!("index {index} should be < len {length}"))
+    }
+}
+
+impl<T, S: StoreMultiple + StoreStable, const N: usize> Extend<T> for StoreStableVec<T, S, N> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for element in iter {
+            self.push(element);
+        }
+    }
+}
+
+impl<T, S: StoreMultiple + StoreStable + Default, const N: usize> FromIterator<T> for StoreStableVec<T, S, N> {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut this = Self::new();
+
+        this.extend(iter);
+
+        this
+    }
+}
+
+impl<'a, T, S: StoreMultiple + StoreStable, const N: usize> IntoIterator for &'a StoreStableVec<T, S, N> {
+    type Item = &'a T;
+    type IntoIter = StableVecIter<'a, T, S, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator of references to the elements of a `StoreStableVec`.
+pub struct StableVecIter<'a, T, S: StoreMultiple, const N: usize> {
+    vec: &'a StoreStableVec<T, S, N>,
+    index: usize,
+}
+
+impl<'a, T, S: StoreMultiple + StoreStable, const N: usize> Iterator for StableVecIter<'a, T, S, N> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let element = self.vec.get(self.index)?;
+
+        self.index += 1;
+
+        Some(element)
+    }
+}
+
+//
+//  Implementation
+//
+
+impl<T, S: StoreMultiple, const N: usize> StoreStableVec<T, S, N> {
+    fn chunk_layout() -> Option<Layout> {
+        Layout::array::<T>(N).ok()
+    }
+
+    fn directory_layout(capacity: usize) -> Option<Layout> {
+        Layout::array::<S::Handle>(capacity).ok()
+    }
+
+    //  Returns the layout of the directory, as computed when it was allocated, with the current capacity.
+    fn allocated_directory_layout(&self) -> Layout {
+        let layout = Self::directory_layout(self.directory_capacity.get());
+
+        debug_assert!(layout.is_some());
+
+        //  Safety:
+        //  -   The layout was computed successfully when the directory was allocated with the current capacity.
+        unsafe { layout.unwrap_unchecked() }
+    }
+
+    //  Resolves the directory.
+    //
+    //  The pointer is only valid until the next call to the store.
+    fn directory(&self) -> NonNull<S::Handle> {
+        let directory = self.directory.get();
+
+        debug_assert!(directory.is_some());
+
+        //  Safety:
+        //  -   The directory is allocated as soon as a chunk is, and chunks are only resolved once they are.
+        let directory = unsafe { directory.unwrap_unchecked() };
+
+        //  Safety:
+        //  -   `directory` was allocated by `self.store`, and is still valid.
+        unsafe { self.store.resolve(directory) }.cast()
+    }
+
+    //  Resolves the first slot of `chunk`.
+    //
+    //  #   Safety
+    //
+    //  -   `chunk` must be less than `self.chunks`.
+    unsafe fn chunk(&self, chunk: usize) -> NonNull<T> {
+        debug_assert!(chunk < self.chunks.get());
+
+        //  Safety:
+        //  -   `chunk < self.chunks`, hence its handle is initialized.
+        let handle = unsafe { self.directory().add(chunk).read() };
+
+        //  Safety:
+        //  -   `handle` was allocated by `self.store`, or is dangling, and is still valid.
+        unsafe { self.store.resolve(handle) }.cast()
+    }
+
+    //  Resolves the slot at `index`.
+    //
+    //  #   Safety
+    //
+    //  -   `index` must be less than `self.capacity()`.
+    unsafe fn slot(&self, index: usize) -> NonNull<T> {
+        debug_assert!(index < self.capacity());
+
+        //  Safety:
+        //  -   `index / N < self.chunks`, as `index < self.capacity()`.
+        let chunk = unsafe { self.chunk(index / N) };
+
+        //  Safety:
+        //  -   `index % N < N`, hence within the chunk.
+        unsafe { chunk.add(index % N) }
+    }
+
+    //  Appends an element, and returns a reference to it.
+    //
+    //  #   Safety
+    //
+    //  -   `self.len()` must be less than `self.capacity()`.
+    //  -   `S` must implement `StoreStable`, for the reference to remain valid.
+    unsafe fn push_unchecked(&self, value: T) -> &T {
+        let index = self.len();
+
+        //  Safety:
+        //  -   `index < self.capacity()`, as per pre-conditions.
+        let slot = unsafe { self.slot(index) };
+
+        //  Safety:
+        //  -   The slot at `index` is not initialized, nor referenced.
+        unsafe { ptr::write(slot.as_ptr(), value) };
+
+        self.length.set(index + 1);
+
+        //  Safety:
+        //  -   The element at `index` is initialized, see `get` for the lifetime of the reference.
+        unsafe { slot.as_ref() }
+    }
+
+    //  Chains a new chunk, growing the directory if necessary.
+    //
+    //  On failure, the vector is left untouched, though the directory may have grown.
+    fn try_chain_chunk(&self) -> Result<(), AllocError> {
+        let chunks = self.chunks.get();
+
+        let layout = Self::chunk_layout().ok_or(AllocError)?;
+
+        //  The capacity, in number of elements, must remain representable, notably for zero-sized `T`.
+        if (chunks + 1).checked_mul(N).is_none() {
+            return Err(AllocError);
+        }
+
+        if chunks == self.directory_capacity.get() {
+            self.grow_directory()?;
+        }
+
+        let handle = if layout.size() == 0 {
+            self.store.dangling(Alignment::of::<T>())?
+        } else {
+            self.store.allocate(layout)?.0
+        };
+
+        //  Safety:
+        //  -   `chunks < self.directory_capacity`, hence within the directory.
+        unsafe { self.directory().add(chunks).write(handle) };
+
+        self.chunks.set(chunks + 1);
+
+        Ok(())
+    }
+
+    fn grow_directory(&self) -> Result<(), AllocError> {
+        let capacity = self.directory_capacity.get();
+
+        let new_capacity = <S as Store>::GROWTH
+            .capacity(capacity, capacity + 1)
+            .ok_or(AllocError)?;
+        let new_layout = Self::directory_layout(new_capacity).ok_or(AllocError)?;
+
+        let (directory, _) = match self.directory.get() {
+            None => self.store.allocate(new_layout)?,
+            Some(directory) => {
+                let old_layout = self.allocated_directory_layout();
+
+                //  Safety:
+                //  -   `directory` was allocated by `self.store`, with `old_layout`, and is still valid.
+                //  -   `new_layout` is larger than `old_layout`.
+                unsafe { self.store.grow(directory, old_layout, new_layout)? }
+            }
+        };
+
+        self.directory.set(Some(directory));
+        self.directory_capacity.set(new_capacity);
+
+        Ok(())
+    }
+
+    #[inline(never)]
+    fn capacity_exceeded() -> ! {
+        panic!("New chunk exceeds isize::MAX bytes")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{cell::RefCell, iter};
+
+    use crate::{
+        collection::utils::Global,
+        store::{InlineBumpStore, RefStore, TrackingStore},
+    };
+
+    use super::*;
+
+    type Bump = InlineBumpStore<u16, [u32; 64]>;
+
+    #[test]
+    fn push_get() {
+        let vec = StoreStableVec::<u32, Global, 4>::new();
+
+        assert!(vec.is_empty());
+        assert_eq!(None, vec.get(0));
+
+        let first = vec.push(1);
+
+        let later: Vec<_> = (2..=10).map(|n| vec.push(n)).collect();
+
+        //  References remain valid across chunks.
+        assert_eq!(1, *first);
+        assert!(later.iter().copied().eq(vec.iter().skip(1)));
+
+        assert_eq!(10, vec.len());
+        assert_eq!(12, vec.capacity());
+        assert_eq!(Some(&5), vec.get(4));
+        assert_eq!(10, vec[9]);
+        assert_eq!("[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]", format!("{vec:?}"));
+    }
+
+    #[test]
+    fn graph() {
+        struct Node<'a> {
+            id: u32,
+            edges: RefCell<Vec<&'a Node<'a>>>,
+        }
+
+        let nodes = StoreStableVec::<Node<'_>, Global, 2>::new();
+
+        let node = |id| {
+            nodes.push(Node {
+                id,
+                edges: RefCell::new(Vec::new()),
+            })
+        };
+
+        let root = node(0);
+
+        //  Each node links to its parent, and its parent to it, cycles included.
+        for id in 1..10 {
+            let parent = nodes.get((id as usize - 1) / 2).unwrap();
+            let child = node(id);
+
+            parent.edges.borrow_mut().push(child);
+            child.edges.borrow_mut().push(parent);
+        }
+
+        let ids = |node: &Node<'_>| node.edges.borrow().iter().map(|n| n.id).collect::<Vec<_>>();
+
+        assert_eq!([1, 2], *ids(root));
+        assert_eq!([0, 3, 4], *ids(&nodes[1]));
+        assert_eq!([4], *ids(&nodes[9]));
+        assert!(ptr::eq(root, root.edges.borrow()[0].edges.borrow()[0]));
+    }
+
+    #[test]
+    fn fixed_capacity() {
+        let tracking = TrackingStore::<Bump, 8>::new(Bump::default());
+
+        {
+            let vec = StoreStableVec::<u32, _, 8>::new_in(RefStore::new(&tracking));
+
+            let mut pushed = 0;
+
+            while vec.try_push(pushed).is_ok() {
+                pushed += 1;
+            }
+
+            assert_eq!(pushed as usize, vec.len());
+            assert!(vec.iter().copied().eq(0..pushed));
+
+            let usage = vec.memory_usage();
+
+            assert_eq!(vec.len() / 8 + 1, usage.allocations);
+            assert_eq!(tracking.len(), usage.allocations);
+        }
+
+        assert_eq!(0, tracking.len());
+    }
+
+    #[test]
+    fn zero_sized() {
+        let mut vec = StoreStableVec::<(), Bump, 4>::new();
+
+        vec.extend(iter::repeat(()).take(10));
+
+        assert_eq!(10, vec.len());
+        assert_eq!(Some(&()), vec.get(9));
+        assert_eq!(1, vec.memory_usage().allocations);
+    }
+
+    #[test]
+    fn drops() {
+        struct Counted<'a>(&'a Cell<usize>);
+
+        impl Drop for Counted<'_> {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        let dropped = Cell::new(0);
+
+        let mut vec = StoreStableVec::<_, Global, 4>::new();
+
+        vec.extend((0..10).map(|_| Counted(&dropped)));
+        vec.clear();
+
+        assert_eq!(10, dropped.get());
+        assert_eq!(12, vec.capacity());
+
+        vec.push(Counted(&dropped));
+
+        drop(vec);
+
+        assert_eq!(11, dropped.get());
+    }
+} // mod tests
+
+//  Exercises the raw access paths of the vector: references handed out by `push` are kept alive whilst later pushes
+//  allocate new chunks, and write into the chunk they point into.
+//
+//  Run with `RUSTFLAGS="--cfg storage_miri_strict" MIRIFLAGS="-Zmiri-tree-borrows" cargo miri test tests_miri`, and
+//  without `-Zmiri-tree-borrows` for Stacked Borrows.
+#[cfg(all(test, storage_miri_strict))]
+mod tests_miri {
+    use crate::collection::utils::Global;
+
+    use super::*;
+
+    #[test]
+    fn push_across_chunks() {
+        let vec = StoreStableVec::<String, Global, 2>::new();
+
+        let first = vec.push(String::from("0"));
+        let pushed: Vec<&String> = (1..9).map(|i| vec.push(i.to_string())).collect();
+
+        let iterated: Vec<&String> = vec.iter().collect();
+
+        assert_eq!("0", first);
+        assert!(pushed.iter().eq(&iterated[1..]));
+        assert_eq!(Some(first), vec.get(0));
+    }
+} // mod tests_miri
@@ -0,0 +1,504 @@
+//! A UTF-8 String.
+//!
+//! A thin layer over `StoreVec<u8, S>`, guaranteeing that its bytes are valid UTF-8. With an `InlineSingleStore`, as
+//! in `InlineString`, the string is of fixed capacity, and may be built in const contexts, and thus in statics.
+
+use core::{
+    alloc::{AllocError, Layout},
+    cmp, fmt, hash,
+    ops::{Deref, DerefMut},
+    ptr, str,
+};
+
+use crate::{
+    alloc,
+    collection::{PersistedSlice, StoreVec},
+    interface::{StoreDangling, StoreRegion, StoreSingle},
+    store::InlineSingleStore,
+};
+
+/// A UTF-8 string.
+pub struct StoreString<S: StoreSingle> {
+    //  Type invariant:
+    //  -   `self.vec` is valid UTF-8.
+    vec: StoreVec<u8, S>,
+}
+
+/// A string of fixed capacity, of up to `N` bytes, stored inline.
+///
+/// Usable in const contexts, and thus in statics:
+///
+/// ```
+/// #![feature(const_trait_impl)]
+///
+/// use storage::collection::InlineString;
+///
+/// static GREETING: InlineString<16> = {
+///     let mut greeting = InlineString::new();
+///
+///     greeting.push_str("Hello, ");
+///     greeting.push_str("World!");
+///
+///     greeting
+/// };
+///
+/// assert_eq!("Hello, World!", &*GREETING);
+/// ```
+pub type InlineString<const N: usize> = StoreString<InlineSingleStore<[u8; N]>>;
+
+impl<const N: usize> InlineString<N> {
+    /// Creates a new, empty, instance.
+    pub const fn new() -> Self {
+        Self::new_in(InlineSingleStore::new())
+    }
+
+    /// Attempts to create a new instance, holding a copy of `s`.
+    ///
+    /// Returns an error if `s` is longer than `N` bytes.
+    pub const fn try_from_str(s: &str) -> Result<Self, AllocError> {
+        if s.len() > N {
+            return Err(AllocError);
+        }
+
+        let mut this = Self::new();
+
+        this.push_str(s);
+
+        Ok(this)
+    }
+}
+
+impl<S: StoreSingle> StoreString<S> {
+    /// Creates a new, empty, instance.
+    pub const fn new_in(store: S) -> Self
+    where
+        S: ~const StoreDangling,
+    {
+        let vec = StoreVec::new_in(store);
+
+        Self { vec }
+    }
+
+    /// Creates a new, empty, instance with at least the specified capacity, in bytes.
+    pub const fn with_capacity_in(capacity: usize, store: S) -> Self
+    where
+        S: ~const StoreSingle + ~const StoreDangling,
+    {
+        let vec = StoreVec::with_capacity_in(capacity, store);
+
+        Self { vec }
+    }
+
+    /// Returns whether the string is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.vec.is_empty()
+    }
+
+    /// Returns the length of the string, in bytes.
+    pub const fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    /// Returns the capacity of the string, in bytes.
+    pub const fn capacity(&self) -> usize {
+        self.vec.capacity()
+    }
+
+    /// Returns the string, as a string slice.
+    pub const fn as_str(&self) -> &str
+    where
+        S: ~const StoreSingle,
+    {
+        //  Safety:
+        //  -   `self.vec` is valid UTF-8, as per type invariant.
+        unsafe { str::from_utf8_unchecked(self.vec.as_slice()) }
+    }
+
+    /// Returns the string, as a mutable string slice.
+    pub const fn as_mut_str(&mut self) -> &mut str
+    where
+        S: ~const StoreSingle,
+    {
+        //  Safety:
+        //  -   `self.vec` is valid UTF-8, as per type invariant.
+        unsafe { str::from_utf8_unchecked_mut(self.vec.as_mut_slice()) }
+    }
+
+    /// Returns the bytes of the string.
+    pub const fn as_bytes(&self) -> &[u8]
+    where
+        S: ~const StoreSingle,
+    {
+        self.vec.as_slice()
+    }
+
+    /// Returns the underlying vector of bytes.
+    pub fn into_bytes(self) -> StoreVec<u8, S> {
+        self.vec
+    }
+}
+
+impl<S: StoreSingle> StoreString<S> {
+    /// Appends `s` at the back of the string.
+    ///
+    /// #   Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    ///
+    /// Calls `handle_alloc_error` if the store fails to provide the new capacity.
+    pub const fn push_str(&mut self, s: &str)
+    where
+        S: ~const StoreSingle + ~const StoreDangling,
+    {
+        let Ok(()) = self.try_push_str(s) else {
+            let Ok(layout) = Layout::array::<u8>(self.len().saturating_add(s.len())) else {
+                panic!("New capacity exceeds isize::MAX bytes")
+            };
+
+            alloc::handle_alloc_error(layout)
+        };
+    }
+
+    /// Attempts to append `s` at the back of the string.
+    ///
+    /// Returns an error if the new capacity exceeds `isize::MAX` bytes, or if the store fails to provide it, in which
+    /// case the string is left untouched.
+    pub const fn try_push_str(&mut self, s: &str) -> Result<(), AllocError>
+    where
+        S: ~const StoreSingle + ~const StoreDangling,
+    {
+        let Ok(()) = self.vec.try_reserve(s.len()) else {
+            return Err(AllocError);
+        };
+
+        let length = self.vec.len();
+        let spare = self.vec.spare_capacity_mut();
+
+        //  Safety:
+        //  -   `s` is valid for reads of `s.len()` bytes.
+        //  -   `spare` is valid for writes of `s.len()` bytes, as capacity was reserved.
+        //  -   `s` and `spare` do not overlap, as `self` is borrowed mutably.
+        unsafe { ptr::copy_nonoverlapping(s.as_ptr(), spare.as_mut_ptr() as *mut u8, s.len()) };
+
+        //  Safety:
+        //  -   `length + s.len() <= self.capacity()`, as capacity was reserved.
+        //  -   The bytes in `length..length + s.len()` were just initialized, and are valid UTF-8, as is `self`.
+        unsafe { self.vec.set_len(length + s.len()) };
+
+        Ok(())
+    }
+
+    /// Appends `c` at the back of the string.
+    ///
+    /// #   Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    ///
+    /// Calls `handle_alloc_error` if the store fails to provide the new capacity.
+    pub const fn push(&mut self, c: char)
+    where
+        S: ~const StoreSingle + ~const StoreDangling,
+    {
+        let mut buffer = [0; 4];
+
+        self.push_str(c.encode_utf8(&mut buffer));
+    }
+
+    /// Attempts to append `c` at the back of the string.
+    ///
+    /// Returns an error if the new capacity exceeds `isize::MAX` bytes, or if the store fails to provide it, in which
+    /// case the string is left untouched.
+    pub const fn try_push(&mut self, c: char) -> Result<(), AllocError>
+    where
+        S: ~const StoreSingle + ~const StoreDangling,
+    {
+        let mut buffer = [0; 4];
+
+        self.try_push_str(c.encode_utf8(&mut buffer))
+    }
+
+    /// Attempts to append the formatted `args` at the back of the string.
+    ///
+    /// Unlike `write!`, which reports the failure to grow the string as an opaque `fmt::Error`, this reports it as an
+    /// `AllocError`. On failure, the string is left untouched.
+    ///
+    /// #   Panics
+    ///
+    /// If a formatting trait implementation returns an error.
+    pub fn try_write_fmt(&mut self, args: fmt::Arguments<'_>) -> Result<(), AllocError> {
+        //  Only whole `str` are appended, and any partial write is rolled back, hence the bytes remain valid UTF-8.
+        self.vec.try_write_fmt(args)
+    }
+
+    /// Removes the last character from the string and returns it, if any.
+    pub fn pop(&mut self) -> Option<char> {
+        let c = self.as_str().chars().next_back()?;
+
+        //  Safety:
+        //  -   The new length is less than the current length, itself less than or equal to the capacity.
+        //  -   The remaining bytes are valid UTF-8, as `c` was the last character.
+        unsafe { self.vec.set_len(self.len() - c.len_utf8()) };
+
+        Some(c)
+    }
+
+    /// Shortens the string to `new_len` bytes, if longer.
+    ///
+    /// #   Panics
+    ///
+    /// If `new_len` does not lie on a character boundary.
+    pub fn truncate(&mut self, new_len: usize) {
+        if new_len >= self.len() {
+            return;
+        }
+
+        assert!(
+            self.as_str().is_char_boundary(new_len),
+            "new_len must lie on a character boundary"
+        );
+
+        //  Safety:
+        //  -   `new_len` is less than the current length, itself less than or equal to the capacity.
+        //  -   The remaining bytes are valid UTF-8, as `new_len` lies on a character boundary.
+        unsafe { self.vec.set_len(new_len) };
+    }
+
+    /// Clears the string, keeping its capacity.
+    pub fn clear(&mut self) {
+        self.vec.clear();
+    }
+}
+
+impl<S: StoreSingle + StoreRegion> StoreString<S> {
+    /// Returns the location of the bytes within the region of the store, so that they may be viewed in place, without
+    /// copying, once the region is persisted and loaded again, see `PersistedSlice::view_str`.
+    ///
+    /// The location is only valid until the string is next modified.
+    pub fn persisted(&self) -> PersistedSlice {
+        self.vec.persisted()
+    }
+}
+
+impl<S: StoreSingle> AsRef<str> for StoreString<S> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<S: StoreSingle> AsRef<[u8]> for StoreString<S> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<S: StoreSingle> Deref for StoreString<S> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<S: StoreSingle> DerefMut for StoreString<S> {
+    fn deref_mut(&mut self) -> &mut str {
+        self.as_mut_str()
+    }
+}
+
+impl<S: StoreSingle + Default> Default for StoreString<S> {
+    fn default() -> Self {
+        Self::new_in(S::default())
+    }
+}
+
+impl<S: StoreSingle> fmt::Debug for StoreString<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<S: StoreSingle> fmt::Display for StoreString<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+/// Writes append to the string, growing it as necessary.
+///
+/// Each string is either appended in full, or not at all, in which case `fmt::Error` is returned.
+impl<S: StoreSingle> fmt::Write for StoreString<S> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.try_push_str(s).map_err(|_| fmt::Error)
+    }
+}
+
+impl<S, OS> cmp::PartialEq<StoreString<OS>> for StoreString<S>
+where
+    S: StoreSingle,
+    OS: StoreSingle,
+{
+    fn eq(&self, other: &StoreString<OS>) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl<S: StoreSingle> cmp::PartialEq<str> for StoreString<S> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<S: StoreSingle> cmp::PartialEq<&str> for StoreString<S> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<S: StoreSingle> cmp::PartialEq<StoreString<S>> for str {
+    fn eq(&self, other: &StoreString<S>) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl<S: StoreSingle> cmp::PartialEq<StoreString<S>> for &str {
+    fn eq(&self, other: &StoreString<S>) -> bool {
+        *self == other.as_str()
+    }
+}
+
+impl<S: StoreSingle> cmp::Eq for StoreString<S> {}
+
+impl<S, OS> cmp::PartialOrd<StoreString<OS>> for StoreString<S>
+where
+    S: StoreSingle,
+    OS: StoreSingle,
+{
+    fn partial_cmp(&self, other: &StoreString<OS>) -> Option<cmp::Ordering> {
+        self.as_str().partial_cmp(other.as_str())
+    }
+}
+
+impl<S: StoreSingle> cmp::Ord for StoreString<S> {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl<S: StoreSingle> hash::Hash for StoreString<S> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::fmt::Write;
+
+    use crate::store::{StackBumpBlock, StackBumpStore};
+
+    use super::*;
+
+    #[test]
+    fn const_inline_string() {
+        const fn greeting() -> InlineString<16> {
+            let mut greeting = InlineString::new();
+
+            greeting.push_str("Hello");
+            greeting.push(',');
+            greeting.push(' ');
+
+            let Ok(()) = greeting.try_push_str("World!") else {
+                panic!("Capacity to suffice");
+            };
+
+            greeting
+        }
+
+        static GREETING: InlineString<16> = greeting();
+        static SHORT: Result<InlineString<2>, AllocError> = InlineString::try_from_str("Hi");
+
+        assert_eq!("Hello, World!", GREETING.as_str());
+        assert_eq!("Hi", SHORT.as_ref().unwrap());
+    }
+
+    #[test]
+    fn overflow() {
+        let mut s = InlineString::<4>::new();
+
+        s.push('é');
+
+        assert_eq!(Err(AllocError), s.try_push_str("abc"));
+        assert_eq!(Err(AllocError), s.try_push('€'));
+        assert_eq!(Ok(()), s.try_push_str("ab"));
+        assert_eq!(Err(AllocError), InlineString::<4>::try_from_str("hello").map(|_| ()));
+
+        assert_eq!("éab", s);
+        assert_eq!(4, s.len());
+    }
+
+    #[test]
+    fn pop_truncate() {
+        let mut s = InlineString::<16>::try_from_str("añb€").unwrap();
+
+        assert_eq!(Some('€'), s.pop());
+        assert_eq!("añb", s);
+
+        s.truncate(3);
+        assert_eq!("añ", s);
+
+        s.truncate(8);
+        assert_eq!("añ", s);
+
+        s.clear();
+        assert_eq!(None, s.pop());
+        assert!(s.is_empty());
+    }
+
+    #[test]
+    fn fmt() {
+        let mut s = InlineString::<8>::new();
+
+        write!(s, "{}-{}", 1, 2).unwrap();
+
+        assert_eq!("1-2", format!("{s}"));
+        assert_eq!("\"1-2\"", format!("{s:?}"));
+
+        assert!(write!(s, "{}", 123_456).is_err());
+        assert_eq!("1-2", s);
+
+        //  The first argument fits, yet is rolled back as the second does not.
+        assert_eq!(Err(AllocError), s.try_write_fmt(format_args!("{}{}", 34, 5678)));
+        assert_eq!("1-2", s);
+
+        assert_eq!(Ok(()), s.try_write_fmt(format_args!("{}{}", 34, 5)));
+        assert_eq!("1-2345", s);
+    }
+
+    #[test]
+    fn growing() {
+        let block = StackBumpBlock::<[u8; 64]>::new();
+
+        let mut s = StoreString::new_in(block.create_store::<u8>());
+
+        for word in ["a", "bc", "def"] {
+            s.push_str(word);
+        }
+
+        let bytes: StoreVec<u8, StackBumpStore<'_, u8>> = s.into_bytes();
+
+        assert_eq!(b"abcdef", bytes.as_slice());
+    }
+
+    #[test]
+    fn comparison() {
+        let (a, b) = (
+            InlineString::<4>::try_from_str("ab").unwrap(),
+            InlineString::<8>::try_from_str("b").unwrap(),
+        );
+
+        assert!(a < b);
+        assert!(a != b);
+        assert!(a == "ab");
+        assert!("b" == b);
+    }
+} // mod tests
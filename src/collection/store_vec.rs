@@ -1,19 +1,55 @@
 //! A Dynamic Array.
 //!
 //! This implementation is solely meant to demonstrate the use of `StoreSharing`, it is incomplete, and may be buggy.
+//!
+//! Growing the vector may relocate its elements, leaving the old block behind in stores which cannot reuse it, such as
+//! bump stores; see `SegmentedVec` for a vector which chains new segments instead.
 
 use core::{
-    mem::{self, MaybeUninit},
-    ops::Range,
+    alloc::{AllocError, Layout},
+    cmp, fmt, hash,
+    iter::FusedIterator,
+    mem::{self, ManuallyDrop, MaybeUninit},
+    ops::{Deref, DerefMut, Range},
     ptr::{self, NonNull},
+    slice,
 };
 
+#[cfg(feature = "alloc")]
+use core::alloc::Allocator;
+
+#[cfg(feature = "alloc")]
+use std_alloc::vec::Vec;
+
+#[cfg(feature = "std")]
+use std::io;
+
 use crate::{
+    alloc,
+    collection::{MemoryUsage, PersistedSlice, Pod, StoreBox},
     extension::unique_single::UniqueSingleHandle,
-    interface::{StoreDangling, StoreSingle},
+    interface::{PersistError, StoreDangling, StorePersist, StorePinning, StoreRegion, StoreSharing, StoreSingle},
 };
 
 /// A dynamic array.
+///
+/// #   Layout
+///
+/// The vector is `#[repr(C)]`. Unless the `coercible-metadata` feature is enabled, its layout is that of the
+/// following C struct, where `H` is the handle of the store, and `S` the store itself:
+///
+/// ```c
+/// struct StoreVec {
+///     size_t length;
+///     H handle;
+///     size_t capacity;
+///     S store;
+/// };
+/// ```
+///
+/// The handle and the store are only meaningful to the store itself; see the `ffi_vec` example for resolving the
+/// handle of a `StoreRegion` from C.
+#[repr(C)]
 pub struct StoreVec<T, S: StoreSingle> {
     //  Type invariant:
     //  -   `self.length < self.array.capacity()`.
@@ -75,6 +111,19 @@ impl<T, S: StoreSingle> StoreVec<T, S> {
         self.array.capacity()
     }
 
+    /// Returns the memory footprint of the vector, as per the layout of its buffer.
+    pub const fn memory_usage(&self) -> MemoryUsage {
+        let bytes_allocated = self.capacity() * mem::size_of::<T>();
+        let bytes_used = self.length * mem::size_of::<T>();
+        let allocations = if bytes_allocated > 0 { 1 } else { 0 };
+
+        MemoryUsage {
+            bytes_allocated,
+            bytes_used,
+            allocations,
+        }
+    }
+
     /// Forces the length of the vector to `new_len`.
     ///
     /// #   Safety
@@ -163,6 +212,34 @@ impl<T, S: StoreSingle> StoreVec<T, S> {
         //  -   `self` is borrowed mutably for the lifetime of the result.
         unsafe { slice.as_uninit_slice_mut() }
     }
+
+    /// Returns the elements of the vector, and its remaining spare capacity, as a pair of disjoint slices.
+    ///
+    /// Once some of the spare capacity is initialized, `set_len` may be used to make it part of the vector.
+    pub const fn split_at_spare_mut(&mut self) -> (&mut [T], &mut [MaybeUninit<T>])
+    where
+        S: ~const StoreSingle,
+    {
+        debug_assert!(self.length <= self.capacity());
+
+        let capacity = self.capacity();
+        let slots = self.array.as_mut_slice().as_mut_ptr();
+
+        //  Safety:
+        //  -   `slots` is valid for reads and writes of `self.length` elements, as `self.length <= capacity`.
+        //  -   Slots in `0..self.length` are initialized, as per type invariant.
+        //  -   `self` is borrowed mutably for the lifetime of the result, and the slices are disjoint.
+        let elements = unsafe { slice::from_raw_parts_mut(slots, self.length) };
+
+        //  Safety:
+        //  -   `slots.add(self.length)` is valid for reads and writes of `capacity - self.length` elements.
+        //  -   `MaybeUninit<T>` has the same layout as `T`, and need not be initialized.
+        //  -   `self` is borrowed mutably for the lifetime of the result, and the slices are disjoint.
+        let spare =
+            unsafe { slice::from_raw_parts_mut(slots.add(self.length) as *mut MaybeUninit<T>, capacity - self.length) };
+
+        (elements, spare)
+    }
 }
 
 impl<T, S: StoreSingle> StoreVec<T, S> {
@@ -179,7 +256,21 @@ impl<T, S: StoreSingle> StoreVec<T, S> {
             return;
         }
 
-        self.grow_for(additional)
+        self.grow_for(additional);
+    }
+
+    /// Attempts to reserve capacity for at least `additional` more elements.
+    ///
+    /// Returns an error if the new capacity exceeds `isize::MAX` bytes, or if the store fails to provide it.
+    pub const fn try_reserve(&mut self, additional: usize) -> Result<(), AllocError>
+    where
+        S: ~const StoreSingle + ~const StoreDangling,
+    {
+        if additional < self.capacity() && self.length <= self.capacity() - additional {
+            return Ok(());
+        }
+
+        self.try_grow_for(additional)
     }
 }
 
@@ -240,22 +331,23 @@ impl<T, S: StoreSingle> StoreVec<T, S> {
 
         let length = mem::replace(&mut self.length, 0);
 
-        //  Safety:
-        //  -   `0 <= length`, as `length` is unsigned.
-        //  -   `length <= self.capacity()`, as per type invariant.
-        let slice = unsafe { self.array.as_mut_sub_slice_unchecked(0..length) };
-
-        let pointer: *mut [T] = slice.as_ptr();
+        let array = &mut self.array;
 
         //  Safety:
-        //  -   `pointer` is properly aligned.
-        //  -   `pointer` is non-null.
-        //  -   `pointer` is valid for both reads and writes.
-        //  -   `pointer` points to a slice of initialized elements.
-        unsafe { ptr::drop_in_place(pointer) };
+        //  -   `array.handle` was allocated by `array.store`, and is still valid.
+        //  -   `length <= self.capacity()`, and the first `length` elements are initialized, as per type invariant.
+        //  -   No reference to the elements is active, as `self` is borrowed mutably, and they are no longer used.
+        unsafe { array.handle.drop_initialized_prefix(length, &mut array.store) };
     }
 
     /// Appends an element at the back the vector.
+    ///
+    /// #   Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    ///
+    /// Calls `handle_alloc_error` if the store fails to provide the new capacity.
+    #[inline]
     pub const fn push(&mut self, value: T)
     where
         S: ~const StoreSingle + ~const StoreDangling,
@@ -264,6 +356,21 @@ impl<T, S: StoreSingle> StoreVec<T, S> {
             self.grow_for(1);
         }
 
+        //  Safety:
+        //  -   `self.len() < self.capacity()`, after growth.
+        unsafe { self.push_unchecked(value) };
+    }
+
+    /// Appends an element at the back of the vector, without checking the capacity.
+    ///
+    /// #   Safety
+    ///
+    /// -   `self.len()` must be strictly less than `self.capacity()`, for example after reserving.
+    #[inline]
+    pub const unsafe fn push_unchecked(&mut self, value: T)
+    where
+        S: ~const StoreSingle,
+    {
         let spare = self.spare_capacity_mut();
         debug_assert!(!spare.is_empty());
 
@@ -271,7 +378,7 @@ impl<T, S: StoreSingle> StoreVec<T, S> {
 
         //  Safety:
         //  -   `slot` is well aligned.
-        //  -   `slot` is valid for writes of size `T`, since `spare` is not empty after growth.
+        //  -   `slot` is valid for writes of size `T`, since `spare` is not empty as per pre-conditions.
         unsafe { ptr::write(slot, value) };
 
         self.length += 1;
@@ -305,333 +412,2575 @@ impl<T, S: StoreSingle> StoreVec<T, S> {
 
         Some(element)
     }
-}
 
-impl<T, S: StoreSingle + Default> Default for StoreVec<T, S> {
-    fn default() -> Self {
-        Self::new()
-    }
-}
+    /// Attempts to append an element at the back of the vector.
+    ///
+    /// Returns `value` back if the new capacity exceeds `isize::MAX` bytes, or if the store fails to provide it, so
+    /// that it is not lost.
+    pub fn try_push(&mut self, value: T) -> Result<(), T> {
+        if self.try_reserve(1).is_err() {
+            return Err(value);
+        }
 
-impl<T, S: StoreSingle> Drop for StoreVec<T, S> {
-    fn drop(&mut self) {
-        self.clear();
+        //  Safety:
+        //  -   `self.len() < self.capacity()`, after reserving.
+        unsafe { self.push_unchecked(value) };
+
+        Ok(())
     }
-}
 
-//
-//  Implementation
-//
+    /// Attempts to insert an element at position `index`, shifting all elements after it to the right.
+    ///
+    /// Returns `value` back if the new capacity exceeds `isize::MAX` bytes, or if the store fails to provide it, so
+    /// that it is not lost.
+    ///
+    /// #   Panics
+    ///
+    /// Panics if `index > self.len()`.
+    pub fn try_insert(&mut self, index: usize, value: T) -> Result<(), T> {
+        if self.try_reserve(1).is_err() {
+            return Err(value);
+        }
 
-impl<T, S: StoreSingle> StoreVec<T, S> {
-    #[inline(never)]
-    const fn grow_for(&mut self, additional: usize)
-    where
-        S: ~const StoreSingle + ~const StoreDangling,
-    {
-        let Some(target_capacity) = self.length.checked_add(additional) else {
-            UniqueArray::<T, S>::capacity_exceeded()
-        };
+        self.insert(index, value);
 
-        //  The caller shouldn't have called...
-        if target_capacity <= self.capacity() {
-            return;
+        Ok(())
+    }
+
+    /// Inserts an element at position `index`, shifting all elements after it to the right.
+    ///
+    /// #   Panics
+    ///
+    /// Panics if `index > self.len()`, or if the new capacity exceeds `isize::MAX` bytes.
+    ///
+    /// Calls `handle_alloc_error` if the store fails to provide the new capacity.
+    pub fn insert(&mut self, index: usize, value: T) {
+        assert!(
+            index <= self.length,
+            "insertion index {index} should be <= len {}",
+            self.length
+        );
+
+        if self.length == self.capacity() {
+            self.grow_for(1);
         }
 
-        let target_capacity = UniqueArray::<T, S>::round_up_capacity(target_capacity);
+        //  Safety:
+        //  -   `index <= self.length`, as per assertion above.
+        //  -   `self.length < self.capacity()`, after growth.
+        let slice = unsafe { self.array.as_mut_sub_slice_unchecked(index..self.length + 1) };
+
+        let slot = slice.as_mut_ptr();
 
         //  Safety:
-        //  -   `target_capacity` is greater than or equal to `self.array.capacity()`.
-        unsafe { self.array.grow_to(target_capacity) };
+        //  -   `slot` is valid for reads and writes of `self.length - index + 1` elements.
+        //  -   `slot` is well-aligned.
+        unsafe {
+            ptr::copy(slot, slot.add(1), self.length - index);
+            ptr::write(slot, value);
+        }
+
+        self.length += 1;
     }
-}
 
-struct UniqueArray<T, S: StoreSingle> {
-    handle: UniqueSingleHandle<[T], S::Handle>,
-    store: S,
-}
+    /// Removes and returns the element at position `index`, shifting all elements after it to the left.
+    ///
+    /// #   Panics
+    ///
+    /// Panics if `index >= self.len()`.
+    pub fn remove(&mut self, index: usize) -> T {
+        assert!(
+            index < self.length,
+            "removal index {index} should be < len {}",
+            self.length
+        );
 
-impl<T, S: StoreSingle> UniqueArray<T, S> {
-    const fn new_in(store: S) -> Self
-    where
-        S: ~const StoreDangling,
-    {
-        let handle = UniqueSingleHandle::dangling_slice(&store);
+        //  Safety:
+        //  -   `index < self.length`, as per assertion above.
+        //  -   `self.length <= self.capacity()`, as per type invariant.
+        let slice = unsafe { self.array.as_mut_sub_slice_unchecked(index..self.length) };
 
-        Self { handle, store }
+        let slot = slice.as_mut_ptr();
+
+        //  Safety:
+        //  -   `slot` is valid for reads and writes of `self.length - index` elements.
+        //  -   `slot` is well-aligned.
+        //  -   `slot` is initialized, as per type invariant.
+        let element = unsafe { ptr::read(slot) };
+
+        //  Safety:
+        //  -   `slot` is valid for reads and writes of `self.length - index` elements.
+        //  -   The elements after `slot` are initialized, as per type invariant.
+        unsafe { ptr::copy(slot.add(1), slot, self.length - index - 1) };
+
+        self.length -= 1;
+
+        element
     }
 
-    const fn with_capacity_in(capacity: usize, mut store: S) -> Self
+    /// Splits the vector in two at `at`, returning the elements in `at..` in a new vector, using a new store.
+    ///
+    /// The capacity of `self` is unchanged.
+    ///
+    /// #   Panics
+    ///
+    /// Panics if `at > self.len()`.
+    ///
+    /// Calls `handle_alloc_error` if the new store fails to provide the capacity for the elements.
+    pub fn split_off(&mut self, at: usize) -> Self
     where
-        S: ~const StoreSingle + ~const StoreDangling,
+        S: Default,
     {
-        let handle = UniqueSingleHandle::allocate_slice(capacity, &mut store);
-
-        Self { handle, store }
+        self.split_off_in(at, S::default())
     }
 
-    const fn capacity(&self) -> usize {
-        self.handle.len()
+    /// Splits the vector in two at `at`, returning the elements in `at..` in a new vector, using `store`.
+    ///
+    /// The capacity of `self` is unchanged.
+    ///
+    /// #   Panics
+    ///
+    /// Panics if `at > self.len()`.
+    ///
+    /// Calls `handle_alloc_error` if `store` fails to provide the capacity for the elements.
+    pub fn split_off_in<OS: StoreSingle>(&mut self, at: usize, store: OS) -> StoreVec<T, OS> {
+        let count = self.split_off_count(at);
+
+        let mut other = StoreVec::new_in(store);
+        other.reserve(count);
+
+        //  Safety:
+        //  -   `at + count == self.len()`, as per `split_off_count`.
+        //  -   `count <= other.capacity()`, after reserving.
+        unsafe { self.move_tail_into(at, &mut other) };
+
+        other
     }
 
-    const fn as_slice(&self) -> NonNull<[T]>
-    where
-        S: ~const StoreSingle,
-    {
+    /// Attempts to split the vector in two at `at`, returning the elements in `at..` in a new vector, using `store`.
+    ///
+    /// Returns an error, leaving `self` unchanged, if `store` fails to provide the capacity for the elements.
+    ///
+    /// #   Panics
+    ///
+    /// Panics if `at > self.len()`.
+    pub fn try_split_off_in<OS: StoreSingle>(&mut self, at: usize, store: OS) -> Result<StoreVec<T, OS>, AllocError> {
+        let count = self.split_off_count(at);
+
+        let mut other = StoreVec::new_in(store);
+        other.try_reserve(count)?;
+
         //  Safety:
-        //  -   `self.handle` is a valid or dangling handle.
-        //  -   `self.handle` was obtained from `self.store` in either case.
-        unsafe { self.handle.resolve_raw(&self.store) }
+        //  -   `at + count == self.len()`, as per `split_off_count`.
+        //  -   `count <= other.capacity()`, after reserving.
+        unsafe { self.move_tail_into(at, &mut other) };
+
+        Ok(other)
     }
+}
 
-    const fn as_mut_slice(&mut self) -> NonNull<[T]>
+/// Bulk initialization.
+///
+/// Each operation reserves capacity once, resolves the handle once, writes the elements directly into the spare
+/// capacity, then commits the length. Should producing or cloning an element panic, the elements already written are
+/// committed, so that they are dropped with the vector.
+impl<T, S: StoreSingle> StoreVec<T, S> {
+    /// Appends `n` elements at the back of the vector, the `i`-th of which is produced by `f(i)`, for `i` in `0..n`.
+    ///
+    /// #   Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    ///
+    /// Calls `handle_alloc_error` if the store fails to provide the new capacity.
+    pub fn extend_from_fn<F>(&mut self, n: usize, f: F)
     where
-        S: ~const StoreSingle,
+        F: FnMut(usize) -> T,
     {
+        self.reserve(n);
+
         //  Safety:
-        //  -   `self.handle` is a valid or dangling handle.
-        //  -   `self.handle` was obtained from `self.store` in either case.
-        unsafe { self.handle.resolve_raw_mut(&mut self.store) }
+        //  -   `n <= self.capacity() - self.len()`, after reserving.
+        unsafe { self.extend_from_fn_unchecked(n, f) };
     }
 
-    //  #   Safety
-    //
-    //  -   `range.start <= range.end`.
-    //  -   `range.end <= self.capacity()`.
-    const unsafe fn as_sub_slice_unchecked(&self, range: Range<usize>) -> NonNull<[T]>
+    /// Attempts to append `n` elements at the back of the vector, the `i`-th of which is produced by `f(i)`, for `i`
+    /// in `0..n`.
+    ///
+    /// Returns an error, without calling `f`, if the new capacity exceeds `isize::MAX` bytes, or if the store fails to
+    /// provide it.
+    pub fn try_extend_from_fn<F>(&mut self, n: usize, f: F) -> Result<(), AllocError>
     where
-        S: ~const StoreSingle,
+        F: FnMut(usize) -> T,
     {
-        debug_assert!(range.start <= range.end);
-        debug_assert!(range.end <= self.handle.len());
-
-        let slice = self.as_slice();
-
-        let pointer = slice.as_mut_ptr();
-
-        //  Safety:
-        //  -   `pointer` is correctly aligned.
-        //  -   `range.start <= slice.len()`.
-        let pointer = unsafe { pointer.add(range.start) };
+        self.try_reserve(n)?;
 
         //  Safety:
-        //  -   `pointer` is non-null, since it comes from a `NonNull`, and was not decremented.
-        let pointer = unsafe { NonNull::new_unchecked(pointer) };
+        //  -   `n <= self.capacity() - self.len()`, after reserving.
+        unsafe { self.extend_from_fn_unchecked(n, f) };
 
-        NonNull::slice_from_raw_parts(pointer, range.end - range.start)
+        Ok(())
     }
 
-    //  #   Safety
-    //
-    //  -   `range.start <= range.end`.
-    //  -   `range.end <= self.capacity()`.
-    const unsafe fn as_mut_sub_slice_unchecked(&mut self, range: Range<usize>) -> NonNull<[T]>
+    /// Fills the spare capacity of the vector with clones of `value`, so that its length matches its capacity.
+    ///
+    /// Returns the number of elements appended. `value` is moved into the last slot, if any, and dropped otherwise.
+    ///
+    /// No memory is allocated: combine with `with_capacity_in`, or `reserve`, to fill a given number of slots, for
+    /// example prior to decoding a fixed-size frame in place.
+    pub fn fill_spare(&mut self, value: T) -> usize
     where
-        S: ~const StoreSingle,
+        T: Clone,
     {
-        debug_assert!(range.start <= range.end);
-        debug_assert!(range.end <= self.handle.len());
-
-        let slice = self.as_mut_slice();
+        debug_assert!(self.length <= self.capacity());
 
-        let pointer = slice.as_mut_ptr();
+        let Some(n) = (self.capacity() - self.length).checked_sub(1) else {
+            return 0;
+        };
 
         //  Safety:
-        //  -   `pointer` is correctly aligned.
-        //  -   `range.start <= slice.len()`.
-        let pointer = unsafe { pointer.add(range.start) };
+        //  -   `n < self.capacity() - self.len()`, as per the computation of `n`.
+        unsafe { self.extend_from_fn_unchecked(n, |_| value.clone()) };
 
         //  Safety:
-        //  -   `pointer` is non-null, since it comes from a `NonNull`, and was not decremented.
-        let pointer = unsafe { NonNull::new_unchecked(pointer) };
+        //  -   `self.len() < self.capacity()`, as `n` elements were appended, out of `n + 1` spare slots.
+        unsafe { self.push_unchecked(value) };
 
-        NonNull::slice_from_raw_parts(pointer, range.end - range.start)
+        n + 1
     }
 }
 
-impl<T, S: StoreSingle> UniqueArray<T, S> {
-    #[cold]
-    #[inline(never)]
-    const fn capacity_exceeded() -> ! {
-        panic!("New capacity exceeds isize::MAX bytes")
+/// In-place reordering.
+///
+/// Each operation resolves the handle once, then operates on the resulting slice, and lets go of the slice before any
+/// further call to the store. Collections built atop a store should follow this pattern: resolving per element is
+/// wasteful, and holding on to a resolved pointer across a call to the store may leave it dangling.
+impl<T, S: StoreSingle> StoreVec<T, S> {
+    /// Swaps the elements at positions `a` and `b`.
+    ///
+    /// #   Panics
+    ///
+    /// Panics if `a >= self.len()` or `b >= self.len()`.
+    pub fn swap(&mut self, a: usize, b: usize) {
+        self.as_mut_slice().swap(a, b);
     }
 
-    const fn round_up_capacity(min_capacity: usize) -> usize {
-        if min_capacity <= 1 || min_capacity.count_ones() == 1 {
-            return min_capacity;
-        }
+    /// Rotates the elements in-place, such that the first `mid` elements move to the end.
+    ///
+    /// #   Panics
+    ///
+    /// Panics if `mid > self.len()`.
+    pub fn rotate_left(&mut self, mid: usize) {
+        self.as_mut_slice().rotate_left(mid);
+    }
 
-        if min_capacity >= 1 << (usize::BITS - 1) {
-            Self::capacity_exceeded()
-        }
+    /// Rotates the elements in-place, such that the last `k` elements move to the front.
+    ///
+    /// #   Panics
+    ///
+    /// Panics if `k > self.len()`.
+    pub fn rotate_right(&mut self, k: usize) {
+        self.as_mut_slice().rotate_right(k);
+    }
 
-        let shift = usize::BITS - (min_capacity - 1).leading_zeros();
+    /// Reverses the order of the elements in-place.
+    pub fn reverse(&mut self) {
+        self.as_mut_slice().reverse();
+    }
+}
+
+/// Sorted vector, used as a map.
+///
+/// The following methods assume that the vector is sorted by the key extracted by `f`, and keep it sorted. This is
+/// a lightweight replacement for a `BTreeMap`, notably when the number of elements is small and the store is inline.
+///
+/// ```
+/// use storage::{collection::StoreVec, store::InlineSingleStore};
+///
+/// let mut map = StoreVec::<(u8, &str), InlineSingleStore<[(u8, &str); 4]>>::new();
+///
+/// map.insert_sorted_by_key((3, "three"), |entry| entry.0);
+/// map.insert_sorted_by_key((1, "one"), |entry| entry.0);
+/// map.insert_sorted_by_key((2, "two"), |entry| entry.0);
+///
+/// assert_eq!(Some(&(2, "two")), map.get_sorted_by_key(&2, |entry| entry.0));
+/// assert_eq!(Some((1, "one")), map.remove_sorted_by_key(&1, |entry| entry.0));
+/// assert_eq!(&[(2, "two"), (3, "three")], &map[..]);
+/// ```
+impl<T, S: StoreSingle> StoreVec<T, S> {
+    /// Returns a reference to the element whose key is `key`, if any.
+    pub fn get_sorted_by_key<B, F>(&self, key: &B, f: F) -> Option<&T>
+    where
+        B: Ord,
+        F: FnMut(&T) -> B,
+    {
+        let index = self.binary_search_by_key(key, f).ok()?;
 
-        1 << shift
+        self.get(index)
     }
 
-    //  #   Safety
-    //
-    //  -   `target_capacity` must be greater than or equal to `self.capacity()`.
-    //
-    //  #   Panics
-    //
-    //  If the new capacity exceeds `isize::MAX` bytes.
-    const unsafe fn grow_to(&mut self, target_capacity: usize)
+    /// Returns a mutable reference to the element whose key is `key`, if any.
+    ///
+    /// The key of the element must not be modified through the reference, lest the vector be no longer sorted.
+    pub fn get_sorted_by_key_mut<B, F>(&mut self, key: &B, f: F) -> Option<&mut T>
     where
-        S: ~const StoreSingle + ~const StoreDangling,
+        B: Ord,
+        F: FnMut(&T) -> B,
     {
-        const MAX_BYTES: usize = isize::MAX as usize;
+        let index = self.binary_search_by_key(key, f).ok()?;
 
-        let Some(target_bytes) = target_capacity.checked_mul(mem::size_of::<T>()) else {
-            Self::capacity_exceeded()
-        };
+        self.get_mut(index)
+    }
+
+    /// Inserts `value` at its sorted position, returning the element with the same key it replaces, if any.
+    pub fn insert_sorted_by_key<B, F>(&mut self, value: T, mut f: F) -> Option<T>
+    where
+        B: Ord,
+        F: FnMut(&T) -> B,
+    {
+        let key = f(&value);
+
+        match self.binary_search_by_key(&key, f) {
+            Ok(index) => Some(mem::replace(&mut self[index], value)),
+            Err(index) => {
+                self.insert(index, value);
+                None
+            }
+        }
+    }
+
+    /// Removes and returns the element whose key is `key`, if any.
+    pub fn remove_sorted_by_key<B, F>(&mut self, key: &B, f: F) -> Option<T>
+    where
+        B: Ord,
+        F: FnMut(&T) -> B,
+    {
+        let index = self.binary_search_by_key(key, f).ok()?;
+
+        Some(self.remove(index))
+    }
+}
+
+impl<T, S: StoreSingle> StoreVec<T, S> {
+    /// Returns an iterator over the elements of the vector.
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.as_slice().iter()
+    }
+
+    /// Returns an iterator over mutable references to the elements of the vector.
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, T> {
+        self.as_mut_slice().iter_mut()
+    }
+}
+
+impl<T, S: StoreSingle> AsRef<[T]> for StoreVec<T, S> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, S: StoreSingle> AsMut<[T]> for StoreVec<T, S> {
+    fn as_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+/// Slice methods, such as `sort_unstable`, `binary_search`, or `as_chunks`, are available through `Deref`.
+impl<T, S: StoreSingle> Deref for StoreVec<T, S> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, S: StoreSingle> DerefMut for StoreVec<T, S> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T, S: StoreSingle + Default> Default for StoreVec<T, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, S: StoreSingle> Drop for StoreVec<T, S> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T: fmt::Debug, S: StoreSingle> fmt::Debug for StoreVec<T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+//
+//  Comparison
+//
+
+impl<T, S, OS> cmp::PartialEq<StoreVec<T, OS>> for StoreVec<T, S>
+where
+    T: cmp::PartialEq,
+    S: StoreSingle,
+    OS: StoreSingle,
+{
+    fn eq(&self, other: &StoreVec<T, OS>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: cmp::PartialEq, S: StoreSingle> cmp::PartialEq<[T]> for StoreVec<T, S> {
+    fn eq(&self, other: &[T]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl<T: cmp::PartialEq, S: StoreSingle> cmp::PartialEq<&[T]> for StoreVec<T, S> {
+    fn eq(&self, other: &&[T]) -> bool {
+        self.as_slice() == *other
+    }
+}
+
+impl<T: cmp::PartialEq, S: StoreSingle, const N: usize> cmp::PartialEq<[T; N]> for StoreVec<T, S> {
+    fn eq(&self, other: &[T; N]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl<T: cmp::PartialEq, S: StoreSingle> cmp::PartialEq<StoreVec<T, S>> for [T] {
+    fn eq(&self, other: &StoreVec<T, S>) -> bool {
+        self == other.as_slice()
+    }
+}
+
+impl<T: cmp::PartialEq, S: StoreSingle> cmp::PartialEq<StoreVec<T, S>> for &[T] {
+    fn eq(&self, other: &StoreVec<T, S>) -> bool {
+        *self == other.as_slice()
+    }
+}
+
+impl<T: cmp::PartialEq, S: StoreSingle, const N: usize> cmp::PartialEq<StoreVec<T, S>> for [T; N] {
+    fn eq(&self, other: &StoreVec<T, S>) -> bool {
+        self == other.as_slice()
+    }
+}
+
+impl<T: cmp::Eq, S: StoreSingle> cmp::Eq for StoreVec<T, S> {}
+
+/// Hashes as a slice, hence as a `Vec`, with the same elements.
+impl<T: hash::Hash, S: StoreSingle> hash::Hash for StoreVec<T, S> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state);
+    }
+}
+
+impl<T, S, OS> cmp::PartialOrd<StoreVec<T, OS>> for StoreVec<T, S>
+where
+    T: cmp::PartialOrd,
+    S: StoreSingle,
+    OS: StoreSingle,
+{
+    fn partial_cmp(&self, other: &StoreVec<T, OS>) -> Option<cmp::Ordering> {
+        self.as_slice().partial_cmp(other.as_slice())
+    }
+}
+
+impl<T: cmp::Ord, S: StoreSingle> cmp::Ord for StoreVec<T, S> {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.as_slice().cmp(other.as_slice())
+    }
+}
+
+//
+//  Iteration
+//
+
+impl<T, S: StoreSingle> IntoIterator for StoreVec<T, S> {
+    type Item = T;
+    type IntoIter = IntoIter<T, S>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let this = ManuallyDrop::new(self);
+
+        let front = 0;
+        let back = this.length;
+
+        //  Safety:
+        //  -   `this.array` will never be used ever again, as `this` is never dropped.
+        let array = unsafe { ptr::read(&this.array) };
+
+        IntoIter { front, back, array }
+    }
+}
+
+impl<'a, T, S: StoreSingle> IntoIterator for &'a StoreVec<T, S> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, S: StoreSingle> IntoIterator for &'a mut StoreVec<T, S> {
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// Extends the vector, reserving capacity for the lower bound of the size hint of the iterator upfront.
+///
+/// Exact-size iterators thus lead to a single reservation, which matters for bump stores in which a block of memory
+/// relocated by growth is never reclaimed.
+impl<T, S: StoreSingle> Extend<T> for StoreVec<T, S> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut iter = iter.into_iter();
+
+        let (lower, _) = iter.size_hint();
+
+        self.reserve(lower);
+
+        //  The size hint may be wrong, hence only the first `lower` elements may be written without checking.
+        for element in iter.by_ref().take(lower) {
+            //  Safety:
+            //  -   `self.len() < self.capacity()`, as at most `lower` elements are appended after reserving `lower`.
+            unsafe { self.push_unchecked(element) };
+        }
+
+        //  The remaining elements are written through a pointer to the slots, which is only resolved anew when growth
+        //  relocates the block of memory.
+        let mut slots = self.array.as_mut_slice().as_mut_ptr();
+
+        for element in iter {
+            if self.length == self.capacity() && self.grow_for(1) {
+                slots = self.array.as_mut_slice().as_mut_ptr();
+            }
+
+            debug_assert!(self.length < self.capacity());
+
+            //  Safety:
+            //  -   `slots` points to the block of memory, as it was resolved anew after any relocation.
+            //  -   `slots.add(self.length)` is in bounds, and valid for writes, as `self.length < self.capacity()`.
+            unsafe { ptr::write(slots.add(self.length), element) };
+
+            self.length += 1;
+        }
+    }
+}
+
+impl<T, S: StoreSingle + Default> FromIterator<T> for StoreVec<T, S> {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut this = Self::new();
+
+        this.extend(iter);
+
+        this
+    }
+}
+
+/// Iterator over a dynamic array.
+pub struct IntoIter<T, S: StoreSingle> {
+    //  Type invariant:
+    //  -   `self.front <= self.back <= self.array.capacity()`.
+    //  -   Slots in `self.front..self.back` are initialized, all others are not.
+    front: usize,
+    back: usize,
+    array: UniqueArray<T, S>,
+}
+
+impl<T, S: StoreSingle> Iterator for IntoIter<T, S> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+
+        let index = self.front;
+        self.front += 1;
+
+        //  Safety:
+        //  -   `index` is initialized, as per type invariant, and is now considered uninitialized.
+        Some(unsafe { self.read(index) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let length = self.back - self.front;
+
+        (length, Some(length))
+    }
+}
+
+impl<T, S: StoreSingle> DoubleEndedIterator for IntoIter<T, S> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.back -= 1;
+
+        //  Safety:
+        //  -   `self.back` is initialized, as per type invariant, and is now considered uninitialized.
+        Some(unsafe { self.read(self.back) })
+    }
+}
+
+impl<T, S: StoreSingle> ExactSizeIterator for IntoIter<T, S> {}
+
+impl<T, S: StoreSingle> FusedIterator for IntoIter<T, S> {}
+
+impl<T, S: StoreSingle> Drop for IntoIter<T, S> {
+    fn drop(&mut self) {
+        let range = mem::replace(&mut self.front, self.back)..self.back;
+
+        //  Safety:
+        //  -   `range.start <= range.end`, as per type invariant.
+        //  -   `range.end <= self.array.capacity()`, as per type invariant.
+        let slice = unsafe { self.array.as_mut_sub_slice_unchecked(range) };
+
+        //  Safety:
+        //  -   `slice` is properly aligned, non-null, and valid for both reads and writes.
+        //  -   `slice` points to a slice of initialized elements, as per type invariant.
+        unsafe { ptr::drop_in_place(slice.as_ptr()) };
+    }
+}
+
+impl<T, S: StoreSingle> IntoIter<T, S> {
+    //  #   Safety
+    //
+    //  -   `index` must be within `0..self.array.capacity()`.
+    //  -   The slot at `index` must be initialized, and will be considered uninitialized afterwards.
+    unsafe fn read(&mut self, index: usize) -> T {
+        //  Safety:
+        //  -   `index <= index + 1`.
+        //  -   `index + 1 <= self.array.capacity()`, as per pre-conditions.
+        let slice = unsafe { self.array.as_mut_sub_slice_unchecked(index..index + 1) };
+
+        let slot = slice.as_mut_ptr() as *const T;
+
+        //  Safety:
+        //  -   `slot` is well-aligned.
+        //  -   `slot` is valid for read of size T.
+        //  -   `slot` is initialized, as per pre-conditions.
+        unsafe { ptr::read(slot) }
+    }
+}
+
+//
+//  Conversion
+//
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<T, A: Allocator> From<Vec<T, A>> for StoreVec<T, A> {
+    fn from(value: Vec<T, A>) -> Self {
+        let mut value = ManuallyDrop::new(value);
+
+        let length = value.len();
+        let capacity = value.capacity();
+
+        //  Safety:
+        //  -   `value.as_mut_ptr()` is non-null, even when dangling.
+        let pointer = unsafe { NonNull::new_unchecked(value.as_mut_ptr()) };
+
+        //  Safety:
+        //  -   `value.allocator()` will never be used ever again, as `value` is never dropped.
+        let store = unsafe { ptr::read(value.allocator()) };
+
+        if capacity > 0 && mem::size_of::<T>() > 0 {
+            //  Safety:
+            //  -   `pointer` is associated to a block of memory allocated by `store`, which fits `capacity` instances
+            //      of `T`.
+            //  -   No copy of `pointer` will be used henceforth, since `value` is forgotten.
+            let handle = unsafe { UniqueSingleHandle::from_raw_parts(pointer.cast().into(), capacity.into()) };
+
+            let array = UniqueArray { handle, store };
+
+            return Self { length, array };
+        }
+
+        //  `Vec` never allocates zero-sized blocks of memory, using dangling pointers instead, which `UniqueArray` only
+        //  expects with an empty handle.
+        let mut array = UniqueArray::new_in(store);
+
+        if length > 0 {
+            //  Safety:
+            //  -   `length` is greater than or equal to `array.capacity()`, which is 0.
+            unsafe { array.grow_to(length) };
+        }
+
+        Self { length, array }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<T, A: Allocator> From<StoreVec<T, A>> for Vec<T, A> {
+    fn from(value: StoreVec<T, A>) -> Self {
+        let mut value = ManuallyDrop::new(value);
+
+        let length = value.len();
+        let capacity = value.capacity();
+        let pointer = value.as_mut_ptr();
+
+        //  Safety:
+        //  -   `value.array.store` will never be used ever again, as `value` is never dropped.
+        let store = unsafe { ptr::read(&value.array.store) };
+
+        //  Safety:
+        //  -   `pointer` was allocated by `store`, with a layout fitting `capacity` instances of `T`, or is dangling
+        //      if `capacity` is 0.
+        //  -   Slots in `0..length` are initialized, as per type invariant.
+        unsafe { Vec::from_raw_parts_in(pointer, length, capacity, store) }
+    }
+}
+
+impl<T, S: StoreSingle> StoreVec<T, S> {
+    /// Converts the vector into a `StoreBox<[T], S>`, dropping any excess capacity.
+    pub fn into_boxed_slice(self) -> StoreBox<[T], S> {
+        let this = ManuallyDrop::new(self);
+
+        let length = this.length;
+
+        //  Safety:
+        //  -   `this.array.handle` will never be used ever again, as `this` is never dropped.
+        let mut handle = unsafe { ptr::read(&this.array.handle) };
+
+        //  Safety:
+        //  -   `this.array.store` will never be used ever again, as `this` is never dropped.
+        let mut store = unsafe { ptr::read(&this.array.store) };
+
+        let handle = if handle.is_empty() || mem::size_of::<T>() == 0 {
+            //  `UniqueArray` uses dangling handles where `StoreBox` requires an allocated, if zero-sized, block.
+            let (handle, _) = UniqueSingleHandle::<[T; 0], _>::allocate(&mut store).to_raw_parts();
+
+            handle
+        } else {
+            if length < handle.len() {
+                //  Safety:
+                //  -   `handle` was allocated by `store`.
+                //  -   `handle` is still valid.
+                //  -   `length` is less than or equal to `handle.len()`.
+                unsafe { handle.shrink(length, &mut store) };
+            }
+
+            let (handle, _) = handle.to_raw_parts();
+
+            handle
+        };
+
+        //  Safety:
+        //  -   `handle` is associated to a block of memory allocated by `store`, which fits `length` instances of
+        //      `T`, as the block was either shrunk to `length`, or is zero-sized with `T` zero-sized too.
+        //  -   No copy of `handle` will be used henceforth.
+        let handle = unsafe { UniqueSingleHandle::from_raw_parts(handle, length.into()) };
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        //  -   `handle` is associated to a block of memory containing `length` initialized instances of `T`.
+        unsafe { StoreBox::from_raw_parts(handle, store) }
+    }
+
+    /// Converts a `StoreBox<[T], S>` into a vector, without reallocating if possible.
+    pub fn from_boxed_slice(boxed: StoreBox<[T], S>) -> Self {
+        let (handle, mut store) = boxed.into_raw_parts();
+
+        let length = handle.len();
+
+        if length > 0 && mem::size_of::<T>() > 0 {
+            let array = UniqueArray { handle, store };
+
+            return Self { length, array };
+        }
+
+        //  `UniqueArray` never holds onto zero-sized blocks of memory, using dangling handles instead.
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`.
+        //  -   `handle` is still valid.
+        unsafe { handle.deallocate(&mut store) };
+
+        let mut array = UniqueArray::new_in(store);
+
+        if length > 0 {
+            //  Safety:
+            //  -   `length` is greater than or equal to `array.capacity()`, which is 0.
+            unsafe { array.grow_to(length) };
+        }
+
+        Self { length, array }
+    }
+
+    /// Creates an instance from its raw parts, as returned by `into_raw_parts`.
+    ///
+    /// #   Safety
+    ///
+    /// -   If `handle` is empty, or `T` is zero-sized, `handle` must be dangling, as per `dangling_slice`.
+    /// -   Otherwise, `handle` must have been allocated by `store`, and must still be valid.
+    /// -   `length` must be less than or equal to `handle.len()`.
+    /// -   The first `length` elements of `handle` must be initialized.
+    pub const unsafe fn from_raw_parts(handle: UniqueSingleHandle<[T], S::Handle>, length: usize, store: S) -> Self {
+        let array = UniqueArray { handle, store };
+
+        Self { length, array }
+    }
+
+    /// Decomposes the vector into its raw parts: its handle, its length, and its store.
+    ///
+    /// The length of the handle is the capacity of the vector. The caller becomes responsible for dropping the first
+    /// `length` elements, and deallocating the handle, unless it is dangling; or for reassembling a vector with
+    /// `from_raw_parts`.
+    pub fn into_raw_parts(self) -> (UniqueSingleHandle<[T], S::Handle>, usize, S) {
+        let this = ManuallyDrop::new(self);
+
+        //  Safety:
+        //  -   `this.array.handle` will never be used ever again, as `this` is never dropped.
+        let handle = unsafe { ptr::read(&this.array.handle) };
+
+        //  Safety:
+        //  -   `this.array.store` will never be used ever again, as `this` is never dropped.
+        let store = unsafe { ptr::read(&this.array.store) };
+
+        (handle, this.length, store)
+    }
+
+    /// Leaks the vector, returning a mutable reference to its elements.
+    ///
+    /// Neither the elements, nor the block of memory, nor the store are ever dropped. As the store is pinning, the
+    /// elements remain valid for as long as the store type itself, as per `StorePinning`.
+    pub fn leak<'a>(self) -> &'a mut [T]
+    where
+        S: StorePinning + 'a,
+    {
+        let mut this = ManuallyDrop::new(self);
+
+        let length = this.length;
+        let pointer = this.as_mut_ptr();
+
+        //  Safety:
+        //  -   `pointer` points to `length` initialized elements, as per type invariant.
+        //  -   `pointer` remains valid for `'a`, as the store is pinning and is never dropped.
+        //  -   The elements are exclusively accessible through the result, as the vector is forgotten.
+        unsafe { slice::from_raw_parts_mut(pointer, length) }
+    }
+}
+
+impl<T, S: StoreSingle> From<StoreBox<[T], S>> for StoreVec<T, S> {
+    fn from(value: StoreBox<[T], S>) -> Self {
+        Self::from_boxed_slice(value)
+    }
+}
+
+impl<T, S: StoreSingle> From<StoreVec<T, S>> for StoreBox<[T], S> {
+    fn from(value: StoreVec<T, S>) -> Self {
+        value.into_boxed_slice()
+    }
+}
+
+//
+//  I/O
+//
+
+/// Writes append to the vector, growing it as necessary.
+///
+/// If the store cannot accommodate the additional bytes, as many bytes as fit in the remaining capacity are written.
+///
+/// For reading, wrap the vector, or a reference to it, in a `std::io::Cursor`.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl<S: StoreSingle> io::Write for StoreVec<u8, S> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        //  On failure, fall back to filling the remaining capacity.
+        let _ = self.try_reserve(buf.len());
+
+        let spare = self.spare_capacity_mut();
+        let written = cmp::min(spare.len(), buf.len());
+
+        //  Safety:
+        //  -   `buf` is valid for reads of `written` bytes, as `written <= buf.len()`.
+        //  -   `spare` is valid for writes of `written` bytes, as `written <= spare.len()`.
+        //  -   `buf` and `spare` do not overlap, as `self` is borrowed mutably.
+        unsafe { ptr::copy_nonoverlapping(buf.as_ptr(), spare.as_mut_ptr() as *mut u8, written) };
+
+        //  Safety:
+        //  -   `self.length + written <= self.capacity()`, as `written <= spare.len()`.
+        //  -   The elements in `self.length..self.length + written` were just initialized.
+        unsafe { self.set_len(self.length + written) };
+
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<S: StoreSingle> StoreVec<u8, S> {
+    /// Attempts to append the bytes of `s` at the back of the vector.
+    ///
+    /// Returns an error if the new capacity exceeds `isize::MAX` bytes, or if the store fails to provide it, in which
+    /// case the vector is left untouched.
+    pub fn try_push_str(&mut self, s: &str) -> Result<(), AllocError> {
+        self.try_reserve(s.len())?;
+
+        let spare = self.spare_capacity_mut();
+
+        //  Safety:
+        //  -   `s` is valid for reads of `s.len()` bytes.
+        //  -   `spare` is valid for writes of `s.len()` bytes, as capacity was reserved.
+        //  -   `s` and `spare` do not overlap, as `self` is borrowed mutably.
+        unsafe { ptr::copy_nonoverlapping(s.as_ptr(), spare.as_mut_ptr() as *mut u8, s.len()) };
+
+        //  Safety:
+        //  -   `self.length + s.len() <= self.capacity()`, as capacity was reserved.
+        //  -   The elements in `self.length..self.length + s.len()` were just initialized.
+        unsafe { self.set_len(self.length + s.len()) };
+
+        Ok(())
+    }
+
+    /// Attempts to append the formatted `args` at the back of the vector.
+    ///
+    /// Unlike `write!`, which reports the failure to grow the vector as an opaque `fmt::Error`, this reports it as an
+    /// `AllocError`. On failure, the vector is left untouched.
+    ///
+    /// #   Panics
+    ///
+    /// If a formatting trait implementation returns an error.
+    pub fn try_write_fmt(&mut self, args: fmt::Arguments<'_>) -> Result<(), AllocError> {
+        let length = self.length;
+
+        let mut writer = TryWrite {
+            vec: self,
+            result: Ok(()),
+        };
+
+        let formatted = fmt::write(&mut writer, args);
+        let result = writer.result;
+
+        if result.is_err() || formatted.is_err() {
+            //  Safety:
+            //  -   `length` is less than or equal to `self.len()`, and the elements up to it are still initialized.
+            unsafe { self.set_len(length) };
+        }
+
+        if result.is_ok() && formatted.is_err() {
+            panic!("a formatting trait implementation returned an error");
+        }
+
+        result
+    }
+}
+
+/// Writes append to the vector, growing it as necessary.
+///
+/// Each string is either appended in full, or not at all, in which case `fmt::Error` is returned. Use `try_write_fmt`
+/// to distinguish the failure to grow the vector from a formatting failure.
+impl<S: StoreSingle> fmt::Write for StoreVec<u8, S> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.try_push_str(s).map_err(|_| fmt::Error)
+    }
+}
+
+//  Appends to a vector, recording the failure to grow it.
+struct TryWrite<'a, S: StoreSingle> {
+    vec: &'a mut StoreVec<u8, S>,
+    result: Result<(), AllocError>,
+}
+
+impl<S: StoreSingle> fmt::Write for TryWrite<'_, S> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.result = self.vec.try_push_str(s);
+
+        self.result.map_err(|_| fmt::Error)
+    }
+}
+
+//
+//  Sharing
+//
+
+impl<T, S: StoreSingle + StoreSharing> StoreVec<T, S> {
+    /// Appends an element created by `f` from a store sharing with the store of the vector.
+    ///
+    /// This is meant for nested collections, such as a `StoreVec` of `LinkedList`, so that the inner collections all
+    /// allocate from the same sharing set as the vector; see `SharingStore`.
+    ///
+    /// #   Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    ///
+    /// Calls `handle_alloc_error` if the store fails to provide the new capacity.
+    pub fn push_shared<F>(&mut self, f: F)
+    where
+        S: StoreSharing<SharingError = !>,
+        F: FnOnce(S) -> T,
+    {
+        self.try_push_shared(f).into_ok()
+    }
+
+    /// Attempts to append an element created by `f` from a store sharing with the store of the vector.
+    ///
+    /// Returns an error if the store cannot be shared, in which case `f` is not called.
+    ///
+    /// #   Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    ///
+    /// Calls `handle_alloc_error` if the store fails to provide the new capacity.
+    pub fn try_push_shared<F>(&mut self, f: F) -> Result<(), S::SharingError>
+    where
+        F: FnOnce(S) -> T,
+    {
+        let store = self.array.store.share()?;
+
+        self.push(f(store));
+
+        Ok(())
+    }
+
+    /// Splits the vector in two at `at`, returning the elements in `at..` in a new vector, using a store sharing with
+    /// the store of the vector.
+    ///
+    /// The capacity of `self` is unchanged.
+    ///
+    /// #   Panics
+    ///
+    /// Panics if `at > self.len()`.
+    ///
+    /// Calls `handle_alloc_error` if the shared store fails to provide the capacity for the elements.
+    pub fn split_off_shared(&mut self, at: usize) -> Self
+    where
+        S: StoreSharing<SharingError = !>,
+    {
+        self.try_split_off_shared(at).into_ok()
+    }
+
+    /// Attempts to split the vector in two at `at`, returning the elements in `at..` in a new vector, using a store
+    /// sharing with the store of the vector.
+    ///
+    /// Returns an error, leaving `self` unchanged, if the store cannot be shared.
+    ///
+    /// #   Panics
+    ///
+    /// Panics if `at > self.len()`.
+    ///
+    /// Calls `handle_alloc_error` if the shared store fails to provide the capacity for the elements.
+    pub fn try_split_off_shared(&mut self, at: usize) -> Result<Self, S::SharingError> {
+        //  Panics prior to sharing the store, should `at` be out of bounds.
+        self.split_off_count(at);
+
+        let store = self.array.store.share()?;
+
+        debug_assert_eq!(
+            self.array.store.sharing_token(),
+            store.sharing_token(),
+            "A store must have the same sharing token as its shares"
+        );
+
+        Ok(self.split_off_in(at, store))
+    }
+}
+
+//
+//  Persistence
+//
+
+impl<T, S: StoreSingle + StorePersist> StoreVec<T, S> {
+    /// Appends an element at the back of the vector, then persists the vector, as per `persist`.
+    ///
+    /// Each call incurs a flush and a snapshot; to amortize their cost over a batch of elements, `push` each of them,
+    /// then `persist` once.
+    ///
+    /// #   Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    ///
+    /// Calls `handle_alloc_error` if the store fails to provide the new capacity.
+    pub fn push_persistent(&mut self, value: T) -> Result<(), PersistError> {
+        self.push(value);
+
+        self.persist()
+    }
+
+    /// Flushes the elements of the vector, then snapshots the store.
+    ///
+    /// The length of the vector is held by the vector itself, and thus only persisted if the vector itself lives in
+    /// persisted memory, which is up to its owner to flush.
+    pub fn persist(&self) -> Result<(), PersistError> {
+        let bytes = self.length * mem::size_of::<T>();
+
+        if bytes > 0 {
+            //  Safety:
+            //  -   `mem::align_of::<T>()` is a power of 2.
+            //  -   `bytes` does not exceed `isize::MAX`, as the elements fit in memory.
+            let layout = unsafe { Layout::from_size_align_unchecked(bytes, mem::align_of::<T>()) };
+
+            //  Safety:
+            //  -   The handle is valid, and not dangling, as the vector holds elements of non-zero size.
+            //  -   `layout.size()` is less than or equal to the size of the block of memory, holding `self.capacity()`
+            //      elements.
+            unsafe { self.array.store.flush(self.array.raw_handle(), layout) };
+        }
+
+        self.array.store.snapshot()
+    }
+}
+
+impl<T: Pod, S: StoreSingle + StoreRegion> StoreVec<T, S> {
+    /// Returns the location of the elements within the region of the store, so that they may be viewed in place,
+    /// without copying, once the region is persisted and loaded again, see `PersistedSlice`.
+    ///
+    /// The location is only valid until the vector is next modified.
+    pub fn persisted(&self) -> PersistedSlice {
+        if self.length == 0 {
+            return PersistedSlice::default();
+        }
+
+        let region = self.array.store.region();
+
+        //  The elements lie within the region, as per the guarantees of `StoreRegion`.
+        let offset = self.as_ptr().addr() - region.as_mut_ptr().addr();
+
+        PersistedSlice::new(offset as u64, self.length as u64)
+    }
+}
+
+//
+//  Implementation
+//
+
+impl<T, S: StoreSingle> StoreVec<T, S> {
+    //  Returns the number of elements in `at..`.
+    //
+    //  #   Panics
+    //
+    //  Panics if `at > self.len()`.
+    fn split_off_count(&self, at: usize) -> usize {
+        assert!(at <= self.length, "split index {at} should be <= len {}", self.length);
+
+        self.length - at
+    }
+
+    //  Moves the elements in `at..` to the back of `other`, truncating `self` to `at` elements.
+    //
+    //  #   Safety
+    //
+    //  -   `at` must be less than or equal to `self.len()`.
+    //  -   `self.len() - at` must be less than or equal to `other.capacity() - other.len()`.
+    unsafe fn move_tail_into<OS: StoreSingle>(&mut self, at: usize, other: &mut StoreVec<T, OS>) {
+        debug_assert!(at <= self.length);
+
+        let count = self.length - at;
+
+        debug_assert!(count <= other.capacity() - other.length);
+
+        //  Safety:
+        //  -   `at <= self.length`, as per pre-conditions.
+        //  -   `self.length <= self.capacity()`, as per type invariant.
+        let source = unsafe { self.array.as_mut_sub_slice_unchecked(at..self.length) };
+
+        //  Safety:
+        //  -   `source` is valid for reads of `count` elements, which are initialized, as per type invariant.
+        //  -   `other.spare_capacity_mut()` is valid for writes of `count` elements, as per pre-conditions.
+        //  -   The blocks of memory do not overlap, as they belong to different vectors.
+        unsafe {
+            ptr::copy_nonoverlapping(
+                source.as_mut_ptr() as *const T,
+                other.spare_capacity_mut().as_mut_ptr() as *mut T,
+                count,
+            )
+        };
+
+        //  The elements are moved, hence `self` no longer owns them.
+        self.length = at;
+        other.length += count;
+    }
+
+    //  Appends `n` elements, the `i`-th of which is produced by `f(i)`, committing the length once all are written, or
+    //  once `f` panics.
+    //
+    //  #   Safety
+    //
+    //  -   `n` must be less than or equal to `self.capacity() - self.len()`.
+    unsafe fn extend_from_fn_unchecked<F>(&mut self, n: usize, mut f: F)
+    where
+        F: FnMut(usize) -> T,
+    {
+        //  Commits the length of the vector on drop, including when unwinding.
+        struct CommitLength<'a> {
+            length: &'a mut usize,
+            written: usize,
+        }
+
+        impl Drop for CommitLength<'_> {
+            fn drop(&mut self) {
+                *self.length = self.written;
+            }
+        }
+
+        debug_assert!(n <= self.capacity() - self.length);
+
+        let slots = self.array.as_mut_slice().as_mut_ptr();
+
+        let mut commit = CommitLength {
+            written: self.length,
+            length: &mut self.length,
+        };
+
+        for i in 0..n {
+            let element = f(i);
+
+            //  Safety:
+            //  -   `slots.add(commit.written)` is in bounds, and valid for writes, as `commit.written` is less than
+            //      the initial length plus `n`, which is less than or equal to the capacity, as per pre-conditions.
+            unsafe { ptr::write(slots.add(commit.written), element) };
+
+            commit.written += 1;
+        }
+    }
+
+    //  Returns whether the block of memory was relocated, as per `StoreSingle::grow_reporting`.
+    #[inline(never)]
+    const fn grow_for(&mut self, additional: usize) -> bool
+    where
+        S: ~const StoreSingle + ~const StoreDangling,
+    {
+        let Some(target_capacity) = self.length.checked_add(additional) else {
+            UniqueArray::<T, S>::capacity_exceeded()
+        };
+
+        //  The caller shouldn't have called...
+        if target_capacity <= self.capacity() {
+            return false;
+        }
+
+        let Some(target_capacity) = S::GROWTH.capacity(self.capacity(), target_capacity) else {
+            UniqueArray::<T, S>::capacity_exceeded()
+        };
+
+        //  Safety:
+        //  -   `target_capacity` is greater than or equal to `self.array.capacity()`.
+        unsafe { self.array.grow_to(target_capacity) }
+    }
+
+    #[inline(never)]
+    const fn try_grow_for(&mut self, additional: usize) -> Result<(), AllocError>
+    where
+        S: ~const StoreSingle + ~const StoreDangling,
+    {
+        let Some(target_capacity) = self.length.checked_add(additional) else {
+            return Err(AllocError);
+        };
+
+        //  The caller shouldn't have called...
+        if target_capacity <= self.capacity() {
+            return Ok(());
+        }
+
+        if let Some(grown_capacity) = S::GROWTH.capacity(self.capacity(), target_capacity) {
+            //  Safety:
+            //  -   `grown_capacity` is greater than or equal to `target_capacity`, itself greater than
+            //      `self.array.capacity()`.
+            if unsafe { self.array.try_grow_to(grown_capacity) }.is_ok() {
+                return Ok(());
+            }
+        }
+
+        //  The store may still be able to accommodate the exact capacity, notably if of fixed capacity.
+
+        //  Safety:
+        //  -   `target_capacity` is greater than `self.array.capacity()`.
+        let Ok(_) = (unsafe { self.array.try_grow_to(target_capacity) }) else {
+            return Err(AllocError);
+        };
+
+        Ok(())
+    }
+}
+
+//  `#[repr(C)]`, as part of the layout of `StoreVec`.
+#[repr(C)]
+struct UniqueArray<T, S: StoreSingle> {
+    handle: UniqueSingleHandle<[T], S::Handle>,
+    store: S,
+}
+
+impl<T, S: StoreSingle> UniqueArray<T, S> {
+    const fn new_in(store: S) -> Self
+    where
+        S: ~const StoreDangling,
+    {
+        let handle = UniqueSingleHandle::dangling_slice(&store);
+
+        Self { handle, store }
+    }
+
+    const fn with_capacity_in(capacity: usize, mut store: S) -> Self
+    where
+        S: ~const StoreSingle + ~const StoreDangling,
+    {
+        let handle = UniqueSingleHandle::allocate_slice(capacity, &mut store);
+
+        Self { handle, store }
+    }
+
+    const fn capacity(&self) -> usize {
+        self.handle.len()
+    }
+
+    //  Returns a copy of the raw handle.
+    fn raw_handle(&self) -> S::Handle {
+        //  Safety:
+        //  -   The copy is decomposed immediately, and thus never used as a unique handle.
+        let handle = unsafe { ptr::read(&self.handle) };
+
+        let (handle, _) = handle.to_raw_parts();
+
+        handle
+    }
+
+    const fn as_slice(&self) -> NonNull<[T]>
+    where
+        S: ~const StoreSingle,
+    {
+        //  Safety:
+        //  -   `self.handle` is a valid or dangling handle.
+        //  -   `self.handle` was obtained from `self.store` in either case.
+        unsafe { self.handle.resolve_raw(&self.store) }
+    }
+
+    const fn as_mut_slice(&mut self) -> NonNull<[T]>
+    where
+        S: ~const StoreSingle,
+    {
+        //  Safety:
+        //  -   `self.handle` is a valid or dangling handle.
+        //  -   `self.handle` was obtained from `self.store` in either case.
+        unsafe { self.handle.resolve_raw_mut(&mut self.store) }
+    }
+
+    //  #   Safety
+    //
+    //  -   `range.start <= range.end`.
+    //  -   `range.end <= self.capacity()`.
+    const unsafe fn as_sub_slice_unchecked(&self, range: Range<usize>) -> NonNull<[T]>
+    where
+        S: ~const StoreSingle,
+    {
+        debug_assert!(range.start <= range.end);
+        debug_assert!(range.end <= self.handle.len());
+
+        let slice = self.as_slice();
+
+        let pointer = slice.as_mut_ptr();
+
+        //  Safety:
+        //  -   `pointer` is correctly aligned.
+        //  -   `range.start <= slice.len()`.
+        let pointer = unsafe { pointer.add(range.start) };
+
+        //  Safety:
+        //  -   `pointer` is non-null, since it comes from a `NonNull`, and was not decremented.
+        let pointer = unsafe { NonNull::new_unchecked(pointer) };
+
+        NonNull::slice_from_raw_parts(pointer, range.end - range.start)
+    }
+
+    //  #   Safety
+    //
+    //  -   `range.start <= range.end`.
+    //  -   `range.end <= self.capacity()`.
+    const unsafe fn as_mut_sub_slice_unchecked(&mut self, range: Range<usize>) -> NonNull<[T]>
+    where
+        S: ~const StoreSingle,
+    {
+        debug_assert!(range.start <= range.end);
+        debug_assert!(range.end <= self.handle.len());
+
+        let slice = self.as_mut_slice();
+
+        let pointer = slice.as_mut_ptr();
+
+        //  Safety:
+        //  -   `pointer` is correctly aligned.
+        //  -   `range.start <= slice.len()`.
+        let pointer = unsafe { pointer.add(range.start) };
+
+        //  Safety:
+        //  -   `pointer` is non-null, since it comes from a `NonNull`, and was not decremented.
+        let pointer = unsafe { NonNull::new_unchecked(pointer) };
+
+        NonNull::slice_from_raw_parts(pointer, range.end - range.start)
+    }
+}
+
+impl<T, S: StoreSingle> UniqueArray<T, S> {
+    #[cold]
+    #[inline(never)]
+    const fn capacity_exceeded() -> ! {
+        panic!("New capacity exceeds isize::MAX bytes")
+    }
+
+    //  Returns whether the block of memory was relocated, as per `StoreSingle::grow_reporting`.
+    //
+    //  #   Safety
+    //
+    //  -   `target_capacity` must be greater than or equal to `self.capacity()`.
+    //
+    //  #   Panics
+    //
+    //  If the new capacity exceeds `isize::MAX` bytes.
+    const unsafe fn grow_to(&mut self, target_capacity: usize) -> bool
+    where
+        S: ~const StoreSingle + ~const StoreDangling,
+    {
+        const MAX_BYTES: usize = isize::MAX as usize;
+
+        let Some(target_bytes) = target_capacity.checked_mul(mem::size_of::<T>()) else {
+            Self::capacity_exceeded()
+        };
 
         if target_bytes > MAX_BYTES {
             Self::capacity_exceeded()
         }
 
-        if self.handle.is_empty() {
-            self.handle = UniqueSingleHandle::allocate_slice(target_capacity, &mut self.store);
-        } else {
-            //  Safety:
-            //  -   `self.handle` was allocated by `self.store`.
-            //  -   `self.handle` is still valid.
-            //  -   `target_capacity` is greater than or equal to `self.handle.len()`.
-            unsafe { self.handle.grow(target_capacity, &mut self.store) };
+        if self.handle.is_empty() {
+            self.handle = UniqueSingleHandle::allocate_slice(target_capacity, &mut self.store);
+
+            return true;
+        }
+
+        //  Safety:
+        //  -   `self.handle` was allocated by `self.store`.
+        //  -   `self.handle` is still valid.
+        //  -   `target_capacity` is greater than or equal to `self.handle.len()`.
+        let result = unsafe { self.handle.try_grow_reporting(target_capacity, &mut self.store) };
+
+        let Ok(relocated) = result else {
+            let Ok(layout) = Layout::array::<T>(target_capacity) else {
+                Self::capacity_exceeded()
+            };
+
+            alloc::handle_alloc_error(layout)
+        };
+
+        relocated
+    }
+}
+
+impl<T, S: StoreSingle> UniqueArray<T, S> {
+    //  Returns whether the block of memory was relocated, as per `StoreSingle::grow_reporting`.
+    //
+    //  #   Safety
+    //
+    //  -   `target_capacity` must be greater than or equal to `self.capacity()`.
+    const unsafe fn try_grow_to(&mut self, target_capacity: usize) -> Result<bool, AllocError>
+    where
+        S: ~const StoreSingle + ~const StoreDangling,
+    {
+        const MAX_BYTES: usize = isize::MAX as usize;
+
+        let Some(target_bytes) = target_capacity.checked_mul(mem::size_of::<T>()) else {
+            return Err(AllocError);
+        };
+
+        if target_bytes > MAX_BYTES {
+            return Err(AllocError);
+        }
+
+        if self.handle.is_empty() {
+            let Ok(handle) = UniqueSingleHandle::try_allocate_slice(target_capacity, &mut self.store) else {
+                return Err(AllocError);
+            };
+
+            self.handle = handle;
+
+            Ok(true)
+        } else {
+            //  Safety:
+            //  -   `self.handle` was allocated by `self.store`.
+            //  -   `self.handle` is still valid.
+            //  -   `target_capacity` is greater than or equal to `self.handle.len()`.
+            unsafe { self.handle.try_grow_reporting(target_capacity, &mut self.store) }
+        }
+    }
+}
+
+impl<T, S: StoreSingle> Drop for UniqueArray<T, S> {
+    fn drop(&mut self) {
+        if self.handle.is_empty() {
+            return;
+        }
+
+        //  Safety:
+        //  -   `self.handle` is valid.
+        //  -   `self.handle` will not be used after this point.
+        let handle = unsafe { ptr::read(&self.handle) };
+
+        //  Safety:
+        //  -   `handle` is still valid, notably it is not dangling since its length is non-zero.
+        //  -   `handle` was allocated by `self.store`.
+        unsafe { handle.deallocate(&mut self.store) };
+    }
+}
+
+#[cfg(test)]
+mod tests_inline {
+    use crate::store::InlineSingleStore;
+
+    use super::*;
+
+    type InlineVec<T, const N: usize> = StoreVec<T, InlineSingleStore<[T; N]>>;
+
+    #[test]
+    fn const_inline_vec() {
+        const fn fib<const N: usize>() -> InlineVec<i64, N> {
+            let mut v = InlineVec::new_in(InlineSingleStore::new());
+
+            if N > 0 {
+                v.push(0);
+            }
+
+            if N > 1 {
+                v.push(1);
+            }
+
+            let mut n_2 = 0;
+            let mut n_1 = 1;
+
+            while v.len() < N {
+                let n = n_1 + n_2;
+                n_2 = n_1;
+                n_1 = n;
+
+                v.push(n);
+            }
+
+            v
+        }
+
+        static FIB: InlineVec<i64, 10> = fib::<10>();
+
+        assert_eq!(&[0, 1, 1, 2, 3, 5, 8, 13, 21, 34][..], FIB.as_slice());
+    }
+
+    #[test]
+    fn send_sync() {
+        fn require_send<T: Send>() {}
+        fn require_sync<T: Sync>() {}
+
+        require_send::<InlineVec<String, 2>>();
+        require_sync::<InlineVec<String, 2>>();
+    }
+
+    #[test]
+    fn brush() {
+        let mut v = InlineVec::<String, 12>::new();
+
+        assert_eq!(0, v.len());
+        assert_eq!(0, v.capacity());
+        assert_eq!(None, v.pop());
+
+        v.push(String::from("0"));
+
+        assert_eq!(1, v.len());
+        assert_eq!(12, v.capacity());
+
+        v.push(String::from("2"));
+
+        assert_eq!(Some("2"), v.pop().as_deref());
+
+        v.push(String::from("2"));
+        v.push(String::from("2"));
+
+        let s = v.get_mut(1).unwrap();
+        s.clear();
+        s.push('1');
+
+        assert_eq!(["0", "1", "2"], v.as_slice());
+    }
+
+    #[test]
+    fn iter() {
+        let mut v = InlineVec::<String, 4>::new();
+
+        v.push(String::from("0"));
+        v.push(String::from("1"));
+
+        for s in &mut v {
+            s.push('a');
+        }
+
+        let iter = v.iter();
+
+        assert_eq!(2, iter.len());
+        assert_eq!(["0a", "1a"], iter.map(String::as_str).collect::<Vec<_>>().as_slice());
+    }
+
+    #[test]
+    fn into_iter() {
+        let mut v = InlineVec::<String, 4>::new();
+
+        v.push(String::from("0"));
+        v.push(String::from("1"));
+        v.push(String::from("2"));
+
+        let mut iter = v.into_iter();
+
+        assert_eq!(3, iter.len());
+        assert_eq!(Some("2"), iter.next_back().as_deref());
+        assert_eq!(Some("0"), iter.next().as_deref());
+        assert_eq!(1, iter.len());
+
+        //  The remaining element is dropped with the iterator.
+    }
+
+    #[test]
+    fn into_boxed_slice() {
+        let mut v = InlineVec::<String, 4>::new();
+
+        v.push(String::from("0"));
+        v.push(String::from("1"));
+
+        let boxed = v.into_boxed_slice();
+
+        assert_eq!(["0", "1"], &*boxed);
+
+        let mut v = InlineVec::from_boxed_slice(boxed);
+
+        assert_eq!(2, v.len());
+
+        v.push(String::from("2"));
+
+        assert_eq!(["0", "1", "2"], v.as_slice());
+    }
+
+    #[test]
+    fn into_boxed_slice_empty() {
+        let v = InlineVec::<String, 4>::new();
+
+        let boxed = v.into_boxed_slice();
+
+        assert!(boxed.is_empty());
+
+        let v = InlineVec::from_boxed_slice(boxed);
+
+        assert_eq!(0, v.len());
+        assert_eq!(0, v.capacity());
+    }
+
+    #[test]
+    fn into_boxed_slice_zero_sized() {
+        let mut v = InlineVec::<(), 4>::new();
+
+        v.push(());
+        v.push(());
+
+        let boxed = v.into_boxed_slice();
+
+        assert_eq!(2, boxed.len());
+
+        let mut v = InlineVec::from_boxed_slice(boxed);
+
+        v.push(());
+
+        assert_eq!(3, v.len());
+    }
+
+    #[test]
+    fn raw_parts() {
+        let mut v = InlineVec::<String, 4>::new();
+
+        v.push(String::from("0"));
+        v.push(String::from("1"));
+
+        let (handle, length, store) = v.into_raw_parts();
+
+        assert_eq!(2, length);
+        assert_eq!(4, handle.len());
+
+        //  Safety:
+        //  -   `handle`, `length`, and `store` were obtained from `into_raw_parts`.
+        let mut v = unsafe { InlineVec::from_raw_parts(handle, length, store) };
+
+        v.push(String::from("2"));
+
+        assert_eq!(["0", "1", "2"], v.as_slice());
+    }
+
+    #[test]
+    fn raw_parts_empty() {
+        let v = InlineVec::<String, 4>::new();
+
+        let (handle, length, store) = v.into_raw_parts();
+
+        assert_eq!(0, length);
+        assert!(handle.is_empty());
+
+        //  Safety:
+        //  -   `handle`, `length`, and `store` were obtained from `into_raw_parts`.
+        let mut v = unsafe { InlineVec::from_raw_parts(handle, length, store) };
+
+        v.push(String::from("0"));
+
+        assert_eq!(["0"], v.as_slice());
+    }
+
+    #[test]
+    fn slice_methods() {
+        let mut v = InlineVec::<u32, 8>::new();
+
+        [5, 3, 8, 1, 7, 2].into_iter().for_each(|n| v.push(n));
+
+        v.sort_unstable();
+
+        assert_eq!([1, 2, 3, 5, 7, 8], &v[..]);
+        assert_eq!(Ok(3), v.binary_search(&5));
+        assert_eq!(Err(3), v.binary_search(&4));
+        assert_eq!(
+            vec![3, 8, 15],
+            v.chunks_exact(2).map(|c| c[0] + c[1]).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn insert_remove() {
+        let mut v = InlineVec::<u32, 4>::new();
+
+        v.insert(0, 2);
+        v.insert(0, 0);
+        v.insert(1, 1);
+        v.insert(3, 3);
+
+        assert_eq!([0, 1, 2, 3], &v[..]);
+
+        assert_eq!(1, v.remove(1));
+        assert_eq!(3, v.remove(2));
+
+        assert_eq!([0, 2], &v[..]);
+    }
+
+    #[test]
+    fn try_push_insert() {
+        let mut v = InlineVec::<u32, 4>::new();
+
+        v.try_push(1).unwrap();
+        v.try_push(3).unwrap();
+        v.try_insert(0, 0).unwrap();
+        v.try_insert(2, 2).unwrap();
+
+        assert_eq!([0, 1, 2, 3], &v[..]);
+
+        //  The store is full.
+        assert_eq!(Err(4), v.try_push(4));
+        assert_eq!(Err(4), v.try_insert(0, 4));
+
+        assert_eq!([0, 1, 2, 3], &v[..]);
+    }
+
+    #[test]
+    fn collect_extend() {
+        let mut v: InlineVec<u32, 8> = (0..4).collect();
+
+        assert_eq!([0, 1, 2, 3], &v[..]);
+
+        //  No useful size hint, hence growth as needed.
+        v.extend((4..8).filter(|n| n % 2 == 0));
+
+        assert_eq!([0, 1, 2, 3, 4, 6], &v[..]);
+    }
+
+    #[test]
+    fn reorder() {
+        let mut v = InlineVec::<String, 8>::new();
+
+        (0..5).for_each(|n| v.push(n.to_string()));
+
+        v.swap(0, 4);
+
+        assert_eq!(["4", "1", "2", "3", "0"], v.as_slice());
+
+        v.rotate_left(2);
+
+        assert_eq!(["2", "3", "0", "4", "1"], v.as_slice());
+
+        v.rotate_right(1);
+
+        assert_eq!(["1", "2", "3", "0", "4"], v.as_slice());
+
+        v.reverse();
+
+        assert_eq!(["4", "0", "3", "2", "1"], v.as_slice());
+    }
+
+    #[test]
+    fn push_unchecked() {
+        let mut v = InlineVec::<String, 2>::new();
+
+        v.reserve(2);
+
+        //  Safety:
+        //  -   `v.len() < v.capacity()`, after reserving.
+        unsafe { v.push_unchecked(String::from("0")) };
+
+        //  Safety:
+        //  -   `v.len() < v.capacity()`, after reserving.
+        unsafe { v.push_unchecked(String::from("1")) };
+
+        assert_eq!(["0", "1"], v.as_slice());
+
+        //  The element is handed back, rather than lost.
+        assert_eq!(Err(String::from("2")), v.try_push(String::from("2")));
+    }
+
+    #[test]
+    fn sorted_by_key() {
+        let mut map = InlineVec::<(u8, u32), 4>::new();
+
+        assert_eq!(None, map.insert_sorted_by_key((2, 20), |e| e.0));
+        assert_eq!(None, map.insert_sorted_by_key((1, 10), |e| e.0));
+        assert_eq!(Some((2, 20)), map.insert_sorted_by_key((2, 22), |e| e.0));
+
+        map.get_sorted_by_key_mut(&1, |e| e.0).unwrap().1 = 11;
+
+        assert_eq!([(1, 11), (2, 22)], &map[..]);
+        assert_eq!(None, map.get_sorted_by_key(&3, |e| e.0));
+        assert_eq!(None, map.remove_sorted_by_key(&3, |e| e.0));
+        assert_eq!(Some((1, 11)), map.remove_sorted_by_key(&1, |e| e.0));
+    }
+
+    #[test]
+    fn memory_usage() {
+        let mut v = InlineVec::<u32, 8>::new();
+
+        v.extend([1, 2, 3]);
+
+        let usage = v.memory_usage();
+
+        assert_eq!(
+            (32, 12, 1),
+            (usage.bytes_allocated, usage.bytes_used, usage.allocations)
+        );
+        assert_eq!(20, usage.overhead());
+
+        let zst = InlineVec::<(), 8>::new();
+
+        assert_eq!(MemoryUsage::default(), zst.memory_usage());
+    }
+
+    #[test]
+    fn comparison() {
+        use std::hash::{BuildHasher, RandomState};
+
+        let mut v = InlineVec::<u32, 8>::new();
+        let mut w = StoreVec::<u32, InlineSingleStore<[u32; 4]>>::new();
+
+        v.extend([1, 2, 3]);
+        w.extend([1, 2, 4]);
+
+        assert_eq!("[1, 2, 3]", format!("{v:?}"));
+
+        assert_ne!(v, w);
+        assert!(v < w);
+        assert_eq!(v, [1, 2, 3]);
+        assert_eq!(v, &[1, 2, 3][..]);
+        assert_eq!([1, 2, 3], v);
+        assert_eq!(&[1, 2, 3][..], v);
+
+        w.pop();
+        w.push(3);
+
+        assert_eq!(v, w);
+
+        let state = RandomState::new();
+
+        assert_eq!(state.hash_one(vec![1u32, 2, 3]), state.hash_one(&v));
+    }
+
+    #[test]
+    fn fmt_write() {
+        use core::fmt::Write;
+
+        let mut v = InlineVec::<u8, 8>::new();
+
+        write!(v, "{}-{}", 1, 2).unwrap();
+
+        assert_eq!(b"1-2", v.as_slice());
+
+        assert!(write!(v, "{}", "overflow").is_err());
+        assert_eq!(b"1-2", v.as_slice());
+    }
+
+    #[test]
+    fn try_write_fmt() {
+        let mut v = InlineVec::<u8, 8>::new();
+
+        v.try_push_str("0").unwrap();
+        v.try_write_fmt(format_args!("{}{}", 1, 2)).unwrap();
+
+        assert_eq!(b"012", v.as_slice());
+
+        //  The first argument fits, the second does not: both are rolled back.
+        assert_eq!(Err(AllocError), v.try_write_fmt(format_args!("{}{}", 34, 5678)));
+        assert_eq!(b"012", v.as_slice());
+    }
+
+    #[test]
+    fn extend_from_fn() {
+        let mut v = InlineVec::<u32, 8>::new();
+
+        v.push(7);
+        v.extend_from_fn(4, |i| i as u32 * 10);
+
+        assert_eq!([7, 0, 10, 20, 30], v.as_slice());
+
+        assert_eq!(Err(AllocError), v.try_extend_from_fn(4, |_| unreachable!()));
+        assert_eq!(Ok(()), v.try_extend_from_fn(3, |i| i as u32));
+
+        assert_eq!([7, 0, 10, 20, 30, 0, 1, 2], v.as_slice());
+    }
+
+    #[test]
+    fn extend_from_fn_panic() {
+        use std::panic::{self, AssertUnwindSafe};
+
+        let mut v = InlineVec::<String, 8>::new();
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            v.extend_from_fn(4, |i| {
+                assert!(i < 2, "Decoding failed");
+
+                i.to_string()
+            })
+        }));
+
+        assert!(result.is_err());
+
+        //  The elements produced prior to the panic are kept.
+        assert_eq!(["0", "1"], v.as_slice());
+    }
+
+    #[test]
+    fn fill_spare() {
+        let mut v = InlineVec::<String, 4>::new();
+
+        v.push(String::from("0"));
+
+        assert_eq!(3, v.fill_spare(String::from("frame")));
+        assert_eq!(["0", "frame", "frame", "frame"], v.as_slice());
+
+        assert_eq!(0, v.fill_spare(String::from("dropped")));
+        assert_eq!(4, v.len());
+
+        let mut frame = InlineVec::<u8, 16>::with_capacity(16);
+
+        assert_eq!(16, frame.fill_spare(0));
+
+        frame[..4].copy_from_slice(b"abcd");
+
+        assert_eq!(b"abcd\0\0", &frame[..6]);
+    }
+
+    #[test]
+    fn split_off() {
+        let mut v = InlineVec::<String, 8>::new();
+
+        v.extend(["0", "1", "2", "3", "4"].map(String::from));
+
+        let w = v.split_off(3);
+
+        assert_eq!(["0", "1", "2"], v.as_slice());
+        assert_eq!(["3", "4"], w.as_slice());
+        assert_eq!(8, v.capacity());
+
+        let w = v.split_off(3);
+
+        assert!(w.is_empty());
+        assert_eq!(3, v.len());
+
+        let w = v.split_off_in(1, InlineSingleStore::<[String; 2]>::new());
+
+        assert_eq!(["0"], v.as_slice());
+        assert_eq!(["1", "2"], w.as_slice());
+    }
+
+    #[test]
+    fn try_split_off_in() {
+        let mut v = InlineVec::<u32, 8>::new();
+
+        v.extend([0, 1, 2, 3, 4]);
+
+        let result = v.try_split_off_in(1, InlineSingleStore::<[u32; 2]>::new());
+
+        assert_eq!(Err(AllocError), result.map(|_| ()));
+        assert_eq!([0, 1, 2, 3, 4], v.as_slice());
+
+        let w = v.try_split_off_in(2, InlineSingleStore::<[u32; 4]>::new()).unwrap();
+
+        assert_eq!([0, 1], v.as_slice());
+        assert_eq!([2, 3, 4], w.as_slice());
+    }
+
+    #[test]
+    fn split_at_spare_mut() {
+        let mut v = InlineVec::<u8, 8>::with_capacity(8);
+
+        v.extend(*b"ab");
+
+        let (elements, spare) = v.split_at_spare_mut();
+
+        assert_eq!(b"ab", elements);
+        assert_eq!(6, spare.len());
+
+        //  Copy the elements into the spare capacity, as a chunking workflow would.
+        for (slot, element) in spare.iter_mut().zip(elements.iter()) {
+            slot.write(element.to_ascii_uppercase());
+        }
+
+        //  Safety:
+        //  -   `4 <= v.capacity()`.
+        //  -   Slots in `0..4` are initialized.
+        unsafe { v.set_len(4) };
+
+        assert_eq!(b"abAB", v.as_slice());
+
+        let mut empty = InlineVec::<u8, 8>::new();
+
+        let (elements, spare) = empty.split_at_spare_mut();
+
+        assert!(elements.is_empty() && spare.is_empty());
+    }
+} // mod tests_inline
+
+#[cfg(test)]
+mod tests_stack {
+    use crate::{
+        interface::Store,
+        store::{RefStore, StackBumpBlock, StackBumpStore},
+    };
+
+    use super::*;
+
+    type StackVec<'a, T> = StoreVec<T, StackBumpStore<'a, usize>>;
+
+    #[test]
+    fn brush() {
+        let block = StackBumpBlock::<[String; 12]>::new();
+
+        let mut v = StackVec::<'_, String>::new_in(block.create_store());
+
+        assert_eq!(0, v.len());
+        assert_eq!(0, v.capacity());
+        assert_eq!(None, v.pop());
+
+        v.push(String::from("0"));
+
+        assert_eq!(1, v.len());
+        assert_eq!(1, v.capacity());
+
+        v.push(String::from("2"));
+
+        assert_eq!(Some("2"), v.pop().as_deref());
+
+        v.push(String::from("2"));
+        v.push(String::from("2"));
+
+        let s = v.get_mut(1).unwrap();
+        s.clear();
+        s.push('1');
+
+        assert_eq!(["0", "1", "2"], v.as_slice());
+    }
+
+    #[test]
+    fn into_boxed_slice() {
+        let block = StackBumpBlock::<[String; 12]>::new();
+
+        let mut v = StackVec::<'_, String>::with_capacity_in(4, block.create_store());
+
+        v.push(String::from("0"));
+        v.push(String::from("1"));
+
+        let boxed = v.into_boxed_slice();
+
+        assert_eq!(["0", "1"], &*boxed);
+
+        let v = StackVec::from_boxed_slice(boxed);
+
+        assert_eq!(2, v.len());
+        assert_eq!(2, v.capacity());
+        assert_eq!(["0", "1"], v.as_slice());
+    }
+
+    #[test]
+    fn leak() {
+        let block = StackBumpBlock::<[u32; 4]>::new();
+
+        let mut v = StoreVec::<u32, StackBumpStore<'_, usize>>::with_capacity_in(4, block.create_store());
+
+        v.push(1);
+        v.push(2);
+
+        let leaked = v.leak();
+
+        leaked[0] = 3;
+
+        assert_eq!([3, 2], leaked);
+    }
+
+    #[test]
+    fn amortized_growth() {
+        let block = StackBumpBlock::<[u32; 16]>::new();
+        let store = block.create_store::<usize>();
+
+        let mut v = StoreVec::new_in(RefStore::new(&store));
+
+        for i in 0..5 {
+            v.push(i);
+        }
+
+        //  The memory of a bump store is never reclaimed, hence growth is geometric, so as to bound the number of
+        //  relocations, and of blocks left behind, when the vector is not the last allocation.
+        assert_eq!(6, v.capacity());
+        assert_eq!(6 * mem::size_of::<u32>(), store.used());
+
+        let mut w = StoreVec::new_in(RefStore::new(&store));
+
+        for i in 5..8 {
+            w.push(i);
         }
+
+        assert_eq!([0, 1, 2, 3, 4], v.as_slice());
+        assert_eq!([5, 6, 7], w.as_slice());
     }
-}
 
-impl<T, S: StoreSingle> Drop for UniqueArray<T, S> {
-    fn drop(&mut self) {
-        if self.handle.is_empty() {
-            return;
-        }
+    #[test]
+    fn extend_grown_in_place_or_relocated() {
+        let block = StackBumpBlock::<[u32; 64]>::new();
+        let store = block.create_store::<usize>();
 
-        //  Safety:
-        //  -   `self.handle` is valid.
-        //  -   `self.handle` will not be used after this point.
-        let handle = unsafe { ptr::read(&self.handle) };
+        let mut v = StoreVec::new_in(RefStore::new(&store));
 
-        //  Safety:
-        //  -   `handle` is still valid, notably it is not dangling since its length is non-zero.
-        //  -   `handle` was allocated by `self.store`.
-        unsafe { handle.deallocate(&mut self.store) };
+        //  Every third element allocates in the same store, hence the next growth relocates the vector, whereas all
+        //  others grow it in place.
+        let elements = (0..12u32).filter(|_| true).inspect(|n| {
+            if n % 3 == 2 {
+                Store::allocate(&store, Layout::new::<u32>()).unwrap();
+            }
+        });
+
+        v.extend(elements);
+
+        assert_eq!([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11], v.as_slice());
     }
-}
+
+    #[test]
+    fn extend_exact_size() {
+        type Element<'a> = StoreBox<u32, RefStore<'a, StackBumpStore<'a, usize>>>;
+
+        const EXACT: usize = 4 * mem::size_of::<Element<'_>>() + 4 * mem::size_of::<u32>();
+
+        //  Each element is allocated in the same store while iterating, hence growing the vector relocates it, leaving
+        //  its previous block of memory behind.
+        #[cfg(not(feature = "coercible-metadata"))]
+        let naive = StackBumpBlock::<[u64; 32]>::new();
+
+        //  The handles of `coercible-metadata` embed a pointer, making the elements larger.
+        #[cfg(feature = "coercible-metadata")]
+        let naive = StackBumpBlock::<[u64; 4 * EXACT / 8]>::new();
+
+        let store = naive.create_store::<usize>();
+
+        let mut v = StoreVec::new_in(RefStore::new(&store));
+        let boxes = (0..4).map(|n| StoreBox::new_in(n, RefStore::new(&store)));
+
+        //  Hide the size hint.
+        v.extend(boxes.filter(|_| true));
+
+        assert!(store.used() > EXACT);
+
+        //  With an exact size hint, the vector is allocated once, upfront, and the block fits exactly.
+        let block = StackBumpBlock::<[u64; EXACT / 8]>::new();
+        let store = block.create_store::<usize>();
+
+        let mut v = StoreVec::new_in(RefStore::new(&store));
+        v.extend((0..4).map(|n| StoreBox::new_in(n, RefStore::new(&store))));
+
+        assert_eq!(EXACT, store.used());
+        assert_eq!(0, store.remaining());
+        assert_eq!([0, 1, 2, 3], v.iter().map(|boxed| **boxed).collect::<Vec<_>>()[..]);
+    }
+
+    #[test]
+    fn split_off_shared() {
+        let block = StackBumpBlock::<[String; 12]>::new();
+
+        let mut v = StackVec::<'_, String>::new_in(block.create_store());
+
+        v.extend(["0", "1", "2", "3"].map(String::from));
+
+        let mut w = v.split_off_shared(1);
+
+        assert_eq!(["0"], v.as_slice());
+        assert_eq!(["1", "2", "3"], w.as_slice());
+
+        let x = w.split_off_shared(2);
+
+        assert_eq!(["1", "2"], w.as_slice());
+        assert_eq!(["3"], x.as_slice());
+
+        //  All vectors allocate from the same block, each with the capacity for its original elements.
+        assert_eq!((4 + 3 + 1) * mem::size_of::<String>(), x.array.store.used());
+        assert_eq!(v.array.store.sharing_token(), x.array.store.sharing_token());
+    }
+} // mod tests_stack
 
 #[cfg(test)]
-mod tests_inline {
-    use crate::store::InlineSingleStore;
+mod tests_persist {
+    use core::{
+        alloc::Layout,
+        cell::Cell,
+        ptr::{Alignment, NonNull},
+    };
+
+    use crate::{
+        interface::{Growth, PersistError, StorePersist},
+        store::{InlineSingleStore, SmallStore},
+    };
 
     use super::*;
 
-    type InlineVec<T, const N: usize> = StoreVec<T, InlineSingleStore<[T; N]>>;
+    //  A store recording the number of bytes flushed, and the number of snapshots, failing snapshots on demand.
+    #[derive(Default)]
+    struct LogStore {
+        store: InlineSingleStore<[u32; 4]>,
+        flushed: Cell<usize>,
+        snapshots: Cell<usize>,
+        failing: Cell<bool>,
+    }
 
-    #[test]
-    fn const_inline_vec() {
-        const fn fib<const N: usize>() -> InlineVec<i64, N> {
-            let mut v = InlineVec::new_in(InlineSingleStore::new());
+    unsafe impl StoreDangling for LogStore {
+        type Handle = ();
 
-            if N > 0 {
-                v.push(0);
-            }
+        fn dangling(&self, alignment: Alignment) -> Result<(), AllocError> {
+            self.store.dangling(alignment)
+        }
+    }
 
-            if N > 1 {
-                v.push(1);
-            }
+    unsafe impl StoreSingle for LogStore {
+        const GROWTH: Growth = Growth::Exact;
 
-            let mut n_2 = 0;
-            let mut n_1 = 1;
+        unsafe fn resolve(&self, handle: ()) -> NonNull<u8> {
+            //  Safety:
+            //  -   As per pre-conditions.
+            unsafe { self.store.resolve(handle) }
+        }
 
-            while v.len() < N {
-                let n = n_1 + n_2;
-                n_2 = n_1;
-                n_1 = n;
+        unsafe fn resolve_mut(&mut self, handle: ()) -> NonNull<u8> {
+            //  Safety:
+            //  -   As per pre-conditions.
+            unsafe { self.store.resolve_mut(handle) }
+        }
 
-                v.push(n);
+        fn allocate(&mut self, layout: Layout) -> Result<((), usize), AllocError> {
+            self.store.allocate(layout)
+        }
+
+        unsafe fn deallocate(&mut self, handle: (), layout: Layout) {
+            //  Safety:
+            //  -   As per pre-conditions.
+            unsafe { self.store.deallocate(handle, layout) }
+        }
+
+        unsafe fn grow(&mut self, handle: (), old: Layout, new: Layout) -> Result<((), usize), AllocError> {
+            //  Safety:
+            //  -   As per pre-conditions.
+            unsafe { self.store.grow(handle, old, new) }
+        }
+
+        unsafe fn shrink(&mut self, handle: (), old: Layout, new: Layout) -> Result<((), usize), AllocError> {
+            //  Safety:
+            //  -   As per pre-conditions.
+            unsafe { self.store.shrink(handle, old, new) }
+        }
+    }
+
+    impl StorePersist for LogStore {
+        unsafe fn flush(&self, _handle: (), layout: Layout) {
+            self.flushed.set(layout.size());
+        }
+
+        fn snapshot(&self) -> Result<(), PersistError> {
+            if self.failing.get() {
+                return Err(PersistError);
             }
 
-            v
+            self.snapshots.set(self.snapshots.get() + 1);
+
+            Ok(())
         }
+    }
 
-        static FIB: InlineVec<i64, 10> = fib::<10>();
+    #[test]
+    fn push_persistent() {
+        let mut v = StoreVec::<u32, LogStore>::new();
 
-        assert_eq!(&[0, 1, 1, 2, 3, 5, 8, 13, 21, 34][..], FIB.as_slice());
+        assert_eq!(Ok(()), v.persist());
+        assert_eq!((0, 1), (v.array.store.flushed.get(), v.array.store.snapshots.get()));
+
+        v.push_persistent(1).unwrap();
+        v.push_persistent(2).unwrap();
+
+        assert_eq!((8, 3), (v.array.store.flushed.get(), v.array.store.snapshots.get()));
+
+        v.array.store.failing.set(true);
+
+        assert_eq!(Err(PersistError), v.push_persistent(3));
+        assert_eq!([1, 2, 3], &v[..]);
+        assert_eq!(12, v.array.store.flushed.get());
     }
 
     #[test]
-    fn send_sync() {
-        fn require_send<T: Send>() {}
-        fn require_sync<T: Sync>() {}
+    fn spilled() {
+        let mut v = StoreVec::<u32, SmallStore<[u32; 1], LogStore>>::new();
+
+        //  Only the spilled block of memory is flushed, though every push takes a snapshot.
+        v.push_persistent(1).unwrap();
+
+        assert_eq!(
+            (0, 1),
+            (
+                v.array.store.spill().flushed.get(),
+                v.array.store.spill().snapshots.get()
+            )
+        );
+
+        v.push_persistent(2).unwrap();
+
+        assert_eq!(
+            (8, 2),
+            (
+                v.array.store.spill().flushed.get(),
+                v.array.store.spill().snapshots.get()
+            )
+        );
+        assert_eq!([1, 2], &v[..]);
+    }
 
-        require_send::<InlineVec<String, 2>>();
-        require_sync::<InlineVec<String, 2>>();
+    #[test]
+    fn volatile() {
+        let mut v = StoreVec::<u32, InlineSingleStore<[u32; 4]>>::new();
+
+        v.push_persistent(1).unwrap();
+        v.extend([2, 3]);
+
+        assert_eq!(Ok(()), v.persist());
+        assert_eq!([1, 2, 3], &v[..]);
     }
+} // mod tests_persist
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests_allocator {
+    use std::alloc::System;
+
+    use super::*;
+
+    type SystemVec<T> = StoreVec<T, System>;
 
     #[test]
-    fn brush() {
-        let mut v = InlineVec::<String, 12>::new();
+    fn from_vec() {
+        let mut v = Vec::with_capacity_in(4, System);
+
+        v.push(String::from("0"));
+        v.push(String::from("1"));
+
+        let mut v = SystemVec::from(v);
+
+        assert_eq!(2, v.len());
+        assert!(v.capacity() >= 4);
+
+        v.push(String::from("2"));
+
+        assert_eq!(["0", "1", "2"], v.as_slice());
+    }
+
+    #[test]
+    fn from_vec_empty() {
+        let mut v = SystemVec::from(Vec::new_in(System));
 
         assert_eq!(0, v.len());
         assert_eq!(0, v.capacity());
-        assert_eq!(None, v.pop());
 
         v.push(String::from("0"));
 
-        assert_eq!(1, v.len());
-        assert_eq!(12, v.capacity());
+        assert_eq!(["0"], v.as_slice());
+    }
 
-        v.push(String::from("2"));
+    #[test]
+    fn into_vec() {
+        let mut v = SystemVec::new_in(System);
 
-        assert_eq!(Some("2"), v.pop().as_deref());
+        v.push(String::from("0"));
+        v.push(String::from("1"));
+
+        let mut v = Vec::from(v);
 
-        v.push(String::from("2"));
         v.push(String::from("2"));
 
-        let s = v.get_mut(1).unwrap();
-        s.clear();
-        s.push('1');
+        assert_eq!(["0", "1", "2"], &v[..]);
+    }
 
-        assert_eq!(["0", "1", "2"], v.as_slice());
+    #[test]
+    fn from_vec_zero_sized() {
+        let v = SystemVec::from(Vec::<(), _>::new_in(System));
+
+        assert_eq!(0, v.len());
+        assert_eq!(0, v.capacity());
+
+        let mut v = Vec::new_in(System);
+
+        v.extend([(); 3]);
+
+        let mut v = SystemVec::from(v);
+
+        assert_eq!(3, v.len());
+        assert!(v.capacity() >= 3);
+
+        v.push(());
+
+        let v = Vec::from(v);
+
+        assert_eq!(4, v.len());
     }
-} // mod tests_inline
 
-#[cfg(test)]
-mod tests_stack {
-    use crate::store::{StackBumpBlock, StackBumpStore};
+    #[test]
+    fn into_vec_zero_sized() {
+        let mut v = SystemVec::new_in(System);
+
+        v.push(());
+        v.push(());
+
+        let mut v = Vec::from(v);
+
+        assert_eq!(2, v.len());
+
+        v.push(());
+
+        let v = SystemVec::from(v);
+
+        assert_eq!(3, v.len());
+        assert!(v.capacity() >= 3);
+    }
+} // mod tests_allocator
+
+#[cfg(all(test, feature = "std"))]
+mod tests_std {
+    use std::io::{Cursor, Read, Write};
+
+    use crate::store::InlineSingleStore;
 
     use super::*;
 
-    type StackVec<'a, T> = StoreVec<T, StackBumpStore<'a, usize>>;
+    type InlineVec<T, const N: usize> = StoreVec<T, InlineSingleStore<[T; N]>>;
 
     #[test]
-    fn brush() {
-        let block = StackBumpBlock::<[String; 12]>::new();
+    fn write() {
+        let mut v = InlineVec::<u8, 16>::new();
 
-        let mut v = StackVec::<'_, String>::new_in(block.create_store());
+        let name = "World";
 
-        assert_eq!(0, v.len());
-        assert_eq!(0, v.capacity());
-        assert_eq!(None, v.pop());
+        write!(v, "Hello, {name}!").unwrap();
 
-        v.push(String::from("0"));
+        assert_eq!(b"Hello, World!", v.as_slice());
+    }
 
-        assert_eq!(1, v.len());
-        assert_eq!(1, v.capacity());
+    #[test]
+    fn write_all_overflow() {
+        let mut v = InlineVec::<u8, 4>::new();
 
-        v.push(String::from("2"));
+        v.write_all(b"012").unwrap();
 
-        assert_eq!(Some("2"), v.pop().as_deref());
+        let error = v.write_all(b"34").unwrap_err();
 
-        v.push(String::from("2"));
-        v.push(String::from("2"));
+        assert_eq!(std::io::ErrorKind::WriteZero, error.kind());
+        assert_eq!(b"0123", v.as_slice());
+    }
 
-        let s = v.get_mut(1).unwrap();
-        s.clear();
-        s.push('1');
+    #[test]
+    fn read() {
+        let mut v = InlineVec::<u8, 8>::new();
 
-        assert_eq!(["0", "1", "2"], v.as_slice());
+        v.write_all(b"0123").unwrap();
+
+        let mut cursor = Cursor::new(&v);
+        let mut buffer = [0u8; 3];
+
+        assert_eq!(3, cursor.read(&mut buffer).unwrap());
+        assert_eq!(b"012", &buffer);
+
+        assert_eq!(1, cursor.read(&mut buffer).unwrap());
+        assert_eq!(b'3', buffer[0]);
     }
-} // mod tests_stack
+} // mod tests_std
+
+//  Exercises the raw access paths of the vector: mutable references to disjoint elements are used out of order, and
+//  shared references to the elements are kept alive whilst the vector is read through other paths.
+//
+//  Run with `RUSTFLAGS="--cfg storage_miri_strict" MIRIFLAGS="-Zmiri-tree-borrows" cargo miri test tests_miri`, and
+//  without `-Zmiri-tree-borrows` for Stacked Borrows.
+#[cfg(all(test, storage_miri_strict))]
+mod tests_miri {
+    use crate::store::InlineBumpStore;
+
+    use super::*;
+
+    type TestVec = StoreVec<String, InlineBumpStore<u16, [u64; 32]>>;
+
+    #[test]
+    fn iter_mut_both_ends() {
+        let mut vec = TestVec::new();
+
+        vec.extend((0..4).map(|i| i.to_string()));
+
+        let mut iter = vec.iter_mut();
+
+        let (first, last) = (iter.next().unwrap(), iter.next_back().unwrap());
+        let (second, third) = (iter.next().unwrap(), iter.next_back().unwrap());
+
+        for element in [third, first, last, second] {
+            element.push('a');
+        }
+
+        assert_eq!(["0a", "1a", "2a", "3a"], vec.as_slice());
+    }
+
+    #[test]
+    fn shared_across_reads() {
+        let mut vec = TestVec::new();
+
+        vec.extend((0..4).map(|i| i.to_string()));
+
+        let (first, slice) = (&vec[0], vec.as_slice());
+
+        let clone = vec.iter().cloned().collect::<TestVec>();
+
+        assert_eq!(vec.as_ptr(), slice.as_ptr());
+        assert_eq!(Some(&vec[2]), vec.get_sorted_by_key(&2, |s| s.parse::<i32>().unwrap()));
+
+        assert_eq!("0", first);
+        assert_eq!(clone.as_slice(), slice);
+    }
+} // mod tests_miri
@@ -0,0 +1,375 @@
+//! Zero-copy views of the elements of collections persisted within a region of memory.
+//!
+//! The elements of a `StoreVec`, or the bytes of a `StoreString`, whose store is a `StoreRegion` live at a fixed offset
+//! within the region. `StoreVec::persisted` and `StoreString::persisted` capture this offset, and the length, in a
+//! `PersistedSlice`, which is itself `Pod` and may thus be stored within the region too, for example as the root of a
+//! `SelfHostedStore`.
+//!
+//! Once the region is persisted, and loaded again -- possibly at a different address, possibly as a mere `&[u8]` --
+//! the elements may be viewed in place, without copying, after validating that they lie within the region and are
+//! suitably aligned:
+//!
+//! ```
+//! #![feature(allocator_api)]
+//! #![feature(unwrap_infallible)]
+//!
+//! use core::{mem, ptr::NonNull, slice};
+//!
+//! use storage::{
+//!     collection::{PersistedSlice, StoreVec},
+//!     interface::StoreSharing,
+//!     store::SelfHostedStore,
+//! };
+//!
+//! let mut block = [0u64; 32];
+//! let region = NonNull::slice_from_raw_parts(NonNull::from(&mut block).cast(), mem::size_of_val(&block));
+//!
+//! //  Safety:
+//! //  -   `region` is only accessed through this store, and outlives it.
+//! let store = unsafe { SelfHostedStore::<u32>::format(region) }.unwrap();
+//!
+//! let mut vec = StoreVec::new_in(store.share().into_ok());
+//! vec.extend([1u32, 2, 3]);
+//!
+//! let persisted = vec.persisted();
+//!
+//! drop((vec, store));
+//!
+//! //  Persist the region, and load it again, elsewhere.
+//! let loaded = block;
+//!
+//! //  Safety:
+//! //  -   `loaded` is an initialized array of `u64`, and thus of bytes.
+//! let bytes = unsafe { slice::from_raw_parts(loaded.as_ptr().cast::<u8>(), mem::size_of_val(&loaded)) };
+//!
+//! assert_eq!(Ok(&[1u32, 2, 3][..]), persisted.view::<u32>(bytes));
+//! ```
+//!
+//! Only `Pod` elements may be viewed, as the bytes of a persisted region may hold any bit pattern, while strings are
+//! validated as UTF-8 by `PersistedSlice::view_str`.
+
+use core::{fmt, mem, ptr::NonNull, slice, str};
+
+use crate::interface::StoreRegion;
+
+/// A "Plain Old Data" type, valid for any bit pattern, and thus viewable in place from arbitrary bytes.
+///
+/// Such a type should not hold pointers, nor references, as the address of a persisted region changes when it is loaded
+/// again; handles of offset stores, however, remain meaningful.
+///
+/// #   Safety
+///
+/// Implementers must guarantee that:
+///
+/// -   Any initialized bit pattern of `mem::size_of::<Self>()` bytes is a valid value of `Self`.
+/// -   `Self` has no padding bytes, so that the bytes of any value are initialized.
+pub unsafe trait Pod: Copy + 'static {}
+
+macro_rules! pod_impls {
+    ($($ty:ty),*) => {
+        $(
+            //  Safety:
+            //  -   Any bit pattern is valid for a primitive integer, or floating point, type.
+            //  -   Primitive integer, and floating point, types have no padding bytes.
+            unsafe impl Pod for $ty {}
+        )*
+    };
+}
+
+pod_impls!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+//  Safety:
+//  -   Any bit pattern is valid for an array of `T`, as it is for `T`.
+//  -   An array has no padding bytes besides those of its elements, and `T` has none.
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+
+/// The reasons for which a `PersistedSlice` may not be viewed within a region of memory.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ViewError {
+    /// The elements do not lie entirely within the region.
+    OutOfBounds,
+    /// The elements are insufficiently aligned, as the region is loaded at an insufficiently aligned address.
+    Misaligned,
+    /// The bytes are not valid UTF-8.
+    InvalidUtf8,
+}
+
+impl fmt::Display for ViewError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            Self::OutOfBounds => f.write_str("elements out of the bounds of the region"),
+            Self::Misaligned => f.write_str("elements insufficiently aligned"),
+            Self::InvalidUtf8 => f.write_str("bytes not valid UTF-8"),
+        }
+    }
+}
+
+/// The location of a slice of elements within a region of memory: the offset of its first element from the start of
+/// the region, in bytes, and its number of elements.
+///
+/// The descriptor is `#[repr(C)]`, and its fields are `u64` whatever the target, so that its layout is that of the
+/// following C struct:
+///
+/// ```c
+/// struct PersistedSlice {
+///     uint64_t offset;
+///     uint64_t length;
+/// };
+/// ```
+///
+/// The fields are written in native endianness.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct PersistedSlice {
+    offset: u64,
+    length: u64,
+}
+
+//  Safety:
+//  -   Any bit pattern is valid for a pair of `u64`.
+//  -   A `#[repr(C)]` pair of `u64` has no padding bytes.
+unsafe impl Pod for PersistedSlice {}
+
+impl PersistedSlice {
+    /// Creates a descriptor of `length` elements, starting `offset` bytes from the start of the region.
+    pub const fn new(offset: u64, length: u64) -> Self {
+        Self { offset, length }
+    }
+
+    /// Returns the offset of the first element from the start of the region, in bytes.
+    pub const fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Returns the number of elements.
+    pub const fn length(&self) -> u64 {
+        self.length
+    }
+
+    /// Views the elements within `region`, without copying them.
+    ///
+    /// #   Errors
+    ///
+    /// Returns `ViewError::OutOfBounds` if the elements do not lie entirely within `region`, and
+    /// `ViewError::Misaligned` if they are insufficiently aligned for `T`.
+    pub fn view<'a, T: Pod>(&self, region: &'a [u8]) -> Result<&'a [T], ViewError> {
+        let pointer = self.locate::<T>(NonNull::from(region))?;
+
+        //  Safety:
+        //  -   `pointer` is suitably aligned, and valid for reads of `self.length` elements, as they lie within
+        //      `region`, as per `locate`.
+        //  -   The elements are initialized, as `region` is, and valid, as `T` is `Pod`.
+        //  -   `region` is borrowed immutably for `'a`.
+        Ok(unsafe { slice::from_raw_parts(pointer.as_ptr(), self.length as usize) })
+    }
+
+    /// Views the bytes within `region` as a string, without copying them.
+    ///
+    /// #   Errors
+    ///
+    /// Returns `ViewError::OutOfBounds` if the bytes do not lie entirely within `region`, and `ViewError::InvalidUtf8`
+    /// if they are not valid UTF-8.
+    pub fn view_str<'a>(&self, region: &'a [u8]) -> Result<&'a str, ViewError> {
+        let bytes = self.view::<u8>(region)?;
+
+        str::from_utf8(bytes).map_err(|_| ViewError::InvalidUtf8)
+    }
+
+    /// Views the elements within the region of `store`, as per `StoreRegion::region`, without copying them.
+    ///
+    /// #   Errors
+    ///
+    /// Returns `ViewError::OutOfBounds` if the elements do not lie entirely within the region, and
+    /// `ViewError::Misaligned` if they are insufficiently aligned for `T`.
+    ///
+    /// #   Safety
+    ///
+    /// -   The bytes of the elements must be initialized, as is the case if they were written by the collection this
+    ///     descriptor was taken from, or if the region was loaded from a persisted medium.
+    /// -   The bytes of the elements must not be written to for the lifetime `'a`.
+    pub unsafe fn view_in<'a, T: Pod, S: StoreRegion>(&self, store: &'a S) -> Result<&'a [T], ViewError> {
+        let pointer = self.locate::<T>(store.region())?;
+
+        //  Safety:
+        //  -   `pointer` is suitably aligned, and valid for reads of `self.length` elements, as they lie within the
+        //      region of `store`, which remains valid as long as `store` is, as per `locate`.
+        //  -   The elements are initialized, as per pre-conditions, and valid, as `T` is `Pod`.
+        //  -   The elements are not written to for `'a`, as per pre-conditions.
+        Ok(unsafe { slice::from_raw_parts(pointer.as_ptr(), self.length as usize) })
+    }
+
+    /// Views the bytes within the region of `store`, as per `StoreRegion::region`, as a string, without copying them.
+    ///
+    /// #   Errors
+    ///
+    /// Returns `ViewError::OutOfBounds` if the bytes do not lie entirely within the region, and
+    /// `ViewError::InvalidUtf8` if they are not valid UTF-8.
+    ///
+    /// #   Safety
+    ///
+    /// -   The bytes must be initialized, as is the case if they were written by the string this descriptor was taken
+    ///     from, or if the region was loaded from a persisted medium.
+    /// -   The bytes must not be written to for the lifetime `'a`.
+    pub unsafe fn view_str_in<'a, S: StoreRegion>(&self, store: &'a S) -> Result<&'a str, ViewError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        let bytes = unsafe { self.view_in::<u8, S>(store)? };
+
+        str::from_utf8(bytes).map_err(|_| ViewError::InvalidUtf8)
+    }
+}
+
+//
+//  Implementation
+//
+
+impl PersistedSlice {
+    //  Returns a pointer to the first element within `region`, after checking that the elements lie entirely within
+    //  `region`, and that the pointer is suitably aligned for `T`.
+    //
+    //  On success, `self.length` is guaranteed to fit within `usize`.
+    fn locate<T>(&self, region: NonNull<[u8]>) -> Result<NonNull<T>, ViewError> {
+        let base = region.cast::<u8>();
+
+        let offset = usize::try_from(self.offset).map_err(|_| ViewError::OutOfBounds)?;
+        let length = usize::try_from(self.length).map_err(|_| ViewError::OutOfBounds)?;
+
+        //  An empty slice need not lie within the region, nor be aligned.
+        if length == 0 {
+            return Ok(NonNull::dangling());
+        }
+
+        let bytes = length.checked_mul(mem::size_of::<T>()).ok_or(ViewError::OutOfBounds)?;
+        let end = offset.checked_add(bytes).ok_or(ViewError::OutOfBounds)?;
+
+        if end > region.len() {
+            return Err(ViewError::OutOfBounds);
+        }
+
+        //  Safety:
+        //  -   `offset` is within the bounds of `region`, or one past its end, as `offset <= end <= region.len()`.
+        let pointer = unsafe { base.add(offset) };
+
+        if !pointer.as_ptr().addr().is_multiple_of(mem::align_of::<T>()) {
+            return Err(ViewError::Misaligned);
+        }
+
+        Ok(pointer.cast())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::alloc::Layout;
+
+    use crate::{
+        collection::{StoreString, StoreVec},
+        interface::{Store, StoreSharing},
+        store::SelfHostedStore,
+    };
+
+    use super::*;
+
+    type TestStore = SelfHostedStore<u32>;
+
+    fn bytes(block: &[u64]) -> &[u8] {
+        //  Safety:
+        //  -   `block` is initialized, and `u8` has no alignment requirement.
+        unsafe { slice::from_raw_parts(block.as_ptr().cast(), mem::size_of_val(block)) }
+    }
+
+    fn region(block: &mut [u64]) -> NonNull<[u8]> {
+        NonNull::slice_from_raw_parts(NonNull::from(&mut *block).cast(), mem::size_of_val(block))
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut block = [0u64; 64];
+
+        //  Safety:
+        //  -   `block` is only accessed through the store, and outlives it.
+        let store = unsafe { TestStore::format(region(&mut block)) }.unwrap();
+
+        let mut vec = StoreVec::new_in(store.share().into_ok());
+        vec.extend([1u32, 2, 3, 4]);
+
+        let mut string = StoreString::new_in(store.share().into_ok());
+        string.push_str("Hello, World");
+
+        let (root, _) = Store::allocate(&store, Layout::new::<[PersistedSlice; 2]>()).unwrap();
+
+        //  Safety:
+        //  -   `root` was allocated by `store`, for a `[PersistedSlice; 2]`.
+        unsafe {
+            Store::resolve(&store, root)
+                .cast::<[PersistedSlice; 2]>()
+                .write([vec.persisted(), string.persisted()])
+        };
+
+        store.set_root(Some(root));
+
+        drop((vec, string, store));
+
+        //  Persist the region, and load it again, elsewhere.
+        let mut loaded = block;
+
+        //  Safety:
+        //  -   `loaded` is initialized, only accessed through the store, and outlives it.
+        let store = unsafe { TestStore::attach(region(&mut loaded)) }.unwrap();
+
+        let root = PersistedSlice::new(store.root().unwrap().into(), 2);
+
+        //  Safety:
+        //  -   The region is initialized, and not written to while viewed.
+        let &[vec, string] = unsafe { root.view_in::<PersistedSlice, _>(&store) }.unwrap() else {
+            unreachable!()
+        };
+
+        //  Safety:
+        //  -   The region is initialized, and not written to while viewed.
+        unsafe {
+            assert_eq!(Ok(&[1u32, 2, 3, 4][..]), vec.view_in::<u32, _>(&store));
+            assert_eq!(Ok("Hello, World"), string.view_str_in(&store));
+        }
+
+        drop(store);
+
+        assert_eq!(Ok(&[1u32, 2, 3, 4][..]), vec.view::<u32>(bytes(&loaded)));
+        assert_eq!(Ok("Hello, World"), string.view_str(bytes(&loaded)));
+    }
+
+    #[test]
+    fn persisted_empty() {
+        let mut block = [0u64; 16];
+
+        //  Safety:
+        //  -   `block` is only accessed through the store, and outlives it.
+        let store = unsafe { TestStore::format(region(&mut block)) }.unwrap();
+
+        let vec = StoreVec::<u64, _>::new_in(store.share().into_ok());
+        let string = StoreString::new_in(store);
+
+        assert_eq!(PersistedSlice::default(), vec.persisted());
+        assert_eq!(PersistedSlice::default(), string.persisted());
+    }
+
+    #[test]
+    fn view_validation() {
+        use ViewError::{InvalidUtf8, Misaligned, OutOfBounds};
+
+        let block = [u64::from_ne_bytes([b'a', b'b', b'c', 0xFF, 0, 0, 0, 0]); 4];
+        let region = bytes(&block);
+
+        assert_eq!(Ok("abc"), PersistedSlice::new(0, 3).view_str(region));
+        assert_eq!(Ok(&[][..]), PersistedSlice::new(u64::MAX, 0).view::<u32>(region));
+        assert_eq!(Ok(&block[..]), PersistedSlice::new(0, 4).view::<u64>(region));
+
+        assert_eq!(Err(InvalidUtf8), PersistedSlice::new(0, 4).view_str(region));
+        assert_eq!(Err(OutOfBounds), PersistedSlice::new(28, 2).view::<u32>(region));
+        assert_eq!(Err(OutOfBounds), PersistedSlice::new(32, 1).view::<u8>(region));
+        assert_eq!(Err(OutOfBounds), PersistedSlice::new(8, u64::MAX).view::<u32>(region));
+        assert_eq!(Err(OutOfBounds), PersistedSlice::new(u64::MAX, 1).view::<u8>(region));
+        assert_eq!(Err(Misaligned), PersistedSlice::new(2, 1).view::<u32>(region));
+        assert_eq!(Err(Misaligned), PersistedSlice::new(8, 1).view::<u32>(&region[1..]));
+    }
+} // mod tests
@@ -0,0 +1,553 @@
+//! Compatibility shims for the traits of the earlier iterations of the API.
+//!
+//! Prototype collections written against `storage-poc`, the first iteration, or against the `Storage` trait which
+//! followed, may be ported incrementally: the traits of this module keep the old names and, where semantics allow, the
+//! old signatures, and are implemented for any store of the current API. A prototype collection thus compiles against
+//! the current stores as is, and may then be ported method by method.
+//!
+//! The mapping is as follows:
+//!
+//! -   `RawHandle<S>` and `Storage` map onto `StoreSingle`: the untyped handle, methods taking `&mut self`, and
+//!     resolution taking the layout of the block, which is ignored.
+//! -   `ElementStorage`, `SingleElementStorage`, and `MultiElementStorage` map onto `Store`, with `TypedHandle<T, _>`
+//!     as their typed handle.
+//! -   `RangeStorage`, `SingleRangeStorage`, and `MultiRangeStorage` map onto `Store`, with `TypedHandle<[T], _>` as
+//!     their typed handle, and `usize` as their capacity.
+//!
+//! Where the semantics differ:
+//!
+//! -   The element and range traits are only implemented for `Store`, not `StoreSingle`: their typed handles resolve
+//!     through `&self`, which a `StoreSingle` only guarantees for its single handle.
+//! -   `SingleElementStorage::allocate` is `unsafe`, as the metadata of an unsized `T` must describe a layout whose
+//!     size does not exceed `isize::MAX`, which is not checked.
+//! -   The `Single` and `Multi` variants are identical, as every `Store` may have multiple outstanding handles.
+//!
+//! Every item is deprecated, pointing to its replacement.
+
+//  The blanket implementations necessarily refer to the deprecated traits.
+#![allow(deprecated)]
+
+use core::{
+    alloc::{AllocError, Layout},
+    marker::Unsize,
+    mem::{self, MaybeUninit},
+    ptr::{self, NonNull, Pointee},
+};
+
+use crate::{
+    extension::{typed::TypedHandle, typed_metadata::TypedMetadata},
+    interface::{Store, StoreDangling, StoreSingle},
+};
+
+/// The untyped handle of a store.
+#[deprecated(note = "use `<S as StoreDangling>::Handle` instead")]
+pub type RawHandle<S> = <S as StoreDangling>::Handle;
+
+/// A storage of untyped blocks of memory, resolved with their layout.
+///
+/// Implemented for every `StoreSingle`.
+///
+/// #   Safety
+///
+/// Same as `StoreSingle`.
+#[deprecated(note = "use `StoreSingle` instead, whose `allocate` also returns the size of the block")]
+pub unsafe trait Storage: StoreDangling {
+    /// Allocates a block of memory fitting `layout`.
+    fn allocate(&mut self, layout: Layout) -> Result<RawHandle<Self>, AllocError>;
+
+    /// Deallocates the block of memory associated to `handle`.
+    ///
+    /// #   Safety
+    ///
+    /// Same as `StoreSingle::deallocate`.
+    unsafe fn deallocate(&mut self, handle: RawHandle<Self>, layout: Layout);
+
+    /// Resolves `handle` into a pointer to its block of memory, which may not be dereferenced mutably.
+    ///
+    /// #   Safety
+    ///
+    /// Same as `StoreSingle::resolve`; `layout` is ignored.
+    unsafe fn resolve(&self, handle: RawHandle<Self>, layout: Layout) -> NonNull<u8>;
+
+    /// Resolves `handle` into a pointer to its block of memory.
+    ///
+    /// #   Safety
+    ///
+    /// Same as `StoreSingle::resolve_mut`; `layout` is ignored.
+    unsafe fn resolve_mut(&mut self, handle: RawHandle<Self>, layout: Layout) -> NonNull<u8>;
+
+    /// Grows the block of memory associated to `handle`.
+    ///
+    /// #   Safety
+    ///
+    /// Same as `StoreSingle::grow`.
+    unsafe fn grow(
+        &mut self,
+        handle: RawHandle<Self>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<RawHandle<Self>, AllocError>;
+
+    /// Shrinks the block of memory associated to `handle`.
+    ///
+    /// #   Safety
+    ///
+    /// Same as `StoreSingle::shrink`.
+    unsafe fn shrink(
+        &mut self,
+        handle: RawHandle<Self>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<RawHandle<Self>, AllocError>;
+}
+
+unsafe impl<S> Storage for S
+where
+    S: StoreSingle,
+{
+    fn allocate(&mut self, layout: Layout) -> Result<RawHandle<Self>, AllocError> {
+        StoreSingle::allocate(self, layout).map(|(handle, _)| handle)
+    }
+
+    unsafe fn deallocate(&mut self, handle: RawHandle<Self>, layout: Layout) {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { StoreSingle::deallocate(self, handle, layout) }
+    }
+
+    unsafe fn resolve(&self, handle: RawHandle<Self>, _layout: Layout) -> NonNull<u8> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { StoreSingle::resolve(self, handle) }
+    }
+
+    unsafe fn resolve_mut(&mut self, handle: RawHandle<Self>, _layout: Layout) -> NonNull<u8> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { StoreSingle::resolve_mut(self, handle) }
+    }
+
+    unsafe fn grow(
+        &mut self,
+        handle: RawHandle<Self>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<RawHandle<Self>, AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { StoreSingle::grow(self, handle, old_layout, new_layout) }.map(|(handle, _)| handle)
+    }
+
+    unsafe fn shrink(
+        &mut self,
+        handle: RawHandle<Self>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<RawHandle<Self>, AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { StoreSingle::shrink(self, handle, old_layout, new_layout) }.map(|(handle, _)| handle)
+    }
+}
+
+/// A storage of typed elements.
+///
+/// Implemented for every `Store`.
+#[deprecated(note = "use `Store` with `TypedHandle` instead")]
+pub trait ElementStorage {
+    /// The typed handle to an element.
+    type Handle<T: ?Sized>: Copy;
+
+    /// Releases the memory of the element, without dropping it.
+    ///
+    /// #   Safety
+    ///
+    /// -   `handle` must have been allocated by `self`, and must still be valid.
+    /// -   `handle` is invalidated alongside any copy of it.
+    unsafe fn release<T: ?Sized>(&mut self, handle: Self::Handle<T>);
+
+    /// Drops the element, then releases its memory.
+    ///
+    /// #   Safety
+    ///
+    /// -   `handle` must have been allocated by `self`, and must still be valid.
+    /// -   `handle` must be associated to a valid instance of `T`.
+    /// -   `handle` is invalidated alongside any copy of it.
+    unsafe fn destroy<T: ?Sized>(&mut self, handle: Self::Handle<T>) {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe {
+            ptr::drop_in_place(self.get(handle).as_ptr());
+            self.release(handle);
+        }
+    }
+
+    /// Resolves the handle into a pointer to the element.
+    ///
+    /// #   Safety
+    ///
+    /// -   `handle` must have been allocated by `self`, and must still be valid.
+    unsafe fn get<T: ?Sized>(&self, handle: Self::Handle<T>) -> NonNull<T>;
+
+    /// Coerces the handle into a handle to an unsized element.
+    fn coerce<U: ?Sized, T: ?Sized + Unsize<U>>(&self, handle: Self::Handle<T>) -> Self::Handle<U>;
+}
+
+impl<S> ElementStorage for S
+where
+    S: Store,
+{
+    type Handle<T: ?Sized> = TypedHandle<T, <S as StoreDangling>::Handle>;
+
+    unsafe fn release<T: ?Sized>(&mut self, handle: Self::Handle<T>) {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { handle.deallocate(self) }
+    }
+
+    unsafe fn get<T: ?Sized>(&self, handle: Self::Handle<T>) -> NonNull<T> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { handle.resolve_raw(self) }
+    }
+
+    fn coerce<U: ?Sized, T: ?Sized + Unsize<U>>(&self, handle: Self::Handle<T>) -> Self::Handle<U> {
+        handle.coerce()
+    }
+}
+
+/// A storage of typed elements, able to allocate them.
+///
+/// Implemented for every `Store`.
+#[deprecated(note = "use `Store` with `TypedHandle::try_new` or `TypedHandle::try_allocate` instead")]
+pub trait SingleElementStorage: ElementStorage {
+    /// Moves `value` into a newly allocated element, or returns it on failure.
+    fn create<T>(&mut self, value: T) -> Result<Self::Handle<T>, T>;
+
+    /// Allocates the memory of an element, as described by `metadata`, leaving it uninitialized.
+    ///
+    /// #   Safety
+    ///
+    /// -   `metadata` must describe a layout whose size does not exceed `isize::MAX`, as per `Layout::for_value_raw`.
+    unsafe fn allocate<T: ?Sized>(&mut self, metadata: <T as Pointee>::Metadata)
+        -> Result<Self::Handle<T>, AllocError>;
+}
+
+impl<S> SingleElementStorage for S
+where
+    S: Store,
+{
+    fn create<T>(&mut self, value: T) -> Result<Self::Handle<T>, T> {
+        let Ok(handle) = TypedHandle::try_allocate(self) else {
+            return Err(value);
+        };
+
+        //  Safety:
+        //  -   `handle` was just allocated by `self`, and is thus valid.
+        let pointer = unsafe { handle.resolve_raw(self) };
+
+        //  Safety:
+        //  -   `pointer` is valid for writes of a `T`, and exclusive.
+        unsafe { ptr::write(pointer.as_ptr(), value) };
+
+        Ok(handle)
+    }
+
+    unsafe fn allocate<T: ?Sized>(
+        &mut self,
+        metadata: <T as Pointee>::Metadata,
+    ) -> Result<Self::Handle<T>, AllocError> {
+        let pointer: *const T = ptr::from_raw_parts(ptr::null::<()>(), metadata);
+
+        //  Safety:
+        //  -   The size described by `metadata` does not exceed `isize::MAX`, as per pre-conditions.
+        let layout = unsafe { Layout::for_value_raw(pointer) };
+
+        let (handle, _) = Store::allocate(self, layout)?;
+
+        let metadata = TypedMetadata::from_metadata(metadata);
+
+        Ok(TypedHandle::from_raw_parts(handle, metadata))
+    }
+}
+
+/// A storage of typed elements, able to allocate several of them at once.
+///
+/// Implemented for every `Store`, which may always have multiple outstanding handles.
+#[deprecated(note = "use `Store`, or `StoreMultiple` in bounds, instead")]
+pub trait MultiElementStorage: SingleElementStorage {}
+
+impl<S> MultiElementStorage for S where S: Store {}
+
+/// A storage of typed ranges of elements.
+///
+/// Implemented for every `Store`.
+#[deprecated(note = "use `Store` with `TypedHandle<[T], _>` instead")]
+pub trait RangeStorage {
+    /// The typed handle to a range of elements, which carries its capacity.
+    type Handle<T>: Copy;
+
+    /// Returns the maximum capacity of a range of `T`.
+    fn maximum_capacity<T>(&self) -> usize {
+        match mem::size_of::<T>() {
+            0 => usize::MAX,
+            size => isize::MAX as usize / size,
+        }
+    }
+
+    /// Releases the memory of the range, without dropping its elements.
+    ///
+    /// #   Safety
+    ///
+    /// -   `handle` must have been allocated by `self`, and must still be valid.
+    /// -   `handle` is invalidated alongside any copy of it.
+    unsafe fn release<T>(&mut self, handle: Self::Handle<T>);
+
+    /// Resolves the handle into a pointer to the, possibly uninitialized, elements of the range.
+    ///
+    /// #   Safety
+    ///
+    /// -   `handle` must have been allocated by `self`, and must still be valid.
+    unsafe fn get<T>(&self, handle: Self::Handle<T>) -> NonNull<[MaybeUninit<T>]>;
+
+    /// Attempts to grow the range to at least `new_capacity` elements.
+    ///
+    /// On success, `handle` is invalidated alongside any copy of it.
+    ///
+    /// #   Safety
+    ///
+    /// -   `handle` must have been allocated by `self`, and must still be valid.
+    /// -   `new_capacity` must be greater than or equal to the capacity of `handle`.
+    unsafe fn try_grow<T>(
+        &mut self,
+        handle: Self::Handle<T>,
+        new_capacity: usize,
+    ) -> Result<Self::Handle<T>, AllocError>;
+
+    /// Attempts to shrink the range to at least `new_capacity` elements.
+    ///
+    /// On success, `handle` is invalidated alongside any copy of it.
+    ///
+    /// #   Safety
+    ///
+    /// -   `handle` must have been allocated by `self`, and must still be valid.
+    /// -   `new_capacity` must be less than or equal to the capacity of `handle`.
+    unsafe fn try_shrink<T>(
+        &mut self,
+        handle: Self::Handle<T>,
+        new_capacity: usize,
+    ) -> Result<Self::Handle<T>, AllocError>;
+}
+
+impl<S> RangeStorage for S
+where
+    S: Store,
+{
+    type Handle<T> = TypedHandle<[T], <S as StoreDangling>::Handle>;
+
+    unsafe fn release<T>(&mut self, handle: Self::Handle<T>) {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { handle.deallocate(self) }
+    }
+
+    unsafe fn get<T>(&self, handle: Self::Handle<T>) -> NonNull<[MaybeUninit<T>]> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        let pointer = unsafe { handle.resolve_raw(self) };
+
+        NonNull::slice_from_raw_parts(pointer.as_non_null_ptr().cast(), pointer.len())
+    }
+
+    unsafe fn try_grow<T>(
+        &mut self,
+        mut handle: Self::Handle<T>,
+        new_capacity: usize,
+    ) -> Result<Self::Handle<T>, AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { handle.try_grow(new_capacity, self)? };
+
+        Ok(handle)
+    }
+
+    unsafe fn try_shrink<T>(
+        &mut self,
+        mut handle: Self::Handle<T>,
+        new_capacity: usize,
+    ) -> Result<Self::Handle<T>, AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { handle.try_shrink(new_capacity, self)? };
+
+        Ok(handle)
+    }
+}
+
+/// A storage of typed ranges of elements, able to allocate them.
+///
+/// Implemented for every `Store`.
+#[deprecated(note = "use `Store` with `TypedHandle::try_allocate_slice` instead")]
+pub trait SingleRangeStorage: RangeStorage {
+    /// Allocates a range of at least `capacity` elements, leaving them uninitialized.
+    fn allocate<T>(&mut self, capacity: usize) -> Result<Self::Handle<T>, AllocError>;
+}
+
+impl<S> SingleRangeStorage for S
+where
+    S: Store,
+{
+    fn allocate<T>(&mut self, capacity: usize) -> Result<Self::Handle<T>, AllocError> {
+        TypedHandle::try_allocate_slice(capacity, self)
+    }
+}
+
+/// A storage of typed ranges of elements, able to allocate several of them at once.
+///
+/// Implemented for every `Store`, which may always have multiple outstanding handles.
+#[deprecated(note = "use `Store`, or `StoreMultiple` in bounds, instead")]
+pub trait MultiRangeStorage: SingleRangeStorage {}
+
+impl<S> MultiRangeStorage for S where S: Store {}
+
+#[cfg(test)]
+mod tests {
+    use crate::store::{InlineBumpStore, InlineSingleStore, RefStore};
+
+    use super::*;
+
+    type Bump = InlineBumpStore<u8, [u64; 4]>;
+
+    //  A prototype box, as written against the first iteration.
+    struct PocBox<T: ?Sized, S: SingleElementStorage> {
+        handle: S::Handle<T>,
+        storage: S,
+    }
+
+    impl<T, S: SingleElementStorage> PocBox<T, S> {
+        fn new(value: T, mut storage: S) -> Result<Self, T> {
+            let handle = storage.create(value)?;
+
+            Ok(Self { handle, storage })
+        }
+    }
+
+    impl<T: ?Sized, S: SingleElementStorage> PocBox<T, S> {
+        fn get(&self) -> &T {
+            //  Safety:
+            //  -   `self.handle` was allocated by `self.storage`, and is still valid.
+            unsafe { self.storage.get(self.handle).as_ref() }
+        }
+
+        fn coerce<U: ?Sized>(self) -> PocBox<U, S>
+        where
+            T: Unsize<U>,
+        {
+            let this = mem::ManuallyDrop::new(self);
+
+            //  Safety:
+            //  -   `this.storage` is not used after this point.
+            let storage = unsafe { ptr::read(&this.storage) };
+            let handle = storage.coerce(this.handle);
+
+            PocBox { handle, storage }
+        }
+    }
+
+    impl<T: ?Sized, S: SingleElementStorage> Drop for PocBox<T, S> {
+        fn drop(&mut self) {
+            //  Safety:
+            //  -   `self.handle` was allocated by `self.storage`, is still valid, and points to a valid `T`.
+            unsafe { self.storage.destroy(self.handle) };
+        }
+    }
+
+    #[test]
+    fn storage() {
+        let mut store = InlineSingleStore::<u64>::default();
+
+        let layout = Layout::new::<u64>();
+
+        let handle = Storage::allocate(&mut store, layout).unwrap();
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, with `layout`, and is still valid.
+        unsafe {
+            Storage::resolve_mut(&mut store, handle, layout).cast::<u64>().write(42);
+
+            assert_eq!(42, Storage::resolve(&store, handle, layout).cast::<u64>().read());
+
+            Storage::deallocate(&mut store, handle, layout);
+        }
+    }
+
+    #[test]
+    fn element() {
+        let boxed = PocBox::new([1u32, 2, 3], Bump::default()).unwrap_or_else(|_| unreachable!());
+
+        assert_eq!([1, 2, 3], *boxed.get());
+
+        let boxed: PocBox<[u32], _> = boxed.coerce();
+
+        assert_eq!([1, 2, 3], boxed.get());
+
+        let store = InlineBumpStore::<u8, [u64; 1]>::default();
+
+        let boxed = PocBox::new(7u64, RefStore::new(&store)).unwrap_or_else(|_| unreachable!());
+
+        assert_eq!(7, *boxed.get());
+        assert_eq!(Err(8), PocBox::new(8u64, RefStore::new(&store)).map(|_| ()));
+    }
+
+    #[test]
+    fn element_unsized() {
+        let mut store = Bump::default();
+
+        //  Safety:
+        //  -   A slice of 4 `u32` fits within `isize::MAX` bytes.
+        let handle = unsafe { SingleElementStorage::allocate::<[u32]>(&mut store, 4) }.unwrap();
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        let pointer = unsafe { ElementStorage::get(&store, handle) };
+
+        assert_eq!(4, pointer.len());
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        unsafe { ElementStorage::release(&mut store, handle) };
+    }
+
+    #[test]
+    fn range() {
+        fn assert_multi<S: MultiElementStorage + MultiRangeStorage>(_: &S) {}
+
+        let mut store = InlineBumpStore::<u8, [u32; 16]>::default();
+
+        assert_multi(&store);
+        assert_eq!(isize::MAX as usize / 4, RangeStorage::maximum_capacity::<u32>(&store));
+        assert_eq!(usize::MAX, RangeStorage::maximum_capacity::<()>(&store));
+
+        let handle = SingleRangeStorage::allocate::<u32>(&mut store, 4).unwrap();
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        let handle = unsafe { RangeStorage::try_grow(&mut store, handle, 8) }.unwrap();
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        let elements = unsafe { RangeStorage::get(&store, handle) };
+
+        assert_eq!(8, elements.len());
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid, and its capacity is at least 2.
+        let handle = unsafe { RangeStorage::try_shrink(&mut store, handle, 2) }.unwrap();
+
+        assert!(handle.len() >= 2);
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        unsafe { RangeStorage::release(&mut store, handle) };
+    }
+} // mod tests
@@ -1,5 +1,10 @@
 //! Possible extensions to be built around `Store`.
 
+pub mod handle_slice;
+pub mod header_slice;
+pub mod layout;
+pub mod owned;
+pub mod sub_handle;
 pub mod typed;
 pub mod typed_metadata;
 pub mod unique;
@@ -0,0 +1,397 @@
+//! A slice of typed handles, stored in a block of memory of the store.
+//!
+//! Storing handles within allocations is a common need of linked data-structures, and a tricky one to get right: the
+//! block of memory must be initialized before being read, and each access requires resolving the slice anew.
+
+use core::{
+    alloc::{AllocError, Layout},
+    ptr::NonNull,
+};
+
+use crate::{
+    alloc,
+    extension::typed::TypedHandle,
+    interface::{Store, StoreDangling},
+};
+
+/// A slice of typed handles, stored in a block of memory of the store.
+///
+/// All the elements of the slice are always initialized, initially to dangling handles. The elements themselves may
+/// be dangling, or invalid: it is the responsibility of the user to track which of them are valid.
+pub struct HandleSlice<T, H> {
+    handles: TypedHandle<[TypedHandle<T, H>], H>,
+    //  Whether `handles` was allocated, even if empty, rather than dangling.
+    allocated: bool,
+}
+
+impl<T, H: Copy> HandleSlice<T, H> {
+    /// Creates a dangling, empty, slice.
+    ///
+    /// Calls `handle_alloc_error` if the creation of the handle fails.
+    #[inline(always)]
+    pub fn dangling<S>(store: &S) -> Self
+    where
+        S: StoreDangling<Handle = H>,
+    {
+        let handles = TypedHandle::dangling_slice(store);
+
+        Self {
+            handles,
+            allocated: false,
+        }
+    }
+
+    /// Allocates a new slice, with room for at least `size` handles, all initialized to dangling handles.
+    ///
+    /// Calls `handle_alloc_error` if the allocation fails.
+    #[inline(always)]
+    pub fn allocate<S>(size: usize, store: &S) -> Self
+    where
+        S: Store<Handle = H>,
+    {
+        let Ok(this) = Self::try_allocate(size, store) else {
+            alloc::handle_alloc_error(Layout::new::<TypedHandle<T, H>>())
+        };
+
+        this
+    }
+
+    /// Attempts to allocate a new slice, with room for at least `size` handles, all initialized to dangling handles.
+    #[inline]
+    pub fn try_allocate<S>(size: usize, store: &S) -> Result<Self, AllocError>
+    where
+        S: Store<Handle = H>,
+    {
+        let dangling = TypedHandle::try_dangling(store)?;
+
+        let handles = TypedHandle::try_allocate_slice(size, store)?;
+
+        //  Safety:
+        //  -   `handles` was allocated by `store`, and is still valid.
+        unsafe { Self::fill(handles, 0, dangling, store) };
+
+        Ok(Self {
+            handles,
+            allocated: true,
+        })
+    }
+
+    /// Returns whether the slice is empty.
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.handles.is_empty()
+    }
+
+    /// Returns the number of handles in the slice.
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.handles.len()
+    }
+
+    /// Deallocates the memory associated with the slice, but not the memory associated with its elements.
+    ///
+    /// #   Safety
+    ///
+    /// -   `self` must have been allocated by `store`, or be dangling.
+    /// -   `self` must still be valid.
+    #[inline]
+    pub unsafe fn deallocate<S>(self, store: &S)
+    where
+        S: Store<Handle = H>,
+    {
+        //  A dangling slice was never allocated, and thus cannot be deallocated, unlike an allocated empty slice.
+        if !self.allocated {
+            return;
+        }
+
+        //  Safety:
+        //  -   `self.handles` was allocated by `store`, and is still valid, as per pre-conditions.
+        unsafe { self.handles.deallocate(store) };
+    }
+
+    /// Returns a copy of the `index`-th handle, if within bounds.
+    ///
+    /// #   Safety
+    ///
+    /// -   `self` must have been allocated by `store`, or be dangling.
+    /// -   `self` must still be valid.
+    #[inline(always)]
+    pub unsafe fn get<S>(&self, index: usize, store: &S) -> Option<TypedHandle<T, H>>
+    where
+        S: Store<Handle = H>,
+    {
+        if index >= self.len() {
+            return None;
+        }
+
+        //  Safety:
+        //  -   `self` was allocated by `store`, and is still valid, as per pre-conditions.
+        //  -   `index` is within bounds.
+        let pointer = unsafe { self.element(index, store) };
+
+        //  Safety:
+        //  -   `pointer` is valid for reads, and properly aligned.
+        //  -   `pointer` points to an initialized handle, as all elements are always initialized.
+        Some(unsafe { pointer.read() })
+    }
+
+    /// Overwrites the `index`-th handle with `handle`, returning the previous one, if within bounds.
+    ///
+    /// If out of bounds, `handle` is returned as an error.
+    ///
+    /// #   Safety
+    ///
+    /// -   `self` must have been allocated by `store`, or be dangling.
+    /// -   `self` must still be valid.
+    /// -   No reference to the elements of `self`, as obtained by `resolve`, may be active.
+    #[inline]
+    pub unsafe fn set<S>(
+        &mut self,
+        index: usize,
+        handle: TypedHandle<T, H>,
+        store: &S,
+    ) -> Result<TypedHandle<T, H>, TypedHandle<T, H>>
+    where
+        S: Store<Handle = H>,
+    {
+        if index >= self.len() {
+            return Err(handle);
+        }
+
+        //  Safety:
+        //  -   `self` was allocated by `store`, and is still valid, as per pre-conditions.
+        //  -   `index` is within bounds.
+        let pointer = unsafe { self.element(index, store) };
+
+        //  Safety:
+        //  -   `pointer` is valid for reads and writes, and properly aligned.
+        //  -   `pointer` points to an initialized handle, as all elements are always initialized.
+        //  -   No reference to the element is active, as per pre-conditions.
+        Ok(unsafe { pointer.replace(handle) })
+    }
+
+    /// Resolves the slice to a reference.
+    ///
+    /// #   Safety
+    ///
+    /// -   `self` must have been allocated by `store`, or be dangling.
+    /// -   `self` must still be valid.
+    /// -   The reference is only guaranteed to be valid as long as `self` is valid, and pointers resolved from `self`
+    ///     are not invalidated.
+    #[inline(always)]
+    pub unsafe fn resolve<'a, S>(&self, store: &'a S) -> &'a [TypedHandle<T, H>]
+    where
+        S: Store<Handle = H>,
+    {
+        //  Safety:
+        //  -   `self.handles` was allocated by `store`, and is still valid, as per pre-conditions.
+        //  -   All elements are always initialized.
+        unsafe { self.handles.resolve(store) }
+    }
+
+    /// Resolves the slice to a mutable reference.
+    ///
+    /// #   Safety
+    ///
+    /// -   `self` must have been allocated by `store`, or be dangling.
+    /// -   `self` must still be valid.
+    /// -   The reference is only guaranteed to be valid as long as `self` is valid, and pointers resolved from `self`
+    ///     are not invalidated.
+    #[allow(clippy::mut_from_ref)]
+    #[inline(always)]
+    pub unsafe fn resolve_mut<'a, S>(&mut self, store: &'a S) -> &'a mut [TypedHandle<T, H>]
+    where
+        S: Store<Handle = H>,
+    {
+        //  Safety:
+        //  -   `self.handles` was allocated by `store`, and is still valid, as per pre-conditions.
+        //  -   All elements are always initialized.
+        //  -   No other reference to the elements is active, as `self` is borrowed mutably.
+        unsafe { self.handles.resolve_mut(store) }
+    }
+
+    /// Grows the slice to hold at least `new_size` handles, initializing the new handles to `with`.
+    ///
+    /// On success, all the copies of the slice are invalidated. On failure, an error is returned, and `self` is left
+    /// untouched.
+    ///
+    /// #   Safety
+    ///
+    /// -   `self` must have been allocated by `store`, or be dangling.
+    /// -   `self` must still be valid.
+    /// -   `new_size` must be greater than or equal to `self.len()`.
+    #[inline]
+    pub unsafe fn try_grow<S>(&mut self, new_size: usize, with: TypedHandle<T, H>, store: &S) -> Result<(), AllocError>
+    where
+        S: Store<Handle = H>,
+    {
+        debug_assert!(new_size >= self.len());
+
+        let old_size = self.len();
+
+        if !self.allocated {
+            *self = Self::try_allocate(new_size, store)?;
+        } else {
+            //  Safety:
+            //  -   `self.handles` was allocated by `store`, and is still valid, as per pre-conditions.
+            //  -   `new_size` is greater than or equal to `self.len()`, as per pre-conditions.
+            unsafe { self.handles.try_grow(new_size, store)? };
+        }
+
+        //  Safety:
+        //  -   `self.handles` was allocated by `store`, and is still valid.
+        unsafe { Self::fill(self.handles, old_size, with, store) };
+
+        Ok(())
+    }
+}
+
+impl<T, H: Copy> Clone for HandleSlice<T, H> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, H: Copy> Copy for HandleSlice<T, H> {}
+
+//
+//  Implementation
+//
+
+impl<T, H: Copy> HandleSlice<T, H> {
+    //  Returns a pointer to the `index`-th element.
+    //
+    //  #   Safety
+    //
+    //  -   `self` must have been allocated by `store`, and still be valid.
+    //  -   `index` must be within bounds.
+    #[inline(always)]
+    unsafe fn element<S>(&self, index: usize, store: &S) -> NonNull<TypedHandle<T, H>>
+    where
+        S: Store<Handle = H>,
+    {
+        debug_assert!(index < self.len());
+
+        //  Safety:
+        //  -   `self.handles` was allocated by `store`, and is still valid, as per pre-conditions.
+        let slice = unsafe { self.handles.resolve_raw(store) };
+
+        //  Safety:
+        //  -   `index` is within bounds, as per pre-conditions.
+        unsafe { slice.as_non_null_ptr().add(index) }
+    }
+
+    //  Initializes the elements of `handles` from `from` onwards with `with`.
+    //
+    //  #   Safety
+    //
+    //  -   `handles` must have been allocated by `store`, and still be valid.
+    unsafe fn fill<S>(handles: TypedHandle<[TypedHandle<T, H>], H>, from: usize, with: TypedHandle<T, H>, store: &S)
+    where
+        S: Store<Handle = H>,
+    {
+        if from >= handles.len() {
+            return;
+        }
+
+        //  Safety:
+        //  -   `handles` was allocated by `store`, and is still valid, as per pre-conditions.
+        let slice = unsafe { handles.resolve_raw(store) };
+
+        for index in from..handles.len() {
+            //  Safety:
+            //  -   `index` is within bounds.
+            //  -   The pointer is valid for writes, and properly aligned.
+            unsafe { slice.as_non_null_ptr().add(index).write(with) };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::store::{StackBumpBlock, TrackingStore};
+
+    use super::*;
+
+    #[test]
+    fn get_set() {
+        let block = StackBumpBlock::<[u64; 16]>::new();
+        let store = block.create_store::<usize>();
+
+        let mut slice = HandleSlice::<u32, _>::allocate(3, &store);
+
+        assert_eq!(3, slice.len());
+
+        let one = TypedHandle::new(1u32, &store);
+        let two = TypedHandle::new(2u32, &store);
+
+        //  Safety:
+        //  -   `slice` was allocated by `store`, and is still valid.
+        unsafe {
+            assert!(slice.set(0, one, &store).is_ok());
+            assert!(slice.set(2, two, &store).is_ok());
+
+            assert!(slice.set(3, two, &store).is_err());
+            assert!(slice.get(3, &store).is_none());
+
+            assert_eq!(1, *slice.get(0, &store).unwrap().resolve(&store));
+            assert_eq!(2, *slice.get(2, &store).unwrap().resolve(&store));
+
+            assert_eq!(3, slice.resolve(&store).len());
+
+            slice.deallocate(&store);
+        }
+    }
+
+    #[test]
+    fn grow() {
+        let block = StackBumpBlock::<[u64; 16]>::new();
+        let store = block.create_store::<usize>();
+
+        let one = TypedHandle::new(1u32, &store);
+        let two = TypedHandle::new(2u32, &store);
+
+        let mut slice = HandleSlice::dangling(&store);
+
+        assert!(slice.is_empty());
+
+        //  Safety:
+        //  -   `slice` is dangling, then allocated by `store`, and is still valid.
+        unsafe {
+            slice.try_grow(1, one, &store).unwrap();
+            slice.try_grow(3, two, &store).unwrap();
+
+            let values: Vec<_> = slice
+                .resolve(&store)
+                .iter()
+                .map(|handle| *handle.resolve(&store))
+                .collect();
+
+            assert_eq!(vec![1, 2, 2], values);
+
+            slice.deallocate(&store);
+        }
+    }
+
+    #[test]
+    fn deallocate_empty() {
+        let block = StackBumpBlock::<[u64; 16]>::new();
+        let store = TrackingStore::<_, 2>::new(block.create_store::<usize>());
+
+        let dangling = HandleSlice::<u32, _>::dangling(&store);
+        let empty = HandleSlice::<u32, _>::allocate(0, &store);
+
+        assert!(empty.is_empty());
+        assert_eq!(1, store.len());
+
+        //  Safety:
+        //  -   `dangling` is dangling, and `empty` was allocated by `store`, and both are still valid.
+        unsafe {
+            dangling.deallocate(&store);
+            empty.deallocate(&store);
+        }
+
+        assert!(store.is_empty());
+    }
+} // mod tests
@@ -0,0 +1,540 @@
+//! A typed handle to a header followed by a slice, in a single block of memory.
+//!
+//! A fixed header followed by a dynamic array is a common layout for the nodes of linked data-structures, such as the
+//! nodes of a skip list, and a tricky one to get right: the offset of the array must be computed from the layouts of
+//! both header and elements, and the header must be told of the length of the array, since the handle is not.
+
+use core::{
+    alloc::{AllocError, Layout, LayoutError},
+    cmp,
+    marker::PhantomData,
+    ops::Range,
+    ptr::{self, Alignment, NonNull},
+    slice,
+};
+
+use crate::{
+    alloc,
+    interface::{Store, StoreDangling},
+};
+
+/// A typed handle to a block of memory containing a `Head`, followed by a slice of `T`.
+///
+/// The handle does not track the length of the slice, which is typically stored within the header itself, and must
+/// thus be supplied to most operations.
+///
+/// A handle may be dangling, or may be invalid. It is the responsibility of the user to ensure that the handle is valid
+/// when necessary.
+///
+/// The handle is `#[repr(transparent)]`, and thus has the layout of `H`.
+#[repr(transparent)]
+pub struct HeaderSliceHandle<Head, T, H> {
+    handle: H,
+    //  Like `TypedHandle`, the handle neither owns nor borrows a `Head` or a `T`.
+    _marker: PhantomData<fn() -> (Head, T)>,
+}
+
+impl<Head, T, H: Copy> HeaderSliceHandle<Head, T, H> {
+    /// Creates a dangling handle.
+    ///
+    /// Calls `handle_alloc_error` if the creation of the handle fails.
+    #[inline(always)]
+    pub fn dangling<S>(store: &S) -> Self
+    where
+        S: StoreDangling<Handle = H>,
+    {
+        let Ok(this) = Self::try_dangling(store) else {
+            alloc::handle_alloc_error(Layout::new::<Head>())
+        };
+
+        this
+    }
+
+    /// Attempts to create a dangling handle.
+    ///
+    /// Returns `AllocError` on failure.
+    #[inline(always)]
+    pub fn try_dangling<S>(store: &S) -> Result<Self, AllocError>
+    where
+        S: StoreDangling<Handle = H>,
+    {
+        let alignment = cmp::max(Alignment::of::<Head>(), Alignment::of::<T>());
+
+        let handle = store.dangling(alignment)?;
+
+        Ok(Self::from_raw_parts(handle))
+    }
+
+    /// Creates a new block of memory, containing `head` followed by `length` elements created by `element`.
+    ///
+    /// `element` is called with the index of each element, in order.
+    ///
+    /// Calls `handle_alloc_error` if the allocation fails.
+    #[inline(always)]
+    pub fn new<S>(head: Head, length: usize, element: impl FnMut(usize) -> T, store: &S) -> Self
+    where
+        S: Store<Handle = H>,
+    {
+        let Ok(this) = Self::try_new(head, length, element, store) else {
+            alloc::handle_alloc_error(Self::layout_or_head(length))
+        };
+
+        this
+    }
+
+    /// Attempts to create a new block of memory, containing `head` followed by `length` elements created by `element`.
+    ///
+    /// `element` is called with the index of each element, in order.
+    ///
+    /// Returns an error if the allocation fails, in which case `element` is not called.
+    #[inline]
+    pub fn try_new<S>(head: Head, length: usize, element: impl FnMut(usize) -> T, store: &S) -> Result<Self, AllocError>
+    where
+        S: Store<Handle = H>,
+    {
+        let Ok((layout, offset)) = Self::layout(length) else {
+            return Err(AllocError);
+        };
+
+        let (handle, _) = store.allocate(layout)?;
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        let pointer = unsafe { store.resolve(handle) };
+
+        //  Safety:
+        //  -   `pointer` is valid for writes of `Head`, as the block of memory fits `layout`.
+        //  -   `pointer` is suitably aligned for `Head`, as the block of memory fits `layout`.
+        unsafe { ptr::write(pointer.as_ptr() as *mut Head, head) };
+
+        //  Safety:
+        //  -   `pointer` points to a writeable block of memory fitting the layout of `length` elements.
+        //  -   `offset` is the offset of the slice within this layout.
+        unsafe { Self::initialize(pointer, offset, 0..length, element) };
+
+        Ok(Self::from_raw_parts(handle))
+    }
+
+    /// Grows the slice from `old_length` to `new_length` elements, the additional elements created by `element`.
+    ///
+    /// `element` is called with the index of each additional element, in order.
+    ///
+    /// On success, all the copies of the handle are invalidated. The header is not modified, and may need to be
+    /// updated to record the new length.
+    ///
+    /// Calls `handle_alloc_error` if the allocation fails.
+    ///
+    /// #   Safety
+    ///
+    /// -   `self` must have been allocated by `store`.
+    /// -   `self` must still be valid.
+    /// -   `old_length` must be the current length of the slice.
+    /// -   `new_length` must be greater than or equal to `old_length`.
+    /// -   No reference to the block of memory associated with `self` must be active.
+    #[inline(always)]
+    pub unsafe fn grow<S>(&mut self, old_length: usize, new_length: usize, element: impl FnMut(usize) -> T, store: &S)
+    where
+        S: Store<Handle = H>,
+    {
+        //  Safety:
+        //  -   As per pre-conditions.
+        let result = unsafe { self.try_grow(old_length, new_length, element, store) };
+
+        if result.is_err() {
+            alloc::handle_alloc_error(Self::layout_or_head(new_length))
+        }
+    }
+
+    /// Attempts to grow the slice from `old_length` to `new_length` elements, the additional elements created by
+    /// `element`.
+    ///
+    /// `element` is called with the index of each additional element, in order.
+    ///
+    /// On success, all the copies of the handle are invalidated. The header is not modified, and may need to be
+    /// updated to record the new length. On failure, an error is returned, and `self` is left untouched.
+    ///
+    /// #   Safety
+    ///
+    /// -   `self` must have been allocated by `store`.
+    /// -   `self` must still be valid.
+    /// -   `old_length` must be the current length of the slice.
+    /// -   `new_length` must be greater than or equal to `old_length`.
+    /// -   No reference to the block of memory associated with `self` must be active.
+    #[inline]
+    pub unsafe fn try_grow<S>(
+        &mut self,
+        old_length: usize,
+        new_length: usize,
+        element: impl FnMut(usize) -> T,
+        store: &S,
+    ) -> Result<(), AllocError>
+    where
+        S: Store<Handle = H>,
+    {
+        debug_assert!(new_length >= old_length);
+
+        let Ok((old_layout, _)) = Self::layout(old_length) else {
+            return Err(AllocError);
+        };
+
+        let Ok((new_layout, offset)) = Self::layout(new_length) else {
+            return Err(AllocError);
+        };
+
+        //  Safety:
+        //  -   `self.handle` was allocated by `store`, as per pre-conditions.
+        //  -   `self.handle` is still valid, as per pre-conditions.
+        //  -   `old_layout` fits the block of memory associated to `self.handle`, as `old_length` is the current
+        //      length, as per pre-conditions.
+        //  -   `new_layout`'s size is greater than or equal to the size of `old_layout`, as per pre-conditions.
+        let (handle, _) = unsafe { store.grow(self.handle, old_layout, new_layout)? };
+
+        self.handle = handle;
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        let pointer = unsafe { store.resolve(handle) };
+
+        //  Since the slice is at the end of the block of memory, its offset does not depend on its length, and the
+        //  existing elements were thus copied over in place by `grow`.
+        //
+        //  Safety:
+        //  -   `pointer` points to a writeable block of memory fitting the layout of `new_length` elements.
+        //  -   `offset` is the offset of the slice within this layout.
+        unsafe { Self::initialize(pointer, offset, old_length..new_length, element) };
+
+        Ok(())
+    }
+
+    /// Drops the elements of the slice, deallocates the block of memory, and returns the header.
+    ///
+    /// #   Safety
+    ///
+    /// -   `self` must have been allocated by `store`.
+    /// -   `self` must still be valid.
+    /// -   `length` must be the current length of the slice.
+    /// -   `self` is invalidated alongside any copy of it.
+    /// -   No reference to the block of memory associated with `self` must be active.
+    #[inline]
+    pub unsafe fn deallocate<S>(self, length: usize, store: &S) -> Head
+    where
+        S: Store<Handle = H>,
+    {
+        //  Safety:
+        //  -   `length` is the current length of the slice, as per pre-conditions.
+        let (layout, offset) = unsafe { Self::allocated_layout(length) };
+
+        //  Safety:
+        //  -   `self.handle` was allocated by `store`, and is still valid, as per pre-conditions.
+        let pointer = unsafe { store.resolve(self.handle) };
+
+        //  Safety:
+        //  -   `pointer` points to a live instance of `Head`, as per pre-conditions.
+        //  -   The instance will no longer be used.
+        let head = unsafe { ptr::read(pointer.as_ptr() as *const Head) };
+
+        //  Safety:
+        //  -   `offset` is within the block of memory, as it fits `layout`.
+        let first = unsafe { pointer.as_ptr().add(offset) as *mut T };
+
+        //  Safety:
+        //  -   `first` points to `length` live instances of `T`, as per pre-conditions.
+        //  -   The instances will no longer be used.
+        unsafe { ptr::drop_in_place(ptr::slice_from_raw_parts_mut(first, length)) };
+
+        //  Safety:
+        //  -   `self.handle` was allocated by `store`, and is still valid, as per pre-conditions.
+        //  -   `layout` fits the block of memory, as `length` is the current length, as per pre-conditions.
+        unsafe { store.deallocate(self.handle, layout) };
+
+        head
+    }
+
+    /// Resolves the handle to a reference to the header.
+    ///
+    /// #   Safety
+    ///
+    /// -   `self` must have been allocated by `store`.
+    /// -   `self` must still be valid.
+    /// -   No access through a mutable reference to the header must overlap with accesses through the result.
+    /// -   The reference is only guaranteed to be valid as long as `self` is valid.
+    /// -   The reference is only guaranteed to be valid as long as pointers resolved from `self` are not invalidated.
+    ///     Most notably, unless `store` implements `StoreStable`, any method call on `store`, including other
+    ///     `resolve` calls, may invalidate the reference.
+    #[inline(always)]
+    pub unsafe fn resolve<'a, S>(&self, store: &'a S) -> &'a Head
+    where
+        S: Store<Handle = H>,
+    {
+        //  Safety:
+        //  -   `self.handle` was allocated by `store`, and is still valid, as per pre-conditions.
+        let pointer = unsafe { self.resolve_raw(store) };
+
+        //  Safety:
+        //  -   `pointer` points to a live instance of `Head`, as per pre-conditions.
+        //  -   The resulting reference borrows `store` immutably, guaranteeing it won't be invalidated by moving
+        //      or destroying store, though it may still be invalidated by allocating.
+        unsafe { pointer.as_ref() }
+    }
+
+    /// Resolves the handle to a mutable reference to the header.
+    ///
+    /// #   Safety
+    ///
+    /// -   `self` must have been allocated by `store`.
+    /// -   `self` must still be valid.
+    /// -   No access through any reference to the header must overlap with accesses through the result.
+    /// -   The reference is only guaranteed to be valid as long as `self` is valid.
+    /// -   The reference is only guaranteed to be valid as long as pointers resolved from `self` are not invalidated.
+    ///     Most notably, unless `store` implements `StoreStable`, any method call on `store`, including other
+    ///     `resolve` calls, may invalidate the reference.
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn resolve_mut<'a, S>(&mut self, store: &'a S) -> &'a mut Head
+    where
+        S: Store<Handle = H>,
+    {
+        //  Safety:
+        //  -   `self.handle` was allocated by `store`, and is still valid, as per pre-conditions.
+        let mut pointer = unsafe { self.resolve_raw(store) };
+
+        //  Safety:
+        //  -   `pointer` points to a live instance of `Head`, as per pre-conditions.
+        //  -   The resulting reference borrows `store` immutably, guaranteeing it won't be invalidated by moving
+        //      or destroying store, though it may still be invalidated by allocating.
+        unsafe { pointer.as_mut() }
+    }
+
+    /// Resolves the handle to a non-null pointer to the header.
+    ///
+    /// #   Safety
+    ///
+    /// -   `self` must have been allocated by `store`.
+    /// -   `self` must still be valid.
+    /// -   The pointer is only guaranteed to be valid as long as `self` is valid.
+    /// -   The pointer is only guaranteed to be valid as long as pointers resolved from `self` are not invalidated.
+    ///     Most notably, unless `store` implements `StoreStable`, any method call on `store`, including other
+    ///     `resolve` calls, may invalidate the pointer.
+    #[inline(always)]
+    pub unsafe fn resolve_raw<S>(&self, store: &S) -> NonNull<Head>
+    where
+        S: Store<Handle = H>,
+    {
+        //  Safety:
+        //  -   `self.handle` was allocated by `store`, and is still valid, as per pre-conditions.
+        let pointer = unsafe { store.resolve(self.handle) };
+
+        pointer.cast()
+    }
+}
+
+impl<Head, T, H> HeaderSliceHandle<Head, T, H> {
+    /// Creates a handle from its raw parts.
+    ///
+    /// If `handle` is valid, and associated to a block of memory containing a `Head` followed by a slice of `T`, then
+    /// the resulting handle is valid.
+    #[inline(always)]
+    pub const fn from_raw_parts(handle: H) -> Self {
+        let _marker = PhantomData;
+
+        Self { handle, _marker }
+    }
+
+    /// Decomposes a handle into its raw parts.
+    #[inline(always)]
+    pub fn to_raw_parts(self) -> H {
+        self.handle
+    }
+
+    /// Returns the layout of a block of memory containing a `Head` followed by `length` elements, and the offset of the
+    /// first element within it.
+    #[inline]
+    pub fn layout(length: usize) -> Result<(Layout, usize), LayoutError> {
+        let head = Layout::new::<Head>();
+        let tail = Layout::array::<T>(length)?;
+
+        head.extend(tail)
+    }
+
+    //  Returns the layout, and offset of the slice, of a block of memory containing `length` elements.
+    //
+    //  #   Safety
+    //
+    //  -   A block of memory containing `length` elements must have been allocated, hence its layout was computed
+    //      successfully then.
+    #[inline(always)]
+    unsafe fn allocated_layout(length: usize) -> (Layout, usize) {
+        let layout = Self::layout(length);
+
+        debug_assert!(layout.is_ok());
+
+        //  Safety:
+        //  -   The layout was computed successfully at allocation, as per pre-conditions.
+        unsafe { layout.unwrap_unchecked() }
+    }
+
+    /// Returns the slice following `head`.
+    ///
+    /// #   Safety
+    ///
+    /// -   `head` must be the header of a block of memory associated with a valid handle.
+    /// -   `length` must be the current length of the slice.
+    /// -   No access through a mutable reference to the slice must overlap with accesses through the result.
+    #[inline(always)]
+    pub unsafe fn tail(head: &Head, length: usize) -> &[T] {
+        //  Safety:
+        //  -   `length` is the current length of the slice, as per pre-conditions.
+        let (_, offset) = unsafe { Self::allocated_layout(length) };
+
+        //  Safety:
+        //  -   `offset` is within bounds, since the block of memory was allocated.
+        let first = unsafe { (head as *const Head as *const u8).add(offset) };
+
+        //  Safety:
+        //  -   The pointer is properly aligned, as per the layout.
+        //  -   The pointer is dereferenceable, and points to `length` initialized instances of `T`, as per
+        //      pre-conditions.
+        //  -   The slice is accessible in shared mode, since `head` is, and its lifetime is bound to `head`.
+        unsafe { slice::from_raw_parts(first as *const T, length) }
+    }
+
+    /// Returns the slice following `head`.
+    ///
+    /// #   Safety
+    ///
+    /// -   `head` must be the header of a block of memory associated with a valid handle.
+    /// -   `length` must be the current length of the slice.
+    /// -   No access through any other reference to the slice must overlap with accesses through the result.
+    #[inline(always)]
+    pub unsafe fn tail_mut(head: &mut Head, length: usize) -> &mut [T] {
+        //  Safety:
+        //  -   `length` is the current length of the slice, as per pre-conditions.
+        let (_, offset) = unsafe { Self::allocated_layout(length) };
+
+        //  Safety:
+        //  -   `offset` is within bounds, since the block of memory was allocated.
+        let first = unsafe { (head as *mut Head as *mut u8).add(offset) };
+
+        //  Safety:
+        //  -   The pointer is properly aligned, as per the layout.
+        //  -   The pointer is dereferenceable, and points to `length` initialized instances of `T`, as per
+        //      pre-conditions.
+        //  -   The slice is accessible in exclusive mode, since `head` is, and its lifetime is bound to `head`.
+        unsafe { slice::from_raw_parts_mut(first as *mut T, length) }
+    }
+}
+
+impl<Head, T, H: Copy> Clone for HeaderSliceHandle<Head, T, H> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Head, T, H: Copy> Copy for HeaderSliceHandle<Head, T, H> {}
+
+//
+//  Implementation
+//
+
+impl<Head, T, H> HeaderSliceHandle<Head, T, H> {
+    //  Returns the layout of `length` elements, or that of `Head` alone if it overflows, for error reporting.
+    #[cold]
+    fn layout_or_head(length: usize) -> Layout {
+        Self::layout(length).map_or(Layout::new::<Head>(), |(layout, _)| layout)
+    }
+
+    //  Initializes the elements in `range`, calling `element` with the index of each.
+    //
+    //  #   Safety
+    //
+    //  -   `pointer` must point to a writeable block of memory fitting the layout of `range.end` elements.
+    //  -   `offset` must be the offset of the first element within this layout.
+    unsafe fn initialize(
+        pointer: NonNull<u8>,
+        offset: usize,
+        range: Range<usize>,
+        mut element: impl FnMut(usize) -> T,
+    ) {
+        //  Safety:
+        //  -   `offset` is within bounds, as per pre-conditions.
+        let first = unsafe { pointer.as_ptr().add(offset) as *mut T };
+
+        for index in range {
+            //  Safety:
+            //  -   `index` is within bounds, as `index < range.end`.
+            //  -   The pointer is valid for writes, and properly aligned, as per the layout.
+            unsafe { ptr::write(first.add(index), element(index)) };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::store::InlineBumpStore;
+
+    use super::*;
+
+    type TestStore = InlineBumpStore<u8, [u64; 16]>;
+
+    type TestHandle = HeaderSliceHandle<u16, u32, u8>;
+
+    #[test]
+    fn layout() {
+        let (layout, offset) = TestHandle::layout(3).unwrap();
+
+        assert_eq!(4, offset);
+        assert_eq!(16, layout.size());
+        assert_eq!(4, layout.align());
+    }
+
+    #[test]
+    fn new_grow_deallocate() {
+        let store = TestStore::default();
+
+        let mut handle = TestHandle::new(2, 2, |index| index as u32 * 10, &store);
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        //  -   The length of the slice is 2.
+        let (head, tail) = unsafe {
+            let head = handle.resolve(&store);
+
+            (*head, TestHandle::tail(head, 2))
+        };
+
+        assert_eq!(2, head);
+        assert_eq!([0, 10], tail);
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        //  -   The length of the slice is 2.
+        //  -   No reference to the block of memory is active.
+        unsafe { handle.grow(2, 4, |index| index as u32 * 100, &store) };
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        //  -   The length of the slice is 4.
+        let tail = unsafe {
+            let head = handle.resolve_mut(&store);
+
+            *head = 4;
+
+            let tail = TestHandle::tail_mut(head, 4);
+
+            tail[0] = 1;
+
+            &*tail
+        };
+
+        assert_eq!([1, 10, 200, 300], tail);
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        //  -   The length of the slice is 4.
+        //  -   No reference to the block of memory is active.
+        let head = unsafe { handle.deallocate(4, &store) };
+
+        assert_eq!(4, head);
+    }
+} // mod tests
@@ -0,0 +1,57 @@
+//! Compile-time layout assertions, for FFI.
+//!
+//! Sharing store-backed structures with C, or across processes, requires the Rust and C definitions of the structures
+//! to agree on their layouts. The types of this crate document their layout guarantees, if any, and the helpers of this
+//! module check, at compile-time, that a mirror definition matches.
+
+use core::mem;
+
+/// Asserts that `T` has the given `size` and `align`ment.
+///
+/// When evaluated in a constant context, a mismatch is diagnosed at compile-time:
+///
+/// ```
+/// use storage::{extension::{layout::assert_layout, typed::TypedHandle}, store::AllocatorHandle};
+///
+/// # #[cfg(not(feature = "coercible-metadata"))]
+/// const _: () = assert_layout::<TypedHandle<u32, AllocatorHandle>>(
+///     core::mem::size_of::<*mut u8>(),
+///     core::mem::align_of::<*mut u8>(),
+/// );
+/// ```
+///
+/// ```compile_fail
+/// use storage::{extension::{layout::assert_layout, typed::TypedHandle}};
+///
+/// const _: () = assert_layout::<TypedHandle<[u32], u8>>(1, 1);
+/// ```
+pub const fn assert_layout<T>(size: usize, align: usize) {
+    assert!(mem::size_of::<T>() == size, "mismatched size");
+    assert!(mem::align_of::<T>() == align, "mismatched alignment");
+}
+
+/// Asserts that `T` and `U` have the same size and alignment.
+///
+/// When evaluated in a constant context, a mismatch is diagnosed at compile-time:
+///
+/// ```
+/// use storage::extension::{layout::assert_same_layout, typed::TypedHandle};
+///
+/// #[repr(C)]
+/// struct SliceHandle {
+///     handle: u16,
+///     length: usize,
+/// }
+///
+/// # #[cfg(not(feature = "coercible-metadata"))]
+/// const _: () = assert_same_layout::<TypedHandle<[u32], u16>, SliceHandle>();
+/// ```
+///
+/// ```compile_fail
+/// use storage::extension::{layout::assert_same_layout, typed::TypedHandle};
+///
+/// const _: () = assert_same_layout::<TypedHandle<[u32], u16>, u16>();
+/// ```
+pub const fn assert_same_layout<T, U>() {
+    assert_layout::<T>(mem::size_of::<U>(), mem::align_of::<U>());
+}
@@ -0,0 +1,374 @@
+//! A typed, unique handle, for stores whose handles are not `Copy`.
+
+use core::{
+    alloc::{AllocError, Layout},
+    marker::Unsize,
+    mem,
+    ptr::{self, Alignment, NonNull},
+};
+
+use crate::{alloc, extension::typed_metadata::TypedMetadata, interface::StoreOwned};
+
+/// A typed, unique handle, for stores implementing `StoreOwned`.
+///
+/// Unlike `UniqueHandle`, the untyped handle need only be `Clone`, and is never cloned: it is moved into `deallocate`,
+/// and updated in place by `try_grow` and `try_shrink`. As every `Store` is a `StoreOwned`, `OwnedHandle` may be used
+/// with any store.
+pub struct OwnedHandle<T: ?Sized, H> {
+    handle: H,
+    metadata: TypedMetadata<T>,
+}
+
+impl<T, H: Clone> OwnedHandle<T, H> {
+    /// Attempts to create a dangling handle.
+    ///
+    /// Returns an error on allocation failure.
+    #[inline(always)]
+    pub fn try_dangling<S>(store: &S) -> Result<Self, AllocError>
+    where
+        S: StoreOwned<Handle = H>,
+    {
+        let handle = store.dangling(Alignment::of::<T>())?;
+
+        Ok(Self::from_raw_parts(handle, TypedMetadata::new()))
+    }
+
+    /// Creates a new handle, pointing to a `T`.
+    ///
+    /// Calls `handle_alloc_error` on allocation failure.
+    #[inline(always)]
+    pub fn new<S>(value: T, store: &S) -> Self
+    where
+        S: StoreOwned<Handle = H>,
+    {
+        let Ok(this) = Self::try_new(value, store) else {
+            alloc::handle_alloc_error(Layout::new::<T>())
+        };
+
+        this
+    }
+
+    /// Attempts to create a new handle, pointing to a `T`.
+    #[inline(always)]
+    pub fn try_new<S>(value: T, store: &S) -> Result<Self, AllocError>
+    where
+        S: StoreOwned<Handle = H>,
+    {
+        let this = Self::try_allocate(store)?;
+
+        //  Safety:
+        //  -   `this.handle` was just allocated by `store`, and is thus valid.
+        let pointer = unsafe { this.resolve_raw(store) };
+
+        //  Safety:
+        //  -   `pointer` points to a writeable, sufficiently aligned and sized, memory area.
+        //  -   `pointer` has exclusive access to the memory area it points to.
+        unsafe { ptr::write(pointer.as_ptr(), value) };
+
+        Ok(this)
+    }
+
+    /// Attempts to allocate a new handle, with enough space for `T`.
+    ///
+    /// The allocated memory is left uninitialized.
+    #[inline(always)]
+    pub fn try_allocate<S>(store: &S) -> Result<Self, AllocError>
+    where
+        S: StoreOwned<Handle = H>,
+    {
+        let (handle, _) = store.allocate(Layout::new::<T>())?;
+
+        Ok(Self::from_raw_parts(handle, TypedMetadata::new()))
+    }
+}
+
+impl<T: ?Sized, H: Clone> OwnedHandle<T, H> {
+    /// Creates a handle from raw parts.
+    ///
+    /// -   If `handle` is valid, and associated to a block of memory which fits an instance of `T`, then the resulting
+    ///     typed handle is valid.
+    /// -   If `handle` is invalid, then the resulting typed handle is invalid.
+    /// -   If `handle` is valid and `metadata` does not fit the block of memory associated with it, then the resulting
+    ///     typed handle is invalid.
+    #[inline(always)]
+    pub const fn from_raw_parts(handle: H, metadata: TypedMetadata<T>) -> Self {
+        Self { handle, metadata }
+    }
+
+    /// Decomposes a handle into raw parts.
+    #[inline(always)]
+    pub fn into_raw_parts(self) -> (H, TypedMetadata<T>) {
+        (self.handle, self.metadata)
+    }
+
+    /// Deallocates the memory associated with the handle.
+    ///
+    /// #   Safety
+    ///
+    /// -   `self` must have been allocated by `store`.
+    /// -   `self` must still be valid.
+    #[inline(always)]
+    pub unsafe fn deallocate<S>(self, store: &S)
+    where
+        S: StoreOwned<Handle = H>,
+    {
+        //  Safety:
+        //  -   `self.handle` was allocated by `store`, and is still valid, as per pre-conditions.
+        let pointer = unsafe { self.resolve_raw(store) };
+
+        //  Safety:
+        //  -   `pointer` has valid metadata for `T`.
+        let layout = unsafe { Layout::for_value_raw(pointer.as_ptr() as *const T) };
+
+        //  Safety:
+        //  -   `self.handle` was allocated by `store`, and is still valid, as per pre-conditions.
+        //  -   `layout` fits the block of memory associated with `self.handle`, as for `TypedHandle::deallocate`.
+        unsafe { store.deallocate(self.handle, layout) };
+    }
+
+    /// Resolves the handle to a reference.
+    ///
+    /// #   Safety
+    ///
+    /// -   `self` must have been allocated by `store`.
+    /// -   `self` must still be valid.
+    /// -   `self` must be associated to a block of memory containing a valid instance of `T`.
+    /// -   The reference is only guaranteed to be valid as long as `self` is valid.
+    /// -   The reference is only guaranteed to be valid as long as pointers resolved from `self` are not invalidated.
+    ///     Most notably, unless `store` implements `StoreStable`, any method call on `store`, including other
+    ///     `resolve` calls, may invalidate the reference.
+    #[inline(always)]
+    pub unsafe fn resolve<'a, S>(&'a self, store: &'a S) -> &'a T
+    where
+        S: StoreOwned<Handle = H>,
+    {
+        //  Safety:
+        //  -   As per pre-conditions.
+        let pointer = unsafe { self.resolve_raw(store) };
+
+        //  Safety:
+        //  -   `pointer` points to a live instance of `T`, as per pre-conditions.
+        //  -   The resulting reference borrows `self` immutably, guaranteeing that no mutable reference exists.
+        unsafe { pointer.as_ref() }
+    }
+
+    /// Resolves the handle to a mutable reference.
+    ///
+    /// #   Safety
+    ///
+    /// Same as `resolve`.
+    #[allow(clippy::mut_from_ref)]
+    #[inline(always)]
+    pub unsafe fn resolve_mut<'a, S>(&'a mut self, store: &'a S) -> &'a mut T
+    where
+        S: StoreOwned<Handle = H>,
+    {
+        //  Safety:
+        //  -   As per pre-conditions.
+        let mut pointer = unsafe { self.resolve_raw(store) };
+
+        //  Safety:
+        //  -   `pointer` points to a live instance of `T`, as per pre-conditions.
+        //  -   The resulting reference borrows `self` mutably, guaranteeing that no other reference exists.
+        unsafe { pointer.as_mut() }
+    }
+
+    /// Resolves the handle to a pointer.
+    ///
+    /// #   Safety
+    ///
+    /// -   `self` must have been allocated by `store`, or be dangling.
+    /// -   `self` must still be valid.
+    /// -   The pointer is only guaranteed to be valid as long as pointers resolved from `self` are not invalidated.
+    #[inline(always)]
+    pub unsafe fn resolve_raw<S>(&self, store: &S) -> NonNull<T>
+    where
+        S: StoreOwned<Handle = H>,
+    {
+        //  Safety:
+        //  -   As per pre-conditions.
+        let pointer = unsafe { store.resolve(&self.handle) };
+
+        NonNull::from_raw_parts(pointer.cast(), self.metadata.get())
+    }
+
+    /// Coerces the handle into another.
+    #[inline(always)]
+    pub fn coerce<U: ?Sized>(self) -> OwnedHandle<U, H>
+    where
+        T: Unsize<U>,
+    {
+        let metadata = self.metadata.coerce();
+
+        OwnedHandle::from_raw_parts(self.handle, metadata)
+    }
+}
+
+impl<T, H: Clone> OwnedHandle<[T], H> {
+    /// Attempts to create a dangling handle, with a length of 0.
+    ///
+    /// Returns an error on allocation failure.
+    #[inline(always)]
+    pub fn try_dangling_slice<S>(store: &S) -> Result<Self, AllocError>
+    where
+        S: StoreOwned<Handle = H>,
+    {
+        let handle = store.dangling(Alignment::of::<T>())?;
+
+        Ok(Self::from_raw_parts(handle, TypedMetadata::from_metadata(0)))
+    }
+
+    /// Attempts to allocate a new handle, with enough space for `size` elements `T`.
+    ///
+    /// The allocated memory is left uninitialized.
+    #[inline(always)]
+    pub fn try_allocate_slice<S>(size: usize, store: &S) -> Result<Self, AllocError>
+    where
+        S: StoreOwned<Handle = H>,
+    {
+        let layout = Self::layout(size)?;
+
+        let (handle, _) = store.allocate(layout)?;
+
+        Ok(Self::from_raw_parts(handle, TypedMetadata::from_metadata(size)))
+    }
+
+    /// Returns whether the memory area associated to `self` may not contain any `T`.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of elements the memory area associated to `self` may contain.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.metadata.get()
+    }
+
+    /// Attempts to grow the block of memory associated with the handle, to `new_size` elements.
+    ///
+    /// On success, the extra memory is left uninitialized. On failure, the handle is left untouched.
+    ///
+    /// #   Safety
+    ///
+    /// -   `self` must have been allocated by `store`.
+    /// -   `self` must still be valid.
+    /// -   `new_size` must be greater than or equal to `self.len()`.
+    #[inline]
+    pub unsafe fn try_grow<S>(&mut self, new_size: usize, store: &S) -> Result<(), AllocError>
+    where
+        S: StoreOwned<Handle = H>,
+    {
+        debug_assert!(new_size >= self.len());
+
+        let old_layout = Self::layout(self.len())?;
+        let new_layout = Self::layout(new_size)?;
+
+        //  Safety:
+        //  -   `self.handle` was allocated by `store`, and is still valid, as per pre-conditions.
+        //  -   `old_layout` fits the block of memory associated to `self.handle`, by construction.
+        //  -   `new_layout`'s size is greater than or equal to the size of `old_layout`, as per pre-conditions.
+        unsafe { store.grow(&mut self.handle, old_layout, new_layout)? };
+
+        self.metadata = TypedMetadata::from_metadata(new_size);
+
+        Ok(())
+    }
+
+    /// Attempts to shrink the block of memory associated with the handle, to `new_size` elements.
+    ///
+    /// On failure, the handle is left untouched.
+    ///
+    /// #   Safety
+    ///
+    /// -   `self` must have been allocated by `store`.
+    /// -   `self` must still be valid.
+    /// -   `new_size` must be less than or equal to `self.len()`.
+    #[inline]
+    pub unsafe fn try_shrink<S>(&mut self, new_size: usize, store: &S) -> Result<(), AllocError>
+    where
+        S: StoreOwned<Handle = H>,
+    {
+        debug_assert!(new_size <= self.len());
+
+        let old_layout = Self::layout(self.len())?;
+        let new_layout = Self::layout(new_size)?;
+
+        //  Safety:
+        //  -   `self.handle` was allocated by `store`, and is still valid, as per pre-conditions.
+        //  -   `old_layout` fits the block of memory associated to `self.handle`, by construction.
+        //  -   `new_layout`'s size is less than or equal to the size of `old_layout`, as per pre-conditions.
+        unsafe { store.shrink(&mut self.handle, old_layout, new_layout)? };
+
+        self.metadata = TypedMetadata::from_metadata(new_size);
+
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn layout(size: usize) -> Result<Layout, AllocError> {
+        let size = mem::size_of::<T>().checked_mul(size).ok_or(AllocError)?;
+
+        Layout::from_size_align(size, mem::align_of::<T>()).map_err(|_| AllocError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::store::InlineBumpStore;
+
+    use super::*;
+
+    #[test]
+    fn new_resolve() {
+        let store = InlineBumpStore::<u8, [u64; 4]>::default();
+
+        let mut handle = OwnedHandle::new([1u32, 2], &store);
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, is still valid, and points to a valid `[u32; 2]`.
+        unsafe { handle.resolve_mut(&store)[1] = 3 };
+
+        let handle: OwnedHandle<[u32], _> = handle.coerce();
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, is still valid, and points to a valid `[u32]`.
+        assert_eq!([1, 3], unsafe { handle.resolve(&store) });
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        unsafe { handle.deallocate(&store) };
+    }
+
+    #[test]
+    fn slice() {
+        let store = InlineBumpStore::<u8, [u64; 4]>::default();
+
+        let mut handle = OwnedHandle::<[u16], _>::try_dangling_slice(&store).unwrap();
+
+        assert!(handle.is_empty());
+
+        handle = OwnedHandle::try_allocate_slice(2, &store).unwrap();
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid, and 8 is greater than 2.
+        unsafe { handle.try_grow(8, &store).unwrap() };
+
+        assert_eq!(8, handle.len());
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid, and 64 is greater than 8.
+        assert!(unsafe { handle.try_grow(64, &store) }.is_err());
+        assert_eq!(8, handle.len());
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid, and 4 is less than 8.
+        unsafe { handle.try_shrink(4, &store).unwrap() };
+
+        assert_eq!(4, handle.len());
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        unsafe { handle.deallocate(&store) };
+    }
+} // mod tests
@@ -0,0 +1,206 @@
+//! A handle to a sub-range of a block of memory.
+//!
+//! Header and payload protocols, such as network packets, commonly hand out the payload of a buffer on its own, while
+//! the buffer as a whole remains owned elsewhere. A `SubHandle` refers to such a sub-range: it resolves independently,
+//! by offsetting the resolved pointer of its parent, but it is never deallocated on its own.
+//!
+//! #   Invalidation
+//!
+//! A `SubHandle` borrows the validity of its parent handle:
+//!
+//! -   It is valid for as long as the parent handle is valid, and invalidated alongside it.
+//! -   It is invalidated when the block of memory of the parent is deallocated, grown, or shrunk, even if the store
+//!     returns the very same handle, as the block of memory may have been moved, or the sub-range may no longer fit.
+//! -   The pointers it resolves to follow the same rules as the pointers the parent handle resolves to; notably, unless
+//!     the store implements `StoreStable`, any method call on the store may invalidate them.
+
+use core::{alloc::Layout, ptr::NonNull};
+
+use crate::interface::Store;
+
+/// A handle to a sub-range of the block of memory associated to a parent handle.
+///
+/// A sub-handle may be invalid, see the module documentation for the invalidation rules.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct SubHandle<H> {
+    parent: H,
+    offset: usize,
+    layout: Layout,
+}
+
+impl<H: Copy> SubHandle<H> {
+    /// Creates a sub-handle to the range of `layout.size()` bytes at `offset` within the block of memory of `parent`.
+    ///
+    /// Returns `None` if the range does not fit within `parent_layout`, or if `offset` is not suitably aligned for
+    /// `layout` in a block of memory aligned as per `parent_layout`.
+    #[inline(always)]
+    pub const fn new(parent: H, parent_layout: Layout, offset: usize, layout: Layout) -> Option<Self> {
+        let Some(end) = offset.checked_add(layout.size()) else {
+            return None;
+        };
+
+        if end > parent_layout.size() {
+            return None;
+        }
+
+        if layout.align() > parent_layout.align() || !offset.is_multiple_of(layout.align()) {
+            return None;
+        }
+
+        Some(Self { parent, offset, layout })
+    }
+
+    /// Creates a sub-handle to the range of `layout.size()` bytes at `offset` within the block of memory of `parent`.
+    ///
+    /// #   Safety
+    ///
+    /// -   The range must fit within the block of memory associated to `parent`.
+    /// -   `offset` must be suitably aligned for `layout`, within the block of memory associated to `parent`.
+    #[inline(always)]
+    pub const unsafe fn new_unchecked(parent: H, offset: usize, layout: Layout) -> Self {
+        Self { parent, offset, layout }
+    }
+
+    /// Creates a sub-handle to a sub-range of `self`, at `offset` within it.
+    ///
+    /// Returns `None` if the range does not fit within `self`, or is not suitably aligned, as per `new`.
+    #[inline(always)]
+    pub const fn sub(&self, offset: usize, layout: Layout) -> Option<Self> {
+        let Some(this) = Self::new(self.parent, self.layout, offset, layout) else {
+            return None;
+        };
+
+        //  Cannot overflow, since `self.offset + self.layout.size()` did not, and `offset < self.layout.size()`.
+        let offset = self.offset + this.offset;
+
+        Some(Self { offset, ..this })
+    }
+
+    /// Returns the parent handle.
+    #[inline(always)]
+    pub const fn parent(&self) -> H {
+        self.parent
+    }
+
+    /// Returns the offset of the sub-range within the block of memory of the parent.
+    #[inline(always)]
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the layout of the sub-range.
+    #[inline(always)]
+    pub const fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    /// Resolves the sub-handle to a pointer to the first byte of the sub-range.
+    ///
+    /// #   Safety
+    ///
+    /// -   The parent handle must have been allocated by `store`.
+    /// -   `self` must still be valid, as per the invalidation rules of the module documentation.
+    /// -   The pointer is only guaranteed to be valid as long as pointers resolved from the parent handle are.
+    #[inline(always)]
+    pub unsafe fn resolve<S>(&self, store: &S) -> NonNull<u8>
+    where
+        S: Store<Handle = H>,
+    {
+        //  Safety:
+        //  -   `self.parent` was allocated by `store`, and is still valid, as per pre-conditions.
+        let pointer = unsafe { store.resolve(self.parent) };
+
+        //  Safety:
+        //  -   `self.offset` is within the block of memory, as the sub-range fits within it.
+        unsafe { pointer.add(self.offset) }
+    }
+
+    /// Resolves the sub-handle to a pointer to the sub-range.
+    ///
+    /// #   Safety
+    ///
+    /// -   The parent handle must have been allocated by `store`.
+    /// -   `self` must still be valid, as per the invalidation rules of the module documentation.
+    /// -   The pointer is only guaranteed to be valid as long as pointers resolved from the parent handle are.
+    #[inline(always)]
+    pub unsafe fn resolve_slice<S>(&self, store: &S) -> NonNull<[u8]>
+    where
+        S: Store<Handle = H>,
+    {
+        //  Safety:
+        //  -   As per pre-conditions.
+        let pointer = unsafe { self.resolve(store) };
+
+        NonNull::slice_from_raw_parts(pointer, self.layout.size())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::store::InlineBumpStore;
+
+    use super::*;
+
+    type TestStore = InlineBumpStore<u8, [u64; 8]>;
+
+    #[test]
+    fn new() {
+        let parent = Layout::from_size_align(16, 4).unwrap();
+
+        let header = SubHandle::new(0u8, parent, 0, Layout::new::<u32>()).unwrap();
+
+        assert_eq!(0, header.offset());
+        assert_eq!(Layout::new::<u32>(), header.layout());
+
+        //  Out of bounds.
+        assert_eq!(None, SubHandle::new(0u8, parent, 12, Layout::new::<u64>()));
+        assert_eq!(None, SubHandle::new(0u8, parent, usize::MAX, Layout::new::<u8>()));
+
+        //  Misaligned, either within the parent, or for the parent.
+        assert_eq!(None, SubHandle::new(0u8, parent, 2, Layout::new::<u32>()));
+        assert_eq!(None, SubHandle::new(0u8, parent, 8, Layout::new::<u64>()));
+    }
+
+    #[test]
+    fn sub() {
+        let parent = Layout::from_size_align(16, 4).unwrap();
+
+        let payload = SubHandle::new(0u8, parent, 4, Layout::from_size_align(12, 4).unwrap()).unwrap();
+        let tail = payload.sub(8, Layout::new::<u32>()).unwrap();
+
+        assert_eq!(12, tail.offset());
+        assert_eq!(None, payload.sub(12, Layout::new::<u8>()));
+    }
+
+    #[test]
+    fn resolve() {
+        let store = TestStore::default();
+
+        let packet = Layout::from_size_align(16, 4).unwrap();
+        let (handle, _) = store.allocate(packet).unwrap();
+
+        let header = SubHandle::new(handle, packet, 0, Layout::new::<u32>()).unwrap();
+        let payload = SubHandle::new(handle, packet, 4, Layout::from_size_align(12, 1).unwrap()).unwrap();
+
+        //  Safety:
+        //  -   `header` and `payload` are valid, as `handle` was allocated by `store`, and is still valid.
+        //  -   `header` and `payload` do not overlap.
+        unsafe {
+            header.resolve(&store).cast::<u32>().write(12);
+            payload.resolve_slice(&store).as_mut().fill(7);
+        }
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        //  -   The block of memory was entirely initialized, through the sub-handles.
+        let bytes = unsafe { store.resolve(handle).cast::<[u8; 16]>().read() };
+
+        assert_eq!(12u32.to_ne_bytes(), bytes[..4]);
+        assert_eq!([7; 12], bytes[4..]);
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, with `packet`, and is still valid.
+        //  -   `header` and `payload` are invalidated alongside it.
+        unsafe { store.deallocate(handle, packet) };
+    }
+} // mod tests
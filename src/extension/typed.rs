@@ -12,14 +12,21 @@ use core::ops::CoerceUnsized;
 
 use crate::{
     alloc,
-    extension::typed_metadata::TypedMetadata,
-    interface::{Store, StoreDangling},
+    extension::{typed_metadata::TypedMetadata, unique::UniqueHandle},
+    interface::{Store, StoreDangling, StoreEnumerate, StoreOffsetHandle, StoreSharing},
 };
 
 /// Arbitrary typed handle, for type safety, and coercion.
 ///
 /// A typed handle may be dangling, or may be invalid. It is the responsibility of the user to ensure that the typed
 /// handle is valid when necessary.
+///
+/// #   Layout
+///
+/// The handle is `#[repr(C)]`: the untyped handle, followed by the `TypedMetadata`. Unless the `coercible-metadata`
+/// feature is enabled, the typed handle of a sized `T` thus has the layout of `H`, and the typed handle of a `[T]` that
+/// of a C struct containing `H` followed by the length, as a `size_t`.
+#[repr(C)]
 pub struct TypedHandle<T: ?Sized, H> {
     handle: H,
     metadata: TypedMetadata<T>,
@@ -77,6 +84,7 @@ impl<T, H: Copy> TypedHandle<T, H> {
     where
         S: Store<Handle = H>,
     {
+        //  The actual size of the block of memory is irrelevant: `Layout::new::<T>()` fits it regardless.
         let (handle, _) = store.allocate(Layout::new::<T>())?;
 
         //  Safety:
@@ -158,6 +166,175 @@ impl<T, H: Copy> TypedHandle<T, H> {
 
         Ok(Self { handle, metadata })
     }
+
+    /// Casts the handle into a handle to a `U`, reinterpreting the block of memory in place.
+    ///
+    /// If `self` is valid, the resulting typed handle is valid; otherwise it is invalid.
+    ///
+    /// #   Safety
+    ///
+    /// -   `U` must have the same layout as `T`: same size, and same alignment.
+    /// -   Resolving the resulting handle to a reference requires that the block of memory contains a valid instance
+    ///     of `U`, which the bytes of the instance of `T` may, or may not, be.
+    #[inline(always)]
+    pub const unsafe fn cast<U>(&self) -> TypedHandle<U, H> {
+        debug_assert!(mem::size_of::<T>() == mem::size_of::<U>());
+        debug_assert!(mem::align_of::<T>() == mem::align_of::<U>());
+
+        let handle = self.handle;
+        let metadata = TypedMetadata::new();
+
+        TypedHandle { handle, metadata }
+    }
+
+    /// Coerces the handle into another, checking that the resulting typed handle fits its block of memory.
+    ///
+    /// The layout of the block of memory is queried from `store`, amongst the live allocations it enumerates. This is
+    /// meant as a debugging aid, to catch typed handles constructed incorrectly -- for example via `from_raw_parts` --
+    /// as early as possible, typically in tests.
+    ///
+    /// Returns an error if `self` is not associated to any live block of memory of `store`, unless the value is
+    /// zero-sized, or if the layout of the value does not fit the block of memory.
+    pub fn try_coerce_checked<U: ?Sized, S>(&self, store: &S) -> Result<TypedHandle<U, H>, CoerceError>
+    where
+        T: Unsize<U>,
+        S: StoreEnumerate<Handle = H>,
+        H: PartialEq,
+    {
+        let coerced = self.coerce::<U>();
+
+        let pointer: *const U = ptr::from_raw_parts(ptr::null::<()>(), coerced.metadata.get());
+
+        //  Safety:
+        //  -   The metadata of `pointer` was obtained by unsizing a `T`, hence is valid, and describes a value of the
+        //      same size as `T`, which thus fits in `isize`.
+        let value = unsafe { Layout::for_value_raw(pointer) };
+
+        if value.size() == 0 {
+            return Ok(coerced);
+        }
+
+        let mut block = None;
+
+        store.for_each_handle(|handle, layout| {
+            if handle == self.handle {
+                block = Some(layout);
+            }
+        });
+
+        let block = block.ok_or(CoerceError::Unknown)?;
+
+        if value.size() > block.size() || value.align() > block.align() {
+            return Err(CoerceError::Mismatch { block, value });
+        }
+
+        Ok(coerced)
+    }
+
+    /// Swaps the values associated to `a` and `b`, which may have been allocated by different stores.
+    ///
+    /// `a` and `b` may be copies of the same handle, in which case the values are left untouched.
+    ///
+    /// #   Safety
+    ///
+    /// -   `a` must have been allocated by `store_a`, and `b` by `store_b`.
+    /// -   `a` and `b` must still be valid.
+    /// -   `a` and `b` must be associated to blocks of memory containing valid instances of `T`.
+    /// -   No reference to either instance of `T` must be active.
+    /// -   If `store_a` and `store_b` may resolve to the same blocks of memory, for example because they are the same
+    ///     store, or parts of the same sharing set, then resolving `b` must not invalidate the pointer resolved from
+    ///     `a`, as guaranteed by `StoreStable`.
+    ///
+    /// In debug builds, the values are checked to be either the same, or disjoint.
+    pub unsafe fn swap_values<S1, S2, H2>(a: Self, store_a: &S1, b: TypedHandle<T, H2>, store_b: &S2)
+    where
+        S1: Store<Handle = H>,
+        S2: Store<Handle = H2>,
+        H2: Copy,
+    {
+        //  Safety:
+        //  -   `a` was allocated by `store_a`, and is still valid, as per pre-conditions.
+        let pointer_a = unsafe { a.resolve_raw(store_a) };
+
+        //  Safety:
+        //  -   `b` was allocated by `store_b`, and is still valid, as per pre-conditions.
+        //  -   `pointer_a` is not invalidated, as per pre-conditions.
+        let pointer_b = unsafe { b.resolve_raw(store_b) };
+
+        if pointer_a == pointer_b {
+            return;
+        }
+
+        //  Safety:
+        //  -   `pointer_a` and `pointer_b` point to distinct valid instances of `T`, with no active reference, as per
+        //      pre-conditions.
+        unsafe { Self::swap_resolved(pointer_a, pointer_b) };
+    }
+
+    /// Swaps the values associated to `a` and `b`, which may have been allocated by different instances of a sharing
+    /// store, as per `swap_values`.
+    ///
+    /// If `store_a` and `store_b` belong to the same sharing set, as per their sharing tokens, and `a` and `b` are the
+    /// same handle, then the values are left untouched without resolving either handle.
+    ///
+    /// #   Safety
+    ///
+    /// -   As per `swap_values`.
+    ///
+    /// In debug builds, the values are checked to be disjoint, unless both stores belong to the same sharing set, in
+    /// which case they may also be the same.
+    pub unsafe fn swap_values_sharing<S>(a: Self, store_a: &S, b: Self, store_b: &S)
+    where
+        S: Store<Handle = H> + StoreSharing,
+        H: Eq,
+    {
+        let sharing = store_a.sharing_token() == store_b.sharing_token();
+
+        if sharing && a.handle == b.handle {
+            return;
+        }
+
+        //  Safety:
+        //  -   `a` was allocated by `store_a`, and is still valid, as per pre-conditions.
+        let pointer_a = unsafe { a.resolve_raw(store_a) };
+
+        //  Safety:
+        //  -   `b` was allocated by `store_b`, and is still valid, as per pre-conditions.
+        //  -   `pointer_a` is not invalidated, as per pre-conditions.
+        let pointer_b = unsafe { b.resolve_raw(store_b) };
+
+        debug_assert!(
+            sharing || pointer_a != pointer_b,
+            "{pointer_a:?} must not be resolved by stores of distinct sharing sets"
+        );
+
+        if pointer_a == pointer_b {
+            return;
+        }
+
+        //  Safety:
+        //  -   `pointer_a` and `pointer_b` point to distinct valid instances of `T`, with no active reference, as per
+        //      pre-conditions.
+        unsafe { Self::swap_resolved(pointer_a, pointer_b) };
+    }
+
+    //  #   Safety
+    //
+    //  -   `pointer_a` and `pointer_b` must point to distinct valid instances of `T`.
+    //  -   No reference to either instance of `T` must be active.
+    unsafe fn swap_resolved(pointer_a: NonNull<T>, pointer_b: NonNull<T>) {
+        debug_assert!(
+            pointer_a.addr().get().abs_diff(pointer_b.addr().get()) >= mem::size_of::<T>(),
+            "{pointer_a:?} and {pointer_b:?} must point to disjoint values"
+        );
+
+        //  Safety:
+        //  -   `pointer_a` and `pointer_b` are valid for reads and writes of a `T`, and properly aligned, as they point
+        //      to valid instances of `T`, as per pre-conditions.
+        //  -   `pointer_a` and `pointer_b` point to disjoint values, as two distinct live values of `T` cannot overlap.
+        //  -   No reference to either value is active, as per pre-conditions.
+        unsafe { ptr::swap_nonoverlapping(pointer_a.as_ptr(), pointer_b.as_ptr(), 1) };
+    }
 }
 
 impl<T: ?Sized, H: Copy> TypedHandle<T, H> {
@@ -168,15 +345,29 @@ impl<T: ?Sized, H: Copy> TypedHandle<T, H> {
     /// -   If `handle` is invalid, then the resulting typed handle is invalid.
     /// -   If `handle` is valid and `metadata` does not fit the block of memory associated with it, then the resulting
     ///     typed handle is invalid.
+    #[inline(always)]
     pub const fn from_raw_parts(handle: H, metadata: TypedMetadata<T>) -> Self {
         Self { handle, metadata }
     }
 
     /// Decomposes a (possibly wide) pointer into its (raw) handle and metadata components.
+    #[inline(always)]
     pub const fn to_raw_parts(self) -> (H, TypedMetadata<T>) {
         (self.handle, self.metadata)
     }
 
+    /// Converts the handle into a unique handle.
+    ///
+    /// #   Safety
+    ///
+    /// -   No copy of `self` must be used henceforth.
+    #[inline(always)]
+    pub const unsafe fn assume_unique(self) -> UniqueHandle<T, H> {
+        //  Safety:
+        //  -   No copy of `self.handle` will be used henceforth, as per pre-conditions.
+        unsafe { UniqueHandle::from_raw_parts(self.handle, self.metadata) }
+    }
+
     /// Deallocates the memory associated with the handle.
     ///
     /// #   Safety
@@ -201,7 +392,8 @@ impl<T: ?Sized, H: Copy> TypedHandle<T, H> {
         //  Safety:
         //  -   `self.handle` was allocated by `store`, as per pre-conditions.
         //  -   `self.handle` is still valid, as per pre-conditions.
-        //  -   `layout` fits the block of memory associated with `self.handle`.
+        //  -   `layout` fits the block of memory associated with `self.handle`, as it is the layout requested at
+        //      allocation for sized `T`, and covers at most the actual size of the block of memory for slices.
         unsafe { store.deallocate(self.handle, layout) };
     }
 
@@ -434,15 +626,89 @@ impl<T, H: Copy> TypedHandle<[T], H> {
     }
 
     /// Returns whether the memory area associated to `self` may not contain any element.
+    #[inline(always)]
     pub const fn is_empty(&self) -> bool {
         self.metadata.get() == 0
     }
 
     /// Returns the number of elements the memory area associated to `self` may contain.
+    #[inline(always)]
     pub const fn len(&self) -> usize {
         self.metadata.get()
     }
 
+    /// Drops the first `len` elements of the slice, in place.
+    ///
+    /// The block of memory is left allocated, and the dropped elements are left uninitialized.
+    ///
+    /// #   Safety
+    ///
+    /// -   `self` must have been allocated by `store`.
+    /// -   `self` must still be valid.
+    /// -   `len` must be less than or equal to `self.len()`.
+    /// -   The first `len` elements must be initialized, and must not be used afterwards.
+    /// -   No reference to any of the first `len` elements must be active.
+    #[inline]
+    pub unsafe fn drop_initialized_prefix<S>(&self, len: usize, store: &S)
+    where
+        S: Store<Handle = H>,
+    {
+        debug_assert!(len <= self.len());
+
+        if !mem::needs_drop::<T>() {
+            return;
+        }
+
+        //  Safety:
+        //  -   `self` was allocated by `store`, and is still valid, as per pre-conditions.
+        let slice = unsafe { self.resolve_raw(store) };
+
+        //  Safety:
+        //  -   `len <= self.len()`, as per pre-conditions.
+        let prefix = unsafe { slice.get_unchecked_mut(..len) };
+
+        //  Safety:
+        //  -   `prefix` is valid for both reads and writes, and properly aligned.
+        //  -   `prefix` points to `len` initialized elements, as per pre-conditions.
+        //  -   No reference to those elements is active, and they will not be used afterwards, as per pre-conditions.
+        unsafe { ptr::drop_in_place(prefix.as_ptr()) };
+    }
+
+    /// Returns a handle to the element at `index`, computed without resolving `self`.
+    ///
+    /// The resulting handle may only be resolved, and is invalidated alongside `self`, see `StoreOffsetHandle`.
+    ///
+    /// #   Safety
+    ///
+    /// -   `self` must have been allocated by `store`.
+    /// -   `self` must still be valid.
+    /// -   `index` must be strictly less than `self.len()`.
+    #[inline(always)]
+    pub unsafe fn element<S>(&self, index: usize, _store: &S) -> TypedHandle<T, H>
+    where
+        S: StoreOffsetHandle<Handle = H>,
+    {
+        debug_assert!(index < self.len());
+
+        let metadata = TypedMetadata::new();
+
+        //  The element starts at the start of the block of memory, which is not strictly within an empty block.
+        if mem::size_of::<T>() == 0 {
+            return TypedHandle {
+                handle: self.handle,
+                metadata,
+            };
+        }
+
+        //  Safety:
+        //  -   `self.handle` is valid, as per pre-conditions.
+        //  -   The offset is strictly less than `self.len() * mem::size_of::<T>()`, itself at most the size of the
+        //      block of memory, as `index < self.len()` as per pre-conditions.
+        let handle = unsafe { S::offset(self.handle, index * mem::size_of::<T>()) };
+
+        TypedHandle { handle, metadata }
+    }
+
     /// Grows the block of memory associated with the handle.
     ///
     /// On success, all the copies of the handle are invalidated, and the extra memory is left uninitialized. On
@@ -453,6 +719,7 @@ impl<T, H: Copy> TypedHandle<[T], H> {
     /// -   `self` must have been allocated by `store`.
     /// -   `self` must still be valid.
     /// -   `new_size` must be greater than or equal to `self.len()`.
+    #[inline]
     pub const unsafe fn grow<S>(&mut self, new_size: usize, store: &S)
     where
         S: ~const Store<Handle = H>,
@@ -478,6 +745,7 @@ impl<T, H: Copy> TypedHandle<[T], H> {
     /// -   `self` must have been allocated by `store`.
     /// -   `self` must still be valid.
     /// -   `new_size` must be greater than or equal to `self.len()`.
+    #[inline]
     pub const unsafe fn try_grow<S>(&mut self, new_size: usize, store: &S) -> Result<(), AllocError>
     where
         S: ~const Store<Handle = H>,
@@ -521,6 +789,7 @@ impl<T, H: Copy> TypedHandle<[T], H> {
     /// -   `self` must have been allocated by `store`.
     /// -   `self` must still be valid.
     /// -   `new_size` must be greater than or equal to `self.len()`.
+    #[inline]
     pub const unsafe fn grow_zeroed<S>(&mut self, new_size: usize, store: &S)
     where
         S: ~const Store<Handle = H>,
@@ -546,6 +815,7 @@ impl<T, H: Copy> TypedHandle<[T], H> {
     /// -   `self` must have been allocated by `store`.
     /// -   `self` must still be valid.
     /// -   `new_size` must be greater than or equal to `self.len()`.
+    #[inline]
     pub const unsafe fn try_grow_zeroed<S>(&mut self, new_size: usize, store: &S) -> Result<(), AllocError>
     where
         S: ~const Store<Handle = H>,
@@ -588,6 +858,7 @@ impl<T, H: Copy> TypedHandle<[T], H> {
     /// -   `self` must have been allocated by `store`.
     /// -   `self` must still be valid.
     /// -   `new_size` must be less than or equal to `self.len()`.
+    #[inline]
     pub const unsafe fn shrink<S>(&mut self, new_size: usize, store: &S)
     where
         S: ~const Store<Handle = H>,
@@ -612,6 +883,7 @@ impl<T, H: Copy> TypedHandle<[T], H> {
     /// -   `self` must have been allocated by `store`.
     /// -   `self` must still be valid.
     /// -   `new_size` must be less than or equal to `self.len()`.
+    #[inline]
     pub const unsafe fn try_shrink<S>(&mut self, new_size: usize, store: &S) -> Result<(), AllocError>
     where
         S: ~const Store<Handle = H>,
@@ -651,6 +923,7 @@ impl<T, H: Copy> TypedHandle<[T], H> {
 }
 
 impl<T: ?Sized, H: Copy> Clone for TypedHandle<T, H> {
+    #[inline(always)]
     fn clone(&self) -> Self {
         *self
     }
@@ -659,13 +932,29 @@ impl<T: ?Sized, H: Copy> Clone for TypedHandle<T, H> {
 impl<T: ?Sized, H: Copy> Copy for TypedHandle<T, H> {}
 
 #[cfg(feature = "coercible-metadata")]
-impl<T, U: ?Sized, H: Copy> CoerceUnsized<TypedHandle<U, H>> for TypedHandle<T, H> where T: Unsize<U> {}
+#[cfg_attr(docsrs, doc(cfg(feature = "coercible-metadata")))]
+impl<T: ?Sized, U: ?Sized, H: Copy> CoerceUnsized<TypedHandle<U, H>> for TypedHandle<T, H> where T: Unsize<U> {}
+
+/// Error returned by `TypedHandle::try_coerce_checked`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CoerceError {
+    /// The handle is not associated to any live block of memory of the store.
+    Unknown,
+    /// The layout of the value does not fit the block of memory.
+    Mismatch {
+        /// The layout of the block of memory, as recorded by the store.
+        block: Layout,
+        /// The layout of the value.
+        value: Layout,
+    },
+}
 
 //
 //  Implementation
 //
 
 impl<T, H> TypedHandle<[T], H> {
+    #[inline(always)]
     const fn layout(size: usize) -> Result<Layout, AllocError> {
         let Some(size) = mem::size_of::<T>().checked_mul(size) else {
             return Err(AllocError);
@@ -680,3 +969,305 @@ impl<T, H> TypedHandle<[T], H> {
         Ok(layout)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        extension::unique::UniqueHandle,
+        store::{InlineBumpStore, StackBumpBlock, TrackingStore},
+    };
+
+    use super::*;
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct Header {
+        kind: u16,
+        length: u16,
+    }
+
+    #[test]
+    fn cast() {
+        let block = StackBumpBlock::<[u64; 4]>::new();
+        let store = block.create_store::<usize>();
+
+        let bytes = TypedHandle::new([1u16, 4], &store);
+
+        //  Safety:
+        //  -   `Header` has the same layout as `[u16; 2]`.
+        let header: TypedHandle<Header, _> = unsafe { bytes.cast() };
+
+        //  Safety:
+        //  -   `header` was allocated by `store`, and is still valid.
+        //  -   `header` is associated to a valid instance of `Header`.
+        assert_eq!(Header { kind: 1, length: 4 }, unsafe { *header.resolve(&store) });
+
+        //  Safety:
+        //  -   `header` was allocated by `store`, and is still valid.
+        unsafe { header.deallocate(&store) };
+    }
+
+    #[test]
+    fn cast_unique() {
+        let block = StackBumpBlock::<[u64; 4]>::new();
+        let store = block.create_store::<usize>();
+
+        let header = UniqueHandle::new(Header { kind: 2, length: 8 }, &store);
+
+        //  Safety:
+        //  -   `[u16; 2]` has the same layout as `Header`.
+        let bytes: UniqueHandle<[u16; 2], _> = unsafe { header.cast() };
+
+        //  Safety:
+        //  -   `bytes` was allocated by `store`, and is still valid.
+        //  -   `bytes` is associated to a valid instance of `[u16; 2]`.
+        assert_eq!([2, 8], unsafe { *bytes.resolve(&store) });
+
+        //  Safety:
+        //  -   `bytes` was allocated by `store`, and is still valid.
+        unsafe { bytes.deallocate(&store) };
+    }
+
+    #[test]
+    fn unique_round_trip() {
+        let block = StackBumpBlock::<[u64; 4]>::new();
+        let store = block.create_store::<usize>();
+
+        let unique = UniqueHandle::new([1u16, 4], &store);
+
+        let typed: TypedHandle<[u16; 2], _> = unique.into();
+
+        //  Safety:
+        //  -   No copy of `typed` is used henceforth.
+        let mut unique = unsafe { typed.assume_unique() };
+
+        //  Safety:
+        //  -   `unique` was allocated by `store`, and is still valid.
+        //  -   `unique` is associated to a valid instance of `[u16; 2]`.
+        unsafe { unique.resolve_mut(&store)[1] = 8 };
+
+        //  Safety:
+        //  -   `unique` was allocated by `store`, and is still valid.
+        //  -   `unique` is associated to a valid instance of `[u16; 2]`.
+        assert_eq!([1, 8], unsafe { *unique.resolve(&store) });
+
+        //  Safety:
+        //  -   `unique` was allocated by `store`, and is still valid.
+        unsafe { unique.deallocate(&store) };
+    }
+
+    #[test]
+    fn coerce_checked() {
+        let store = TrackingStore::<InlineBumpStore<u8, [u64; 4]>, 4>::new(InlineBumpStore::default());
+
+        let array = TypedHandle::new([1u16, 2, 3], &store);
+
+        let slice: TypedHandle<[u16], _> = array.try_coerce_checked(&store).unwrap();
+
+        assert_eq!(3, slice.len());
+
+        //  Safety:
+        //  -   `array` was allocated by `store`, and is still valid.
+        unsafe { array.deallocate(&store) };
+
+        assert_eq!(
+            Err(CoerceError::Unknown),
+            array.try_coerce_checked::<[u16], _>(&store).map(|_| ())
+        );
+
+        //  Zero-sized values need no block of memory.
+        let empty = TypedHandle::<[u16; 0], _>::dangling(&store);
+
+        assert!(empty.try_coerce_checked::<[u16], _>(&store).is_ok());
+    }
+
+    #[test]
+    fn coerce_checked_mismatch() {
+        let store = TrackingStore::<InlineBumpStore<u8, [u64; 4]>, 4>::new(InlineBumpStore::default());
+
+        let byte = TypedHandle::new(1u8, &store);
+
+        //  Incorrectly constructed: the block of memory only fits a single `u8`.
+        let (handle, _) = byte.to_raw_parts();
+        let array = TypedHandle::<[u8; 4], _>::from_raw_parts(handle, TypedMetadata::new());
+
+        let block = Layout::new::<u8>();
+        let value = Layout::new::<[u8; 4]>();
+
+        assert_eq!(
+            Err(CoerceError::Mismatch { block, value }),
+            array.try_coerce_checked::<[u8], _>(&store).map(|_| ())
+        );
+
+        //  Safety:
+        //  -   `byte` was allocated by `store`, and is still valid.
+        unsafe { byte.deallocate(&store) };
+    }
+
+    #[test]
+    fn element() {
+        fn check<S>(store: &S)
+        where
+            S: Store<Handle = u16> + StoreOffsetHandle,
+        {
+            let slice = TypedHandle::<[u32], _>::allocate_slice(4, store);
+
+            //  Safety:
+            //  -   `slice` was allocated by `store`, and is still valid.
+            unsafe { slice.resolve_raw(store).as_mut().copy_from_slice(&[1, 2, 3, 4]) };
+
+            for (index, expected) in [1u32, 2, 3, 4].into_iter().enumerate() {
+                //  Safety:
+                //  -   `slice` was allocated by `store`, and is still valid.
+                //  -   `index` is within bounds.
+                let element = unsafe { slice.element(index, store) };
+
+                //  Safety:
+                //  -   `element` is valid, as `slice` is.
+                //  -   `element` is associated to an initialized `u32`.
+                assert_eq!(expected, unsafe { *element.resolve(store) });
+            }
+
+            //  Safety:
+            //  -   `slice` was allocated by `store`, and is still valid.
+            unsafe { slice.deallocate(store) };
+        }
+
+        check(&InlineBumpStore::<u16, [u64; 4]>::default());
+
+        let (first, second) = (StackBumpBlock::<[u64; 2]>::new(), StackBumpBlock::<[u64; 2]>::new());
+        let blocks = [&first, &second];
+        let store = StackBumpBlock::chain(&blocks);
+
+        //  Fill the first block, so that the slice is allocated in the second.
+        let _ = TypedHandle::new(0u8, &store);
+
+        check(&store);
+
+        //  Zero-sized elements share the address of the slice.
+        let store = InlineBumpStore::<u16, [u64; 4]>::default();
+        let units = TypedHandle::<[()], _>::allocate_slice(4, &store);
+
+        //  Safety:
+        //  -   `units` was allocated by `store`, and is still valid.
+        //  -   `3` is within bounds.
+        let unit = unsafe { units.element(3, &store) };
+
+        assert_eq!(units.to_raw_parts().0, unit.to_raw_parts().0);
+    }
+
+    #[test]
+    fn drop_initialized_prefix() {
+        use core::cell::Cell;
+
+        struct Counted<'a>(&'a Cell<usize>);
+
+        impl Drop for Counted<'_> {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        let dropped = Cell::new(0);
+        let store = InlineBumpStore::<u16, [u64; 4]>::default();
+
+        let slice = TypedHandle::<[Counted<'_>], _>::allocate_slice(4, &store);
+
+        //  Safety:
+        //  -   `slice` was allocated by `store`, and is still valid.
+        let pointer = unsafe { slice.resolve_raw(&store) };
+
+        for index in 0..3 {
+            //  Safety:
+            //  -   `index` is within bounds, and the element is uninitialized.
+            unsafe { pointer.get_unchecked_mut(index).write(Counted(&dropped)) };
+        }
+
+        //  Safety:
+        //  -   `slice` was allocated by `store`, and is still valid.
+        //  -   The first 3 elements are initialized, and not used afterwards.
+        unsafe { slice.drop_initialized_prefix(3, &store) };
+
+        assert_eq!(3, dropped.get());
+
+        //  Safety:
+        //  -   `slice` was allocated by `store`, and is still valid.
+        unsafe { slice.deallocate(&store) };
+    }
+
+    #[test]
+    fn swap_values() {
+        let block = StackBumpBlock::<[u64; 8]>::new();
+        let store = block.create_store::<u8>();
+
+        let inline = InlineBumpStore::<u16, [u64; 4]>::new();
+
+        let (a, b) = (
+            TypedHandle::new(String::from("a"), &store),
+            TypedHandle::new(String::from("b"), &store),
+        );
+        let c = TypedHandle::new(String::from("c"), &inline);
+
+        //  Safety:
+        //  -   `a` and `b` were allocated by `store`, which is stable, and `c` by `inline`.
+        //  -   All are still valid, and associated to valid instances of `String`.
+        //  -   No reference to any is active.
+        unsafe {
+            TypedHandle::swap_values(a, &store, b, &store);
+            TypedHandle::swap_values(a, &store, c, &inline);
+            TypedHandle::swap_values(b, &store, b, &store);
+        }
+
+        //  Safety:
+        //  -   As above.
+        let values = unsafe { [a.resolve(&store), b.resolve(&store), c.resolve(&inline)] };
+
+        assert_eq!(["c", "a", "b"], values);
+
+        //  Safety:
+        //  -   `a` and `b` were allocated by `store`, and `c` by `inline`, and are still valid.
+        unsafe {
+            ptr::drop_in_place(a.resolve_raw(&store).as_ptr());
+            ptr::drop_in_place(b.resolve_raw(&store).as_ptr());
+            ptr::drop_in_place(c.resolve_raw(&inline).as_ptr());
+        }
+    }
+
+    #[test]
+    fn swap_values_sharing() {
+        let blocks = [StackBumpBlock::<[u64; 8]>::new(), StackBumpBlock::new()];
+        let store = blocks[0].create_store::<u8>();
+        let shared = store.share().unwrap();
+        let other = blocks[1].create_store::<u8>();
+
+        let (a, b) = (
+            TypedHandle::new(String::from("a"), &store),
+            TypedHandle::new(String::from("b"), &shared),
+        );
+        let c = TypedHandle::new(String::from("c"), &other);
+
+        //  Safety:
+        //  -   `a` was allocated by `store`, `b` by `shared`, of the same sharing set, and `c` by `other`.
+        //  -   All are still valid, and associated to valid instances of `String`.
+        //  -   No reference to any is active.
+        unsafe {
+            TypedHandle::swap_values_sharing(a, &store, b, &shared);
+            TypedHandle::swap_values_sharing(a, &shared, c, &other);
+            TypedHandle::swap_values_sharing(b, &store, b, &shared);
+        }
+
+        //  Safety:
+        //  -   As above.
+        let values = unsafe { [a.resolve(&store), b.resolve(&store), c.resolve(&other)] };
+
+        assert_eq!(["c", "a", "b"], values);
+
+        //  Safety:
+        //  -   `a` and `b` were allocated by `store`, or `shared`, and `c` by `other`, and are still valid.
+        unsafe {
+            ptr::drop_in_place(a.resolve_raw(&store).as_ptr());
+            ptr::drop_in_place(b.resolve_raw(&store).as_ptr());
+            ptr::drop_in_place(c.resolve_raw(&other).as_ptr());
+        }
+    }
+} // mod tests
@@ -12,6 +12,10 @@ mod implementation {
     };
 
     /// Typed Metadata, for type-safe APIs.
+    ///
+    /// The metadata is `#[repr(C)]`, and thus has the layout of `<T as Pointee>::Metadata`: zero-sized for a sized
+    /// `T`, and a `usize` for a slice.
+    #[repr(C)]
     pub struct TypedMetadata<T: ?Sized> {
         metadata: <T as Pointee>::Metadata,
         //  Work-around for https://github.com/rust-lang/rust/issues/111821.
@@ -23,6 +27,7 @@ mod implementation {
 
     impl<T: ?Sized> TypedMetadata<T> {
         /// Creates a new instance from the given metadata.
+        #[inline(always)]
         pub const fn from_metadata(metadata: <T as Pointee>::Metadata) -> Self {
             #[allow(clippy::let_unit_value)]
             let _self_is_always_sized = ();
@@ -34,11 +39,13 @@ mod implementation {
         }
 
         /// Retrieves the metadata.
+        #[inline(always)]
         pub const fn get(&self) -> <T as Pointee>::Metadata {
             self.metadata
         }
 
         /// Coerces the metadata into another.
+        #[inline(always)]
         pub const fn coerce<U: ?Sized>(&self) -> TypedMetadata<U>
         where
             T: Unsize<U>,
@@ -67,20 +74,25 @@ mod implementation {
     };
 
     /// Typed Metadata, for type-safe APIs.
+    ///
+    /// The metadata is a pointer to `T`, whose layout is unspecified for an unsized `T`, hence unsuitable for FFI.
     pub struct TypedMetadata<T: ?Sized>(NonNull<T>);
 
     impl<T: ?Sized> TypedMetadata<T> {
         /// Creates a new Typed metadata.
+        #[inline(always)]
         pub const fn from_metadata(metadata: <T as Pointee>::Metadata) -> Self {
             Self(NonNull::from_raw_parts(NonNull::dangling(), metadata))
         }
 
         /// Retrieves the metadata.
+        #[inline(always)]
         pub const fn get(&self) -> <T as Pointee>::Metadata {
             self.0.to_raw_parts().1
         }
 
         /// Coerces the metadata into another.
+        #[inline(always)]
         pub const fn coerce<U: ?Sized>(&self) -> TypedMetadata<U>
         where
             T: Unsize<U>,
@@ -90,17 +102,28 @@ mod implementation {
     }
 
     #[cfg(feature = "coercible-metadata")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "coercible-metadata")))]
     impl<T: ?Sized, U: ?Sized> CoerceUnsized<TypedMetadata<U>> for TypedMetadata<T> where T: Unsize<U> {}
+
+    //  Safety:
+    //  -   The pointer is dangling, and never dereferenced: only its metadata is used, which is `Send`.
+    unsafe impl<T: ?Sized + Send> Send for TypedMetadata<T> {}
+
+    //  Safety:
+    //  -   The pointer is dangling, and never dereferenced: only its metadata is used, which is `Sync`.
+    unsafe impl<T: ?Sized + Sync> Sync for TypedMetadata<T> {}
 } // mod implementation
 
 impl<T> TypedMetadata<T> {
     /// Creates a new instance.
+    #[inline(always)]
     pub const fn new() -> Self {
         Self::from_metadata(())
     }
 }
 
 impl<T: ?Sized> Clone for TypedMetadata<T> {
+    #[inline(always)]
     fn clone(&self) -> Self {
         *self
     }
@@ -115,12 +138,14 @@ impl<T: ?Sized> fmt::Debug for TypedMetadata<T> {
 }
 
 impl<T> Default for TypedMetadata<T> {
+    #[inline(always)]
     fn default() -> Self {
         Self::from_metadata(())
     }
 }
 
 impl<T> From<usize> for TypedMetadata<[T]> {
+    #[inline(always)]
     fn from(value: usize) -> Self {
         Self::from_metadata(value)
     }
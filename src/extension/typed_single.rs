@@ -12,7 +12,7 @@ use core::ops::CoerceUnsized;
 
 use crate::{
     alloc,
-    extension::typed_metadata::TypedMetadata,
+    extension::{typed_metadata::TypedMetadata, unique_single::UniqueSingleHandle},
     interface::{StoreDangling, StoreSingle},
 };
 
@@ -20,6 +20,13 @@ use crate::{
 ///
 /// A typed handle may be dangling, or may be invalid. It is the responsibility of the user to ensure that the typed
 /// handle is valid when necessary.
+///
+/// #   Layout
+///
+/// The handle is `#[repr(C)]`: the untyped handle, followed by the `TypedMetadata`. Unless the `coercible-metadata`
+/// feature is enabled, the typed handle of a sized `T` thus has the layout of `H`, and the typed handle of a `[T]` that
+/// of a C struct containing `H` followed by the length, as a `size_t`.
+#[repr(C)]
 pub struct TypedSingleHandle<T: ?Sized, H> {
     handle: H,
     metadata: TypedMetadata<T>,
@@ -77,6 +84,7 @@ impl<T, H: Copy> TypedSingleHandle<T, H> {
     where
         S: StoreSingle<Handle = H>,
     {
+        //  The actual size of the block of memory is irrelevant: `Layout::new::<T>()` fits it regardless.
         let (handle, _) = store.allocate(Layout::new::<T>())?;
 
         //  Safety:
@@ -168,15 +176,29 @@ impl<T: ?Sized, H: Copy> TypedSingleHandle<T, H> {
     /// -   If `handle` is invalid, then the resulting typed handle is invalid.
     /// -   If `handle` is valid and `metadata` does not fit the block of memory associated with it, then the resulting
     ///     typed handle is invalid.
+    #[inline(always)]
     pub const fn from_raw_parts(handle: H, metadata: TypedMetadata<T>) -> Self {
         Self { handle, metadata }
     }
 
     /// Decomposes a (possibly wide) pointer into its (raw) handle and metadata components.
+    #[inline(always)]
     pub const fn to_raw_parts(self) -> (H, TypedMetadata<T>) {
         (self.handle, self.metadata)
     }
 
+    /// Converts the handle into a unique handle.
+    ///
+    /// #   Safety
+    ///
+    /// -   No copy of `self` must be used henceforth.
+    #[inline(always)]
+    pub const unsafe fn assume_unique(self) -> UniqueSingleHandle<T, H> {
+        //  Safety:
+        //  -   No copy of `self.handle` will be used henceforth, as per pre-conditions.
+        unsafe { UniqueSingleHandle::from_raw_parts(self.handle, self.metadata) }
+    }
+
     /// Deallocates the memory associated with the handle.
     ///
     /// #   Safety
@@ -201,7 +223,8 @@ impl<T: ?Sized, H: Copy> TypedSingleHandle<T, H> {
         //  Safety:
         //  -   `self.handle` was allocated by `store`, as per pre-conditions.
         //  -   `self.handle` is still valid, as per pre-conditions.
-        //  -   `layout` fits the block of memory associated with `self.handle`.
+        //  -   `layout` fits the block of memory associated with `self.handle`, as it is the layout requested at
+        //      allocation for sized `T`, and covers at most the actual size of the block of memory for slices.
         unsafe { store.deallocate(self.handle, layout) };
     }
 
@@ -458,15 +481,54 @@ impl<T, H: Copy> TypedSingleHandle<[T], H> {
     }
 
     /// Returns whether the memory area associated to `self` may not contain any element.
+    #[inline(always)]
     pub const fn is_empty(&self) -> bool {
         self.metadata.get() == 0
     }
 
     /// Returns the number of elements the memory area associated to `self` may contain.
+    #[inline(always)]
     pub const fn len(&self) -> usize {
         self.metadata.get()
     }
 
+    /// Drops the first `len` elements of the slice, in place.
+    ///
+    /// The block of memory is left allocated, and the dropped elements are left uninitialized.
+    ///
+    /// #   Safety
+    ///
+    /// -   `self` must have been allocated by `store`.
+    /// -   `self` must still be valid.
+    /// -   `len` must be less than or equal to `self.len()`.
+    /// -   The first `len` elements must be initialized, and must not be used afterwards.
+    /// -   No reference to any of the first `len` elements must be active.
+    #[inline]
+    pub unsafe fn drop_initialized_prefix<S>(&self, len: usize, store: &mut S)
+    where
+        S: StoreSingle<Handle = H>,
+    {
+        debug_assert!(len <= self.len());
+
+        if !mem::needs_drop::<T>() {
+            return;
+        }
+
+        //  Safety:
+        //  -   `self` was allocated by `store`, and is still valid, as per pre-conditions.
+        let slice = unsafe { self.resolve_raw_mut(store) };
+
+        //  Safety:
+        //  -   `len <= self.len()`, as per pre-conditions.
+        let prefix = unsafe { slice.get_unchecked_mut(..len) };
+
+        //  Safety:
+        //  -   `prefix` is valid for both reads and writes, and properly aligned.
+        //  -   `prefix` points to `len` initialized elements, as per pre-conditions.
+        //  -   No reference to those elements is active, and they will not be used afterwards, as per pre-conditions.
+        unsafe { ptr::drop_in_place(prefix.as_ptr()) };
+    }
+
     /// Grows the block of memory associated with the handle.
     ///
     /// On success, all the copies of the handle are invalidated, and the extra memory is left uninitialized. On
@@ -477,6 +539,7 @@ impl<T, H: Copy> TypedSingleHandle<[T], H> {
     /// -   `self` must have been allocated by `store`.
     /// -   `self` must still be valid.
     /// -   `new_size` must be greater than or equal to `self.len()`.
+    #[inline]
     pub const unsafe fn grow<S>(&mut self, new_size: usize, store: &mut S)
     where
         S: ~const StoreSingle<Handle = H>,
@@ -502,7 +565,33 @@ impl<T, H: Copy> TypedSingleHandle<[T], H> {
     /// -   `self` must have been allocated by `store`.
     /// -   `self` must still be valid.
     /// -   `new_size` must be greater than or equal to `self.len()`.
+    #[inline]
     pub const unsafe fn try_grow<S>(&mut self, new_size: usize, store: &mut S) -> Result<(), AllocError>
+    where
+        S: ~const StoreSingle<Handle = H>,
+    {
+        //  Safety:
+        //  -   As per pre-conditions.
+        let Ok(_) = (unsafe { self.try_grow_reporting(new_size, store) }) else {
+            return Err(AllocError);
+        };
+
+        Ok(())
+    }
+
+    /// Attempts to grow the block of memory associated with the handle, reporting whether it was relocated.
+    ///
+    /// On success, all the copies of the handle are invalidated, the extra memory is left uninitialized, and whether
+    /// the block of memory was relocated is returned, as per `StoreSingle::grow_reporting`. On failure, an error is
+    /// returned.
+    ///
+    /// #   Safety
+    ///
+    /// -   `self` must have been allocated by `store`.
+    /// -   `self` must still be valid.
+    /// -   `new_size` must be greater than or equal to `self.len()`.
+    #[inline]
+    pub const unsafe fn try_grow_reporting<S>(&mut self, new_size: usize, store: &mut S) -> Result<bool, AllocError>
     where
         S: ~const StoreSingle<Handle = H>,
     {
@@ -521,9 +610,9 @@ impl<T, H: Copy> TypedSingleHandle<[T], H> {
         //  -   `self.handle` is still valid, as per pre-conditions.
         //  -   `old_layout` fits the block of memory associated to `self.handle`, by construction.
         //  -   `new_layout`'s size is greater than or equal to the size of `old_layout`, as per pre-conditions.
-        let result = unsafe { store.grow(self.handle, old_layout, new_layout) };
+        let result = unsafe { store.grow_reporting(self.handle, old_layout, new_layout) };
 
-        let Ok((handle, bytes)) = result else {
+        let Ok((handle, bytes, relocated)) = result else {
             return Err(AllocError);
         };
 
@@ -532,7 +621,7 @@ impl<T, H: Copy> TypedSingleHandle<[T], H> {
         self.handle = handle;
         self.metadata = TypedMetadata::from_metadata(bytes / mem::size_of::<T>());
 
-        Ok(())
+        Ok(relocated)
     }
 
     /// Grows the block of memory associated with the handle.
@@ -545,6 +634,7 @@ impl<T, H: Copy> TypedSingleHandle<[T], H> {
     /// -   `self` must have been allocated by `store`.
     /// -   `self` must still be valid.
     /// -   `new_size` must be greater than or equal to `self.len()`.
+    #[inline]
     pub const unsafe fn grow_zeroed<S>(&mut self, new_size: usize, store: &mut S)
     where
         S: ~const StoreSingle<Handle = H>,
@@ -570,6 +660,7 @@ impl<T, H: Copy> TypedSingleHandle<[T], H> {
     /// -   `self` must have been allocated by `store`.
     /// -   `self` must still be valid.
     /// -   `new_size` must be greater than or equal to `self.len()`.
+    #[inline]
     pub const unsafe fn try_grow_zeroed<S>(&mut self, new_size: usize, store: &mut S) -> Result<(), AllocError>
     where
         S: ~const StoreSingle<Handle = H>,
@@ -612,6 +703,7 @@ impl<T, H: Copy> TypedSingleHandle<[T], H> {
     /// -   `self` must have been allocated by `store`.
     /// -   `self` must still be valid.
     /// -   `new_size` must be less than or equal to `self.len()`.
+    #[inline]
     pub const unsafe fn shrink<S>(&mut self, new_size: usize, store: &mut S)
     where
         S: ~const StoreSingle<Handle = H>,
@@ -636,6 +728,7 @@ impl<T, H: Copy> TypedSingleHandle<[T], H> {
     /// -   `self` must have been allocated by `store`.
     /// -   `self` must still be valid.
     /// -   `new_size` must be less than or equal to `self.len()`.
+    #[inline]
     pub const unsafe fn try_shrink<S>(&mut self, new_size: usize, store: &mut S) -> Result<(), AllocError>
     where
         S: ~const StoreSingle<Handle = H>,
@@ -675,6 +768,7 @@ impl<T, H: Copy> TypedSingleHandle<[T], H> {
 }
 
 impl<T: ?Sized, H: Copy> Clone for TypedSingleHandle<T, H> {
+    #[inline(always)]
     fn clone(&self) -> Self {
         *self
     }
@@ -683,13 +777,16 @@ impl<T: ?Sized, H: Copy> Clone for TypedSingleHandle<T, H> {
 impl<T: ?Sized, H: Copy> Copy for TypedSingleHandle<T, H> {}
 
 #[cfg(feature = "coercible-metadata")]
-impl<T, U: ?Sized, H: Copy> CoerceUnsized<TypedSingleHandle<U, H>> for TypedSingleHandle<T, H> where T: Unsize<U> {}
+#[cfg_attr(docsrs, doc(cfg(feature = "coercible-metadata")))]
+impl<T: ?Sized, U: ?Sized, H: Copy> CoerceUnsized<TypedSingleHandle<U, H>> for TypedSingleHandle<T, H> where T: Unsize<U>
+{}
 
 //
 //  Implementation
 //
 
 impl<T, H> TypedSingleHandle<[T], H> {
+    #[inline(always)]
     const fn layout(size: usize) -> Result<Layout, AllocError> {
         let Some(size) = mem::size_of::<T>().checked_mul(size) else {
             return Err(AllocError);
@@ -11,6 +11,9 @@ use crate::{
 };
 
 /// A typed, unique handle.
+///
+/// The handle is `#[repr(transparent)]`, and thus has the layout of `TypedHandle`.
+#[repr(transparent)]
 pub struct UniqueHandle<T: ?Sized, H>(TypedHandle<T, H>);
 
 impl<T, H: Copy> UniqueHandle<T, H> {
@@ -109,6 +112,20 @@ impl<T, H: Copy> UniqueHandle<T, H> {
 
         Ok(Self(handle))
     }
+
+    /// Casts the handle into a handle to a `U`, reinterpreting the block of memory in place.
+    ///
+    /// #   Safety
+    ///
+    /// -   `U` must have the same layout as `T`: same size, and same alignment.
+    /// -   Resolving the resulting handle to a reference requires that the block of memory contains a valid instance
+    ///     of `U`, which the bytes of the instance of `T` may, or may not, be.
+    #[inline(always)]
+    pub const unsafe fn cast<U>(self) -> UniqueHandle<U, H> {
+        //  Safety:
+        //  -   `U` has the same layout as `T`, as per pre-conditions.
+        UniqueHandle(unsafe { self.0.cast() })
+    }
 }
 
 impl<T: ?Sized, H: Copy> UniqueHandle<T, H> {
@@ -123,15 +140,26 @@ impl<T: ?Sized, H: Copy> UniqueHandle<T, H> {
     /// #   Safety
     ///
     /// -   No copy of `handle` must be used henceforth.
+    #[inline(always)]
     pub const unsafe fn from_raw_parts(handle: H, metadata: TypedMetadata<T>) -> Self {
         Self(TypedHandle::from_raw_parts(handle, metadata))
     }
 
     /// Decomposes a (possibly wide) pointer into its handle and metadata components.
+    #[inline(always)]
     pub const fn to_raw_parts(self) -> (H, TypedMetadata<T>) {
         self.0.to_raw_parts()
     }
 
+    /// Converts the handle into a typed handle, giving up uniqueness.
+    ///
+    /// The resulting typed handle may be freely copied; it is then up to the user to ensure that its copies are not
+    /// used after deallocation, nor to create aliasing mutable references.
+    #[inline(always)]
+    pub const fn into_typed(self) -> TypedHandle<T, H> {
+        self.0
+    }
+
     /// Deallocates the memory associated with the handle.
     ///
     /// #   Safety
@@ -316,15 +344,38 @@ impl<T, H: Copy> UniqueHandle<[T], H> {
     }
 
     /// Returns whether the memory area associated to `self` may not contain any element.
+    #[inline(always)]
     pub const fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
 
     /// Returns the number of elements the memory area associated to `self` may contain.
+    #[inline(always)]
     pub const fn len(&self) -> usize {
         self.0.len()
     }
 
+    /// Drops the first `len` elements of the slice, in place.
+    ///
+    /// The block of memory is left allocated, and the dropped elements are left uninitialized.
+    ///
+    /// #   Safety
+    ///
+    /// -   `self` must have been allocated by `store`.
+    /// -   `self` must still be valid.
+    /// -   `len` must be less than or equal to `self.len()`.
+    /// -   The first `len` elements must be initialized, and must not be used afterwards.
+    /// -   No reference to any of the first `len` elements must be active.
+    #[inline(always)]
+    pub unsafe fn drop_initialized_prefix<S>(&self, len: usize, store: &S)
+    where
+        S: Store<Handle = H>,
+    {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.0.drop_initialized_prefix(len, store) }
+    }
+
     /// Grows the block of memory associated with the handle.
     ///
     /// On success, the extra memory is left uninitialized. On failure, an error is returned.
@@ -334,6 +385,7 @@ impl<T, H: Copy> UniqueHandle<[T], H> {
     /// -   `self` must have been allocated by `store`.
     /// -   `self` must still be valid.
     /// -   `new_size` must be greater than or equal to `self.len()`.
+    #[inline]
     pub const unsafe fn grow<S>(&mut self, new_size: usize, store: &S)
     where
         S: ~const Store<Handle = H>,
@@ -354,6 +406,7 @@ impl<T, H: Copy> UniqueHandle<[T], H> {
     /// -   `self` must have been allocated by `store`.
     /// -   `self` must still be valid.
     /// -   `new_size` must be greater than or equal to `self.len()`.
+    #[inline]
     pub const unsafe fn try_grow<S>(&mut self, new_size: usize, store: &S) -> Result<(), AllocError>
     where
         S: ~const Store<Handle = H>,
@@ -374,6 +427,7 @@ impl<T, H: Copy> UniqueHandle<[T], H> {
     /// -   `self` must have been allocated by `store`.
     /// -   `self` must still be valid.
     /// -   `new_size` must be greater than or equal to `self.len()`.
+    #[inline]
     pub const unsafe fn grow_zeroed<S>(&mut self, new_size: usize, store: &S)
     where
         S: ~const Store<Handle = H>,
@@ -394,6 +448,7 @@ impl<T, H: Copy> UniqueHandle<[T], H> {
     /// -   `self` must have been allocated by `store`.
     /// -   `self` must still be valid.
     /// -   `new_size` must be greater than or equal to `self.len()`.
+    #[inline]
     pub const unsafe fn try_grow_zeroed<S>(&mut self, new_size: usize, store: &S) -> Result<(), AllocError>
     where
         S: ~const Store<Handle = H>,
@@ -414,6 +469,7 @@ impl<T, H: Copy> UniqueHandle<[T], H> {
     /// -   `self` must have been allocated by `store`.
     /// -   `self` must still be valid.
     /// -   `new_size` must be less than or equal to `self.len()`.
+    #[inline]
     pub const unsafe fn shrink<S>(&mut self, new_size: usize, store: &S)
     where
         S: ~const Store<Handle = H>,
@@ -434,6 +490,7 @@ impl<T, H: Copy> UniqueHandle<[T], H> {
     /// -   `self` must have been allocated by `store`.
     /// -   `self` must still be valid.
     /// -   `new_size` must be less than or equal to `self.len()`.
+    #[inline]
     pub const unsafe fn try_shrink<S>(&mut self, new_size: usize, store: &S) -> Result<(), AllocError>
     where
         S: ~const Store<Handle = H>,
@@ -446,5 +503,13 @@ impl<T, H: Copy> UniqueHandle<[T], H> {
     }
 }
 
+impl<T: ?Sized, H: Copy> From<UniqueHandle<T, H>> for TypedHandle<T, H> {
+    #[inline(always)]
+    fn from(value: UniqueHandle<T, H>) -> Self {
+        value.into_typed()
+    }
+}
+
 #[cfg(feature = "coercible-metadata")]
-impl<T, U: ?Sized, H: Copy> CoerceUnsized<UniqueHandle<U, H>> for UniqueHandle<T, H> where T: Unsize<U> {}
+#[cfg_attr(docsrs, doc(cfg(feature = "coercible-metadata")))]
+impl<T: ?Sized, U: ?Sized, H: Copy> CoerceUnsized<UniqueHandle<U, H>> for UniqueHandle<T, H> where T: Unsize<U> {}
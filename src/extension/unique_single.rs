@@ -1,6 +1,11 @@
 //! A typed, unique handle.
 
-use core::{alloc::AllocError, marker::Unsize, ptr::NonNull};
+use core::{
+    alloc::AllocError,
+    marker::Unsize,
+    mem::MaybeUninit,
+    ptr::{self, NonNull},
+};
 
 #[cfg(feature = "coercible-metadata")]
 use core::ops::CoerceUnsized;
@@ -11,6 +16,9 @@ use crate::{
 };
 
 /// A typed, unique handle.
+///
+/// The handle is `#[repr(transparent)]`, and thus has the layout of `TypedSingleHandle`.
+#[repr(transparent)]
 pub struct UniqueSingleHandle<T: ?Sized, H>(TypedSingleHandle<T, H>);
 
 impl<T, H: Copy> UniqueSingleHandle<T, H> {
@@ -109,6 +117,70 @@ impl<T, H: Copy> UniqueSingleHandle<T, H> {
 
         Ok(Self(handle))
     }
+
+    /// Allocates a new handle, with enough space for `T`.
+    ///
+    /// The allocated memory is left uninitialized, as reflected in the type of the handle.
+    #[inline(always)]
+    pub const fn allocate_uninit<S>(store: &mut S) -> UniqueSingleHandle<MaybeUninit<T>, H>
+    where
+        S: ~const StoreSingle<Handle = H>,
+    {
+        UniqueSingleHandle::allocate(store)
+    }
+
+    /// Attempts to allocate a new handle, with enough space for `T`.
+    ///
+    /// The allocated memory is left uninitialized, as reflected in the type of the handle.
+    #[inline(always)]
+    pub const fn try_allocate_uninit<S>(store: &mut S) -> Result<UniqueSingleHandle<MaybeUninit<T>, H>, AllocError>
+    where
+        S: ~const StoreSingle<Handle = H>,
+    {
+        UniqueSingleHandle::try_allocate(store)
+    }
+}
+
+impl<T, H: Copy> UniqueSingleHandle<MaybeUninit<T>, H> {
+    /// Converts to a handle to an initialized `T`.
+    ///
+    /// #   Safety
+    ///
+    /// -   If `self` is valid, the block of memory associated with it must contain an initialized instance of `T`.
+    #[inline(always)]
+    pub const unsafe fn assume_init(self) -> UniqueSingleHandle<T, H> {
+        let (handle, _) = self.to_raw_parts();
+
+        //  Safety:
+        //  -   `handle` is associated to a block of memory fitting `MaybeUninit<T>`, hence fitting `T`.
+        //  -   No copy of `handle` is used henceforth, as `self` was consumed.
+        unsafe { UniqueSingleHandle::from_raw_parts(handle, TypedMetadata::new()) }
+    }
+
+    /// Initializes the block of memory with `value`, and converts to a handle to an initialized `T`.
+    ///
+    /// #   Safety
+    ///
+    /// -   `self` must have been allocated by `store`.
+    /// -   `self` must still be valid.
+    #[inline(always)]
+    pub unsafe fn write<S>(self, value: T, store: &mut S) -> UniqueSingleHandle<T, H>
+    where
+        S: StoreSingle<Handle = H>,
+    {
+        //  Safety:
+        //  -   `self` was allocated by `store`, as per pre-conditions.
+        //  -   `self` is still valid, as per pre-conditions.
+        let pointer = unsafe { self.resolve_raw_mut(store) };
+
+        //  Safety:
+        //  -   `pointer` is valid for writes of `T`, and suitably aligned, as `self` is valid.
+        unsafe { ptr::write(pointer.as_ptr() as *mut T, value) };
+
+        //  Safety:
+        //  -   The block of memory was just initialized.
+        unsafe { self.assume_init() }
+    }
 }
 
 impl<T: ?Sized, H: Copy> UniqueSingleHandle<T, H> {
@@ -123,15 +195,26 @@ impl<T: ?Sized, H: Copy> UniqueSingleHandle<T, H> {
     /// #   Safety
     ///
     /// -   No copy of `handle` must be used henceforth.
+    #[inline(always)]
     pub const unsafe fn from_raw_parts(handle: H, metadata: TypedMetadata<T>) -> Self {
         Self(TypedSingleHandle::from_raw_parts(handle, metadata))
     }
 
     /// Decomposes a (possibly wide) pointer into its handle and metadata components.
+    #[inline(always)]
     pub const fn to_raw_parts(self) -> (H, TypedMetadata<T>) {
         self.0.to_raw_parts()
     }
 
+    /// Converts the handle into a typed handle, giving up uniqueness.
+    ///
+    /// The resulting typed handle may be freely copied; it is then up to the user to ensure that its copies are not
+    /// used after deallocation, nor to create aliasing mutable references.
+    #[inline(always)]
+    pub const fn into_typed(self) -> TypedSingleHandle<T, H> {
+        self.0
+    }
+
     /// Deallocates the memory associated with the handle.
     ///
     /// #   Safety
@@ -337,16 +420,64 @@ impl<T, H: Copy> UniqueSingleHandle<[T], H> {
         Ok(Self(handle))
     }
 
+    /// Allocates a new handle, with enough space for at least `size` elements `T`.
+    ///
+    /// The allocated memory is left uninitialized, as reflected in the type of the handle.
+    #[inline(always)]
+    pub const fn allocate_uninit_slice<S>(size: usize, store: &mut S) -> UniqueSingleHandle<[MaybeUninit<T>], H>
+    where
+        S: ~const StoreSingle<Handle = H> + ~const StoreDangling<Handle = H>,
+    {
+        UniqueSingleHandle::allocate_slice(size, store)
+    }
+
+    /// Attempts to allocate a new handle, with enough space for at least `size` elements `T`.
+    ///
+    /// The allocated memory is left uninitialized, as reflected in the type of the handle.
+    #[inline(always)]
+    pub const fn try_allocate_uninit_slice<S>(
+        size: usize,
+        store: &mut S,
+    ) -> Result<UniqueSingleHandle<[MaybeUninit<T>], H>, AllocError>
+    where
+        S: ~const StoreSingle<Handle = H> + ~const StoreDangling<Handle = H>,
+    {
+        UniqueSingleHandle::try_allocate_slice(size, store)
+    }
+
     /// Returns whether the memory area associated to `self` may not contain any element.
+    #[inline(always)]
     pub const fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
 
     /// Returns the number of elements the memory area associated to `self` may contain.
+    #[inline(always)]
     pub const fn len(&self) -> usize {
         self.0.len()
     }
 
+    /// Drops the first `len` elements of the slice, in place.
+    ///
+    /// The block of memory is left allocated, and the dropped elements are left uninitialized.
+    ///
+    /// #   Safety
+    ///
+    /// -   `self` must have been allocated by `store`.
+    /// -   `self` must still be valid.
+    /// -   `len` must be less than or equal to `self.len()`.
+    /// -   The first `len` elements must be initialized, and must not be used afterwards.
+    /// -   No reference to any of the first `len` elements must be active.
+    #[inline(always)]
+    pub unsafe fn drop_initialized_prefix<S>(&self, len: usize, store: &mut S)
+    where
+        S: StoreSingle<Handle = H>,
+    {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.0.drop_initialized_prefix(len, store) }
+    }
+
     /// Grows the block of memory associated with the handle.
     ///
     /// On success, the extra memory is left uninitialized. On failure, an error is returned.
@@ -356,6 +487,7 @@ impl<T, H: Copy> UniqueSingleHandle<[T], H> {
     /// -   `self` must have been allocated by `store`.
     /// -   `self` must still be valid.
     /// -   `new_size` must be greater than or equal to `self.len()`.
+    #[inline]
     pub const unsafe fn grow<S>(&mut self, new_size: usize, store: &mut S)
     where
         S: ~const StoreSingle<Handle = H>,
@@ -376,6 +508,7 @@ impl<T, H: Copy> UniqueSingleHandle<[T], H> {
     /// -   `self` must have been allocated by `store`.
     /// -   `self` must still be valid.
     /// -   `new_size` must be greater than or equal to `self.len()`.
+    #[inline]
     pub const unsafe fn try_grow<S>(&mut self, new_size: usize, store: &mut S) -> Result<(), AllocError>
     where
         S: ~const StoreSingle<Handle = H>,
@@ -387,6 +520,28 @@ impl<T, H: Copy> UniqueSingleHandle<[T], H> {
         unsafe { self.0.try_grow(new_size, store) }
     }
 
+    /// Attempts to grow the block of memory associated with the handle, reporting whether it was relocated.
+    ///
+    /// On success, the extra memory is left uninitialized, and whether the block of memory was relocated is returned,
+    /// as per `StoreSingle::grow_reporting`. On failure, an error is returned.
+    ///
+    /// #   Safety
+    ///
+    /// -   `self` must have been allocated by `store`.
+    /// -   `self` must still be valid.
+    /// -   `new_size` must be greater than or equal to `self.len()`.
+    #[inline]
+    pub const unsafe fn try_grow_reporting<S>(&mut self, new_size: usize, store: &mut S) -> Result<bool, AllocError>
+    where
+        S: ~const StoreSingle<Handle = H>,
+    {
+        //  Safety:
+        //  -   `self.0` has been allocated by `store`, as per pre-conditions.
+        //  -   `self.0` is still valid, as per pre-conditions.
+        //  -   `new_size` is greater than or equal to `self.0.len()`.
+        unsafe { self.0.try_grow_reporting(new_size, store) }
+    }
+
     /// Grows the block of memory associated with the handle.
     ///
     /// On success, the extra memory is zeroed. On failure, an error is returned.
@@ -396,6 +551,7 @@ impl<T, H: Copy> UniqueSingleHandle<[T], H> {
     /// -   `self` must have been allocated by `store`.
     /// -   `self` must still be valid.
     /// -   `new_size` must be greater than or equal to `self.len()`.
+    #[inline]
     pub const unsafe fn grow_zeroed<S>(&mut self, new_size: usize, store: &mut S)
     where
         S: ~const StoreSingle<Handle = H>,
@@ -416,6 +572,7 @@ impl<T, H: Copy> UniqueSingleHandle<[T], H> {
     /// -   `self` must have been allocated by `store`.
     /// -   `self` must still be valid.
     /// -   `new_size` must be greater than or equal to `self.len()`.
+    #[inline]
     pub const unsafe fn try_grow_zeroed<S>(&mut self, new_size: usize, store: &mut S) -> Result<(), AllocError>
     where
         S: ~const StoreSingle<Handle = H>,
@@ -436,6 +593,7 @@ impl<T, H: Copy> UniqueSingleHandle<[T], H> {
     /// -   `self` must have been allocated by `store`.
     /// -   `self` must still be valid.
     /// -   `new_size` must be less than or equal to `self.len()`.
+    #[inline]
     pub const unsafe fn shrink<S>(&mut self, new_size: usize, store: &mut S)
     where
         S: ~const StoreSingle<Handle = H>,
@@ -456,6 +614,7 @@ impl<T, H: Copy> UniqueSingleHandle<[T], H> {
     /// -   `self` must have been allocated by `store`.
     /// -   `self` must still be valid.
     /// -   `new_size` must be less than or equal to `self.len()`.
+    #[inline]
     pub const unsafe fn try_shrink<S>(&mut self, new_size: usize, store: &mut S) -> Result<(), AllocError>
     where
         S: ~const StoreSingle<Handle = H>,
@@ -468,5 +627,35 @@ impl<T, H: Copy> UniqueSingleHandle<[T], H> {
     }
 }
 
+impl<T, H: Copy> UniqueSingleHandle<[MaybeUninit<T>], H> {
+    /// Converts to a handle to a slice of initialized `T`.
+    ///
+    /// #   Safety
+    ///
+    /// -   If `self` is valid, the block of memory associated with it must contain `self.len()` initialized instances
+    ///     of `T`.
+    #[inline(always)]
+    pub const unsafe fn assume_init(self) -> UniqueSingleHandle<[T], H> {
+        let (handle, metadata) = self.to_raw_parts();
+
+        //  Safety:
+        //  -   `handle` is associated to a block of memory fitting `[MaybeUninit<T>]`, hence fitting `[T]` of the same
+        //      length.
+        //  -   No copy of `handle` is used henceforth, as `self` was consumed.
+        unsafe { UniqueSingleHandle::from_raw_parts(handle, TypedMetadata::from_metadata(metadata.get())) }
+    }
+}
+
+impl<T: ?Sized, H: Copy> From<UniqueSingleHandle<T, H>> for TypedSingleHandle<T, H> {
+    #[inline(always)]
+    fn from(value: UniqueSingleHandle<T, H>) -> Self {
+        value.into_typed()
+    }
+}
+
 #[cfg(feature = "coercible-metadata")]
-impl<T, U: ?Sized, H: Copy> CoerceUnsized<UniqueSingleHandle<U, H>> for UniqueSingleHandle<T, H> where T: Unsize<U> {}
+#[cfg_attr(docsrs, doc(cfg(feature = "coercible-metadata")))]
+impl<T: ?Sized, U: ?Sized, H: Copy> CoerceUnsized<UniqueSingleHandle<U, H>> for UniqueSingleHandle<T, H> where
+    T: Unsize<U>
+{
+}
@@ -2,6 +2,8 @@
 
 use core::{
     alloc::{AllocError, Layout},
+    fmt, hash,
+    mem::MaybeUninit,
     ptr::{self, Alignment, NonNull},
 };
 
@@ -43,10 +45,40 @@ use core::{
 ///     `Store::resolve`. Pointers from different handles are only guaranteed to remain valid across those calls for
 ///     instances also implementing `StoreStable`.
 ///
+/// Memory Fitting:
+///
+/// A `layout` fits the block of memory associated with a handle if:
+///
+/// -   `layout.align()` is equal to the alignment of the layout used to allocate, grow, or shrink the block of memory.
+/// -   `layout.size()` is in the range `min..=max`, where `min` is the size of the layout used to allocate, grow, or
+///     shrink the block of memory, and `max` is the actual size returned by that call.
+///
+/// All methods taking a layout argument must accept any layout which fits, not solely the exact layout used to
+/// allocate. Users are thus free to either remember the actual size of the block of memory, or to discard it and
+/// recompute the requested layout, as `TypedHandle` does via `Layout::for_value_raw`.
+///
+/// Resizing:
+///
+/// `grow`, `shrink`, and their variants must accept the following edge cases, and on success:
+///
+/// -   Equal layouts: `new_layout` may be equal to `old_layout`. The contents of the block of memory are preserved, and
+///     the store should return `handle` as is, without relocating.
+/// -   Zero sizes: `old_layout.size()` may be 0 when growing, and `new_layout.size()` may be 0 when shrinking. The
+///     resulting block of memory is a block like any other, to be deallocated with a layout fitting `new_layout`.
+/// -   Alignment changes: `new_layout.align()` may be greater or smaller than `old_layout.align()`, whether the size
+///     changes or not. The resulting block of memory must be aligned as per `new_layout`, hence the store must
+///     relocate the block of memory if insufficiently aligned, or fail.
+///
+/// In all cases, the first `min(old_layout.size(), new_layout.size())` bytes of the block of memory are preserved, and
+/// `new_layout` fits the resulting block of memory.
+///
 /// A specific implementation of `Store` may provide extended validity guarantees, and should implement the extended
 /// guarantees traits when it does so.
 #[const_trait]
 pub unsafe trait Store: StoreDangling {
+    /// The growth strategy collections should follow when growing their blocks of memory within this store.
+    const GROWTH: Growth = Growth::Amortized;
+
     /// Resolves the `handle` into a pointer to the first byte of the associated block of memory.
     ///
     /// Unless `self` implements `StoreStable`, all previously resolved pointers from different handles may be
@@ -60,6 +92,41 @@ pub unsafe trait Store: StoreDangling {
     ///     sooner, see [Pointer Invalidation].
     unsafe fn resolve(&self, handle: Self::Handle) -> NonNull<u8>;
 
+    /// Resolves each of the `handles` into a pointer to the first byte of the associated block of memory, written in
+    /// the element of `out` at the same index.
+    ///
+    /// The default implementation calls `resolve` on each handle in turn. Stores for which resolving involves bounds
+    /// checks or table lookups may override it to amortize this work across the handles.
+    ///
+    /// Unless `self` implements `StoreStable`, only the last resolved pointer is guaranteed to be valid, as if each
+    /// handle had been resolved by a separate call to `resolve`.
+    ///
+    /// #   Panics
+    ///
+    /// If `out` is shorter than `handles`.
+    ///
+    /// #   Safety
+    ///
+    /// -   All `handles` must have been allocated by `self`.
+    /// -   All `handles` must still be valid.
+    /// -   The resulting pointers are only valid for as long as their handle is valid itself, and may be invalidated
+    ///     sooner, see [Pointer Invalidation].
+    unsafe fn resolve_many(&self, handles: &[Self::Handle], out: &mut [MaybeUninit<NonNull<u8>>]) {
+        assert!(handles.len() <= out.len());
+
+        let mut index = 0;
+
+        //  FIXME(const): Use `for` when available in const contexts.
+        while index < handles.len() {
+            //  Safety:
+            //  -   `handles[index]` has been allocated by `self`, as per pre-conditions.
+            //  -   `handles[index]` is still valid, as per pre-conditions.
+            out[index] = MaybeUninit::new(unsafe { self.resolve(handles[index]) });
+
+            index += 1;
+        }
+    }
+
     /// Attempts to allocate a block of memory.
     ///
     /// On success, returns a `Handle` to a block of memory meeting the size and alignment guarantees of `Layout` and
@@ -71,6 +138,10 @@ pub unsafe trait Store: StoreDangling {
     ///
     /// Returning `Err` indicates that either the memory is exhausted, or the store cannot satisfy `layout`
     /// constraints.
+    ///
+    /// Stores whose handles are offsets may also fail if the offset of the block of memory is not representable by
+    /// the handle, even though memory is available. Such stores may offer a more detailed error, as the bump stores do
+    /// with `BumpError::HandleOverflow`.
     fn allocate(&self, layout: Layout) -> Result<(Self::Handle, usize), AllocError>;
 
     /// Deallocates the memory referenced by `handle`.
@@ -104,6 +175,8 @@ pub unsafe trait Store: StoreDangling {
     /// -   `old_layout` must fit the associated block of memory.
     /// -   `new_layout.size()` must be greater than or equal to `old_layout.size()`.
     ///
+    /// `new_layout` may otherwise be equal to `old_layout`, or differ in alignment only, see Resizing in `Store`.
+    ///
     /// #   Errors
     ///
     /// Returning `Err` indicates that either the memory is exhausted, or the store cannot satisfy `new_layout`
@@ -169,6 +242,8 @@ pub unsafe trait Store: StoreDangling {
     /// -   `old_layout` must fit the associated block of memory.
     /// -   `new_layout.size()` must be smaller than or equal to `old_layout.size()`.
     ///
+    /// `new_layout` may otherwise be equal to `old_layout`, or differ in alignment only, see Resizing in `Store`.
+    ///
     /// #   Errors
     ///
     /// Returning `Err` indicates that either the memory is exhausted, or the store cannot satisfy `new_layout`
@@ -201,10 +276,11 @@ pub unsafe trait Store: StoreDangling {
         //  Safety:
         //  -   `current_ptr` is valid for reads, as `handle` is valid.
         //  -   `new_ptr` is valid for writes, as `handle` is valid _and_ exclusive access is guaranteed.
-        //  -   `new_ptr` is valid `new_size` bytes, as it was allocated with `new_layout`.
-        //  -   `current_ptr` is valid for `new_size` bytes, as it is smaller than or equal to
+        //  -   `new_ptr` is valid for `new_layout.size()` bytes, as it was allocated with `new_layout`.
+        //  -   `current_ptr` is valid for `new_layout.size()` bytes, as it is smaller than or equal to
         //      `old_layout.size()` as per the pre-conditions of `shrink`.
-        unsafe { ptr::copy_nonoverlapping(current_ptr.as_ptr(), new_ptr.as_ptr(), new_size) };
+        //  -   `new_size` may exceed `old_layout.size()`, should the store over-allocate, hence is not copied.
+        unsafe { ptr::copy_nonoverlapping(current_ptr.as_ptr(), new_ptr.as_ptr(), new_layout.size()) };
 
         //  Safety:
         //  -   `handle` has been allocated by `self`, as per the pre-conditions of `shrink`.
@@ -282,6 +358,66 @@ pub unsafe trait Store: StoreDangling {
 
         Ok((handle, new_size))
     }
+
+    /// Behaves like `grow`, but also reports whether the block of memory was relocated.
+    ///
+    /// On success, returns the new handle, the actual size of the block of memory, and whether the block was relocated.
+    /// When the block was not relocated, it starts at the same address as before, and pointers resolved from `handle`
+    /// prior to the call remain valid, and may be used to access the extended block of memory.
+    ///
+    /// The default implementation conservatively reports that the block of memory was relocated; stores which know
+    /// better should override it.
+    ///
+    /// #   Safety
+    ///
+    /// As per `grow`.
+    ///
+    /// #   Errors
+    ///
+    /// As per `grow`.
+    unsafe fn grow_reporting(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        //  Safety:
+        //  -   All pre-conditions of `grow` are pre-conditions of `grow_reporting`.
+        let Ok((handle, new_size)) = (unsafe { self.grow(handle, old_layout, new_layout) }) else {
+            return Err(AllocError);
+        };
+
+        Ok((handle, new_size, true))
+    }
+
+    /// Behaves like `shrink`, but also reports whether the block of memory was relocated.
+    ///
+    /// See `grow_reporting` for the meaning of the report.
+    ///
+    /// The default implementation conservatively reports that the block of memory was relocated; stores which know
+    /// better should override it.
+    ///
+    /// #   Safety
+    ///
+    /// As per `shrink`.
+    ///
+    /// #   Errors
+    ///
+    /// As per `shrink`.
+    unsafe fn shrink_reporting(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        //  Safety:
+        //  -   All pre-conditions of `shrink` are pre-conditions of `shrink_reporting`.
+        let Ok((handle, new_size)) = (unsafe { self.shrink(handle, old_layout, new_layout) }) else {
+            return Err(AllocError);
+        };
+
+        Ok((handle, new_size, true))
+    }
 }
 
 /// A trait abstracting a memory store specialized for a single outstanding allocation.
@@ -323,12 +459,25 @@ pub unsafe trait Store: StoreDangling {
 ///     `StoreSingle`.
 ///     Pointers are only guaranteed to remain valid across moves for instances also implementing `StorePinning`.
 /// -   All pointers resolved by an instance of `StoreSingle` are invalidated when calling `StoreSingle::allocate`,
-///     `StoreSingle::deallocate`, `StoreSingle::grow`, `StoreSingle::shrink`, or their zeroed variants.
+///     `StoreSingle::deallocate`, `StoreSingle::grow`, `StoreSingle::shrink`, or their zeroed variants, unless
+///     `StoreSingle::grow_reporting` or `StoreSingle::shrink_reporting` report that the block was not relocated.
+///
+/// Memory Fitting:
+///
+/// A `layout` fits the block of memory associated with a handle under the same conditions as for `Store`, and all
+/// methods taking a layout argument must likewise accept any layout which fits.
+///
+/// Resizing:
+///
+/// `grow`, `shrink`, and their variants must handle equal layouts, zero sizes, and alignment changes as for `Store`.
 ///
 /// A specific implementation of `StoreSingle` may provide extended validity guarantees, and should implement the
 /// extended guarantees traits when it does so.
 #[const_trait]
 pub unsafe trait StoreSingle: StoreDangling {
+    /// The growth strategy collections should follow when growing their block of memory within this store.
+    const GROWTH: Growth = Growth::Amortized;
+
     /// Resolves the `handle` into a pointer to the first byte of the associated block of memory.
     ///
     /// The resolved pointer may not be dereferenced mutably, unless `self` implements `Store` in which case both
@@ -392,6 +541,8 @@ pub unsafe trait StoreSingle: StoreDangling {
     /// -   `old_layout` must fit the associated block of memory.
     /// -   `new_layout.size()` must be greater than or equal to `old_layout.size()`.
     ///
+    /// `new_layout` may otherwise be equal to `old_layout`, or differ in alignment only, see Resizing in `Store`.
+    ///
     /// #   Errors
     ///
     /// Returning `Err` indicates that either the memory is exhausted, or the store cannot satisfy `new_layout`
@@ -418,6 +569,8 @@ pub unsafe trait StoreSingle: StoreDangling {
     /// -   `old_layout` must fit the associated block of memory.
     /// -   `new_layout.size()` must be smaller than or equal to `old_layout.size()`.
     ///
+    /// `new_layout` may otherwise be equal to `old_layout`, or differ in alignment only, see Resizing in `Store`.
+    ///
     /// #   Errors
     ///
     /// Returning `Err` indicates that either the memory is exhausted, or the store cannot satisfy `new_layout`
@@ -495,6 +648,122 @@ pub unsafe trait StoreSingle: StoreDangling {
 
         Ok((handle, new_size))
     }
+
+    /// Behaves like `grow`, but also reports whether the block of memory was relocated.
+    ///
+    /// On success, returns the new handle, the actual size of the block of memory, and whether the block was relocated.
+    /// When the block was not relocated, it starts at the same address as before, and pointers resolved from `handle`
+    /// prior to the call remain valid, and may be used to access the extended block of memory.
+    ///
+    /// The default implementation conservatively reports that the block of memory was relocated; stores which know
+    /// better should override it. Stores whose block of memory is part of `self` must report relocation, as pointers
+    /// resolved prior to the call do not survive the exclusive borrow of `self`.
+    ///
+    /// #   Safety
+    ///
+    /// As per `grow`.
+    ///
+    /// #   Errors
+    ///
+    /// As per `grow`.
+    unsafe fn grow_reporting(
+        &mut self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        //  Safety:
+        //  -   All pre-conditions of `grow` are pre-conditions of `grow_reporting`.
+        let Ok((handle, new_size)) = (unsafe { self.grow(handle, old_layout, new_layout) }) else {
+            return Err(AllocError);
+        };
+
+        Ok((handle, new_size, true))
+    }
+
+    /// Behaves like `shrink`, but also reports whether the block of memory was relocated.
+    ///
+    /// See `grow_reporting` for the meaning of the report.
+    ///
+    /// The default implementation conservatively reports that the block of memory was relocated; stores which know
+    /// better should override it.
+    ///
+    /// #   Safety
+    ///
+    /// As per `shrink`.
+    ///
+    /// #   Errors
+    ///
+    /// As per `shrink`.
+    unsafe fn shrink_reporting(
+        &mut self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        //  Safety:
+        //  -   All pre-conditions of `shrink` are pre-conditions of `shrink_reporting`.
+        let Ok((handle, new_size)) = (unsafe { self.shrink(handle, old_layout, new_layout) }) else {
+            return Err(AllocError);
+        };
+
+        Ok((handle, new_size, true))
+    }
+}
+
+/// The growth strategy collections should follow when growing their blocks of memory.
+///
+/// This is a hint, collections may ignore it, for example if they require a power-of-two capacity.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Growth {
+    /// Grows to exactly the required capacity.
+    ///
+    /// Suitable for single-allocation fixed-capacity stores, such as `InlineSingleStore`, which always grow in place.
+    Exact,
+    /// Grows by at least half the current capacity, amortizing the cost of growing.
+    ///
+    /// Suitable for allocators, and bump stores alike: the latter never reclaim memory, hence each relocation leaves a
+    /// block of memory behind, and growing geometrically bounds their number.
+    Amortized,
+    /// Grows to the smallest power of two greater than or equal to the required capacity.
+    PowerOfTwo,
+}
+
+impl Growth {
+    /// Returns the capacity to grow to, from `current`, so as to accommodate at least `required`.
+    ///
+    /// Returns `None` if the capacity to grow to is not representable.
+    ///
+    /// ```
+    /// use storage::interface::Growth;
+    ///
+    /// assert_eq!(Some(5), Growth::Exact.capacity(4, 5));
+    /// assert_eq!(Some(6), Growth::Amortized.capacity(4, 5));
+    /// assert_eq!(Some(8), Growth::PowerOfTwo.capacity(4, 5));
+    ///
+    /// assert_eq!(None, Growth::PowerOfTwo.capacity(4, usize::MAX));
+    /// ```
+    pub const fn capacity(self, current: usize, required: usize) -> Option<usize> {
+        match self {
+            Self::Exact => Some(required),
+            Self::Amortized => {
+                let amortized = current.saturating_add(current / 2);
+
+                Some(if amortized > required { amortized } else { required })
+            }
+            Self::PowerOfTwo => required.checked_next_power_of_two(),
+        }
+    }
+}
+
+/// The error returned by `StorePersist::snapshot`, when the snapshot could not be made durable.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct PersistError;
+
+impl fmt::Display for PersistError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str("snapshot failed")
+    }
 }
 
 /// A base for `Store` and `StoreSingle`, introducing the handle type, and the ability to allocate dangling handles.
@@ -529,6 +798,155 @@ pub unsafe trait StoreDangling {
     fn dangling(&self, alignment: Alignment) -> Result<Self::Handle, AllocError>;
 }
 
+/// A marker for stores which may have multiple outstanding allocations at a time.
+///
+/// This is the distinction between `Store` and `StoreSingle`: allocating with a `Store` never invalidates previously
+/// allocated handles, whereas allocating with a `StoreSingle` may invalidate the single outstanding handle. Every
+/// `Store` is thus a `StoreMultiple`, as guaranteed by the blanket implementation, and this trait exists purely to
+/// spell out the guarantee in bounds and documentation.
+///
+/// #   Safety
+///
+/// Implementers of this trait must guarantee that allocating a new handle never invalidates any other handle.
+pub unsafe trait StoreMultiple: Store {}
+
+//  Safety:
+//  -   `Store::allocate` never invalidates any other handle, as per the pre-conditions of `Store`.
+unsafe impl<S> StoreMultiple for S where S: ?Sized + Store {}
+
+/// A variant of `Store` whose handles need only be `Clone`, rather than `Copy`.
+///
+/// This trait allows stores whose handles own resources, such as a reference-counted liveness flag, or debugging
+/// information, which `StoreDangling::Handle: Copy` rules out. As such handles may be expensive to clone, they are
+/// passed by reference to `resolve`, and updated in place by `grow` and `shrink`, rather than returned anew.
+///
+/// Every `Store` is a `StoreOwned`, as guaranteed by the blanket implementation, so that code written against
+/// `StoreOwned` accepts any store, with the `Copy` handles of a `Store` as fast path. See `OwnedHandle` for a typed
+/// handle built on top of this trait, and `CheckedStore` for a store with non-`Copy` handles.
+///
+/// _Note: the methods share their names with those of `Store`, hence this trait is not part of the prelude._
+///
+/// #   Safety
+///
+/// Same as `Store`, where a handle and all its clones are invalidated at the same time, and a handle passed by
+/// reference to `grow` or `shrink` is updated in place, rather than invalidated, on success.
+pub unsafe trait StoreOwned {
+    /// A Handle to memory allocated by the instance of Store which creates it.
+    type Handle: Clone;
+
+    /// The growth strategy collections should follow when growing their blocks of memory within this store.
+    const GROWTH: Growth = Growth::Amortized;
+
+    /// Creates a dangling handle, see `StoreDangling::dangling`.
+    fn dangling(&self, alignment: Alignment) -> Result<Self::Handle, AllocError>;
+
+    /// Resolves the `handle` into a pointer to the first byte of the associated block of memory.
+    ///
+    /// #   Safety
+    ///
+    /// Same as `Store::resolve`.
+    unsafe fn resolve(&self, handle: &Self::Handle) -> NonNull<u8>;
+
+    /// Attempts to allocate a block of memory, see `Store::allocate`.
+    fn allocate(&self, layout: Layout) -> Result<(Self::Handle, usize), AllocError>;
+
+    /// Deallocates the memory referenced by `handle`, see `Store::deallocate`.
+    ///
+    /// #   Safety
+    ///
+    /// Same as `Store::deallocate`.
+    unsafe fn deallocate(&self, handle: Self::Handle, layout: Layout);
+
+    /// Attempts to extend the block of memory associated with `handle`, updating it in place on success.
+    ///
+    /// On failure, `handle` is left untouched, and remains valid.
+    ///
+    /// #   Safety
+    ///
+    /// Same as `Store::grow`.
+    unsafe fn grow(
+        &self,
+        handle: &mut Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<usize, AllocError>;
+
+    /// Attempts to shrink the block of memory associated with `handle`, updating it in place on success.
+    ///
+    /// On failure, `handle` is left untouched, and remains valid.
+    ///
+    /// #   Safety
+    ///
+    /// Same as `Store::shrink`.
+    unsafe fn shrink(
+        &self,
+        handle: &mut Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<usize, AllocError>;
+}
+
+//  Safety:
+//  -   Each method delegates to its `Store` counterpart, and `Copy` handles are trivially `Clone`.
+unsafe impl<S> StoreOwned for S
+where
+    S: ?Sized + Store,
+{
+    type Handle = <S as StoreDangling>::Handle;
+
+    const GROWTH: Growth = <S as Store>::GROWTH;
+
+    fn dangling(&self, alignment: Alignment) -> Result<Self::Handle, AllocError> {
+        StoreDangling::dangling(self, alignment)
+    }
+
+    unsafe fn resolve(&self, handle: &Self::Handle) -> NonNull<u8> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { Store::resolve(self, *handle) }
+    }
+
+    fn allocate(&self, layout: Layout) -> Result<(Self::Handle, usize), AllocError> {
+        Store::allocate(self, layout)
+    }
+
+    unsafe fn deallocate(&self, handle: Self::Handle, layout: Layout) {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { Store::deallocate(self, handle, layout) }
+    }
+
+    unsafe fn grow(
+        &self,
+        handle: &mut Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<usize, AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        let (grown, size) = unsafe { Store::grow(self, *handle, old_layout, new_layout)? };
+
+        *handle = grown;
+
+        Ok(size)
+    }
+
+    unsafe fn shrink(
+        &self,
+        handle: &mut Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<usize, AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        let (shrunk, size) = unsafe { Store::shrink(self, *handle, old_layout, new_layout)? };
+
+        *handle = shrunk;
+
+        Ok(size)
+    }
+}
+
 /// A refinement of a store which guarantees that the blocks of memory are stable in memory across method calls, but
 /// not necessarily across moves.
 ///
@@ -552,6 +970,105 @@ pub unsafe trait StoreStable {}
 /// the lifetime bound of the `Store` concrete type (if not `'static`) expires, whichever comes first.
 pub unsafe trait StorePinning: StoreStable {}
 
+/// Implements the item-less marker traits of a store: `StoreStable`, and optionally `StorePinning`.
+///
+/// Naming `StorePinning` implements both `StorePinning` and its super-trait `StoreStable`, with the same generic
+/// parameters and bounds, which is by far the most common case. Naming `StoreStable` only implements `StoreStable`.
+///
+/// Naming an underlying store after `=>` makes each trait conditional on the underlying store implementing it, as
+/// befits wrappers: with `=> S`, `StoreStable` is implemented if `S: StoreStable`, and `StorePinning` if
+/// `S: StorePinning`.
+///
+/// The generic parameters are listed in square brackets after `impl`, and the optional bounds in square brackets after
+/// `where`. The `unsafe` keyword is mandatory, as a reminder that the caller vouches for the guarantees of the traits,
+/// which should be justified by a `Safety` comment just like any `unsafe impl`.
+///
+/// `StoreRegion` and `StoreSharing` have items, and must therefore be implemented manually.
+///
+/// #   Example
+///
+/// ```
+/// #![feature(allocator_api)]
+///
+/// use std::alloc::Global;
+///
+/// use storage::{interface::{StorePinning, StoreStable}, store_markers};
+///
+/// struct Wrapper<S>(S);
+///
+/// store_markers! {
+///     //  Safety:
+///     //  -   `S` is pinning, and `Wrapper` resolves handles through it.
+///     unsafe impl[S] StorePinning for Wrapper<S> where [S: StorePinning];
+/// }
+///
+/// struct Forwarding<S>(S);
+///
+/// store_markers! {
+///     //  Safety:
+///     //  -   `Forwarding` resolves handles through `S`, hence is as stable, or pinning, as `S`.
+///     unsafe impl[S] StorePinning for Forwarding<S> => S;
+/// }
+///
+/// fn is_pinning<S: StorePinning>() {}
+/// fn is_stable<S: StoreStable>() {}
+///
+/// is_pinning::<Wrapper<Global>>();
+/// is_stable::<Wrapper<Global>>();
+/// is_pinning::<Forwarding<Global>>();
+/// ```
+#[macro_export]
+macro_rules! store_markers {
+    (@marker StoreStable [$($generics:tt)*] $ty:ty [$($bounds:tt)*] []) => {
+        unsafe impl<$($generics)*> $crate::interface::StoreStable for $ty where $($bounds)* {}
+    };
+    (@marker StoreStable [$($generics:tt)*] $ty:ty [$($bounds:tt)*] [$inner:ident]) => {
+        unsafe impl<$($generics)*> $crate::interface::StoreStable for $ty
+        where
+            $inner: $crate::interface::StoreStable,
+            $($bounds)*
+        {
+        }
+    };
+    (@marker StorePinning [$($generics:tt)*] $ty:ty [$($bounds:tt)*] []) => {
+        $crate::store_markers!(@marker StoreStable [$($generics)*] $ty [$($bounds)*] []);
+
+        unsafe impl<$($generics)*> $crate::interface::StorePinning for $ty where $($bounds)* {}
+    };
+    (@marker StorePinning [$($generics:tt)*] $ty:ty [$($bounds:tt)*] [$inner:ident]) => {
+        $crate::store_markers!(@marker StoreStable [$($generics)*] $ty [$($bounds)*] [$inner]);
+
+        unsafe impl<$($generics)*> $crate::interface::StorePinning for $ty
+        where
+            $inner: $crate::interface::StorePinning,
+            $($bounds)*
+        {
+        }
+    };
+    ($(unsafe impl[$($generics:tt)*] $marker:ident for $ty:ty $(=> $inner:ident)? $(where [$($bounds:tt)*])?;)*) => {
+        $(
+            $crate::store_markers!(@marker $marker [$($generics)*] $ty [$($($bounds)*)?] [$($inner)?]);
+        )*
+    };
+}
+
+/// A refinement of `StorePinning` for stores backed by a single contiguous region of memory.
+///
+/// This is notably useful for DMA, where the hardware must be informed of the (fixed) addresses of the buffers ahead
+/// of time.
+///
+/// #   Safety
+///
+/// Implementers of this trait must guarantee that:
+///
+/// -   The region of memory never changes: it is neither moved nor resized for as long as the instance of the store,
+///     or any other instance it shares with, is alive.
+/// -   Any valid handle resolves to a pointer within this region, and its block of memory lies entirely within it.
+pub unsafe trait StoreRegion: StorePinning {
+    /// Returns the region of memory from which all blocks of memory are allocated.
+    fn region(&self) -> NonNull<[u8]>;
+}
+
 /// A refinement of `StorePinning` which allows multiple instances to share the handles and their associated blocks of
 /// memory.
 ///
@@ -562,14 +1079,34 @@ pub unsafe trait StorePinning: StoreStable {}
 ///
 /// A set of sharing stores is effectively the morale equivalent of a `Rc<Store>` or `Arc<Store>`.
 ///
+/// In particular, a handle outlives the part which created it: the part may be moved, or dropped, and the handle
+/// remains usable with any other part of the set, including those created by `share` after the move.
+///
 /// #   Safety
 ///
 /// Implementers of this trait must guarantee that a handle created by one part of a sharing set may be used with any
-/// other part: resolved, deallocated, grown, or shrunk.
+/// other part: resolved, deallocated, grown, or shrunk. This holds regardless of whether the part which created the
+/// handle has since been moved, or dropped, as long as at least one part of the set is alive.
+///
+/// Implementers must also guarantee that two live instances whose tokens, as returned by `sharing_token`, are equal
+/// belong to the same sharing set.
 pub unsafe trait StoreSharing: StorePinning {
     /// Error returned if sharing is not currently possible.
     type SharingError;
 
+    /// Token identifying a sharing set.
+    type SharingToken: Copy + Eq + hash::Hash + fmt::Debug;
+
+    /// Returns the token identifying the sharing set `self` belongs to.
+    ///
+    /// Unlike `is_sharing_with`, which compares two instances, the token may be stored, and later compared with the
+    /// token of another instance, or used as a key in a registry of stores.
+    ///
+    /// Two live instances whose tokens are equal belong to the same sharing set. As for `is_sharing_with`, the tokens
+    /// of two instances of the same sharing set may differ, though they should not. Once all the instances of a
+    /// sharing set are dropped, its token may be reused by another sharing set.
+    fn sharing_token(&self) -> Self::SharingToken;
+
     /// Returns whether two instances belong to the same sharing set.
     ///
     /// The implementation is permitted to return `false` even if the two instances do, indeed, belong to the same
@@ -582,3 +1119,257 @@ pub unsafe trait StoreSharing: StorePinning {
     where
         Self: Sized;
 }
+
+/// An extension of a store attributing each allocation to a purpose, identified by a tag.
+///
+/// Tags are opaque to the store, which typically uses them for accounting, for example to budget memory per subsystem.
+/// All methods default to their untagged counterparts, ignoring the tag.
+///
+/// Callers are expected to pass the same tag for the whole lifetime of a block of memory, from its allocation to its
+/// deallocation; `TaggedStore` does so for any collection.
+///
+/// #   Safety
+///
+/// Implementers of this trait must guarantee that each method upholds the guarantees of its untagged counterpart.
+pub unsafe trait StoreTagged: Store {
+    /// Allocates a block of memory, as per `Store::allocate`, attributed to `tag`.
+    fn allocate_tagged(&self, layout: Layout, _tag: u32) -> Result<(Self::Handle, usize), AllocError> {
+        self.allocate(layout)
+    }
+
+    /// Deallocates a block of memory, as per `Store::deallocate`, attributed to `tag`.
+    ///
+    /// #   Safety
+    ///
+    /// As per `Store::deallocate`.
+    unsafe fn deallocate_tagged(&self, handle: Self::Handle, layout: Layout, _tag: u32) {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.deallocate(handle, layout) }
+    }
+
+    /// Grows a block of memory, as per `Store::grow`, attributed to `tag`.
+    ///
+    /// #   Safety
+    ///
+    /// As per `Store::grow`.
+    unsafe fn grow_tagged(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+        _tag: u32,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.grow(handle, old_layout, new_layout) }
+    }
+
+    /// Shrinks a block of memory, as per `Store::shrink`, attributed to `tag`.
+    ///
+    /// #   Safety
+    ///
+    /// As per `Store::shrink`.
+    unsafe fn shrink_tagged(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+        _tag: u32,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.shrink(handle, old_layout, new_layout) }
+    }
+
+    /// Grows a block of memory, as per `Store::grow_reporting`, attributed to `tag`.
+    ///
+    /// The default implementation forwards to `grow_tagged`, and conservatively reports that the block of memory was
+    /// relocated.
+    ///
+    /// #   Safety
+    ///
+    /// As per `Store::grow`.
+    unsafe fn grow_reporting_tagged(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+        tag: u32,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        let (handle, new_size) = unsafe { self.grow_tagged(handle, old_layout, new_layout, tag)? };
+
+        Ok((handle, new_size, true))
+    }
+
+    /// Shrinks a block of memory, as per `Store::shrink_reporting`, attributed to `tag`.
+    ///
+    /// The default implementation forwards to `shrink_tagged`, and conservatively reports that the block of memory was
+    /// relocated.
+    ///
+    /// #   Safety
+    ///
+    /// As per `Store::shrink`.
+    unsafe fn shrink_reporting_tagged(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+        tag: u32,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        let (handle, new_size) = unsafe { self.shrink_tagged(handle, old_layout, new_layout, tag)? };
+
+        Ok((handle, new_size, true))
+    }
+}
+
+/// A refinement of a store whose handles may be advanced within their block of memory, without resolving them.
+///
+/// Stores whose handles are offsets into their memory may implement this trait cheaply, via integer arithmetic. It
+/// allows computing, and storing, the handle of an element of a slice, for example, without resolving the slice.
+///
+/// #   Safety
+///
+/// Implementers of this trait must guarantee that, for any valid handle `handle`, and any `bytes` strictly less than
+/// the size of its block of memory, the handle returned by `offset(handle, bytes)`:
+///
+/// -   Resolves to the pointer `handle` resolves to, advanced by `bytes`.
+/// -   Remains valid for as long as `handle` is, and is invalidated alongside it.
+///
+/// The returned handle may only be resolved, it may not be deallocated, grown, or shrunk.
+pub unsafe trait StoreOffsetHandle: StoreDangling {
+    /// Returns a handle to the byte at `bytes` from the start of the block of memory associated to `handle`.
+    ///
+    /// #   Safety
+    ///
+    /// -   `handle` must be valid.
+    /// -   `bytes` must be strictly less than the size of the block of memory associated to `handle`.
+    ///
+    /// #   Panics
+    ///
+    /// Implementations may panic if the resulting handle cannot be represented, for example if the handle type is too
+    /// small to represent offsets within the block of memory.
+    unsafe fn offset(handle: Self::Handle, bytes: usize) -> Self::Handle;
+}
+
+/// An extension of a store whose blocks of memory may be persisted, for example to a memory-mapped file, or to
+/// persistent memory.
+///
+/// Both hooks default to no-ops, as befits volatile stores, so that collections may call them unconditionally, and the
+/// same collection code may target volatile and persistent stores alike; see `StoreVec::push_persistent`.
+///
+/// This trait is not `unsafe` to implement, as it is about durability, and not memory safety.
+pub trait StorePersist: StoreDangling {
+    /// Flushes the first `layout.size()` bytes of the block of memory associated to `handle` to the persistent medium.
+    ///
+    /// #   Safety
+    ///
+    /// -   `handle` must be valid.
+    /// -   `layout.size()` must be less than or equal to the size of the block of memory associated to `handle`.
+    unsafe fn flush(&self, _handle: Self::Handle, _layout: Layout) {}
+
+    /// Makes the bookkeeping of the store, and the bytes flushed so far, durable as a whole.
+    ///
+    /// Once a snapshot succeeds, a crash may only ever revert the store to this snapshot, or a later one.
+    fn snapshot(&self) -> Result<(), PersistError> {
+        Ok(())
+    }
+}
+
+/// An extension of a store which may report whether a handle is currently valid.
+///
+/// This trait is meant as a debugging aid, for example to validate the handles held by a collection in tests. It is
+/// not `unsafe` to implement, and thus its results cannot be relied upon for soundness.
+pub trait StoreValidate: StoreDangling {
+    /// Returns whether `handle` is currently valid, if known.
+    ///
+    /// -   `Some(true)` indicates that `handle` is known to be valid.
+    /// -   `Some(false)` indicates that `handle` is known to be invalid.
+    /// -   `None` indicates that the validity of `handle` is unknown.
+    ///
+    /// A dangling handle is never valid, though the implementation may not be able to distinguish it from a valid one.
+    fn is_valid(&self, handle: Self::Handle) -> Option<bool>;
+}
+
+/// An extension of a store which may enumerate its currently allocated blocks of memory.
+///
+/// This trait is meant as a debugging aid, for example to dump the content of an arena, or to locate leaks. It is not
+/// `unsafe` to implement, and thus its results cannot be relied upon for soundness.
+pub trait StoreEnumerate: StoreDangling {
+    /// Calls `f` with the handle and layout of each currently allocated block of memory, in unspecified order.
+    ///
+    /// The layout is the one the block of memory was last allocated, grown, or shrunk with, and thus fits the block.
+    ///
+    /// `f` must not allocate, deallocate, grow, nor shrink any block of memory from `self`.
+    fn for_each_handle(&self, f: impl FnMut(Self::Handle, Layout));
+}
+
+/// The limits of the layouts a store may ever satisfy, as reported by `StoreIntrospect::limits`.
+///
+/// The limits are upper bounds: a layout beyond the limits is never satisfied, whereas a layout within the limits may
+/// still fail to be allocated, for example once the store is exhausted.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct StoreLimits {
+    /// The maximum alignment of a block of memory.
+    pub max_align: Alignment,
+    /// The maximum size of a block of memory, in bytes.
+    pub max_size: usize,
+    /// Whether the memory of deallocated blocks may be handed out again.
+    ///
+    /// Bump stores, notably, never reuse the memory of deallocated blocks.
+    pub supports_dealloc_reuse: bool,
+}
+
+impl StoreLimits {
+    /// The limits of a store bounded solely by the limits of `Layout` itself, and reusing deallocated memory.
+    pub const UNBOUNDED: Self = Self {
+        //  Safety:
+        //  -   `1 << (usize::BITS - 1)` is a power of 2, the largest representable.
+        max_align: unsafe { Alignment::new_unchecked(1 << (usize::BITS - 1)) },
+        max_size: isize::MAX as usize,
+        supports_dealloc_reuse: true,
+    };
+
+    /// Returns whether `layout` is within the limits.
+    ///
+    /// ```
+    /// #![feature(ptr_alignment_type)]
+    ///
+    /// use core::{alloc::Layout, ptr::Alignment};
+    ///
+    /// use storage::interface::StoreLimits;
+    ///
+    /// let limits = StoreLimits { max_align: Alignment::of::<u32>(), max_size: 16, supports_dealloc_reuse: false };
+    ///
+    /// assert!(limits.fits(Layout::new::<[u32; 4]>()));
+    /// assert!(!limits.fits(Layout::new::<[u32; 5]>()));
+    /// assert!(!limits.fits(Layout::from_size_align(8, 8).unwrap()));
+    /// ```
+    pub const fn fits(&self, layout: Layout) -> bool {
+        layout.align() <= self.max_align.as_usize() && layout.size() <= self.max_size
+    }
+}
+
+impl Default for StoreLimits {
+    fn default() -> Self {
+        Self::UNBOUNDED
+    }
+}
+
+/// An extension of a store which may report the limits of the layouts it may satisfy.
+///
+/// Generic code may validate layouts against the limits upfront, so as to report a precise error rather than a bare
+/// `AllocError`, or to pick a different store altogether. The limits default to `StoreLimits::UNBOUNDED`, as befits
+/// stores backed by an allocator.
+///
+/// This trait is not `unsafe` to implement, and thus its results cannot be relied upon for soundness.
+pub trait StoreIntrospect: StoreDangling {
+    /// Returns the limits of the layouts the store may satisfy.
+    fn limits(&self) -> StoreLimits {
+        StoreLimits::UNBOUNDED
+    }
+}
@@ -6,8 +6,12 @@
 //! This project does NOT aim at displacing `Allocator`, but rather at providing a lower-level abstraction layer for
 //! when greater flexibility is required. Zero-Cost compatibility with `Allocator` is desired, so that collections can
 //! be implemented in terms of `Store`, but used with an `Allocator` easily.
+//!
+//! Allocation failures are reported as errors, rather than panics: the `try_` methods of the stores and collections do
+//! not panic on allocation paths, their internal invariants being checked by `debug_assert!` only. Other methods call
+//! `handle_alloc_error` on allocation failure, and document any other panic in their `#   Panics` section.
 
-#![cfg_attr(not(test), no_std)]
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
 //  Features
 #![feature(allocator_api)]
 #![feature(alloc_layout_extra)]
@@ -21,7 +25,7 @@
 #![feature(const_trait_impl)]
 #![feature(const_try)]
 #![feature(const_ptr_write)]
-#![feature(hasher_prefixfree_extras)]
+#![feature(dropck_eyepatch)]
 #![feature(layout_for_ptr)]
 #![feature(maybe_uninit_write_slice)]
 #![feature(offset_of)]
@@ -30,20 +34,28 @@
 #![feature(ptr_as_uninit)]
 #![feature(ptr_metadata)]
 #![feature(slice_ptr_get)]
-#![feature(specialization)]
 #![feature(strict_provenance)]
 #![feature(unsize)]
 #![feature(unwrap_infallible)]
+#![cfg_attr(feature = "alloc", feature(const_alloc_error))]
+#![cfg_attr(feature = "prefix-free-hash", feature(hasher_prefixfree_extras))]
+#![cfg_attr(feature = "specialization", feature(specialization))]
+#![cfg_attr(docsrs, feature(doc_cfg))]
 //  Lints
 #![deny(missing_docs)]
 #![deny(unsafe_op_in_unsafe_fn)]
-#![allow(incomplete_features)] //  For specialization.
+#![cfg_attr(feature = "specialization", allow(incomplete_features))] //  For specialization.
 
 #[cfg(feature = "alloc")]
-extern crate alloc;
+extern crate alloc as std_alloc;
 
 mod alloc;
+#[cfg(feature = "c-alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "c-alloc")))]
+pub mod c_alloc;
 pub mod collection;
+pub mod compat;
 pub mod extension;
 pub mod interface;
+pub mod prelude;
 pub mod store;
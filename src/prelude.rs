@@ -0,0 +1,52 @@
+//! The prelude, for convenient importing.
+//!
+//! Re-exports the `Store` traits, the main handles, the provided stores, and the collections, so that a single
+//! `use storage::prelude::*;` suffices to get started.
+
+pub use crate::interface::{
+    Store, StoreDangling, StoreIntrospect, StoreMultiple, StorePersist, StorePinning, StoreRegion, StoreSharing,
+    StoreSingle, StoreStable, StoreValidate,
+};
+
+pub use crate::extension::{
+    typed::TypedHandle, typed_single::TypedSingleHandle, unique::UniqueHandle, unique_single::UniqueSingleHandle,
+};
+
+pub use crate::store::{
+    InlineBumpStore, InlineSingleStore, PoolStore, RefStore, SharingPool, SmallStore, StackBumpBlock, StackBumpStore,
+    StackChainStore,
+};
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use crate::store::{ArcStore, BoxStore, Global, RcStore, StabilizingStore};
+
+pub use crate::collection::{
+    ConcurrentVec, ConcurrentVecRef, GapBuffer, IntrusiveList, LinkedList, RefStoreBox, SegmentedVec, SkipList,
+    SpscQueue, StoreBox, StoreCell, StoreCowSlice, StoreHashSet, StorePool, StoreSoA, StoreStableVec, StoreVec,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_use() {
+        let block = StackBumpBlock::<[u32; 16]>::new();
+        let store = block.create_store::<u8>();
+
+        let mut list = LinkedList::new_in(store);
+        list.try_push_front(1u32).unwrap();
+
+        assert_eq!(Some(&1), list.front());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn global() {
+        let mut vec = StoreVec::new_in(Global);
+        vec.push(1u32);
+
+        assert_eq!(&[1], vec.as_slice());
+    }
+} // mod tests
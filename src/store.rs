@@ -1,10 +1,696 @@
 //! Provides implementations of multiple stores or store adapters.
 
+mod aligned_block;
 mod allocator_store;
+#[cfg(feature = "alloc")]
+mod box_store;
+mod bump_core;
+#[cfg(feature = "alloc")]
+mod checked_store;
 mod inline_bump_store;
 mod inline_single_store;
+mod pool_store;
+mod ref_store;
+mod rounding;
+#[cfg(feature = "alloc")]
+mod scripted_store;
+mod self_hosted_store;
+mod shared_cell_store;
+#[cfg(feature = "alloc")]
+mod shared_store;
+mod sharing_store;
+mod small_store;
+#[cfg(feature = "alloc")]
+mod stabilizing_store;
 mod stack_bump_store;
+mod stack_chain_store;
+mod tagged_stats_store;
+mod tagged_store;
+mod tracking_store;
 
+pub use aligned_block::{
+    Align, Align1, Align1024, Align128, Align16, Align2, Align2048, Align256, Align32, Align4, Align4096, Align512,
+    Align64, Align8, AlignedBlock, SupportedAlign,
+};
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use box_store::BoxStore;
+#[cfg(debug_assertions)]
+pub use bump_core::BumpDump;
+pub use bump_core::{assert_handle_fits, BumpCore, BumpError, BumpMemory, HandleFromUsize, MaxOffset};
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use checked_store::{CheckedHandle, CheckedStore};
 pub use inline_bump_store::InlineBumpStore;
 pub use inline_single_store::InlineSingleStore;
+pub use pool_store::{PoolStore, SharingExhausted, SharingPool};
+pub use ref_store::RefStore;
+pub use rounding::{NoRounding, RoundToAlign, RoundToPowerOfTwo, RoundingPolicy};
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use scripted_store::{Script, ScriptedBehavior, ScriptedOperation, ScriptedStore, SeededScript};
+pub use self_hosted_store::{SelfHostedError, SelfHostedStore};
+pub use shared_cell_store::SharedCellStore;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use shared_store::{ArcStore, RcStore};
+pub use sharing_store::SharingStore;
+pub use small_store::{SmallHandle, SmallStore};
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use stabilizing_store::{StabilizingHandle, StabilizingStore};
 pub use stack_bump_store::{StackBumpBlock, StackBumpStore};
+pub use stack_chain_store::StackChainStore;
+pub use tagged_stats_store::{TagStats, TaggedStatsStore};
+pub use tagged_store::TaggedStore;
+pub use tracking_store::TrackingStore;
+
+/// An `InlineBumpStore` with `u32` offsets.
+///
+/// On 16-bits and 32-bits targets, such as wasm32, any offset within the block of memory is representable.
+pub type InlineBumpStore32<T> = InlineBumpStore<u32, T>;
+
+/// A `StackBumpStore` with `u32` offsets.
+///
+/// On 16-bits and 32-bits targets, such as wasm32, any offset within the block of memory is representable.
+pub type StackBumpStore32<'a> = StackBumpStore<'a, u32>;
+
+/// The global memory allocator, usable as a `Store` with `AllocatorHandle` handles.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use std_alloc::alloc::Global;
+
+pub use allocator_store::AllocatorHandle;
+
+//  Implements `StoreDangling`, `Store`, `StoreSingle`, `StoreValidate`, `StoreEnumerate`, `StoreIntrospect`, and
+//  `StorePersist` for a struct, by forwarding every call to the underlying store.
+//
+//  The generic parameters are listed in square brackets after `impl`, and the type of the underlying store is named
+//  after `=>`. The underlying store is the one the first field of a tuple struct dereferences to, unless a path of
+//  fields is given after `via`, such as `via pool.store`. The `unsafe` keyword is mandatory, as a reminder that the
+//  caller vouches that forwarding upholds the guarantees of the traits, which should be justified by a `Safety` comment
+//  just like any `unsafe impl`.
+macro_rules! delegate_store {
+    ($(unsafe impl[$($generics:tt)*] Store for $ty:ty => $inner:ident $(via $($field:ident).+)?;)*) => {
+        $(
+            $crate::store::delegate_store!(@impl [$($generics)*] $ty, $inner, [$($($field).+)?]);
+        )*
+    };
+    (@impl [$($generics:tt)*] $ty:ty, $inner:ident, []) => {
+        $crate::store::delegate_store!(@impl [$($generics)*] $ty, $inner, [0]);
+    };
+    (@impl [$($generics:tt)*] $ty:ty, $inner:ident, [$($path:tt)+]) => {
+        unsafe impl<$($generics)*> $crate::interface::StoreDangling for $ty
+        where
+            $inner: $crate::interface::StoreDangling,
+        {
+            type Handle = $inner::Handle;
+
+            #[inline(always)]
+            fn dangling(
+                &self,
+                alignment: ::core::ptr::Alignment,
+            ) -> ::core::result::Result<Self::Handle, ::core::alloc::AllocError> {
+                self.$($path)+.dangling(alignment)
+            }
+        }
+
+        unsafe impl<$($generics)*> $crate::interface::Store for $ty
+        where
+            $inner: $crate::interface::Store,
+        {
+            const GROWTH: $crate::interface::Growth = <$inner as $crate::interface::Store>::GROWTH;
+
+            #[inline(always)]
+            unsafe fn resolve(&self, handle: Self::Handle) -> ::core::ptr::NonNull<u8> {
+                //  Safety:
+                //  -   As per pre-conditions.
+                unsafe { self.$($path)+.resolve(handle) }
+            }
+
+            #[inline(always)]
+            unsafe fn resolve_many(
+                &self,
+                handles: &[Self::Handle],
+                out: &mut [::core::mem::MaybeUninit<::core::ptr::NonNull<u8>>],
+            ) {
+                //  Safety:
+                //  -   As per pre-conditions.
+                unsafe { self.$($path)+.resolve_many(handles, out) }
+            }
+
+            #[inline(always)]
+            fn allocate(
+                &self,
+                layout: ::core::alloc::Layout,
+            ) -> ::core::result::Result<(Self::Handle, usize), ::core::alloc::AllocError> {
+                self.$($path)+.allocate(layout)
+            }
+
+            #[inline(always)]
+            unsafe fn deallocate(&self, handle: Self::Handle, layout: ::core::alloc::Layout) {
+                //  Safety:
+                //  -   As per pre-conditions.
+                unsafe { self.$($path)+.deallocate(handle, layout) }
+            }
+
+            #[inline(always)]
+            unsafe fn grow(
+                &self,
+                handle: Self::Handle,
+                old_layout: ::core::alloc::Layout,
+                new_layout: ::core::alloc::Layout,
+            ) -> ::core::result::Result<(Self::Handle, usize), ::core::alloc::AllocError> {
+                //  Safety:
+                //  -   As per pre-conditions.
+                unsafe { self.$($path)+.grow(handle, old_layout, new_layout) }
+            }
+
+            #[inline(always)]
+            unsafe fn shrink(
+                &self,
+                handle: Self::Handle,
+                old_layout: ::core::alloc::Layout,
+                new_layout: ::core::alloc::Layout,
+            ) -> ::core::result::Result<(Self::Handle, usize), ::core::alloc::AllocError> {
+                //  Safety:
+                //  -   As per pre-conditions.
+                unsafe { self.$($path)+.shrink(handle, old_layout, new_layout) }
+            }
+
+            #[inline(always)]
+            fn allocate_zeroed(
+                &self,
+                layout: ::core::alloc::Layout,
+            ) -> ::core::result::Result<(Self::Handle, usize), ::core::alloc::AllocError> {
+                self.$($path)+.allocate_zeroed(layout)
+            }
+
+            #[inline(always)]
+            unsafe fn grow_zeroed(
+                &self,
+                handle: Self::Handle,
+                old_layout: ::core::alloc::Layout,
+                new_layout: ::core::alloc::Layout,
+            ) -> ::core::result::Result<(Self::Handle, usize), ::core::alloc::AllocError> {
+                //  Safety:
+                //  -   As per pre-conditions.
+                unsafe { self.$($path)+.grow_zeroed(handle, old_layout, new_layout) }
+            }
+
+            #[inline(always)]
+            unsafe fn grow_reporting(
+                &self,
+                handle: Self::Handle,
+                old_layout: ::core::alloc::Layout,
+                new_layout: ::core::alloc::Layout,
+            ) -> ::core::result::Result<(Self::Handle, usize, bool), ::core::alloc::AllocError> {
+                //  Safety:
+                //  -   As per pre-conditions.
+                unsafe { self.$($path)+.grow_reporting(handle, old_layout, new_layout) }
+            }
+
+            #[inline(always)]
+            unsafe fn shrink_reporting(
+                &self,
+                handle: Self::Handle,
+                old_layout: ::core::alloc::Layout,
+                new_layout: ::core::alloc::Layout,
+            ) -> ::core::result::Result<(Self::Handle, usize, bool), ::core::alloc::AllocError> {
+                //  Safety:
+                //  -   As per pre-conditions.
+                unsafe { self.$($path)+.shrink_reporting(handle, old_layout, new_layout) }
+            }
+        }
+
+        unsafe impl<$($generics)*> $crate::interface::StoreSingle for $ty
+        where
+            $inner: $crate::interface::Store,
+        {
+            const GROWTH: $crate::interface::Growth = <$inner as $crate::interface::Store>::GROWTH;
+
+            #[inline(always)]
+            unsafe fn resolve(&self, handle: Self::Handle) -> ::core::ptr::NonNull<u8> {
+                //  Safety:
+                //  -   As per pre-conditions.
+                unsafe { <Self as $crate::interface::Store>::resolve(self, handle) }
+            }
+
+            #[inline(always)]
+            unsafe fn resolve_mut(&mut self, handle: Self::Handle) -> ::core::ptr::NonNull<u8> {
+                //  Safety:
+                //  -   As per pre-conditions.
+                unsafe { <Self as $crate::interface::Store>::resolve(self, handle) }
+            }
+
+            #[inline(always)]
+            fn allocate(
+                &mut self,
+                layout: ::core::alloc::Layout,
+            ) -> ::core::result::Result<(Self::Handle, usize), ::core::alloc::AllocError> {
+                <Self as $crate::interface::Store>::allocate(self, layout)
+            }
+
+            #[inline(always)]
+            unsafe fn deallocate(&mut self, handle: Self::Handle, layout: ::core::alloc::Layout) {
+                //  Safety:
+                //  -   As per pre-conditions.
+                unsafe { <Self as $crate::interface::Store>::deallocate(self, handle, layout) }
+            }
+
+            #[inline(always)]
+            unsafe fn grow(
+                &mut self,
+                handle: Self::Handle,
+                old_layout: ::core::alloc::Layout,
+                new_layout: ::core::alloc::Layout,
+            ) -> ::core::result::Result<(Self::Handle, usize), ::core::alloc::AllocError> {
+                //  Safety:
+                //  -   As per pre-conditions.
+                unsafe { <Self as $crate::interface::Store>::grow(self, handle, old_layout, new_layout) }
+            }
+
+            #[inline(always)]
+            unsafe fn shrink(
+                &mut self,
+                handle: Self::Handle,
+                old_layout: ::core::alloc::Layout,
+                new_layout: ::core::alloc::Layout,
+            ) -> ::core::result::Result<(Self::Handle, usize), ::core::alloc::AllocError> {
+                //  Safety:
+                //  -   As per pre-conditions.
+                unsafe { <Self as $crate::interface::Store>::shrink(self, handle, old_layout, new_layout) }
+            }
+
+            #[inline(always)]
+            fn allocate_zeroed(
+                &mut self,
+                layout: ::core::alloc::Layout,
+            ) -> ::core::result::Result<(Self::Handle, usize), ::core::alloc::AllocError> {
+                <Self as $crate::interface::Store>::allocate_zeroed(self, layout)
+            }
+
+            #[inline(always)]
+            unsafe fn grow_zeroed(
+                &mut self,
+                handle: Self::Handle,
+                old_layout: ::core::alloc::Layout,
+                new_layout: ::core::alloc::Layout,
+            ) -> ::core::result::Result<(Self::Handle, usize), ::core::alloc::AllocError> {
+                //  Safety:
+                //  -   As per pre-conditions.
+                unsafe { <Self as $crate::interface::Store>::grow_zeroed(self, handle, old_layout, new_layout) }
+            }
+
+            #[inline(always)]
+            unsafe fn grow_reporting(
+                &mut self,
+                handle: Self::Handle,
+                old_layout: ::core::alloc::Layout,
+                new_layout: ::core::alloc::Layout,
+            ) -> ::core::result::Result<(Self::Handle, usize, bool), ::core::alloc::AllocError> {
+                //  Safety:
+                //  -   As per pre-conditions.
+                unsafe { <Self as $crate::interface::Store>::grow_reporting(self, handle, old_layout, new_layout) }
+            }
+
+            #[inline(always)]
+            unsafe fn shrink_reporting(
+                &mut self,
+                handle: Self::Handle,
+                old_layout: ::core::alloc::Layout,
+                new_layout: ::core::alloc::Layout,
+            ) -> ::core::result::Result<(Self::Handle, usize, bool), ::core::alloc::AllocError> {
+                //  Safety:
+                //  -   As per pre-conditions.
+                unsafe { <Self as $crate::interface::Store>::shrink_reporting(self, handle, old_layout, new_layout) }
+            }
+        }
+
+        impl<$($generics)*> $crate::interface::StoreValidate for $ty
+        where
+            $inner: $crate::interface::StoreValidate,
+        {
+            fn is_valid(&self, handle: Self::Handle) -> ::core::option::Option<bool> {
+                self.$($path)+.is_valid(handle)
+            }
+        }
+
+        impl<$($generics)*> $crate::interface::StoreEnumerate for $ty
+        where
+            $inner: $crate::interface::StoreEnumerate,
+        {
+            fn for_each_handle(&self, f: impl FnMut(Self::Handle, ::core::alloc::Layout)) {
+                self.$($path)+.for_each_handle(f)
+            }
+        }
+
+        impl<$($generics)*> $crate::interface::StoreIntrospect for $ty
+        where
+            $inner: $crate::interface::StoreIntrospect,
+        {
+            fn limits(&self) -> $crate::interface::StoreLimits {
+                self.$($path)+.limits()
+            }
+        }
+
+        impl<$($generics)*> $crate::interface::StorePersist for $ty
+        where
+            $inner: $crate::interface::StorePersist,
+        {
+            unsafe fn flush(&self, handle: Self::Handle, layout: ::core::alloc::Layout) {
+                //  Safety:
+                //  -   As per pre-conditions.
+                unsafe { self.$($path)+.flush(handle, layout) }
+            }
+
+            fn snapshot(&self) -> ::core::result::Result<(), $crate::interface::PersistError> {
+                self.$($path)+.snapshot()
+            }
+        }
+    };
+}
+
+use delegate_store;
+
+#[cfg(test)]
+mod tests {
+    use core::{alloc::Layout, fmt, mem::MaybeUninit, ptr::Alignment};
+    use std::alloc::System;
+
+    use crate::interface::{Store, StoreIntrospect, StoreLimits, StoreMultiple, StoreSharing, StoreSingle};
+
+    use super::*;
+
+    fn assert_multiple<S: StoreMultiple>() {}
+
+    #[test]
+    fn store_multiple() {
+        assert_multiple::<System>();
+        assert_multiple::<InlineBumpStore<u8, [u8; 16]>>();
+        assert_multiple::<StackBumpStore<'static, u8>>();
+        assert_multiple::<StackChainStore<'static, [u8; 16], u8>>();
+    }
+
+    fn check_resolve_many<S: Store>(store: &S, layout: Layout, count: usize) {
+        let handles: Vec<_> = (0..count).map(|_| store.allocate(layout).unwrap().0).collect();
+
+        let mut pointers = vec![MaybeUninit::uninit(); count + 1];
+
+        //  Safety:
+        //  -   `handles` were allocated by `store`, and are still valid.
+        unsafe { store.resolve_many(&handles, &mut pointers) };
+
+        for (handle, pointer) in handles.iter().zip(&pointers) {
+            //  Safety:
+            //  -   `handle` was allocated by `store`, and is still valid.
+            //  -   `pointer` was initialized by `resolve_many`.
+            assert_eq!(unsafe { store.resolve(*handle) }, unsafe { pointer.assume_init() });
+        }
+    }
+
+    #[test]
+    fn resolve_many() {
+        let layout = Layout::new::<[u32; 2]>();
+
+        check_resolve_many(&InlineBumpStore::<u8, [u32; 8]>::default(), layout, 3);
+
+        //  Spanning two blocks.
+        let (first, second) = (StackBumpBlock::<[u32; 4]>::new(), StackBumpBlock::new());
+        let blocks = [&first, &second];
+
+        check_resolve_many(&StackBumpBlock::chain::<u8>(&blocks), layout, 4);
+    }
+
+    //  Checks that `store` accepts any layout fitting a block of memory, not solely the requested layout.
+    fn check_fitting_layouts<S: Store>(store: &S) {
+        let layout = Layout::from_size_align(6, 4).unwrap();
+        let fitting = |size| Layout::from_size_align(size, layout.align()).unwrap();
+
+        //  Deallocating with the actual size.
+        let (handle, size) = store.allocate(layout).unwrap();
+
+        assert!(size >= layout.size());
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        //  -   `fitting(size)` fits, as `size` is the actual size.
+        unsafe { store.deallocate(handle, fitting(size)) };
+
+        //  Growing from the actual size, then shrinking from the actual size.
+        let (handle, size) = store.allocate(layout).unwrap();
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        //  -   `fitting(size)` fits, as `size` is the actual size.
+        let (handle, size) = unsafe { store.grow(handle, fitting(size), fitting(size + 8)) }.unwrap();
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        //  -   `fitting(size)` fits, as `size` is the actual size.
+        let (handle, _) = unsafe { store.shrink(handle, fitting(size), layout) }.unwrap();
+
+        //  Deallocating with the requested size, discarding the actual size.
+        //
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        //  -   `layout` fits, as it was requested.
+        unsafe { store.deallocate(handle, layout) };
+    }
+
+    //  Checks that `store` accepts any layout fitting a block of memory, not solely the requested layout.
+    fn check_fitting_layouts_single<S: StoreSingle>(store: &mut S) {
+        let layout = Layout::from_size_align(6, 4).unwrap();
+        let fitting = |size| Layout::from_size_align(size, layout.align()).unwrap();
+
+        //  Deallocating with the actual size.
+        let (handle, size) = store.allocate(layout).unwrap();
+
+        assert!(size >= layout.size());
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        //  -   `fitting(size)` fits, as `size` is the actual size.
+        unsafe { store.deallocate(handle, fitting(size)) };
+
+        //  Shrinking from the actual size.
+        let (handle, size) = store.allocate(layout).unwrap();
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        //  -   `fitting(size)` fits, as `size` is the actual size.
+        let (handle, _) = unsafe { store.shrink(handle, fitting(size), fitting(4)) }.unwrap();
+
+        //  Deallocating with the requested size, discarding the actual size.
+        //
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        //  -   `fitting(4)` fits, as it was requested.
+        unsafe { store.deallocate(handle, fitting(4)) };
+    }
+
+    #[test]
+    fn fitting_layouts() {
+        check_fitting_layouts(&System);
+        check_fitting_layouts(&InlineBumpStore::<u8, [u32; 16]>::default());
+
+        let block = StackBumpBlock::<[u32; 16]>::new();
+
+        check_fitting_layouts(&block.create_store::<u8>());
+
+        let (first, second) = (StackBumpBlock::<[u32; 16]>::new(), StackBumpBlock::new());
+        let blocks = [&first, &second];
+
+        check_fitting_layouts(&StackBumpBlock::chain::<u8>(&blocks));
+
+        check_fitting_layouts_single(&mut InlineSingleStore::<[u32; 4]>::new());
+        check_fitting_layouts_single(&mut SmallStore::<[u32; 4], System>::new(System));
+    }
+
+    //  Checks that the handles allocated by `store` remain usable by the other parts of its sharing set, after `store`
+    //  is moved, then dropped.
+    fn check_sharing_part_dropped<S>(store: S)
+    where
+        S: Store + StoreSharing<SharingError: fmt::Debug>,
+    {
+        let layout = Layout::new::<[u32; 4]>();
+        let grown = Layout::new::<[u32; 8]>();
+
+        let (handle, _) = store.allocate(layout).unwrap();
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        //  -   `handle` is associated to a block of memory fitting `[u32; 4]`.
+        unsafe { store.resolve(handle).cast::<[u32; 4]>().write([1, 2, 3, 4]) };
+
+        let token = store.sharing_token();
+
+        //  Moving the part to another address, then creating another part from it, and dropping it.
+        let moved = Box::new(store);
+        let other = moved.share().unwrap();
+
+        assert_eq!(token, moved.sharing_token());
+
+        drop(moved);
+
+        assert_eq!(token, other.sharing_token());
+
+        //  Safety:
+        //  -   `handle` was allocated by a part of the sharing set of `other`, and is still valid.
+        //  -   `handle` is associated to an initialized `[u32; 4]`.
+        assert_eq!([1, 2, 3, 4], unsafe { other.resolve(handle).cast::<[u32; 4]>().read() });
+
+        //  Allocating in-between, so that growing cannot simply extend the block of memory of `handle`.
+        let (blocker, _) = other.allocate(layout).unwrap();
+
+        //  Safety:
+        //  -   `handle` was allocated by a part of the sharing set of `other`, and is still valid.
+        //  -   `layout` fits the block of memory associated to `handle`.
+        //  -   `grown` is larger than `layout`.
+        let (handle, _) = unsafe { other.grow(handle, layout, grown) }.unwrap();
+
+        //  Safety:
+        //  -   `handle` was returned by `other.grow`, and is still valid.
+        //  -   The first 4 elements of `handle` were initialized, and preserved by `grow`.
+        assert_eq!([1, 2, 3, 4], unsafe { other.resolve(handle).cast::<[u32; 4]>().read() });
+
+        //  Safety:
+        //  -   `handle` and `blocker` were allocated by a part of the sharing set of `other`, and are still valid.
+        //  -   `grown` and `layout` fit their respective blocks of memory.
+        unsafe {
+            other.deallocate(handle, grown);
+            other.deallocate(blocker, layout);
+        }
+    }
+
+    #[test]
+    fn sharing_part_dropped() {
+        let block = StackBumpBlock::<[u32; 32]>::new();
+
+        check_sharing_part_dropped(block.create_store::<u8>());
+
+        let (first, second) = (StackBumpBlock::<[u32; 16]>::new(), StackBumpBlock::new());
+        let blocks = [&first, &second];
+
+        check_sharing_part_dropped(StackBumpBlock::chain::<u8>(&blocks));
+
+        let inline = InlineBumpStore::<u8, [u32; 32]>::default();
+
+        check_sharing_part_dropped(RefStore::new(&inline));
+
+        let pool = SharingPool::new(InlineBumpStore::<u8, [u32; 32]>::default(), 2);
+
+        check_sharing_part_dropped(pool.try_create_store().unwrap());
+
+        assert_eq!(2, pool.available());
+    }
+
+    #[test]
+    fn sharing_token_registry() {
+        use std::collections::HashMap;
+
+        let (first, second) = (InlineBumpStore::<u8, [u32; 8]>::default(), InlineBumpStore::default());
+
+        let stores = [RefStore::new(&first), RefStore::new(&second), RefStore::new(&first)];
+
+        let mut registry = HashMap::new();
+
+        for (index, store) in stores.iter().enumerate() {
+            registry
+                .entry(store.sharing_token())
+                .or_insert_with(Vec::new)
+                .push(index);
+        }
+
+        assert_eq!(2, registry.len());
+        assert_eq!(vec![0, 2], registry[&stores[0].sharing_token()]);
+        assert_eq!(vec![1], registry[&stores[1].sharing_token()]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn sharing_part_dropped_alloc() {
+        check_sharing_part_dropped(Global);
+        check_sharing_part_dropped(RcStore::new(InlineBumpStore::<u8, [u32; 32]>::default()));
+        check_sharing_part_dropped(ArcStore::new(InlineBumpStore::<u8, [u32; 32]>::default()));
+    }
+
+    //  Checks that `store` never satisfies a layout beyond its limits, and satisfies the largest layout within them.
+    fn check_limits<S: Store + StoreIntrospect>(store: &S) -> StoreLimits {
+        let limits = store.limits();
+
+        let beyond_size = Layout::from_size_align(limits.max_size + 1, 1).unwrap();
+        let beyond_align = Layout::from_size_align(1, limits.max_align.as_usize() * 2).unwrap();
+
+        assert!(store.allocate(beyond_size).is_err());
+        assert!(store.allocate(beyond_align).is_err());
+
+        let largest = Layout::from_size_align(limits.max_size, 1).unwrap();
+
+        assert!(store.allocate(largest).is_ok());
+
+        limits
+    }
+
+    fn limits_of<T>(max_size: usize, supports_dealloc_reuse: bool) -> StoreLimits {
+        let max_align = Alignment::of::<T>();
+
+        StoreLimits {
+            max_align,
+            max_size,
+            supports_dealloc_reuse,
+        }
+    }
+
+    #[test]
+    fn limits() {
+        let inline = check_limits(&InlineBumpStore::<u8, [u32; 8]>::default());
+
+        assert_eq!(limits_of::<u32>(32, false), inline);
+
+        let block = StackBumpBlock::<[u32; 8]>::new();
+        let stack = check_limits(&block.create_store::<u8>());
+
+        assert!(stack.max_align.as_usize() >= 4);
+        assert_eq!(32, stack.max_size);
+
+        let (first, second) = (StackBumpBlock::<[u32; 8]>::new(), StackBumpBlock::new());
+        let blocks = [&first, &second];
+
+        assert_eq!(inline, check_limits(&StackBumpBlock::chain::<u8>(&blocks)));
+
+        let single = InlineSingleStore::<[u16; 3]>::new().limits();
+
+        assert_eq!(limits_of::<u16>(6, true), single);
+
+        let small = SmallStore::<[u16; 3], InlineBumpStore<u8, [u32; 8]>>::default().limits();
+
+        assert_eq!(limits_of::<u32>(32, false), small);
+
+        assert_eq!(StoreLimits::UNBOUNDED, System.limits());
+        assert!(StoreLimits::UNBOUNDED.fits(Layout::new::<[u64; 1024]>()));
+    }
+
+    #[test]
+    fn u32_offsets() {
+        //  Any offset within the blocks of memory is representable by `u32`, regardless of the target.
+        const _: () = assert_handle_fits::<u32, [u64; 4]>();
+
+        let block = StackBumpBlock::<[u64; 4]>::new();
+        let store: StackBumpStore32<'_> = block.create_store();
+
+        Store::allocate(&store, Layout::new::<u8>()).unwrap();
+
+        let (handle, _) = Store::allocate(&store, Layout::new::<u64>()).unwrap();
+
+        assert_eq!(8, handle);
+
+        let store = InlineBumpStore32::<[u64; 4]>::default();
+
+        Store::allocate(&store, Layout::new::<u8>()).unwrap();
+
+        let (handle, _) = Store::allocate(&store, Layout::new::<u64>()).unwrap();
+
+        assert_eq!(8, handle);
+    }
+} // mod tests
@@ -0,0 +1,141 @@
+//! A block of memory of explicit size and alignment.
+//!
+//! The block-based stores size and align their block of memory as per a type parameter `T`, which requires finding a
+//! type with the desired geometry. `AlignedBlock<SIZE, ALIGN>` makes the geometry explicit instead:
+//!
+//! ```
+//! #![feature(allocator_api)]
+//!
+//! use storage::{
+//!     collection::StoreVec,
+//!     store::{AlignedBlock, InlineBumpStore},
+//! };
+//!
+//! //  256 bytes, aligned on 16 bytes.
+//! type Block = AlignedBlock<256, 16>;
+//!
+//! let mut vec = StoreVec::<u128, InlineBumpStore<u16, Block>>::new();
+//!
+//! vec.extend([1, 2, 3]);
+//!
+//! assert_eq!([1, 2, 3], &vec[..]);
+//! ```
+//!
+//! The alignment is provided by one of the `AlignN` zero-sized types, for `N` any power of 2 from 1 to 4096, as
+//! `#[repr(align(...))]` cannot be generic.
+
+use core::fmt;
+
+/// A block of memory of `SIZE` bytes, aligned on `ALIGN` bytes.
+///
+/// Meant to be used as the `T` parameter of `StackBumpBlock`, `InlineBumpStore`, or `InlineSingleStore`. As for any
+/// Rust type, the size is a multiple of the alignment, hence `SIZE` is rounded up to the next multiple of `ALIGN`.
+#[repr(C)]
+pub struct AlignedBlock<const SIZE: usize, const ALIGN: usize>
+where
+    Align<ALIGN>: SupportedAlign,
+{
+    _alignment: [<Align<ALIGN> as SupportedAlign>::Aligned; 0],
+    _bytes: [u8; SIZE],
+}
+
+/// A marker for an alignment of `ALIGN` bytes, implementing `SupportedAlign` if `ALIGN` is supported.
+pub struct Align<const ALIGN: usize>;
+
+/// An alignment for which a zero-sized type aligned on that many bytes is provided.
+pub trait SupportedAlign {
+    /// A zero-sized type, with the alignment of this marker.
+    type Aligned;
+}
+
+impl<const SIZE: usize, const ALIGN: usize> fmt::Debug for AlignedBlock<SIZE, ALIGN>
+where
+    Align<ALIGN>: SupportedAlign,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_struct("AlignedBlock")
+            .field("size", &SIZE)
+            .field("align", &ALIGN)
+            .finish()
+    }
+}
+
+macro_rules! aligned {
+    ($($name:ident = $align:literal),* $(,)?) => {
+        $(
+            #[doc = concat!("A zero-sized type aligned on ", stringify!($align), " bytes.")]
+            #[derive(Clone, Copy, Debug, Default)]
+            #[repr(align($align))]
+            pub struct $name;
+
+            impl SupportedAlign for Align<$align> {
+                type Aligned = $name;
+            }
+        )*
+    };
+}
+
+aligned! {
+    Align1 = 1,
+    Align2 = 2,
+    Align4 = 4,
+    Align8 = 8,
+    Align16 = 16,
+    Align32 = 32,
+    Align64 = 64,
+    Align128 = 128,
+    Align256 = 256,
+    Align512 = 512,
+    Align1024 = 1024,
+    Align2048 = 2048,
+    Align4096 = 4096,
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{alloc::Layout, mem};
+
+    use crate::{
+        collection::StoreBox,
+        interface::Store,
+        store::{InlineSingleStore, StackBumpBlock},
+    };
+
+    use super::*;
+
+    #[test]
+    fn geometry() {
+        let geometry = |layout: Layout| (layout.size(), layout.align());
+
+        assert_eq!((24, 8), geometry(Layout::new::<AlignedBlock<24, 8>>()));
+        assert_eq!((64, 64), geometry(Layout::new::<AlignedBlock<1, 64>>()));
+        assert_eq!((0, 4096), geometry(Layout::new::<AlignedBlock<0, 4096>>()));
+    }
+
+    #[test]
+    fn stack_bump_block() {
+        let block = StackBumpBlock::<AlignedBlock<64, 32>>::new();
+        let store = block.create_store::<u8>();
+
+        let (first, _) = Store::allocate(&store, Layout::new::<u8>()).unwrap();
+        let (second, _) = Store::allocate(&store, Layout::from_size_align(32, 32).unwrap()).unwrap();
+
+        //  Safety:
+        //  -   `first` and `second` were allocated by `store`, and are still valid.
+        let (first, second) = unsafe { (Store::resolve(&store, first), Store::resolve(&store, second)) };
+
+        assert_eq!(0, first.addr().get() % 32);
+        assert_eq!(32, second.addr().get() - first.addr().get());
+    }
+
+    #[test]
+    fn inline_single_store() {
+        let boxed = StoreBox::new_in([1u64, 2], InlineSingleStore::<AlignedBlock<16, 8>>::new());
+
+        assert_eq!([1, 2], *boxed);
+
+        //  The handles of `coercible-metadata` embed a pointer, and are therefore not zero-sized.
+        #[cfg(not(feature = "coercible-metadata"))]
+        assert_eq!(16, mem::size_of_val(&boxed));
+    }
+} // mod tests
@@ -6,26 +6,32 @@ use core::{
 };
 
 #[cfg(feature = "alloc")]
-use alloc::alloc::Global;
+use std_alloc::alloc::Global;
 
-use crate::interface::{Store, StoreDangling, StorePinning, StoreSingle, StoreStable};
+use crate::interface::{Store, StoreDangling, StoreIntrospect, StorePersist, StoreSingle};
 
 #[cfg(feature = "alloc")]
 use crate::interface::StoreSharing;
 
+/// The handle of any `Allocator` used as a `Store`, a thin wrapper around the allocated pointer.
+///
+/// The handle is `#[repr(transparent)]`, and thus has the layout of a non-null C pointer.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[repr(transparent)]
 pub struct AllocatorHandle(NonNull<u8>);
 
 unsafe impl Send for AllocatorHandle {}
 unsafe impl Sync for AllocatorHandle {}
 
 impl From<NonNull<u8>> for AllocatorHandle {
+    #[inline(always)]
     fn from(value: NonNull<u8>) -> Self {
         Self(value)
     }
 }
 
 impl From<AllocatorHandle> for NonNull<u8> {
+    #[inline(always)]
     fn from(value: AllocatorHandle) -> Self {
         value.0
     }
@@ -37,6 +43,7 @@ where
 {
     type Handle = AllocatorHandle;
 
+    #[inline(always)]
     fn dangling(&self, alignment: Alignment) -> Result<Self::Handle, AllocError> {
         let pointer = ptr::invalid_mut(alignment.as_usize());
 
@@ -52,14 +59,17 @@ unsafe impl<A> Store for A
 where
     A: Allocator,
 {
+    #[inline(always)]
     unsafe fn resolve(&self, handle: Self::Handle) -> NonNull<u8> {
         handle.into()
     }
 
+    #[inline]
     fn allocate(&self, layout: Layout) -> Result<(Self::Handle, usize), AllocError> {
         Allocator::allocate(self, layout).map(|slice| (slice.as_non_null_ptr().into(), slice.len()))
     }
 
+    #[inline]
     unsafe fn deallocate(&self, handle: Self::Handle, layout: Layout) {
         //  Safety:
         //  -   `handle` is valid, as per the pre-conditions of `deallocate`.
@@ -67,6 +77,7 @@ where
         unsafe { Allocator::deallocate(self, handle.into(), layout) };
     }
 
+    #[inline]
     unsafe fn grow(
         &self,
         handle: Self::Handle,
@@ -83,6 +94,7 @@ where
         result.map(|slice| (slice.as_non_null_ptr().into(), slice.len()))
     }
 
+    #[inline]
     unsafe fn shrink(
         &self,
         handle: Self::Handle,
@@ -99,10 +111,12 @@ where
         result.map(|slice| (slice.as_non_null_ptr().into(), slice.len()))
     }
 
+    #[inline]
     fn allocate_zeroed(&self, layout: Layout) -> Result<(Self::Handle, usize), AllocError> {
         Allocator::allocate_zeroed(self, layout).map(|slice| (slice.as_non_null_ptr().into(), slice.len()))
     }
 
+    #[inline]
     unsafe fn grow_zeroed(
         &self,
         handle: Self::Handle,
@@ -124,18 +138,22 @@ unsafe impl<A> StoreSingle for A
 where
     A: Allocator,
 {
+    #[inline(always)]
     unsafe fn resolve(&self, handle: Self::Handle) -> NonNull<u8> {
         handle.into()
     }
 
+    #[inline(always)]
     unsafe fn resolve_mut(&mut self, handle: Self::Handle) -> NonNull<u8> {
         handle.into()
     }
 
+    #[inline]
     fn allocate(&mut self, layout: Layout) -> Result<(Self::Handle, usize), AllocError> {
         Allocator::allocate(self, layout).map(|slice| (slice.as_non_null_ptr().into(), slice.len()))
     }
 
+    #[inline]
     unsafe fn deallocate(&mut self, handle: Self::Handle, layout: Layout) {
         //  Safety:
         //  -   `handle` is valid, as per the pre-conditions of `deallocate`.
@@ -143,6 +161,7 @@ where
         unsafe { Allocator::deallocate(self, handle.into(), layout) };
     }
 
+    #[inline]
     unsafe fn grow(
         &mut self,
         handle: Self::Handle,
@@ -159,6 +178,7 @@ where
         result.map(|slice| (slice.as_non_null_ptr().into(), slice.len()))
     }
 
+    #[inline]
     unsafe fn shrink(
         &mut self,
         handle: Self::Handle,
@@ -175,10 +195,12 @@ where
         result.map(|slice| (slice.as_non_null_ptr().into(), slice.len()))
     }
 
+    #[inline]
     fn allocate_zeroed(&mut self, layout: Layout) -> Result<(Self::Handle, usize), AllocError> {
         Allocator::allocate_zeroed(self, layout).map(|slice| (slice.as_non_null_ptr().into(), slice.len()))
     }
 
+    #[inline]
     unsafe fn grow_zeroed(
         &mut self,
         handle: Self::Handle,
@@ -196,13 +218,17 @@ where
     }
 }
 
-//  Safety:
-//  -   `Allocator` allocations are pinned.
-unsafe impl<A> StoreStable for A where A: Allocator {}
+crate::store_markers! {
+    //  Safety:
+    //  -   `Allocator` allocations are pinned.
+    unsafe impl[A] StorePinning for A where [A: Allocator];
+}
 
-//  Safety:
-//  -   `Allocator` allocations are pinned.
-unsafe impl<A> StorePinning for A where A: Allocator {}
+//  `Allocator` memory is volatile.
+impl<A> StorePersist for A where A: Allocator {}
+
+//  `Allocator` are only bounded by the limits of `Layout`.
+impl<A> StoreIntrospect for A where A: Allocator {}
 
 //  Safety:
 //  -   `Allocator` are always sharing, today.
@@ -210,10 +236,14 @@ unsafe impl<A> StorePinning for A where A: Allocator {}
 unsafe impl StoreSharing for Global {
     type SharingError = !;
 
+    type SharingToken = ();
+
     fn is_sharing_with(&self, _other: &Self) -> bool {
         true
     }
 
+    fn sharing_token(&self) -> Self::SharingToken {}
+
     fn share(&self) -> Result<Self, Self::SharingError> {
         Ok(*self)
     }
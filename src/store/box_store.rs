@@ -0,0 +1,82 @@
+//! A Store owning its underlying store behind a `Box`.
+//!
+//! _Note: the store traits cannot be implemented for `Box<S>` directly, as this would conflict with the blanket
+//! implementations for `Allocator`._
+
+use core::fmt;
+
+use std_alloc::boxed::Box;
+
+use crate::interface::StoreStable;
+
+/// A store owning its underlying store through a `Box`.
+///
+/// The underlying store does not move when `self` is moved, hence a stable store is pinning once boxed, and a large
+/// inline store may be moved around cheaply.
+pub struct BoxStore<S>(Box<S>);
+
+impl<S> BoxStore<S> {
+    /// Creates a new instance, owning `store`.
+    pub fn new(store: S) -> Self {
+        Self(Box::new(store))
+    }
+
+    /// Returns a reference to the underlying store.
+    pub fn get(&self) -> &S {
+        &self.0
+    }
+
+    /// Returns the underlying store.
+    pub fn into_inner(self) -> S {
+        *self.0
+    }
+}
+
+crate::store::delegate_store! {
+    //  Safety:
+    //  -   `self` forwards every call to `S`.
+    unsafe impl[S] Store for BoxStore<S> => S;
+}
+
+crate::store_markers! {
+    //  Safety:
+    //  -   `S` is stable, and never moves, even when `self` is moved.
+    unsafe impl[S] StorePinning for BoxStore<S> where [S: StoreStable];
+}
+
+impl<S: Default> Default for BoxStore<S> {
+    fn default() -> Self {
+        Self::new(S::default())
+    }
+}
+
+impl<S> fmt::Debug for BoxStore<S>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_tuple("BoxStore").field(&*self.0).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{collection::StoreVec, store::InlineBumpStore};
+
+    use super::*;
+
+    #[test]
+    fn moved_vector() {
+        let mut vec = StoreVec::new_in(BoxStore::new(InlineBumpStore::<u16, [u64; 32]>::default()));
+
+        vec.extend([1u32, 2, 3]);
+
+        let first = vec.as_ptr();
+
+        //  Moving the vector does not move the underlying store, hence its elements.
+        let moved = Box::new(vec);
+
+        assert_eq!(first, moved.as_ptr());
+        assert_eq!([1, 2, 3], moved[..]);
+    }
+} // mod tests
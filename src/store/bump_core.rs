@@ -0,0 +1,1355 @@
+//! The bookkeeping of a "bump allocator", reusable to implement bump stores.
+//!
+//! A bump store only needs to provide a block of memory, described by a `BumpMemory`, and a `BumpCore`: the latter
+//! takes care of aligning, rounding, and bumping the watermark, as well as converting offsets to and from handles.
+//!
+//! ```
+//! #![feature(allocator_api)]
+//! #![feature(ptr_alignment_type)]
+//!
+//! use core::{
+//!     alloc::{AllocError, Layout},
+//!     ptr::{Alignment, NonNull},
+//! };
+//!
+//! use storage::{
+//!     interface::{Store, StoreDangling},
+//!     store::{BumpCore, BumpMemory, NoRounding},
+//! };
+//!
+//! /// A bump store over a leaked block of memory, with `u32` handles.
+//! struct LeakedBumpStore {
+//!     core: BumpCore,
+//!     memory: BumpMemory,
+//! }
+//!
+//! impl LeakedBumpStore {
+//!     fn new(size: usize) -> Self {
+//!         let memory = NonNull::from(Box::leak(vec![0u64; size / 8].into_boxed_slice()));
+//!         let memory = NonNull::slice_from_raw_parts(memory.cast::<u8>(), size / 8 * 8);
+//!
+//!         Self { core: BumpCore::new(), memory: BumpMemory::movable(memory, Alignment::of::<u64>()) }
+//!     }
+//! }
+//!
+//! unsafe impl StoreDangling for LeakedBumpStore {
+//!     type Handle = u32;
+//!
+//!     fn dangling(&self, alignment: Alignment) -> Result<u32, AllocError> {
+//!         Ok(self.memory.dangling(alignment)?)
+//!     }
+//! }
+//!
+//! unsafe impl Store for LeakedBumpStore {
+//!     fn allocate(&self, layout: Layout) -> Result<(u32, usize), AllocError> {
+//!         Ok(self.core.allocate::<_, NoRounding>(self.memory, layout)?)
+//!     }
+//!
+//!     unsafe fn deallocate(&self, _handle: u32, _layout: Layout) {}
+//!
+//!     unsafe fn resolve(&self, handle: u32) -> NonNull<u8> {
+//!         unsafe { self.memory.resolve(handle) }
+//!     }
+//!
+//!     unsafe fn grow(&self, handle: u32, old: Layout, new: Layout) -> Result<(u32, usize), AllocError> {
+//!         Ok(unsafe { self.core.grow::<_, NoRounding>(self.memory, handle, old, new) }?)
+//!     }
+//!
+//!     unsafe fn shrink(&self, handle: u32, old: Layout, new: Layout) -> Result<(u32, usize), AllocError> {
+//!         Ok(unsafe { self.core.shrink::<_, NoRounding>(self.memory, handle, old, new) }?)
+//!     }
+//! }
+//!
+//! let store = LeakedBumpStore::new(64);
+//!
+//! let (first, _) = store.allocate(Layout::new::<u8>()).unwrap();
+//! let (second, _) = store.allocate(Layout::new::<u32>()).unwrap();
+//!
+//! assert_eq!((0, 4), (first, second));
+//! assert_eq!(8, store.core.used());
+//! assert_eq!(3, store.core.padding());
+//! ```
+
+use core::{
+    alloc::{AllocError, Layout},
+    array,
+    cell::Cell,
+    cmp, fmt, mem,
+    ptr::{self, Alignment, NonNull},
+};
+
+use crate::{interface::StoreLimits, store::RoundingPolicy};
+
+/// The bookkeeping of a bump allocator: its watermark, and the padding wasted so far.
+///
+/// A `BumpCore` does not own the block of memory it manages, which is instead described by a `BumpMemory` passed to
+/// each operation. Blocks of memory are identified by their offset within it, converted to and from handles `H`.
+///
+/// Memory is never reclaimed: deallocating is a no-op, and shrinking returns the block of memory as is, unless it must
+/// be relocated to satisfy a greater alignment. As a result, the memory past the watermark has never been handed out,
+/// and if it was zero-initialized to begin with, as declared by `new_zeroed`, it still is: `allocate_zeroed` and
+/// `grow_zeroed` then skip zeroing it.
+#[derive(Debug, Default)]
+pub struct BumpCore {
+    watermark: Cell<usize>,
+    padding: Cell<usize>,
+    //  Whether the memory past the watermark is zero-initialized.
+    zeroed: bool,
+}
+
+/// The reasons for which a `BumpCore` may fail to allocate.
+///
+/// Converts to `AllocError`, as required by the `Store` API, but allows diagnosing failures.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum BumpError {
+    /// The block of memory is too small, or insufficiently aligned, to accommodate the requested layout.
+    Exhausted,
+    /// The block of memory could accommodate the requested layout, but its offset is not representable by the handle.
+    HandleOverflow,
+}
+
+impl From<BumpError> for AllocError {
+    fn from(_: BumpError) -> Self {
+        AllocError
+    }
+}
+
+impl fmt::Display for BumpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            Self::Exhausted => f.write_str("memory exhausted"),
+            Self::HandleOverflow => f.write_str("offset not representable by the handle"),
+        }
+    }
+}
+
+/// An unsigned integer usable as an offset handle, whose greatest representable offset is known at compile-time.
+pub trait MaxOffset {
+    /// The greatest offset representable.
+    const MAX_OFFSET: usize;
+}
+
+impl MaxOffset for u8 {
+    const MAX_OFFSET: usize = u8::MAX as usize;
+}
+
+impl MaxOffset for u16 {
+    const MAX_OFFSET: usize = u16::MAX as usize;
+}
+
+impl MaxOffset for u32 {
+    const MAX_OFFSET: usize = if u32::BITS < usize::BITS {
+        u32::MAX as usize
+    } else {
+        usize::MAX
+    };
+}
+
+impl MaxOffset for u64 {
+    const MAX_OFFSET: usize = if u64::BITS < usize::BITS {
+        u64::MAX as usize
+    } else {
+        usize::MAX
+    };
+}
+
+impl MaxOffset for usize {
+    const MAX_OFFSET: usize = usize::MAX;
+}
+
+/// An unsigned integer usable as an offset handle, convertible from `usize` in const contexts.
+///
+/// `TryFrom<usize>` is not marked `#[const_trait]`, hence this trait is required for creating handles in const
+/// contexts, such as by `InlineBumpStore::new`. It is only required by such const constructors: custom handle types
+/// convertible to and from `usize` may be used with the bump stores without implementing it.
+#[const_trait]
+pub trait HandleFromUsize: Sized {
+    /// Converts `offset` into a handle, if representable.
+    fn from_usize(offset: usize) -> Option<Self>;
+}
+
+impl const HandleFromUsize for u8 {
+    fn from_usize(offset: usize) -> Option<Self> {
+        let handle = offset as Self;
+
+        if handle as usize == offset {
+            Some(handle)
+        } else {
+            None
+        }
+    }
+}
+
+impl const HandleFromUsize for u16 {
+    fn from_usize(offset: usize) -> Option<Self> {
+        let handle = offset as Self;
+
+        if handle as usize == offset {
+            Some(handle)
+        } else {
+            None
+        }
+    }
+}
+
+impl const HandleFromUsize for u32 {
+    fn from_usize(offset: usize) -> Option<Self> {
+        let handle = offset as Self;
+
+        if handle as usize == offset {
+            Some(handle)
+        } else {
+            None
+        }
+    }
+}
+
+impl const HandleFromUsize for u64 {
+    fn from_usize(offset: usize) -> Option<Self> {
+        let handle = offset as Self;
+
+        if handle as usize == offset {
+            Some(handle)
+        } else {
+            None
+        }
+    }
+}
+
+impl const HandleFromUsize for usize {
+    fn from_usize(offset: usize) -> Option<Self> {
+        Some(offset)
+    }
+}
+
+/// Asserts that any offset within a block of memory sized as per `T` is representable by `H`.
+///
+/// When evaluated in a constant context, a misconfigured store is diagnosed at compile-time:
+///
+/// ```
+/// use storage::store::assert_handle_fits;
+///
+/// const _: () = assert_handle_fits::<u8, [u8; 255]>();
+/// ```
+///
+/// ```compile_fail
+/// use storage::store::assert_handle_fits;
+///
+/// const _: () = assert_handle_fits::<u8, [u8; 256]>();
+/// ```
+pub const fn assert_handle_fits<H, T>()
+where
+    H: MaxOffset,
+{
+    assert!(
+        mem::size_of::<T>() <= H::MAX_OFFSET,
+        "the size of the block of memory is not representable by the handle"
+    );
+}
+
+/// The block of memory managed by a `BumpCore`.
+#[derive(Clone, Copy, Debug)]
+pub struct BumpMemory {
+    memory: NonNull<[u8]>,
+    //  The alignment of the block of memory, if it may move, in which case no allocation may be aligned beyond it.
+    alignment: Option<Alignment>,
+}
+
+impl BumpMemory {
+    /// Describes a block of memory which does not move for as long as it is used, for example because it is borrowed.
+    ///
+    /// Allocations may be aligned beyond the alignment of the block of memory, as the address is aligned, rather than
+    /// the offset.
+    pub const fn pinned(memory: NonNull<[u8]>) -> Self {
+        Self {
+            memory,
+            alignment: None,
+        }
+    }
+
+    /// Describes a block of memory aligned as per `alignment`, which may move between operations, for example because
+    /// it is inline.
+    ///
+    /// Allocations may not be aligned beyond `alignment`, as moving the block of memory would break their alignment.
+    pub const fn movable(memory: NonNull<[u8]>, alignment: Alignment) -> Self {
+        Self {
+            memory,
+            alignment: Some(alignment),
+        }
+    }
+
+    /// Returns the size of the block of memory.
+    pub const fn size(&self) -> usize {
+        self.memory.len()
+    }
+
+    /// Returns the limits of the layouts which may be allocated within the block of memory.
+    ///
+    /// The memory of deallocated blocks is never reused, as per `BumpCore`.
+    pub fn limits(&self) -> StoreLimits {
+        let max_align = match self.alignment {
+            Some(alignment) => alignment,
+            None => {
+                //  All addresses within `start..end` share the bits above the highest bit in which `start` and
+                //  `end - 1` differ, hence the most aligned address is aligned on this bit. An empty block of memory
+                //  only has `start`.
+                let start = self.memory.as_mut_ptr().addr();
+                let last = start + self.size().saturating_sub(1);
+
+                let shift = match start ^ last {
+                    0 => start.trailing_zeros(),
+                    difference => usize::BITS - 1 - difference.leading_zeros(),
+                };
+
+                //  Safety:
+                //  -   `1 << shift` is a power of 2, as `shift < usize::BITS`.
+                unsafe { Alignment::new_unchecked(1 << shift) }
+            }
+        };
+
+        StoreLimits {
+            max_align,
+            max_size: self.size(),
+            supports_dealloc_reuse: false,
+        }
+    }
+
+    /// Returns a dangling handle, aligned as per `alignment`.
+    pub fn dangling<H>(&self, alignment: Alignment) -> Result<H, BumpError>
+    where
+        H: TryFrom<usize>,
+    {
+        let offset = self.align_offset(0, alignment.as_usize()).ok_or(BumpError::Exhausted)?;
+
+        if offset > self.size() {
+            return Err(BumpError::Exhausted);
+        }
+
+        BumpCore::from_offset(offset)
+    }
+
+    /// Resolves `handle` into a pointer within the block of memory.
+    ///
+    /// #   Safety
+    ///
+    /// -   `handle` must have been allocated, or created dangling, with this block of memory.
+    #[inline(always)]
+    pub unsafe fn resolve<H>(&self, handle: H) -> NonNull<u8>
+    where
+        H: TryInto<usize>,
+    {
+        let offset = BumpCore::into_offset(handle);
+
+        debug_assert!(offset <= self.size());
+
+        //  Safety:
+        //  -   `offset` is within bounds of `self.memory`, as `handle` was allocated by `self` as per pre-conditions.
+        let pointer = unsafe { self.memory.as_mut_ptr().add(offset) };
+
+        //  Safety:
+        //  -   `pointer` is non null as `self.memory` is non null.
+        unsafe { NonNull::new_unchecked(pointer) }
+    }
+}
+
+impl BumpCore {
+    /// Creates a new instance, with nothing allocated yet.
+    pub const fn new() -> Self {
+        let watermark = Cell::new(0);
+        let padding = Cell::new(0);
+        let zeroed = false;
+
+        Self {
+            watermark,
+            padding,
+            zeroed,
+        }
+    }
+
+    /// Creates a new instance, with nothing allocated yet, managing a zero-initialized block of memory.
+    ///
+    /// #   Safety
+    ///
+    /// -   The block of memory passed to each operation must be zero-initialized, past the watermark.
+    pub const unsafe fn new_zeroed() -> Self {
+        let watermark = Cell::new(0);
+        let padding = Cell::new(0);
+        let zeroed = true;
+
+        Self {
+            watermark,
+            padding,
+            zeroed,
+        }
+    }
+
+    /// Creates a new instance, resuming from `used` bytes consumed, of which `padding` were wasted on alignment.
+    ///
+    /// This allows persisting the bookkeeping elsewhere, for example within the managed block of memory itself, by
+    /// saving `used` and `padding` after each operation.
+    ///
+    /// #   Safety
+    ///
+    /// -   No block of memory past `used` may still be in use.
+    pub const unsafe fn from_raw_parts(used: usize, padding: usize) -> Self {
+        let watermark = Cell::new(used);
+        let padding = Cell::new(padding);
+        let zeroed = false;
+
+        Self {
+            watermark,
+            padding,
+            zeroed,
+        }
+    }
+
+    /// Returns the number of bytes consumed so far, including alignment padding and rounding.
+    pub fn used(&self) -> usize {
+        self.watermark.get()
+    }
+
+    /// Returns the number of bytes wasted on alignment padding so far.
+    pub fn padding(&self) -> usize {
+        self.padding.get()
+    }
+
+    /// Returns whether the memory past the watermark is known to be zero-initialized.
+    pub fn is_zeroed(&self) -> bool {
+        self.zeroed
+    }
+
+    /// Converts `offset` into a handle, if representable.
+    #[inline(always)]
+    pub fn from_offset<H>(offset: usize) -> Result<H, BumpError>
+    where
+        H: TryFrom<usize>,
+    {
+        offset.try_into().map_err(|_| BumpError::HandleOverflow)
+    }
+
+    /// Converts `handle` back into an offset, if representable.
+    #[inline(always)]
+    pub fn try_into_offset<H>(handle: H) -> Option<usize>
+    where
+        H: TryInto<usize>,
+    {
+        handle.try_into().ok()
+    }
+
+    /// Converts `handle` back into an offset.
+    ///
+    /// The conversion is infallible for the usual handles, such as `u8` to `usize`, in which case the check is
+    /// optimized out.
+    ///
+    /// #   Panics
+    ///
+    /// If `handle` cannot be converted into an offset, which a handle created by `from_offset` always can.
+    #[inline(always)]
+    pub fn into_offset<H>(handle: H) -> usize
+    where
+        H: TryInto<usize>,
+    {
+        let Some(offset) = Self::try_into_offset(handle) else {
+            panic!("Handle cannot be converted into an offset")
+        };
+
+        offset
+    }
+
+    /// Advances `handle` by `bytes`, as per `StoreOffsetHandle::offset`.
+    ///
+    /// #   Panics
+    ///
+    /// If `handle` cannot be converted into an offset, or if the resulting offset cannot be represented as a handle.
+    #[inline(always)]
+    pub fn advance<H>(handle: H, bytes: usize) -> H
+    where
+        H: TryFrom<usize> + TryInto<usize>,
+    {
+        let offset = Self::try_into_offset(handle).and_then(|offset| offset.checked_add(bytes));
+
+        let Some(Ok(handle)) = offset.map(Self::from_offset) else {
+            panic!("Advanced handle cannot be represented")
+        };
+
+        handle
+    }
+
+    /// Allocates a block of memory fitting `layout`, rounded as per `R`, within `memory`.
+    ///
+    /// Returns the handle and actual size of the block of memory.
+    pub fn allocate<H, R>(&self, memory: BumpMemory, layout: Layout) -> Result<(H, usize), BumpError>
+    where
+        H: TryFrom<usize>,
+        R: RoundingPolicy,
+    {
+        let layout = Self::round::<R>(layout)?;
+
+        let result = self.bump(memory, layout)?;
+
+        Ok((result, layout.size()))
+    }
+
+    /// Allocates a zero-initialized block of memory fitting `layout`, rounded as per `R`, within `memory`.
+    ///
+    /// Returns the handle and actual size of the block of memory.
+    pub fn allocate_zeroed<H, R>(&self, memory: BumpMemory, layout: Layout) -> Result<(H, usize), BumpError>
+    where
+        H: Copy + TryFrom<usize> + TryInto<usize>,
+        R: RoundingPolicy,
+    {
+        let (handle, size) = self.allocate::<H, R>(memory, layout)?;
+
+        //  The block of memory was carved out past the watermark.
+        if !self.zeroed {
+            //  Safety:
+            //  -   `handle` was just allocated within `memory`.
+            let pointer = unsafe { memory.resolve(handle) };
+
+            //  Safety:
+            //  -   `pointer` is valid for `size` bytes, since it was just allocated.
+            //  -   Access to the next `size` bytes is exclusive, since they were just allocated.
+            unsafe { ptr::write_bytes(pointer.as_ptr(), 0, size) };
+        }
+
+        Ok((handle, size))
+    }
+
+    /// Allocates a batch of blocks of memory fitting `layouts`, rounded as per `R`, within `memory`, by order of
+    /// decreasing alignment so as to minimize padding.
+    ///
+    /// Returns the handles and actual sizes of the blocks of memory, in the order of `layouts`. On failure, the blocks
+    /// of memory allocated so far are not reclaimed.
+    pub fn allocate_batch<H, R, const N: usize>(
+        &self,
+        memory: BumpMemory,
+        layouts: [Layout; N],
+    ) -> Result<[(H, usize); N], BumpError>
+    where
+        H: Copy + TryFrom<usize>,
+        R: RoundingPolicy,
+    {
+        let mut results = [None; N];
+
+        for index in order_by_alignment(&layouts) {
+            results[index] = Some(self.allocate::<H, R>(memory, layouts[index])?);
+        }
+
+        Ok(results.map(|result| {
+            debug_assert!(result.is_some());
+
+            //  Safety:
+            //  -   `order_by_alignment` returns a permutation of the indices of `layouts`, hence all were allocated.
+            unsafe { result.unwrap_unchecked() }
+        }))
+    }
+
+    /// Grows the block of memory associated to `handle`, in place if it is the last allocated, and by relocation
+    /// otherwise.
+    ///
+    /// Returns the handle and actual size of the grown block of memory.
+    ///
+    /// #   Safety
+    ///
+    /// -   `handle` must have been allocated by `self`, within `memory`.
+    /// -   `old_layout` must fit the block of memory associated to `handle`.
+    /// -   `new_layout.size()` must be greater than or equal to `old_layout.size()`.
+    #[inline(always)]
+    pub unsafe fn grow<H, R>(
+        &self,
+        memory: BumpMemory,
+        handle: H,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(H, usize), BumpError>
+    where
+        H: Copy + TryFrom<usize> + TryInto<usize>,
+        R: RoundingPolicy,
+    {
+        //  Safety:
+        //  -   As per pre-conditions.
+        let (handle, size, _) = unsafe { self.grow_reporting::<H, R>(memory, handle, old_layout, new_layout)? };
+
+        Ok((handle, size))
+    }
+
+    /// Grows the block of memory associated to `handle`, as per `grow`, reporting whether it was relocated.
+    ///
+    /// Returns the handle and actual size of the grown block of memory, and whether it was relocated.
+    ///
+    /// #   Safety
+    ///
+    /// -   As per `grow`.
+    pub unsafe fn grow_reporting<H, R>(
+        &self,
+        memory: BumpMemory,
+        handle: H,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(H, usize, bool), BumpError>
+    where
+        H: Copy + TryFrom<usize> + TryInto<usize>,
+        R: RoundingPolicy,
+    {
+        debug_assert!(
+            new_layout.size() >= old_layout.size(),
+            "{new_layout:?} must have a greater size than {old_layout:?}"
+        );
+
+        let (old_layout, new_layout) = (Self::round::<R>(old_layout)?, Self::round::<R>(new_layout)?);
+
+        //  As an optimization, if `handle` points to the last allocation, growth may actually occur _in place_.
+        {
+            let offset = Self::into_offset(handle);
+
+            //  The block of memory may already be large enough, once rounded, wherever it is.
+            if new_layout.size() <= old_layout.size() && memory.align_offset(offset, new_layout.align()) == Some(offset)
+            {
+                return Ok((handle, old_layout.size(), false));
+            }
+
+            let watermark = self.watermark.get();
+
+            if offset.checked_add(old_layout.size()) == Some(watermark)
+                && memory.align_offset(offset, new_layout.align()) == Some(offset)
+                && offset
+                    .checked_add(new_layout.size())
+                    .is_some_and(|end| end <= memory.size())
+            {
+                let new_watermark = watermark - old_layout.size() + new_layout.size();
+                self.watermark.set(new_watermark);
+
+                return Ok((handle, new_layout.size(), false));
+            }
+        }
+
+        //  Safety:
+        //  -   `handle` is valid, and `old_layout` fits, as per pre-conditions.
+        //  -   `old_layout.size()` is smaller than or equal to `new_layout.size()`, as per pre-conditions.
+        let (handle, size) = unsafe { self.relocate(memory, handle, old_layout.size(), new_layout)? };
+
+        Ok((handle, size, true))
+    }
+
+    /// Grows the block of memory associated to `handle`, as per `grow`, zeroing the extra memory.
+    ///
+    /// Returns the handle and actual size of the grown block of memory.
+    ///
+    /// #   Safety
+    ///
+    /// -   As per `grow`.
+    pub unsafe fn grow_zeroed<H, R>(
+        &self,
+        memory: BumpMemory,
+        handle: H,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(H, usize), BumpError>
+    where
+        H: Copy + TryFrom<usize> + TryInto<usize>,
+        R: RoundingPolicy,
+    {
+        let old_size = Self::round::<R>(old_layout)?.size();
+
+        //  Safety:
+        //  -   As per pre-conditions.
+        let (handle, new_size) = unsafe { self.grow::<H, R>(memory, handle, old_layout, new_layout)? };
+
+        //  Whether grown in place or relocated, the block of memory consists of:
+        //
+        //  -   `old_layout.size()` bytes of data.
+        //  -   Up to `old_size`, the bytes of the old block of memory past the data, or a copy thereof, which may have
+        //      been written prior to shrinking.
+        //  -   Up to `new_size`, the bytes past the old watermark, which have never been handed out.
+        let end = if self.zeroed { old_size } else { new_size };
+
+        if end > old_layout.size() {
+            //  Safety:
+            //  -   `handle` is valid, since it was just returned by `grow`.
+            let pointer = unsafe { memory.resolve(handle) };
+
+            //  Safety:
+            //  -   `pointer` is valid for `new_size` bytes, and `end <= new_size`.
+            //  -   Access to the block of memory is exclusive, as per pre-conditions.
+            unsafe { ptr::write_bytes(pointer.as_ptr().add(old_layout.size()), 0, end - old_layout.size()) };
+        }
+
+        Ok((handle, new_size))
+    }
+
+    /// Shrinks the block of memory associated to `handle`, which is returned as is if aligned as per `new_layout`, and
+    /// relocated otherwise.
+    ///
+    /// Returns the handle and actual size of the shrunk block of memory, that is the size of `old_layout` once rounded
+    /// unless relocated.
+    ///
+    /// #   Safety
+    ///
+    /// -   `handle` must have been allocated by `self`, within `memory`.
+    /// -   `old_layout` must fit the block of memory associated to `handle`.
+    /// -   `new_layout.size()` must be smaller than or equal to `old_layout.size()`.
+    #[inline(always)]
+    pub unsafe fn shrink<H, R>(
+        &self,
+        memory: BumpMemory,
+        handle: H,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(H, usize), BumpError>
+    where
+        H: Copy + TryFrom<usize> + TryInto<usize>,
+        R: RoundingPolicy,
+    {
+        //  Safety:
+        //  -   As per pre-conditions.
+        let (handle, size, _) = unsafe { self.shrink_reporting::<H, R>(memory, handle, old_layout, new_layout)? };
+
+        Ok((handle, size))
+    }
+
+    /// Shrinks the block of memory associated to `handle`, as per `shrink`, reporting whether it was relocated.
+    ///
+    /// Returns the handle and actual size of the shrunk block of memory, and whether it was relocated.
+    ///
+    /// #   Safety
+    ///
+    /// -   As per `shrink`.
+    pub unsafe fn shrink_reporting<H, R>(
+        &self,
+        memory: BumpMemory,
+        handle: H,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(H, usize, bool), BumpError>
+    where
+        H: Copy + TryFrom<usize> + TryInto<usize>,
+        R: RoundingPolicy,
+    {
+        debug_assert!(
+            new_layout.size() <= old_layout.size(),
+            "{new_layout:?} must have a smaller size than {old_layout:?}"
+        );
+
+        let (old_layout, new_layout) = (Self::round::<R>(old_layout)?, Self::round::<R>(new_layout)?);
+
+        let offset = Self::into_offset(handle);
+
+        if memory.align_offset(offset, new_layout.align()) == Some(offset) {
+            return Ok((handle, old_layout.size(), false));
+        }
+
+        //  Safety:
+        //  -   `handle` is valid, and `old_layout` fits, as per pre-conditions.
+        //  -   `new_layout.size()` is smaller than or equal to `old_layout.size()`, as per pre-conditions.
+        let (handle, size) = unsafe { self.relocate(memory, handle, new_layout.size(), new_layout)? };
+
+        Ok((handle, size, true))
+    }
+
+    /// Returns whether `handle` is valid, as per `StoreValidate::is_valid`.
+    pub fn is_valid<H>(&self, handle: H) -> Option<bool>
+    where
+        H: TryInto<usize>,
+    {
+        let Some(offset) = Self::try_into_offset(handle) else {
+            return Some(false);
+        };
+
+        //  Memory is never reclaimed, hence any handle below the watermark may still be valid, or may be dangling.
+        if offset > self.watermark.get() {
+            return Some(false);
+        }
+
+        None
+    }
+}
+
+//
+//  Debugging
+//
+
+/// A human-readable dump of the memory used by a bump store, for debugging purposes.
+///
+/// Its `Debug` representation starts with the watermark, followed by the used memory as hexadecimal, 16 bytes per line,
+/// each line prefixed by its offset and followed by its printable ASCII characters:
+///
+/// ```text
+/// BumpDump { used: 23, padding: 3, size: 64 }
+/// 0000: 2a 00 00 00 48 65 6c 6c 6f 2c 20 57 6f 72 6c 64  |*...Hello, World|
+/// 0010: 2c 20 61 67 61 69 6e                             |, again|
+/// ```
+///
+/// Bump stores do not track their allocations, hence the dump does not delimit them; see `TrackingStore` to enumerate
+/// them.
+///
+/// Only available in debug builds, that is with `debug_assertions` enabled.
+#[cfg(debug_assertions)]
+#[derive(Clone, Copy)]
+pub struct BumpDump<'a> {
+    bytes: &'a [u8],
+    padding: usize,
+    size: usize,
+}
+
+#[cfg(debug_assertions)]
+impl BumpCore {
+    /// Returns a dump of the memory used within `memory`, that is up to the watermark.
+    ///
+    /// #   Safety
+    ///
+    /// -   `memory` must be valid for reads for the lifetime `'a`, and not written to for its duration.
+    /// -   The used memory must be initialized. Memory zero-initialized upfront remains initialized as long as the
+    ///     values written to it have no padding bytes.
+    pub unsafe fn dump<'a>(&self, memory: BumpMemory) -> BumpDump<'a> {
+        let used = self.used();
+
+        debug_assert!(used <= memory.size());
+
+        //  Safety:
+        //  -   `memory` is valid for reads of `used` bytes, as `used <= memory.size()`, for `'a`, as per
+        //      pre-conditions.
+        //  -   The `used` bytes are initialized, as per pre-conditions.
+        let bytes = unsafe { core::slice::from_raw_parts(memory.memory.as_mut_ptr(), used) };
+
+        BumpDump {
+            bytes,
+            padding: self.padding(),
+            size: memory.size(),
+        }
+    }
+}
+
+#[cfg(debug_assertions)]
+impl BumpDump<'_> {
+    /// Returns the used memory.
+    pub fn bytes(&self) -> &[u8] {
+        self.bytes
+    }
+}
+
+#[cfg(debug_assertions)]
+impl fmt::Debug for BumpDump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        const WIDTH: usize = 16;
+
+        write!(
+            f,
+            "BumpDump {{ used: {}, padding: {}, size: {} }}",
+            self.bytes.len(),
+            self.padding,
+            self.size
+        )?;
+
+        for (index, line) in self.bytes.chunks(WIDTH).enumerate() {
+            write!(f, "\n{:04x}:", index * WIDTH)?;
+
+            for byte in line {
+                write!(f, " {byte:02x}")?;
+            }
+
+            //  Align the ASCII column of the last line with the preceding ones.
+            for _ in line.len()..WIDTH {
+                f.write_str("   ")?;
+            }
+
+            f.write_str("  |")?;
+
+            for &byte in line {
+                let c = if matches!(byte, b' '..=b'~') { byte as char } else { '.' };
+
+                write!(f, "{c}")?;
+            }
+
+            f.write_str("|")?;
+        }
+
+        Ok(())
+    }
+}
+
+//
+//  Implementation
+//
+
+impl BumpMemory {
+    //  Returns the smallest offset, greater than or equal to `offset`, which is aligned as per `align`.
+    #[inline(always)]
+    fn align_offset(&self, offset: usize, align: usize) -> Option<usize> {
+        debug_assert!(align.is_power_of_two());
+
+        //  Since `align` is always a power of 2, aligning to the next multiple of `align` can be done with this one
+        //  simple trick.
+        let alignment_mask = align - 1;
+
+        let Some(alignment) = self.alignment else {
+            //  The block of memory cannot move, hence aligning the address, rather than the offset, is sound, and
+            //  allows alignments greater than that of the block itself.
+            let base = self.memory.as_mut_ptr().addr();
+            let address = base.checked_add(offset)?;
+            let aligned = address.checked_add(alignment_mask)? & !alignment_mask;
+
+            return Some(aligned - base);
+        };
+
+        if align > alignment.as_usize() {
+            //  Even if the offset was aligned for the current address of the block of memory, moving it would risk
+            //  breaking this alignment.
+            return None;
+        }
+
+        Some(offset.checked_add(alignment_mask)? & !alignment_mask)
+    }
+}
+
+impl BumpCore {
+    #[inline(always)]
+    fn round<R>(layout: Layout) -> Result<Layout, BumpError>
+    where
+        R: RoundingPolicy,
+    {
+        R::round(layout).map_err(|_| BumpError::Exhausted)
+    }
+
+    //  Returns the handle of the newly allocated memory block, after bumping the watermark and accounting for padding.
+    fn bump<H>(&self, memory: BumpMemory, layout: Layout) -> Result<H, BumpError>
+    where
+        H: TryFrom<usize>,
+    {
+        let watermark = self.watermark.get();
+
+        let aligned = memory
+            .align_offset(watermark, layout.align())
+            .ok_or(BumpError::Exhausted)?;
+
+        let new_watermark = aligned.checked_add(layout.size()).ok_or(BumpError::Exhausted)?;
+
+        if new_watermark > memory.size() {
+            return Err(BumpError::Exhausted);
+        }
+
+        //  The block of memory fits, yet its offset may not be representable.
+        let result = Self::from_offset(aligned)?;
+
+        self.watermark.set(new_watermark);
+        self.padding.set(self.padding.get() + (aligned - watermark));
+
+        Ok(result)
+    }
+
+    //  Slow part of `grow` and `shrink`: allocates a new block of memory, then copies the first `copied` bytes over.
+    //
+    //  #   Safety
+    //
+    //  -   `handle` must have been allocated by `self`, within `memory`, and must fit a layout already rounded.
+    //  -   `copied` must be smaller than or equal to both the size of that layout and `new_layout.size()`.
+    #[inline(never)]
+    unsafe fn relocate<H>(
+        &self,
+        memory: BumpMemory,
+        handle: H,
+        copied: usize,
+        new_layout: Layout,
+    ) -> Result<(H, usize), BumpError>
+    where
+        H: Copy + TryFrom<usize> + TryInto<usize>,
+    {
+        let result = self.bump(memory, new_layout)?;
+
+        //  Safety:
+        //  -   `handle` is valid, as per pre-conditions.
+        //  -   `result` is valid, since newly allocated.
+        let (new, old) = unsafe { (memory.resolve(result), memory.resolve(handle)) };
+
+        //  Safety:
+        //  -   `old` is valid for `copied` bytes, as per pre-conditions.
+        //  -   `new` is valid for `copied` bytes, since it is valid for `new_layout.size()` bytes and as per
+        //      pre-conditions `new_layout.size() >= copied`.
+        //  -   `old` and `new` are at least 1-byte aligned.
+        //  -   `old` and `new` point to non-overlapping areas, since `old` points to a memory area prior to the
+        //      watermark and `new` points to a memory area post the watermark (as the beginning of this function),
+        //      since the block of memory associated to `handle` was allocated by `self`, as per pre-conditions.
+        unsafe { ptr::copy_nonoverlapping(old.as_ptr(), new.as_ptr(), copied) };
+
+        Ok((result, new_layout.size()))
+    }
+}
+
+//  Returns the indices of `layouts`, by order of decreasing alignment.
+//
+//  Allocating in this order from a bump allocator avoids any padding between the allocations, as long as the size of
+//  each layout is a multiple of its alignment, as is the case for all Rust types.
+fn order_by_alignment<const N: usize>(layouts: &[Layout; N]) -> [usize; N] {
+    let mut indices: [usize; N] = array::from_fn(|index| index);
+
+    indices.sort_unstable_by_key(|index| cmp::Reverse(layouts[*index].align()));
+
+    indices
+}
+
+#[cfg(test)]
+mod tests {
+    use core::mem::MaybeUninit;
+    use std::panic;
+
+    use crate::store::{NoRounding, RoundToPowerOfTwo};
+
+    use super::*;
+
+    #[test]
+    fn handle_from_usize() {
+        const MAX: Option<u8> = u8::from_usize(255);
+
+        assert_eq!(Some(255), MAX);
+        assert_eq!(None, u8::from_usize(256));
+        assert_eq!(Some(256), u16::from_usize(256));
+        assert_eq!(Some(usize::MAX), usize::from_usize(usize::MAX));
+    }
+
+    #[test]
+    fn movable_alignment() {
+        let mut block = MaybeUninit::<[u32; 4]>::uninit();
+        let memory = BumpMemory::movable(NonNull::from(&mut block).cast::<[u8; 16]>(), Alignment::of::<u32>());
+
+        let core = BumpCore::new();
+
+        core.allocate::<u8, NoRounding>(memory, Layout::new::<u8>()).unwrap();
+
+        let (handle, _) = core.allocate::<u8, NoRounding>(memory, Layout::new::<u32>()).unwrap();
+
+        assert_eq!(4, handle);
+        assert_eq!(3, core.padding());
+
+        assert_eq!(
+            Err(BumpError::Exhausted),
+            core.allocate::<u8, NoRounding>(memory, Layout::new::<u64>())
+        );
+        assert_eq!(
+            Err::<u8, _>(BumpError::Exhausted),
+            memory.dangling(Alignment::of::<u64>())
+        );
+    }
+
+    #[test]
+    fn grow_in_place() {
+        let mut block = MaybeUninit::<[u8; 16]>::uninit();
+        let memory = BumpMemory::movable(NonNull::from(&mut block).cast::<[u8; 16]>(), Alignment::of::<u8>());
+
+        let core = BumpCore::new();
+
+        let (handle, size) = core
+            .allocate::<u8, RoundToPowerOfTwo>(memory, Layout::new::<[u8; 3]>())
+            .unwrap();
+
+        assert_eq!((0, 4), (handle, size));
+
+        //  Safety:
+        //  -   `handle` was allocated by `core`, within `memory`, with a fitting layout.
+        let grown = unsafe {
+            core.grow::<u8, RoundToPowerOfTwo>(memory, handle, Layout::new::<[u8; 3]>(), Layout::new::<[u8; 5]>())
+        };
+
+        assert_eq!(Ok((0, 8)), grown);
+        assert_eq!(8, core.used());
+
+        assert_eq!(None, core.is_valid(8u8));
+        assert_eq!(Some(false), core.is_valid(9u8));
+    }
+
+    #[test]
+    fn limits_pinned() {
+        #[repr(align(16))]
+        struct Aligned([u8; 16]);
+
+        let mut block = MaybeUninit::<Aligned>::uninit();
+
+        //  Safety:
+        //  -   `1` is within the bounds of `block`.
+        let start = unsafe { NonNull::from(&mut block).cast::<u8>().add(1) };
+
+        //  Only `block + 4` is aligned on 4 bytes within `block + 1..block + 8`; `block + 8` is past the end.
+        let memory = BumpMemory::pinned(NonNull::slice_from_raw_parts(start, 7));
+
+        assert_eq!(4, memory.limits().max_align.as_usize());
+
+        let memory = BumpMemory::pinned(NonNull::slice_from_raw_parts(start, 8));
+
+        assert_eq!(8, memory.limits().max_align.as_usize());
+    }
+
+    #[test]
+    fn grow_reporting() {
+        let mut block = MaybeUninit::<[u8; 16]>::uninit();
+        let memory = BumpMemory::movable(NonNull::from(&mut block).cast::<[u8; 16]>(), Alignment::of::<u8>());
+
+        let core = BumpCore::new();
+
+        let (first, _) = core
+            .allocate::<u8, NoRounding>(memory, Layout::new::<[u8; 2]>())
+            .unwrap();
+
+        //  Safety:
+        //  -   `first` was allocated by `core`, within `memory`, with a fitting layout.
+        let grown = unsafe {
+            core.grow_reporting::<u8, NoRounding>(memory, first, Layout::new::<[u8; 2]>(), Layout::new::<[u8; 4]>())
+        };
+
+        assert_eq!(Ok((0, 4, false)), grown);
+
+        core.allocate::<u8, NoRounding>(memory, Layout::new::<u8>()).unwrap();
+
+        //  Safety:
+        //  -   `first` is still valid, as it was grown in place, with a fitting layout.
+        let grown = unsafe {
+            core.grow_reporting::<u8, NoRounding>(memory, first, Layout::new::<[u8; 4]>(), Layout::new::<[u8; 8]>())
+        };
+
+        assert_eq!(Ok((5, 8, true)), grown);
+    }
+
+    #[test]
+    fn grow_reporting_rounded() {
+        let mut block = MaybeUninit::<[u8; 16]>::uninit();
+        let memory = BumpMemory::movable(NonNull::from(&mut block).cast::<[u8; 16]>(), Alignment::of::<u8>());
+
+        let core = BumpCore::new();
+
+        let (first, _) = core
+            .allocate::<u8, RoundToPowerOfTwo>(memory, Layout::new::<[u8; 3]>())
+            .unwrap();
+
+        core.allocate::<u8, RoundToPowerOfTwo>(memory, Layout::new::<u8>())
+            .unwrap();
+
+        //  Safety:
+        //  -   `first` was allocated by `core`, within `memory`, with a fitting layout.
+        let grown = unsafe {
+            core.grow_reporting::<u8, RoundToPowerOfTwo>(
+                memory,
+                first,
+                Layout::new::<[u8; 3]>(),
+                Layout::new::<[u8; 4]>(),
+            )
+        };
+
+        //  `first` is not the last allocation, yet its rounded size already suffices.
+        assert_eq!(Ok((0, 4, false)), grown);
+    }
+
+    #[test]
+    fn resize_realigned() {
+        fn layout(size: usize, align: usize) -> Layout {
+            Layout::from_size_align(size, align).unwrap()
+        }
+
+        let mut block = MaybeUninit::<[u32; 4]>::uninit();
+        let memory = BumpMemory::movable(NonNull::from(&mut block).cast::<[u8; 16]>(), Alignment::of::<u32>());
+
+        let core = BumpCore::new();
+
+        core.allocate::<u8, NoRounding>(memory, layout(1, 1)).unwrap();
+
+        let (block, _) = core.allocate::<u8, NoRounding>(memory, layout(4, 1)).unwrap();
+
+        assert_eq!(1, block);
+
+        //  Safety:
+        //  -   `block` was allocated by `core`, within `memory`, with a fitting layout.
+        let shrunk = unsafe { core.shrink_reporting::<u8, NoRounding>(memory, block, layout(4, 1), layout(2, 1)) };
+
+        assert_eq!(Ok((1, 4, false)), shrunk);
+
+        //  Safety:
+        //  -   `block` is still valid, as it was shrunk in place, with a fitting layout.
+        let shrunk = unsafe { core.shrink_reporting::<u8, NoRounding>(memory, block, layout(2, 1), layout(2, 2)) };
+
+        assert_eq!(Ok((6, 2, true)), shrunk);
+
+        //  Safety:
+        //  -   The relocated block is valid, with a fitting layout, and the last allocated.
+        let grown = unsafe { core.grow_reporting::<u8, NoRounding>(memory, 6, layout(2, 2), layout(4, 2)) };
+
+        assert_eq!(Ok((6, 4, false)), grown);
+
+        //  Safety:
+        //  -   The block is still valid, as it was grown in place, with a fitting layout.
+        let grown = unsafe { core.grow_reporting::<u8, NoRounding>(memory, 6, layout(4, 2), layout(4, 4)) };
+
+        assert_eq!(Ok((12, 4, true)), grown);
+    }
+
+    #[test]
+    fn grow_zeroed_after_reuse() {
+        //  Safety:
+        //  -   The block of memory it is paired with is zero-initialized.
+        let zeroed = unsafe { BumpCore::new_zeroed() };
+
+        //  The first block of memory is filled with garbage, in lieu of being uninitialized.
+        for (mut block, core) in [([0xFFu8; 32], BumpCore::new()), ([0u8; 32], zeroed)] {
+            let memory = BumpMemory::movable(NonNull::from(&mut block), Alignment::of::<u8>());
+
+            let read = |handle: u8, size: usize| {
+                //  Safety:
+                //  -   `handle` was allocated by `core`, within `memory`, for at least `size` bytes.
+                let pointer = unsafe { memory.resolve(handle) };
+
+                //  Safety:
+                //  -   `pointer` is valid for `size` bytes, all of which are initialized.
+                unsafe { NonNull::slice_from_raw_parts(pointer, size).as_ref() }.to_vec()
+            };
+
+            let write = |handle: u8, size: usize| {
+                //  Safety:
+                //  -   `handle` was allocated by `core`, within `memory`, for at least `size` bytes.
+                unsafe { ptr::write_bytes(memory.resolve(handle).as_ptr(), 0xAA, size) };
+            };
+
+            let (first, size) = core
+                .allocate_zeroed::<u8, RoundToPowerOfTwo>(memory, Layout::new::<[u8; 3]>())
+                .unwrap();
+
+            assert_eq!(vec![0; 4], read(first, size));
+
+            //  Write the whole block of memory, including its rounded tail, which then holds stale data.
+            write(first, size);
+
+            //  Safety:
+            //  -   `first` was allocated by `core`, within `memory`, with a fitting layout.
+            let grown = unsafe {
+                core.grow_zeroed::<u8, RoundToPowerOfTwo>(
+                    memory,
+                    first,
+                    Layout::new::<[u8; 3]>(),
+                    Layout::new::<[u8; 5]>(),
+                )
+            };
+
+            assert_eq!(Ok((first, 8)), grown);
+            assert_eq!(vec![0xAA, 0xAA, 0xAA, 0, 0, 0, 0, 0], read(first, 8));
+
+            //  Relocate, so that the stale tail is copied.
+            write(first, 8);
+
+            let (second, _) = core
+                .allocate_zeroed::<u8, RoundToPowerOfTwo>(memory, Layout::new::<u8>())
+                .unwrap();
+
+            //  Safety:
+            //  -   `first` was allocated by `core`, within `memory`, with a fitting layout.
+            let (third, size) = unsafe {
+                core.grow_zeroed::<u8, RoundToPowerOfTwo>(
+                    memory,
+                    first,
+                    Layout::new::<[u8; 5]>(),
+                    Layout::new::<[u8; 9]>(),
+                )
+            }
+            .unwrap();
+
+            assert_eq!((9, 16), (third, size));
+            assert_eq!(vec![0], read(second, 1));
+            assert_eq!([&[0xAA; 5][..], &[0; 11]].concat(), read(third, size));
+        }
+    }
+
+    #[test]
+    fn narrow_handle() {
+        let mut block = MaybeUninit::<[u8; 512]>::uninit();
+        let memory = BumpMemory::movable(NonNull::from(&mut block).cast::<[u8; 512]>(), Alignment::of::<u8>());
+
+        let core = BumpCore::new();
+
+        core.allocate::<u8, NoRounding>(memory, Layout::new::<[u8; 256]>())
+            .unwrap();
+
+        //  The offset, 256, is not representable by `u8`: the watermark must remain untouched.
+        assert_eq!(
+            Err(BumpError::HandleOverflow),
+            core.allocate::<u8, NoRounding>(memory, Layout::new::<u8>())
+        );
+        assert_eq!(256, core.used());
+
+        //  Whereas a layout exceeding the block of memory exhausts it.
+        assert_eq!(
+            Err(BumpError::Exhausted),
+            core.allocate::<u16, NoRounding>(memory, Layout::new::<[u8; 257]>())
+        );
+    }
+
+    #[test]
+    fn unrepresentable_handle() {
+        let core = BumpCore::new();
+
+        //  A handle which cannot be converted into an offset was not created by `core`.
+        assert_eq!(None, BumpCore::try_into_offset(-1i8));
+        assert_eq!(Some(false), core.is_valid(-1i8));
+        assert_eq!(None, core.is_valid(0i8));
+    }
+
+    #[test]
+    fn advance() {
+        assert_eq!(7u8, BumpCore::advance(3u8, 4));
+        assert_eq!(255u8, BumpCore::advance(0u8, 255));
+
+        //  Neither an overflowing offset, nor an unrepresentable one, is ever silently wrapped around.
+        assert!(panic::catch_unwind(|| BumpCore::advance(usize::MAX, 1)).is_err());
+        assert!(panic::catch_unwind(|| BumpCore::advance(255u8, 1)).is_err());
+        assert!(panic::catch_unwind(|| BumpCore::advance(-1i8, 1)).is_err());
+    }
+
+    #[test]
+    fn handle_fits() {
+        const _: () = assert_handle_fits::<u8, [u8; 255]>();
+        const _: () = assert_handle_fits::<u16, [u8; 256]>();
+        const _: () = assert_handle_fits::<usize, [u64; 1024]>();
+
+        assert_eq!(255, u8::MAX_OFFSET);
+        assert_eq!(usize::MAX, u64::MAX_OFFSET);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    fn dump() {
+        let mut block = [0u32; 16];
+        let memory = BumpMemory::movable(
+            NonNull::slice_from_raw_parts(NonNull::from(&mut block).cast(), mem::size_of_val(&block)),
+            Alignment::of::<u32>(),
+        );
+
+        let core = BumpCore::new();
+
+        let (first, _) = core.allocate::<usize, NoRounding>(memory, Layout::new::<u8>()).unwrap();
+        let (second, _) = core
+            .allocate::<usize, NoRounding>(memory, Layout::new::<[u8; 19]>().align_to(4).unwrap())
+            .unwrap();
+
+        //  Safety:
+        //  -   `first` and `second` were allocated within `memory`, for 1 and 19 bytes respectively.
+        unsafe {
+            memory.resolve(first).write(b'*');
+            memory.resolve(second).cast::<[u8; 19]>().write(*b"Hello, World, again");
+        }
+
+        //  Safety:
+        //  -   `block` is zero-initialized, and only bytes were written to it.
+        //  -   `block` is not written to while the dump is alive.
+        let dump = unsafe { core.dump(memory) };
+
+        assert_eq!(23, dump.bytes().len());
+        assert_eq!(
+            concat!(
+                "BumpDump { used: 23, padding: 3, size: 64 }\n",
+                "0000: 2a 00 00 00 48 65 6c 6c 6f 2c 20 57 6f 72 6c 64  |*...Hello, World|\n",
+                "0010: 2c 20 61 67 61 69 6e                             |, again|",
+            ),
+            format!("{dump:?}")
+        );
+
+        let empty = BumpCore::new();
+
+        //  Safety:
+        //  -   Nothing is used.
+        let dump = unsafe { empty.dump(memory) };
+
+        assert_eq!("BumpDump { used: 0, padding: 0, size: 64 }", format!("{dump:?}"));
+    }
+} // mod tests
@@ -0,0 +1,270 @@
+//! A Store checking the liveness of its handles.
+//!
+//! An adapter over another store, whose handles carry a shared liveness flag, so that a stale handle -- a clone of a
+//! handle which has since been deallocated, grown, or shrunk -- is caught on use rather than silently resolving to
+//! freed memory.
+//!
+//! As its handles are not `Copy`, this store only implements `StoreOwned`, and is meant to be used with `OwnedHandle`,
+//! or any code written against `StoreOwned`. It is mostly meant as a debugging aid.
+
+use core::{
+    alloc::{AllocError, Layout},
+    cell::Cell,
+    fmt,
+    ptr::{Alignment, NonNull},
+};
+
+use std_alloc::rc::Rc;
+
+use crate::interface::{Growth, Store, StoreOwned};
+
+/// A store checking the liveness of the handles of its underlying store.
+pub struct CheckedStore<S>(S);
+
+impl<S> CheckedStore<S> {
+    /// Creates a new instance, wrapping `store`.
+    pub const fn new(store: S) -> Self {
+        Self(store)
+    }
+
+    /// Returns a reference to the underlying store.
+    pub const fn get(&self) -> &S {
+        &self.0
+    }
+
+    /// Returns whether `handle` is still live, that is neither it nor any of its clones has been deallocated, grown,
+    /// or shrunk since.
+    ///
+    /// Dangling handles are always live.
+    pub fn is_live<H>(handle: &CheckedHandle<H>) -> bool {
+        handle.live.as_ref().is_none_or(|live| live.get())
+    }
+
+    #[track_caller]
+    fn check<H>(handle: &CheckedHandle<H>) {
+        assert!(Self::is_live(handle), "CheckedStore: use of a stale handle");
+    }
+
+    #[track_caller]
+    fn retire<H>(handle: &CheckedHandle<H>) {
+        Self::check(handle);
+
+        if let Some(live) = &handle.live {
+            live.set(false);
+        }
+    }
+}
+
+impl<S> Default for CheckedStore<S>
+where
+    S: Default,
+{
+    fn default() -> Self {
+        Self::new(S::default())
+    }
+}
+
+impl<S> fmt::Debug for CheckedStore<S>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CheckedStore").field(&self.0).finish()
+    }
+}
+
+//  Safety:
+//  -   Each method delegates to the underlying store, only ever adding checks.
+//  -   A handle and all its clones share the same liveness flag, and are thus invalidated at the same time.
+unsafe impl<S> StoreOwned for CheckedStore<S>
+where
+    S: Store,
+{
+    type Handle = CheckedHandle<S::Handle>;
+
+    const GROWTH: Growth = <S as Store>::GROWTH;
+
+    fn dangling(&self, alignment: Alignment) -> Result<Self::Handle, AllocError> {
+        let handle = self.0.dangling(alignment)?;
+
+        Ok(CheckedHandle { handle, live: None })
+    }
+
+    #[track_caller]
+    unsafe fn resolve(&self, handle: &Self::Handle) -> NonNull<u8> {
+        Self::check(handle);
+
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.0.resolve(handle.handle) }
+    }
+
+    fn allocate(&self, layout: Layout) -> Result<(Self::Handle, usize), AllocError> {
+        let (handle, size) = self.0.allocate(layout)?;
+
+        Ok((CheckedHandle::live(handle), size))
+    }
+
+    #[track_caller]
+    unsafe fn deallocate(&self, handle: Self::Handle, layout: Layout) {
+        Self::retire(&handle);
+
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.0.deallocate(handle.handle, layout) }
+    }
+
+    #[track_caller]
+    unsafe fn grow(
+        &self,
+        handle: &mut Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<usize, AllocError> {
+        Self::check(handle);
+
+        //  Safety:
+        //  -   As per pre-conditions.
+        let (grown, size) = unsafe { self.0.grow(handle.handle, old_layout, new_layout)? };
+
+        Self::retire(handle);
+
+        *handle = CheckedHandle::live(grown);
+
+        Ok(size)
+    }
+
+    #[track_caller]
+    unsafe fn shrink(
+        &self,
+        handle: &mut Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<usize, AllocError> {
+        Self::check(handle);
+
+        //  Safety:
+        //  -   As per pre-conditions.
+        let (shrunk, size) = unsafe { self.0.shrink(handle.handle, old_layout, new_layout)? };
+
+        Self::retire(handle);
+
+        *handle = CheckedHandle::live(shrunk);
+
+        Ok(size)
+    }
+}
+
+/// The handle of a `CheckedStore`.
+///
+/// Cloning a handle shares its liveness flag: once any clone is deallocated, grown, or shrunk, all are stale.
+#[derive(Clone)]
+pub struct CheckedHandle<H> {
+    handle: H,
+    //  `None` for dangling handles.
+    live: Option<Rc<Cell<bool>>>,
+}
+
+impl<H> CheckedHandle<H> {
+    fn live(handle: H) -> Self {
+        let live = Some(Rc::new(Cell::new(true)));
+
+        Self { handle, live }
+    }
+}
+
+impl<H> fmt::Debug for CheckedHandle<H>
+where
+    H: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let live = self.live.as_ref().map(|live| live.get());
+
+        f.debug_struct("CheckedHandle")
+            .field("handle", &self.handle)
+            .field("live", &live)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{extension::owned::OwnedHandle, store::InlineBumpStore};
+
+    use super::*;
+
+    type Checked = CheckedStore<InlineBumpStore<u8, [u64; 4]>>;
+
+    #[test]
+    fn dangling() {
+        let store = Checked::default();
+
+        let handle = OwnedHandle::<[u32], _>::try_dangling_slice(&store).unwrap();
+        let (handle, _) = handle.into_raw_parts();
+
+        assert!(Checked::is_live(&handle));
+    }
+
+    #[test]
+    fn deallocate() {
+        let store = Checked::default();
+
+        let handle = OwnedHandle::new(7u32, &store);
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, is still valid, and points to a valid `u32`.
+        assert_eq!(7, unsafe { *handle.resolve(&store) });
+
+        let (handle, metadata) = handle.into_raw_parts();
+        let stale = handle.clone();
+
+        assert!(Checked::is_live(&stale));
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        unsafe { OwnedHandle::<u32, _>::from_raw_parts(handle, metadata).deallocate(&store) };
+
+        assert!(!Checked::is_live(&stale));
+    }
+
+    #[test]
+    fn grow_shrink() {
+        let store = Checked::default();
+
+        let mut handle = OwnedHandle::<[u16], _>::try_allocate_slice(2, &store).unwrap();
+
+        let (raw, metadata) = handle.into_raw_parts();
+        let before_grow = raw.clone();
+        handle = OwnedHandle::from_raw_parts(raw, metadata);
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid, and 64 is greater than 2.
+        assert!(unsafe { handle.try_grow(64, &store) }.is_err());
+
+        assert!(Checked::is_live(&before_grow));
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid, and 8 is greater than 2.
+        unsafe { handle.try_grow(8, &store).unwrap() };
+
+        assert!(!Checked::is_live(&before_grow));
+
+        let (raw, metadata) = handle.into_raw_parts();
+        let before_shrink = raw.clone();
+        handle = OwnedHandle::from_raw_parts(raw, metadata);
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid, and 4 is less than 8.
+        unsafe { handle.try_shrink(4, &store).unwrap() };
+
+        assert!(!Checked::is_live(&before_shrink));
+
+        let (raw, metadata) = handle.into_raw_parts();
+
+        assert!(Checked::is_live(&raw));
+
+        //  Safety:
+        //  -   `raw` was allocated by `store`, and is still valid.
+        unsafe { OwnedHandle::<[u16], _>::from_raw_parts(raw, metadata).deallocate(&store) };
+    }
+} // mod tests
@@ -6,75 +6,150 @@
 
 use core::{
     alloc::{AllocError, Layout},
-    cell::{Cell, UnsafeCell},
+    cell::UnsafeCell,
     fmt,
-    mem::MaybeUninit,
-    ptr::{self, Alignment, NonNull},
+    marker::PhantomData,
+    mem::{self, MaybeUninit},
+    ptr::{Alignment, NonNull},
 };
 
-use crate::interface::{Store, StoreDangling, StoreSingle, StoreStable};
+use crate::{
+    interface::{
+        Store, StoreDangling, StoreIntrospect, StoreLimits, StoreOffsetHandle, StorePersist, StoreSingle, StoreValidate,
+    },
+    store::{
+        assert_handle_fits, BumpCore, BumpError, BumpMemory, HandleFromUsize, MaxOffset, NoRounding, RoundingPolicy,
+    },
+};
+
+#[cfg(debug_assertions)]
+use crate::store::BumpDump;
 
 /// An implementation of `Store` providing a single, inline, block of memory.
 ///
 /// Generic parameters:
 ///
-/// -   `H` is the handle type, it must convertible to and from `usize`.
+/// -   `H` is the handle type, it must convertible to and from `usize`. See `assert_handle_fits` to check, at
+///     compile-time, that it can represent any offset within the block of memory.
 /// -   The block of memory is aligned and sized as per `T`.
-pub struct InlineBumpStore<H, T> {
-    watermark: Cell<H>,
+/// -   `R` is the rounding policy, applied to the size of each allocation.
+pub struct InlineBumpStore<H, T, R = NoRounding> {
+    core: BumpCore,
+    //  The handle of offset 0, computed upfront so that `dangling` needs no conversion, and thus may be const for any
+    //  handle type.
+    dangling: H,
     memory: UnsafeCell<MaybeUninit<T>>,
+    _marker: PhantomData<fn(H) -> (H, R)>,
 }
 
-impl<H, T> InlineBumpStore<H, T>
+impl<H, T, R> InlineBumpStore<H, T, R>
 where
-    H: TryFrom<usize>,
+    H: MaxOffset,
 {
-    fn new() -> Result<Self, AllocError> {
-        let _ = Self::from_offset(Self::memory_layout().size())?;
+    /// Creates a new instance.
+    ///
+    /// Usable in const contexts, for example to create empty collections.
+    ///
+    /// #   Panics
+    ///
+    /// If the size of `T` is not representable by `H`, at compile-time when evaluated in a const context.
+    pub const fn new() -> Self
+    where
+        H: Copy + ~const HandleFromUsize,
+    {
+        assert_handle_fits::<H, T>();
+
+        let dangling = match H::from_usize(0) {
+            Some(handle) => handle,
+            None => panic!("Offset 0 to be representable by `H`"),
+        };
 
-        let watermark = Cell::new(Self::from_offset(0)?);
+        let core = BumpCore::new();
         let memory = UnsafeCell::new(MaybeUninit::uninit());
+        let _marker = PhantomData;
 
-        Ok(Self { watermark, memory })
+        Self {
+            core,
+            dangling,
+            memory,
+            _marker,
+        }
     }
 }
 
-impl<H, T> Default for InlineBumpStore<H, T>
+impl<H, T, R> InlineBumpStore<H, T, R>
+where
+    H: TryFrom<usize>,
+{
+    /// Creates a new instance, whose block of memory is zero-initialized upfront.
+    ///
+    /// Zeroing the whole block of memory once lets `allocate_zeroed` and `grow_zeroed` skip zeroing, except for memory
+    /// previously handed out.
+    ///
+    /// #   Panics
+    ///
+    /// If the size of `T` is not representable by `H`.
+    pub fn new_zeroed() -> Self {
+        //  Safety:
+        //  -   The block of memory is zero-initialized.
+        let core = unsafe { BumpCore::new_zeroed() };
+
+        Self::with_memory(core, MaybeUninit::zeroed()).expect("Size of `T` to be representable by `H`")
+    }
+
+    fn try_new() -> Result<Self, BumpError> {
+        Self::with_memory(BumpCore::new(), MaybeUninit::uninit())
+    }
+
+    fn with_memory(core: BumpCore, memory: MaybeUninit<T>) -> Result<Self, BumpError> {
+        let _ = BumpCore::from_offset::<H>(Self::memory_layout().size())?;
+
+        let dangling = BumpCore::from_offset(0)?;
+        let memory = UnsafeCell::new(memory);
+        let _marker = PhantomData;
+
+        Ok(Self {
+            core,
+            dangling,
+            memory,
+            _marker,
+        })
+    }
+}
+
+impl<H, T, R> Default for InlineBumpStore<H, T, R>
 where
     H: TryFrom<usize>,
 {
     fn default() -> Self {
-        Self::new().expect("Size of `T` to be representable by `H`")
+        Self::try_new().expect("Size of `T` to be representable by `H`")
     }
 }
 
-//  Cannot be const, because TryFrom is not marked #[const_trait].
-unsafe impl<H, T> StoreDangling for InlineBumpStore<H, T>
+unsafe impl<H, T, R> const StoreDangling for InlineBumpStore<H, T, R>
 where
-    H: Copy + TryFrom<usize>,
+    H: Copy,
 {
     type Handle = H;
 
     fn dangling(&self, alignment: Alignment) -> Result<Self::Handle, AllocError> {
-        let layout = Self::memory_layout();
-
-        if alignment.as_usize() > layout.align() {
+        //  The block of memory may move, hence no handle may be aligned beyond `T`, and the start is suitably aligned
+        //  for any alignment up to that of `T`.
+        if alignment.as_usize() > mem::align_of::<T>() {
             return Err(AllocError);
         }
 
-        Self::from_offset(alignment.as_usize())
+        Ok(self.dangling)
     }
 }
 
-unsafe impl<H, T> Store for InlineBumpStore<H, T>
+unsafe impl<H, T, R> Store for InlineBumpStore<H, T, R>
 where
     H: Copy + TryFrom<usize> + TryInto<usize>,
+    R: RoundingPolicy,
 {
     fn allocate(&self, layout: Layout) -> Result<(Self::Handle, usize), AllocError> {
-        let (result, new_watermark) = Self::compute_offset(self.watermark.get(), layout)?;
-        self.watermark.set(new_watermark);
-
-        Ok((result, layout.size()))
+        Ok(self.try_allocate(layout)?)
     }
 
     #[inline(always)]
@@ -82,69 +157,89 @@ where
 
     #[inline(always)]
     unsafe fn resolve(&self, handle: Self::Handle) -> NonNull<u8> {
-        debug_assert!(Self::into_offset(handle) <= Self::memory_layout().size());
-
-        let offset = Self::into_offset(handle);
-        let pointer = self.memory.get() as *mut u8;
-
         //  Safety:
-        //  -   `offset` is within bounds of `self.memory`, as `handle` was allocated by `self` as per pre-conditions.
-        let pointer = unsafe { pointer.add(offset) };
+        //  -   `handle` was allocated by `self`, or created dangling, as per pre-conditions.
+        unsafe { self.memory().resolve(handle) }
+    }
 
+    unsafe fn grow(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
         //  Safety:
-        //  -   `pointer` is non null as `self` is non null.
-        unsafe { NonNull::new_unchecked(pointer) }
+        //  -   As per pre-conditions.
+        Ok(unsafe { self.core.grow::<H, R>(self.memory(), handle, old_layout, new_layout) }?)
     }
 
-    unsafe fn grow(
+    #[inline(always)]
+    unsafe fn shrink(
         &self,
         handle: Self::Handle,
         old_layout: Layout,
         new_layout: Layout,
     ) -> Result<(Self::Handle, usize), AllocError> {
-        debug_assert!(
-            new_layout.size() >= old_layout.size(),
-            "{new_layout:?} must have a greater size than {old_layout:?}"
-        );
+        //  Safety:
+        //  -   As per pre-conditions.
+        Ok(unsafe { self.core.shrink::<H, R>(self.memory(), handle, old_layout, new_layout) }?)
+    }
 
-        //  As an optimization, if `handle` points to the last allocation, growth may actually occur _in place_.
-        {
-            let offset = Self::into_offset(handle);
-            let watermark = Self::into_offset(self.watermark.get());
+    fn allocate_zeroed(&self, layout: Layout) -> Result<(Self::Handle, usize), AllocError> {
+        Ok(self.core.allocate_zeroed::<H, R>(self.memory(), layout)?)
+    }
 
-            if offset + old_layout.size() == watermark
-                && new_layout.align() <= old_layout.align()
-                && offset + new_layout.size() <= Self::memory_layout().size()
-            {
-                let new_watermark = Self::from_offset(watermark - old_layout.size() + new_layout.size())?;
-                self.watermark.set(new_watermark);
+    unsafe fn grow_zeroed(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        let memory = self.memory();
 
-                return Ok((handle, new_layout.size()));
-            }
-        }
+        //  Safety:
+        //  -   As per pre-conditions.
+        Ok(unsafe { self.core.grow_zeroed::<H, R>(memory, handle, old_layout, new_layout) }?)
+    }
+
+    unsafe fn grow_reporting(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        let memory = self.memory();
 
-        self.grow_by_relocation(handle, old_layout, new_layout)
+        //  Safety:
+        //  -   As per pre-conditions.
+        Ok(unsafe { self.core.grow_reporting::<H, R>(memory, handle, old_layout, new_layout) }?)
     }
 
-    #[inline(always)]
-    unsafe fn shrink(
+    unsafe fn shrink_reporting(
         &self,
         handle: Self::Handle,
         old_layout: Layout,
-        _new_layout: Layout,
-    ) -> Result<(Self::Handle, usize), AllocError> {
-        debug_assert!(
-            _new_layout.size() >= old_layout.size(),
-            "{_new_layout:?} must have a smaller size than {old_layout:?}"
-        );
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        let memory = self.memory();
 
-        Ok((handle, old_layout.size()))
+        //  Safety:
+        //  -   As per pre-conditions.
+        let result = unsafe {
+            self.core
+                .shrink_reporting::<H, R>(memory, handle, old_layout, new_layout)
+        };
+
+        Ok(result?)
     }
 }
 
-unsafe impl<H, T> StoreSingle for InlineBumpStore<H, T>
+//  `grow_reporting` conservatively reports relocation: the block of memory is part of `self`, hence pointers resolved
+//  prior to the call do not survive the exclusive borrow of `self` it requires.
+unsafe impl<H, T, R> StoreSingle for InlineBumpStore<H, T, R>
 where
     H: Copy + TryFrom<usize> + TryInto<usize>,
+    R: RoundingPolicy,
 {
     #[inline(always)]
     unsafe fn resolve(&self, handle: Self::Handle) -> NonNull<u8> {
@@ -189,19 +284,140 @@ where
         //  -   As per pre-conditions.
         unsafe { <Self as Store>::shrink(self, handle, old_layout, new_layout) }
     }
+
+    fn allocate_zeroed(&mut self, layout: Layout) -> Result<(Self::Handle, usize), AllocError> {
+        <Self as Store>::allocate_zeroed(self, layout)
+    }
+
+    unsafe fn grow_zeroed(
+        &mut self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { <Self as Store>::grow_zeroed(self, handle, old_layout, new_layout) }
+    }
+}
+
+crate::store_markers! {
+    //  Safety:
+    //  -   `self.resolve(handle)` always returns the same address, as long as `self` doesn't move.
+    unsafe impl[H, T, R] StoreStable for InlineBumpStore<H, T, R>
+    where
+        [H: Copy + TryFrom<usize> + TryInto<usize>];
 }
 
 //  Safety:
-//  -   `self.resolve(handle)` always returns the same address, as long as `self` doesn't move.
-unsafe impl<H, T> StoreStable for InlineBumpStore<H, T> where H: Copy + TryFrom<usize> + TryInto<usize> {}
+//  -   Handles are offsets from the start of the memory, hence advancing the offset advances the resolved pointer.
+unsafe impl<H, T, R> StoreOffsetHandle for InlineBumpStore<H, T, R>
+where
+    H: Copy + TryFrom<usize> + TryInto<usize>,
+{
+    #[inline(always)]
+    unsafe fn offset(handle: Self::Handle, bytes: usize) -> Self::Handle {
+        BumpCore::advance(handle, bytes)
+    }
+}
+
+impl<H, T, R> StoreValidate for InlineBumpStore<H, T, R>
+where
+    H: Copy + TryFrom<usize> + TryInto<usize>,
+{
+    fn is_valid(&self, handle: Self::Handle) -> Option<bool> {
+        self.core.is_valid(handle)
+    }
+}
+
+//  The memory is inline, and moves along with `self`, hence it cannot be mapped onto a persistent medium.
+impl<H, T, R> StorePersist for InlineBumpStore<H, T, R> where H: Copy {}
+
+impl<H, T, R> StoreIntrospect for InlineBumpStore<H, T, R>
+where
+    H: Copy,
+{
+    fn limits(&self) -> StoreLimits {
+        self.memory().limits()
+    }
+}
+
+impl<H, T, R> InlineBumpStore<H, T, R>
+where
+    R: RoundingPolicy,
+{
+    /// Returns the number of bytes consumed so far, including alignment padding and rounding.
+    pub fn used(&self) -> usize {
+        self.core.used()
+    }
+
+    /// Returns the number of bytes still available.
+    pub fn remaining(&self) -> usize {
+        Self::memory_layout().size() - self.used()
+    }
 
-impl<H, T> fmt::Debug for InlineBumpStore<H, T> {
+    /// Returns the number of bytes wasted on alignment padding so far.
+    pub fn padding(&self) -> usize {
+        self.core.padding()
+    }
+
+    /// Returns the name of the rounding policy applied to the size of each allocation.
+    pub fn rounding(&self) -> &'static str {
+        R::NAME
+    }
+}
+
+#[cfg(debug_assertions)]
+impl<H, T, R> InlineBumpStore<H, T, R> {
+    /// Returns a dump of the memory used so far, for debugging purposes, see `BumpDump`.
+    ///
+    /// #   Safety
+    ///
+    /// -   The used memory must be initialized, as is the case if the store was created by `new_zeroed`, and only
+    ///     values without padding bytes are written to it.
+    /// -   The used memory must not be written to for as long as the dump is alive.
+    pub unsafe fn dump(&self) -> BumpDump<'_> {
+        //  Safety:
+        //  -   The block of memory is valid for reads for as long as `self` is borrowed.
+        //  -   The used memory is initialized, and not written to, as per pre-conditions.
+        unsafe { self.core.dump(self.memory()) }
+    }
+}
+
+impl<H, T, R> InlineBumpStore<H, T, R>
+where
+    H: Copy + TryFrom<usize> + TryInto<usize>,
+    R: RoundingPolicy,
+{
+    /// Attempts to allocate a block of memory, as per `Store::allocate`, reporting why allocation failed, if it did.
+    ///
+    /// Notably, allocation fails with `BumpError::HandleOverflow` if the offset of the block of memory is not
+    /// representable by `H`, even though the block of memory fits.
+    pub fn try_allocate(&self, layout: Layout) -> Result<(H, usize), BumpError> {
+        self.core.allocate::<H, R>(self.memory(), layout)
+    }
+
+    /// Allocates a batch of blocks of memory, by order of decreasing alignment so as to minimize padding.
+    ///
+    /// The handles and sizes are returned in the order of `layouts`. On failure, the blocks of memory allocated so far
+    /// are not reclaimed.
+    pub fn allocate_batch<const N: usize>(&self, layouts: [Layout; N]) -> Result<[(H, usize); N], AllocError> {
+        Ok(self.core.allocate_batch::<H, R, N>(self.memory(), layouts)?)
+    }
+}
+
+impl<H, T, R> fmt::Debug for InlineBumpStore<H, T, R>
+where
+    R: RoundingPolicy,
+{
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
         let layout = Layout::new::<T>();
 
         f.debug_struct("InlineBumpStore")
             .field("size", &layout.size())
             .field("align", &layout.align())
+            .field("padding", &self.core.padding())
+            .field("rounding", &R::NAME)
             .finish()
     }
 }
@@ -210,103 +426,125 @@ impl<H, T> fmt::Debug for InlineBumpStore<H, T> {
 //  Implementation
 //
 
-impl<H, T> InlineBumpStore<H, T> {
+impl<H, T, R> InlineBumpStore<H, T, R> {
     #[inline(always)]
     const fn memory_layout() -> Layout {
         Layout::new::<T>()
     }
-}
 
-impl<H, T> InlineBumpStore<H, T>
-where
-    H: TryFrom<usize>,
-{
     #[inline(always)]
-    fn from_offset(offset: usize) -> Result<H, AllocError> {
-        debug_assert!(offset <= Self::memory_layout().size());
+    fn memory(&self) -> BumpMemory {
+        //  Safety:
+        //  -   `self.memory.get()` is non null, as `self` is non null.
+        let address = unsafe { NonNull::new_unchecked(self.memory.get() as *mut u8) };
+
+        let memory = NonNull::slice_from_raw_parts(address, mem::size_of::<T>());
 
-        offset.try_into().map_err(|_| AllocError)
+        BumpMemory::movable(memory, Alignment::of::<T>())
     }
 }
 
-impl<H, T> InlineBumpStore<H, T>
-where
-    H: TryInto<usize>,
-{
-    #[inline(always)]
-    fn into_offset(handle: H) -> usize {
-        let offset = handle.try_into();
+#[cfg(test)]
+mod tests {
+    use crate::{collection::StoreVec, store::RefStore};
 
-        debug_assert!(offset.is_ok());
+    use super::*;
 
-        //  Safety:
-        //  -   `handle` was created from `usize`, hence converting back always succeeds.
-        unsafe { offset.unwrap_unchecked() }
+    #[test]
+    fn narrow_handle() {
+        assert!(InlineBumpStore::<u8, [u8; 255]>::try_new().is_ok());
+        assert!(InlineBumpStore::<u8, [u8; 256]>::try_new().is_err());
     }
-}
 
-impl<H, T> InlineBumpStore<H, T>
-where
-    H: TryFrom<usize> + TryInto<usize>,
-{
-    //  Returns the offset and new watermark of the newly allocated memory block.
-    fn compute_offset(watermark: H, layout: Layout) -> Result<(H, H), AllocError> {
-        let watermark = Self::into_offset(watermark);
-        let memory = Self::memory_layout();
+    #[test]
+    fn offset_overflow() {
+        let store = InlineBumpStore::<u8, [u8; 64]>::default();
 
-        if layout.align() > memory.align() {
-            //  Even if the memory block was aligned for the current address of `self.memory`, moving `self` would risk
-            //  breaking this alignment.
+        Store::allocate(&store, Layout::new::<u8>()).unwrap();
 
-            return Err(AllocError);
-        }
+        let huge = Layout::from_size_align(isize::MAX as usize, 1).unwrap();
+
+        assert!(Store::allocate(&store, huge).is_err());
+        assert_eq!(1, store.used());
+    }
 
-        let aligned = {
-            //  Since `layout.align()` is always a power of 2, aligning to the next multiple of `layout.align()` can be
-            //  done with this one simple trick.
-            let alignment_mask = layout.align() - 1;
+    #[test]
+    fn const_empty_vec() {
+        let mut vec = const { StoreVec::<u32, InlineBumpStore<u8, [u32; 4]>>::new_in(InlineBumpStore::new()) };
 
-            (watermark + alignment_mask) & !alignment_mask
-        };
+        assert!(vec.is_empty());
 
-        let new_watermark = aligned + layout.size();
+        vec.extend([1, 2, 3]);
 
-        if new_watermark > memory.size() {
-            return Err(AllocError);
+        assert_eq!([1, 2, 3], &vec[..]);
+    }
+
+    #[test]
+    fn custom_handle() {
+        //  A handle convertible to and from `usize`, yet not implementing `HandleFromUsize`.
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        struct Handle(u16);
+
+        impl TryFrom<usize> for Handle {
+            type Error = <u16 as TryFrom<usize>>::Error;
+
+            fn try_from(offset: usize) -> Result<Self, Self::Error> {
+                u16::try_from(offset).map(Handle)
+            }
+        }
+
+        impl From<Handle> for usize {
+            fn from(handle: Handle) -> usize {
+                handle.0.into()
+            }
         }
 
-        let aligned = Self::from_offset(aligned)?;
-        let new_watermark = Self::from_offset(new_watermark)?;
+        let store = InlineBumpStore::<Handle, [u32; 4]>::default();
+        let mut vec = StoreVec::new_in(RefStore::new(&store));
+
+        vec.extend([1u32, 2, 3]);
 
-        Ok((aligned, new_watermark))
+        assert_eq!([1, 2, 3], &vec[..]);
     }
-}
 
-impl<H, T> InlineBumpStore<H, T>
-where
-    H: Copy + TryFrom<usize> + TryInto<usize>,
-{
-    //  Slow part of `grow`.
-    #[inline(never)]
-    fn grow_by_relocation(&self, handle: H, old_layout: Layout, new_layout: Layout) -> Result<(H, usize), AllocError> {
-        let (result, new_watermark) = Self::compute_offset(self.watermark.get(), new_layout)?;
-        self.watermark.set(new_watermark);
+    #[test]
+    fn allocate_zeroed() {
+        for store in [
+            InlineBumpStore::<u8, [u32; 4]>::default(),
+            InlineBumpStore::new_zeroed(),
+        ] {
+            let (handle, _) = Store::allocate_zeroed(&store, Layout::new::<[u32; 2]>()).unwrap();
 
-        //  Safety:
-        //  -   `handle` is valid, as per pre-conditions.
-        //  -   `result` is valid, since newly allocated.
-        let (new, old) = unsafe { (Store::resolve(self, result), Store::resolve(self, handle)) };
+            //  Safety:
+            //  -   `handle` was allocated by `store`, and is still valid.
+            //  -   The block of memory was zero-initialized.
+            let array = unsafe { Store::resolve(&store, handle).cast::<[u32; 2]>().read() };
 
-        //  Safety:
-        //  -   `old` is valid for `old_layout.size()` bytes, as per pre-conditions.
-        //  -   `new` is valid for `old_layout.size()` bytes, since it is valid for `new_layout.size()` bytes and as per
-        //      pre-conditions `new_layout.size() >= old_layout.size()`.
-        //  -   `old` and `new` are at least 1-byte aligned.
-        //  -   `old` and `new` point to non-overlapping areas, since `old` points to a memory area prior to the
-        //      watermark and `new` points to a memory area post the watermark (as the beginning of this function),
-        //      since `old_layout` fits `old` as per pre-conditions.
-        unsafe { ptr::copy_nonoverlapping(old.as_ptr(), new.as_ptr(), old_layout.size()) };
+            assert_eq!([0, 0], array);
+        }
+    }
 
-        Ok((result, new_layout.size()))
+    #[cfg(debug_assertions)]
+    #[test]
+    fn dump() {
+        let store = InlineBumpStore::<u8, [u64; 2]>::new_zeroed();
+
+        let mut vec = StoreVec::with_capacity_in(4, RefStore::new(&store));
+        vec.extend(*b"ok!");
+
+        //  Safety:
+        //  -   The store was created by `new_zeroed`, and only bytes were written to it.
+        //  -   The store is not written to while the dump is alive.
+        let dump = unsafe { store.dump() };
+
+        //  The spare capacity of the vector is used, and still zeroed.
+        assert_eq!(b"ok!\0", dump.bytes());
+        assert_eq!(
+            concat!(
+                "BumpDump { used: 4, padding: 0, size: 16 }\n",
+                "0000: 6f 6b 21 00                                      |ok!.|",
+            ),
+            format!("{dump:?}")
+        );
     }
-}
+} // mod tests
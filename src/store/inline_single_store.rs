@@ -9,7 +9,7 @@ use core::{
     ptr::{self, Alignment, NonNull},
 };
 
-use crate::interface::{StoreDangling, StoreSingle, StoreStable};
+use crate::interface::{Growth, StoreDangling, StoreIntrospect, StoreLimits, StorePersist, StoreSingle};
 
 /// An implementation of `Store` providing a single, inline, block of memory.
 ///
@@ -42,6 +42,8 @@ unsafe impl<T> const StoreDangling for InlineSingleStore<T> {
 }
 
 unsafe impl<T> const StoreSingle for InlineSingleStore<T> {
+    const GROWTH: Growth = Growth::Exact;
+
     unsafe fn resolve(&self, _handle: Self::Handle) -> NonNull<u8> {
         let pointer = self.0.as_ptr() as *mut T;
 
@@ -90,13 +92,18 @@ unsafe impl<T> const StoreSingle for InlineSingleStore<T> {
         &mut self,
         _handle: Self::Handle,
         _old_layout: Layout,
-        _new_layout: Layout,
+        new_layout: Layout,
     ) -> Result<(Self::Handle, usize), AllocError> {
         debug_assert!(
-            _new_layout.size() >= _old_layout.size(),
-            "_new_layout must have a smaller size than _old_layout"
+            new_layout.size() <= _old_layout.size(),
+            "new_layout must have a smaller size than _old_layout"
         );
 
+        //  The size fits, as it shrinks, but the alignment may have increased.
+        if Self::validate_layout(new_layout).is_err() {
+            return Err(AllocError);
+        }
+
         Ok(((), mem::size_of::<T>()))
     }
 
@@ -149,9 +156,24 @@ unsafe impl<T> const StoreSingle for InlineSingleStore<T> {
     }
 }
 
-//  Safety:
-//  -   `self.resolve(handle)` always returns the same address, as long as `self` doesn't move.
-unsafe impl<T> StoreStable for InlineSingleStore<T> {}
+crate::store_markers! {
+    //  Safety:
+    //  -   `self.resolve(handle)` always returns the same address, as long as `self` doesn't move.
+    unsafe impl[T] StoreStable for InlineSingleStore<T>;
+}
+
+//  The block of memory is part of `self`, and vanishes with it.
+impl<T> StorePersist for InlineSingleStore<T> {}
+
+impl<T> StoreIntrospect for InlineSingleStore<T> {
+    fn limits(&self) -> StoreLimits {
+        StoreLimits {
+            max_align: Alignment::of::<T>(),
+            max_size: mem::size_of::<T>(),
+            supports_dealloc_reuse: true,
+        }
+    }
+}
 
 impl<T> fmt::Debug for InlineSingleStore<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
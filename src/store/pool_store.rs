@@ -0,0 +1,209 @@
+//! A Store with a bounded number of sharing slots.
+//!
+//! A store referencing a pool, which owns the underlying store and a fixed number of slots. Each instance occupies one
+//! slot for as long as it lives, hence sharing fails once all slots are occupied, until an instance is dropped.
+//!
+//! This store mostly serves to exercise the fallible path of `StoreSharing::share`.
+
+use core::{cell::Cell, fmt, ptr};
+
+use crate::interface::{StoreSharing, StoreStable};
+
+/// A pool of sharing slots for a store.
+pub struct SharingPool<S> {
+    store: S,
+    available: Cell<usize>,
+}
+
+impl<S> SharingPool<S> {
+    /// Creates a new pool, owning `store`, with `slots` sharing slots.
+    pub const fn new(store: S, slots: usize) -> Self {
+        let available = Cell::new(slots);
+
+        Self { store, available }
+    }
+
+    /// Returns the number of available slots.
+    pub fn available(&self) -> usize {
+        self.available.get()
+    }
+
+    /// Attempts to create a store referencing this pool, occupying one slot.
+    ///
+    /// Returns an error if all the slots are occupied.
+    pub fn try_create_store(&self) -> Result<PoolStore<'_, S>, SharingExhausted> {
+        let available = self.available.get();
+
+        if available == 0 {
+            return Err(SharingExhausted);
+        }
+
+        self.available.set(available - 1);
+
+        Ok(PoolStore { pool: self })
+    }
+}
+
+impl<S> fmt::Debug for SharingPool<S>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_struct("SharingPool")
+            .field("store", &self.store)
+            .field("available", &self.available.get())
+            .finish()
+    }
+}
+
+/// The error returned when all the slots of a `SharingPool` are occupied.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SharingExhausted;
+
+impl fmt::Display for SharingExhausted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str("all sharing slots are occupied")
+    }
+}
+
+/// A store referencing a `SharingPool`, occupying one of its slots.
+///
+/// All instances referencing the same pool are fungible.
+pub struct PoolStore<'a, S> {
+    pool: &'a SharingPool<S>,
+}
+
+impl<'a, S> PoolStore<'a, S> {
+    /// Returns a reference to the pool.
+    pub const fn pool(&self) -> &'a SharingPool<S> {
+        self.pool
+    }
+}
+
+crate::store::delegate_store! {
+    //  Safety:
+    //  -   `self` forwards every call to `S`, owned by the pool.
+    unsafe impl['a, S] Store for PoolStore<'a, S> => S via pool.store;
+}
+
+crate::store_markers! {
+    //  Safety:
+    //  -   `S` is stable, and cannot move while borrowed, even when `self` is moved.
+    unsafe impl['a, S] StorePinning for PoolStore<'a, S> where [S: StoreStable];
+}
+
+//  Safety:
+//  -   All instances referencing the same pool are fungible, as they refer to the same instance of `S`.
+unsafe impl<'a, S> StoreSharing for PoolStore<'a, S>
+where
+    S: StoreStable,
+{
+    type SharingError = SharingExhausted;
+
+    type SharingToken = usize;
+
+    fn is_sharing_with(&self, other: &Self) -> bool {
+        ptr::eq(self.pool, other.pool)
+    }
+
+    fn sharing_token(&self) -> Self::SharingToken {
+        ptr::from_ref(self.pool).addr()
+    }
+
+    fn share(&self) -> Result<Self, Self::SharingError> {
+        self.pool.try_create_store()
+    }
+}
+
+impl<'a, S> Drop for PoolStore<'a, S> {
+    fn drop(&mut self) {
+        self.pool.available.set(self.pool.available.get() + 1);
+    }
+}
+
+impl<'a, S> fmt::Debug for PoolStore<'a, S>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_tuple("PoolStore").field(&self.pool.store).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::alloc::Layout;
+
+    use crate::{
+        collection::{CloneError, LinkedList},
+        interface::Store,
+        store::InlineBumpStore,
+    };
+
+    use super::*;
+
+    type TestStore = InlineBumpStore<u16, [u64; 32]>;
+
+    #[test]
+    fn share_exhausted() {
+        let pool = SharingPool::new(TestStore::default(), 2);
+
+        let first = pool.try_create_store().unwrap();
+        let second = first.share().unwrap();
+
+        assert!(first.is_sharing_with(&second));
+        assert_eq!(0, pool.available());
+        assert_eq!(Err(SharingExhausted), first.share().map(|_| ()));
+
+        drop(second);
+
+        assert_eq!(1, pool.available());
+        assert!(first.share().is_ok());
+        assert_eq!(1, pool.available());
+    }
+
+    #[test]
+    fn grow_reporting_in_place() {
+        let pool = SharingPool::new(TestStore::default(), 1);
+        let store = pool.try_create_store().unwrap();
+
+        let (old_layout, new_layout) = (Layout::new::<[u32; 2]>(), Layout::new::<[u32; 4]>());
+
+        let (handle, _) = store.allocate(old_layout).unwrap();
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, with `old_layout`, and is still valid.
+        //  -   `new_layout` is larger than `old_layout`, with the same alignment.
+        let (_, _, relocated) = unsafe { store.grow_reporting(handle, old_layout, new_layout).unwrap() };
+
+        //  The bump store grows its last allocation in place, which the pool store reports as is.
+        assert!(!relocated);
+    }
+
+    #[test]
+    fn list_try_split_off() {
+        let pool = SharingPool::new(TestStore::default(), 2);
+
+        let mut list = LinkedList::new_in(pool.try_create_store().unwrap());
+
+        list.try_extend((0..4).map(|i| i.to_string())).unwrap();
+
+        let tail = list.try_split_off(2).unwrap();
+
+        assert_eq!(r#"["0", "1"]"#, format!("{list:?}"));
+        assert_eq!(r#"["2", "3"]"#, format!("{tail:?}"));
+
+        //  Both slots are occupied: the list is left untouched.
+        assert_eq!(Err(SharingExhausted), list.try_split_off(1).map(|_| ()));
+        assert_eq!(Err(CloneError::Sharing(SharingExhausted)), list.try_clone().map(|_| ()));
+
+        assert_eq!(r#"["0", "1"]"#, format!("{list:?}"));
+
+        drop(tail);
+
+        let tail = list.try_split_off(1).unwrap();
+
+        assert_eq!(r#"["0"]"#, format!("{list:?}"));
+        assert_eq!(r#"["1"]"#, format!("{tail:?}"));
+    }
+} // mod tests
@@ -0,0 +1,94 @@
+//! A borrowing Store.
+//!
+//! A store which borrows its underlying store, so that multiple collections may share a single store for the duration
+//! of the borrow, regardless of whether the underlying store implements `StoreSharing`.
+//!
+//! _Note: the store traits cannot be implemented for `&S` directly, as those would conflict with the blanket
+//! implementations for `Allocator`._
+
+use core::{fmt, ptr};
+
+use crate::interface::{StoreOffsetHandle, StoreSharing, StoreStable};
+
+/// A store borrowing its underlying store.
+///
+/// All copies of an instance are fungible.
+pub struct RefStore<'a, S>(&'a S);
+
+impl<'a, S> RefStore<'a, S> {
+    /// Creates a new instance, borrowing `store`.
+    pub const fn new(store: &'a S) -> Self {
+        Self(store)
+    }
+
+    /// Returns a reference to the underlying store.
+    pub const fn get(&self) -> &'a S {
+        self.0
+    }
+}
+
+crate::store::delegate_store! {
+    //  Safety:
+    //  -   `self` forwards every call to `S`.
+    unsafe impl['a, S] Store for RefStore<'a, S> => S;
+}
+
+crate::store_markers! {
+    //  Safety:
+    //  -   `S` is stable, and cannot move while borrowed, even when `self` is moved.
+    unsafe impl['a, S] StorePinning for RefStore<'a, S> where [S: StoreStable];
+}
+
+//  Safety:
+//  -   All instances borrowing the same store are fungible, as they refer to the same instance of `S`.
+unsafe impl<'a, S> StoreSharing for RefStore<'a, S>
+where
+    S: StoreStable,
+{
+    type SharingError = !;
+
+    type SharingToken = usize;
+
+    fn is_sharing_with(&self, other: &Self) -> bool {
+        ptr::eq(self.0, other.0)
+    }
+
+    fn sharing_token(&self) -> Self::SharingToken {
+        ptr::from_ref(self.0).addr()
+    }
+
+    fn share(&self) -> Result<Self, Self::SharingError> {
+        Ok(*self)
+    }
+}
+
+//  Safety:
+//  -   `self` resolves handles through `S`.
+unsafe impl<'a, S> StoreOffsetHandle for RefStore<'a, S>
+where
+    S: StoreOffsetHandle,
+{
+    #[inline(always)]
+    unsafe fn offset(handle: Self::Handle, bytes: usize) -> Self::Handle {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { S::offset(handle, bytes) }
+    }
+}
+
+impl<'a, S> Clone for RefStore<'a, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, S> Copy for RefStore<'a, S> {}
+
+impl<'a, S> fmt::Debug for RefStore<'a, S>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_tuple("RefStore").field(self.0).finish()
+    }
+}
@@ -0,0 +1,109 @@
+//! Rounding policies for the sizes of the allocations of bump stores.
+//!
+//! Bump stores never reclaim memory, hence rounding up the size of the allocations trades internal fragmentation for a
+//! greater likelihood of growing in place, as collections may use the extra capacity directly.
+
+use core::alloc::{AllocError, Layout};
+
+/// A policy for rounding up the size of allocations.
+///
+/// #   Safety
+///
+/// Implementers of this trait must guarantee that the layout returned by `round`:
+///
+/// -   Has the same alignment as the original layout.
+/// -   Has a size greater than or equal to that of the original layout.
+/// -   Is a fixed point: rounding it again returns the same layout.
+pub unsafe trait RoundingPolicy {
+    /// The name of the policy, as displayed by `Debug`.
+    const NAME: &'static str;
+
+    /// Returns the rounded up layout, or `AllocError` if rounding up overflows.
+    fn round(layout: Layout) -> Result<Layout, AllocError>;
+}
+
+/// Allocates exactly the requested size.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct NoRounding;
+
+//  Safety:
+//  -   The layout is returned as is.
+unsafe impl RoundingPolicy for NoRounding {
+    const NAME: &'static str = "NoRounding";
+
+    #[inline(always)]
+    fn round(layout: Layout) -> Result<Layout, AllocError> {
+        Ok(layout)
+    }
+}
+
+/// Rounds up the requested size to a multiple of the requested alignment.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct RoundToAlign;
+
+//  Safety:
+//  -   `pad_to_align` preserves the alignment, never decreases the size, and a padded layout is already padded.
+unsafe impl RoundingPolicy for RoundToAlign {
+    const NAME: &'static str = "RoundToAlign";
+
+    #[inline(always)]
+    fn round(layout: Layout) -> Result<Layout, AllocError> {
+        Ok(layout.pad_to_align())
+    }
+}
+
+/// Rounds up the requested size to the next power of two, unless zero.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct RoundToPowerOfTwo;
+
+//  Safety:
+//  -   The alignment is preserved, the size never decreases, and a power of two is its own next power of two.
+unsafe impl RoundingPolicy for RoundToPowerOfTwo {
+    const NAME: &'static str = "RoundToPowerOfTwo";
+
+    #[inline(always)]
+    fn round(layout: Layout) -> Result<Layout, AllocError> {
+        if layout.size() == 0 {
+            return Ok(layout);
+        }
+
+        let size = layout.size().checked_next_power_of_two().ok_or(AllocError)?;
+
+        Layout::from_size_align(size, layout.align()).map_err(|_| AllocError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round<R: RoundingPolicy>(size: usize, align: usize) -> (usize, usize) {
+        let layout = Layout::from_size_align(size, align).unwrap();
+        let rounded = R::round(layout).unwrap();
+
+        assert_eq!(rounded, R::round(rounded).unwrap());
+
+        (rounded.size(), rounded.align())
+    }
+
+    #[test]
+    fn no_rounding() {
+        assert_eq!((0, 1), round::<NoRounding>(0, 1));
+        assert_eq!((5, 4), round::<NoRounding>(5, 4));
+    }
+
+    #[test]
+    fn round_to_align() {
+        assert_eq!((0, 1), round::<RoundToAlign>(0, 1));
+        assert_eq!((8, 4), round::<RoundToAlign>(5, 4));
+        assert_eq!((24, 8), round::<RoundToAlign>(24, 8));
+    }
+
+    #[test]
+    fn round_to_power_of_two() {
+        assert_eq!((0, 1), round::<RoundToPowerOfTwo>(0, 1));
+        assert_eq!((8, 4), round::<RoundToPowerOfTwo>(5, 4));
+        assert_eq!((32, 8), round::<RoundToPowerOfTwo>(24, 8));
+        assert!(RoundToPowerOfTwo::round(Layout::from_size_align(isize::MAX as usize / 2 + 2, 1).unwrap()).is_err());
+    }
+} // mod tests
@@ -0,0 +1,566 @@
+//! A Store whose behavior is scripted, for testing.
+//!
+//! The store allocates from the global allocator, yet each allocation, growth, and shrinkage follows the behavior
+//! decided by a script: it may fail, return more memory than requested, or move the block of memory rather than
+//! resizing it in place. All those behaviors are legal, yet most stores rarely exhibit them, hence collections are
+//! rarely tested against them.
+//!
+//! The store also records the blocks of memory it handed out, and panics if a collection deallocates, grows, or
+//! shrinks a block of memory with a layout which does not fit it.
+
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    cell::{Cell, RefCell},
+    cmp, fmt,
+    ptr::{self, Alignment, NonNull},
+};
+
+use std_alloc::{alloc::Global, collections::BTreeMap};
+
+use oorandom::Rand32;
+
+use crate::{
+    interface::{Store, StoreDangling, StoreSingle},
+    store::AllocatorHandle,
+};
+
+/// An operation of a `ScriptedStore`, as presented to its script.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ScriptedOperation {
+    /// Allocation of a new block of memory, whether zeroed or not.
+    Allocate,
+    /// Growth of an existing block of memory, whether zeroed or not.
+    Grow,
+    /// Shrinkage of an existing block of memory.
+    Shrink,
+}
+
+/// The behavior of a `ScriptedStore` for one operation, as decided by its script.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct ScriptedBehavior {
+    /// Whether the operation fails.
+    pub fail: bool,
+    /// The number of bytes returned beyond the requested size.
+    ///
+    /// For shrinkage, the returned size is capped at the size of the block of memory prior to shrinking.
+    pub extra: usize,
+    /// Whether the block of memory is moved to a new address, for growth and shrinkage.
+    ///
+    /// Otherwise, the block of memory is resized in place if it has room to, and delegated to the global allocator
+    /// otherwise.
+    pub relocate: bool,
+}
+
+/// The script of a `ScriptedStore`, deciding the behavior of each operation.
+///
+/// Any `FnMut(ScriptedOperation, Layout) -> ScriptedBehavior` is a script, called with the operation and the requested
+/// layout.
+pub trait Script {
+    /// Returns the behavior of the store for `operation`, requesting `layout`.
+    fn next(&mut self, operation: ScriptedOperation, layout: Layout) -> ScriptedBehavior;
+}
+
+impl<F> Script for F
+where
+    F: FnMut(ScriptedOperation, Layout) -> ScriptedBehavior,
+{
+    fn next(&mut self, operation: ScriptedOperation, layout: Layout) -> ScriptedBehavior {
+        self(operation, layout)
+    }
+}
+
+/// A script drawing behaviors from a pseudo-random generator, deterministically for a given seed.
+#[derive(Clone, Debug)]
+pub struct SeededScript {
+    random: Rand32,
+    failure_one_in: u32,
+    max_extra: u32,
+}
+
+impl SeededScript {
+    /// Creates a script from `seed`, failing one operation in 8 on average, and returning up to 32 extra bytes.
+    pub fn new(seed: u64) -> Self {
+        Self::with_failures(seed, 8)
+    }
+
+    /// Creates a script from `seed`, failing one operation in `failure_one_in` on average, or never if 0, and
+    /// returning up to 32 extra bytes.
+    pub fn with_failures(seed: u64, failure_one_in: u32) -> Self {
+        let random = Rand32::new(seed);
+
+        Self {
+            random,
+            failure_one_in,
+            max_extra: 32,
+        }
+    }
+}
+
+impl Script for SeededScript {
+    fn next(&mut self, _operation: ScriptedOperation, _layout: Layout) -> ScriptedBehavior {
+        let fail = self.failure_one_in != 0 && self.random.rand_range(0..self.failure_one_in) == 0;
+        let extra = self.random.rand_range(0..self.max_extra + 1) as usize;
+        let relocate = self.random.rand_range(0..2) == 1;
+
+        ScriptedBehavior { fail, extra, relocate }
+    }
+}
+
+/// A store whose behavior is scripted, for testing collections against any legal store behavior.
+///
+/// Handles are pointers, as for the global allocator.
+///
+/// #   Panics
+///
+/// Panics if a block of memory is deallocated, grown, or shrunk with a layout which does not fit it, or if it was not
+/// allocated by this store.
+pub struct ScriptedStore<P> {
+    script: RefCell<P>,
+    //  The live blocks of memory, by address.
+    blocks: RefCell<BTreeMap<usize, Block>>,
+    //  The number of blocks of memory resized in place.
+    in_place: Cell<usize>,
+}
+
+impl<P> ScriptedStore<P> {
+    /// Creates a new instance, following `script`.
+    pub const fn new(script: P) -> Self {
+        let script = RefCell::new(script);
+        let blocks = RefCell::new(BTreeMap::new());
+        let in_place = Cell::new(0);
+
+        Self {
+            script,
+            blocks,
+            in_place,
+        }
+    }
+
+    /// Returns the number of live blocks of memory.
+    pub fn live_blocks(&self) -> usize {
+        self.blocks.borrow().len()
+    }
+
+    /// Returns the number of times a block of memory was grown, or shrunk, in place.
+    pub fn in_place_resizes(&self) -> usize {
+        self.in_place.get()
+    }
+}
+
+impl ScriptedStore<SeededScript> {
+    /// Creates a new instance, following a `SeededScript` created from `seed`.
+    pub fn from_seed(seed: u64) -> Self {
+        Self::new(SeededScript::new(seed))
+    }
+}
+
+impl<P> Drop for ScriptedStore<P> {
+    fn drop(&mut self) {
+        for block in self.blocks.get_mut().values() {
+            //  Safety:
+            //  -   `block.pointer` was allocated by `Global`, with `block.actual`, and not deallocated since.
+            unsafe { Allocator::deallocate(&Global, block.pointer, block.actual) };
+        }
+    }
+}
+
+impl<P> fmt::Debug for ScriptedStore<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScriptedStore")
+            .field("live_blocks", &self.live_blocks())
+            .finish_non_exhaustive()
+    }
+}
+
+unsafe impl<P> StoreDangling for ScriptedStore<P> {
+    type Handle = AllocatorHandle;
+
+    fn dangling(&self, alignment: Alignment) -> Result<Self::Handle, AllocError> {
+        StoreDangling::dangling(&Global, alignment)
+    }
+}
+
+//  Safety:
+//  -   Blocks of memory are allocated by `Global`, and only ever deallocated, resized, or moved on request.
+//  -   The contents of a block of memory are preserved, up to the smaller of the old and new sizes, when moved.
+unsafe impl<P> Store for ScriptedStore<P>
+where
+    P: Script,
+{
+    #[inline(always)]
+    unsafe fn resolve(&self, handle: Self::Handle) -> NonNull<u8> {
+        handle.into()
+    }
+
+    fn allocate(&self, layout: Layout) -> Result<(Self::Handle, usize), AllocError> {
+        let behavior = self.script.borrow_mut().next(ScriptedOperation::Allocate, layout);
+
+        if behavior.fail {
+            return Err(AllocError);
+        }
+
+        let size = layout.size().checked_add(behavior.extra).ok_or(AllocError)?;
+
+        let pointer = self.allocate_block(layout.align(), layout.size(), size)?;
+
+        Ok((pointer.into(), size))
+    }
+
+    unsafe fn deallocate(&self, handle: Self::Handle, layout: Layout) {
+        let block = self.take_block(handle, layout);
+
+        //  Safety:
+        //  -   `handle` was allocated by `Global`, with `block.actual`, and not deallocated since.
+        unsafe { Allocator::deallocate(&Global, handle.into(), block.actual) };
+    }
+
+    unsafe fn grow(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        let (handle, size, _) = unsafe { self.grow_reporting(handle, old_layout, new_layout)? };
+
+        Ok((handle, size))
+    }
+
+    unsafe fn grow_reporting(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        self.check_block(handle, old_layout);
+
+        let behavior = self.script.borrow_mut().next(ScriptedOperation::Grow, new_layout);
+
+        if behavior.fail {
+            return Err(AllocError);
+        }
+
+        let size = new_layout.size().checked_add(behavior.extra).ok_or(AllocError)?;
+
+        //  Safety:
+        //  -   `handle` is a live block of memory, as checked above.
+        //  -   `old_layout.size()` bytes of the block are preserved, as `old_layout.size() <= size`.
+        unsafe { self.resize_block(handle, old_layout.size(), new_layout, size, behavior.relocate) }
+    }
+
+    unsafe fn shrink(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        let (handle, size, _) = unsafe { self.shrink_reporting(handle, old_layout, new_layout)? };
+
+        Ok((handle, size))
+    }
+
+    unsafe fn shrink_reporting(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        self.check_block(handle, old_layout);
+
+        let behavior = self.script.borrow_mut().next(ScriptedOperation::Shrink, new_layout);
+
+        if behavior.fail {
+            return Err(AllocError);
+        }
+
+        let size = cmp::min(new_layout.size().saturating_add(behavior.extra), old_layout.size());
+
+        //  Safety:
+        //  -   `handle` is a live block of memory, as checked above.
+        //  -   `new_layout.size()` bytes of the block are preserved, as `new_layout.size() <= size`.
+        unsafe { self.resize_block(handle, new_layout.size(), new_layout, size, behavior.relocate) }
+    }
+}
+
+unsafe impl<P> StoreSingle for ScriptedStore<P>
+where
+    P: Script,
+{
+    #[inline(always)]
+    unsafe fn resolve(&self, handle: Self::Handle) -> NonNull<u8> {
+        handle.into()
+    }
+
+    #[inline(always)]
+    unsafe fn resolve_mut(&mut self, handle: Self::Handle) -> NonNull<u8> {
+        handle.into()
+    }
+
+    fn allocate(&mut self, layout: Layout) -> Result<(Self::Handle, usize), AllocError> {
+        Store::allocate(self, layout)
+    }
+
+    unsafe fn deallocate(&mut self, handle: Self::Handle, layout: Layout) {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { Store::deallocate(self, handle, layout) }
+    }
+
+    unsafe fn grow(
+        &mut self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { Store::grow(self, handle, old_layout, new_layout) }
+    }
+
+    unsafe fn grow_reporting(
+        &mut self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { Store::grow_reporting(self, handle, old_layout, new_layout) }
+    }
+
+    unsafe fn shrink(
+        &mut self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { Store::shrink(self, handle, old_layout, new_layout) }
+    }
+
+    unsafe fn shrink_reporting(
+        &mut self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { Store::shrink_reporting(self, handle, old_layout, new_layout) }
+    }
+}
+
+crate::store_markers! {
+    //  Safety:
+    //  -   Blocks of memory are allocated by `Global`, and thus never move unless grown or shrunk.
+    unsafe impl[P] StorePinning for ScriptedStore<P>;
+}
+
+//
+//  Implementation
+//
+
+//  A live block of memory.
+#[derive(Clone, Copy, Debug)]
+struct Block {
+    //  The pointer actually allocated from `Global`.
+    pointer: NonNull<u8>,
+    //  The layout actually allocated from `Global`.
+    actual: Layout,
+    //  The alignment requested.
+    align: usize,
+    //  The sizes requested, and returned: any layout whose size is within fits.
+    sizes: (usize, usize),
+}
+
+impl<P> ScriptedStore<P> {
+    //  Allocates a block of `size` bytes, recording it as fitting layouts of `requested` to `size` bytes.
+    fn allocate_block(&self, align: usize, requested: usize, size: usize) -> Result<NonNull<u8>, AllocError> {
+        //  The block is over-allocated, unbeknownst to the caller, so that it may later grow in place. Allocating at
+        //  least 1 byte also ensures that each live block has a distinct address.
+        let actual = size
+            .checked_mul(2)
+            .and_then(|size| size.checked_add(64))
+            .ok_or(AllocError)?;
+        let actual = Layout::from_size_align(actual, align).map_err(|_| AllocError)?;
+
+        let pointer = Allocator::allocate(&Global, actual)?.as_non_null_ptr();
+
+        let block = Block {
+            pointer,
+            actual,
+            align,
+            sizes: (requested, size),
+        };
+
+        self.blocks.borrow_mut().insert(pointer.as_ptr().addr(), block);
+
+        Ok(pointer)
+    }
+
+    //  Resizes the block associated to `handle` to `size` bytes, fitting `layout`, preserving its first `preserved`
+    //  bytes, and returns whether it was relocated.
+    //
+    //  #   Safety
+    //
+    //  -   `handle` must be associated to a live block of memory.
+    //  -   `preserved` must be less than or equal to both the current size of the block, and `size`.
+    unsafe fn resize_block(
+        &self,
+        handle: AllocatorHandle,
+        preserved: usize,
+        layout: Layout,
+        size: usize,
+        relocate: bool,
+    ) -> Result<(AllocatorHandle, usize, bool), AllocError> {
+        let pointer: NonNull<u8> = handle.into();
+        let address = pointer.as_ptr().addr();
+
+        let block = self.blocks.borrow()[&address];
+
+        //  In place, whenever the block of memory actually allocated has room.
+        if !relocate && size <= block.actual.size() && layout.align() <= block.actual.align() {
+            let block = Block {
+                align: layout.align(),
+                sizes: (layout.size(), size),
+                ..block
+            };
+
+            self.blocks.borrow_mut().insert(address, block);
+            self.in_place.set(self.in_place.get() + 1);
+
+            return Ok((handle, size, false));
+        }
+
+        let new_pointer = self.allocate_block(layout.align(), layout.size(), size)?;
+
+        //  Safety:
+        //  -   `pointer` is valid for reads of `preserved` bytes, as per pre-conditions.
+        //  -   `new_pointer` is valid for writes of `preserved` bytes, as `preserved <= size`.
+        //  -   The two blocks of memory are distinct, as both are live.
+        unsafe { ptr::copy_nonoverlapping(pointer.as_ptr(), new_pointer.as_ptr(), preserved) };
+
+        self.blocks.borrow_mut().remove(&address);
+
+        //  Safety:
+        //  -   `pointer` was allocated by `Global`, with `block.actual`, and not deallocated since.
+        unsafe { Allocator::deallocate(&Global, pointer, block.actual) };
+
+        Ok((new_pointer.into(), size, true))
+    }
+
+    //  Checks that `handle` is associated to a live block of memory, which `layout` fits.
+    #[track_caller]
+    fn check_block(&self, handle: AllocatorHandle, layout: Layout) -> Block {
+        let pointer: NonNull<u8> = handle.into();
+
+        let blocks = self.blocks.borrow();
+
+        let Some(block) = blocks.get(&pointer.as_ptr().addr()) else {
+            panic!("ScriptedStore: {pointer:?} is not a live block of memory");
+        };
+
+        assert!(
+            layout.align() == block.align && block.sizes.0 <= layout.size() && layout.size() <= block.sizes.1,
+            "ScriptedStore: {layout:?} does not fit {block:?}"
+        );
+
+        *block
+    }
+
+    //  Checks that `handle` is associated to a live block of memory, which `layout` fits, and forgets it.
+    #[track_caller]
+    fn take_block(&self, handle: AllocatorHandle, layout: Layout) -> Block {
+        let block = self.check_block(handle, layout);
+
+        let pointer: NonNull<u8> = handle.into();
+
+        self.blocks.borrow_mut().remove(&pointer.as_ptr().addr());
+
+        block
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scripted() {
+        let mut calls = 0;
+
+        let store = ScriptedStore::new(|operation, _| {
+            calls += 1;
+
+            ScriptedBehavior {
+                fail: calls == 1,
+                extra: 8,
+                relocate: operation == ScriptedOperation::Grow,
+            }
+        });
+
+        let layout = Layout::new::<[u32; 2]>();
+
+        assert_eq!(Err(AllocError), Store::allocate(&store, layout));
+
+        let (handle, size) = Store::allocate(&store, layout).unwrap();
+
+        assert_eq!(16, size);
+        assert_eq!(1, store.live_blocks());
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        //  -   The block of memory fits `[u32; 2]`.
+        unsafe { Store::resolve(&store, handle).cast::<[u32; 2]>().write([1, 2]) };
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, with `layout`, and is still valid.
+        let grown = unsafe { Store::grow_reporting(&store, handle, layout, Layout::new::<[u32; 4]>()) };
+        let (grown, size, relocated) = grown.unwrap();
+
+        assert!(relocated);
+        assert_ne!(handle, grown);
+        assert_eq!(24, size);
+        assert_eq!(1, store.live_blocks());
+
+        //  Safety:
+        //  -   `grown` was allocated by `store`, and is still valid.
+        //  -   The block of memory fits `[u32; 2]`, and its first 8 bytes were preserved.
+        let preserved = unsafe { Store::resolve(&store, grown).cast::<[u32; 2]>().read() };
+
+        assert_eq!([1, 2], preserved);
+
+        //  Safety:
+        //  -   `grown` was allocated by `store`, and is still valid.
+        //  -   Any size in between the requested and returned sizes fits.
+        let shrunk = unsafe { Store::shrink_reporting(&store, grown, Layout::from_size_align(20, 4).unwrap(), layout) };
+
+        assert_eq!(Ok((grown, 16, false)), shrunk);
+        assert_eq!(1, store.in_place_resizes());
+
+        //  Safety:
+        //  -   `grown` was allocated by `store`, and is still valid, as it was shrunk in place.
+        //  -   Any size in between the requested and returned sizes fits.
+        unsafe { Store::deallocate(&store, grown, Layout::from_size_align(12, 4).unwrap()) };
+
+        assert_eq!(0, store.live_blocks());
+    }
+
+    #[test]
+    fn seeded() {
+        let (first, second) = (ScriptedStore::from_seed(42), ScriptedStore::from_seed(42));
+
+        let layout = Layout::new::<u64>();
+
+        for _ in 0..16 {
+            let (first, second) = (Store::allocate(&first, layout), Store::allocate(&second, layout));
+
+            assert_eq!(first.is_ok(), second.is_ok());
+            assert_eq!(first.map(|(_, size)| size), second.map(|(_, size)| size));
+        }
+    }
+} // mod tests
@@ -0,0 +1,839 @@
+//! A "bump allocator" Store keeping its bookkeeping within its own region of memory.
+//!
+//! A store for persistent single-blob designs, such as a memory-mapped file holding both the data and the bookkeeping
+//! of the store. The region starts with a versioned header, followed by the blocks of memory handed out, whose handles
+//! are offsets from the start of the region. Since nothing lives outside the region, it may be detached, moved or
+//! mapped at a different address, and attached again, with all its handles remaining valid.
+//!
+//! `SelfHostedStore::format` initializes a fresh header, while `SelfHostedStore::attach` validates and re-opens an
+//! existing one. The root handle, stored in the header, lets the application find its data again once re-attached:
+//!
+//! ```
+//! #![feature(allocator_api)]
+//!
+//! use core::{alloc::Layout, mem, ptr::NonNull};
+//!
+//! use storage::{interface::Store, store::SelfHostedStore};
+//!
+//! let mut block = [0u64; 32];
+//! let region = NonNull::slice_from_raw_parts(NonNull::from(&mut block).cast(), mem::size_of_val(&block));
+//!
+//! //  Safety:
+//! //  -   `region` is only accessed through this store, and outlives it.
+//! let store = unsafe { SelfHostedStore::<u32>::format(region) }.unwrap();
+//!
+//! let (handle, _) = store.allocate(Layout::new::<u64>()).unwrap();
+//!
+//! //  Safety:
+//! //  -   `handle` was allocated by `store`, for a `u64`.
+//! unsafe { store.resolve(handle).cast::<u64>().write(42) };
+//!
+//! store.set_root(Some(handle));
+//!
+//! drop(store);
+//!
+//! //  Safety:
+//! //  -   `region` is initialized, only accessed through this store, and outlives it.
+//! let store = unsafe { SelfHostedStore::<u32>::attach(region) }.unwrap();
+//!
+//! let handle = store.root().unwrap();
+//!
+//! //  Safety:
+//! //  -   `handle` was allocated by a store attached to the same region, for a `u64`.
+//! assert_eq!(42, unsafe { store.resolve(handle).cast::<u64>().read() });
+//! ```
+//!
+//! The header is written in native endianness: a region formatted on a machine of different endianness is rejected as
+//! an unsupported version.
+
+use core::{
+    alloc::{AllocError, Layout},
+    cmp, fmt,
+    marker::PhantomData,
+    mem,
+    ptr::{Alignment, NonNull},
+};
+
+use crate::{
+    interface::{
+        Store, StoreDangling, StoreIntrospect, StoreLimits, StoreOffsetHandle, StorePersist, StoreRegion, StoreSharing,
+        StoreSingle, StoreValidate,
+    },
+    store::{BumpCore, BumpError, BumpMemory, NoRounding, RoundingPolicy},
+};
+
+#[cfg(debug_assertions)]
+use crate::store::BumpDump;
+
+/// A store keeping its bookkeeping at the start of the region of memory it manages.
+///
+/// Generic parameters:
+///
+/// -   `H` is the handle type, the offset of a block of memory from the start of the region.
+/// -   `R` is the rounding policy, applied to the size of each allocation.
+///
+/// All instances attached to the same region are fungible.
+pub struct SelfHostedStore<H, R = NoRounding> {
+    region: NonNull<[u8]>,
+    _marker: PhantomData<fn(H) -> (H, R)>,
+}
+
+/// The reasons for which a region may not be formatted, or attached.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SelfHostedError {
+    /// The region is too small to hold the header.
+    TooSmall,
+    /// The region is insufficiently aligned, either for the header, or for the alignment recorded in the header.
+    Misaligned,
+    /// The region does not start with a header, as the magic bytes do not match.
+    NotFormatted,
+    /// The header was written by an unsupported version, or on a machine of different endianness.
+    UnsupportedVersion(u32),
+    /// The region is smaller than the memory handed out, according to the header.
+    Truncated,
+    /// The header is inconsistent.
+    Corrupted,
+}
+
+impl fmt::Display for SelfHostedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            Self::TooSmall => f.write_str("region too small for the header"),
+            Self::Misaligned => f.write_str("region insufficiently aligned"),
+            Self::NotFormatted => f.write_str("region not formatted"),
+            Self::UnsupportedVersion(version) => write!(f, "unsupported header version {version}"),
+            Self::Truncated => f.write_str("region truncated"),
+            Self::Corrupted => f.write_str("header corrupted"),
+        }
+    }
+}
+
+impl<H, R> SelfHostedStore<H, R> {
+    /// The version of the header written by `format`, and accepted by `attach`.
+    pub const VERSION: u32 = 1;
+
+    /// The size of the header, at the start of the region.
+    pub const HEADER_SIZE: usize = mem::size_of::<Header>();
+
+    /// Formats `region`, writing a fresh header at its start, and attaches to it.
+    ///
+    /// Any content of `region` is discarded: all handles previously allocated within it are invalidated.
+    ///
+    /// The blocks of memory may be aligned up to the alignment of the address of `region`, capped to 4096 bytes, and
+    /// the region must remain aligned as such whenever attached again.
+    ///
+    /// #   Safety
+    ///
+    /// -   `region` must be valid for reads and writes for as long as any instance attached to it is used.
+    /// -   `region` must only be accessed through the instances attached to it, or through the blocks of memory they
+    ///     hand out, for as long as any of them is used.
+    pub unsafe fn format(region: NonNull<[u8]>) -> Result<Self, SelfHostedError> {
+        Self::check_geometry(region)?;
+
+        let base = region.as_mut_ptr().addr();
+        let alignment = cmp::min(1usize << base.trailing_zeros(), MAX_ALIGNMENT);
+
+        let header = Header {
+            magic: MAGIC,
+            version: Self::VERSION,
+            alignment: alignment as u32,
+            watermark: Self::HEADER_SIZE as u64,
+            padding: 0,
+            root: 0,
+        };
+
+        //  Safety:
+        //  -   `region` is valid for writes, as per pre-conditions.
+        //  -   `region` is large enough, and sufficiently aligned, for a `Header`, as checked above.
+        unsafe { region.cast::<Header>().write(header) };
+
+        Ok(Self::new(region))
+    }
+
+    /// Attaches to `region`, previously formatted, after validating its header.
+    ///
+    /// All handles allocated within `region` by the instances previously attached to it remain valid, even if `region`
+    /// has since moved, as long as its content was preserved.
+    ///
+    /// #   Safety
+    ///
+    /// -   `region` must be initialized.
+    /// -   As per `format`.
+    pub unsafe fn attach(region: NonNull<[u8]>) -> Result<Self, SelfHostedError> {
+        Self::check_geometry(region)?;
+
+        //  Safety:
+        //  -   `region` is valid for reads, and initialized, as per pre-conditions.
+        //  -   `region` is large enough, and sufficiently aligned, for a `Header`, as checked above.
+        let header = unsafe { region.cast::<Header>().read() };
+
+        if header.magic != MAGIC {
+            return Err(SelfHostedError::NotFormatted);
+        }
+
+        if header.version != Self::VERSION {
+            return Err(SelfHostedError::UnsupportedVersion(header.version));
+        }
+
+        let alignment = header.alignment as usize;
+
+        if !alignment.is_power_of_two() || alignment < mem::align_of::<Header>() || alignment > MAX_ALIGNMENT {
+            return Err(SelfHostedError::Corrupted);
+        }
+
+        if region.as_mut_ptr().addr() & (alignment - 1) != 0 {
+            return Err(SelfHostedError::Misaligned);
+        }
+
+        let (watermark, padding, root) = (header.watermark, header.padding, header.root);
+
+        if watermark < Self::HEADER_SIZE as u64 || padding > watermark {
+            return Err(SelfHostedError::Corrupted);
+        }
+
+        if watermark > region.len() as u64 {
+            return Err(SelfHostedError::Truncated);
+        }
+
+        if root != 0 && (root < Self::HEADER_SIZE as u64 || root > watermark) {
+            return Err(SelfHostedError::Corrupted);
+        }
+
+        Ok(Self::new(region))
+    }
+
+    /// Returns the number of bytes consumed so far, including the header, alignment padding, and rounding.
+    pub fn used(&self) -> usize {
+        self.header().watermark as usize
+    }
+
+    /// Returns the number of bytes still available.
+    pub fn remaining(&self) -> usize {
+        self.region.len() - self.used()
+    }
+
+    /// Returns the number of bytes wasted on alignment padding so far.
+    pub fn padding(&self) -> usize {
+        self.header().padding as usize
+    }
+}
+
+impl<H, R> SelfHostedStore<H, R>
+where
+    H: Copy + TryFrom<usize> + TryInto<usize>,
+    R: RoundingPolicy,
+{
+    /// Returns the root handle, as last set by `set_root`, if any.
+    pub fn root(&self) -> Option<H> {
+        let root = self.header().root;
+
+        if root == 0 {
+            return None;
+        }
+
+        BumpCore::from_offset(root as usize).ok()
+    }
+
+    /// Sets the root handle, stored in the header, so that it can be retrieved once attached again.
+    ///
+    /// The root handle is not otherwise used by the store: it is up to the caller to ensure it remains valid.
+    pub fn set_root(&self, root: Option<H>) {
+        let mut header = self.header();
+
+        header.root = root.map_or(0, |root| BumpCore::into_offset(root) as u64);
+
+        self.set_header(header);
+    }
+
+    /// Attempts to allocate a block of memory, as per `Store::allocate`, reporting why allocation failed, if it did.
+    pub fn try_allocate(&self, layout: Layout) -> Result<(H, usize), BumpError> {
+        self.with_core(|core, memory| core.allocate::<H, R>(memory, layout))
+    }
+}
+
+unsafe impl<H, R> StoreDangling for SelfHostedStore<H, R>
+where
+    H: Copy + TryFrom<usize>,
+{
+    type Handle = H;
+
+    fn dangling(&self, alignment: Alignment) -> Result<Self::Handle, AllocError> {
+        self.bump_memory().dangling(alignment).map_err(AllocError::from)
+    }
+}
+
+unsafe impl<H, R> Store for SelfHostedStore<H, R>
+where
+    H: Copy + TryFrom<usize> + TryInto<usize>,
+    R: RoundingPolicy,
+{
+    fn allocate(&self, layout: Layout) -> Result<(Self::Handle, usize), AllocError> {
+        Ok(self.try_allocate(layout)?)
+    }
+
+    #[inline(always)]
+    unsafe fn deallocate(&self, _handle: Self::Handle, _layout: Layout) {}
+
+    #[inline(always)]
+    unsafe fn resolve(&self, handle: Self::Handle) -> NonNull<u8> {
+        //  Safety:
+        //  -   `handle` was allocated within this region, or created dangling, as per pre-conditions.
+        unsafe { self.bump_memory().resolve(handle) }
+    }
+
+    unsafe fn grow(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        let result =
+            self.with_core(|core, memory| unsafe { core.grow::<H, R>(memory, handle, old_layout, new_layout) });
+
+        Ok(result?)
+    }
+
+    unsafe fn shrink(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        let result =
+            self.with_core(|core, memory| unsafe { core.shrink::<H, R>(memory, handle, old_layout, new_layout) });
+
+        Ok(result?)
+    }
+
+    unsafe fn grow_reporting(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        let result = self
+            .with_core(|core, memory| unsafe { core.grow_reporting::<H, R>(memory, handle, old_layout, new_layout) });
+
+        Ok(result?)
+    }
+
+    unsafe fn shrink_reporting(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        let result = self
+            .with_core(|core, memory| unsafe { core.shrink_reporting::<H, R>(memory, handle, old_layout, new_layout) });
+
+        Ok(result?)
+    }
+}
+
+//  As for `StackBumpStore`, allocating does not reset the watermark, since other instances may be attached to the same
+//  region.
+unsafe impl<H, R> StoreSingle for SelfHostedStore<H, R>
+where
+    H: Copy + TryFrom<usize> + TryInto<usize>,
+    R: RoundingPolicy,
+{
+    #[inline(always)]
+    unsafe fn resolve(&self, handle: Self::Handle) -> NonNull<u8> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { <Self as Store>::resolve(self, handle) }
+    }
+
+    #[inline(always)]
+    unsafe fn resolve_mut(&mut self, handle: Self::Handle) -> NonNull<u8> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { <Self as Store>::resolve(self, handle) }
+    }
+
+    fn allocate(&mut self, layout: Layout) -> Result<(Self::Handle, usize), AllocError> {
+        <Self as Store>::allocate(self, layout)
+    }
+
+    #[inline(always)]
+    unsafe fn deallocate(&mut self, _handle: Self::Handle, _layout: Layout) {}
+
+    unsafe fn grow(
+        &mut self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { <Self as Store>::grow(self, handle, old_layout, new_layout) }
+    }
+
+    #[inline(always)]
+    unsafe fn shrink(
+        &mut self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { <Self as Store>::shrink(self, handle, old_layout, new_layout) }
+    }
+
+    unsafe fn grow_reporting(
+        &mut self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { <Self as Store>::grow_reporting(self, handle, old_layout, new_layout) }
+    }
+
+    #[inline(always)]
+    unsafe fn shrink_reporting(
+        &mut self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { <Self as Store>::shrink_reporting(self, handle, old_layout, new_layout) }
+    }
+}
+
+crate::store_markers! {
+    //  Safety:
+    //  -   `self.resolve(handle)` always returns the same address, as the region cannot move while attached.
+    unsafe impl[H, R] StorePinning for SelfHostedStore<H, R> where [H: Copy + TryFrom<usize> + TryInto<usize>];
+}
+
+//  Safety:
+//  -   `self.region` cannot move while attached, as per the pre-conditions of `format` and `attach`.
+//  -   All blocks of memory are allocated from within `self.region`.
+unsafe impl<H, R> StoreRegion for SelfHostedStore<H, R>
+where
+    H: Copy + TryFrom<usize> + TryInto<usize>,
+{
+    fn region(&self) -> NonNull<[u8]> {
+        self.region
+    }
+}
+
+//  Safety:
+//  -   All instances attached to the same region are fungible, as the bookkeeping lives within the region.
+unsafe impl<H, R> StoreSharing for SelfHostedStore<H, R>
+where
+    H: Copy + TryFrom<usize> + TryInto<usize>,
+{
+    type SharingError = !;
+
+    type SharingToken = usize;
+
+    fn is_sharing_with(&self, other: &Self) -> bool {
+        self.region.as_mut_ptr() == other.region.as_mut_ptr()
+    }
+
+    fn sharing_token(&self) -> Self::SharingToken {
+        self.region.as_mut_ptr().addr()
+    }
+
+    fn share(&self) -> Result<Self, Self::SharingError>
+    where
+        Self: Sized,
+    {
+        Ok(Self::new(self.region))
+    }
+}
+
+//  Safety:
+//  -   Handles are offsets from the start of the region, hence advancing the offset advances the resolved pointer.
+unsafe impl<H, R> StoreOffsetHandle for SelfHostedStore<H, R>
+where
+    H: Copy + TryFrom<usize> + TryInto<usize>,
+{
+    #[inline(always)]
+    unsafe fn offset(handle: Self::Handle, bytes: usize) -> Self::Handle {
+        BumpCore::advance(handle, bytes)
+    }
+}
+
+impl<H, R> StoreValidate for SelfHostedStore<H, R>
+where
+    H: Copy + TryFrom<usize> + TryInto<usize>,
+{
+    fn is_valid(&self, handle: Self::Handle) -> Option<bool> {
+        let Some(offset) = BumpCore::try_into_offset(handle) else {
+            return Some(false);
+        };
+
+        //  The header is never handed out, and neither is the memory past the watermark.
+        if offset > self.used() || (offset < Self::HEADER_SIZE && offset != 0) {
+            return Some(false);
+        }
+
+        None
+    }
+}
+
+//  The region is the persistent medium itself, and the bookkeeping is saved into it after each operation, hence there
+//  is nothing to flush from the store's side. Writing the region back to its backing storage, for example via `msync`
+//  for a memory-mapped file, is up to its owner, who may wrap the store to do so in these hooks.
+impl<H, R> StorePersist for SelfHostedStore<H, R> where H: Copy + TryFrom<usize> {}
+
+impl<H, R> StoreIntrospect for SelfHostedStore<H, R>
+where
+    H: Copy + TryFrom<usize>,
+{
+    fn limits(&self) -> StoreLimits {
+        let limits = self.bump_memory().limits();
+
+        //  The header is never handed out.
+        StoreLimits {
+            max_size: limits.max_size.saturating_sub(Self::HEADER_SIZE),
+            ..limits
+        }
+    }
+}
+
+#[cfg(debug_assertions)]
+impl<H, R> SelfHostedStore<H, R> {
+    /// Returns a dump of the memory used so far, header included, for debugging purposes, see `BumpDump`.
+    ///
+    /// #   Safety
+    ///
+    /// -   The used memory must be initialized, as is the case if the region was initialized prior to `format`, and
+    ///     only values without padding bytes are written to it.
+    /// -   The used memory must not be written to for as long as the dump is alive.
+    pub unsafe fn dump(&self) -> BumpDump<'_> {
+        //  Safety:
+        //  -   The region is valid for reads for as long as `self` is used, as per the pre-conditions of `format` and
+        //      `attach`.
+        //  -   The used memory is initialized, and not written to, as per pre-conditions.
+        self.with_core(|core, memory| unsafe { core.dump(memory) })
+    }
+}
+
+impl<H, R> fmt::Debug for SelfHostedStore<H, R>
+where
+    R: RoundingPolicy,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        let header = self.header();
+
+        f.debug_struct("SelfHostedStore")
+            .field("version", &header.version)
+            .field("alignment", &header.alignment)
+            .field("watermark", &header.watermark)
+            .field("padding", &header.padding)
+            .field("root", &header.root)
+            .field("region", &self.region.len())
+            .field("rounding", &R::NAME)
+            .finish()
+    }
+}
+
+//
+//  Implementation
+//
+
+const MAGIC: [u8; 8] = *b"StorSelf";
+
+//  The greatest alignment of the blocks of memory, a common page size.
+const MAX_ALIGNMENT: usize = 4096;
+
+//  The header at the start of the region.
+//
+//  Fixed-size fields only, so that its layout does not depend on the platform, beyond endianness.
+#[derive(Clone, Copy)]
+#[repr(C)]
+struct Header {
+    magic: [u8; 8],
+    version: u32,
+    //  The alignment of the region when formatted, and thus the greatest alignment of any block of memory.
+    alignment: u32,
+    //  The bookkeeping of the `BumpCore`.
+    watermark: u64,
+    padding: u64,
+    //  The offset of the root handle, or 0 if none, as the header is never handed out.
+    root: u64,
+}
+
+impl<H, R> SelfHostedStore<H, R> {
+    //  Invariant: `region` starts with a valid header.
+    fn new(region: NonNull<[u8]>) -> Self {
+        let _marker = PhantomData;
+
+        Self { region, _marker }
+    }
+
+    fn check_geometry(region: NonNull<[u8]>) -> Result<(), SelfHostedError> {
+        if region.len() < Self::HEADER_SIZE {
+            return Err(SelfHostedError::TooSmall);
+        }
+
+        if region.as_mut_ptr().addr() & (mem::align_of::<Header>() - 1) != 0 {
+            return Err(SelfHostedError::Misaligned);
+        }
+
+        Ok(())
+    }
+
+    fn header(&self) -> Header {
+        //  Safety:
+        //  -   `self.region` starts with a valid header, as per invariant.
+        unsafe { self.region.cast::<Header>().read() }
+    }
+
+    fn set_header(&self, header: Header) {
+        //  Safety:
+        //  -   `self.region` starts with a valid header, as per invariant.
+        //  -   `self.region` is only accessed through the attached instances, none of which holds a reference to the
+        //      header, as per pre-conditions of `format` and `attach`.
+        unsafe { self.region.cast::<Header>().write(header) }
+    }
+
+    //  The region cannot move while attached, yet may move in between, hence the blocks of memory may not be aligned
+    //  beyond the alignment recorded in the header.
+    fn bump_memory(&self) -> BumpMemory {
+        let alignment = self.header().alignment as usize;
+
+        //  Safety:
+        //  -   `alignment` is a power of 2, as checked by `attach`, or computed by `format`.
+        let alignment = unsafe { Alignment::new_unchecked(alignment) };
+
+        BumpMemory::movable(self.region, alignment)
+    }
+
+    //  Calls `f` with the core resumed from the header, then saves the core back into the header.
+    fn with_core<T>(&self, f: impl FnOnce(&BumpCore, BumpMemory) -> T) -> T {
+        let mut header = self.header();
+
+        //  Safety:
+        //  -   No block of memory past the watermark is in use, as the watermark is saved after each operation.
+        let core = unsafe { BumpCore::from_raw_parts(header.watermark as usize, header.padding as usize) };
+
+        let result = f(&core, self.bump_memory());
+
+        header.watermark = core.used() as u64;
+        header.padding = core.padding() as u64;
+
+        self.set_header(header);
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::ptr;
+
+    use crate::collection::StoreVec;
+
+    use super::*;
+
+    type TestStore = SelfHostedStore<u16>;
+
+    type TestBlock = [u64; 48];
+
+    //  Returns a region of `length` bytes within `block`, starting at the first address aligned on 16 bytes, plus
+    //  `shift` bytes.
+    fn region_of(block: &mut TestBlock, shift: usize, length: usize) -> NonNull<[u8]> {
+        let base = NonNull::from(block).cast::<u8>().as_ptr();
+        let start = base.align_offset(16) + shift;
+
+        assert!(start + length <= mem::size_of::<TestBlock>());
+
+        let start = NonNull::new(base.wrapping_add(start)).unwrap();
+
+        NonNull::slice_from_raw_parts(start, length)
+    }
+
+    #[test]
+    fn format_then_attach() {
+        let mut block: TestBlock = [0; 48];
+        let region = region_of(&mut block, 0, 256);
+
+        //  Safety:
+        //  -   `region` is only accessed through the store, and outlives it.
+        let store = unsafe { TestStore::format(region) }.unwrap();
+
+        assert_eq!(TestStore::HEADER_SIZE, store.used());
+        assert_eq!(None, store.root());
+
+        let mut vec = StoreVec::with_capacity_in(4, store.share().into_ok());
+        vec.extend([1u32, 2, 3]);
+
+        let (handle, _) = Store::allocate(&store, Layout::new::<u64>()).unwrap();
+
+        assert!(handle as usize >= TestStore::HEADER_SIZE);
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, for a `u64`.
+        unsafe { Store::resolve(&store, handle).cast::<u64>().write(42) };
+
+        store.set_root(Some(handle));
+
+        let used = store.used();
+
+        drop((vec, store));
+
+        //  Safety:
+        //  -   `region` is initialized, only accessed through the store, and outlives it.
+        let store = unsafe { TestStore::attach(region) }.unwrap();
+
+        assert_eq!(used, store.used());
+        assert_eq!(Some(handle), store.root());
+
+        //  Safety:
+        //  -   `handle` was allocated within this region, for a `u64`.
+        assert_eq!(42, unsafe { Store::resolve(&store, handle).cast::<u64>().read() });
+
+        let (next, _) = Store::allocate(&store, Layout::new::<u64>()).unwrap();
+
+        assert!(next > handle);
+    }
+
+    #[test]
+    fn attach_validation() {
+        let mut block: TestBlock = [0; 48];
+        let region = region_of(&mut block, 0, 256);
+
+        let attach = |region: NonNull<[u8]>| {
+            //  Safety:
+            //  -   `region` is initialized, and outlives the store, which is dropped immediately.
+            unsafe { TestStore::attach(region) }.map(|_| ())
+        };
+
+        let prefix = |length: usize| NonNull::slice_from_raw_parts(region.cast::<u8>(), length);
+
+        assert_eq!(Err(SelfHostedError::NotFormatted), attach(region));
+        assert_eq!(Err(SelfHostedError::TooSmall), attach(prefix(8)));
+
+        //  Safety:
+        //  -   `region` is only accessed through the store, and outlives it.
+        let store = unsafe { TestStore::format(region) }.unwrap();
+
+        Store::allocate(&store, Layout::new::<[u64; 8]>()).unwrap();
+
+        drop(store);
+
+        assert_eq!(Ok(()), attach(region));
+        assert_eq!(Err(SelfHostedError::Truncated), attach(prefix(64)));
+
+        //  The version immediately follows the magic bytes.
+        let version = region.as_mut_ptr().wrapping_add(8).cast::<u32>();
+
+        //  Safety:
+        //  -   `version` points to the version of the header, within `region`.
+        unsafe { version.write(TestStore::VERSION + 1) };
+
+        let expected = SelfHostedError::UnsupportedVersion(TestStore::VERSION + 1);
+
+        assert_eq!(Err(expected), attach(region));
+    }
+
+    #[test]
+    fn relocated_region() {
+        let (mut first, mut second): (TestBlock, TestBlock) = ([0; 48], [0; 48]);
+        let (region, relocated) = (region_of(&mut first, 0, 256), region_of(&mut second, 0, 256));
+
+        assert_ne!(region.as_mut_ptr(), relocated.as_mut_ptr());
+
+        //  Safety:
+        //  -   `region` is only accessed through the store, and outlives it.
+        let store = unsafe { TestStore::format(region) }.unwrap();
+
+        //  A value, and a node referencing it by handle, as a persisted data-structure would.
+        let (value, _) = Store::allocate(&store, Layout::new::<u64>()).unwrap();
+        let (node, _) = Store::allocate(&store, Layout::new::<[u16; 2]>()).unwrap();
+
+        //  Safety:
+        //  -   `value` and `node` were allocated by `store`, for a `u64` and a `[u16; 2]` respectively.
+        unsafe {
+            Store::resolve(&store, value).cast::<u64>().write(42);
+            Store::resolve(&store, node).cast::<[u16; 2]>().write([value, 7]);
+        }
+
+        store.set_root(Some(node));
+
+        let used = store.used();
+
+        drop(store);
+
+        //  Safety:
+        //  -   `region` and `relocated` are both valid for 256 bytes, and do not overlap.
+        unsafe { ptr::copy_nonoverlapping(region.as_mut_ptr(), relocated.as_mut_ptr(), 256) };
+
+        //  Safety:
+        //  -   `relocated` is initialized, only accessed through the store, and outlives it.
+        let store = unsafe { TestStore::attach(relocated) }.unwrap();
+
+        assert_eq!(used, store.used());
+        assert_eq!(Some(node), store.root());
+
+        //  Safety:
+        //  -   `node` was allocated within the original region, for a `[u16; 2]`, and the region was copied as is.
+        let [handle, tag] = unsafe { Store::resolve(&store, node).cast::<[u16; 2]>().read() };
+
+        assert_eq!((value, 7), (handle, tag));
+
+        //  Safety:
+        //  -   `handle` was allocated within the original region, for a `u64`.
+        let pointer = unsafe { Store::resolve(&store, handle) };
+
+        assert!(
+            relocated.as_mut_ptr() < pointer.as_ptr() && pointer.as_ptr() < relocated.as_mut_ptr().wrapping_add(256)
+        );
+
+        //  Safety:
+        //  -   `pointer` points to the `u64` written prior to the copy.
+        assert_eq!(42, unsafe { pointer.cast::<u64>().read() });
+
+        let (next, _) = Store::allocate(&store, Layout::new::<u64>()).unwrap();
+
+        assert!(next > node);
+    }
+
+    #[test]
+    fn moved_region() {
+        let mut block: TestBlock = [0; 48];
+        let (region, misaligned) = (region_of(&mut block, 0, 128), region_of(&mut block, 8, 128));
+
+        //  Safety:
+        //  -   `region` is only accessed through the store, and outlives it.
+        let store = unsafe { TestStore::format(region) }.unwrap();
+
+        let aligned = Layout::from_size_align(16, 16).unwrap();
+        let over_aligned = Layout::from_size_align(16, 2 * MAX_ALIGNMENT).unwrap();
+
+        let (handle, _) = Store::allocate(&store, aligned).unwrap();
+
+        assert_eq!(Err(BumpError::Exhausted), store.try_allocate(over_aligned));
+
+        store.set_root(Some(handle));
+
+        drop(store);
+
+        //  Safety:
+        //  -   `region` and `misaligned` are both valid for 128 bytes.
+        unsafe { ptr::copy(region.as_mut_ptr(), misaligned.as_mut_ptr(), 128) };
+
+        //  Safety:
+        //  -   `misaligned` is initialized, and outlives the store, which is dropped immediately.
+        let result = unsafe { TestStore::attach(misaligned) }.map(|_| ());
+
+        assert_eq!(Err(SelfHostedError::Misaligned), result);
+    }
+} // mod tests
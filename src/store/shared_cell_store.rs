@@ -0,0 +1,334 @@
+//! A Store adapting a `StoreSingle` to the `&self`-based `Store` interface, on a single thread.
+//!
+//! The underlying store is kept in a `RefCell`, and each method of `Store` borrows it mutably for the duration of the
+//! call only. As no borrow outlives a call, and the underlying store never calls back into the adapter, borrowing
+//! never fails.
+//!
+//! A `StoreSingle` only guarantees a single outstanding block of memory, hence the adapter only allows a single live
+//! allocation at a time: further allocations fail until the live one is deallocated.
+
+use core::{
+    alloc::{AllocError, Layout},
+    cell::{Cell, RefCell},
+    fmt,
+    ptr::{Alignment, NonNull},
+};
+
+use crate::interface::{Growth, Store, StoreDangling, StoreSingle};
+
+/// A store adapting a `StoreSingle` to the `Store` interface, allowing a single live allocation at a time.
+///
+/// This is useful to use a `StoreSingle`, such as `InlineSingleStore`, with a collection requiring `Store`, yet only
+/// ever allocating a single block of memory, such as `ConcurrentVec`.
+pub struct SharedCellStore<S> {
+    store: RefCell<S>,
+    live: Cell<bool>,
+}
+
+impl<S> SharedCellStore<S> {
+    /// Creates a new instance, wrapping `store`.
+    pub const fn new(store: S) -> Self {
+        let store = RefCell::new(store);
+        let live = Cell::new(false);
+
+        Self { store, live }
+    }
+
+    /// Returns the underlying store.
+    pub fn into_inner(self) -> S {
+        self.store.into_inner()
+    }
+
+    /// Returns whether a block of memory is currently allocated.
+    pub fn is_allocated(&self) -> bool {
+        self.live.get()
+    }
+}
+
+impl<S> Default for SharedCellStore<S>
+where
+    S: Default,
+{
+    fn default() -> Self {
+        Self::new(S::default())
+    }
+}
+
+impl<S> fmt::Debug for SharedCellStore<S>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedCellStore")
+            .field("store", &self.store)
+            .field("live", &self.live)
+            .finish()
+    }
+}
+
+unsafe impl<S> StoreDangling for SharedCellStore<S>
+where
+    S: StoreDangling,
+{
+    type Handle = S::Handle;
+
+    fn dangling(&self, alignment: Alignment) -> Result<Self::Handle, AllocError> {
+        self.store.borrow().dangling(alignment)
+    }
+}
+
+//  Safety:
+//  -   At most a single block of memory is live at any time, which a `StoreSingle` supports.
+//  -   The pointers resolved are obtained from `StoreSingle::resolve_mut`, and may thus be dereferenced mutably.
+unsafe impl<S> Store for SharedCellStore<S>
+where
+    S: StoreSingle,
+{
+    const GROWTH: Growth = <S as StoreSingle>::GROWTH;
+
+    unsafe fn resolve(&self, handle: Self::Handle) -> NonNull<u8> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.store.borrow_mut().resolve_mut(handle) }
+    }
+
+    fn allocate(&self, layout: Layout) -> Result<(Self::Handle, usize), AllocError> {
+        self.occupy(|store| store.allocate(layout))
+    }
+
+    fn allocate_zeroed(&self, layout: Layout) -> Result<(Self::Handle, usize), AllocError> {
+        self.occupy(|store| store.allocate_zeroed(layout))
+    }
+
+    unsafe fn deallocate(&self, handle: Self::Handle, layout: Layout) {
+        debug_assert!(self.live.get());
+
+        self.live.set(false);
+
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.store.borrow_mut().deallocate(handle, layout) }
+    }
+
+    unsafe fn grow(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.store.borrow_mut().grow(handle, old_layout, new_layout) }
+    }
+
+    unsafe fn grow_zeroed(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.store.borrow_mut().grow_zeroed(handle, old_layout, new_layout) }
+    }
+
+    unsafe fn shrink(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.store.borrow_mut().shrink(handle, old_layout, new_layout) }
+    }
+
+    unsafe fn grow_reporting(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.store.borrow_mut().grow_reporting(handle, old_layout, new_layout) }
+    }
+
+    unsafe fn shrink_reporting(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.store.borrow_mut().shrink_reporting(handle, old_layout, new_layout) }
+    }
+}
+
+//  Safety:
+//  -   Each method delegates to the underlying store, with exclusive access, hence without any runtime borrow.
+unsafe impl<S> StoreSingle for SharedCellStore<S>
+where
+    S: StoreSingle,
+{
+    const GROWTH: Growth = <S as StoreSingle>::GROWTH;
+
+    unsafe fn resolve(&self, handle: Self::Handle) -> NonNull<u8> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.store.borrow().resolve(handle) }
+    }
+
+    unsafe fn resolve_mut(&mut self, handle: Self::Handle) -> NonNull<u8> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.store.get_mut().resolve_mut(handle) }
+    }
+
+    fn allocate(&mut self, layout: Layout) -> Result<(Self::Handle, usize), AllocError> {
+        Store::allocate(self, layout)
+    }
+
+    fn allocate_zeroed(&mut self, layout: Layout) -> Result<(Self::Handle, usize), AllocError> {
+        Store::allocate_zeroed(self, layout)
+    }
+
+    unsafe fn deallocate(&mut self, handle: Self::Handle, layout: Layout) {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { Store::deallocate(self, handle, layout) }
+    }
+
+    unsafe fn grow(
+        &mut self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.store.get_mut().grow(handle, old_layout, new_layout) }
+    }
+
+    unsafe fn grow_zeroed(
+        &mut self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.store.get_mut().grow_zeroed(handle, old_layout, new_layout) }
+    }
+
+    unsafe fn shrink(
+        &mut self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.store.get_mut().shrink(handle, old_layout, new_layout) }
+    }
+
+    unsafe fn grow_reporting(
+        &mut self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.store.get_mut().grow_reporting(handle, old_layout, new_layout) }
+    }
+
+    unsafe fn shrink_reporting(
+        &mut self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.store.get_mut().shrink_reporting(handle, old_layout, new_layout) }
+    }
+}
+
+//
+//  Implementation
+//
+
+impl<S> SharedCellStore<S>
+where
+    S: StoreSingle,
+{
+    //  Allocates a block of memory via `allocate`, unless a block of memory is already live.
+    fn occupy<F>(&self, allocate: F) -> Result<(S::Handle, usize), AllocError>
+    where
+        F: FnOnce(&mut S) -> Result<(S::Handle, usize), AllocError>,
+    {
+        if self.live.get() {
+            return Err(AllocError);
+        }
+
+        let result = allocate(&mut self.store.borrow_mut());
+
+        self.live.set(result.is_ok());
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{collection::ConcurrentVec, store::InlineSingleStore};
+
+    use super::*;
+
+    type Inline = SharedCellStore<InlineSingleStore<[u32; 4]>>;
+
+    #[test]
+    fn single_allocation() {
+        let store = Inline::default();
+
+        let layout = Layout::new::<[u32; 2]>();
+
+        let (handle, _) = Store::allocate(&store, layout).unwrap();
+
+        assert!(store.is_allocated());
+        assert_eq!(Err(AllocError), Store::allocate(&store, layout));
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, with `layout`, and is still valid.
+        let (handle, size) = unsafe { Store::grow(&store, handle, layout, Layout::new::<[u32; 4]>()) }.unwrap();
+
+        assert_eq!(16, size);
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        let pointer = unsafe { Store::resolve(&store, handle) };
+
+        //  Safety:
+        //  -   `pointer` points to a writeable block of memory of 16 bytes, suitably aligned for `u32`.
+        unsafe { pointer.cast::<u32>().write(7) };
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, with a layout of 16 bytes, and is still valid.
+        unsafe { Store::deallocate(&store, handle, Layout::new::<[u32; 4]>()) };
+
+        assert!(!store.is_allocated());
+        assert!(Store::allocate(&store, layout).is_ok());
+    }
+
+    #[test]
+    fn concurrent_vec() {
+        let vec = ConcurrentVec::<u32, Inline>::with_store(4, Inline::default());
+
+        vec.push(1).unwrap();
+        vec.push(2).unwrap();
+
+        assert_eq!([1, 2], vec.as_slice());
+    }
+} // mod tests
@@ -0,0 +1,192 @@
+//! Reference-counted Stores.
+//!
+//! Stores which own their underlying store behind a reference-counted pointer, so that multiple collections may share
+//! a single store, regardless of whether the underlying store implements `StoreSharing`.
+//!
+//! _Note: the store traits cannot be implemented for `Box<S>`, `Rc<S>`, or `Arc<S>` directly, as those would conflict
+//! with the blanket implementations for `Allocator`._
+
+use core::fmt;
+
+use std_alloc::{rc::Rc, sync::Arc};
+
+use crate::interface::{StoreSharing, StoreStable};
+
+/// A store sharing its underlying store through a `Rc`.
+///
+/// All instances created by `share` or `clone` are fungible.
+pub struct RcStore<S>(Rc<S>);
+
+impl<S> RcStore<S> {
+    /// Creates a new instance, owning `store`.
+    pub fn new(store: S) -> Self {
+        Self(Rc::new(store))
+    }
+
+    /// Returns a reference to the underlying store.
+    pub fn get(&self) -> &S {
+        &self.0
+    }
+}
+
+crate::store::delegate_store! {
+    //  Safety:
+    //  -   `self` forwards every call to `S`.
+    unsafe impl[S] Store for RcStore<S> => S;
+}
+
+crate::store_markers! {
+    //  Safety:
+    //  -   `S` is stable, and never moves, even when `self` is moved.
+    unsafe impl[S] StorePinning for RcStore<S> where [S: StoreStable];
+}
+
+//  Safety:
+//  -   All instances sharing the same `Rc` are fungible, as they refer to the same instance of `S`.
+unsafe impl<S> StoreSharing for RcStore<S>
+where
+    S: StoreStable,
+{
+    type SharingError = !;
+
+    type SharingToken = usize;
+
+    fn is_sharing_with(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+
+    fn sharing_token(&self) -> Self::SharingToken {
+        Rc::as_ptr(&self.0).addr()
+    }
+
+    fn share(&self) -> Result<Self, Self::SharingError> {
+        Ok(self.clone())
+    }
+}
+
+impl<S> Clone for RcStore<S> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<S> fmt::Debug for RcStore<S>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_tuple("RcStore").field(&*self.0).finish()
+    }
+}
+
+/// A store sharing its underlying store through an `Arc`.
+///
+/// All instances created by `share` or `clone` are fungible.
+pub struct ArcStore<S>(Arc<S>);
+
+impl<S> ArcStore<S> {
+    /// Creates a new instance, owning `store`.
+    pub fn new(store: S) -> Self {
+        Self(Arc::new(store))
+    }
+
+    /// Returns a reference to the underlying store.
+    pub fn get(&self) -> &S {
+        &self.0
+    }
+}
+
+crate::store::delegate_store! {
+    //  Safety:
+    //  -   `self` forwards every call to `S`.
+    unsafe impl[S] Store for ArcStore<S> => S;
+}
+
+crate::store_markers! {
+    //  Safety:
+    //  -   `S` is stable, and never moves, even when `self` is moved.
+    unsafe impl[S] StorePinning for ArcStore<S> where [S: StoreStable];
+}
+
+//  Safety:
+//  -   All instances sharing the same `Arc` are fungible, as they refer to the same instance of `S`.
+unsafe impl<S> StoreSharing for ArcStore<S>
+where
+    S: StoreStable,
+{
+    type SharingError = !;
+
+    type SharingToken = usize;
+
+    fn is_sharing_with(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+
+    fn sharing_token(&self) -> Self::SharingToken {
+        Arc::as_ptr(&self.0).addr()
+    }
+
+    fn share(&self) -> Result<Self, Self::SharingError> {
+        Ok(self.clone())
+    }
+}
+
+impl<S> Clone for ArcStore<S> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<S> fmt::Debug for ArcStore<S>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_tuple("ArcStore").field(&*self.0).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{collection::LinkedList, store::InlineBumpStore};
+
+    use super::*;
+
+    //  The handles of `coercible-metadata` embed a pointer, requiring a block aligned for it.
+    #[cfg(not(feature = "coercible-metadata"))]
+    type TestBlock = [u32; 64];
+
+    #[cfg(feature = "coercible-metadata")]
+    type TestBlock = [u64; 32];
+
+    #[test]
+    fn rc_shared_lists() {
+        let store = RcStore::new(InlineBumpStore::<u16, TestBlock>::default());
+
+        let mut a = LinkedList::new_in(store.share().into_ok());
+        let mut b = LinkedList::new_in(store.share().into_ok());
+
+        a.try_push_back(1u32).unwrap();
+        b.try_push_back(2u32).unwrap();
+        a.try_push_back(3u32).unwrap();
+
+        assert!(store.is_sharing_with(&store.clone()));
+        assert_eq!(vec![1, 3], a.iter().copied().collect::<Vec<_>>());
+        assert_eq!(vec![2], b.iter().copied().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn arc_shared_lists() {
+        let store = ArcStore::new(InlineBumpStore::<u16, TestBlock>::default());
+
+        let mut a = LinkedList::new_in(store.share().into_ok());
+        let mut b = LinkedList::new_in(store.share().into_ok());
+
+        a.try_push_back(1u32).unwrap();
+        b.try_push_back(2u32).unwrap();
+
+        assert!(!store.is_sharing_with(&ArcStore::new(InlineBumpStore::default())));
+        assert_eq!(Some(&1), a.front());
+        assert_eq!(Some(&2), b.front());
+    }
+} // mod tests
@@ -0,0 +1,419 @@
+//! A Store whose instances can only be obtained by sharing.
+//!
+//! Nesting collections, such as a `StoreVec` of `LinkedList`, typically requires one store per collection. When all the
+//! collections should allocate from the same sharing set, any instance of the store created from scratch -- via
+//! `Default`, or `Clone` for stores whose clone is not a share -- silently starts a new sharing set, and a handle moved
+//! from one collection to another would then be resolved by the wrong store.
+//!
+//! `SharingStore` wraps a sharing store, and does not implement `Default` nor `Clone`: the APIs of the collections
+//! which create stores from scratch, such as `new`, `Clone`, or `FromIterator`, are not available, and the only way to
+//! create another instance is `share`.
+
+use core::{
+    alloc::{AllocError, Layout},
+    fmt,
+    mem::MaybeUninit,
+    ptr::{Alignment, NonNull},
+};
+
+use crate::interface::{
+    Growth, PersistError, Store, StoreDangling, StoreEnumerate, StoreIntrospect, StoreLimits, StoreOffsetHandle,
+    StorePersist, StoreSharing, StoreSingle, StoreValidate,
+};
+
+/// A store wrapping a sharing store, whose instances can only be obtained by sharing.
+///
+/// The first instance is created by `new`, and all others by `share`, so that all the instances belong to the same
+/// sharing set. As a result, collections requiring `S: Default` to create their stores cannot be used by accident:
+///
+/// ```compile_fail
+/// #![feature(allocator_api)]
+///
+/// use storage::{collection::LinkedList, store::SharingStore};
+///
+/// //  `SharingStore` does not implement `Default`, hence `LinkedList::new` is unavailable.
+/// let list = LinkedList::<u32, SharingStore<std::alloc::Global>>::new();
+/// ```
+pub struct SharingStore<S>(S);
+
+impl<S> SharingStore<S>
+where
+    S: StoreSharing,
+{
+    /// Creates a new instance, owning `store`.
+    ///
+    /// Further instances sharing with this one are created by `share`.
+    pub const fn new(store: S) -> Self {
+        Self(store)
+    }
+}
+
+impl<S> SharingStore<S> {
+    /// Returns a reference to the underlying store.
+    pub const fn get(&self) -> &S {
+        &self.0
+    }
+
+    /// Returns the underlying store.
+    pub fn into_inner(self) -> S {
+        self.0
+    }
+}
+
+unsafe impl<S> StoreDangling for SharingStore<S>
+where
+    S: StoreDangling,
+{
+    type Handle = S::Handle;
+
+    #[inline(always)]
+    fn dangling(&self, alignment: Alignment) -> Result<Self::Handle, AllocError> {
+        self.0.dangling(alignment)
+    }
+}
+
+unsafe impl<S> Store for SharingStore<S>
+where
+    S: Store,
+{
+    const GROWTH: Growth = <S as Store>::GROWTH;
+
+    #[inline(always)]
+    unsafe fn resolve(&self, handle: Self::Handle) -> NonNull<u8> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.0.resolve(handle) }
+    }
+
+    #[inline(always)]
+    unsafe fn resolve_many(&self, handles: &[Self::Handle], out: &mut [MaybeUninit<NonNull<u8>>]) {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.0.resolve_many(handles, out) }
+    }
+
+    #[inline(always)]
+    fn allocate(&self, layout: Layout) -> Result<(Self::Handle, usize), AllocError> {
+        self.0.allocate(layout)
+    }
+
+    #[inline(always)]
+    unsafe fn deallocate(&self, handle: Self::Handle, layout: Layout) {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.0.deallocate(handle, layout) }
+    }
+
+    #[inline(always)]
+    unsafe fn grow(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.0.grow(handle, old_layout, new_layout) }
+    }
+
+    #[inline(always)]
+    unsafe fn shrink(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.0.shrink(handle, old_layout, new_layout) }
+    }
+
+    #[inline(always)]
+    fn allocate_zeroed(&self, layout: Layout) -> Result<(Self::Handle, usize), AllocError> {
+        self.0.allocate_zeroed(layout)
+    }
+
+    #[inline(always)]
+    unsafe fn grow_zeroed(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.0.grow_zeroed(handle, old_layout, new_layout) }
+    }
+
+    #[inline(always)]
+    unsafe fn grow_reporting(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.0.grow_reporting(handle, old_layout, new_layout) }
+    }
+
+    #[inline(always)]
+    unsafe fn shrink_reporting(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.0.shrink_reporting(handle, old_layout, new_layout) }
+    }
+}
+
+unsafe impl<S> StoreSingle for SharingStore<S>
+where
+    S: StoreSingle,
+{
+    const GROWTH: Growth = <S as StoreSingle>::GROWTH;
+
+    #[inline(always)]
+    unsafe fn resolve(&self, handle: Self::Handle) -> NonNull<u8> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.0.resolve(handle) }
+    }
+
+    #[inline(always)]
+    unsafe fn resolve_mut(&mut self, handle: Self::Handle) -> NonNull<u8> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.0.resolve_mut(handle) }
+    }
+
+    #[inline(always)]
+    fn allocate(&mut self, layout: Layout) -> Result<(Self::Handle, usize), AllocError> {
+        self.0.allocate(layout)
+    }
+
+    #[inline(always)]
+    unsafe fn deallocate(&mut self, handle: Self::Handle, layout: Layout) {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.0.deallocate(handle, layout) }
+    }
+
+    #[inline(always)]
+    unsafe fn grow(
+        &mut self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.0.grow(handle, old_layout, new_layout) }
+    }
+
+    #[inline(always)]
+    unsafe fn shrink(
+        &mut self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.0.shrink(handle, old_layout, new_layout) }
+    }
+
+    #[inline(always)]
+    fn allocate_zeroed(&mut self, layout: Layout) -> Result<(Self::Handle, usize), AllocError> {
+        self.0.allocate_zeroed(layout)
+    }
+
+    #[inline(always)]
+    unsafe fn grow_zeroed(
+        &mut self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.0.grow_zeroed(handle, old_layout, new_layout) }
+    }
+
+    #[inline(always)]
+    unsafe fn grow_reporting(
+        &mut self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.0.grow_reporting(handle, old_layout, new_layout) }
+    }
+
+    #[inline(always)]
+    unsafe fn shrink_reporting(
+        &mut self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.0.shrink_reporting(handle, old_layout, new_layout) }
+    }
+}
+
+crate::store_markers! {
+    //  Safety:
+    //  -   The blocks of memory are those of `S`, hence are as stable, or pinning, as `S`.
+    unsafe impl[S] StorePinning for SharingStore<S> => S;
+}
+
+//  Safety:
+//  -   Sharing is delegated to `S`, hence two instances are sharing if and only if their underlying stores are.
+unsafe impl<S> StoreSharing for SharingStore<S>
+where
+    S: StoreSharing,
+{
+    type SharingError = S::SharingError;
+
+    type SharingToken = S::SharingToken;
+
+    fn is_sharing_with(&self, other: &Self) -> bool {
+        self.0.is_sharing_with(&other.0)
+    }
+
+    fn sharing_token(&self) -> Self::SharingToken {
+        self.0.sharing_token()
+    }
+
+    fn share(&self) -> Result<Self, Self::SharingError> {
+        self.0.share().map(Self)
+    }
+}
+
+unsafe impl<S> StoreOffsetHandle for SharingStore<S>
+where
+    S: StoreOffsetHandle,
+{
+    #[inline(always)]
+    unsafe fn offset(handle: Self::Handle, bytes: usize) -> Self::Handle {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { S::offset(handle, bytes) }
+    }
+}
+
+impl<S> StoreValidate for SharingStore<S>
+where
+    S: StoreValidate,
+{
+    fn is_valid(&self, handle: Self::Handle) -> Option<bool> {
+        self.0.is_valid(handle)
+    }
+}
+
+impl<S> StoreEnumerate for SharingStore<S>
+where
+    S: StoreEnumerate,
+{
+    fn for_each_handle(&self, f: impl FnMut(Self::Handle, Layout)) {
+        self.0.for_each_handle(f)
+    }
+}
+
+impl<S> StoreIntrospect for SharingStore<S>
+where
+    S: StoreIntrospect,
+{
+    fn limits(&self) -> StoreLimits {
+        self.0.limits()
+    }
+}
+
+impl<S> StorePersist for SharingStore<S>
+where
+    S: StorePersist,
+{
+    unsafe fn flush(&self, handle: Self::Handle, layout: Layout) {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.0.flush(handle, layout) }
+    }
+
+    fn snapshot(&self) -> Result<(), PersistError> {
+        self.0.snapshot()
+    }
+}
+
+impl<S> fmt::Debug for SharingStore<S>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_tuple("SharingStore").field(&self.0).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        collection::{LinkedList, StoreVec},
+        store::{StackBumpBlock, StackBumpStore},
+    };
+
+    use super::*;
+
+    type Sharing<'a> = SharingStore<StackBumpStore<'a, u16>>;
+
+    #[test]
+    fn nested_collections() {
+        #[cfg(not(feature = "coercible-metadata"))]
+        let block = StackBumpBlock::<[u64; 64]>::new();
+
+        //  The handles of `coercible-metadata` embed a pointer, making the nodes and the vector larger.
+        #[cfg(feature = "coercible-metadata")]
+        let block = StackBumpBlock::<[u64; 128]>::new();
+
+        let mut buckets = StoreVec::<LinkedList<u32, Sharing<'_>>, _>::new_in(Sharing::new(block.create_store()));
+
+        buckets.reserve(3);
+
+        for _ in 0..3 {
+            buckets.push_shared(LinkedList::new_in);
+        }
+
+        for n in 0..9 {
+            buckets[n as usize % 3].push_back(n);
+        }
+
+        let (first, rest) = buckets.split_at_mut(1);
+
+        first[0].try_append(&mut rest[1]).unwrap();
+
+        assert_eq!(6, buckets[0].len());
+        assert_eq!(3, buckets[1].len());
+        assert!(buckets[2].is_empty());
+
+        let mut extra = buckets[2].new_sharing();
+
+        extra.push_back(9);
+
+        buckets[2].try_append(&mut extra).unwrap();
+
+        assert_eq!(1, buckets[2].len());
+    }
+
+    #[test]
+    fn introspect() {
+        let block = StackBumpBlock::<[u64; 16]>::new();
+        let store = Sharing::new(block.create_store());
+
+        assert_eq!(store.get().limits(), store.limits());
+    }
+} // mod tests
@@ -0,0 +1,366 @@
+//! A tiered Store, providing a single inline block of memory, and spilling over to another store when too small.
+//!
+//! This store is suitable for `Box`, `Vec`, or `VecDeque`, for example, providing a small-buffer optimization: as long
+//! as the allocation fits inline, no memory is requested from the spill-over store, and resolving a handle does not
+//! involve the spill-over store either.
+
+use core::{
+    alloc::{AllocError, Layout},
+    cmp, fmt,
+    ptr::{self, Alignment, NonNull},
+};
+
+use crate::{
+    interface::{Growth, PersistError, StoreDangling, StoreIntrospect, StoreLimits, StorePersist, StoreSingle},
+    store::InlineSingleStore,
+};
+
+/// A tiered Store, providing a single inline block of memory, and spilling over to `S` when too small.
+///
+/// The inline block of memory is aligned and sized as per `T`.
+pub struct SmallStore<T, S> {
+    inline: InlineSingleStore<T>,
+    spill: S,
+}
+
+/// The handle of a `SmallStore`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SmallHandle<H> {
+    /// The block of memory is inline.
+    Inline,
+    /// The block of memory was provided by the spill-over store.
+    Spilled(H),
+}
+
+impl<T, S> SmallStore<T, S> {
+    /// Creates a new instance, spilling over to `spill`.
+    pub const fn new(spill: S) -> Self {
+        let inline = InlineSingleStore::new();
+
+        Self { inline, spill }
+    }
+
+    /// Returns a reference to the spill-over store.
+    pub const fn spill(&self) -> &S {
+        &self.spill
+    }
+}
+
+impl<T, S: Default> Default for SmallStore<T, S> {
+    fn default() -> Self {
+        Self::new(S::default())
+    }
+}
+
+unsafe impl<T, S> StoreDangling for SmallStore<T, S>
+where
+    S: StoreDangling,
+{
+    type Handle = SmallHandle<S::Handle>;
+
+    fn dangling(&self, alignment: Alignment) -> Result<Self::Handle, AllocError> {
+        if let Ok(()) = self.inline.dangling(alignment) {
+            return Ok(SmallHandle::Inline);
+        }
+
+        self.spill.dangling(alignment).map(SmallHandle::Spilled)
+    }
+}
+
+unsafe impl<T, S> StoreSingle for SmallStore<T, S>
+where
+    S: StoreSingle,
+{
+    //  Once spilled, growth follows the spill-over store.
+    const GROWTH: Growth = S::GROWTH;
+
+    #[inline(always)]
+    unsafe fn resolve(&self, handle: Self::Handle) -> NonNull<u8> {
+        match handle {
+            //  Safety:
+            //  -   As per pre-conditions.
+            SmallHandle::Inline => unsafe { self.inline.resolve(()) },
+            //  Safety:
+            //  -   As per pre-conditions.
+            SmallHandle::Spilled(handle) => unsafe { self.spill.resolve(handle) },
+        }
+    }
+
+    #[inline(always)]
+    unsafe fn resolve_mut(&mut self, handle: Self::Handle) -> NonNull<u8> {
+        match handle {
+            //  Safety:
+            //  -   As per pre-conditions.
+            SmallHandle::Inline => unsafe { self.inline.resolve_mut(()) },
+            //  Safety:
+            //  -   As per pre-conditions.
+            SmallHandle::Spilled(handle) => unsafe { self.spill.resolve_mut(handle) },
+        }
+    }
+
+    fn allocate(&mut self, layout: Layout) -> Result<(Self::Handle, usize), AllocError> {
+        if let Ok(((), size)) = self.inline.allocate(layout) {
+            return Ok((SmallHandle::Inline, size));
+        }
+
+        let (handle, size) = self.spill.allocate(layout)?;
+
+        Ok((SmallHandle::Spilled(handle), size))
+    }
+
+    unsafe fn deallocate(&mut self, handle: Self::Handle, layout: Layout) {
+        match handle {
+            //  Safety:
+            //  -   As per pre-conditions.
+            SmallHandle::Inline => unsafe { self.inline.deallocate((), layout) },
+            //  Safety:
+            //  -   As per pre-conditions.
+            SmallHandle::Spilled(handle) => unsafe { self.spill.deallocate(handle, layout) },
+        }
+    }
+
+    unsafe fn grow(
+        &mut self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        debug_assert!(
+            new_layout.size() >= old_layout.size(),
+            "{new_layout:?} must have a greater size than {old_layout:?}"
+        );
+
+        let SmallHandle::Spilled(handle) = handle else {
+            //  Safety:
+            //  -   As per pre-conditions.
+            return unsafe { self.grow_inline(old_layout, new_layout) };
+        };
+
+        //  Safety:
+        //  -   As per pre-conditions.
+        let (handle, size) = unsafe { self.spill.grow(handle, old_layout, new_layout)? };
+
+        Ok((SmallHandle::Spilled(handle), size))
+    }
+
+    unsafe fn shrink(
+        &mut self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        debug_assert!(
+            new_layout.size() <= old_layout.size(),
+            "{new_layout:?} must have a smaller size than {old_layout:?}"
+        );
+
+        match handle {
+            SmallHandle::Inline => {
+                //  Safety:
+                //  -   As per pre-conditions.
+                let ((), size) = unsafe { self.inline.shrink((), old_layout, new_layout)? };
+
+                Ok((SmallHandle::Inline, size))
+            }
+            SmallHandle::Spilled(handle) => {
+                //  Safety:
+                //  -   As per pre-conditions.
+                let (handle, size) = unsafe { self.spill.shrink(handle, old_layout, new_layout)? };
+
+                Ok((SmallHandle::Spilled(handle), size))
+            }
+        }
+    }
+
+    //  The inline block of memory is part of `self`, hence only the spilled block of memory may be reported as not
+    //  relocated.
+    unsafe fn grow_reporting(
+        &mut self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        let SmallHandle::Spilled(handle) = handle else {
+            //  Safety:
+            //  -   As per pre-conditions.
+            let (handle, size) = unsafe { self.grow_inline(old_layout, new_layout)? };
+
+            return Ok((handle, size, true));
+        };
+
+        //  Safety:
+        //  -   As per pre-conditions.
+        let (handle, size, relocated) = unsafe { self.spill.grow_reporting(handle, old_layout, new_layout)? };
+
+        Ok((SmallHandle::Spilled(handle), size, relocated))
+    }
+
+    unsafe fn shrink_reporting(
+        &mut self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        let SmallHandle::Spilled(handle) = handle else {
+            //  Safety:
+            //  -   As per pre-conditions.
+            let (handle, size) = unsafe { self.shrink(handle, old_layout, new_layout)? };
+
+            return Ok((handle, size, true));
+        };
+
+        //  Safety:
+        //  -   As per pre-conditions.
+        let (handle, size, relocated) = unsafe { self.spill.shrink_reporting(handle, old_layout, new_layout)? };
+
+        Ok((SmallHandle::Spilled(handle), size, relocated))
+    }
+}
+
+impl<T, S> StoreIntrospect for SmallStore<T, S>
+where
+    S: StoreIntrospect,
+{
+    fn limits(&self) -> StoreLimits {
+        let (inline, spill) = (self.inline.limits(), self.spill.limits());
+
+        //  Layouts which do not fit inline spill over, and spilled memory is only reused if the spill-over store does.
+        StoreLimits {
+            max_align: cmp::max(inline.max_align, spill.max_align),
+            max_size: cmp::max(inline.max_size, spill.max_size),
+            supports_dealloc_reuse: spill.supports_dealloc_reuse,
+        }
+    }
+}
+
+//  The inline block of memory is part of `self`, hence only spilled blocks of memory are ever persisted.
+impl<T, S> StorePersist for SmallStore<T, S>
+where
+    S: StorePersist,
+{
+    unsafe fn flush(&self, handle: Self::Handle, layout: Layout) {
+        if let SmallHandle::Spilled(handle) = handle {
+            //  Safety:
+            //  -   As per pre-conditions.
+            unsafe { self.spill.flush(handle, layout) }
+        }
+    }
+
+    fn snapshot(&self) -> Result<(), PersistError> {
+        self.spill.snapshot()
+    }
+}
+
+impl<T, S> fmt::Debug for SmallStore<T, S>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_struct("SmallStore")
+            .field("inline", &self.inline)
+            .field("spill", &self.spill)
+            .finish()
+    }
+}
+
+//
+//  Implementation
+//
+
+impl<T, S> SmallStore<T, S>
+where
+    S: StoreSingle,
+{
+    //  Grows the inline block of memory, spilling over if necessary.
+    //
+    //  #   Safety
+    //
+    //  -   The inline block of memory must be allocated.
+    //  -   `old_layout` must fit the inline block of memory.
+    //  -   `new_layout.size()` must be greater than or equal to `old_layout.size()`.
+    #[inline(never)]
+    unsafe fn grow_inline(
+        &mut self,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(SmallHandle<S::Handle>, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        if let Ok(((), size)) = unsafe { self.inline.grow((), old_layout, new_layout) } {
+            return Ok((SmallHandle::Inline, size));
+        }
+
+        let (handle, size) = self.spill.allocate(new_layout)?;
+
+        //  Safety:
+        //  -   The inline block of memory is allocated, as per pre-conditions.
+        let source = unsafe { self.inline.resolve(()) };
+
+        //  Safety:
+        //  -   `handle` was just allocated by `self.spill`.
+        let destination = unsafe { self.spill.resolve_mut(handle) };
+
+        //  Safety:
+        //  -   `source` is valid for reads of `old_layout.size()` bytes, as `old_layout` fits.
+        //  -   `destination` is valid for writes of `old_layout.size()` bytes, as `new_layout.size()` is greater.
+        //  -   `source` and `destination` do not overlap, as they belong to different stores.
+        unsafe { ptr::copy_nonoverlapping(source.as_ptr(), destination.as_ptr(), old_layout.size()) };
+
+        //  Safety:
+        //  -   The inline block of memory is allocated, as per pre-conditions.
+        //  -   `old_layout` fits, as per pre-conditions.
+        unsafe { self.inline.deallocate((), old_layout) };
+
+        Ok((SmallHandle::Spilled(handle), size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::alloc::System;
+
+    use crate::{collection::StoreVec, store::StackBumpBlock};
+
+    use super::*;
+
+    type SmallVec<T, const N: usize> = StoreVec<T, SmallStore<[T; N], System>>;
+
+    #[test]
+    fn inline() {
+        let mut vec = SmallVec::<u32, 4>::new();
+
+        for i in 1..5 {
+            vec.push(i);
+        }
+
+        assert_eq!([1, 2, 3, 4], &vec[..]);
+        assert_eq!(4, vec.capacity());
+    }
+
+    #[test]
+    fn spill() {
+        let mut vec = SmallVec::<String, 2>::new();
+
+        for i in 0..5 {
+            vec.push(i.to_string());
+        }
+
+        assert_eq!(["0", "1", "2", "3", "4"], &vec[..]);
+        assert!(vec.capacity() >= 5);
+    }
+
+    #[test]
+    fn spill_exhausted() {
+        let block = StackBumpBlock::<[u32; 4]>::new();
+        let store = SmallStore::<[u32; 2], _>::new(block.create_store::<u8>());
+
+        let mut vec = StoreVec::new_in(store);
+
+        vec.try_push(1u32).unwrap();
+        vec.try_push(2).unwrap();
+        vec.try_push(3).unwrap();
+
+        assert_eq!([1, 2, 3], &vec[..]);
+        assert_eq!(Err(AllocError), vec.try_reserve(4));
+    }
+} // mod tests
@@ -0,0 +1,570 @@
+//! A store adapter providing stable, and pinned, blocks of memory on top of any store.
+//!
+//! `StabilizingStore<S>` allocates each block of memory in its own heap allocation, from `Global`, and hands out the
+//! index of the block within a table, itself allocated from `S`. Resolving a handle thus goes through two indirections:
+//! first the table, which may move whenever `S` pleases, and then the block, which never moves.
+//!
+//! This trades an extra indirection for stability: collections requiring `StoreStable`, or `StorePinning`, may be used
+//! atop a store offering neither. The underlying store keeps the bookkeeping, and thus bounds the number of live blocks
+//! if of fixed capacity.
+
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    cell::Cell,
+    fmt,
+    ptr::{self, Alignment, NonNull},
+};
+
+use std_alloc::alloc::Global;
+
+use crate::interface::{Store, StoreDangling, StoreIntrospect};
+
+/// A store adapter allocating each block of memory separately, so as to provide stable and pinned blocks of memory.
+///
+/// The table of blocks is allocated from `S`, and grown as per `S::GROWTH`.
+pub struct StabilizingStore<S: Store> {
+    store: S,
+    //  The table of slots, allocated from `store` once the first block is allocated, with `capacity` slots.
+    table: Cell<Option<S::Handle>>,
+    capacity: Cell<usize>,
+    //  The number of slots initialized, the others are not.
+    initialized: Cell<usize>,
+    //  The number of occupied slots.
+    occupied: Cell<usize>,
+    //  The index of the first vacant slot, if any, itself linked to the next vacant slot.
+    vacant: Cell<Option<usize>>,
+}
+
+/// The handle of a `StabilizingStore`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum StabilizingHandle {
+    /// A dangling handle, aligned as specified.
+    Dangling(Alignment),
+    /// The index of the block of memory within the table.
+    Block(usize),
+}
+
+impl<S: Store> StabilizingStore<S> {
+    /// Creates a new instance, keeping its table of blocks in `store`.
+    pub const fn new(store: S) -> Self {
+        Self {
+            store,
+            table: Cell::new(None),
+            capacity: Cell::new(0),
+            initialized: Cell::new(0),
+            occupied: Cell::new(0),
+            vacant: Cell::new(None),
+        }
+    }
+
+    /// Returns a reference to the underlying store.
+    pub const fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Returns whether no block of memory is currently allocated.
+    pub fn is_empty(&self) -> bool {
+        self.occupied.get() == 0
+    }
+
+    /// Returns the number of blocks of memory currently allocated.
+    pub fn len(&self) -> usize {
+        self.occupied.get()
+    }
+}
+
+unsafe impl<S> StoreDangling for StabilizingStore<S>
+where
+    S: Store,
+{
+    type Handle = StabilizingHandle;
+
+    fn dangling(&self, alignment: Alignment) -> Result<Self::Handle, AllocError> {
+        Ok(StabilizingHandle::Dangling(alignment))
+    }
+}
+
+unsafe impl<S> Store for StabilizingStore<S>
+where
+    S: Store,
+{
+    unsafe fn resolve(&self, handle: Self::Handle) -> NonNull<u8> {
+        match handle {
+            StabilizingHandle::Dangling(alignment) => {
+                let pointer = ptr::invalid_mut(alignment.as_usize());
+
+                //  Safety:
+                //  -   Non-null, since `alignment` is non-zero.
+                unsafe { NonNull::new_unchecked(pointer) }
+            }
+            StabilizingHandle::Block(index) => {
+                //  Safety:
+                //  -   `index` is the index of an occupied slot, as `handle` is valid, as per pre-conditions.
+                let (block, _) = unsafe { self.block(index) };
+
+                block
+            }
+        }
+    }
+
+    fn allocate(&self, layout: Layout) -> Result<(Self::Handle, usize), AllocError> {
+        let block = Allocator::allocate(&Global, layout)?;
+        let size = block.len();
+        let block = block.as_non_null_ptr();
+
+        match self.occupy(block, layout) {
+            Ok(index) => Ok((StabilizingHandle::Block(index), size)),
+            Err(error) => {
+                //  Safety:
+                //  -   `block` was allocated by `Global`, with `layout`.
+                unsafe { Allocator::deallocate(&Global, block, layout) };
+
+                Err(error)
+            }
+        }
+    }
+
+    unsafe fn deallocate(&self, handle: Self::Handle, layout: Layout) {
+        let StabilizingHandle::Block(index) = handle else {
+            unreachable!("Dangling handles cannot be deallocated");
+        };
+
+        //  Safety:
+        //  -   `index` is the index of an occupied slot, as `handle` is valid, as per pre-conditions.
+        let (block, _) = unsafe { self.block(index) };
+
+        //  Safety:
+        //  -   `block` was allocated by `Global`, and is still valid, as `handle` is, as per pre-conditions.
+        //  -   `layout` fits `block`, as per pre-conditions.
+        unsafe { Allocator::deallocate(&Global, block, layout) };
+
+        //  Safety:
+        //  -   `index` is the index of an occupied slot, whose block was just deallocated.
+        unsafe { self.vacate(index) };
+    }
+
+    unsafe fn grow(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        let (handle, size, _) = unsafe { self.grow_reporting(handle, old_layout, new_layout)? };
+
+        Ok((handle, size))
+    }
+
+    unsafe fn grow_reporting(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe {
+            self.reallocate(handle, new_layout, |block| {
+                Allocator::grow(&Global, block, old_layout, new_layout)
+            })
+        }
+    }
+
+    unsafe fn shrink(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        let (handle, size, _) = unsafe { self.shrink_reporting(handle, old_layout, new_layout)? };
+
+        Ok((handle, size))
+    }
+
+    unsafe fn shrink_reporting(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe {
+            self.reallocate(handle, new_layout, |block| {
+                Allocator::shrink(&Global, block, old_layout, new_layout)
+            })
+        }
+    }
+
+    unsafe fn grow_zeroed(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        let (handle, size, _) = unsafe {
+            self.reallocate(handle, new_layout, |block| {
+                Allocator::grow_zeroed(&Global, block, old_layout, new_layout)
+            })?
+        };
+
+        Ok((handle, size))
+    }
+}
+
+crate::store_markers! {
+    //  Safety:
+    //  -   Each block of memory is allocated from `Global`, which never moves it, and the table of `self.store` is
+    //      resolved anew on each call, hence a handle always resolves to the same block, even after moving `self`.
+    unsafe impl[S] StorePinning for StabilizingStore<S> where [S: Store];
+}
+
+//  The blocks of memory are allocated from `Global`, hence only bounded by the limits of `Layout`.
+impl<S> StoreIntrospect for StabilizingStore<S> where S: Store {}
+
+impl<S> Drop for StabilizingStore<S>
+where
+    S: Store,
+{
+    fn drop(&mut self) {
+        let Some(table) = self.table.get() else {
+            return;
+        };
+
+        //  The blocks of memory still allocated are leaked by their owner, yet owned by `Global`.
+        for index in 0..self.initialized.get() {
+            //  Safety:
+            //  -   `index` is less than `self.initialized`.
+            if let Slot::Occupied(block, layout) = unsafe { self.slot(index) } {
+                //  Safety:
+                //  -   `block` was allocated by `Global`, with `layout`, and is still valid.
+                unsafe { Allocator::deallocate(&Global, block, layout) };
+            }
+        }
+
+        let layout = self.allocated_table_layout();
+
+        //  Safety:
+        //  -   `table` was allocated by `self.store`, with `layout`, and is still valid.
+        unsafe { self.store.deallocate(table, layout) };
+    }
+}
+
+impl<S> fmt::Debug for StabilizingStore<S>
+where
+    S: Store + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_struct("StabilizingStore")
+            .field("store", &self.store)
+            .field("capacity", &self.capacity.get())
+            .field("occupied", &self.occupied.get())
+            .finish()
+    }
+}
+
+//
+//  Implementation
+//
+
+#[derive(Clone, Copy)]
+enum Slot {
+    Occupied(NonNull<u8>, Layout),
+    Vacant(Option<usize>),
+}
+
+impl<S> StabilizingStore<S>
+where
+    S: Store,
+{
+    //  Returns the block of memory, and its layout, of the slot at `index`.
+    //
+    //  #   Safety
+    //
+    //  -   The slot at `index` must be occupied.
+    unsafe fn block(&self, index: usize) -> (NonNull<u8>, Layout) {
+        //  Safety:
+        //  -   `index` is less than `self.initialized`, as the slot is occupied.
+        let Slot::Occupied(block, layout) = (unsafe { self.slot(index) }) else {
+            unreachable!("Slot {index} should be occupied");
+        };
+
+        (block, layout)
+    }
+
+    //  #   Safety
+    //
+    //  -   `index` must be less than `self.initialized`.
+    unsafe fn slot(&self, index: usize) -> Slot {
+        debug_assert!(index < self.initialized.get());
+
+        //  Safety:
+        //  -   `index` is less than `self.initialized`, hence initialized, and within the table.
+        unsafe { self.slots().add(index).read() }
+    }
+
+    //  #   Safety
+    //
+    //  -   `index` must be less than or equal to `self.initialized`, and less than `self.capacity`.
+    unsafe fn set_slot(&self, index: usize, slot: Slot) {
+        debug_assert!(index <= self.initialized.get());
+        debug_assert!(index < self.capacity.get());
+
+        //  Safety:
+        //  -   `index` is less than `self.capacity`, hence within the table.
+        unsafe { self.slots().add(index).write(slot) };
+    }
+
+    //  Resolves the table.
+    //
+    //  The pointer is only valid until the next call to the underlying store.
+    fn slots(&self) -> NonNull<Slot> {
+        let table = self.table.get();
+
+        debug_assert!(table.is_some());
+
+        //  Safety:
+        //  -   The table is allocated as soon as a slot is, and slots are only resolved once they are.
+        let table = unsafe { table.unwrap_unchecked() };
+
+        //  Safety:
+        //  -   `table` was allocated by `self.store`, and is still valid.
+        unsafe { self.store.resolve(table) }.cast()
+    }
+
+    //  Records `block` in a vacant slot, returning its index.
+    fn occupy(&self, block: NonNull<u8>, layout: Layout) -> Result<usize, AllocError> {
+        let index = match self.vacant.get() {
+            Some(index) => {
+                //  Safety:
+                //  -   `index` is the index of a vacant slot, hence initialized.
+                let Slot::Vacant(next) = (unsafe { self.slot(index) }) else {
+                    unreachable!("Slot {index} should be vacant");
+                };
+
+                self.vacant.set(next);
+
+                index
+            }
+            None => {
+                let index = self.initialized.get();
+
+                if index == self.capacity.get() {
+                    self.grow_table()?;
+                }
+
+                self.initialized.set(index + 1);
+
+                index
+            }
+        };
+
+        //  Safety:
+        //  -   `index` is less than `self.initialized`, and less than `self.capacity`.
+        unsafe { self.set_slot(index, Slot::Occupied(block, layout)) };
+
+        self.occupied.set(self.occupied.get() + 1);
+
+        Ok(index)
+    }
+
+    //  Marks the slot at `index` as vacant.
+    //
+    //  #   Safety
+    //
+    //  -   The slot at `index` must be occupied, and its block no longer used.
+    unsafe fn vacate(&self, index: usize) {
+        //  Safety:
+        //  -   `index` is less than `self.initialized`, as the slot is occupied.
+        unsafe { self.set_slot(index, Slot::Vacant(self.vacant.get())) };
+
+        self.vacant.set(Some(index));
+        self.occupied.set(self.occupied.get() - 1);
+    }
+
+    //  Reallocates the block of memory of `handle` with `reallocate`, recording the new block in the same slot.
+    //
+    //  Returns whether the block of memory was relocated, as per `Store::grow_reporting`.
+    //
+    //  #   Safety
+    //
+    //  -   `handle` must have been allocated by `self`, and must still be valid.
+    //  -   `reallocate` must uphold the pre-conditions of `Store::grow` or `Store::shrink`, when called with the block.
+    unsafe fn reallocate<F>(
+        &self,
+        handle: StabilizingHandle,
+        new_layout: Layout,
+        reallocate: F,
+    ) -> Result<(StabilizingHandle, usize, bool), AllocError>
+    where
+        F: FnOnce(NonNull<u8>) -> Result<NonNull<[u8]>, AllocError>,
+    {
+        let StabilizingHandle::Block(index) = handle else {
+            unreachable!("Dangling handles cannot be reallocated");
+        };
+
+        //  Safety:
+        //  -   `index` is the index of an occupied slot, as `handle` is valid, as per pre-conditions.
+        let (block, _) = unsafe { self.block(index) };
+
+        let new_block = reallocate(block)?;
+        let relocated = new_block.as_non_null_ptr() != block;
+
+        //  Safety:
+        //  -   `index` is less than `self.initialized`, as the slot is occupied.
+        unsafe { self.set_slot(index, Slot::Occupied(new_block.as_non_null_ptr(), new_layout)) };
+
+        Ok((handle, new_block.len(), relocated))
+    }
+
+    fn grow_table(&self) -> Result<(), AllocError> {
+        let capacity = self.capacity.get();
+
+        let new_capacity = <S as Store>::GROWTH
+            .capacity(capacity, capacity + 1)
+            .ok_or(AllocError)?;
+        let new_layout = Self::table_layout(new_capacity).ok_or(AllocError)?;
+
+        let (table, _) = match self.table.get() {
+            None => self.store.allocate(new_layout)?,
+            Some(table) => {
+                let old_layout = self.allocated_table_layout();
+
+                //  Safety:
+                //  -   `table` was allocated by `self.store`, with `old_layout`, and is still valid.
+                //  -   `new_layout` is larger than `old_layout`.
+                unsafe { self.store.grow(table, old_layout, new_layout)? }
+            }
+        };
+
+        self.table.set(Some(table));
+        self.capacity.set(new_capacity);
+
+        Ok(())
+    }
+
+    fn table_layout(capacity: usize) -> Option<Layout> {
+        Layout::array::<Slot>(capacity).ok()
+    }
+
+    //  Returns the layout of the table, as computed when it was allocated, with the current capacity.
+    fn allocated_table_layout(&self) -> Layout {
+        let layout = Self::table_layout(self.capacity.get());
+
+        debug_assert!(layout.is_some());
+
+        //  Safety:
+        //  -   The layout was computed successfully when the table was allocated with the current capacity.
+        unsafe { layout.unwrap_unchecked() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        collection::{LinkedList, StoreVec},
+        interface::{StorePinning, StoreStable},
+        store::{InlineBumpStore, RefStore, TrackingStore},
+    };
+
+    use super::*;
+
+    type Inline = InlineBumpStore<u16, [u64; 64]>;
+
+    fn is_pinning<S: StorePinning>(_: &S) {}
+
+    #[test]
+    fn stable_across_moves() {
+        let store = StabilizingStore::new(Inline::default());
+
+        is_pinning(&store);
+
+        let (handle, _) = store.allocate(Layout::new::<u64>()).unwrap();
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, for a `u64`.
+        let pointer = unsafe { store.resolve(handle) };
+
+        //  Safety:
+        //  -   `pointer` is valid for writes of a `u64`.
+        unsafe { pointer.cast::<u64>().write(42) };
+
+        let moved = Box::new(store);
+
+        //  Safety:
+        //  -   `handle` was allocated by `moved`, and is still valid.
+        assert_eq!(pointer, unsafe { moved.resolve(handle) });
+
+        //  Safety:
+        //  -   `pointer` is valid for reads of a `u64`, and was initialized above.
+        assert_eq!(42, unsafe { pointer.cast::<u64>().read() });
+
+        //  Safety:
+        //  -   `handle` was allocated by `moved`, and is still valid, for a `u64`.
+        unsafe { moved.deallocate(handle, Layout::new::<u64>()) };
+
+        assert!(moved.is_empty());
+    }
+
+    #[test]
+    fn slots_reused() {
+        let tracking = TrackingStore::<Inline, 4>::new(Inline::default());
+        let store = StabilizingStore::new(RefStore::new(&tracking));
+
+        let layout = Layout::new::<u32>();
+
+        let handles: Vec<_> = (0..10).map(|_| store.allocate(layout).unwrap().0).collect();
+
+        //  A single table, grown in place.
+        assert_eq!(10, store.len());
+        assert_eq!(1, tracking.len());
+
+        //  Safety:
+        //  -   `handles[3]` was allocated by `store`, and is still valid, for a `u32`.
+        unsafe { store.deallocate(handles[3], layout) };
+
+        let (handle, _) = store.allocate(layout).unwrap();
+
+        assert_eq!(handles[3], handle);
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid, for a `u32`.
+        let (grown, _) = unsafe { store.grow(handle, layout, Layout::new::<[u32; 64]>()) }.unwrap();
+
+        assert_eq!(handle, grown);
+
+        //  The remaining blocks are released with the store, and so is the table.
+        drop(store);
+
+        assert_eq!(0, tracking.len());
+    }
+
+    #[test]
+    fn collections() {
+        fn is_stable<S: StoreStable>(_: &S) {}
+
+        let mut list = LinkedList::new_in(StabilizingStore::new(Inline::default()));
+
+        (0..8).for_each(|n| list.push_back(n));
+
+        let mut pinned = list.into_pinned();
+
+        assert_eq!(7, *pinned.back_pinned().unwrap());
+
+        let store = StabilizingStore::new(Inline::default());
+
+        is_stable(&store);
+
+        let mut vec = StoreVec::new_in(RefStore::new(&store));
+
+        vec.extend(0..32u32);
+
+        assert_eq!(1, store.len());
+        assert_eq!(31, vec[31]);
+    }
+} // mod tests
@@ -2,17 +2,30 @@
 //!
 //! A store which references a stack or statically allocated fixed-sized block of memory. Multiple instances may
 //! reference the same block, and all instances referencing the same block are fungible.
+//!
+//! All instances referencing a block form a single sharing set, whether created by `share` or `create_store`, and
+//! handles remain valid across all of them for as long as the block lives, even after the instance which allocated them
+//! is dropped.
 
 use core::{
     alloc::{AllocError, Layout},
-    cell::{Cell, UnsafeCell},
+    cell::UnsafeCell,
     fmt,
     marker::PhantomData,
     mem::{self, MaybeUninit},
-    ptr::{self, Alignment, NonNull},
+    ptr::{Alignment, NonNull},
+};
+
+use crate::{
+    interface::{
+        Store, StoreDangling, StoreIntrospect, StoreLimits, StoreOffsetHandle, StorePersist, StoreRegion, StoreSharing,
+        StoreSingle, StoreValidate,
+    },
+    store::{BumpCore, BumpError, BumpMemory, NoRounding, RoundingPolicy},
 };
 
-use crate::interface::{Store, StoreDangling, StorePinning, StoreSharing, StoreSingle, StoreStable};
+#[cfg(debug_assertions)]
+use crate::store::BumpDump;
 
 /// The backing block of memory for the store.
 ///
@@ -20,37 +33,59 @@ use crate::interface::{Store, StoreDangling, StorePinning, StoreSharing, StoreSi
 ///
 /// -   The block of memory is aligned and sized as per `T`.
 pub struct StackBumpBlock<T> {
-    watermark: Cell<usize>,
+    core: BumpCore,
     memory: UnsafeCell<MaybeUninit<T>>,
 }
 
 impl<T> StackBumpBlock<T> {
     /// Creates a new, empty, block.
-    pub fn new() -> Self {
-        let watermark = Cell::new(0);
+    pub const fn new() -> Self {
+        let core = BumpCore::new();
         let memory = UnsafeCell::new(MaybeUninit::uninit());
 
-        Self { watermark, memory }
+        Self { core, memory }
+    }
+
+    /// Creates a new, empty, block, whose memory is zero-initialized upfront.
+    ///
+    /// Zeroing the whole block of memory once lets `allocate_zeroed` and `grow_zeroed` skip zeroing, except for memory
+    /// previously handed out.
+    pub const fn new_zeroed() -> Self {
+        //  Safety:
+        //  -   The block of memory is zero-initialized.
+        let core = unsafe { BumpCore::new_zeroed() };
+        let memory = UnsafeCell::new(MaybeUninit::zeroed());
+
+        Self { core, memory }
+    }
+
+    /// Returns whether the memory not yet handed out is known to be zero-initialized.
+    pub fn is_zeroed(&self) -> bool {
+        self.core.is_zeroed()
     }
 
     /// Creates a new store referencing this block.
-    pub fn create_store<H>(&self) -> StackBumpStore<'_, H> {
-        let watermark = &self.watermark;
+    pub const fn create_store<H>(&self) -> StackBumpStore<'_, H> {
+        self.create_rounding_store()
+    }
+
+    /// Creates a new store referencing this block, rounding the size of each allocation as per `R`.
+    pub const fn create_rounding_store<H, R>(&self) -> StackBumpStore<'_, H, R> {
+        let core = &self.core;
 
         let memory = {
             let length = mem::size_of::<T>();
-            let address = NonNull::from(&self.memory).cast();
+
+            //  Safety:
+            //  -   `self.memory.get()` is non null, as `self` is non null.
+            let address = unsafe { NonNull::new_unchecked(self.memory.get() as *mut u8) };
 
             NonNull::slice_from_raw_parts(address, length)
         };
 
         let _marker = PhantomData;
 
-        StackBumpStore {
-            watermark,
-            memory,
-            _marker,
-        }
+        StackBumpStore { core, memory, _marker }
     }
 }
 
@@ -62,36 +97,42 @@ impl<T> Default for StackBumpBlock<T> {
 
 /// A store instance referencing its block.
 ///
+/// Since the block cannot move while referenced, its address is known, and allocations are aligned relative to it,
+/// rather than relative to its start: alignments beyond that of `T` are satisfied by padding from the actual address,
+/// up to the greatest alignment of any address within the block, as reported by `StoreIntrospect::limits`. Hence a
+/// 64-byte aligned allocation may be carved out of a byte-aligned block, at the cost of up to 63 bytes of padding.
+///
 /// Generic parameters:
 ///
-/// -   `H` is the handle type, it must convertible to and from `usize`.
-pub struct StackBumpStore<'a, H> {
-    watermark: &'a Cell<usize>,
+/// -   `H` is the handle type, it must convertible to and from `usize`. See `assert_handle_fits` to check, at
+///     compile-time, that it can represent any offset within the block of memory.
+/// -   `R` is the rounding policy, applied to the size of each allocation.
+pub struct StackBumpStore<'a, H, R = NoRounding> {
+    core: &'a BumpCore,
     memory: NonNull<[u8]>,
-    _marker: PhantomData<fn(H) -> H>,
+    _marker: PhantomData<fn(H) -> (H, R)>,
 }
 
-//  Cannot be const, because TryFrom is not marked #[const_trait].
-unsafe impl<'a, H> StoreDangling for StackBumpStore<'a, H>
+//  Cannot be const, because the offset of a dangling handle depends on the address of the block of memory.
+unsafe impl<'a, H, R> StoreDangling for StackBumpStore<'a, H, R>
 where
     H: Copy + TryFrom<usize>,
 {
     type Handle = H;
 
     fn dangling(&self, alignment: Alignment) -> Result<Self::Handle, AllocError> {
-        Self::from_offset(alignment.as_usize())
+        //  The block of memory is only aligned as per `T`, hence the offset must account for its actual address.
+        self.bump_memory().dangling(alignment).map_err(AllocError::from)
     }
 }
 
-unsafe impl<'a, H> Store for StackBumpStore<'a, H>
+unsafe impl<'a, H, R> Store for StackBumpStore<'a, H, R>
 where
     H: Copy + TryFrom<usize> + TryInto<usize>,
+    R: RoundingPolicy,
 {
     fn allocate(&self, layout: Layout) -> Result<(Self::Handle, usize), AllocError> {
-        let (result, new_watermark) = self.compute_offset(layout)?;
-        self.watermark.set(new_watermark);
-
-        Ok((result, layout.size()))
+        Ok(self.try_allocate(layout)?)
     }
 
     #[inline(always)]
@@ -99,69 +140,94 @@ where
 
     #[inline(always)]
     unsafe fn resolve(&self, handle: Self::Handle) -> NonNull<u8> {
-        debug_assert!(Self::into_offset(handle) <= self.memory.len());
+        //  Safety:
+        //  -   `handle` was allocated by `self`, or created dangling, as per pre-conditions.
+        unsafe { self.bump_memory().resolve(handle) }
+    }
 
-        let offset = Self::into_offset(handle);
-        let pointer = self.memory.as_mut_ptr();
+    unsafe fn grow(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        let memory = self.bump_memory();
 
         //  Safety:
-        //  -   `offset` is within bounds of `self.memory`, as `handle` was allocated by `self` as per pre-conditions.
-        let pointer = unsafe { pointer.add(offset) };
+        //  -   As per pre-conditions.
+        Ok(unsafe { self.core.grow::<H, R>(memory, handle, old_layout, new_layout) }?)
+    }
+
+    #[inline(always)]
+    unsafe fn shrink(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        let memory = self.bump_memory();
 
         //  Safety:
-        //  -   `pointer` is non null as `self` is non null.
-        unsafe { NonNull::new_unchecked(pointer) }
+        //  -   As per pre-conditions.
+        Ok(unsafe { self.core.shrink::<H, R>(memory, handle, old_layout, new_layout) }?)
     }
 
-    unsafe fn grow(
+    fn allocate_zeroed(&self, layout: Layout) -> Result<(Self::Handle, usize), AllocError> {
+        Ok(self.core.allocate_zeroed::<H, R>(self.bump_memory(), layout)?)
+    }
+
+    unsafe fn grow_zeroed(
         &self,
         handle: Self::Handle,
         old_layout: Layout,
         new_layout: Layout,
     ) -> Result<(Self::Handle, usize), AllocError> {
-        debug_assert!(
-            new_layout.size() >= old_layout.size(),
-            "{new_layout:?} must have a greater size than {old_layout:?}"
-        );
-
-        //  As an optimization, if `handle` points to the last allocation, growth may actually occur _in place_.
-        {
-            let offset = Self::into_offset(handle);
-            let watermark = self.watermark.get();
+        let memory = self.bump_memory();
 
-            if offset + old_layout.size() == watermark
-                && new_layout.align() <= old_layout.align()
-                && offset + new_layout.size() <= self.memory.len()
-            {
-                let new_watermark = watermark - old_layout.size() + new_layout.size();
-                self.watermark.set(new_watermark);
+        //  Safety:
+        //  -   As per pre-conditions.
+        Ok(unsafe { self.core.grow_zeroed::<H, R>(memory, handle, old_layout, new_layout) }?)
+    }
 
-                return Ok((handle, new_layout.size()));
-            }
-        }
+    unsafe fn grow_reporting(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        let memory = self.bump_memory();
 
-        self.grow_by_relocation(handle, old_layout, new_layout)
+        //  Safety:
+        //  -   As per pre-conditions.
+        Ok(unsafe { self.core.grow_reporting::<H, R>(memory, handle, old_layout, new_layout) }?)
     }
 
-    #[inline(always)]
-    unsafe fn shrink(
+    unsafe fn shrink_reporting(
         &self,
         handle: Self::Handle,
         old_layout: Layout,
-        _new_layout: Layout,
-    ) -> Result<(Self::Handle, usize), AllocError> {
-        debug_assert!(
-            _new_layout.size() >= old_layout.size(),
-            "{_new_layout:?} must have a smaller size than {old_layout:?}"
-        );
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        let memory = self.bump_memory();
 
-        Ok((handle, old_layout.size()))
+        //  Safety:
+        //  -   As per pre-conditions.
+        let result = unsafe {
+            self.core
+                .shrink_reporting::<H, R>(memory, handle, old_layout, new_layout)
+        };
+
+        Ok(result?)
     }
 }
 
-unsafe impl<'a, H> StoreSingle for StackBumpStore<'a, H>
+//  Allocating does not reset the watermark, even though only a single allocation may be outstanding per instance: other
+//  instances referencing the same block may still have outstanding allocations, and `StoreSingle` must behave
+//  identically to `Store` regardless.
+unsafe impl<'a, H, R> StoreSingle for StackBumpStore<'a, H, R>
 where
     H: Copy + TryFrom<usize> + TryInto<usize>,
+    R: RoundingPolicy,
 {
     #[inline(always)]
     unsafe fn resolve(&self, handle: Self::Handle) -> NonNull<u8> {
@@ -206,136 +272,415 @@ where
         //  -   As per pre-conditions.
         unsafe { <Self as Store>::shrink(self, handle, old_layout, new_layout) }
     }
+
+    fn allocate_zeroed(&mut self, layout: Layout) -> Result<(Self::Handle, usize), AllocError> {
+        <Self as Store>::allocate_zeroed(self, layout)
+    }
+
+    unsafe fn grow_zeroed(
+        &mut self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { <Self as Store>::grow_zeroed(self, handle, old_layout, new_layout) }
+    }
+
+    unsafe fn grow_reporting(
+        &mut self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { <Self as Store>::grow_reporting(self, handle, old_layout, new_layout) }
+    }
+
+    #[inline(always)]
+    unsafe fn shrink_reporting(
+        &mut self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { <Self as Store>::shrink_reporting(self, handle, old_layout, new_layout) }
+    }
 }
 
-//  Safety:
-//  -   `self.resolve(handle)` always returns the same address.
-unsafe impl<'a, H> StoreStable for StackBumpStore<'a, H> where H: Copy + TryFrom<usize> + TryInto<usize> {}
+crate::store_markers! {
+    //  Safety:
+    //  -   `self.resolve(handle)` always returns the same address.
+    unsafe impl['a, H, R] StorePinning for StackBumpStore<'a, H, R> where [H: Copy + TryFrom<usize> + TryInto<usize>];
+}
 
 //  Safety:
-//  -   `self.resolve(handle)` always returns the same address.
-unsafe impl<'a, H> StorePinning for StackBumpStore<'a, H> where H: Copy + TryFrom<usize> + TryInto<usize> {}
+//  -   `self.memory` is the memory of the referenced block, which cannot move while borrowed.
+//  -   All blocks of memory are allocated from within `self.memory`.
+unsafe impl<'a, H, R> StoreRegion for StackBumpStore<'a, H, R>
+where
+    H: Copy + TryFrom<usize> + TryInto<usize>,
+{
+    fn region(&self) -> NonNull<[u8]> {
+        self.memory
+    }
+}
 
 /// Safety:
 /// -   All instances referencing the same StackBumpBlock are fungible.
-unsafe impl<'a, H> StoreSharing for StackBumpStore<'a, H>
+unsafe impl<'a, H, R> StoreSharing for StackBumpStore<'a, H, R>
 where
     H: Copy + TryFrom<usize> + TryInto<usize>,
 {
     type SharingError = !;
 
+    type SharingToken = (usize, usize);
+
     fn is_sharing_with(&self, other: &Self) -> bool {
         self.memory == other.memory
     }
 
+    fn sharing_token(&self) -> Self::SharingToken {
+        (self.memory.as_ptr().addr(), self.memory.len())
+    }
+
     fn share(&self) -> Result<Self, Self::SharingError>
     where
         Self: Sized,
     {
-        let watermark = self.watermark;
+        let core = self.core;
         let memory = self.memory;
         let _marker = PhantomData;
 
-        Ok(Self {
-            watermark,
-            memory,
-            _marker,
-        })
+        Ok(Self { core, memory, _marker })
     }
 }
 
-impl<'a, H> fmt::Debug for StackBumpStore<'a, H> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        f.debug_struct("StackBumpStore")
-            .field("watermark", &self.watermark)
-            .field("memory", &self.memory.len())
-            .finish()
+//  Safety:
+//  -   Handles are offsets from the start of the memory, hence advancing the offset advances the resolved pointer.
+unsafe impl<'a, H, R> StoreOffsetHandle for StackBumpStore<'a, H, R>
+where
+    H: Copy + TryFrom<usize> + TryInto<usize>,
+{
+    #[inline(always)]
+    unsafe fn offset(handle: Self::Handle, bytes: usize) -> Self::Handle {
+        BumpCore::advance(handle, bytes)
     }
 }
 
-//
-//  Implementation
-//
+impl<'a, H, R> StoreValidate for StackBumpStore<'a, H, R>
+where
+    H: Copy + TryFrom<usize> + TryInto<usize>,
+{
+    fn is_valid(&self, handle: Self::Handle) -> Option<bool> {
+        self.core.is_valid(handle)
+    }
+}
 
-impl<'a, H> StackBumpStore<'a, H>
+//  The block is borrowed, typically from the stack; an owner mapping it onto a persistent medium instead may wrap the
+//  store to flush the block in these hooks.
+impl<'a, H, R> StorePersist for StackBumpStore<'a, H, R> where H: Copy + TryFrom<usize> {}
+
+impl<'a, H, R> StoreIntrospect for StackBumpStore<'a, H, R>
 where
-    H: TryFrom<usize>,
+    H: Copy + TryFrom<usize>,
 {
-    #[inline(always)]
-    fn from_offset(offset: usize) -> Result<H, AllocError> {
-        offset.try_into().map_err(|_| AllocError)
+    fn limits(&self) -> StoreLimits {
+        self.bump_memory().limits()
     }
 }
 
-impl<'a, H> StackBumpStore<'a, H>
+impl<'a, H, R> StackBumpStore<'a, H, R>
 where
-    H: TryInto<usize>,
+    R: RoundingPolicy,
 {
-    #[inline(always)]
-    fn into_offset(handle: H) -> usize {
-        let offset = handle.try_into();
+    /// Returns the number of bytes consumed so far, including alignment padding and rounding.
+    pub fn used(&self) -> usize {
+        self.core.used()
+    }
 
-        debug_assert!(offset.is_ok());
+    /// Returns the number of bytes still available.
+    pub fn remaining(&self) -> usize {
+        self.memory.len() - self.used()
+    }
 
+    /// Returns the number of bytes wasted on alignment padding so far.
+    pub fn padding(&self) -> usize {
+        self.core.padding()
+    }
+
+    /// Returns the name of the rounding policy applied to the size of each allocation.
+    pub fn rounding(&self) -> &'static str {
+        R::NAME
+    }
+}
+
+impl<'a, H, R> StackBumpStore<'a, H, R>
+where
+    H: Copy + TryFrom<usize> + TryInto<usize>,
+    R: RoundingPolicy,
+{
+    /// Attempts to allocate a block of memory, as per `Store::allocate`, reporting why allocation failed, if it did.
+    ///
+    /// Notably, allocation fails with `BumpError::HandleOverflow` if the offset of the block of memory is not
+    /// representable by `H`, even though the block of memory fits.
+    pub fn try_allocate(&self, layout: Layout) -> Result<(H, usize), BumpError> {
+        self.core.allocate::<H, R>(self.bump_memory(), layout)
+    }
+
+    /// Allocates a batch of blocks of memory, by order of decreasing alignment so as to minimize padding.
+    ///
+    /// The handles and sizes are returned in the order of `layouts`. On failure, the blocks of memory allocated so far
+    /// are not reclaimed.
+    pub fn allocate_batch<const N: usize>(&self, layouts: [Layout; N]) -> Result<[(H, usize); N], AllocError> {
+        Ok(self.core.allocate_batch::<H, R, N>(self.bump_memory(), layouts)?)
+    }
+}
+
+#[cfg(debug_assertions)]
+impl<'a, H, R> StackBumpStore<'a, H, R> {
+    /// Returns a dump of the memory used so far, for debugging purposes, see `BumpDump`.
+    ///
+    /// #   Safety
+    ///
+    /// -   The used memory must be initialized, as is the case if the block was created by `new_zeroed`, and only
+    ///     values without padding bytes are written to it.
+    /// -   The used memory must not be written to for as long as the dump is alive.
+    pub unsafe fn dump(&self) -> BumpDump<'_> {
         //  Safety:
-        //  -   `handle` was created from `usize`, hence converting back always succeeds.
-        unsafe { offset.unwrap_unchecked() }
+        //  -   The block of memory is valid for reads for as long as it is borrowed, hence as long as `self` is.
+        //  -   The used memory is initialized, and not written to, as per pre-conditions.
+        unsafe { self.core.dump(self.bump_memory()) }
     }
 }
 
-impl<'a, H> StackBumpStore<'a, H>
+impl<'a, H, R> fmt::Debug for StackBumpStore<'a, H, R>
 where
-    H: TryFrom<usize> + TryInto<usize>,
+    R: RoundingPolicy,
 {
-    //  Returns the offset and new watermark of the newly allocated memory block.
-    fn compute_offset(&self, layout: Layout) -> Result<(H, usize), AllocError> {
-        let watermark = self.watermark.get();
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_struct("StackBumpStore")
+            .field("watermark", &self.core.used())
+            .field("padding", &self.core.padding())
+            .field("memory", &self.memory.len())
+            .field("rounding", &R::NAME)
+            .finish()
+    }
+}
+
+//
+//  Implementation
+//
+
+impl<'a, H, R> StackBumpStore<'a, H, R> {
+    //  The block of memory cannot move while borrowed, hence it is pinned.
+    #[inline(always)]
+    fn bump_memory(&self) -> BumpMemory {
+        BumpMemory::pinned(self.memory)
+    }
+}
 
-        let aligned = {
-            //  Since `layout.align()` is always a power of 2, aligning to the next multiple of `layout.align()` can be
-            //  done with this one simple trick.
-            let alignment_mask = layout.align() - 1;
+#[cfg(test)]
+mod tests {
+    use crate::collection::StoreVec;
 
-            (watermark + alignment_mask) & !alignment_mask
+    use super::*;
+
+    #[test]
+    fn padding() {
+        let block = StackBumpBlock::<[u64; 4]>::new();
+        let store = block.create_store::<usize>();
+
+        Store::allocate(&store, Layout::new::<u8>()).unwrap();
+        Store::allocate(&store, Layout::new::<u64>()).unwrap();
+
+        assert_eq!(16, store.used());
+        assert_eq!(16, store.remaining());
+        assert_eq!(7, store.padding());
+    }
+
+    #[test]
+    fn recreated_store() {
+        let block = StackBumpBlock::<[u64; 4]>::new();
+
+        let handle = {
+            let store = block.create_store::<usize>();
+            let (handle, _) = Store::allocate(&store, Layout::new::<u64>()).unwrap();
+
+            //  Safety:
+            //  -   `handle` was allocated by `store`, and is still valid.
+            //  -   `handle` is associated to a block of memory fitting `u64`.
+            unsafe { Store::resolve(&store, handle).cast::<u64>().write(42) };
+
+            handle
         };
 
-        let new_watermark = aligned + layout.size();
+        //  All stores referencing `block` belong to the same sharing set, even when created after the others are gone.
+        let store = block.create_store::<usize>();
+
+        assert!(store.is_sharing_with(&block.create_store()));
+
+        //  Safety:
+        //  -   `handle` was allocated by a store referencing `block`, and is still valid.
+        //  -   `handle` is associated to an initialized `u64`.
+        assert_eq!(42, unsafe { Store::resolve(&store, handle).cast::<u64>().read() });
 
-        if new_watermark > self.memory.len() {
-            return Err(AllocError);
+        //  Safety:
+        //  -   `handle` was allocated by a store referencing `block`, and is still valid.
+        //  -   `handle` is associated to a block of memory fitting `u64`.
+        unsafe { Store::deallocate(&store, handle, Layout::new::<u64>()) };
+    }
+
+    #[test]
+    fn allocate_batch() {
+        let block = StackBumpBlock::<[u64; 4]>::new();
+        let store = block.create_store::<usize>();
+
+        let layouts = [Layout::new::<u8>(), Layout::new::<u64>(), Layout::new::<u16>()];
+
+        let [(a, _), (b, _), (c, _)] = store.allocate_batch(layouts).unwrap();
+
+        assert_eq!((10, 0, 8), (a, b, c));
+        assert_eq!(11, store.used());
+        assert_eq!(0, store.padding());
+    }
+
+    #[test]
+    fn over_aligned() {
+        let block = StackBumpBlock::<[u8; 64]>::new();
+        let store = block.create_store::<u8>();
+
+        Store::allocate(&store, Layout::new::<u8>()).unwrap();
+
+        for layout in [Layout::new::<u64>(), Layout::from_size_align(8, 16).unwrap()] {
+            let (handle, _) = Store::allocate(&store, layout).unwrap();
+
+            //  Safety:
+            //  -   `handle` was allocated by `store`, and is still valid.
+            let pointer = unsafe { Store::resolve(&store, handle) };
+
+            assert_eq!(0, pointer.as_ptr().addr() % layout.align());
         }
 
-        let aligned = Self::from_offset(aligned)?;
+        let dangling = store.dangling(Alignment::of::<u64>()).unwrap();
 
-        Ok((aligned, new_watermark))
+        //  Safety:
+        //  -   `dangling` was created by `store`.
+        let pointer = unsafe { Store::resolve(&store, dangling) };
+
+        assert_eq!(0, pointer.as_ptr().addr() % 8);
     }
-}
 
-impl<'a, H> StackBumpStore<'a, H>
-where
-    H: Copy + TryFrom<usize> + TryInto<usize>,
-{
-    //  Slow part of `grow`.
-    #[inline(never)]
-    fn grow_by_relocation(&self, handle: H, old_layout: Layout, new_layout: Layout) -> Result<(H, usize), AllocError> {
-        let (result, new_watermark) = self.compute_offset(new_layout)?;
-        self.watermark.set(new_watermark);
+    #[test]
+    fn over_aligned_beyond_block() {
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        #[repr(align(64))]
+        struct CacheLine([u8; 64]);
+
+        let block = StackBumpBlock::<[u8; 255]>::new();
+        let store = block.create_store::<u8>();
+
+        assert!(store.limits().max_align.as_usize() >= 64);
+
+        Store::allocate(&store, Layout::new::<u8>()).unwrap();
+
+        let line = Layout::new::<CacheLine>();
+        let (handle, _) = Store::allocate(&store, Layout::from_size_align(8, 64).unwrap()).unwrap();
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, for 8 bytes.
+        unsafe { Store::resolve(&store, handle).as_ptr().write_bytes(0x2A, 8) };
+
+        //  Prevent growing in place.
+        Store::allocate(&store, Layout::new::<u8>()).unwrap();
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, for 8 bytes aligned on 64 bytes.
+        let (handle, _) =
+            unsafe { Store::grow(&store, handle, Layout::from_size_align(8, 64).unwrap(), line) }.unwrap();
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and is still valid.
+        let pointer = unsafe { Store::resolve(&store, handle) };
+
+        assert_eq!(0, pointer.as_ptr().addr() % 64);
 
         //  Safety:
-        //  -   `handle` is valid, as per pre-conditions.
-        //  -   `result` is valid, since newly allocated.
-        let (new, old) = unsafe { (Store::resolve(self, result), Store::resolve(self, handle)) };
+        //  -   The first 8 bytes of `pointer` were initialized prior to growing.
+        assert_eq!([0x2A; 8], unsafe { pointer.cast::<[u8; 8]>().read() });
+
+        let dangling = store.dangling(Alignment::of::<CacheLine>()).unwrap();
 
         //  Safety:
-        //  -   `old` is valid for `old_layout.size()` bytes, as per pre-conditions.
-        //  -   `new` is valid for `old_layout.size()` bytes, since it is valid for `new_layout.size()` bytes and as per
-        //      pre-conditions `new_layout.size() >= old_layout.size()`.
-        //  -   `old` and `new` are at least 1-byte aligned.
-        //  -   `old` and `new` point to non-overlapping areas, since `old` points to a memory area prior to the
-        //      watermark and `new` points to a memory area post the watermark (as the beginning of this function),
-        //      since `old_layout` fits `old` as per pre-conditions.
-        unsafe { ptr::copy_nonoverlapping(old.as_ptr(), new.as_ptr(), old_layout.size()) };
+        //  -   `dangling` was created by `store`.
+        let pointer = unsafe { Store::resolve(&store, dangling) };
+
+        assert_eq!(0, pointer.as_ptr().addr() % 64);
 
-        Ok((result, new_layout.size()))
+        //  Collections of over-aligned elements work alike.
+        let other = StackBumpBlock::<[u8; 255]>::new();
+
+        let mut vec = StoreVec::with_capacity_in(2, other.create_store::<u8>());
+        vec.push(CacheLine([1; 64]));
+        vec.push(CacheLine([2; 64]));
+
+        assert_eq!(0, vec.as_ptr().addr() % 64);
+        assert_eq!([CacheLine([1; 64]), CacheLine([2; 64])], vec.as_slice());
     }
-}
+
+    #[test]
+    fn offset_overflow() {
+        let block = StackBumpBlock::<[u8; 64]>::new();
+        let store = block.create_store::<u8>();
+
+        let huge = Layout::from_size_align(isize::MAX as usize, 1).unwrap();
+
+        assert!(Store::allocate(&store, huge).is_err());
+        assert_eq!(0, store.used());
+    }
+
+    #[test]
+    fn handle_overflow() {
+        let block = StackBumpBlock::<[u8; 512]>::new();
+        let store = block.create_store::<u8>();
+
+        store.try_allocate(Layout::new::<[u8; 256]>()).unwrap();
+
+        assert_eq!(
+            Err(BumpError::HandleOverflow),
+            store.try_allocate(Layout::new::<[u8; 2]>())
+        );
+        assert_eq!(
+            Err(BumpError::Exhausted),
+            store.try_allocate(Layout::new::<[u8; 512]>())
+        );
+    }
+
+    #[test]
+    fn rounding() {
+        use crate::store::RoundToPowerOfTwo;
+
+        let block = StackBumpBlock::<[u64; 4]>::new();
+        let store = block.create_rounding_store::<usize, RoundToPowerOfTwo>();
+
+        let (handle, size) = Store::allocate(&store, Layout::new::<[u8; 5]>()).unwrap();
+
+        assert_eq!(8, size);
+        assert_eq!(8, store.used());
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, with a size of 5 bytes.
+        let (grown, size) =
+            unsafe { Store::grow(&store, handle, Layout::new::<[u8; 5]>(), Layout::new::<[u8; 7]>()) }.unwrap();
+
+        assert_eq!((handle, 8), (grown, size));
+        assert_eq!(8, store.used());
+
+        assert_eq!("RoundToPowerOfTwo", store.rounding());
+        assert!(format!("{store:?}").contains("RoundToPowerOfTwo"));
+    }
+} // mod tests
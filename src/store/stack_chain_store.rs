@@ -0,0 +1,600 @@
+//! A "bump allocator" Store spanning multiple blocks.
+//!
+//! A store which references a list of stack or statically allocated fixed-sized blocks of memory, spilling over from
+//! one block to the next when an allocation does not fit. Multiple instances may reference the same blocks, and all
+//! instances referencing the same blocks are fungible.
+
+use core::{
+    alloc::{AllocError, Layout},
+    fmt,
+    marker::PhantomData,
+    mem::{self, MaybeUninit},
+    ptr::{self, Alignment, NonNull},
+};
+
+use crate::{
+    interface::{
+        Store, StoreDangling, StoreIntrospect, StoreLimits, StoreOffsetHandle, StorePersist, StoreSharing, StoreSingle,
+        StoreValidate,
+    },
+    store::{StackBumpBlock, StackBumpStore},
+};
+
+impl<T> StackBumpBlock<T> {
+    /// Creates a new store referencing all `blocks`, in order.
+    pub fn chain<'a, H>(blocks: &'a [&'a StackBumpBlock<T>]) -> StackChainStore<'a, T, H>
+    where
+        H: TryFrom<usize>,
+    {
+        let dangling = H::try_from(0).ok();
+        let _marker = PhantomData;
+
+        StackChainStore {
+            blocks,
+            dangling,
+            _marker,
+        }
+    }
+}
+
+/// A store instance referencing its blocks.
+///
+/// Allocations are carved out of the first block with enough room left, hence a single allocation can never exceed
+/// the size of a single block.
+///
+/// Generic parameters:
+///
+/// -   Each block of memory is aligned and sized as per `T`.
+/// -   `H` is the handle type, it must convertible to and from `usize`.
+pub struct StackChainStore<'a, T, H> {
+    blocks: &'a [&'a StackBumpBlock<T>],
+    //  The handle of offset 0 within block 0, computed upfront so that `dangling` needs no conversion.
+    dangling: Option<H>,
+    _marker: PhantomData<fn(H) -> H>,
+}
+
+unsafe impl<'a, T, H> const StoreDangling for StackChainStore<'a, T, H>
+where
+    H: Copy,
+{
+    type Handle = H;
+
+    fn dangling(&self, alignment: Alignment) -> Result<Self::Handle, AllocError> {
+        //  The start of the first block is suitably aligned for any alignment up to that of `T`.
+        if self.blocks.is_empty() || alignment.as_usize() > mem::align_of::<T>() {
+            return Err(AllocError);
+        }
+
+        match self.dangling {
+            Some(handle) => Ok(handle),
+            None => Err(AllocError),
+        }
+    }
+}
+
+unsafe impl<'a, T, H> Store for StackChainStore<'a, T, H>
+where
+    H: Copy + TryFrom<usize> + TryInto<usize>,
+{
+    fn allocate(&self, layout: Layout) -> Result<(Self::Handle, usize), AllocError> {
+        self.allocate_with(layout, Store::allocate)
+    }
+
+    #[inline(always)]
+    unsafe fn deallocate(&self, _handle: Self::Handle, _layout: Layout) {}
+
+    #[inline(always)]
+    unsafe fn resolve(&self, handle: Self::Handle) -> NonNull<u8> {
+        let (index, offset) = Self::decode(handle);
+
+        debug_assert!(index < self.blocks.len());
+
+        //  Safety:
+        //  -   `offset` was allocated by the store of the `index`-th block, as per pre-conditions.
+        unsafe { Store::resolve(&self.block_store(index), offset) }
+    }
+
+    unsafe fn resolve_many(&self, handles: &[Self::Handle], out: &mut [MaybeUninit<NonNull<u8>>]) {
+        assert!(handles.len() <= out.len());
+
+        //  Consecutive handles are likely to point into the same block, whose store is then reused.
+        let mut current: Option<(usize, StackBumpStore<'a, usize>)> = None;
+
+        for (handle, out) in handles.iter().zip(out) {
+            let (index, offset) = Self::decode(*handle);
+
+            debug_assert!(index < self.blocks.len());
+
+            if !matches!(current, Some((current, _)) if current == index) {
+                current = Some((index, self.block_store(index)));
+            }
+
+            let Some((_, store)) = &current else {
+                unreachable!("`current` was just set")
+            };
+
+            //  Safety:
+            //  -   `offset` was allocated by the store of the `index`-th block, as per pre-conditions.
+            out.write(unsafe { Store::resolve(store, offset) });
+        }
+    }
+
+    unsafe fn grow(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        let (handle, size, _) = unsafe { self.grow_reporting(handle, old_layout, new_layout)? };
+
+        Ok((handle, size))
+    }
+
+    #[inline(always)]
+    unsafe fn shrink(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        let (handle, size, _) = unsafe { self.shrink_reporting(handle, old_layout, new_layout)? };
+
+        Ok((handle, size))
+    }
+
+    unsafe fn grow_reporting(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        debug_assert!(
+            new_layout.size() >= old_layout.size(),
+            "{new_layout:?} must have a greater size than {old_layout:?}"
+        );
+
+        let (index, offset) = Self::decode(handle);
+
+        //  Safety:
+        //  -   `offset` was allocated by the store of the `index`-th block, as per pre-conditions.
+        //  -   `old_layout` and `new_layout` are suitable, as per pre-conditions.
+        let result = unsafe { Store::grow_reporting(&self.block_store(index), offset, old_layout, new_layout) };
+
+        if let Ok((offset, size, relocated)) = result {
+            return Ok((Self::encode(index, offset)?, size, relocated));
+        }
+
+        let (handle, size) = self.grow_by_spilling(handle, old_layout, new_layout, false)?;
+
+        Ok((handle, size, true))
+    }
+
+    #[inline(always)]
+    unsafe fn shrink_reporting(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        debug_assert!(
+            new_layout.size() <= old_layout.size(),
+            "{new_layout:?} must have a smaller size than {old_layout:?}"
+        );
+
+        let (index, offset) = Self::decode(handle);
+
+        //  Safety:
+        //  -   `offset` was allocated by the store of the `index`-th block, as per pre-conditions.
+        //  -   `old_layout` and `new_layout` are suitable, as per pre-conditions.
+        let result = unsafe { Store::shrink_reporting(&self.block_store(index), offset, old_layout, new_layout) };
+        let (offset, size, relocated) = result?;
+
+        Ok((Self::encode(index, offset)?, size, relocated))
+    }
+
+    fn allocate_zeroed(&self, layout: Layout) -> Result<(Self::Handle, usize), AllocError> {
+        self.allocate_with(layout, Store::allocate_zeroed)
+    }
+
+    unsafe fn grow_zeroed(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        debug_assert!(
+            new_layout.size() >= old_layout.size(),
+            "{new_layout:?} must have a greater size than {old_layout:?}"
+        );
+
+        let (index, offset) = Self::decode(handle);
+
+        //  Safety:
+        //  -   `offset` was allocated by the store of the `index`-th block, as per pre-conditions.
+        //  -   `old_layout` and `new_layout` are suitable, as per pre-conditions.
+        let result = unsafe { Store::grow_zeroed(&self.block_store(index), offset, old_layout, new_layout) };
+
+        if let Ok((offset, size)) = result {
+            return Ok((Self::encode(index, offset)?, size));
+        }
+
+        self.grow_by_spilling(handle, old_layout, new_layout, true)
+    }
+}
+
+unsafe impl<'a, T, H> StoreSingle for StackChainStore<'a, T, H>
+where
+    H: Copy + TryFrom<usize> + TryInto<usize>,
+{
+    #[inline(always)]
+    unsafe fn resolve(&self, handle: Self::Handle) -> NonNull<u8> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { <Self as Store>::resolve(self, handle) }
+    }
+
+    #[inline(always)]
+    unsafe fn resolve_mut(&mut self, handle: Self::Handle) -> NonNull<u8> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { <Self as Store>::resolve(self, handle) }
+    }
+
+    fn allocate(&mut self, layout: Layout) -> Result<(Self::Handle, usize), AllocError> {
+        <Self as Store>::allocate(self, layout)
+    }
+
+    #[inline(always)]
+    unsafe fn deallocate(&mut self, _handle: Self::Handle, _layout: Layout) {}
+
+    unsafe fn grow(
+        &mut self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { <Self as Store>::grow(self, handle, old_layout, new_layout) }
+    }
+
+    #[inline(always)]
+    unsafe fn shrink(
+        &mut self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { <Self as Store>::shrink(self, handle, old_layout, new_layout) }
+    }
+
+    fn allocate_zeroed(&mut self, layout: Layout) -> Result<(Self::Handle, usize), AllocError> {
+        <Self as Store>::allocate_zeroed(self, layout)
+    }
+
+    unsafe fn grow_zeroed(
+        &mut self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { <Self as Store>::grow_zeroed(self, handle, old_layout, new_layout) }
+    }
+
+    unsafe fn grow_reporting(
+        &mut self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { <Self as Store>::grow_reporting(self, handle, old_layout, new_layout) }
+    }
+
+    unsafe fn shrink_reporting(
+        &mut self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { <Self as Store>::shrink_reporting(self, handle, old_layout, new_layout) }
+    }
+}
+
+crate::store_markers! {
+    //  Safety:
+    //  -   `self.resolve(handle)` always returns the same address.
+    unsafe impl['a, T, H] StorePinning for StackChainStore<'a, T, H> where [H: Copy + TryFrom<usize> + TryInto<usize>];
+}
+
+/// Safety:
+/// -   All instances referencing the same StackBumpBlocks are fungible.
+unsafe impl<'a, T, H> StoreSharing for StackChainStore<'a, T, H>
+where
+    H: Copy + TryFrom<usize> + TryInto<usize>,
+{
+    type SharingError = !;
+
+    type SharingToken = (usize, usize);
+
+    fn is_sharing_with(&self, other: &Self) -> bool {
+        ptr::eq(self.blocks, other.blocks)
+    }
+
+    fn sharing_token(&self) -> Self::SharingToken {
+        (self.blocks.as_ptr().addr(), self.blocks.len())
+    }
+
+    fn share(&self) -> Result<Self, Self::SharingError>
+    where
+        Self: Sized,
+    {
+        let (blocks, dangling) = (self.blocks, self.dangling);
+        let _marker = PhantomData;
+
+        Ok(Self {
+            blocks,
+            dangling,
+            _marker,
+        })
+    }
+}
+
+//  Safety:
+//  -   Handles encode the offset within their block in their lower part, and a block of memory never straddles two
+//      blocks, hence advancing the offset within the block of memory advances the resolved pointer.
+unsafe impl<'a, T, H> StoreOffsetHandle for StackChainStore<'a, T, H>
+where
+    H: Copy + TryFrom<usize> + TryInto<usize>,
+{
+    #[inline(always)]
+    unsafe fn offset(handle: Self::Handle, bytes: usize) -> Self::Handle {
+        let (index, offset) = Self::decode(handle);
+
+        let Ok(handle) = Self::encode(index, offset + bytes) else {
+            panic!("Advanced handle cannot be represented")
+        };
+
+        handle
+    }
+}
+
+//  A single allocation never spans multiple blocks, and may not be aligned beyond `T`, as per `allocate_with`.
+impl<'a, T, H> StoreIntrospect for StackChainStore<'a, T, H>
+where
+    H: Copy,
+{
+    fn limits(&self) -> StoreLimits {
+        let max_size = if self.blocks.is_empty() { 0 } else { mem::size_of::<T>() };
+
+        StoreLimits {
+            max_align: Alignment::of::<T>(),
+            max_size,
+            supports_dealloc_reuse: false,
+        }
+    }
+}
+
+//  Like `StackBumpStore`, the blocks are borrowed, and flushing them, should they be mapped onto a persistent medium,
+//  is up to their owner.
+impl<'a, T, H> StorePersist for StackChainStore<'a, T, H> where H: Copy {}
+
+impl<'a, T, H> StoreValidate for StackChainStore<'a, T, H>
+where
+    H: Copy + TryFrom<usize> + TryInto<usize>,
+{
+    fn is_valid(&self, handle: Self::Handle) -> Option<bool> {
+        let (index, offset) = Self::decode(handle);
+
+        if index >= self.blocks.len() {
+            return Some(false);
+        }
+
+        self.block_store(index).is_valid(offset)
+    }
+}
+
+impl<'a, T, H> fmt::Debug for StackChainStore<'a, T, H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_struct("StackChainStore")
+            .field("blocks", &self.blocks.len())
+            .field("memory", &mem::size_of::<T>())
+            .finish()
+    }
+}
+
+//
+//  Implementation
+//
+
+impl<'a, T, H> StackChainStore<'a, T, H> {
+    //  Distance between the offsets of two consecutive blocks.
+    //
+    //  An offset within a block ranges from 0 to the size of the block, both included, hence the extra byte.
+    const STRIDE: usize = mem::size_of::<T>() + 1;
+
+    //  Returns a store referencing the `index`-th block.
+    //
+    //  Panics if `index` is out of bounds.
+    #[inline(always)]
+    fn block_store(&self, index: usize) -> StackBumpStore<'a, usize> {
+        self.blocks[index].create_store()
+    }
+}
+
+impl<'a, T, H> StackChainStore<'a, T, H>
+where
+    H: TryFrom<usize>,
+{
+    #[inline(always)]
+    fn encode(index: usize, offset: usize) -> Result<H, AllocError> {
+        debug_assert!(offset < Self::STRIDE);
+
+        let handle = index
+            .checked_mul(Self::STRIDE)
+            .and_then(|start| start.checked_add(offset))
+            .ok_or(AllocError)?;
+
+        handle.try_into().map_err(|_| AllocError)
+    }
+}
+
+impl<'a, T, H> StackChainStore<'a, T, H>
+where
+    H: TryInto<usize>,
+{
+    #[inline(always)]
+    fn decode(handle: H) -> (usize, usize) {
+        let handle = handle.try_into();
+
+        debug_assert!(handle.is_ok());
+
+        //  Safety:
+        //  -   `handle` was created from `usize`, hence converting back always succeeds.
+        let handle = unsafe { handle.unwrap_unchecked() };
+
+        (handle / Self::STRIDE, handle % Self::STRIDE)
+    }
+}
+
+impl<'a, T, H> StackChainStore<'a, T, H>
+where
+    H: Copy + TryFrom<usize> + TryInto<usize>,
+{
+    //  Allocates a block of memory in the first block with enough room left, via `allocate`.
+    fn allocate_with<F>(&self, layout: Layout, allocate: F) -> Result<(H, usize), AllocError>
+    where
+        F: Fn(&StackBumpStore<'a, usize>, Layout) -> Result<(usize, usize), AllocError>,
+    {
+        if layout.align() > mem::align_of::<T>() {
+            return Err(AllocError);
+        }
+
+        for index in 0..self.blocks.len() {
+            let store = self.block_store(index);
+
+            if let Ok((offset, size)) = allocate(&store, layout) {
+                return Ok((Self::encode(index, offset)?, size));
+            }
+        }
+
+        Err(AllocError)
+    }
+
+    //  Slow part of `grow` and `grow_zeroed`, relocating the allocation to another block.
+    #[inline(never)]
+    fn grow_by_spilling(
+        &self,
+        handle: H,
+        old_layout: Layout,
+        new_layout: Layout,
+        zeroed: bool,
+    ) -> Result<(H, usize), AllocError> {
+        let (result, size) = Store::allocate(self, new_layout)?;
+
+        //  Safety:
+        //  -   `handle` is valid, as per pre-conditions.
+        //  -   `result` is valid, since newly allocated.
+        let (new, old) = unsafe { (Store::resolve(self, result), Store::resolve(self, handle)) };
+
+        //  Safety:
+        //  -   `old` is valid for `old_layout.size()` bytes, as per pre-conditions.
+        //  -   `new` is valid for `old_layout.size()` bytes, since it is valid for `new_layout.size()` bytes and as per
+        //      pre-conditions `new_layout.size() >= old_layout.size()`.
+        //  -   `old` and `new` are at least 1-byte aligned.
+        //  -   `old` and `new` point to non-overlapping areas, since `new` was freshly allocated while `old` is still
+        //      allocated.
+        unsafe { ptr::copy_nonoverlapping(old.as_ptr(), new.as_ptr(), old_layout.size()) };
+
+        //  Past the copied data, the block of memory has never been handed out.
+        if zeroed && !self.blocks[Self::decode(result).0].is_zeroed() {
+            //  Safety:
+            //  -   `new` is valid for `size` bytes, since newly allocated, and `old_layout.size() <= size`.
+            //  -   Access to the block of memory is exclusive, since newly allocated.
+            unsafe { ptr::write_bytes(new.as_ptr().add(old_layout.size()), 0, size - old_layout.size()) };
+        }
+
+        Ok((result, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grow_zeroed_spilling() {
+        let zeroed = [StackBumpBlock::<[u8; 8]>::new_zeroed(), StackBumpBlock::new_zeroed()];
+        let dirty = [StackBumpBlock::<[u8; 8]>::new(), StackBumpBlock::new()];
+
+        for blocks in [&zeroed, &dirty] {
+            let blocks = [&blocks[0], &blocks[1]];
+            let store = StackBumpBlock::chain::<u8>(&blocks);
+
+            let (handle, size) = Store::allocate_zeroed(&store, Layout::new::<[u8; 6]>()).unwrap();
+
+            //  Safety:
+            //  -   `handle` was allocated by `store`, and is still valid, for `size` bytes.
+            unsafe { ptr::write_bytes(Store::resolve(&store, handle).as_ptr(), 0xAA, size) };
+
+            //  Prevent growing in place.
+            Store::allocate(&store, Layout::new::<u8>()).unwrap();
+
+            //  Safety:
+            //  -   `handle` was allocated by `store`, with a fitting layout.
+            let (grown, size) =
+                unsafe { Store::grow_zeroed(&store, handle, Layout::new::<[u8; 6]>(), Layout::new::<[u8; 8]>()) }
+                    .unwrap();
+
+            //  The first block has no room left, hence the allocation spilled over into the second.
+            assert_eq!((1, 0), StackChainStore::<[u8; 8], u8>::decode(grown));
+
+            //  Safety:
+            //  -   `grown` was allocated by `store`, and is still valid, for `size` bytes.
+            let bytes = unsafe { Store::resolve(&store, grown).cast::<[u8; 8]>().read() };
+
+            assert_eq!([0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0, 0], bytes);
+            assert_eq!(8, size);
+        }
+    }
+
+    #[test]
+    fn grow_reporting() {
+        let blocks = [StackBumpBlock::<[u8; 8]>::new(), StackBumpBlock::new()];
+        let blocks = [&blocks[0], &blocks[1]];
+        let store = StackBumpBlock::chain::<u8>(&blocks);
+
+        let (handle, _) = Store::allocate(&store, Layout::new::<[u8; 2]>()).unwrap();
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, with a fitting layout.
+        let grown =
+            unsafe { Store::grow_reporting(&store, handle, Layout::new::<[u8; 2]>(), Layout::new::<[u8; 4]>()) };
+
+        assert_eq!(Ok((handle, 4, false)), grown);
+
+        //  Prevent growing in place.
+        Store::allocate(&store, Layout::new::<u8>()).unwrap();
+
+        //  Safety:
+        //  -   `handle` is still valid, as it was grown in place, with a fitting layout.
+        let (grown, _, relocated) =
+            unsafe { Store::grow_reporting(&store, handle, Layout::new::<[u8; 4]>(), Layout::new::<[u8; 6]>()) }
+                .unwrap();
+
+        //  The first block has no room left, hence the allocation spilled over into the second.
+        assert!(relocated);
+        assert_eq!((1, 0), StackChainStore::<[u8; 8], u8>::decode(grown));
+    }
+} // mod tests
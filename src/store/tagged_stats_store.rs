@@ -0,0 +1,449 @@
+//! A Store aggregating byte counts per tag.
+//!
+//! An adapter over another store, which attributes each allocation to a tag, as per `StoreTagged`, and aggregates the
+//! number of live bytes per tag in a fixed-capacity table. Untagged operations are attributed to tag 0.
+//!
+//! Use `TaggedStatsStore::tagged` to attribute the allocations of a collection to a given tag.
+//!
+//! The counts are based on the layouts passed by the callers, which may be larger than those requested at allocation
+//! when callers remember the actual size of the block of memory, see "Memory Fitting" in `Store`. Hence, rounding
+//! aside, the counts are approximate.
+
+use core::{
+    alloc::{AllocError, Layout},
+    array,
+    cell::Cell,
+    cmp, fmt,
+    mem::MaybeUninit,
+    ptr::{Alignment, NonNull},
+};
+
+use crate::{
+    interface::{
+        Growth, PersistError, Store, StoreDangling, StoreIntrospect, StoreLimits, StorePersist, StoreTagged,
+        StoreValidate,
+    },
+    store::TaggedStore,
+};
+
+/// The statistics of a single tag.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct TagStats {
+    tag: u32,
+    live: usize,
+    peak: usize,
+    allocations: usize,
+}
+
+impl TagStats {
+    /// Returns the tag.
+    pub const fn tag(&self) -> u32 {
+        self.tag
+    }
+
+    /// Returns the number of bytes currently allocated.
+    pub const fn live(&self) -> usize {
+        self.live
+    }
+
+    /// Returns the greatest number of bytes allocated at any one time.
+    pub const fn peak(&self) -> usize {
+        self.peak
+    }
+
+    /// Returns the number of allocations performed so far.
+    pub const fn allocations(&self) -> usize {
+        self.allocations
+    }
+}
+
+/// A store aggregating the byte counts of its underlying store, for up to `N` distinct tags.
+///
+/// Allocating for a new tag fails once `N` distinct tags have been used.
+pub struct TaggedStatsStore<S, const N: usize> {
+    store: S,
+    tags: [Cell<Option<TagStats>>; N],
+}
+
+impl<S, const N: usize> TaggedStatsStore<S, N> {
+    /// Creates a new instance, wrapping `store`.
+    pub fn new(store: S) -> Self {
+        let tags = array::from_fn(|_| Cell::new(None));
+
+        Self { store, tags }
+    }
+
+    /// Returns a reference to the underlying store.
+    pub const fn get(&self) -> &S {
+        &self.store
+    }
+
+    /// Returns a store borrowing `self`, attributing all its allocations to `tag`.
+    pub const fn tagged(&self, tag: u32) -> TaggedStore<'_, Self> {
+        TaggedStore::new(self, tag)
+    }
+
+    /// Returns the statistics of `tag`, if any allocation was ever attributed to it.
+    pub fn stats(&self, tag: u32) -> Option<TagStats> {
+        self.slot(tag).and_then(Cell::get)
+    }
+
+    /// Calls `f` with the statistics of each tag any allocation was ever attributed to.
+    pub fn for_each_tag(&self, f: impl FnMut(TagStats)) {
+        self.tags.iter().filter_map(Cell::get).for_each(f);
+    }
+}
+
+unsafe impl<S, const N: usize> StoreDangling for TaggedStatsStore<S, N>
+where
+    S: StoreDangling,
+{
+    type Handle = S::Handle;
+
+    #[inline(always)]
+    fn dangling(&self, alignment: Alignment) -> Result<Self::Handle, AllocError> {
+        self.store.dangling(alignment)
+    }
+}
+
+unsafe impl<S, const N: usize> Store for TaggedStatsStore<S, N>
+where
+    S: Store,
+{
+    const GROWTH: Growth = <S as Store>::GROWTH;
+
+    #[inline(always)]
+    unsafe fn resolve(&self, handle: Self::Handle) -> NonNull<u8> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.store.resolve(handle) }
+    }
+
+    #[inline(always)]
+    unsafe fn resolve_many(&self, handles: &[Self::Handle], out: &mut [MaybeUninit<NonNull<u8>>]) {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.store.resolve_many(handles, out) }
+    }
+
+    fn allocate(&self, layout: Layout) -> Result<(Self::Handle, usize), AllocError> {
+        self.allocate_tagged(layout, 0)
+    }
+
+    unsafe fn deallocate(&self, handle: Self::Handle, layout: Layout) {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.deallocate_tagged(handle, layout, 0) }
+    }
+
+    unsafe fn grow(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.grow_tagged(handle, old_layout, new_layout, 0) }
+    }
+
+    unsafe fn shrink(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.shrink_tagged(handle, old_layout, new_layout, 0) }
+    }
+
+    unsafe fn grow_reporting(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.grow_reporting_tagged(handle, old_layout, new_layout, 0) }
+    }
+
+    unsafe fn shrink_reporting(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.shrink_reporting_tagged(handle, old_layout, new_layout, 0) }
+    }
+}
+
+unsafe impl<S, const N: usize> StoreTagged for TaggedStatsStore<S, N>
+where
+    S: Store,
+{
+    fn allocate_tagged(&self, layout: Layout, tag: u32) -> Result<(Self::Handle, usize), AllocError> {
+        let slot = self.slot_or_vacant(tag).ok_or(AllocError)?;
+
+        let result = self.store.allocate(layout)?;
+
+        Self::account(slot, tag, layout.size(), 0);
+
+        Ok(result)
+    }
+
+    unsafe fn deallocate_tagged(&self, handle: Self::Handle, layout: Layout, tag: u32) {
+        if let Some(slot) = self.slot(tag) {
+            Self::account(slot, tag, 0, layout.size());
+        }
+
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.store.deallocate(handle, layout) }
+    }
+
+    unsafe fn grow_tagged(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+        tag: u32,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        let slot = self.slot_or_vacant(tag).ok_or(AllocError)?;
+
+        //  Safety:
+        //  -   As per pre-conditions.
+        let result = unsafe { self.store.grow(handle, old_layout, new_layout)? };
+
+        Self::account(slot, tag, new_layout.size(), old_layout.size());
+
+        Ok(result)
+    }
+
+    unsafe fn shrink_tagged(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+        tag: u32,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        let result = unsafe { self.store.shrink(handle, old_layout, new_layout)? };
+
+        if let Some(slot) = self.slot(tag) {
+            Self::account(slot, tag, new_layout.size(), old_layout.size());
+        }
+
+        Ok(result)
+    }
+
+    unsafe fn grow_reporting_tagged(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+        tag: u32,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        let slot = self.slot_or_vacant(tag).ok_or(AllocError)?;
+
+        //  Safety:
+        //  -   As per pre-conditions.
+        let result = unsafe { self.store.grow_reporting(handle, old_layout, new_layout)? };
+
+        Self::account(slot, tag, new_layout.size(), old_layout.size());
+
+        Ok(result)
+    }
+
+    unsafe fn shrink_reporting_tagged(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+        tag: u32,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        let result = unsafe { self.store.shrink_reporting(handle, old_layout, new_layout)? };
+
+        if let Some(slot) = self.slot(tag) {
+            Self::account(slot, tag, new_layout.size(), old_layout.size());
+        }
+
+        Ok(result)
+    }
+}
+
+crate::store_markers! {
+    //  Safety:
+    //  -   The handles are passed through unchanged, hence are as stable, or pinning, as those of `S`.
+    unsafe impl[S, const N: usize] StorePinning for TaggedStatsStore<S, N> => S;
+}
+
+impl<S, const N: usize> StoreValidate for TaggedStatsStore<S, N>
+where
+    S: StoreValidate,
+{
+    fn is_valid(&self, handle: Self::Handle) -> Option<bool> {
+        self.store.is_valid(handle)
+    }
+}
+
+impl<S, const N: usize> StorePersist for TaggedStatsStore<S, N>
+where
+    S: StorePersist,
+{
+    unsafe fn flush(&self, handle: Self::Handle, layout: Layout) {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.store.flush(handle, layout) }
+    }
+
+    fn snapshot(&self) -> Result<(), PersistError> {
+        self.store.snapshot()
+    }
+}
+
+impl<S, const N: usize> StoreIntrospect for TaggedStatsStore<S, N>
+where
+    S: StoreIntrospect,
+{
+    fn limits(&self) -> StoreLimits {
+        self.store.limits()
+    }
+}
+
+impl<S, const N: usize> fmt::Debug for TaggedStatsStore<S, N>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        let tags: [Option<TagStats>; N] = array::from_fn(|index| self.tags[index].get());
+
+        f.debug_struct("TaggedStatsStore")
+            .field("store", &self.store)
+            .field("tags", &tags)
+            .finish()
+    }
+}
+
+//
+//  Implementation
+//
+
+type Slot = Cell<Option<TagStats>>;
+
+impl<S, const N: usize> TaggedStatsStore<S, N> {
+    //  Returns the slot of `tag`, if any.
+    fn slot(&self, tag: u32) -> Option<&Slot> {
+        self.tags
+            .iter()
+            .find(|slot| slot.get().is_some_and(|stats| stats.tag == tag))
+    }
+
+    //  Returns the slot of `tag`, or the first vacant slot, if any.
+    fn slot_or_vacant(&self, tag: u32) -> Option<&Slot> {
+        self.slot(tag)
+            .or_else(|| self.tags.iter().find(|slot| slot.get().is_none()))
+    }
+
+    //  Accounts for `added` bytes allocated, and `removed` bytes deallocated, to `tag` in `slot`.
+    //
+    //  A new allocation is counted whenever no byte is removed.
+    fn account(slot: &Slot, tag: u32, added: usize, removed: usize) {
+        let mut stats = slot.get().unwrap_or(TagStats {
+            tag,
+            live: 0,
+            peak: 0,
+            allocations: 0,
+        });
+
+        stats.live = stats.live.saturating_add(added).saturating_sub(removed);
+        stats.peak = cmp::max(stats.peak, stats.live);
+
+        if removed == 0 && added > 0 {
+            stats.allocations += 1;
+        }
+
+        slot.set(Some(stats));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        collection::{StoreBox, StoreVec},
+        store::InlineBumpStore,
+    };
+
+    use super::*;
+
+    type TestStore = TaggedStatsStore<InlineBumpStore<u16, [u64; 32]>, 2>;
+
+    const AUDIO: u32 = 1;
+    const VIDEO: u32 = 2;
+
+    #[test]
+    fn per_tag() {
+        let store = TestStore::new(InlineBumpStore::default());
+
+        let mut samples = StoreVec::with_capacity_in(2, store.tagged(AUDIO));
+        let frame = StoreBox::new_in([0u8; 24], store.tagged(VIDEO));
+
+        samples.extend([1u32, 2, 3]);
+
+        let audio = store.stats(AUDIO).unwrap();
+
+        assert_eq!((12, 12, 1), (audio.live(), audio.peak(), audio.allocations()));
+        assert_eq!(24, store.stats(VIDEO).unwrap().live());
+
+        drop(frame);
+        drop(samples);
+
+        let (audio, video) = (store.stats(AUDIO).unwrap(), store.stats(VIDEO).unwrap());
+
+        assert_eq!((0, 12), (audio.live(), audio.peak()));
+        assert_eq!((0, 24), (video.live(), video.peak()));
+        assert_eq!(None, store.stats(0));
+    }
+
+    #[test]
+    fn grow_reporting_in_place() {
+        let store = TestStore::new(InlineBumpStore::default());
+        let tagged = store.tagged(AUDIO);
+
+        let (old_layout, new_layout) = (Layout::new::<[u32; 2]>(), Layout::new::<[u32; 4]>());
+
+        let (handle, _) = tagged.allocate(old_layout).unwrap();
+
+        //  Safety:
+        //  -   `handle` was allocated by `tagged`, with `old_layout`, and is still valid.
+        //  -   `new_layout` is larger than `old_layout`, with the same alignment.
+        let (_, _, relocated) = unsafe { tagged.grow_reporting(handle, old_layout, new_layout).unwrap() };
+
+        assert!(!relocated);
+        assert_eq!(16, store.stats(AUDIO).unwrap().live());
+    }
+
+    #[test]
+    fn tags_exhausted() {
+        let store = TestStore::new(InlineBumpStore::default());
+        let layout = Layout::new::<u8>();
+
+        store.allocate_tagged(layout, AUDIO).unwrap();
+        store.allocate_tagged(layout, VIDEO).unwrap();
+
+        //  Untagged allocations are attributed to tag 0, a third tag.
+        assert_eq!(Err(AllocError), Store::allocate(&store, layout).map(|_| ()));
+
+        let mut tags = Vec::new();
+
+        store.for_each_tag(|stats| tags.push((stats.tag(), stats.live())));
+
+        assert_eq!(vec![(AUDIO, 1), (VIDEO, 1)], tags);
+    }
+} // mod tests
@@ -0,0 +1,299 @@
+//! A borrowing Store attributing all its allocations to a single tag.
+//!
+//! A store which borrows a `StoreTagged`, and forwards each operation to its tagged counterpart, with a fixed tag. This
+//! allows attributing the allocations of any collection, without the collection being aware of tags: the tag is
+//! threaded through the store parameter of its constructor.
+//!
+//! ```
+//! #![feature(allocator_api)]
+//!
+//! use storage::{
+//!     collection::StoreVec,
+//!     store::{InlineBumpStore, TaggedStatsStore},
+//! };
+//!
+//! const NETWORK: u32 = 1;
+//!
+//! let store = TaggedStatsStore::<_, 4>::new(InlineBumpStore::<u16, [u64; 32]>::default());
+//!
+//! let mut vec = StoreVec::with_capacity_in(4, store.tagged(NETWORK));
+//! vec.push(1u32);
+//!
+//! assert_eq!(16, store.stats(NETWORK).unwrap().live());
+//! ```
+
+use core::{
+    alloc::{AllocError, Layout},
+    fmt,
+    mem::MaybeUninit,
+    ptr::{Alignment, NonNull},
+};
+
+use crate::interface::{
+    Growth, PersistError, Store, StoreDangling, StoreIntrospect, StoreLimits, StorePersist, StoreSingle, StoreStable,
+    StoreTagged, StoreValidate,
+};
+
+/// A store borrowing its underlying store, attributing all its allocations to `tag`.
+///
+/// All copies of an instance are fungible.
+pub struct TaggedStore<'a, S> {
+    store: &'a S,
+    tag: u32,
+}
+
+impl<'a, S> TaggedStore<'a, S> {
+    /// Creates a new instance, borrowing `store`, and attributing all allocations to `tag`.
+    pub const fn new(store: &'a S, tag: u32) -> Self {
+        Self { store, tag }
+    }
+
+    /// Returns a reference to the underlying store.
+    pub const fn get(&self) -> &'a S {
+        self.store
+    }
+
+    /// Returns the tag all allocations are attributed to.
+    pub const fn tag(&self) -> u32 {
+        self.tag
+    }
+}
+
+unsafe impl<'a, S> StoreDangling for TaggedStore<'a, S>
+where
+    S: StoreDangling,
+{
+    type Handle = S::Handle;
+
+    #[inline(always)]
+    fn dangling(&self, alignment: Alignment) -> Result<Self::Handle, AllocError> {
+        self.store.dangling(alignment)
+    }
+}
+
+unsafe impl<'a, S> Store for TaggedStore<'a, S>
+where
+    S: StoreTagged,
+{
+    const GROWTH: Growth = <S as Store>::GROWTH;
+
+    #[inline(always)]
+    unsafe fn resolve(&self, handle: Self::Handle) -> NonNull<u8> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.store.resolve(handle) }
+    }
+
+    #[inline(always)]
+    unsafe fn resolve_many(&self, handles: &[Self::Handle], out: &mut [MaybeUninit<NonNull<u8>>]) {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.store.resolve_many(handles, out) }
+    }
+
+    #[inline(always)]
+    fn allocate(&self, layout: Layout) -> Result<(Self::Handle, usize), AllocError> {
+        self.store.allocate_tagged(layout, self.tag)
+    }
+
+    #[inline(always)]
+    unsafe fn deallocate(&self, handle: Self::Handle, layout: Layout) {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.store.deallocate_tagged(handle, layout, self.tag) }
+    }
+
+    #[inline(always)]
+    unsafe fn grow(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.store.grow_tagged(handle, old_layout, new_layout, self.tag) }
+    }
+
+    #[inline(always)]
+    unsafe fn shrink(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.store.shrink_tagged(handle, old_layout, new_layout, self.tag) }
+    }
+
+    #[inline(always)]
+    unsafe fn grow_reporting(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe {
+            self.store
+                .grow_reporting_tagged(handle, old_layout, new_layout, self.tag)
+        }
+    }
+
+    #[inline(always)]
+    unsafe fn shrink_reporting(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe {
+            self.store
+                .shrink_reporting_tagged(handle, old_layout, new_layout, self.tag)
+        }
+    }
+}
+
+unsafe impl<'a, S> StoreSingle for TaggedStore<'a, S>
+where
+    S: StoreTagged,
+{
+    const GROWTH: Growth = <S as Store>::GROWTH;
+
+    #[inline(always)]
+    unsafe fn resolve(&self, handle: Self::Handle) -> NonNull<u8> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { <Self as Store>::resolve(self, handle) }
+    }
+
+    #[inline(always)]
+    unsafe fn resolve_mut(&mut self, handle: Self::Handle) -> NonNull<u8> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { <Self as Store>::resolve(self, handle) }
+    }
+
+    #[inline(always)]
+    fn allocate(&mut self, layout: Layout) -> Result<(Self::Handle, usize), AllocError> {
+        <Self as Store>::allocate(self, layout)
+    }
+
+    #[inline(always)]
+    unsafe fn deallocate(&mut self, handle: Self::Handle, layout: Layout) {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { <Self as Store>::deallocate(self, handle, layout) }
+    }
+
+    #[inline(always)]
+    unsafe fn grow(
+        &mut self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { <Self as Store>::grow(self, handle, old_layout, new_layout) }
+    }
+
+    #[inline(always)]
+    unsafe fn shrink(
+        &mut self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { <Self as Store>::shrink(self, handle, old_layout, new_layout) }
+    }
+
+    #[inline(always)]
+    unsafe fn grow_reporting(
+        &mut self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { <Self as Store>::grow_reporting(self, handle, old_layout, new_layout) }
+    }
+
+    #[inline(always)]
+    unsafe fn shrink_reporting(
+        &mut self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { <Self as Store>::shrink_reporting(self, handle, old_layout, new_layout) }
+    }
+}
+
+crate::store_markers! {
+    //  Safety:
+    //  -   `S` is stable, and cannot move while borrowed, even when `self` is moved.
+    unsafe impl['a, S] StorePinning for TaggedStore<'a, S> where [S: StoreStable];
+}
+
+impl<'a, S> StoreValidate for TaggedStore<'a, S>
+where
+    S: StoreValidate,
+{
+    fn is_valid(&self, handle: Self::Handle) -> Option<bool> {
+        self.store.is_valid(handle)
+    }
+}
+
+impl<'a, S> StorePersist for TaggedStore<'a, S>
+where
+    S: StorePersist,
+{
+    unsafe fn flush(&self, handle: Self::Handle, layout: Layout) {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.store.flush(handle, layout) }
+    }
+
+    fn snapshot(&self) -> Result<(), PersistError> {
+        self.store.snapshot()
+    }
+}
+
+impl<'a, S> StoreIntrospect for TaggedStore<'a, S>
+where
+    S: StoreIntrospect,
+{
+    fn limits(&self) -> StoreLimits {
+        self.store.limits()
+    }
+}
+
+impl<'a, S> Clone for TaggedStore<'a, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, S> Copy for TaggedStore<'a, S> {}
+
+impl<'a, S> fmt::Debug for TaggedStore<'a, S>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_struct("TaggedStore")
+            .field("store", self.store)
+            .field("tag", &self.tag)
+            .finish()
+    }
+}
@@ -0,0 +1,398 @@
+//! A Store tracking its live allocations.
+//!
+//! An adapter over another store, which records the handle and layout of each live allocation in a fixed-capacity
+//! table, so as to be able to enumerate them, and to validate handles precisely.
+//!
+//! This store is mostly meant as a debugging aid, to dump the content of an arena, or to check for leaks in tests.
+
+use core::{
+    alloc::{AllocError, Layout},
+    array,
+    cell::Cell,
+    fmt,
+    mem::MaybeUninit,
+    ptr::{Alignment, NonNull},
+};
+
+use crate::interface::{
+    Growth, PersistError, Store, StoreDangling, StoreEnumerate, StoreIntrospect, StoreLimits, StoreOffsetHandle,
+    StorePersist, StoreValidate,
+};
+
+//  A slot of the table of live allocations, vacant if `None`.
+type Slot<H> = Cell<Option<(H, Layout)>>;
+
+/// A store tracking up to `N` live allocations of its underlying store.
+///
+/// Allocating fails once `N` allocations are live, until one of them is deallocated.
+pub struct TrackingStore<S, const N: usize>
+where
+    S: StoreDangling,
+{
+    store: S,
+    live: [Slot<S::Handle>; N],
+}
+
+impl<S, const N: usize> TrackingStore<S, N>
+where
+    S: StoreDangling,
+{
+    /// Creates a new instance, wrapping `store`.
+    pub fn new(store: S) -> Self {
+        let live = array::from_fn(|_| Cell::new(None));
+
+        Self { store, live }
+    }
+
+    /// Returns a reference to the underlying store.
+    pub const fn get(&self) -> &S {
+        &self.store
+    }
+
+    /// Returns the number of live allocations.
+    pub fn len(&self) -> usize {
+        self.live.iter().filter(|slot| slot.get().is_some()).count()
+    }
+
+    /// Returns whether there is no live allocation.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+unsafe impl<S, const N: usize> StoreDangling for TrackingStore<S, N>
+where
+    S: StoreDangling,
+{
+    type Handle = S::Handle;
+
+    #[inline(always)]
+    fn dangling(&self, alignment: Alignment) -> Result<Self::Handle, AllocError> {
+        self.store.dangling(alignment)
+    }
+}
+
+unsafe impl<S, const N: usize> Store for TrackingStore<S, N>
+where
+    S: Store,
+    S::Handle: PartialEq,
+{
+    const GROWTH: Growth = <S as Store>::GROWTH;
+
+    #[inline(always)]
+    unsafe fn resolve(&self, handle: Self::Handle) -> NonNull<u8> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.store.resolve(handle) }
+    }
+
+    #[inline(always)]
+    unsafe fn resolve_many(&self, handles: &[Self::Handle], out: &mut [MaybeUninit<NonNull<u8>>]) {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.store.resolve_many(handles, out) }
+    }
+
+    fn allocate(&self, layout: Layout) -> Result<(Self::Handle, usize), AllocError> {
+        let slot = self.vacant().ok_or(AllocError)?;
+
+        let (handle, size) = self.store.allocate(layout)?;
+
+        slot.set(Some((handle, layout)));
+
+        Ok((handle, size))
+    }
+
+    unsafe fn deallocate(&self, handle: Self::Handle, layout: Layout) {
+        if let Some(slot) = self.occupied(handle) {
+            slot.set(None);
+        }
+
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.store.deallocate(handle, layout) }
+    }
+
+    unsafe fn grow(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        let (new_handle, size) = unsafe { self.store.grow(handle, old_layout, new_layout)? };
+
+        self.replace(handle, new_handle, new_layout);
+
+        Ok((new_handle, size))
+    }
+
+    unsafe fn shrink(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        let (new_handle, size) = unsafe { self.store.shrink(handle, old_layout, new_layout)? };
+
+        self.replace(handle, new_handle, new_layout);
+
+        Ok((new_handle, size))
+    }
+
+    fn allocate_zeroed(&self, layout: Layout) -> Result<(Self::Handle, usize), AllocError> {
+        let slot = self.vacant().ok_or(AllocError)?;
+
+        let (handle, size) = self.store.allocate_zeroed(layout)?;
+
+        slot.set(Some((handle, layout)));
+
+        Ok((handle, size))
+    }
+
+    unsafe fn grow_zeroed(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        let (new_handle, size) = unsafe { self.store.grow_zeroed(handle, old_layout, new_layout)? };
+
+        self.replace(handle, new_handle, new_layout);
+
+        Ok((new_handle, size))
+    }
+
+    unsafe fn grow_reporting(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        let (new_handle, size, relocated) = unsafe { self.store.grow_reporting(handle, old_layout, new_layout)? };
+
+        self.replace(handle, new_handle, new_layout);
+
+        Ok((new_handle, size, relocated))
+    }
+
+    unsafe fn shrink_reporting(
+        &self,
+        handle: Self::Handle,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<(Self::Handle, usize, bool), AllocError> {
+        //  Safety:
+        //  -   As per pre-conditions.
+        let (new_handle, size, relocated) = unsafe { self.store.shrink_reporting(handle, old_layout, new_layout)? };
+
+        self.replace(handle, new_handle, new_layout);
+
+        Ok((new_handle, size, relocated))
+    }
+}
+
+crate::store_markers! {
+    //  Safety:
+    //  -   The handles are passed through unchanged, hence are as stable, or pinning, as those of `S`.
+    unsafe impl[S, const N: usize] StorePinning for TrackingStore<S, N> => S where [S: StoreDangling];
+}
+
+//  Safety:
+//  -   `S` advances handles, and the handles are passed through unchanged.
+unsafe impl<S, const N: usize> StoreOffsetHandle for TrackingStore<S, N>
+where
+    S: StoreOffsetHandle,
+{
+    #[inline(always)]
+    unsafe fn offset(handle: Self::Handle, bytes: usize) -> Self::Handle {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { S::offset(handle, bytes) }
+    }
+}
+
+impl<S, const N: usize> StoreValidate for TrackingStore<S, N>
+where
+    S: StoreDangling,
+    S::Handle: PartialEq,
+{
+    fn is_valid(&self, handle: Self::Handle) -> Option<bool> {
+        Some(self.occupied(handle).is_some())
+    }
+}
+
+impl<S, const N: usize> StorePersist for TrackingStore<S, N>
+where
+    S: StorePersist,
+{
+    unsafe fn flush(&self, handle: Self::Handle, layout: Layout) {
+        //  Safety:
+        //  -   As per pre-conditions.
+        unsafe { self.store.flush(handle, layout) }
+    }
+
+    fn snapshot(&self) -> Result<(), PersistError> {
+        self.store.snapshot()
+    }
+}
+
+impl<S, const N: usize> StoreEnumerate for TrackingStore<S, N>
+where
+    S: StoreDangling,
+{
+    fn for_each_handle(&self, mut f: impl FnMut(Self::Handle, Layout)) {
+        for slot in &self.live {
+            if let Some((handle, layout)) = slot.get() {
+                f(handle, layout);
+            }
+        }
+    }
+}
+
+impl<S, const N: usize> StoreIntrospect for TrackingStore<S, N>
+where
+    S: StoreIntrospect,
+{
+    fn limits(&self) -> StoreLimits {
+        self.store.limits()
+    }
+}
+
+impl<S, const N: usize> fmt::Debug for TrackingStore<S, N>
+where
+    S: StoreDangling + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_struct("TrackingStore")
+            .field("store", &self.store)
+            .field("live", &self.len())
+            .field("capacity", &N)
+            .finish()
+    }
+}
+
+//
+//  Implementation
+//
+
+impl<S, const N: usize> TrackingStore<S, N>
+where
+    S: StoreDangling,
+{
+    //  Returns the first vacant slot, if any.
+    fn vacant(&self) -> Option<&Slot<S::Handle>> {
+        self.live.iter().find(|slot| slot.get().is_none())
+    }
+}
+
+impl<S, const N: usize> TrackingStore<S, N>
+where
+    S: StoreDangling,
+    S::Handle: PartialEq,
+{
+    //  Returns the slot tracking `handle`, if any.
+    fn occupied(&self, handle: S::Handle) -> Option<&Slot<S::Handle>> {
+        self.live
+            .iter()
+            .find(|slot| slot.get().is_some_and(|(live, _)| live == handle))
+    }
+
+    //  Replaces the tracking of `old` by the tracking of `new`, with `layout`.
+    fn replace(&self, old: S::Handle, new: S::Handle, layout: Layout) {
+        if let Some(slot) = self.occupied(old) {
+            slot.set(Some((new, layout)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        collection::{StoreBox, StoreVec},
+        store::{InlineBumpStore, RefStore},
+    };
+
+    use super::*;
+
+    type TestStore = TrackingStore<InlineBumpStore<u16, [u64; 32]>, 4>;
+
+    fn collect(store: &TestStore) -> Vec<(u16, usize)> {
+        let mut handles = Vec::new();
+
+        store.for_each_handle(|handle, layout| handles.push((handle, layout.size())));
+
+        handles.sort();
+        handles
+    }
+
+    #[test]
+    fn enumerate() {
+        let store = TestStore::new(InlineBumpStore::default());
+
+        assert!(store.is_empty());
+
+        let a = StoreBox::new_in(1u32, RefStore::new(&store));
+        let mut v = StoreVec::with_capacity_in(2, RefStore::new(&store));
+
+        v.push(2u64);
+
+        assert_eq!(2, store.len());
+        assert_eq!(vec![(0, 4), (8, 16)], collect(&store));
+
+        v.push(3);
+        v.push(4);
+
+        assert_eq!(vec![(0, 4), (8, 24)], collect(&store));
+
+        drop(a);
+
+        assert_eq!(vec![(8, 24)], collect(&store));
+        assert_eq!(Some(false), store.is_valid(0));
+        assert_eq!(Some(true), store.is_valid(8));
+
+        drop(v);
+
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn grow_reporting_in_place() {
+        let store = TestStore::new(InlineBumpStore::default());
+        let (old_layout, new_layout) = (Layout::new::<[u32; 2]>(), Layout::new::<[u32; 4]>());
+
+        let (handle, _) = store.allocate(old_layout).unwrap();
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, with `old_layout`, and is still valid.
+        //  -   `new_layout` is larger than `old_layout`, with the same alignment.
+        let (new_handle, _, relocated) = unsafe { store.grow_reporting(handle, old_layout, new_layout).unwrap() };
+
+        assert!(!relocated);
+        assert_eq!(vec![(new_handle, 16)], collect(&store));
+    }
+
+    #[test]
+    fn exhausted() {
+        let store = TestStore::new(InlineBumpStore::default());
+        let layout = Layout::new::<u8>();
+
+        let handles: Vec<_> = (0..4).map(|_| store.allocate(layout).unwrap().0).collect();
+
+        assert_eq!(Err(AllocError), store.allocate(layout).map(|_| ()));
+
+        //  Safety:
+        //  -   `handles[1]` was allocated by `store`, with `layout`, and is still valid.
+        unsafe { store.deallocate(handles[1], layout) };
+
+        assert_eq!(3, store.len());
+        assert!(store.allocate(layout).is_ok());
+    }
+} // mod tests
@@ -0,0 +1,94 @@
+//! Checks that every combination of features builds.
+//!
+//! The features are read from `Cargo.toml`, hence new features are picked up automatically. Each combination of
+//! features is checked with `cargo check --all-targets`, which also compiles `features.rs` and the in-crate tests.
+//!
+//! The check is slow, as it builds the crate once per combination, hence it is ignored by default:
+//!
+//! ```text
+//! cargo test --test feature_matrix -- --ignored
+//! ```
+//!
+//! The builds use a dedicated target directory, `target/feature-matrix`, so as not to invalidate the regular builds.
+
+use std::{env, fs, path::Path, process::Command};
+
+#[test]
+#[ignore = "builds the crate once per combination of features"]
+fn feature_matrix() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+
+    let manifest = fs::read_to_string(root.join("Cargo.toml")).expect("Cargo.toml to be readable");
+
+    let features = optional_features(&manifest);
+
+    assert!(!features.is_empty(), "no optional feature found in Cargo.toml");
+    assert!(features.len() < 16, "too many features for a powerset: {features:?}");
+
+    let cargo = env::var("CARGO").unwrap_or_else(|_| String::from("cargo"));
+    let target = root.join("target").join("feature-matrix");
+
+    let mut failures = Vec::new();
+
+    for mask in 0..(1u32 << features.len()) {
+        let selected: Vec<&str> = features
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| mask & (1 << index) != 0)
+            .map(|(_, feature)| feature.as_str())
+            .collect();
+
+        let selected = selected.join(",");
+
+        let status = Command::new(&cargo)
+            .current_dir(root)
+            .args(["check", "--quiet", "--all-targets", "--no-default-features"])
+            .args(["--features", &selected])
+            .arg("--target-dir")
+            .arg(&target)
+            .status()
+            .expect("cargo to be runnable");
+
+        if !status.success() {
+            failures.push(selected);
+        }
+    }
+
+    assert!(failures.is_empty(), "failed feature combinations: {failures:?}");
+}
+
+#[test]
+fn optional_features_parsing() {
+    let manifest = r#"
+[package]
+name = "storage"
+
+[features]
+default = []
+#   Comment.
+alloc = []
+std = ["alloc"]
+
+[dependencies]
+oorandom = "11.1.3"
+"#;
+
+    assert_eq!(vec!["alloc", "std"], optional_features(manifest));
+}
+
+//  Returns the features declared in the `[features]` section of `manifest`, except `default`.
+//
+//  Only handles single-line declarations, as used in `Cargo.toml`.
+fn optional_features(manifest: &str) -> Vec<String> {
+    manifest
+        .lines()
+        .map(str::trim)
+        .skip_while(|line| *line != "[features]")
+        .skip(1)
+        .take_while(|line| !line.starts_with('['))
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(name, _)| name.trim().to_string())
+        .filter(|name| name != "default")
+        .collect()
+}
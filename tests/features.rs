@@ -0,0 +1,145 @@
+//! Exercises the feature-gated APIs, through the public API only.
+//!
+//! Each module is only compiled with its feature, hence running the tests with a given set of features checks that the
+//! APIs gated behind those features are available, and usable together. See `feature_matrix.rs` for checking that
+//! every combination of features builds.
+
+#![cfg_attr(feature = "alloc", feature(allocator_api))]
+
+//  Items available regardless of the features, to check that no feature is accidentally required.
+mod always {
+    use storage::{
+        collection::{StoreBox, StoreVec},
+        store::{InlineBumpStore, InlineSingleStore},
+    };
+
+    #[test]
+    fn inline() {
+        let mut vec = StoreVec::<u32, InlineBumpStore<u8, [u32; 4]>>::new();
+
+        vec.extend([1, 2, 3]);
+
+        let boxed = StoreBox::new_in(vec.len(), InlineSingleStore::<usize>::default());
+
+        assert_eq!([1, 2, 3], &vec[..]);
+        assert_eq!(3, *boxed);
+    }
+} // mod always
+
+#[cfg(feature = "alloc")]
+mod alloc {
+    use storage::{
+        collection::{StoreBox, StoreVec},
+        prelude,
+        store::{ArcStore, BoxStore, Global, RcStore},
+    };
+
+    #[test]
+    fn global() {
+        let vec = StoreVec::from(vec![1, 2, 3]);
+        let vec: StoreVec<i32, Global> = StoreVec::from(Vec::from(vec));
+
+        let boxed = StoreBox::from(Box::new(4)).into_box();
+
+        assert_eq!([1, 2, 3], &vec[..]);
+        assert_eq!(4, *boxed);
+    }
+
+    #[test]
+    fn shared() {
+        let rc = RcStore::new(Global);
+        let arc = ArcStore::new(Global);
+        let boxed = BoxStore::new(Global);
+
+        let first = StoreBox::new_in(1u32, rc);
+        let second = StoreBox::new_in(2u32, arc);
+        let third = StoreBox::new_in(3u32, boxed);
+
+        assert_eq!((1, 2, 3), (*first, *second, *third));
+    }
+
+    #[test]
+    fn prelude() {
+        let rc: prelude::RcStore<prelude::Global> = RcStore::new(Global);
+        let arc: prelude::ArcStore<prelude::Global> = ArcStore::new(Global);
+
+        assert_eq!((1, 2), (*StoreBox::new_in(1u8, rc), *StoreBox::new_in(2u8, arc)));
+    }
+} // mod alloc
+
+#[cfg(feature = "std")]
+mod std {
+    use std::io::Write;
+
+    use storage::{collection::StoreVec, store::InlineBumpStore};
+
+    #[test]
+    fn io_write() {
+        let mut vec = StoreVec::<u8, InlineBumpStore<u8, [u8; 8]>>::new();
+
+        write!(vec, "{}", 42).unwrap();
+
+        assert_eq!(b"42", &vec[..]);
+    }
+} // mod std
+
+#[cfg(feature = "coercible-metadata")]
+mod coercible_metadata {
+    use core::fmt::Debug;
+
+    use storage::{collection::StoreBox, store::InlineSingleStore};
+
+    #[test]
+    fn unsize() {
+        let boxed = StoreBox::new_in([1u8, 2, 3], InlineSingleStore::<[u8; 4]>::default());
+        let boxed: StoreBox<[u8], _> = boxed;
+
+        let debug = StoreBox::new_in(4u8, InlineSingleStore::<[u8; 4]>::default());
+        let debug: StoreBox<dyn Debug, _> = debug;
+
+        assert_eq!([1, 2, 3], &boxed[..]);
+        assert_eq!("StoreBox(4)", format!("{debug:?}"));
+    }
+} // mod coercible_metadata
+
+#[cfg(feature = "c-alloc")]
+mod c_alloc {
+    use storage::{
+        c_alloc::{CAllocator, MALLOC_ALIGNMENT},
+        store::{AlignedBlock, InlineBumpStore},
+    };
+
+    #[test]
+    fn malloc_free() {
+        let allocator = CAllocator::<InlineBumpStore<u16, AlignedBlock<256, 16>>, 4>::new(InlineBumpStore::new());
+
+        let pointer = allocator.malloc(8);
+
+        assert!(!pointer.is_null());
+        assert_eq!(0, pointer.addr() % MALLOC_ALIGNMENT);
+        assert_eq!(1, allocator.live());
+
+        //  Safety:
+        //  -   `pointer` was allocated by `allocator`, and is freed once.
+        unsafe { allocator.free(pointer) };
+
+        assert_eq!(0, allocator.live());
+    }
+} // mod c_alloc
+
+#[cfg(feature = "prefix-free-hash")]
+mod prefix_free_hash {
+    use std::hash::{BuildHasher, RandomState};
+
+    use storage::collection::InlineLinkedList;
+
+    #[test]
+    fn hash_list() {
+        let state = RandomState::new();
+
+        let first: InlineLinkedList<u8, u8, 4> = [1, 2].try_into().unwrap();
+        let second: InlineLinkedList<u8, u8, 4> = [1, 2].try_into().unwrap();
+
+        assert_eq!(state.hash_one(&first), state.hash_one(&second));
+    }
+} // mod prefix_free_hash
@@ -0,0 +1,27 @@
+/*  The C side of `tests/ffi_vec.rs`, see `examples/ffi_vec.rs` for the layout of `struct VecHeader`. */
+
+#include <stddef.h>
+#include <stdint.h>
+
+struct VecHeader {
+    size_t length;
+    uint16_t handle;
+    size_t capacity;
+};
+
+/*  Sums the elements of the vector, or returns `SIZE_MAX` if its length exceeds its capacity. */
+size_t checksum(struct VecHeader const* header, uint8_t const* region) {
+    if (header->length > header->capacity) {
+        return SIZE_MAX;
+    }
+
+    uint8_t const* elements = region + header->handle;
+
+    size_t sum = 0;
+
+    for (size_t index = 0; index < header->length; ++index) {
+        sum += elements[index];
+    }
+
+    return sum;
+}
@@ -0,0 +1,105 @@
+//! Hands a store-backed `StoreVec<u8, _>` to C, as described in `examples/ffi_vec.rs`.
+//!
+//! `tests/ffi/checksum.c` is compiled into a shared library with the C compiler named by `CC`, or `cc` by default, then
+//! loaded with `dlopen`, so that the layout of the vector is checked against the layout computed by a C compiler.
+//!
+//! With the `coercible-metadata` feature, the layout of the handle is unspecified, see `TypedMetadata`, hence the test
+//! is not compiled.
+
+#![cfg(all(unix, not(feature = "coercible-metadata")))]
+
+use std::{
+    alloc::Layout,
+    env,
+    ffi::CString,
+    mem,
+    os::unix::ffi::OsStrExt,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use storage::{
+    collection::StoreVec,
+    interface::{Store as _, StoreRegion},
+    store::{StackBumpBlock, StackBumpStore},
+};
+
+type Store<'a> = StackBumpStore<'a, u16>;
+
+/// The mirror of `struct VecHeader`, only ever handled through pointers.
+enum VecHeader {}
+
+type Checksum = unsafe extern "C" fn(*const VecHeader, *const u8) -> usize;
+
+//  Compiles `tests/ffi/checksum.c` into a shared library, returning its path.
+fn compile() -> PathBuf {
+    let source = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/ffi/checksum.c");
+    let library = Path::new(env!("CARGO_TARGET_TMPDIR")).join("libffi_vec_checksum.so");
+
+    let compiler = env::var("CC").unwrap_or_else(|_| String::from("cc"));
+
+    let status = Command::new(&compiler)
+        .args(["-shared", "-fPIC", "-O2", "-Wall", "-Werror", "-o"])
+        .arg(&library)
+        .arg(&source)
+        .status()
+        .unwrap_or_else(|error| panic!("{compiler} to compile {source:?}: {error}"));
+
+    assert!(status.success(), "{compiler} to compile {source:?}: {status}");
+
+    library
+}
+
+//  Loads `checksum` from the shared library at `library`, which is never unloaded.
+fn load(library: &Path) -> Checksum {
+    let library = CString::new(library.as_os_str().as_bytes()).expect("No NUL in the path");
+
+    //  Safety:
+    //  -   `library` is a NUL-terminated string.
+    let handle = unsafe { libc::dlopen(library.as_ptr(), libc::RTLD_NOW | libc::RTLD_LOCAL) };
+
+    assert!(!handle.is_null(), "{library:?} to be loaded");
+
+    //  Safety:
+    //  -   `handle` is a live handle returned by `dlopen`.
+    //  -   The symbol name is a NUL-terminated string.
+    let symbol = unsafe { libc::dlsym(handle, c"checksum".as_ptr()) };
+
+    assert!(!symbol.is_null(), "checksum to be exported by {library:?}");
+
+    //  Safety:
+    //  -   `symbol` is the address of `checksum`, whose signature `Checksum` mirrors.
+    unsafe { mem::transmute::<*mut libc::c_void, Checksum>(symbol) }
+}
+
+#[test]
+fn checksum() {
+    let checksum = load(&compile());
+
+    let block = StackBumpBlock::<[u64; 8]>::new();
+
+    //  A leading allocation, so that the handle of the vector is not 0.
+    let store: Store<'_> = block.create_store();
+    store
+        .allocate(Layout::new::<[u8; 3]>())
+        .expect("Padding to be allocated");
+
+    let region = store.region();
+
+    let mut vec = StoreVec::with_capacity_in(16, store);
+
+    for byte in b"Hello, C!" {
+        vec.push(*byte);
+    }
+
+    let header = &vec as *const StoreVec<u8, Store<'_>> as *const VecHeader;
+
+    //  Safety:
+    //  -   `header` points to a `StoreVec`, whose prefix is laid out as a `struct VecHeader`.
+    //  -   `region` is the region of the store of the vector, in which its elements are allocated.
+    let sum = unsafe { checksum(header, region.cast::<u8>().as_ptr()) };
+
+    let expected: usize = vec.iter().map(|byte| *byte as usize).sum();
+
+    assert_eq!(expected, sum);
+}
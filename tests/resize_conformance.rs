@@ -0,0 +1,225 @@
+//! Checks that the stores handle the edge cases of resizing, as specified in the Resizing section of `Store`: equal
+//! layouts, zero sizes, and alignment changes.
+//!
+//! Each case allocates a block of memory, fills it with a recognizable pattern, resizes it, then checks that the
+//! resulting block of memory is suitably sized and aligned, and that its contents were preserved.
+
+#![feature(allocator_api)]
+
+use std::alloc::{Layout, System};
+
+use storage::{
+    interface::{Store, StoreSingle},
+    store::{InlineBumpStore, InlineSingleStore, SmallStore, StackBumpBlock},
+};
+
+#[derive(Clone, Copy, Debug)]
+enum Resize {
+    Grow,
+    GrowZeroed,
+    Shrink,
+}
+
+//  All alignments are at most 8, so that stores backed by `[u64; N]` may satisfy them.
+const CASES: [(Resize, Layout, Layout); 14] = [
+    //  Equal layouts.
+    (Resize::Grow, layout(16, 8), layout(16, 8)),
+    (Resize::GrowZeroed, layout(16, 8), layout(16, 8)),
+    (Resize::Shrink, layout(16, 8), layout(16, 8)),
+    //  Zero sizes.
+    (Resize::Grow, layout(0, 1), layout(8, 1)),
+    (Resize::GrowZeroed, layout(0, 4), layout(8, 4)),
+    (Resize::Grow, layout(0, 8), layout(0, 8)),
+    (Resize::Shrink, layout(8, 1), layout(0, 1)),
+    //  Alignment changes, with or without a change in size.
+    (Resize::Grow, layout(8, 1), layout(8, 8)),
+    (Resize::Grow, layout(8, 1), layout(16, 8)),
+    (Resize::GrowZeroed, layout(5, 1), layout(16, 8)),
+    (Resize::Grow, layout(16, 8), layout(16, 1)),
+    (Resize::Shrink, layout(16, 1), layout(16, 8)),
+    (Resize::Shrink, layout(16, 1), layout(8, 8)),
+    (Resize::Shrink, layout(16, 8), layout(8, 1)),
+];
+
+const fn layout(size: usize, align: usize) -> Layout {
+    match Layout::from_size_align(size, align) {
+        Ok(layout) => layout,
+        Err(_) => panic!("Invalid layout"),
+    }
+}
+
+//  Checks all cases against `store`, allocating a single byte prior to each, so that a bump store ends up
+//  insufficiently aligned for any alignment greater than 1.
+//
+//  Each case is checked twice: once with the block of memory last allocated, and once followed by another allocation,
+//  so that a bump store cannot resize it by merely moving its watermark.
+fn check_store<S: Store>(store: &S) {
+    for ((resize, old, new), trailing) in CASES.into_iter().flat_map(|case| [(case, false), (case, true)]) {
+        let (padding, _) = store.allocate(Layout::new::<u8>()).expect("Padding to be allocated");
+
+        let (handle, _) = store.allocate(old).expect("Block to be allocated");
+
+        let trailer = trailing.then(|| store.allocate(Layout::new::<u8>()).expect("Trailer to be allocated").0);
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and `old` fits.
+        unsafe { fill(Store::resolve(store, handle), old.size()) };
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and `old` fits.
+        //  -   `new` is larger than `old`, or smaller, as appropriate.
+        let result = unsafe {
+            match resize {
+                Resize::Grow => Store::grow(store, handle, old, new),
+                Resize::GrowZeroed => Store::grow_zeroed(store, handle, old, new),
+                Resize::Shrink => Store::shrink(store, handle, old, new),
+            }
+        };
+
+        let Ok((handle, size)) = result else {
+            panic!("{resize:?} from {old:?} to {new:?} to succeed")
+        };
+
+        //  Safety:
+        //  -   `handle` was returned by `store`, and `new` fits, with `size` bytes.
+        unsafe { check(Store::resolve(store, handle), resize, old, new, size) };
+
+        //  Safety:
+        //  -   `handle`, `padding`, and `trailer` were allocated by `store`, and their layouts fit.
+        unsafe {
+            Store::deallocate(store, handle, new);
+            Store::deallocate(store, padding, Layout::new::<u8>());
+
+            if let Some(trailer) = trailer {
+                Store::deallocate(store, trailer, Layout::new::<u8>());
+            }
+        }
+    }
+}
+
+//  Checks all cases against `store`, expecting success unless `new` is more aligned than `max_align`, and skipping
+//  those cases where `old` is.
+fn check_store_single<S: StoreSingle>(store: &mut S, max_align: usize) {
+    for (resize, old, new) in CASES {
+        if old.align() > max_align {
+            continue;
+        }
+
+        let (handle, _) = StoreSingle::allocate(store, old).expect("Block to be allocated");
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and `old` fits.
+        unsafe { fill(StoreSingle::resolve_mut(store, handle), old.size()) };
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and `old` fits.
+        //  -   `new` is larger than `old`, or smaller, as appropriate.
+        let result = unsafe {
+            match resize {
+                Resize::Grow => StoreSingle::grow(store, handle, old, new),
+                Resize::GrowZeroed => StoreSingle::grow_zeroed(store, handle, old, new),
+                Resize::Shrink => StoreSingle::shrink(store, handle, old, new),
+            }
+        };
+
+        let Ok((handle, size)) = result else {
+            assert!(new.align() > max_align, "{resize:?} from {old:?} to {new:?} to succeed");
+
+            //  Safety:
+            //  -   On failure, `handle` is still valid, and `old` still fits.
+            unsafe { StoreSingle::deallocate(store, handle, old) };
+
+            continue;
+        };
+
+        //  Safety:
+        //  -   `handle` was returned by `store`, and `new` fits, with `size` bytes.
+        unsafe { check(StoreSingle::resolve(store, handle), resize, old, new, size) };
+
+        //  Safety:
+        //  -   `handle` was allocated by `store`, and `new` fits.
+        unsafe { StoreSingle::deallocate(store, handle, new) };
+    }
+}
+
+//  #   Safety
+//
+//  -   `pointer` must be valid for writes of `size` bytes.
+unsafe fn fill(pointer: std::ptr::NonNull<u8>, size: usize) {
+    for i in 0..size {
+        //  Safety:
+        //  -   `i` is within bounds, as per pre-conditions.
+        unsafe { pointer.as_ptr().add(i).write(i as u8 + 1) };
+    }
+}
+
+//  #   Safety
+//
+//  -   `pointer` must be valid for reads of `size` bytes, with `size >= new.size()`.
+//  -   `pointer` must be the result of resizing a block of memory filled by `fill` with `old.size()` bytes.
+unsafe fn check(pointer: std::ptr::NonNull<u8>, resize: Resize, old: Layout, new: Layout, size: usize) {
+    assert!(
+        size >= new.size(),
+        "{resize:?} from {old:?} to {new:?} returned {size} bytes"
+    );
+    assert!(
+        pointer.as_ptr().addr().is_multiple_of(new.align()),
+        "{resize:?} from {old:?} to {new:?} returned {pointer:?}"
+    );
+
+    for i in 0..old.size().min(new.size()) {
+        //  Safety:
+        //  -   `i` is within bounds, as per pre-conditions.
+        let byte = unsafe { pointer.as_ptr().add(i).read() };
+
+        assert_eq!(i as u8 + 1, byte, "{resize:?} from {old:?} to {new:?} lost byte {i}");
+    }
+
+    if let Resize::GrowZeroed = resize {
+        for i in old.size()..new.size() {
+            //  Safety:
+            //  -   `i` is within bounds, as per pre-conditions.
+            let byte = unsafe { pointer.as_ptr().add(i).read() };
+
+            assert_eq!(0, byte, "{resize:?} from {old:?} to {new:?} did not zero byte {i}");
+        }
+    }
+}
+
+#[test]
+fn allocator() {
+    check_store(&System);
+}
+
+#[test]
+fn inline_bump_store() {
+    let store = InlineBumpStore::<usize, [u64; 128]>::new();
+
+    check_store(&store);
+}
+
+#[test]
+fn stack_bump_store() {
+    let block = StackBumpBlock::<[u64; 128]>::new();
+
+    check_store(&block.create_store::<u32>());
+}
+
+#[test]
+fn stack_chain_store() {
+    let blocks = [StackBumpBlock::<[u64; 64]>::new(), StackBumpBlock::new()];
+    let blocks = [&blocks[0], &blocks[1]];
+
+    check_store(&StackBumpBlock::chain::<usize>(&blocks));
+}
+
+#[test]
+fn inline_single_store() {
+    check_store_single(&mut InlineSingleStore::<[u64; 4]>::new(), 8);
+    check_store_single(&mut InlineSingleStore::<[u32; 8]>::new(), 4);
+}
+
+#[test]
+fn small_store() {
+    check_store_single(&mut SmallStore::<[u64; 4], System>::default(), usize::MAX);
+}
@@ -0,0 +1,205 @@
+//! Property tests of the collections, under arbitrary, yet legal, store behaviors.
+//!
+//! Each test applies an arbitrary sequence of operations both to a collection backed by a `ScriptedStore` -- which
+//! fails, over-allocates, and relocates at the whim of a seeded script -- and to a model from the standard library,
+//! then checks that both agree, and that all blocks of memory were deallocated once the collection is dropped.
+
+#![cfg(feature = "alloc")]
+
+use std::{cell::Cell, collections::VecDeque};
+
+use proptest::prelude::*;
+use storage::{
+    collection::{LinkedList, StoreVec},
+    store::{RefStore, Script, ScriptedOperation, ScriptedStore, SeededScript},
+};
+
+#[derive(Clone, Debug)]
+enum VecOperation {
+    Push(u32),
+    Insert(usize, u32),
+    Pop,
+    Remove(usize),
+    Reserve(usize),
+    Clear,
+}
+
+fn vec_operation() -> impl Strategy<Value = VecOperation> {
+    prop_oneof![
+        4 => any::<u32>().prop_map(VecOperation::Push),
+        2 => (any::<usize>(), any::<u32>()).prop_map(|(index, value)| VecOperation::Insert(index, value)),
+        2 => Just(VecOperation::Pop),
+        1 => any::<usize>().prop_map(VecOperation::Remove),
+        1 => (0..64usize).prop_map(VecOperation::Reserve),
+        1 => Just(VecOperation::Clear),
+    ]
+}
+
+#[derive(Clone, Debug)]
+enum ListOperation {
+    PushFront(u32),
+    PushBack(u32),
+    Insert(usize, u32),
+    PopFront,
+    PopBack,
+    Remove(usize),
+    SplitAppend(usize),
+}
+
+fn list_operation() -> impl Strategy<Value = ListOperation> {
+    prop_oneof![
+        2 => any::<u32>().prop_map(ListOperation::PushFront),
+        2 => any::<u32>().prop_map(ListOperation::PushBack),
+        2 => (any::<usize>(), any::<u32>()).prop_map(|(index, value)| ListOperation::Insert(index, value)),
+        1 => Just(ListOperation::PopFront),
+        1 => Just(ListOperation::PopBack),
+        1 => any::<usize>().prop_map(ListOperation::Remove),
+        1 => any::<usize>().prop_map(ListOperation::SplitAppend),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn store_vec(seed in any::<u64>(), operations in prop::collection::vec(vec_operation(), 0..64)) {
+        let store = ScriptedStore::from_seed(seed);
+
+        {
+            let mut vec = StoreVec::new_in(RefStore::new(&store));
+            let mut model = Vec::new();
+
+            for operation in operations {
+                match operation {
+                    VecOperation::Push(value) => {
+                        //  Strings, so that any double drop, or leak, of an element is caught.
+                        if vec.try_push(value.to_string()).is_ok() {
+                            model.push(value.to_string());
+                        }
+                    }
+                    VecOperation::Insert(index, value) => {
+                        let index = index % (model.len() + 1);
+
+                        if vec.try_insert(index, value.to_string()).is_ok() {
+                            model.insert(index, value.to_string());
+                        }
+                    }
+                    VecOperation::Pop => prop_assert_eq!(model.pop(), vec.pop()),
+                    VecOperation::Remove(index) => {
+                        if !model.is_empty() {
+                            let index = index % model.len();
+
+                            prop_assert_eq!(model.remove(index), vec.remove(index));
+                        }
+                    }
+                    VecOperation::Reserve(additional) => {
+                        if vec.try_reserve(additional).is_ok() {
+                            prop_assert!(vec.capacity() >= vec.len() + additional);
+                        }
+                    }
+                    VecOperation::Clear => {
+                        vec.clear();
+                        model.clear();
+                    }
+                }
+
+                prop_assert!(vec.len() <= vec.capacity());
+                prop_assert_eq!(&model[..], vec.as_slice());
+            }
+        }
+
+        prop_assert_eq!(0, store.live_blocks());
+    }
+
+    #[test]
+    fn store_vec_extend(
+        seed in any::<u64>(),
+        chunks in prop::collection::vec(prop::collection::vec(any::<u32>(), 0..16), 0..8),
+    ) {
+        //  `extend` cannot report failures, hence the script never fails, yet still grows in place or relocates.
+        //
+        //  The first growth is always in place, so that the in-place path of `extend` is exercised whenever it grows.
+        let mut script = SeededScript::with_failures(seed, 0);
+        let grows = Cell::new(0);
+
+        let store = ScriptedStore::new(|operation, layout| {
+            let mut behavior = script.next(operation, layout);
+
+            if operation == ScriptedOperation::Grow {
+                behavior.relocate &= grows.get() > 0;
+                grows.set(grows.get() + 1);
+            }
+
+            behavior
+        });
+
+        {
+            let mut vec = StoreVec::new_in(RefStore::new(&store));
+            let mut model = Vec::new();
+
+            for chunk in chunks {
+                //  Hide the size hint, so that the vector grows while extending.
+                vec.extend(chunk.iter().map(u32::to_string).filter(|_| true));
+                model.extend(chunk.iter().map(u32::to_string));
+
+                prop_assert_eq!(&model[..], vec.as_slice());
+            }
+        }
+
+        prop_assert_eq!(0, store.live_blocks());
+        prop_assert!(grows.get() == 0 || store.in_place_resizes() > 0);
+    }
+
+    #[test]
+    fn linked_list(seed in any::<u64>(), operations in prop::collection::vec(list_operation(), 0..64)) {
+        let store = ScriptedStore::from_seed(seed);
+
+        {
+            let mut list = LinkedList::new_in(RefStore::new(&store));
+            let mut model = VecDeque::new();
+
+            for operation in operations {
+                match operation {
+                    ListOperation::PushFront(value) => {
+                        if list.try_push_front(value.to_string()).is_ok() {
+                            model.push_front(value.to_string());
+                        }
+                    }
+                    ListOperation::PushBack(value) => {
+                        if list.try_push_back(value.to_string()).is_ok() {
+                            model.push_back(value.to_string());
+                        }
+                    }
+                    ListOperation::Insert(index, value) => {
+                        let index = index % (model.len() + 1);
+
+                        if list.try_insert_at(index, value.to_string()).is_ok() {
+                            model.insert(index, value.to_string());
+                        }
+                    }
+                    ListOperation::PopFront => prop_assert_eq!(model.pop_front(), list.pop_front()),
+                    ListOperation::PopBack => prop_assert_eq!(model.pop_back(), list.pop_back()),
+                    ListOperation::Remove(index) => {
+                        let index = index % (model.len() + 1);
+
+                        prop_assert_eq!(model.remove(index), list.remove_at(index));
+                    }
+                    ListOperation::SplitAppend(at) => {
+                        let at = at % (model.len() + 1);
+
+                        let mut tail = list.split_off(at);
+
+                        prop_assert_eq!(at, list.len());
+                        prop_assert_eq!(model.len() - at, tail.len());
+
+                        prop_assert!(list.try_append(&mut tail).is_ok());
+                        prop_assert!(tail.is_empty());
+                    }
+                }
+
+                prop_assert_eq!(model.len(), list.len());
+                prop_assert!(model.iter().eq(list.iter()));
+            }
+        }
+
+        prop_assert_eq!(0, store.live_blocks());
+    }
+}
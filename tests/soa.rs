@@ -0,0 +1,140 @@
+//! Checks `#[derive(SoA)]`, with the `derive` feature.
+
+#![cfg(feature = "derive")]
+
+use std::fmt::Debug;
+
+use storage::{
+    collection::{SoA, StoreSoA},
+    store::InlineBumpStore,
+};
+
+type Store = InlineBumpStore<u16, [u64; 64]>;
+
+#[derive(Clone, Debug, PartialEq, SoA)]
+pub struct Particle {
+    pub position: [f32; 2],
+    pub mass: f32,
+    name: String,
+}
+
+#[derive(Debug, PartialEq, SoA)]
+struct Tagged<'a, T: Debug, const N: usize> {
+    tag: &'a str,
+    values: [T; N],
+}
+
+#[derive(SoA)]
+struct Noisy<'a> {
+    drops: &'a std::cell::Cell<u32>,
+    marker: (),
+}
+
+impl Drop for Noisy<'_> {
+    fn drop(&mut self) {
+        self.drops.set(self.drops.get() + 1);
+    }
+}
+
+fn particle(n: u8) -> Particle {
+    Particle {
+        position: [n as f32, -(n as f32)],
+        mass: n as f32 / 2.0,
+        name: n.to_string(),
+    }
+}
+
+#[test]
+fn push_get_iter() {
+    let mut particles = StoreSoA::<Particle, Store>::new();
+
+    (0..4).for_each(|n| particles.push(particle(n)));
+
+    let ParticleRef { position, mass, name } = particles.get(1).unwrap();
+
+    assert_eq!(([1.0, -1.0], 0.5, "1"), (*position, *mass, &name[..]));
+
+    let ParticleMut { mass, .. } = particles.get_mut(1).unwrap();
+
+    *mass = 8.0;
+
+    let masses: Vec<f32> = particles.iter().map(|particle| *particle.mass).collect();
+
+    assert_eq!(vec![0.0, 8.0, 1.0, 1.5], masses);
+    assert_eq!(Some(particle(3)), particles.pop());
+}
+
+#[test]
+fn slices() {
+    let mut particles: StoreSoA<Particle, Store> = (0..3).map(particle).collect();
+
+    let ParticleSlicesMut { position, .. } = particles.as_mut_slices();
+
+    position.iter_mut().for_each(|position| position[1] = 0.0);
+
+    let slices: ParticleSlices<'_> = particles.as_slices();
+
+    assert_eq!([[0.0, 0.0], [1.0, 0.0], [2.0, 0.0]], slices.position);
+    assert_eq!([0.0, 0.5, 1.0], slices.mass);
+    assert_eq!(["0", "1", "2"], slices.name);
+}
+
+#[test]
+fn generics() {
+    let tag = String::from("tag");
+
+    let mut tagged = StoreSoA::<Tagged<'_, u8, 2>, Store>::new();
+
+    tagged.push(Tagged {
+        tag: &tag,
+        values: [1, 2],
+    });
+    tagged.push(Tagged {
+        tag: "other",
+        values: [3, 4],
+    });
+
+    assert_eq!(["tag", "other"], tagged.as_slices().tag);
+    assert_eq!(&[3, 4], tagged.get(1).unwrap().values);
+    assert_eq!(
+        Some(Tagged {
+            tag: "other",
+            values: [3, 4]
+        }),
+        tagged.pop()
+    );
+}
+
+#[test]
+fn drops() {
+    let drops = std::cell::Cell::new(0);
+
+    {
+        let mut noisy = StoreSoA::<Noisy<'_>, Store>::new();
+
+        (0..3).for_each(|_| {
+            noisy.push(Noisy {
+                drops: &drops,
+                marker: (),
+            })
+        });
+
+        assert_eq!(0, drops.get());
+
+        drop(noisy.pop());
+
+        assert_eq!(1, drops.get());
+    }
+
+    assert_eq!(3, drops.get());
+}
+
+#[test]
+fn layouts() {
+    use core::alloc::Layout;
+
+    assert_eq!(
+        [Layout::new::<[f32; 2]>(), Layout::new::<f32>(), Layout::new::<String>()],
+        Particle::LAYOUTS
+    );
+}